@@ -27,6 +27,7 @@ pub struct Cli {
     pub data: String,
     pub start_time: i64,
     pub end_time: i64,
+    pub cursor: Option<String>,
 }
 
 pub fn args() -> impl IntoIterator<Item = impl Into<clap::Arg>> {
@@ -40,7 +41,7 @@ pub fn args() -> impl IntoIterator<Item = impl Into<clap::Arg>> {
         clap::Arg::new("stream_name").long("stream_name").alias("s").default_value("default")
             .help("stream_name, default is default"),
         clap::Arg::new("file_type").long("file_type").alias("t").default_value("json")
-            .help("file type: json or csv, default is json"),
+            .help("file type: json (newline delimited), csv or parquet, default is json"),
         clap::Arg::new("date").long("date").alias("f").default_value("day")
             .help("create file by day or hour, default is day"),
         clap::Arg::new("data").long("data").alias("d")
@@ -48,7 +49,9 @@ pub fn args() -> impl IntoIterator<Item = impl Into<clap::Arg>> {
         clap::Arg::new("start_time").long("start").alias("start")
             .help("start time,default day"),
         clap::Arg::new("end_time").long("end").alias("end")
-            .help("end time,default now")
+            .help("end time,default now"),
+        clap::Arg::new("cursor").long("cursor")
+            .help("export cursor to resume an interrupted export from")
     ]
 }
 
@@ -110,6 +113,7 @@ fn handle_args(app: clap::ArgMatches) -> Cli {
         data: data.to_string(),
         start_time: start,
         end_time: end,
+        cursor: app.get_one::<String>("cursor").cloned(),
     }
 }
 
@@ -130,6 +134,7 @@ mod tests {
             data: "/tmp/data".to_string(),
             start_time: 1000000,
             end_time: 2000000,
+            cursor: None,
         };
 
         assert_eq!(cli.context, "test_context");
@@ -150,7 +155,7 @@ mod tests {
         let args_vec: Vec<clap::Arg> = args.into_iter().map(|arg| arg.into()).collect();
 
         // Check that we have the expected number of arguments
-        assert_eq!(args_vec.len(), 9);
+        assert_eq!(args_vec.len(), 10);
 
         // Check that specific arguments exist with correct properties
         let stream_arg = args_vec
@@ -227,6 +232,7 @@ mod tests {
             "data",
             "start_time",
             "end_time",
+            "cursor",
         ];
 
         for expected_id in expected_ids {
@@ -387,6 +393,7 @@ mod tests {
             data: "/tmp/data".to_string(),
             start_time: 1000000,
             end_time: 2000000,
+            cursor: None,
         };
 
         // Test cloning
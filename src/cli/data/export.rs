@@ -13,20 +13,21 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{fs, path::Path};
+use std::{fs, io::Write, path::Path};
 
 use async_trait::async_trait;
 use axum::extract::Query;
-use config::{TIMESTAMP_COL_NAME, get_config, meta::search};
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::search::{ExportFormat, ExportRequest},
+};
 use hashbrown::HashMap;
+use tokio::sync::mpsc;
 
 use crate::{
     cli::data::{Context, cli::Cli},
-    common::utils::http::{
-        get_search_event_context_from_request, get_search_type_from_request,
-        get_stream_type_from_request,
-    },
-    service::search as SearchService,
+    common::utils::http::get_stream_type_from_request,
+    service::search::export::{self, ExportProgress},
 };
 
 pub struct Export {}
@@ -34,61 +35,63 @@ pub struct Export {}
 #[async_trait]
 impl Context for Export {
     async fn operator(c: Cli) -> Result<bool, anyhow::Error> {
-        let cfg = get_config();
         let map = HashMap::from([("type".to_string(), c.stream_type)]);
         let query_map = Query(map);
         let stream_type = get_stream_type_from_request(&query_map).unwrap_or_default();
 
-        let table = c.stream_name;
-        let search_type = match get_search_type_from_request(&query_map) {
+        let format = match ExportFormat::try_from(c.file_type.as_str()) {
             Ok(v) => v,
-            Err(_) => return Ok(false),
+            Err(e) => {
+                eprintln!("{e}");
+                return Ok(false);
+            }
         };
-        let search_event_context = search_type
-            .as_ref()
-            .and_then(|event_type| get_search_event_context_from_request(event_type, &query_map));
-        let query = search::Query {
+        let table = c.stream_name;
+        let req = ExportRequest {
             sql: format!("select * from {table} ORDER BY {TIMESTAMP_COL_NAME} ASC"),
-            from: 0,
-            size: cfg.limit.query_default_limit,
-            quick_mode: false,
-            query_type: "".to_owned(),
             start_time: c.start_time,
             end_time: c.end_time,
+            format,
+            cursor: c.cursor,
             ..Default::default()
         };
-
-        let req = search::Request {
-            query,
-            encoding: search::RequestEncoding::Empty,
-            regions: vec![],
-            clusters: vec![],
-            timeout: 0,
-            search_type,
-            search_event_context,
-            use_cache: false,
-            clear_cache: false,
-            local_mode: None,
+        let plan = match export::plan("", &c.org, stream_type, None, req).await {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("export error: {e:?}");
+                return Ok(false);
+            }
         };
 
-        match SearchService::search("", &c.org, stream_type, None, &req).await {
-            Ok(res) => {
-                if c.file_type != "json" {
-                    eprintln!("No other file types are implemented");
-                    return Ok(false);
-                }
-                let path = Path::new(c.data.as_str());
-                fs::create_dir_all(path)?;
-                let file = fs::File::create(path.join(format!(
-                    "{}.{}",
-                    chrono::Local::now().timestamp_micros(),
-                    c.file_type
-                )))?;
-                serde_json::to_writer_pretty(file, &res.hits)?;
+        let path = Path::new(c.data.as_str());
+        fs::create_dir_all(path)?;
+        let file_path = path.join(format!(
+            "{}.{}",
+            chrono::Local::now().timestamp_micros(),
+            format.extension()
+        ));
+        let mut file = fs::File::create(&file_path)?;
+
+        let (tx, mut rx) = mpsc::channel(2);
+        let task = tokio::spawn(async move {
+            let mut progress = ExportProgress::default();
+            let ret = plan.run(tx, &mut progress).await;
+            (ret, progress)
+        });
+        while let Some(Ok(data)) = rx.recv().await {
+            file.write_all(&data)?;
+        }
+        let (ret, progress) = task.await?;
+        match ret {
+            Ok(()) => {
+                println!("exported {} rows to {}", progress.rows, file_path.display());
                 Ok(true)
             }
             Err(e) => {
-                eprintln!("search error: {e:?}");
+                eprintln!("export error after {} rows: {e:?}", progress.rows);
+                if let Some(cursor) = progress.cursor {
+                    eprintln!("resume the export with: --cursor {cursor}");
+                }
                 Ok(false)
             }
         }
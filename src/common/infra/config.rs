@@ -18,10 +18,11 @@ use std::sync::{Arc, LazyLock as Lazy};
 use config::{
     RwAHashMap, RwHashMap,
     meta::{
-        alerts::alert::Alert,
+        alerts::{alert::Alert, silences::Silence},
         destinations::{Destination, Template},
         folder::Folder,
         function::Transform,
+        materialized_view::{MaterializedView, PendingFold, ViewGap},
         pipeline::Pipeline,
         promql::ClusterLeader,
         ratelimit::CachedUserRoles,
@@ -50,6 +51,11 @@ use crate::{
 // global cache variables
 pub static KVS: Lazy<RwHashMap<String, bytes::Bytes>> = Lazy::new(Default::default);
 pub static QUERY_FUNCTIONS: Lazy<RwHashMap<String, Transform>> = Lazy::new(DashMap::default);
+pub static MATERIALIZED_VIEWS: Lazy<RwHashMap<String, MaterializedView>> =
+    Lazy::new(DashMap::default);
+pub static MATERIALIZED_VIEW_PENDING_FOLDS: Lazy<RwHashMap<String, PendingFold>> =
+    Lazy::new(DashMap::default);
+pub static MATERIALIZED_VIEW_GAPS: Lazy<RwHashMap<String, ViewGap>> = Lazy::new(DashMap::default);
 pub static USERS: Lazy<RwHashMap<String, infra::table::users::UserRecord>> =
     Lazy::new(DashMap::default);
 pub static ORG_USERS: Lazy<RwHashMap<String, infra::table::org_users::OrgUserRecord>> =
@@ -71,6 +77,8 @@ pub static ALERTS: Lazy<RwAHashMap<String, (Folder, Alert)>> = Lazy::new(Default
 // Key for realtime alert triggers cache is org/alert_id
 pub static REALTIME_ALERT_TRIGGERS: Lazy<RwAHashMap<String, db_scheduler::Trigger>> =
     Lazy::new(Default::default);
+/// Silences of an organization, loaded on first use
+pub static ALERT_SILENCES: Lazy<RwHashMap<String, Arc<Vec<Silence>>>> = Lazy::new(DashMap::default);
 pub static ALERTS_TEMPLATES: Lazy<RwHashMap<String, Template>> = Lazy::new(Default::default);
 pub static DESTINATIONS: Lazy<RwHashMap<String, Destination>> = Lazy::new(Default::default);
 pub static ENRICHMENT_TABLES: Lazy<RwHashMap<String, StreamTable>> = Lazy::new(Default::default);
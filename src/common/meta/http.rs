@@ -225,6 +225,19 @@ impl HttpResponse {
             .into_response()
     }
 
+    /// Send a UnsupportedMediaType response in json format and associate the
+    /// provided error as `error` field.
+    pub fn unsupported_media_type(error: impl ToString) -> Response {
+        (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(Self::error(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                error.to_string(),
+            )),
+        )
+            .into_response()
+    }
+
     /// Send a response in json format, status code is 200.
     /// The payload should be serde-serializable.
     pub fn json<T: Serialize>(payload: T) -> Response {
@@ -475,6 +488,12 @@ mod tests {
         assert_eq!(response.status(), http::StatusCode::TOO_MANY_REQUESTS);
     }
 
+    #[test]
+    fn test_http_response_unsupported_media_type() {
+        let response = HttpResponse::unsupported_media_type("not protobuf");
+        assert_eq!(response.status(), http::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
     #[test]
     fn test_into_response_503_service_unavailable() {
         let http_response = HttpResponse {
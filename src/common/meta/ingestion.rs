@@ -33,6 +33,8 @@ pub enum SystemJobType {
     SelfReporting,
     InternalGrpc,
     AnomalyDetection,
+    Syslog,
+    MaterializedView,
 }
 
 impl SystemJobType {
@@ -44,6 +46,8 @@ impl SystemJobType {
             SystemJobType::SelfReporting => "self_reporting",
             SystemJobType::InternalGrpc => "internal_grpc",
             SystemJobType::AnomalyDetection => "anomaly_detection",
+            SystemJobType::Syslog => "syslog",
+            SystemJobType::MaterializedView => "materialized_view",
         }
     }
 }
@@ -408,6 +412,7 @@ pub enum IngestionValueType {
     Bulk,
     Hec,
     Loki,
+    Syslog,
 }
 
 pub enum IngestionData {
@@ -859,6 +864,11 @@ mod tests {
             SystemJobType::AnomalyDetection.as_email_local(),
             "anomaly_detection"
         );
+        assert_eq!(SystemJobType::Syslog.as_email_local(), "syslog");
+        assert_eq!(
+            SystemJobType::MaterializedView.as_email_local(),
+            "materialized_view"
+        );
     }
 
     #[test]
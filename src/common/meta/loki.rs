@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use axum::response::IntoResponse;
 use serde::{Deserialize, Serialize};
@@ -109,6 +109,18 @@ pub enum LokiError {
         #[source]
         source: anyhow::Error,
     },
+
+    #[error("Invalid query: {message}")]
+    InvalidQuery { message: String },
+
+    #[error("Query limit exceeded: {message}")]
+    LimitExceeded { message: String },
+
+    #[error("Search failed: {source}")]
+    Search {
+        #[source]
+        source: infra::errors::Error,
+    },
 }
 
 impl IntoResponse for LokiError {
@@ -123,6 +135,14 @@ impl IntoResponse for LokiError {
             )
                 .into_response();
         }
+        if let LokiError::Search { source } = &self {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(header::CONTENT_TYPE, "text/plain")],
+                format!("search failed: {source}"),
+            )
+                .into_response();
+        }
         let body = match self {
             LokiError::InvalidTimestamp { message } => format!("invalid timestamp: {message}"),
             LokiError::InvalidLabels { message } => format!("invalid labels: {message}"),
@@ -143,7 +163,11 @@ impl IntoResponse for LokiError {
             LokiError::GzipDecompression { source } => {
                 format!("failed to decompress gzip: {source}")
             }
-            LokiError::Ingestion { .. } => unreachable!("Already tested above"),
+            LokiError::InvalidQuery { message } => format!("parse error: {message}"),
+            LokiError::LimitExceeded { message } => format!("query limit exceeded: {message}"),
+            LokiError::Ingestion { .. } | LokiError::Search { .. } => {
+                unreachable!("Already tested above")
+            }
         };
 
         (
@@ -155,6 +179,140 @@ impl IntoResponse for LokiError {
     }
 }
 
+/// Query parameters of `/loki/api/v1/query`
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LokiQueryRequest {
+    /// LogQL query
+    pub query: Option<String>,
+    /// Max number of entries to return for log queries, default 100
+    pub limit: Option<i64>,
+    /// Evaluation time as nanosecond unix epoch or RFC3339, default now
+    pub time: Option<String>,
+    /// `forward` or `backward`, default backward
+    pub direction: Option<String>,
+}
+
+/// Query parameters of `/loki/api/v1/query_range`
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LokiRangeQueryRequest {
+    /// LogQL query
+    pub query: Option<String>,
+    /// Max number of entries to return for log queries, default 100
+    pub limit: Option<i64>,
+    /// Start time as nanosecond unix epoch or RFC3339, default one hour ago
+    pub start: Option<String>,
+    /// End time as nanosecond unix epoch or RFC3339, default now
+    pub end: Option<String>,
+    /// Query resolution step as duration or float number of seconds
+    pub step: Option<String>,
+    /// `forward` or `backward`, default backward
+    pub direction: Option<String>,
+}
+
+/// Query parameters of `/loki/api/v1/labels`, `/loki/api/v1/label/{name}/values`
+/// and `/loki/api/v1/series`
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LokiLabelsRequest {
+    /// Start time as nanosecond unix epoch or RFC3339, default one hour ago
+    pub start: Option<String>,
+    /// End time as nanosecond unix epoch or RFC3339, default now
+    pub end: Option<String>,
+    /// Optional stream selector to scope the result
+    pub query: Option<String>,
+    /// Stream selector used by the series API
+    #[serde(rename = "match[]")]
+    pub matcher: Option<String>,
+}
+
+/// Loki query response envelope
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LokiQueryResponse {
+    pub status: String,
+    pub data: LokiQueryData,
+}
+
+impl LokiQueryResponse {
+    pub fn success(data: LokiQueryData) -> Self {
+        Self {
+            status: "success".to_string(),
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LokiQueryData {
+    #[serde(rename = "resultType")]
+    pub result_type: String,
+    pub result: LokiQueryResult,
+    #[serde(default)]
+    pub stats: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LokiQueryResult {
+    Streams(Vec<LokiStreamResult>),
+    Matrix(Vec<LokiMatrixResult>),
+    Vector(Vec<LokiVectorResult>),
+}
+
+impl LokiQueryResult {
+    pub fn result_type(&self) -> &'static str {
+        match self {
+            LokiQueryResult::Streams(_) => "streams",
+            LokiQueryResult::Matrix(_) => "matrix",
+            LokiQueryResult::Vector(_) => "vector",
+        }
+    }
+}
+
+impl From<LokiQueryResult> for LokiQueryData {
+    fn from(result: LokiQueryResult) -> Self {
+        Self {
+            result_type: result.result_type().to_string(),
+            result,
+            stats: serde_json::Value::Object(Default::default()),
+        }
+    }
+}
+
+/// A log stream with its entries as `[<ns timestamp string>, <line>]` pairs
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LokiStreamResult {
+    pub stream: BTreeMap<String, String>,
+    pub values: Vec<(String, String)>,
+}
+
+/// A metric series with its samples as `[<seconds>, <value string>]` pairs
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LokiMatrixResult {
+    pub metric: BTreeMap<String, String>,
+    pub values: Vec<(f64, String)>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LokiVectorResult {
+    pub metric: BTreeMap<String, String>,
+    pub value: (f64, String),
+}
+
+/// Response of the labels, label values and series APIs
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LokiDataResponse<T> {
+    pub status: String,
+    pub data: Vec<T>,
+}
+
+impl<T> LokiDataResponse<T> {
+    pub fn success(data: Vec<T>) -> Self {
+        Self {
+            status: "success".to_string(),
+            data,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
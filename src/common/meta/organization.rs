@@ -277,7 +277,9 @@ impl TriggerStatus {
             if result.module == module {
                 match result.status {
                     usage::TriggerDataStatus::Completed
-                    | usage::TriggerDataStatus::ConditionNotSatisfied => status.healthy += 1,
+                    | usage::TriggerDataStatus::ConditionNotSatisfied
+                    | usage::TriggerDataStatus::Resolved
+                    | usage::TriggerDataStatus::Silenced => status.healthy += 1,
                     usage::TriggerDataStatus::Failed => status.failed += 1,
                     usage::TriggerDataStatus::Skipped => status.warning += 1,
                 }
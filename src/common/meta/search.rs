@@ -13,9 +13,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::meta::{
-    search::{Response, ScanStats},
-    sql::OrderBy,
+use config::{
+    meta::{
+        search::{Response, ScanStats},
+        sql::OrderBy,
+    },
+    utils::calendar::CalendarBuckets,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -55,6 +58,10 @@ pub struct CacheQueryRequest {
     /// not just first/last, since results may not be time-ordered.
     /// Example: SELECT histogram(_timestamp), count(*) ... ORDER BY count DESC
     pub is_histogram_non_ts_order: bool,
+    /// Calendar buckets of the histogram, set when the interval is a calendar
+    /// unit and `histogram_interval` is only its nominal length.
+    #[serde(skip)]
+    pub histogram_calendar: Option<CalendarBuckets>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema, Default)]
@@ -73,6 +80,8 @@ pub struct MultiCachedQueryResponse {
     pub file_path: String,
     pub trace_id: String,
     pub order_by: Vec<(String, OrderBy)>,
+    #[serde(skip)]
+    pub histogram_calendar: Option<CalendarBuckets>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -210,6 +219,7 @@ mod tests {
             histogram_interval: 100,
             is_descending: false,
             is_histogram_non_ts_order: false,
+            histogram_calendar: None,
         };
 
         assert_eq!(request.q_start_time, 1000);
@@ -237,6 +247,7 @@ mod tests {
             is_aggregate: true,
             file_path: "test.json".to_string(),
             trace_id: "trace123".to_string(),
+            histogram_calendar: None,
         };
 
         assert!(response.has_cached_data);
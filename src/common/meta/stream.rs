@@ -102,6 +102,17 @@ pub struct StreamDeleteFields {
     pub fields: Vec<String>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct StreamDeleteByPredicate {
+    /// SQL WHERE clause selecting the rows to delete, e.g. `user_id = 'x'`
+    #[serde(rename = "where")]
+    pub condition: String,
+    /// Start timestamp in microseconds
+    pub start: i64,
+    /// End timestamp in microseconds
+    pub end: i64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct FieldUpdate {
     /// Field name to update
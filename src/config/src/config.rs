@@ -586,6 +586,9 @@ pub struct Config {
     pub prom: Prometheus,
     pub smtp: Smtp,
     pub rum: RUM,
+    pub syslog: Syslog,
+    pub statsd: Statsd,
+    pub pgwire: Pgwire,
     pub chrome: Chrome,
     pub tokio_console: TokioConsole,
     pub pipeline: Pipeline,
@@ -796,6 +799,26 @@ pub struct Grpc {
         help = "this value can be set to webpki or native. Using webpki means client will trust a preset CA bundle. Using native means client will trust the certificates in OS trust store"
     )]
     pub tls_root_certificates: TlsRootCertificates,
+    #[env_config(
+        name = "ZO_FLIGHT_SQL_ENABLED",
+        default = false,
+        help = "Enable the Arrow Flight SQL server on queriers"
+    )]
+    pub flight_sql_enabled: bool,
+    #[env_config(name = "ZO_FLIGHT_SQL_PORT", default = 5083)]
+    pub flight_sql_port: u16,
+    #[env_config(
+        name = "ZO_FLIGHT_SQL_DEFAULT_TIME_RANGE",
+        default = 24,
+        help = "Hours searched by Flight SQL queries which don't filter on _timestamp"
+    )]
+    pub flight_sql_default_time_range: i64,
+    #[env_config(
+        name = "ZO_FLIGHT_SQL_SESSION_TTL",
+        default = 86400,
+        help = "Seconds a Flight SQL bearer token is valid after the handshake"
+    )]
+    pub flight_sql_session_ttl: i64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -1582,6 +1605,24 @@ pub struct Limit {
     pub query_querier_timeout: u64,
     #[env_config(name = "ZO_QUERY_DEFAULT_LIMIT", default = 1000)]
     pub query_default_limit: i64,
+    #[env_config(
+        name = "ZO_QUERY_EXPORT_PAGE_SIZE",
+        default = 10000,
+        help = "Number of rows fetched per search request by the export API"
+    )]
+    pub query_export_page_size: i64,
+    #[env_config(
+        name = "ZO_LOGQL_MAX_SCAN_ROWS",
+        default = 100000,
+        help = "Max number of rows a LogQL query evaluated outside of SQL may scan, queries needing more rows fail"
+    )]
+    pub logql_max_scan_rows: i64,
+    #[env_config(
+        name = "ZO_PROMETHEUS_REMOTE_READ_MAX_SAMPLES",
+        default = 5000000,
+        help = "Max number of samples a single Prometheus remote read query may return, queries returning more samples fail"
+    )]
+    pub prometheus_remote_read_max_samples: i64,
     #[env_config(name = "ZO_QUERY_VALUES_DEFAULT_NUM", default = 10)]
     pub query_values_default_num: i64,
     #[env_config(name = "ZO_QUERY_GROUP_BASE_SPEED", default = 1024)] // MB/s/core
@@ -1676,6 +1717,12 @@ pub struct Limit {
         help = "Time range in minutes for alert preview. If set to 0 (default), uses the alert's period value. If greater than 0, overrides period for preview."
     )]
     pub alert_preview_timerange_minutes: i64,
+    #[env_config(
+        name = "ZO_WEBHOOK_DELIVERY_TIMEOUT",
+        default = 30,
+        help = "Timeout in seconds of a single delivery to an HTTP alert destination, timed out deliveries are retried when the destination has a retry policy."
+    )]
+    pub webhook_delivery_timeout: u64,
     #[env_config(name = "ZO_REPORT_SCHEDULE_TIMEOUT", default = 300)] // seconds
     pub report_schedule_timeout: i64,
     #[env_config(name = "ZO_DERIVED_STREAM_SCHEDULE_INTERVAL", default = 300)] // seconds
@@ -2240,6 +2287,36 @@ pub struct Prometheus {
     pub ha_cluster_label: String,
     #[env_config(name = "ZO_PROMETHEUS_HA_REPLICA", default = "__replica__")]
     pub ha_replica_label: String,
+    #[env_config(
+        name = "ZO_PROMETHEUS_SCRAPE_ENABLED",
+        default = false,
+        help = "Enable scraping of the configured Prometheus targets"
+    )]
+    pub scrape_enabled: bool,
+    #[env_config(
+        name = "ZO_PROMETHEUS_SCRAPE_FILE_SD_DIR",
+        default = "",
+        help = "Directory of the file_sd_configs target files, file based service discovery is disabled when empty"
+    )]
+    pub scrape_file_sd_dir: String,
+    #[env_config(
+        name = "ZO_PROMETHEUS_SCRAPE_CONCURRENCY",
+        default = 16,
+        help = "Maximum number of targets scraped concurrently by a scrape job"
+    )]
+    pub scrape_concurrency: usize,
+    #[env_config(
+        name = "ZO_PROMETHEUS_SCRAPE_ALLOW_PRIVATE_TARGETS",
+        default = false,
+        help = "Allow scraping targets on private networks, scrape targets are subject to the SSRF guard otherwise"
+    )]
+    pub scrape_allow_private_targets: bool,
+    #[env_config(
+        name = "ZO_PROMETHEUS_SCRAPE_BODY_SIZE_LIMIT",
+        default = 52428800,
+        help = "Max size in bytes of an uncompressed scrape response body, used when a scrape job does not set body_size_limit, 0 disables the limit"
+    )]
+    pub scrape_body_size_limit: u64,
 }
 
 #[derive(Serialize, Debug, EnvConfig, Default)]
@@ -2266,6 +2343,126 @@ pub struct RUM {
     pub insecure_http: bool,
 }
 
+#[derive(Serialize, Debug, EnvConfig, Default)]
+pub struct Syslog {
+    #[env_config(name = "ZO_SYSLOG_ENABLED", default = false)]
+    pub enabled: bool,
+    #[env_config(name = "ZO_SYSLOG_ADDR", default = "")]
+    pub addr: String,
+    #[env_config(
+        name = "ZO_SYSLOG_TCP_PORT",
+        default = 5514,
+        help = "TCP port of the syslog receiver, 0 disables the TCP listener"
+    )]
+    pub tcp_port: u16,
+    #[env_config(
+        name = "ZO_SYSLOG_UDP_PORT",
+        default = 5514,
+        help = "UDP port of the syslog receiver, 0 disables the UDP listener"
+    )]
+    pub udp_port: u16,
+    #[env_config(
+        name = "ZO_SYSLOG_UDP_SPLIT_LINES",
+        default = false,
+        help = "Split UDP datagrams into one message per line, for relays packing several messages in a datagram"
+    )]
+    pub udp_split_lines: bool,
+    #[env_config(name = "ZO_SYSLOG_ORG_ID", default = "default")]
+    pub org_id: String,
+    #[env_config(name = "ZO_SYSLOG_STREAM_NAME", default = "syslog")]
+    pub stream_name: String,
+    #[env_config(
+        name = "ZO_SYSLOG_MAX_MESSAGE_SIZE",
+        default = 65536,
+        help = "Max size in bytes of a single syslog frame"
+    )]
+    pub max_message_size: usize,
+    #[env_config(
+        name = "ZO_SYSLOG_BATCH_SIZE",
+        default = 1000,
+        help = "Max number of syslog messages buffered before they are ingested"
+    )]
+    pub batch_size: usize,
+    #[env_config(
+        name = "ZO_SYSLOG_FLUSH_INTERVAL_MS",
+        default = 1000,
+        help = "Max time in milliseconds syslog messages are buffered before they are ingested"
+    )]
+    pub flush_interval_ms: u64,
+}
+
+#[derive(Serialize, Debug, EnvConfig, Default)]
+pub struct Statsd {
+    #[env_config(name = "ZO_STATSD_ENABLED", default = false)]
+    pub enabled: bool,
+    #[env_config(name = "ZO_STATSD_ADDR", default = "")]
+    pub addr: String,
+    #[env_config(
+        name = "ZO_STATSD_TCP_PORT",
+        default = 0,
+        help = "TCP port of the StatsD listener, 0 disables the TCP listener"
+    )]
+    pub tcp_port: u16,
+    #[env_config(
+        name = "ZO_STATSD_UDP_PORT",
+        default = 8125,
+        help = "UDP port of the StatsD listener, 0 disables the UDP listener"
+    )]
+    pub udp_port: u16,
+    #[env_config(name = "ZO_STATSD_ORG_ID", default = "default")]
+    pub org_id: String,
+    #[env_config(
+        name = "ZO_STATSD_MAX_MESSAGE_SIZE",
+        default = 65536,
+        help = "Max size in bytes of a StatsD datagram or line"
+    )]
+    pub max_message_size: usize,
+    #[env_config(
+        name = "ZO_STATSD_FLUSH_INTERVAL_SECS",
+        default = 10,
+        help = "Interval in seconds StatsD metrics are aggregated over before they are ingested"
+    )]
+    pub flush_interval_secs: u64,
+    #[env_config(
+        name = "ZO_STATSD_EXPIRE_AFTER_FLUSHES",
+        default = 60,
+        help = "Number of flush intervals after which the state of a StatsD series that received no metric is dropped, 0 keeps it forever"
+    )]
+    pub expire_after_flushes: u64,
+    #[env_config(
+        name = "ZO_STATSD_PERCENTILES",
+        default = "0.5,0.9,0.95,0.99",
+        help = "Comma separated quantiles computed for StatsD timers and distributions"
+    )]
+    pub percentiles: String,
+}
+
+#[derive(Serialize, Debug, EnvConfig, Default)]
+pub struct Pgwire {
+    #[env_config(
+        name = "ZO_PGWIRE_ENABLED",
+        default = false,
+        help = "Enable the PostgreSQL wire protocol server on queriers"
+    )]
+    pub enabled: bool,
+    #[env_config(name = "ZO_PGWIRE_ADDR", default = "")]
+    pub addr: String,
+    #[env_config(name = "ZO_PGWIRE_PORT", default = 5432)]
+    pub port: u16,
+    #[env_config(
+        name = "ZO_PGWIRE_DEFAULT_TIME_RANGE",
+        default = 24,
+        help = "Hours searched by PostgreSQL wire protocol queries which don't filter on _timestamp"
+    )]
+    pub default_time_range: i64,
+    #[env_config(
+        name = "ZO_PGWIRE_MAX_MESSAGE_SIZE",
+        default = 8388608,
+        help = "Max size in bytes of a message sent by a PostgreSQL client"
+    )]
+    pub max_message_size: usize,
+}
+
 #[derive(Serialize, Debug, EnvConfig, Default)]
 pub struct Pipeline {
     #[env_config(
@@ -2592,6 +2789,21 @@ pub fn init() -> Config {
         panic!("inverted index config error: {e}");
     }
 
+    // check syslog config
+    if let Err(e) = check_syslog_config(&mut cfg) {
+        panic!("syslog config error: {e}");
+    }
+
+    // check statsd config
+    if let Err(e) = check_statsd_config(&mut cfg) {
+        panic!("statsd config error: {e}");
+    }
+
+    // check pgwire config
+    if let Err(e) = check_pgwire_config(&mut cfg) {
+        panic!("pgwire config error: {e}");
+    }
+
     cfg
 }
 
@@ -2978,6 +3190,19 @@ fn check_grpc_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
             "ZO_GRPC_TLS_CERT_DOMAIN, ZO_GRPC_TLS_CERT_PATH and ZO_GRPC_TLS_KEY_PATH must be set when ZO_GRPC_TLS_ENABLED is true"
         ));
     }
+    if cfg.grpc.flight_sql_enabled {
+        if cfg.grpc.flight_sql_port == cfg.grpc.port {
+            return Err(anyhow::anyhow!(
+                "ZO_FLIGHT_SQL_PORT must be different from ZO_GRPC_PORT"
+            ));
+        }
+        if cfg.grpc.flight_sql_default_time_range < 1 {
+            cfg.grpc.flight_sql_default_time_range = 24;
+        }
+        if cfg.grpc.flight_sql_session_ttl < 1 {
+            cfg.grpc.flight_sql_session_ttl = 86400;
+        }
+    }
     Ok(())
 }
 
@@ -3147,6 +3372,12 @@ fn check_memory_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     if cfg.limit.query_default_limit == 0 {
         cfg.limit.query_default_limit = 1000;
     }
+    if cfg.limit.query_export_page_size <= 0 {
+        cfg.limit.query_export_page_size = 10000;
+    }
+    if cfg.limit.prometheus_remote_read_max_samples <= 0 {
+        cfg.limit.prometheus_remote_read_max_samples = 5000000;
+    }
 
     if cfg.limit.inverted_index_footer_cache_max_size == 0 {
         cfg.limit.inverted_index_footer_cache_max_size =
@@ -3530,6 +3761,76 @@ fn check_inverted_index_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+fn check_syslog_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if !cfg.syslog.enabled {
+        return Ok(());
+    }
+    ensure_not_empty(&cfg.syslog.org_id, "ZO_SYSLOG_ORG_ID")?;
+    ensure_not_empty(&cfg.syslog.stream_name, "ZO_SYSLOG_STREAM_NAME")?;
+    if cfg.syslog.tcp_port == 0 && cfg.syslog.udp_port == 0 {
+        return Err(anyhow::anyhow!(
+            "ZO_SYSLOG_TCP_PORT and ZO_SYSLOG_UDP_PORT can not both be 0"
+        ));
+    }
+    if cfg.syslog.max_message_size == 0 {
+        cfg.syslog.max_message_size = 65536;
+    }
+    if cfg.syslog.batch_size == 0 {
+        cfg.syslog.batch_size = 1000;
+    }
+    if cfg.syslog.flush_interval_ms == 0 {
+        cfg.syslog.flush_interval_ms = 1000;
+    }
+    Ok(())
+}
+
+fn check_statsd_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if !cfg.statsd.enabled {
+        return Ok(());
+    }
+    ensure_not_empty(&cfg.statsd.org_id, "ZO_STATSD_ORG_ID")?;
+    if cfg.statsd.tcp_port == 0 && cfg.statsd.udp_port == 0 {
+        return Err(anyhow::anyhow!(
+            "ZO_STATSD_TCP_PORT and ZO_STATSD_UDP_PORT can not both be 0"
+        ));
+    }
+    for quantile in cfg.statsd.percentiles.split(',').map(str::trim) {
+        match quantile.parse::<f64>() {
+            Ok(v) if (0.0..=1.0).contains(&v) => {}
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "ZO_STATSD_PERCENTILES must be quantiles between 0 and 1, got {quantile}"
+                ));
+            }
+        }
+    }
+    if cfg.statsd.max_message_size == 0 {
+        cfg.statsd.max_message_size = 65536;
+    }
+    if cfg.statsd.flush_interval_secs == 0 {
+        cfg.statsd.flush_interval_secs = 10;
+    }
+    Ok(())
+}
+
+fn check_pgwire_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if !cfg.pgwire.enabled {
+        return Ok(());
+    }
+    if cfg.pgwire.port == cfg.http.port || cfg.pgwire.port == cfg.grpc.port {
+        return Err(anyhow::anyhow!(
+            "ZO_PGWIRE_PORT must be different from ZO_HTTP_PORT and ZO_GRPC_PORT"
+        ));
+    }
+    if cfg.pgwire.default_time_range < 1 {
+        cfg.pgwire.default_time_range = 24;
+    }
+    if cfg.pgwire.max_message_size == 0 {
+        cfg.pgwire.max_message_size = 8388608;
+    }
+    Ok(())
+}
+
 pub fn ensure_not_empty(s: &str, name: &str) -> Result<(), anyhow::Error> {
     if s.trim().is_empty() {
         return Err(anyhow::anyhow!("{} is empty", name));
@@ -3867,6 +4168,20 @@ mod tests {
         assert!(common.should_create_span());
     }
 
+    #[test]
+    fn test_check_grpc_config_flight_sql() {
+        let mut cfg = Config::default();
+        cfg.grpc.port = 5081;
+        cfg.grpc.flight_sql_enabled = true;
+        cfg.grpc.flight_sql_port = 5083;
+        assert!(check_grpc_config(&mut cfg).is_ok());
+        assert_eq!(cfg.grpc.flight_sql_default_time_range, 24);
+        assert_eq!(cfg.grpc.flight_sql_session_ttl, 86400);
+
+        cfg.grpc.flight_sql_port = 5081;
+        assert!(check_grpc_config(&mut cfg).is_err());
+    }
+
     #[test]
     fn test_check_grpc_config_no_tls() {
         let mut cfg = Config::default();
@@ -4018,6 +4333,70 @@ mod tests {
         assert_eq!(cfg.pipeline.remote_request_max_retry_time, 3600);
     }
 
+    #[test]
+    fn test_check_syslog_config_defaults() {
+        let mut cfg = Config::default();
+        cfg.syslog.enabled = true;
+        cfg.syslog.org_id = "default".to_string();
+        cfg.syslog.stream_name = "syslog".to_string();
+        cfg.syslog.tcp_port = 5514;
+        check_syslog_config(&mut cfg).unwrap();
+        assert_eq!(cfg.syslog.max_message_size, 65536);
+        assert_eq!(cfg.syslog.batch_size, 1000);
+        assert_eq!(cfg.syslog.flush_interval_ms, 1000);
+    }
+
+    #[test]
+    fn test_check_syslog_config_invalid() {
+        let mut cfg = Config::default();
+        cfg.syslog.enabled = true;
+        cfg.syslog.org_id = "default".to_string();
+        cfg.syslog.stream_name = "syslog".to_string();
+        assert!(check_syslog_config(&mut cfg).is_err());
+
+        cfg.syslog.udp_port = 5514;
+        cfg.syslog.stream_name = " ".to_string();
+        assert!(check_syslog_config(&mut cfg).is_err());
+
+        // disabled receiver is never validated
+        cfg.syslog.enabled = false;
+        assert!(check_syslog_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_statsd_config() {
+        let mut cfg = Config::default();
+        cfg.statsd.enabled = true;
+        cfg.statsd.org_id = "default".to_string();
+        cfg.statsd.udp_port = 8125;
+        cfg.statsd.percentiles = "0.5, 0.99".to_string();
+        check_statsd_config(&mut cfg).unwrap();
+        assert_eq!(cfg.statsd.max_message_size, 65536);
+        assert_eq!(cfg.statsd.flush_interval_secs, 10);
+
+        cfg.statsd.percentiles = "90".to_string();
+        assert!(check_statsd_config(&mut cfg).is_err());
+
+        cfg.statsd.percentiles = "0.9".to_string();
+        cfg.statsd.udp_port = 0;
+        assert!(check_statsd_config(&mut cfg).is_err());
+    }
+
+    #[test]
+    fn test_check_pgwire_config() {
+        let mut cfg = Config::default();
+        cfg.http.port = 5080;
+        cfg.grpc.port = 5081;
+        cfg.pgwire.enabled = true;
+        cfg.pgwire.port = 5432;
+        check_pgwire_config(&mut cfg).unwrap();
+        assert_eq!(cfg.pgwire.default_time_range, 24);
+        assert_eq!(cfg.pgwire.max_message_size, 8388608);
+
+        cfg.pgwire.port = 5080;
+        assert!(check_pgwire_config(&mut cfg).is_err());
+    }
+
     #[test]
     fn test_check_compact_config_defaults() {
         let mut cfg = Config::default();
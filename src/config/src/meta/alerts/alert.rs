@@ -99,6 +99,14 @@ pub struct Alert {
     /// to any incident.
     #[serde(default)]
     pub creates_incident: bool,
+    /// When true, a notification is also sent when an alert instance that was
+    /// firing no longer matches the alert condition.
+    #[serde(default)]
+    pub send_on_resolve: bool,
+    /// Optional template name for resolve notifications. When not specified,
+    /// the template of the firing notifications is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_template: Option<String>,
 }
 
 impl MemorySize for Alert {
@@ -119,6 +127,7 @@ impl MemorySize for Alert {
             + self.owner.mem_size()
             + self.last_edited_by.mem_size()
             + self.deduplication.mem_size()
+            + self.resolve_template.mem_size()
     }
 }
 
@@ -156,6 +165,8 @@ impl Default for Alert {
             last_satisfied_at: None,
             deduplication: None,
             creates_incident: false,
+            send_on_resolve: false,
+            resolve_template: None,
         }
     }
 }
@@ -657,6 +668,19 @@ mod tests {
         assert_eq!(alert.creates_incident, false);
     }
 
+    #[test]
+    fn test_send_on_resolve_defaults_to_false() {
+        let json = r#"{
+            "name": "test_alert",
+            "stream_type": "logs",
+            "stream_name": "test_stream",
+            "destinations": []
+        }"#;
+        let alert: Alert = serde_json::from_str(json).unwrap();
+        assert!(!alert.send_on_resolve);
+        assert!(alert.resolve_template.is_none());
+    }
+
     #[test]
     fn test_row_template_type_backward_compatibility() {
         // Test that deserializing an alert without the row_template_type field
@@ -52,7 +52,9 @@ impl std::str::FromStr for IncidentStatus {
 }
 
 /// Incident severity levels (P1 = highest priority)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize, ToSchema,
+)]
 pub enum IncidentSeverity {
     P1,
     P2,
@@ -22,7 +22,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::{
-    meta::search::SearchEventType,
+    meta::{alerts::incidents::IncidentSeverity, search::SearchEventType},
     stats::MemorySize,
     utils::{
         json::{Map, Value},
@@ -33,6 +33,7 @@ use crate::{
 pub mod alert;
 pub mod deduplication;
 pub mod incidents;
+pub mod silences;
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq, Default)]
 #[serde(default)]
@@ -60,6 +61,18 @@ pub struct TriggerCondition {
     pub tolerance_in_secs: Option<i64>,
     #[serde(default = "default_align_time")]
     pub align_time: bool,
+    /// Severity levels evaluated against the same query result, when set
+    /// they are used instead of `operator` and `threshold`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub severity_levels: Vec<SeverityLevel>,
+    /// Result column compared with the severity level thresholds, the number
+    /// of result rows is compared when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_field: Option<String>,
+    /// (seconds) How long the condition must hold in consecutive evaluations
+    /// before the alert fires, like the `for` of a Prometheus alerting rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_in_secs: Option<i64>,
 }
 
 pub fn default_align_time() -> bool {
@@ -68,7 +81,51 @@ pub fn default_align_time() -> bool {
 
 impl MemorySize for TriggerCondition {
     fn mem_size(&self) -> usize {
-        std::mem::size_of::<TriggerCondition>() + self.timezone.mem_size()
+        std::mem::size_of::<TriggerCondition>()
+            + self.timezone.mem_size()
+            + self.severity_levels.mem_size()
+            + self.threshold_field.mem_size()
+    }
+}
+
+/// The name of the column added to the result rows of an alert with severity
+/// levels, it holds the triggered severity.
+pub const ALERT_SEVERITY_FIELD: &str = "alert_severity";
+
+/// A severity of an alert with its own threshold and notification settings
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SeverityLevel {
+    pub severity: IncidentSeverity,
+    #[serde(default)]
+    pub operator: Operator,
+    pub threshold: f64,
+    /// Destinations notified at this severity, the alert destinations are
+    /// used when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub destinations: Vec<String>,
+    /// Template used at this severity instead of the alert template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+impl SeverityLevel {
+    /// Overrides the destinations and template of the alert with the ones of
+    /// this severity level.
+    pub fn apply_to(&self, alert: &mut alert::Alert) {
+        if !self.destinations.is_empty() {
+            alert.destinations = self.destinations.clone();
+        }
+        if let Some(template) = self.template.as_ref().filter(|t| !t.is_empty()) {
+            alert.template = Some(template.clone());
+        }
+    }
+}
+
+impl MemorySize for SeverityLevel {
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<SeverityLevel>()
+            + self.destinations.mem_size()
+            + self.template.mem_size()
     }
 }
 
@@ -96,6 +153,67 @@ fn get_offset_minutes_from_tz(tz: &Tz, at_time: chrono::DateTime<Utc>) -> i32 {
 }
 
 impl TriggerCondition {
+    /// Returns the most severe level matched by the result rows along with
+    /// the rows that matched a level, each tagged in [`ALERT_SEVERITY_FIELD`]
+    /// with the most severe level it matched. With a `threshold_field` each
+    /// row is compared on its own, otherwise the number of rows is compared.
+    pub fn match_severity_level(
+        &self,
+        rows: Vec<Map<String, Value>>,
+    ) -> Option<(&SeverityLevel, Vec<Map<String, Value>>)> {
+        let mut levels = self.severity_levels.iter().collect::<Vec<_>>();
+        levels.sort_by_key(|level| level.severity);
+        let tag = |row: &mut Map<String, Value>, level: &SeverityLevel| {
+            row.insert(
+                ALERT_SEVERITY_FIELD.to_string(),
+                Value::String(level.severity.to_string()),
+            );
+        };
+        match self.threshold_field.as_deref() {
+            Some(field) => {
+                let mut most_severe: Option<&SeverityLevel> = None;
+                let matched = rows
+                    .into_iter()
+                    .filter_map(|mut row| {
+                        let value = row.get(field).and_then(|value| match value {
+                            Value::Number(v) => v.as_f64(),
+                            Value::String(v) => v.parse::<f64>().ok(),
+                            _ => None,
+                        })?;
+                        let level = levels
+                            .iter()
+                            .copied()
+                            .find(|level| level.operator.compare_f64(value, level.threshold))?;
+                        tag(&mut row, level);
+                        if most_severe.is_none_or(|most| level.severity < most.severity) {
+                            most_severe = Some(level);
+                        }
+                        Some(row)
+                    })
+                    .collect::<Vec<_>>();
+                most_severe.map(|level| (level, matched))
+            }
+            None => {
+                let count = rows.len() as f64;
+                let level = levels
+                    .into_iter()
+                    .find(|level| level.operator.compare_f64(count, level.threshold))?;
+                let mut rows = rows;
+                rows.iter_mut().for_each(|row| tag(row, level));
+                Some((level, rows))
+            }
+        }
+    }
+
+    /// Returns the level a result row, or the labels of an alert instance,
+    /// was tagged with by [`Self::match_severity_level`].
+    pub fn severity_level_of(&self, row: &Map<String, Value>) -> Option<&SeverityLevel> {
+        let severity = row.get(ALERT_SEVERITY_FIELD)?.as_str()?;
+        self.severity_levels
+            .iter()
+            .find(|level| level.severity.to_string() == severity)
+    }
+
     // TODO: Currently, the frequency for alert is in seconds, but the
     // frequency for derived stream is in minutes. This needs to be fixed for alert.
     /// freq_in_secs is true if the frequency is in seconds, false if it is in minutes
@@ -326,6 +444,9 @@ pub struct TriggerEvalResults {
     pub data: Option<Vec<Map<String, Value>>>,
     pub end_time: i64,
     pub query_took: Option<i64>,
+    /// The severity level that matched, for alerts with severity levels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<SeverityLevel>,
 }
 
 #[derive(Clone, Default, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
@@ -677,6 +798,22 @@ pub enum Operator {
     NotContains,
 }
 
+impl Operator {
+    /// Compares a numeric value with a threshold, the `contains` operators
+    /// never match numbers.
+    pub fn compare_f64(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Operator::EqualTo => value == threshold,
+            Operator::NotEqualTo => value != threshold,
+            Operator::GreaterThan => value > threshold,
+            Operator::GreaterThanEquals => value >= threshold,
+            Operator::LessThan => value < threshold,
+            Operator::LessThanEquals => value <= threshold,
+            Operator::Contains | Operator::NotContains => false,
+        }
+    }
+}
+
 impl std::fmt::Display for Operator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -2582,4 +2719,121 @@ mod test {
         assert_eq!(items.len(), 1);
         assert!(matches!(items[0], ConditionList::EndCondition(_)));
     }
+
+    fn severity_rows(values: &[f64]) -> Vec<Map<String, Value>> {
+        values
+            .iter()
+            .map(|v| {
+                let mut row = Map::new();
+                row.insert("cpu".to_string(), serde_json::json!(v));
+                row
+            })
+            .collect()
+    }
+
+    fn severity_condition(threshold_field: Option<&str>) -> TriggerCondition {
+        TriggerCondition {
+            severity_levels: vec![
+                SeverityLevel {
+                    severity: IncidentSeverity::P3,
+                    operator: Operator::GreaterThanEquals,
+                    threshold: 0.8,
+                    ..Default::default()
+                },
+                SeverityLevel {
+                    severity: IncidentSeverity::P1,
+                    operator: Operator::GreaterThanEquals,
+                    threshold: 0.95,
+                    ..Default::default()
+                },
+            ],
+            threshold_field: threshold_field.map(|f| f.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_match_severity_level_by_field() {
+        let condition = severity_condition(Some("cpu"));
+
+        // every matching row is kept with its own severity
+        let (level, rows) = condition
+            .match_severity_level(severity_rows(&[0.5, 0.97, 0.85]))
+            .unwrap();
+        assert_eq!(level.severity, IncidentSeverity::P1);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["cpu"], 0.97);
+        assert_eq!(rows[0][ALERT_SEVERITY_FIELD], "P1");
+        assert_eq!(rows[1]["cpu"], 0.85);
+        assert_eq!(rows[1][ALERT_SEVERITY_FIELD], "P3");
+
+        let (level, rows) = condition
+            .match_severity_level(severity_rows(&[0.5, 0.85]))
+            .unwrap();
+        assert_eq!(level.severity, IncidentSeverity::P3);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][ALERT_SEVERITY_FIELD], "P3");
+
+        assert!(
+            condition
+                .match_severity_level(severity_rows(&[0.5]))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_severity_level_of() {
+        let condition = severity_condition(Some("cpu"));
+        let (_, rows) = condition
+            .match_severity_level(severity_rows(&[0.97, 0.85]))
+            .unwrap();
+        let severities = rows
+            .iter()
+            .map(|row| condition.severity_level_of(row).map(|level| level.severity))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            severities,
+            vec![Some(IncidentSeverity::P1), Some(IncidentSeverity::P3)]
+        );
+        assert!(condition.severity_level_of(&Map::new()).is_none());
+    }
+
+    #[test]
+    fn test_match_severity_level_by_count() {
+        let mut condition = severity_condition(None);
+        condition.severity_levels[0].threshold = 2.0;
+        condition.severity_levels[1].threshold = 3.5;
+
+        let (level, rows) = condition
+            .match_severity_level(severity_rows(&[1.0, 2.0]))
+            .unwrap();
+        assert_eq!(level.severity, IncidentSeverity::P3);
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row[ALERT_SEVERITY_FIELD] == "P3"));
+        assert!(
+            condition
+                .match_severity_level(severity_rows(&[1.0]))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_severity_level_apply_to() {
+        let mut alert = alert::Alert {
+            destinations: vec!["slack".to_string()],
+            template: Some("default".to_string()),
+            ..Default::default()
+        };
+        SeverityLevel::default().apply_to(&mut alert);
+        assert_eq!(alert.destinations, vec!["slack".to_string()]);
+
+        let level = SeverityLevel {
+            destinations: vec!["pager".to_string()],
+            template: Some("critical".to_string()),
+            ..Default::default()
+        };
+        level.apply_to(&mut alert);
+        assert_eq!(alert.destinations, vec!["pager".to_string()]);
+        assert_eq!(alert.template.as_deref(), Some("critical"));
+    }
 }
@@ -0,0 +1,416 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{str::FromStr, sync::OnceLock};
+
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::json::{Map, Value, get_string_value};
+
+/// Label holding the alert name when matching silences
+pub const SILENCE_LABEL_ALERT_NAME: &str = "alert_name";
+/// Label holding the folder name of the alert when matching silences
+pub const SILENCE_LABEL_FOLDER: &str = "folder";
+/// Label holding the folder id of the alert when matching silences
+pub const SILENCE_LABEL_FOLDER_ID: &str = "folder_id";
+/// Label holding the stream name of the alert when matching silences
+pub const SILENCE_LABEL_STREAM_NAME: &str = "stream_name";
+/// Label holding the stream type of the alert when matching silences
+pub const SILENCE_LABEL_STREAM_TYPE: &str = "stream_type";
+
+/// Organization-level silence
+///
+/// A silence suppresses alert notifications whose labels satisfy all of its
+/// matchers while its schedule is active. Labels are taken from the alert
+/// (`alert_name`, `folder`, `folder_id`, `stream_name`, `stream_type`) and,
+/// for any other name, from the fields of the result row.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct Silence {
+    /// Unique identifier, generated on creation
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// All matchers must match for the silence to apply
+    pub matchers: Vec<SilenceMatcher>,
+    pub schedule: SilenceSchedule,
+    #[serde(default)]
+    pub created_by: String,
+    /// Creation time (microseconds)
+    #[serde(default)]
+    pub created_at: i64,
+    /// Last update time (microseconds)
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SilenceMatcher {
+    /// Label name, e.g. `alert_name`, `stream_name` or a result row field
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub operator: SilenceMatchOperator,
+    /// The regex of a regex matcher, compiled on first use
+    #[serde(skip)]
+    #[schema(ignore)]
+    regex: OnceLock<Option<Regex>>,
+}
+
+impl PartialEq for SilenceMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value && self.operator == other.operator
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub enum SilenceMatchOperator {
+    #[default]
+    #[serde(rename = "=")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+    /// Regex match, anchored at both ends
+    #[serde(rename = "=~")]
+    Regex,
+    /// Negated regex match, anchored at both ends
+    #[serde(rename = "!~")]
+    NotRegex,
+}
+
+/// When a silence is active
+///
+/// Recurring windows use a cron expression (with a seconds field) marking the
+/// start of each window, e.g. `0 0 2 * * Sun` with a duration of 120 minutes
+/// silences every Sunday from 02:00 to 04:00 in the given timezone.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SilenceSchedule {
+    /// A single window (microseconds)
+    Once { start_time: i64, end_time: i64 },
+    Recurring {
+        cron: String,
+        duration_minutes: i64,
+        /// IANA timezone name, defaults to UTC
+        #[serde(default)]
+        timezone: Option<String>,
+    },
+}
+
+impl SilenceMatcher {
+    pub fn new(name: &str, operator: SilenceMatchOperator, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            operator,
+            regex: OnceLock::new(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("matcher name cannot be empty".to_string());
+        }
+        if matches!(
+            self.operator,
+            SilenceMatchOperator::Regex | SilenceMatchOperator::NotRegex
+        ) && let Err(e) = anchored_regex(&self.value)
+        {
+            return Err(format!("invalid regex for matcher {}: {e}", self.name));
+        }
+        Ok(())
+    }
+
+    /// Missing labels are treated as an empty value
+    pub fn matches(&self, value: Option<&str>) -> bool {
+        let value = value.unwrap_or_default();
+        match self.operator {
+            SilenceMatchOperator::Equal => value == self.value,
+            SilenceMatchOperator::NotEqual => value != self.value,
+            SilenceMatchOperator::Regex => self.regex().is_some_and(|re| re.is_match(value)),
+            SilenceMatchOperator::NotRegex => self.regex().is_some_and(|re| !re.is_match(value)),
+        }
+    }
+
+    fn regex(&self) -> Option<&Regex> {
+        self.regex
+            .get_or_init(|| anchored_regex(&self.value).ok())
+            .as_ref()
+    }
+}
+
+fn anchored_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{pattern})$"))
+}
+
+impl SilenceSchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SilenceSchedule::Once {
+                start_time,
+                end_time,
+            } => {
+                if end_time <= start_time {
+                    return Err("end_time must be greater than start_time".to_string());
+                }
+            }
+            SilenceSchedule::Recurring {
+                cron,
+                duration_minutes,
+                timezone,
+            } => {
+                Schedule::from_str(cron).map_err(|e| format!("invalid cron expression: {e}"))?;
+                if *duration_minutes <= 0 {
+                    return Err("duration_minutes must be greater than 0".to_string());
+                }
+                if let Some(tz) = timezone
+                    && tz.parse::<Tz>().is_err()
+                {
+                    return Err(format!("invalid timezone: {tz}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the schedule covers the given time (microseconds)
+    pub fn is_active(&self, now: i64) -> bool {
+        match self {
+            SilenceSchedule::Once {
+                start_time,
+                end_time,
+            } => *start_time <= now && now < *end_time,
+            SilenceSchedule::Recurring {
+                cron,
+                duration_minutes,
+                timezone,
+            } => {
+                let Ok(schedule) = Schedule::from_str(cron) else {
+                    return false;
+                };
+                let Some(now) = DateTime::<Utc>::from_timestamp_micros(now) else {
+                    return false;
+                };
+                let tz = timezone
+                    .as_deref()
+                    .and_then(|tz| tz.parse::<Tz>().ok())
+                    .unwrap_or(Tz::UTC);
+                let Some(duration) = Duration::try_minutes(*duration_minutes) else {
+                    return false;
+                };
+                // The window is active if it started within the last `duration_minutes`
+                let window_start = (now - duration).with_timezone(&tz);
+                schedule
+                    .after(&window_start)
+                    .next()
+                    .is_some_and(|start| start.with_timezone(&Utc) <= now)
+            }
+        }
+    }
+}
+
+impl Silence {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("silence name cannot be empty".to_string());
+        }
+        if self.matchers.is_empty() {
+            return Err("silence must have at least one matcher".to_string());
+        }
+        for matcher in self.matchers.iter() {
+            matcher.validate()?;
+        }
+        self.schedule.validate()
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.enabled && self.schedule.is_active(now)
+    }
+
+    /// Checks the matchers against the alert labels, falling back to the
+    /// result row for labels the alert does not define.
+    pub fn matches(&self, alert_labels: &[(&str, &str)], row: Option<&Map<String, Value>>) -> bool {
+        self.matchers.iter().all(|matcher| {
+            let value = alert_labels
+                .iter()
+                .find(|(name, _)| *name == matcher.name)
+                .map(|(_, value)| value.to_string())
+                .or_else(|| {
+                    row.and_then(|row| row.get(&matcher.name))
+                        .map(get_string_value)
+                });
+            matcher.matches(value.as_deref())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(s: &str) -> i64 {
+        DateTime::parse_from_rfc3339(s).unwrap().timestamp_micros()
+    }
+
+    fn silence(matchers: Vec<SilenceMatcher>) -> Silence {
+        Silence {
+            id: "s1".to_string(),
+            name: "maintenance".to_string(),
+            description: String::new(),
+            enabled: true,
+            matchers,
+            schedule: SilenceSchedule::Once {
+                start_time: 0,
+                end_time: i64::MAX,
+            },
+            created_by: String::new(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn matcher(name: &str, operator: SilenceMatchOperator, value: &str) -> SilenceMatcher {
+        SilenceMatcher::new(name, operator, value)
+    }
+
+    #[test]
+    fn test_silence_deserialize() {
+        let json = r#"{
+            "name": "weekly",
+            "matchers": [{"name": "stream_name", "value": "k8s.*", "operator": "=~"}],
+            "schedule": {"type": "recurring", "cron": "0 0 2 * * Sun", "duration_minutes": 120}
+        }"#;
+        let silence: Silence = serde_json::from_str(json).unwrap();
+        assert!(silence.enabled);
+        assert_eq!(silence.matchers[0].operator, SilenceMatchOperator::Regex);
+        assert!(matches!(
+            silence.schedule,
+            SilenceSchedule::Recurring {
+                duration_minutes: 120,
+                timezone: None,
+                ..
+            }
+        ));
+        assert!(silence.validate().is_ok());
+    }
+
+    #[test]
+    fn test_silence_validate() {
+        assert!(silence(vec![]).validate().is_err());
+        assert!(
+            silence(vec![matcher("host", SilenceMatchOperator::Regex, "(")])
+                .validate()
+                .is_err()
+        );
+
+        let mut s = silence(vec![matcher("host", SilenceMatchOperator::Equal, "a")]);
+        assert!(s.validate().is_ok());
+        s.schedule = SilenceSchedule::Once {
+            start_time: 10,
+            end_time: 10,
+        };
+        assert!(s.validate().is_err());
+        s.schedule = SilenceSchedule::Recurring {
+            cron: "0 0 2 * * Sun".to_string(),
+            duration_minutes: 60,
+            timezone: Some("Mars/Olympus".to_string()),
+        };
+        assert!(s.validate().is_err());
+    }
+
+    #[test]
+    fn test_silence_matches() {
+        let labels = [("alert_name", "cpu_high"), ("stream_name", "k8s_logs")];
+        let mut row = Map::new();
+        row.insert("host".to_string(), Value::String("srv01".to_string()));
+        row.insert("code".to_string(), Value::from(500));
+
+        let s = silence(vec![
+            matcher("alert_name", SilenceMatchOperator::Equal, "cpu_high"),
+            matcher("stream_name", SilenceMatchOperator::Regex, "k8s_.*"),
+            matcher("host", SilenceMatchOperator::NotRegex, "db.*"),
+            matcher("code", SilenceMatchOperator::Equal, "500"),
+        ]);
+        assert!(s.matches(&labels, Some(&row)));
+        // the regexes are compiled once and kept with the matcher
+        assert!(s.matchers[1].regex.get().is_some_and(|re| re.is_some()));
+        assert!(s.matchers[0].regex.get().is_none());
+
+        let s = silence(vec![matcher(
+            "host",
+            SilenceMatchOperator::NotEqual,
+            "srv01",
+        )]);
+        assert!(!s.matches(&labels, Some(&row)));
+
+        // Missing labels match an empty value
+        let s = silence(vec![matcher("region", SilenceMatchOperator::Equal, "")]);
+        assert!(s.matches(&labels, Some(&row)));
+        assert!(s.matches(&labels, None));
+    }
+
+    #[test]
+    fn test_once_schedule_is_active() {
+        let schedule = SilenceSchedule::Once {
+            start_time: 100,
+            end_time: 200,
+        };
+        assert!(!schedule.is_active(99));
+        assert!(schedule.is_active(100));
+        assert!(schedule.is_active(199));
+        assert!(!schedule.is_active(200));
+    }
+
+    #[test]
+    fn test_recurring_schedule_is_active() {
+        // Every Sunday 02:00 - 04:00 UTC, 2026-10-18 is a Sunday
+        let schedule = SilenceSchedule::Recurring {
+            cron: "0 0 2 * * Sun".to_string(),
+            duration_minutes: 120,
+            timezone: None,
+        };
+        assert!(!schedule.is_active(micros("2026-10-18T01:59:59Z")));
+        assert!(schedule.is_active(micros("2026-10-18T02:00:00Z")));
+        assert!(schedule.is_active(micros("2026-10-18T03:59:59Z")));
+        assert!(!schedule.is_active(micros("2026-10-18T04:00:00Z")));
+        assert!(!schedule.is_active(micros("2026-10-19T03:00:00Z")));
+
+        // Same window in New York (UTC-4 in October)
+        let schedule = SilenceSchedule::Recurring {
+            cron: "0 0 2 * * Sun".to_string(),
+            duration_minutes: 120,
+            timezone: Some("America/New_York".to_string()),
+        };
+        assert!(!schedule.is_active(micros("2026-10-18T03:00:00Z")));
+        assert!(schedule.is_active(micros("2026-10-18T07:00:00Z")));
+
+        let mut s = silence(vec![matcher("host", SilenceMatchOperator::Equal, "a")]);
+        s.schedule = schedule;
+        s.enabled = false;
+        assert!(!s.is_active(micros("2026-10-18T07:00:00Z")));
+    }
+}
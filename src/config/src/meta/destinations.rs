@@ -88,6 +88,26 @@ pub enum DestinationType {
     Http(Endpoint),
     Email(Email),
     Sns(AwsSns),
+    Slack(Slack),
+    #[serde(rename = "msteams")]
+    MsTeams(MsTeams),
+    #[serde(rename = "pagerduty")]
+    PagerDuty(PagerDuty),
+    Opsgenie(Opsgenie),
+}
+
+impl DestinationType {
+    /// Whether the destination is a native chat or paging integration, these
+    /// build provider specific payloads around the rendered template.
+    pub fn is_native_provider(&self) -> bool {
+        matches!(
+            self,
+            DestinationType::Slack(_)
+                | DestinationType::MsTeams(_)
+                | DestinationType::PagerDuty(_)
+                | DestinationType::Opsgenie(_)
+        )
+    }
 }
 
 impl Default for DestinationType {
@@ -121,6 +141,118 @@ pub struct Endpoint {
     pub destination_type: Option<String>,
     #[serde(default)]
     pub metadata: HashMap<String, String>,
+    /// Retry policy for deliveries failing with a server error or timeout,
+    /// failed deliveries are not retried when not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<WebhookRetryPolicy>,
+    /// Secret used to sign the requests with HMAC-SHA256, requests are not
+    /// signed when not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_secret: Option<String>,
+}
+
+/// Header carrying the request signature, formatted as `t=<timestamp>,v1=<signature>`
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-OpenObserve-Signature";
+
+/// Stream of the organization recording the webhook deliveries that failed
+/// permanently
+pub const WEBHOOK_DEAD_LETTER_STREAM: &str = "webhook_dead_letters";
+
+const WEBHOOK_MAX_RETRIES: u32 = 20;
+
+#[derive(Serialize, Debug, PartialEq, Eq, Deserialize, Clone, ToSchema)]
+#[serde(default)]
+pub struct WebhookRetryPolicy {
+    /// Number of retries after the first failed delivery
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every following retry
+    pub initial_backoff_secs: u64,
+    /// Upper bound of the delay between two retries
+    pub max_backoff_secs: u64,
+}
+
+impl Default for WebhookRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_secs: 30,
+            max_backoff_secs: 3600,
+        }
+    }
+}
+
+impl WebhookRetryPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_retries > WEBHOOK_MAX_RETRIES {
+            return Err(format!("max_retries must be at most {WEBHOOK_MAX_RETRIES}"));
+        }
+        if self.initial_backoff_secs == 0 {
+            return Err("initial_backoff_secs must be greater than 0".to_string());
+        }
+        if self.max_backoff_secs < self.initial_backoff_secs {
+            return Err("max_backoff_secs must not be less than initial_backoff_secs".to_string());
+        }
+        Ok(())
+    }
+
+    /// Delay before the given retry, starting at 1, without jitter
+    pub fn backoff_secs(&self, retry: u32) -> u64 {
+        let factor = 1u64
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        self.initial_backoff_secs
+            .saturating_mul(factor)
+            .min(self.max_backoff_secs)
+    }
+}
+
+/// A webhook delivery waiting for a retry, stored as the data of its
+/// scheduler trigger
+#[derive(Serialize, Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WebhookDelivery {
+    /// Name of the destination the message is delivered to
+    pub destination: String,
+    /// What sent the message, e.g. the alert name
+    pub source: String,
+    pub payload: String,
+    /// Number of delivery attempts so far, including the first one
+    pub attempts: u32,
+    /// When the first delivery was attempted, in microseconds
+    pub first_attempt_at: i64,
+    pub last_error: String,
+}
+
+/// A webhook delivery that failed permanently, recorded in the
+/// [`WEBHOOK_DEAD_LETTER_STREAM`] stream
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+pub struct WebhookDeadLetter {
+    #[serde(rename = "_timestamp")]
+    pub timestamp: i64,
+    pub org: String,
+    pub destination: String,
+    pub url: String,
+    pub source: String,
+    pub payload: String,
+    pub attempts: u32,
+    pub first_attempt_at: i64,
+    pub last_error: String,
+}
+
+impl WebhookDeadLetter {
+    pub fn new(org: &str, url: &str, delivery: WebhookDelivery, timestamp: i64) -> Self {
+        Self {
+            timestamp,
+            org: org.to_string(),
+            destination: delivery.destination,
+            url: url.to_string(),
+            source: delivery.source,
+            payload: delivery.payload,
+            attempts: delivery.attempts,
+            first_attempt_at: delivery.first_attempt_at,
+            last_error: delivery.last_error,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -130,6 +262,95 @@ pub struct AwsSns {
     pub aws_region: String,
 }
 
+/// Slack destination, either an incoming webhook or a bot token posting to a
+/// channel.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Slack {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+}
+
+/// Microsoft Teams destination posting to a Workflows webhook URL
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MsTeams {
+    pub webhook_url: String,
+}
+
+/// PagerDuty Events API v2 destination
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PagerDuty {
+    pub routing_key: String,
+    /// Severity used when the alert does not report one
+    pub severity: PagerDutySeverity,
+    /// Optional dedup key template, defaults to the alert key. Events with the
+    /// same dedup key are grouped into one PagerDuty alert.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PagerDutySeverity {
+    Critical,
+    #[default]
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for PagerDutySeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Critical => write!(f, "critical"),
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+            Self::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// Opsgenie Alert API destination
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Opsgenie {
+    pub api_key: String,
+    /// Priority used when the alert does not report a severity
+    pub priority: OpsgeniePriority,
+    /// API base URL, defaults to `https://api.opsgenie.com`. Use
+    /// `https://api.eu.opsgenie.com` for EU accounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum OpsgeniePriority {
+    P1,
+    P2,
+    #[default]
+    P3,
+    P4,
+    P5,
+}
+
+impl fmt::Display for OpsgeniePriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::P1 => write!(f, "P1"),
+            Self::P2 => write!(f, "P2"),
+            Self::P3 => write!(f, "P3"),
+            Self::P4 => write!(f, "P4"),
+            Self::P5 => write!(f, "P5"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HTTPType {
@@ -366,6 +587,8 @@ mod tests {
             output_format: Some(HTTPOutputFormat::JSON),
             destination_type: Some("custom".to_string()),
             metadata: HashMap::new(),
+            retry: None,
+            signing_secret: None,
         };
 
         assert_eq!(endpoint.url, "https://api.example.com");
@@ -445,6 +668,8 @@ mod tests {
             output_format: None,
             destination_type: Some("openobserve".to_string()),
             metadata: HashMap::new(),
+            retry: None,
+            signing_secret: None,
         };
 
         let dest_type = DestinationType::Http(endpoint.clone());
@@ -475,6 +700,44 @@ mod tests {
         assert!(matches!(dest_type, DestinationType::Sns(_)));
     }
 
+    #[test]
+    fn test_destination_type_native_providers_serde() {
+        let json = r#"{"type":"pagerduty","routing_key":"R0UT1NG","severity":"critical"}"#;
+        let dest_type: DestinationType = serde_json::from_str(json).unwrap();
+        assert!(dest_type.is_native_provider());
+        match &dest_type {
+            DestinationType::PagerDuty(pd) => {
+                assert_eq!(pd.routing_key, "R0UT1NG");
+                assert_eq!(pd.severity, PagerDutySeverity::Critical);
+                assert!(pd.dedup_key.is_none());
+            }
+            _ => panic!("expected PagerDuty destination type"),
+        }
+
+        let json = r#"{"type":"opsgenie","api_key":"key"}"#;
+        let dest_type: DestinationType = serde_json::from_str(json).unwrap();
+        assert!(
+            matches!(dest_type, DestinationType::Opsgenie(og) if og.priority == OpsgeniePriority::P3)
+        );
+
+        let teams = DestinationType::MsTeams(MsTeams {
+            webhook_url: "https://example.com/workflow".to_string(),
+        });
+        let json = serde_json::to_value(&teams).unwrap();
+        assert_eq!(json["type"], "msteams");
+
+        let slack = DestinationType::Slack(Slack {
+            bot_token: Some("xoxb-1".to_string()),
+            channel: Some("#alerts".to_string()),
+            ..Default::default()
+        });
+        let json = serde_json::to_value(&slack).unwrap();
+        assert_eq!(json["type"], "slack");
+        assert!(json.get("webhook_url").is_none());
+
+        assert!(!DestinationType::default().is_native_provider());
+    }
+
     #[test]
     fn test_module_alert() {
         let module = Module::Alert {
@@ -505,6 +768,8 @@ mod tests {
             output_format: None,
             destination_type: Some("splunk".to_string()),
             metadata: HashMap::new(),
+            retry: None,
+            signing_secret: None,
         };
 
         let module = Module::Pipeline {
@@ -751,6 +1016,8 @@ mod tests {
             output_format: None,
             destination_type: None,
             metadata: HashMap::new(),
+            retry: None,
+            signing_secret: None,
         };
         let json = serde_json::to_value(&ep).unwrap();
         let obj = json.as_object().unwrap();
@@ -773,6 +1040,8 @@ mod tests {
             output_format: Some(HTTPOutputFormat::JSON),
             destination_type: Some("custom".to_string()),
             metadata: HashMap::new(),
+            retry: None,
+            signing_secret: None,
         };
         let json = serde_json::to_value(&ep).unwrap();
         let obj = json.as_object().unwrap();
@@ -808,4 +1077,41 @@ mod tests {
         assert!(obj.contains_key("template"));
         assert_eq!(obj["template"], serde_json::json!("my_template"));
     }
+
+    #[test]
+    fn test_webhook_retry_policy() {
+        let policy = WebhookRetryPolicy {
+            max_retries: 5,
+            initial_backoff_secs: 10,
+            max_backoff_secs: 60,
+        };
+        assert!(policy.validate().is_ok());
+        assert_eq!(policy.backoff_secs(1), 10);
+        assert_eq!(policy.backoff_secs(2), 20);
+        assert_eq!(policy.backoff_secs(3), 40);
+        assert_eq!(policy.backoff_secs(4), 60);
+        assert_eq!(policy.backoff_secs(100), 60);
+
+        let invalid = WebhookRetryPolicy {
+            initial_backoff_secs: 0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+        let invalid = WebhookRetryPolicy {
+            max_backoff_secs: 1,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+
+        let endpoint: Endpoint = serde_json::from_value(serde_json::json!({
+            "url": "https://example.com/hook",
+            "retry": {"max_retries": 2},
+            "signing_secret": "s3cret"
+        }))
+        .unwrap();
+        let retry = endpoint.retry.unwrap();
+        assert_eq!(retry.max_retries, 2);
+        assert_eq!(retry.initial_backoff_secs, 30);
+        assert_eq!(endpoint.signing_secret.as_deref(), Some("s3cret"));
+    }
 }
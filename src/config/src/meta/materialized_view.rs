@@ -0,0 +1,256 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Materialized aggregation views.
+//!
+//! A view is defined by a query of the form
+//! `SELECT histogram(_timestamp, '1m'), dims.., aggregates.. FROM stream GROUP BY ..`
+//! and stored as a stream of partial aggregate states, one row per bucket,
+//! dimension values and flush of the base stream. States are kept in columns
+//! named `{alias}__{state}` and are merged at query time. Every row also keeps
+//! the file it was folded from, so that the rows of a file folded twice are
+//! read once.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Separates the alias of an aggregate from the name of its state
+pub const STATE_COLUMN_SEPARATOR: &str = "__";
+
+/// The column holding the file of the base stream a state row was folded from
+pub const FOLD_COLUMN: &str = "_fold";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewAggregateFunc {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl ViewAggregateFunc {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "count" => Some(Self::Count),
+            "sum" => Some(Self::Sum),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "avg" => Some(Self::Avg),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Avg => "avg",
+        }
+    }
+
+    /// The partial states kept for the aggregate, as pairs of the state name,
+    /// which is also the function that builds it from raw rows, and the
+    /// function that merges states.
+    pub fn states(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Count => &[("count", "sum")],
+            Self::Sum => &[("sum", "sum")],
+            Self::Min => &[("min", "min")],
+            Self::Max => &[("max", "max")],
+            Self::Avg => &[("sum", "sum"), ("count", "sum")],
+        }
+    }
+}
+
+impl std::fmt::Display for ViewAggregateFunc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ViewAggregate {
+    pub func: ViewAggregateFunc,
+    /// The aggregated field, `None` for `count(*)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub alias: String,
+}
+
+impl ViewAggregate {
+    pub fn state_column(&self, state: &str) -> String {
+        format!("{}{STATE_COLUMN_SEPARATOR}{state}", self.alias)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MaterializedView {
+    /// Name of the view, which is also the name of the logs stream holding
+    /// its partial states
+    pub name: String,
+    pub org_id: String,
+    /// The logs stream the view aggregates
+    pub stream_name: String,
+    /// The defining query
+    pub sql: String,
+    /// Width of the buckets, in microseconds
+    pub interval: i64,
+    pub dimensions: Vec<String>,
+    pub aggregates: Vec<ViewAggregate>,
+    /// Data flushed before the view was created is not materialized
+    pub created_at: i64,
+}
+
+impl MaterializedView {
+    /// Start of the first bucket that was fully materialized
+    pub fn start_time(&self) -> i64 {
+        let rem = self.created_at.rem_euclid(self.interval);
+        if rem == 0 {
+            self.created_at
+        } else {
+            self.created_at - rem + self.interval
+        }
+    }
+
+    pub fn find_aggregate(
+        &self,
+        func: ViewAggregateFunc,
+        field: Option<&str>,
+    ) -> Option<&ViewAggregate> {
+        self.aggregates
+            .iter()
+            .find(|a| a.func == func && a.field.as_deref() == field)
+    }
+}
+
+/// A file of the base stream that is not folded into a view yet. It is
+/// recorded before the file is folded and removed once it is, so a fold that
+/// failed or was interrupted is replayed, and the view is only read below the
+/// oldest file still pending.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingFold {
+    pub org_id: String,
+    /// Name of the view
+    pub view: String,
+    pub account: String,
+    pub file: String,
+    /// Smallest `_timestamp` of the file
+    pub min_ts: i64,
+    /// Largest `_timestamp` of the file
+    #[serde(default)]
+    pub max_ts: i64,
+    /// The ingester which flushed the file
+    pub node: String,
+    pub created_at: i64,
+}
+
+/// A time range over which the states of a view don't match the base stream,
+/// because a file could not be folded into the view or data was deleted from
+/// the base stream. The view is not read over it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewGap {
+    pub org_id: String,
+    /// Name of the view
+    pub view: String,
+    pub start_time: i64,
+    /// End of the range, exclusive
+    pub end_time: i64,
+    pub reason: String,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct MaterializedViewRequest {
+    pub name: String,
+    pub sql: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct MaterializedViewList {
+    pub list: Vec<MaterializedView>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view() -> MaterializedView {
+        MaterializedView {
+            name: "nginx_1m".to_string(),
+            org_id: "default".to_string(),
+            stream_name: "nginx".to_string(),
+            sql: "".to_string(),
+            interval: 60_000_000,
+            dimensions: vec!["status".to_string()],
+            aggregates: vec![
+                ViewAggregate {
+                    func: ViewAggregateFunc::Count,
+                    field: None,
+                    alias: "cnt".to_string(),
+                },
+                ViewAggregate {
+                    func: ViewAggregateFunc::Avg,
+                    field: Some("took".to_string()),
+                    alias: "avg_took".to_string(),
+                },
+            ],
+            created_at: 90_000_000,
+        }
+    }
+
+    #[test]
+    fn test_start_time() {
+        let mut v = view();
+        assert_eq!(v.start_time(), 120_000_000);
+        v.created_at = 120_000_000;
+        assert_eq!(v.start_time(), 120_000_000);
+    }
+
+    #[test]
+    fn test_find_aggregate() {
+        let v = view();
+        assert_eq!(
+            v.find_aggregate(ViewAggregateFunc::Count, None)
+                .map(|a| a.alias.as_str()),
+            Some("cnt")
+        );
+        assert!(v.find_aggregate(ViewAggregateFunc::Avg, None).is_none());
+        assert!(
+            v.find_aggregate(ViewAggregateFunc::Avg, Some("took"))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_state_columns() {
+        let v = view();
+        let avg = &v.aggregates[1];
+        let columns = avg
+            .func
+            .states()
+            .iter()
+            .map(|(state, _)| avg.state_column(state))
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec!["avg_took__sum", "avg_took__count"]);
+        assert_eq!(
+            ViewAggregateFunc::from_name("AVG"),
+            Some(ViewAggregateFunc::Avg)
+        );
+        assert_eq!(ViewAggregateFunc::from_name("median"), None);
+    }
+}
@@ -25,6 +25,7 @@ pub mod folder;
 pub mod function;
 pub mod inverted_index;
 pub mod logger;
+pub mod materialized_view;
 pub mod meta_store;
 pub mod model_pricing;
 pub mod organization;
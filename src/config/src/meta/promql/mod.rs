@@ -65,6 +65,8 @@ where
 }
 
 pub mod grpc;
+pub mod rules;
+pub mod scrape;
 pub mod value;
 
 pub const NAME_LABEL: &str = "__name__";
@@ -75,6 +77,7 @@ pub const BUCKET_LABEL: &str = "le";
 pub const QUANTILE_LABEL: &str = "quantile";
 pub const METADATA_LABEL: &str = "prom_metadata"; // for schema metadata key
 pub const EXEMPLARS_LABEL: &str = "exemplars";
+pub const NATIVE_HISTOGRAM_LABEL: &str = "native_histogram";
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Metric<'a> {
@@ -83,6 +86,95 @@ pub struct Metric<'a> {
     pub value: f64,
 }
 
+/// A Prometheus native histogram sample, stored as JSON in the
+/// [`NATIVE_HISTOGRAM_LABEL`] column. Bucket counts are absolute, unlike the
+/// delta encoding of integer histograms on the wire.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NativeHistogram {
+    pub schema: i32,
+    pub count: f64,
+    pub sum: f64,
+    pub zero_threshold: f64,
+    pub zero_count: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub positive_spans: Vec<NativeHistogramSpan>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub positive_buckets: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub negative_spans: Vec<NativeHistogramSpan>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub negative_buckets: Vec<f64>,
+    /// Upper bounds of the buckets of a custom buckets (schema -53) histogram
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_values: Vec<f64>,
+    pub reset_hint: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NativeHistogramSpan {
+    pub offset: i32,
+    pub length: u32,
+}
+
+impl From<&prometheus_rpc::Histogram> for NativeHistogram {
+    fn from(h: &prometheus_rpc::Histogram) -> Self {
+        use prometheus_rpc::histogram::{Count, ZeroCount};
+
+        let count = match h.count {
+            Some(Count::CountInt(v)) => v as f64,
+            Some(Count::CountFloat(v)) => v,
+            None => 0.0,
+        };
+        let zero_count = match h.zero_count {
+            Some(ZeroCount::ZeroCountInt(v)) => v as f64,
+            Some(ZeroCount::ZeroCountFloat(v)) => v,
+            None => 0.0,
+        };
+        let reset_hint = prometheus_rpc::histogram::ResetHint::try_from(h.reset_hint)
+            .unwrap_or(prometheus_rpc::histogram::ResetHint::Unknown)
+            .as_str_name()
+            .to_string();
+        Self {
+            schema: h.schema,
+            count,
+            sum: h.sum,
+            zero_threshold: h.zero_threshold,
+            zero_count,
+            positive_spans: h.positive_spans.iter().map(Into::into).collect(),
+            positive_buckets: absolute_buckets(&h.positive_deltas, &h.positive_counts),
+            negative_spans: h.negative_spans.iter().map(Into::into).collect(),
+            negative_buckets: absolute_buckets(&h.negative_deltas, &h.negative_counts),
+            custom_values: h.custom_values.clone(),
+            reset_hint,
+        }
+    }
+}
+
+impl From<&prometheus_rpc::BucketSpan> for NativeHistogramSpan {
+    fn from(span: &prometheus_rpc::BucketSpan) -> Self {
+        Self {
+            offset: span.offset,
+            length: span.length,
+        }
+    }
+}
+
+/// Integer histograms send each bucket as a delta to the previous one, float
+/// histograms send absolute counts.
+fn absolute_buckets(deltas: &[i64], counts: &[f64]) -> Vec<f64> {
+    if deltas.is_empty() {
+        return counts.to_vec();
+    }
+    let mut current = 0i64;
+    deltas
+        .iter()
+        .map(|delta| {
+            current += delta;
+            current as f64
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Serialize, Eq, PartialEq, Deserialize)]
 pub struct ClusterLeader {
     pub name: String,
@@ -725,4 +817,66 @@ mod tests {
         assert!(obj.contains_key("regions"));
         assert!(obj.contains_key("clusters"));
     }
+
+    #[test]
+    fn test_native_histogram_from_proto() {
+        use prometheus_rpc::histogram::{Count, ResetHint, ZeroCount};
+
+        let h = prometheus_rpc::Histogram {
+            count: Some(Count::CountInt(12)),
+            sum: 18.4,
+            schema: 1,
+            zero_threshold: 0.001,
+            zero_count: Some(ZeroCount::ZeroCountInt(2)),
+            positive_spans: vec![
+                prometheus_rpc::BucketSpan {
+                    offset: 0,
+                    length: 2,
+                },
+                prometheus_rpc::BucketSpan {
+                    offset: 1,
+                    length: 2,
+                },
+            ],
+            positive_deltas: vec![1, 1, -1, 0],
+            negative_spans: vec![prometheus_rpc::BucketSpan {
+                offset: 0,
+                length: 1,
+            }],
+            negative_deltas: vec![4],
+            reset_hint: ResetHint::Gauge as i32,
+            ..Default::default()
+        };
+        let nh = NativeHistogram::from(&h);
+        assert_eq!(nh.count, 12.0);
+        assert_eq!(nh.zero_count, 2.0);
+        assert_eq!(nh.positive_buckets, vec![1.0, 2.0, 1.0, 1.0]);
+        assert_eq!(nh.negative_buckets, vec![4.0]);
+        assert_eq!(
+            nh.positive_spans[1],
+            NativeHistogramSpan {
+                offset: 1,
+                length: 2
+            }
+        );
+        assert_eq!(nh.reset_hint, "GAUGE");
+
+        // float histograms carry absolute counts
+        let h = prometheus_rpc::Histogram {
+            count: Some(Count::CountFloat(3.5)),
+            positive_spans: vec![prometheus_rpc::BucketSpan {
+                offset: -2,
+                length: 2,
+            }],
+            positive_counts: vec![1.5, 2.0],
+            ..Default::default()
+        };
+        let nh = NativeHistogram::from(&h);
+        assert_eq!(nh.count, 3.5);
+        assert_eq!(nh.positive_buckets, vec![1.5, 2.0]);
+        assert!(nh.negative_buckets.is_empty());
+        let json = serde_json::to_value(&nh).unwrap();
+        assert!(json.get("negative_spans").is_none());
+        assert_eq!(json["reset_hint"], "UNKNOWN");
+    }
 }
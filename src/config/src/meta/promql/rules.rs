@@ -0,0 +1,382 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus recording and alerting rules.
+//!
+//! The rule groups follow the Prometheus rule file format, see
+//! <https://prometheus.io/docs/prometheus/latest/configuration/recording_rules/>.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::time::parse_milliseconds;
+
+/// Evaluation interval of a rule group that doesn't set one.
+pub const DEFAULT_EVALUATION_INTERVAL: u64 = 60;
+
+/// A rule file, i.e. a list of rule groups.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleFile {
+    #[serde(default)]
+    pub groups: Vec<RuleGroup>,
+}
+
+/// A named list of rules evaluated sequentially at the same interval.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleGroup {
+    pub name: String,
+    /// How often the rules of the group are evaluated, e.g. `30s`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+/// A recording rule (`record`) or an alerting rule (`alert`).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alert: Option<String>,
+    pub expr: String,
+    /// How long the alert condition must hold before the alert fires.
+    #[serde(default, rename = "for", skip_serializing_if = "Option::is_none")]
+    pub for_duration: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleType {
+    Recording,
+    Alerting,
+}
+
+impl Rule {
+    pub fn rule_type(&self) -> RuleType {
+        if self.record.is_some() {
+            RuleType::Recording
+        } else {
+            RuleType::Alerting
+        }
+    }
+
+    /// Name of the recorded metric or of the alert.
+    pub fn name(&self) -> &str {
+        self.record
+            .as_deref()
+            .or(self.alert.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// The `for` duration in seconds, zero if unset.
+    pub fn for_seconds(&self) -> u64 {
+        self.for_duration
+            .as_deref()
+            .and_then(|v| parse_milliseconds(v).ok())
+            .unwrap_or_default()
+            / 1000
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match (self.record.as_deref(), self.alert.as_deref()) {
+            (Some(_), Some(_)) => {
+                return Err("only one of 'record' and 'alert' must be set".to_string());
+            }
+            (None, None) => return Err("one of 'record' or 'alert' must be set".to_string()),
+            (Some(record), None) => {
+                if !is_valid_metric_name(record) {
+                    return Err(format!("invalid recording rule name: {record}"));
+                }
+                if self.for_duration.is_some() || !self.annotations.is_empty() {
+                    return Err(format!(
+                        "recording rule {record}: 'for' and 'annotations' are only valid for alerting rules"
+                    ));
+                }
+            }
+            (None, Some(alert)) => {
+                if !is_valid_label_name(alert) {
+                    return Err(format!("invalid alerting rule name: {alert}"));
+                }
+                if let Some(v) = self.for_duration.as_deref() {
+                    parse_milliseconds(v)
+                        .map_err(|e| format!("alerting rule {alert}: invalid 'for': {e}"))?;
+                }
+            }
+        }
+        if self.expr.trim().is_empty() {
+            return Err(format!("rule {}: field 'expr' must be set", self.name()));
+        }
+        if let Some(name) = self.labels.keys().find(|name| !is_valid_label_name(name)) {
+            return Err(format!("rule {}: invalid label name: {name}", self.name()));
+        }
+        Ok(())
+    }
+}
+
+impl RuleGroup {
+    /// The evaluation interval in seconds.
+    pub fn interval_seconds(&self) -> u64 {
+        self.interval
+            .as_deref()
+            .and_then(|v| parse_milliseconds(v).ok())
+            .map(|v| v / 1000)
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_EVALUATION_INTERVAL)
+    }
+
+    pub fn has_recording_rules(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| rule.rule_type() == RuleType::Recording)
+    }
+
+    /// Checks the group the same way `promtool check rules` does, except for
+    /// the expressions which are checked by the PromQL parser of the caller.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("rule group name must not be empty".to_string());
+        }
+        if self.name.contains('/') {
+            return Err(format!("rule group name cannot contain '/': {}", self.name));
+        }
+        if let Some(v) = self.interval.as_deref() {
+            match parse_milliseconds(v) {
+                Ok(ms) if ms >= 1000 => {}
+                Ok(_) => return Err(format!("group {}: interval must be at least 1s", self.name)),
+                Err(e) => return Err(format!("group {}: invalid interval: {e}", self.name)),
+            }
+        }
+        if self.rules.is_empty() {
+            return Err(format!("group {} has no rules", self.name));
+        }
+        for rule in self.rules.iter() {
+            rule.validate()
+                .map_err(|e| format!("group {}: {e}", self.name))?;
+        }
+        Ok(())
+    }
+}
+
+/// A rule group loaded into an organization.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoredRuleGroup {
+    pub namespace: String,
+    pub group: RuleGroup,
+    /// Alert destinations notified by the alerting rules of the group.
+    #[serde(default)]
+    pub destinations: Vec<String>,
+    /// Ids of the alerts created for the alerting rules, keyed by alert name.
+    #[serde(default)]
+    pub alert_ids: BTreeMap<String, String>,
+}
+
+/// Outcome of the last evaluation of the recording rules of a group.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleGroupHealth {
+    /// Time of the last evaluation, in microseconds.
+    pub last_evaluation: i64,
+    /// Duration of the last evaluation, in seconds.
+    pub evaluation_time: f64,
+    /// Evaluation errors, keyed by the position of the rule in the group.
+    #[serde(default)]
+    pub errors: BTreeMap<usize, String>,
+}
+
+/// Query parameters of `/api/v1/rules`.
+#[derive(Debug, Default, Deserialize)]
+pub struct RequestRules {
+    /// Only return the alerting (`alert`) or the recording (`record`) rules.
+    #[serde(rename = "type")]
+    pub rule_type: Option<String>,
+}
+
+/// Query parameters of the rule group loader.
+#[derive(Debug, Default, Deserialize)]
+pub struct RequestSetRuleGroups {
+    /// Comma separated alert destinations notified by the alerting rules.
+    pub destinations: Option<String>,
+}
+
+/// Response of `/api/v1/rules`.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RuleDiscovery {
+    pub groups: Vec<RuleGroupStatus>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleGroupStatus {
+    pub name: String,
+    /// The namespace the group was loaded into.
+    pub file: String,
+    pub rules: Vec<RuleStatus>,
+    /// Evaluation interval in seconds.
+    pub interval: f64,
+    pub evaluation_time: f64,
+    pub last_evaluation: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleStatus {
+    pub name: String,
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+    pub labels: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alerts: Option<Vec<ActiveAlert>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<AlertState>,
+    pub health: RuleHealth,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub evaluation_time: f64,
+    pub last_evaluation: String,
+    #[serde(rename = "type")]
+    pub rule_type: RuleType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleHealth {
+    Ok,
+    Err,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Inactive,
+    Pending,
+    Firing,
+}
+
+/// Response of `/api/v1/alerts`.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AlertDiscovery {
+    pub alerts: Vec<ActiveAlert>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveAlert {
+    pub labels: BTreeMap<String, String>,
+    pub annotations: BTreeMap<String, String>,
+    pub state: AlertState,
+    pub active_at: String,
+    pub value: String,
+}
+
+fn is_valid_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+pub(crate) fn is_valid_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording_rule(record: &str, expr: &str) -> Rule {
+        Rule {
+            record: Some(record.to_string()),
+            expr: expr.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rule_group_validate() {
+        let mut group = RuleGroup {
+            name: "example".to_string(),
+            interval: Some("30s".to_string()),
+            rules: vec![
+                recording_rule(
+                    "job:http_requests:rate5m",
+                    "sum by (job) (rate(http_requests_total[5m]))",
+                ),
+                Rule {
+                    alert: Some("HighErrorRate".to_string()),
+                    expr: "job:http_requests:rate5m > 10".to_string(),
+                    for_duration: Some("10m".to_string()),
+                    ..Default::default()
+                },
+            ],
+        };
+        assert!(group.validate().is_ok());
+        assert_eq!(group.interval_seconds(), 30);
+        assert!(group.has_recording_rules());
+        assert_eq!(group.rules[1].for_seconds(), 600);
+        assert_eq!(group.rules[1].rule_type(), RuleType::Alerting);
+
+        group.rules[0].record = Some("0invalid".to_string());
+        assert!(group.validate().is_err());
+
+        group.rules[0].alert = Some("Both".to_string());
+        group.rules[0].record = Some("both".to_string());
+        assert!(group.validate().is_err());
+
+        group.rules[0] = recording_rule("valid", " ");
+        assert!(group.validate().is_err());
+
+        group.rules[0] = recording_rule("valid", "up");
+        group.interval = Some("abc".to_string());
+        assert!(group.validate().is_err());
+
+        group.interval = None;
+        assert!(group.validate().is_ok());
+        assert_eq!(group.interval_seconds(), DEFAULT_EVALUATION_INTERVAL);
+
+        group.name = "a/b".to_string();
+        assert!(group.validate().is_err());
+    }
+
+    #[test]
+    fn test_rule_serde() {
+        let rule: Rule = serde_json::from_str(
+            r#"{"alert":"InstanceDown","expr":"up == 0","for":"5m","labels":{"severity":"page"}}"#,
+        )
+        .unwrap();
+        assert_eq!(rule.for_duration.as_deref(), Some("5m"));
+        assert_eq!(
+            rule.labels.get("severity").map(String::as_str),
+            Some("page")
+        );
+        let value = serde_json::to_value(&rule).unwrap();
+        assert_eq!(value["for"], "5m");
+        assert!(value.get("record").is_none());
+        assert!(value.get("annotations").is_none());
+    }
+}
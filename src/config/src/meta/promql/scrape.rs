@@ -0,0 +1,624 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus scrape configuration.
+//!
+//! The scrape configs follow the `scrape_configs` section of the Prometheus
+//! configuration, see
+//! <https://prometheus.io/docs/prometheus/latest/configuration/configuration/#scrape_config>.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::rules::is_valid_label_name;
+use crate::utils::time::parse_milliseconds;
+
+pub const DEFAULT_SCRAPE_INTERVAL: u64 = 60;
+pub const DEFAULT_SCRAPE_TIMEOUT: u64 = 10;
+
+pub const ADDRESS_LABEL: &str = "__address__";
+pub const SCHEME_LABEL: &str = "__scheme__";
+pub const METRICS_PATH_LABEL: &str = "__metrics_path__";
+pub const SCRAPE_INTERVAL_LABEL: &str = "__scrape_interval__";
+pub const SCRAPE_TIMEOUT_LABEL: &str = "__scrape_timeout__";
+pub const PARAM_LABEL_PREFIX: &str = "__param_";
+pub const RESERVED_LABEL_PREFIX: &str = "__";
+pub const JOB_LABEL: &str = "job";
+pub const INSTANCE_LABEL: &str = "instance";
+
+/// The `scrape_configs` section of a Prometheus configuration file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScrapeConfigFile {
+    #[serde(default)]
+    pub scrape_configs: Vec<ScrapeConfig>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScrapeConfig {
+    pub job_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scrape_interval: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scrape_timeout: Option<String>,
+    #[serde(default = "default_metrics_path")]
+    pub metrics_path: String,
+    #[serde(default = "default_scheme")]
+    pub scheme: String,
+    /// Keep the labels of the scraped series on conflicts with the target
+    /// labels, instead of renaming them to `exported_<name>`.
+    #[serde(default)]
+    pub honor_labels: bool,
+    #[serde(default = "default_honor_timestamps")]
+    pub honor_timestamps: bool,
+    /// Max size of an uncompressed response body, like `10MB`, `0` disables the
+    /// limit. The `ZO_PROMETHEUS_SCRAPE_BODY_SIZE_LIMIT` limit applies when not
+    /// set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_size_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub static_configs: Vec<TargetGroup>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_sd_configs: Vec<FileSdConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relabel_configs: Vec<RelabelConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metric_relabel_configs: Vec<RelabelConfig>,
+}
+
+/// A list of targets sharing the same labels, as found in `static_configs` and
+/// in the files of `file_sd_configs`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TargetGroup {
+    #[serde(default)]
+    pub targets: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileSdConfig {
+    /// JSON or YAML files of target groups, the file name may contain `*`.
+    pub files: Vec<String>,
+    /// Accepted for compatibility, the files are read on every scrape.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    #[default]
+    Replace,
+    Keep,
+    Drop,
+    KeepEqual,
+    DropEqual,
+    HashMod,
+    LabelMap,
+    LabelDrop,
+    LabelKeep,
+    Lowercase,
+    Uppercase,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelabelConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_labels: Vec<String>,
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub target_label: String,
+    #[serde(default = "default_regex")]
+    pub regex: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub modulus: u64,
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+    #[serde(default)]
+    pub action: RelabelAction,
+}
+
+fn default_metrics_path() -> String {
+    "/metrics".to_string()
+}
+
+fn default_scheme() -> String {
+    "http".to_string()
+}
+
+fn default_honor_timestamps() -> bool {
+    true
+}
+
+fn default_separator() -> String {
+    ";".to_string()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_string()
+}
+
+fn default_replacement() -> String {
+    "$1".to_string()
+}
+
+fn is_zero(v: &u64) -> bool {
+    *v == 0
+}
+
+/// Parses a size in bytes with an optional unit, the units are powers of 1024
+/// like in Prometheus.
+fn parse_bytes(v: &str) -> Result<u64, String> {
+    let v = v.trim();
+    let split = v.find(|c: char| !c.is_ascii_digit()).unwrap_or(v.len());
+    let (num, unit) = v.split_at(split);
+    let num: u64 = num.parse().map_err(|_| format!("invalid size: {v}"))?;
+    let shift = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 0,
+        "KB" | "KIB" => 10,
+        "MB" | "MIB" => 20,
+        "GB" | "GIB" => 30,
+        "TB" | "TIB" => 40,
+        _ => return Err(format!("invalid size unit: {v}")),
+    };
+    num.checked_mul(1 << shift)
+        .ok_or_else(|| format!("size too large: {v}"))
+}
+
+/// A target to scrape, after relabeling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrapeTarget {
+    pub url: String,
+    /// The labels attached to the scraped series.
+    pub labels: BTreeMap<String, String>,
+}
+
+impl ScrapeConfig {
+    /// The scrape interval in seconds.
+    pub fn interval_seconds(&self) -> u64 {
+        self.scrape_interval
+            .as_deref()
+            .and_then(|v| parse_milliseconds(v).ok())
+            .map(|v| v / 1000)
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_SCRAPE_INTERVAL)
+    }
+
+    /// The scrape timeout in seconds, at most the scrape interval.
+    pub fn timeout_seconds(&self) -> u64 {
+        self.scrape_timeout
+            .as_deref()
+            .and_then(|v| parse_milliseconds(v).ok())
+            .map(|v| v / 1000)
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_SCRAPE_TIMEOUT)
+            .min(self.interval_seconds())
+    }
+
+    /// The max size of a response body in bytes, `None` when there is no
+    /// limit. `default` applies when the job does not set a limit.
+    pub fn body_size_limit_bytes(&self, default: u64) -> Option<u64> {
+        let limit = self
+            .body_size_limit
+            .as_deref()
+            .and_then(|v| parse_bytes(v).ok())
+            .unwrap_or(default);
+        (limit > 0).then_some(limit)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.job_name.trim().is_empty() {
+            return Err("job_name must not be empty".to_string());
+        }
+        if self.job_name.contains('/') {
+            return Err(format!("job_name cannot contain '/': {}", self.job_name));
+        }
+        for (name, v) in [
+            ("scrape_interval", &self.scrape_interval),
+            ("scrape_timeout", &self.scrape_timeout),
+        ] {
+            if let Some(v) = v.as_deref() {
+                match parse_milliseconds(v) {
+                    Ok(ms) if ms >= 1000 => {}
+                    Ok(_) => {
+                        return Err(format!("job {}: {name} must be at least 1s", self.job_name));
+                    }
+                    Err(e) => return Err(format!("job {}: invalid {name}: {e}", self.job_name)),
+                }
+            }
+        }
+        if let Some(v) = self.body_size_limit.as_deref()
+            && let Err(e) = parse_bytes(v)
+        {
+            return Err(format!(
+                "job {}: invalid body_size_limit: {e}",
+                self.job_name
+            ));
+        }
+        if self.scheme != "http" && self.scheme != "https" {
+            return Err(format!(
+                "job {}: unsupported scheme: {}",
+                self.job_name, self.scheme
+            ));
+        }
+        if !self.metrics_path.starts_with('/') {
+            return Err(format!(
+                "job {}: metrics_path must start with '/'",
+                self.job_name
+            ));
+        }
+        if self.static_configs.is_empty() && self.file_sd_configs.is_empty() {
+            return Err(format!("job {} has no targets", self.job_name));
+        }
+        for relabel in self
+            .relabel_configs
+            .iter()
+            .chain(self.metric_relabel_configs.iter())
+        {
+            Relabeler::new(relabel).map_err(|e| format!("job {}: {e}", self.job_name))?;
+        }
+        Ok(())
+    }
+
+    /// Builds the targets of the given target groups: sets the default labels,
+    /// applies the `relabel_configs` and derives the URL from the resulting
+    /// `__scheme__`, `__address__`, `__metrics_path__` and `__param_*` labels.
+    pub fn targets(&self, groups: &[TargetGroup]) -> Result<Vec<ScrapeTarget>, String> {
+        let relabelers = self
+            .relabel_configs
+            .iter()
+            .map(Relabeler::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut targets = Vec::new();
+        for group in groups {
+            for address in group.targets.iter() {
+                let mut labels = BTreeMap::new();
+                labels.insert(JOB_LABEL.to_string(), self.job_name.clone());
+                labels.insert(SCHEME_LABEL.to_string(), self.scheme.clone());
+                labels.insert(METRICS_PATH_LABEL.to_string(), self.metrics_path.clone());
+                labels.insert(
+                    SCRAPE_INTERVAL_LABEL.to_string(),
+                    format!("{}s", self.interval_seconds()),
+                );
+                labels.insert(
+                    SCRAPE_TIMEOUT_LABEL.to_string(),
+                    format!("{}s", self.timeout_seconds()),
+                );
+                for (name, values) in self.params.iter() {
+                    if let Some(v) = values.first() {
+                        labels.insert(format!("{PARAM_LABEL_PREFIX}{name}"), v.clone());
+                    }
+                }
+                // the labels of the target group take precedence over the defaults
+                labels.extend(group.labels.clone());
+                labels.insert(ADDRESS_LABEL.to_string(), address.clone());
+
+                if !relabel(&mut labels, &relabelers) {
+                    continue;
+                }
+                let Some(address) = labels.get(ADDRESS_LABEL).filter(|v| !v.is_empty()) else {
+                    continue;
+                };
+                let mut url = format!(
+                    "{}://{}{}",
+                    labels.get(SCHEME_LABEL).map_or("http", |v| v.as_str()),
+                    address,
+                    labels.get(METRICS_PATH_LABEL).map_or("", |v| v.as_str()),
+                );
+                let params = labels
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.strip_prefix(PARAM_LABEL_PREFIX)?, v)))
+                    .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+                    .collect::<Vec<_>>();
+                if !params.is_empty() {
+                    url.push('?');
+                    url.push_str(&params.join("&"));
+                }
+                if !labels.contains_key(INSTANCE_LABEL) {
+                    let instance = address.clone();
+                    labels.insert(INSTANCE_LABEL.to_string(), instance);
+                }
+                labels.retain(|k, _| !k.starts_with(RESERVED_LABEL_PREFIX));
+                targets.push(ScrapeTarget { url, labels });
+            }
+        }
+        Ok(targets)
+    }
+}
+
+/// A [`RelabelConfig`] with its regex compiled.
+#[derive(Clone, Debug)]
+pub struct Relabeler {
+    config: RelabelConfig,
+    regex: Regex,
+}
+
+impl Relabeler {
+    pub fn new(config: &RelabelConfig) -> Result<Self, String> {
+        let regex = Regex::new(&format!("^(?:{})$", config.regex))
+            .map_err(|e| format!("invalid relabel regex {}: {e}", config.regex))?;
+        let needs_target = matches!(
+            config.action,
+            RelabelAction::Replace
+                | RelabelAction::HashMod
+                | RelabelAction::KeepEqual
+                | RelabelAction::DropEqual
+                | RelabelAction::Lowercase
+                | RelabelAction::Uppercase
+        );
+        if needs_target && config.target_label.is_empty() {
+            return Err(format!(
+                "relabel action {:?} requires a target_label",
+                config.action
+            ));
+        }
+        if config.action == RelabelAction::HashMod && config.modulus == 0 {
+            return Err("relabel action hashmod requires a non-zero modulus".to_string());
+        }
+        Ok(Self {
+            config: config.clone(),
+            regex,
+        })
+    }
+
+    /// Applies the rule, returns false if the label set is dropped.
+    fn apply(&self, labels: &mut BTreeMap<String, String>) -> bool {
+        let cfg = &self.config;
+        let value = cfg
+            .source_labels
+            .iter()
+            .map(|name| labels.get(name).map_or("", |v| v.as_str()))
+            .collect::<Vec<_>>()
+            .join(&cfg.separator);
+        match cfg.action {
+            RelabelAction::Replace => {
+                let Some(caps) = self.regex.captures(&value) else {
+                    return true;
+                };
+                let mut target = String::new();
+                caps.expand(&cfg.target_label, &mut target);
+                if !is_valid_label_name(&target) {
+                    return true;
+                }
+                let mut replacement = String::new();
+                caps.expand(&cfg.replacement, &mut replacement);
+                if replacement.is_empty() {
+                    labels.remove(&target);
+                } else {
+                    labels.insert(target, replacement);
+                }
+            }
+            RelabelAction::Keep => return self.regex.is_match(&value),
+            RelabelAction::Drop => return !self.regex.is_match(&value),
+            RelabelAction::KeepEqual => {
+                return labels.get(&cfg.target_label).map_or("", |v| v.as_str()) == value;
+            }
+            RelabelAction::DropEqual => {
+                return labels.get(&cfg.target_label).map_or("", |v| v.as_str()) != value;
+            }
+            RelabelAction::HashMod => {
+                let digest = md5::compute(value.as_bytes()).0;
+                let hash = u64::from_be_bytes(digest[8..].try_into().unwrap());
+                labels.insert(cfg.target_label.clone(), (hash % cfg.modulus).to_string());
+            }
+            RelabelAction::LabelMap => {
+                let mapped = labels
+                    .iter()
+                    .filter_map(|(name, v)| {
+                        let caps = self.regex.captures(name)?;
+                        let mut target = String::new();
+                        caps.expand(&cfg.replacement, &mut target);
+                        Some((target, v.clone()))
+                    })
+                    .collect::<Vec<_>>();
+                labels.extend(mapped);
+            }
+            RelabelAction::LabelDrop => labels.retain(|name, _| !self.regex.is_match(name)),
+            RelabelAction::LabelKeep => labels.retain(|name, _| self.regex.is_match(name)),
+            RelabelAction::Lowercase => {
+                labels.insert(cfg.target_label.clone(), value.to_lowercase());
+            }
+            RelabelAction::Uppercase => {
+                labels.insert(cfg.target_label.clone(), value.to_uppercase());
+            }
+        }
+        true
+    }
+}
+
+/// Applies the relabeling rules in order, returns false if the label set is
+/// dropped.
+pub fn relabel(labels: &mut BTreeMap<String, String>, relabelers: &[Relabeler]) -> bool {
+    relabelers.iter().all(|r| r.apply(labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn relabeler(yaml_like: serde_json::Value) -> Relabeler {
+        Relabeler::new(&serde_json::from_value(yaml_like).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_relabel_actions() {
+        let mut l = labels(&[("__address__", "host:9100"), ("env", "Prod")]);
+        let rules = vec![
+            relabeler(serde_json::json!({
+                "source_labels": ["__address__"],
+                "regex": "([^:]+):.*",
+                "target_label": "host",
+            })),
+            relabeler(serde_json::json!({
+                "source_labels": ["env"],
+                "target_label": "env",
+                "action": "lowercase",
+            })),
+            relabeler(serde_json::json!({
+                "source_labels": ["host"],
+                "target_label": "shard",
+                "modulus": 4,
+                "action": "hashmod",
+            })),
+        ];
+        assert!(relabel(&mut l, &rules));
+        assert_eq!(l.get("host").map(String::as_str), Some("host"));
+        assert_eq!(l.get("env").map(String::as_str), Some("prod"));
+        assert!(l.get("shard").unwrap().parse::<u64>().unwrap() < 4);
+
+        let keep = relabeler(serde_json::json!({
+            "source_labels": ["env"],
+            "regex": "prod|staging",
+            "action": "keep",
+        }));
+        assert!(keep.apply(&mut l.clone()));
+        let drop = relabeler(serde_json::json!({
+            "source_labels": ["env"],
+            "regex": "prod",
+            "action": "drop",
+        }));
+        assert!(!drop.apply(&mut l.clone()));
+        // the regex is anchored
+        let drop = relabeler(serde_json::json!({
+            "source_labels": ["env"],
+            "regex": "pro",
+            "action": "drop",
+        }));
+        assert!(drop.apply(&mut l.clone()));
+
+        let mut m = labels(&[("__meta_team", "web"), ("job", "node")]);
+        let map = relabeler(serde_json::json!({
+            "regex": "__meta_(.+)",
+            "action": "labelmap",
+        }));
+        assert!(map.apply(&mut m));
+        assert_eq!(m.get("team").map(String::as_str), Some("web"));
+        let drop = relabeler(serde_json::json!({
+            "regex": "__meta_.+",
+            "action": "labeldrop",
+        }));
+        assert!(drop.apply(&mut m));
+        assert_eq!(m, labels(&[("job", "node"), ("team", "web")]));
+
+        // an empty replacement removes the target label
+        let remove = relabeler(serde_json::json!({
+            "target_label": "team",
+            "replacement": "",
+        }));
+        assert!(remove.apply(&mut m));
+        assert_eq!(m, labels(&[("job", "node")]));
+    }
+
+    #[test]
+    fn test_relabeler_validation() {
+        let cfg: RelabelConfig = serde_json::from_value(serde_json::json!({
+            "source_labels": ["a"],
+            "action": "hashmod",
+            "target_label": "b",
+        }))
+        .unwrap();
+        assert!(Relabeler::new(&cfg).is_err());
+        let cfg: RelabelConfig = serde_json::from_value(serde_json::json!({"regex": "("})).unwrap();
+        assert!(Relabeler::new(&cfg).is_err());
+    }
+
+    #[test]
+    fn test_scrape_config_targets() {
+        let cfg: ScrapeConfig = serde_json::from_value(serde_json::json!({
+            "job_name": "node",
+            "scrape_interval": "15s",
+            "params": {"module": ["http_2xx"]},
+            "static_configs": [
+                {"targets": ["a:9100", "b:9100"], "labels": {"env": "prod"}}
+            ],
+            "relabel_configs": [
+                {"source_labels": ["__address__"], "regex": "b:.*", "action": "drop"}
+            ]
+        }))
+        .unwrap();
+        assert!(cfg.validate().is_ok());
+        assert_eq!(cfg.interval_seconds(), 15);
+        assert_eq!(cfg.timeout_seconds(), 10);
+
+        let targets = cfg.targets(&cfg.static_configs).unwrap();
+        assert_eq!(
+            targets,
+            vec![ScrapeTarget {
+                url: "http://a:9100/metrics?module=http_2xx".to_string(),
+                labels: labels(&[("env", "prod"), ("instance", "a:9100"), ("job", "node")]),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_scrape_config_validate() {
+        let mut cfg: ScrapeConfig = serde_json::from_value(serde_json::json!({
+            "job_name": "node",
+            "scrape_interval": "5s",
+            "scrape_timeout": "30s",
+            "static_configs": [{"targets": ["a:9100"]}]
+        }))
+        .unwrap();
+        assert!(cfg.validate().is_ok());
+        assert_eq!(cfg.metrics_path, "/metrics");
+        assert_eq!(cfg.scheme, "http");
+        assert!(cfg.honor_timestamps);
+        assert_eq!(cfg.timeout_seconds(), 5);
+
+        cfg.body_size_limit = Some("10XB".to_string());
+        assert!(cfg.validate().is_err());
+        cfg.body_size_limit = None;
+        cfg.scheme = "ftp".to_string();
+        assert!(cfg.validate().is_err());
+        cfg.scheme = "https".to_string();
+        cfg.static_configs.clear();
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn test_body_size_limit() {
+        assert_eq!(parse_bytes("512"), Ok(512));
+        assert_eq!(parse_bytes("10KB"), Ok(10 * 1024));
+        assert_eq!(parse_bytes("1 MiB"), Ok(1024 * 1024));
+        assert_eq!(parse_bytes("2gb"), Ok(2 << 30));
+        assert!(parse_bytes("MB").is_err());
+        assert!(parse_bytes("1.5MB").is_err());
+
+        let mut cfg: ScrapeConfig = serde_json::from_value(serde_json::json!({
+            "job_name": "node",
+            "static_configs": [{"targets": ["a:9100"]}]
+        }))
+        .unwrap();
+        assert_eq!(cfg.body_size_limit_bytes(1024), Some(1024));
+        assert_eq!(cfg.body_size_limit_bytes(0), None);
+        cfg.body_size_limit = Some("1KB".to_string());
+        assert_eq!(cfg.body_size_limit_bytes(0), Some(1024));
+        cfg.body_size_limit = Some("0".to_string());
+        assert_eq!(cfg.body_size_limit_bytes(1024), None);
+    }
+}
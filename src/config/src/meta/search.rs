@@ -580,6 +580,10 @@ pub struct SearchPartitionRequest {
     pub sampling_ratio: Option<f64>,
     #[serde(default)]
     pub search_type: Option<SearchEventType>,
+    /// Timezone of the histogram buckets, partitions of calendar histograms are
+    /// aligned to the buckets in this timezone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
 
 impl SearchPartitionRequest {
@@ -615,6 +619,115 @@ impl From<&Request> for SearchPartitionRequest {
             histogram_interval: req.query.histogram_interval,
             sampling_ratio: req.query.sampling_ratio,
             search_type: req.search_type,
+            timezone: req.query.timezone.clone(),
+        }
+    }
+}
+
+/// Output format of the export API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    #[default]
+    Ndjson,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+impl TryFrom<&str> for ExportFormat {
+    type Error = String;
+    fn try_from(s: &str) -> std::result::Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" | "ndjson" | "jsonl" => Ok(Self::Ndjson),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(format!("Invalid export format: {s}")),
+        }
+    }
+}
+
+/// Request of the streaming export API, the whole result of the query is
+/// returned, partition by partition.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ExportRequest {
+    pub sql: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(default)]
+    pub encoding: RequestEncoding,
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Resume an interrupted export from this [`ExportCursor`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl ExportRequest {
+    #[inline]
+    pub fn decode(&mut self) -> Result<(), std::io::Error> {
+        if self.encoding == RequestEncoding::Base64 {
+            self.sql = base64::decode_url(&self.sql)?;
+        }
+        self.encoding = RequestEncoding::Empty;
+        Ok(())
+    }
+}
+
+/// Position in an export, which is ordered by `_timestamp`: every row before
+/// `timestamp` (after it, for descending exports) and the first `skip` rows
+/// at `timestamp` were exported. It is written as `<timestamp>:<skip>`, so a
+/// client can also build it from the last rows it received.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExportCursor {
+    pub timestamp: i64,
+    pub skip: i64,
+}
+
+impl ExportCursor {
+    /// Moves the cursor past a row with the given timestamp.
+    pub fn advance(&mut self, timestamp: i64) {
+        if timestamp == self.timestamp {
+            self.skip += 1;
+        } else {
+            self.timestamp = timestamp;
+            self.skip = 1;
+        }
+    }
+}
+
+impl std::fmt::Display for ExportCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.timestamp, self.skip)
+    }
+}
+
+impl std::str::FromStr for ExportCursor {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (timestamp, skip) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid export cursor: {s}"))?;
+        match (timestamp.trim().parse(), skip.trim().parse()) {
+            (Ok(timestamp), Ok(skip)) if skip >= 0 => Ok(Self { timestamp, skip }),
+            _ => Err(format!("Invalid export cursor: {s}")),
         }
     }
 }
@@ -863,6 +976,46 @@ pub struct CancelQueryResponse {
     pub is_success: bool,
 }
 
+/// Limits on how many bytes may be scanned by searches. `0` means unlimited.
+///
+/// Stored as the `scan_budget` / `scan_budget_roles` settings; see
+/// `service::search::scan_budget` for how they are resolved and enforced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct ScanBudget {
+    /// Bytes a single query may scan
+    pub max_bytes_per_query: u64,
+    /// Bytes that may be scanned within the current clock hour
+    pub max_bytes_per_hour: u64,
+    /// Bytes that may be scanned within the last 24 hours
+    pub max_bytes_per_day: u64,
+}
+
+impl ScanBudget {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_bytes_per_query == 0 && self.max_bytes_per_hour == 0 && self.max_bytes_per_day == 0
+    }
+}
+
+/// Estimated cost of a search, returned by a dry run instead of its results.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SearchEstimateResponse {
+    pub trace_id: String,
+    pub took: usize,
+    /// The files the search would read after partition key, bloom filter and
+    /// inverted index pruning, sizes are in bytes
+    pub scan_stats: ScanStats,
+    /// Files in the time range before pruning
+    pub file_list_files: i64,
+    pub row_groups: i64,
+    /// Nodes the search would be distributed to
+    pub nodes: Vec<String>,
+    /// Whether the results cache covers the whole time range
+    pub result_cache_hit: bool,
+    /// Percentage of the time range covered by the results cache
+    pub result_cache_ratio: usize,
+}
+
 #[derive(Clone, Debug, Copy, Default, Serialize, Deserialize, ToSchema)]
 pub struct ScanStats {
     pub files: i64,
@@ -2210,6 +2363,7 @@ mod tests {
             histogram_interval: 0,
             sampling_ratio: None,
             search_type: None,
+            timezone: None,
         };
 
         req.decode().unwrap();
@@ -3757,4 +3911,45 @@ mod tests {
         let req: MultiStreamRequest = serde_json::from_str(json).unwrap();
         assert!(req.sql.is_empty());
     }
+
+    #[test]
+    fn test_export_format() {
+        assert_eq!(ExportFormat::try_from("CSV").unwrap(), ExportFormat::Csv);
+        assert_eq!(
+            ExportFormat::try_from("json").unwrap(),
+            ExportFormat::Ndjson
+        );
+        assert_eq!(
+            ExportFormat::try_from("parquet").unwrap(),
+            ExportFormat::Parquet
+        );
+        assert!(ExportFormat::try_from("xlsx").is_err());
+
+        let req: ExportRequest = serde_json::from_str(
+            r#"{"sql":"select * from logs","start_time":0,"end_time":100,"format":"csv"}"#,
+        )
+        .unwrap();
+        assert_eq!(req.format, ExportFormat::Csv);
+        assert!(req.cursor.is_none());
+    }
+
+    #[test]
+    fn test_export_cursor() {
+        let mut cursor: ExportCursor = "1700000000000000:2".parse().unwrap();
+        assert_eq!(
+            cursor,
+            ExportCursor {
+                timestamp: 1700000000000000,
+                skip: 2
+            }
+        );
+        cursor.advance(1700000000000000);
+        assert_eq!(cursor.to_string(), "1700000000000000:3");
+        cursor.advance(1700000000000001);
+        assert_eq!(cursor.to_string(), "1700000000000001:1");
+
+        assert!("1700000000000000".parse::<ExportCursor>().is_err());
+        assert!("abc:1".parse::<ExportCursor>().is_err());
+        assert!("1:-1".parse::<ExportCursor>().is_err());
+    }
 }
@@ -380,6 +380,8 @@ mod tests {
             dedup_count: None,
             grouped: None,
             group_size: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         let result = queue
@@ -914,6 +916,8 @@ mod tests {
             dedup_count: None,
             grouped: None,
             group_size: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         let error_data = error::ErrorData {
@@ -982,6 +986,8 @@ mod tests {
             dedup_count: None,
             grouped: None,
             group_size: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         let trigger_data2 = TriggerData {
@@ -1040,6 +1046,8 @@ mod tests {
             dedup_count: None,
             grouped: None,
             group_size: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         // Should succeed when queue has space
@@ -40,6 +40,10 @@ pub enum TriggerDataStatus {
     ConditionNotSatisfied,
     #[serde(rename = "skipped")]
     Skipped,
+    #[serde(rename = "resolved")]
+    Resolved,
+    #[serde(rename = "silenced")]
+    Silenced,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -96,6 +100,12 @@ pub struct TriggerData {
     pub grouped: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_size: Option<i32>,
+    /// Fingerprint of the alert instance, set for resolved alert instances
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    /// Id of the silence that suppressed the notification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub silence_id: Option<String>,
 }
 
 impl Default for TriggerData {
@@ -127,6 +137,8 @@ impl Default for TriggerData {
             dedup_count: None,
             grouped: None,
             group_size: None,
+            fingerprint: None,
+            silence_id: None,
         }
     }
 }
@@ -169,6 +181,8 @@ impl TriggerData {
             dedup_count: Some(0),
             grouped: Some(false),
             group_size: Some(0),
+            fingerprint: Some(String::new()),
+            silence_id: Some(String::new()),
         }
     }
 
@@ -441,6 +455,8 @@ pub enum UsageType {
     NewIncident,
     #[serde(rename = "incident_reanalysis")]
     IncidentReAnalysis,
+    #[serde(rename = "scan_usage")]
+    ScanUsage,
 }
 
 impl UsageType {
@@ -522,6 +538,7 @@ impl std::fmt::Display for UsageType {
             UsageType::EnrichmentTable => write!(f, "enrichment_table"),
             UsageType::NewIncident => write!(f, "new_incident"),
             UsageType::IncidentReAnalysis => write!(f, "incident_reanalysis"),
+            UsageType::ScanUsage => write!(f, "scan_usage"),
         }
     }
 }
@@ -769,6 +786,14 @@ mod tests {
             serde_json::to_string(&TriggerDataStatus::Skipped).unwrap(),
             "\"skipped\""
         );
+        assert_eq!(
+            serde_json::to_string(&TriggerDataStatus::Resolved).unwrap(),
+            "\"resolved\""
+        );
+        assert_eq!(
+            serde_json::to_string(&TriggerDataStatus::Silenced).unwrap(),
+            "\"silenced\""
+        );
     }
 
     #[test]
@@ -789,6 +814,14 @@ mod tests {
             serde_json::from_str::<TriggerDataStatus>("\"skipped\"").unwrap(),
             TriggerDataStatus::Skipped
         );
+        assert_eq!(
+            serde_json::from_str::<TriggerDataStatus>("\"resolved\"").unwrap(),
+            TriggerDataStatus::Resolved
+        );
+        assert_eq!(
+            serde_json::from_str::<TriggerDataStatus>("\"silenced\"").unwrap(),
+            TriggerDataStatus::Silenced
+        );
     }
 
     #[test]
@@ -860,6 +893,8 @@ mod tests {
             dedup_count: None,
             grouped: None,
             group_size: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         let json = serde_json::to_string(&trigger_data).unwrap();
@@ -1318,6 +1353,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_usage_type_scan_usage_is_not_search() {
+        // scan accounting must not be billed a second time as a search request
+        assert!(!UsageType::ScanUsage.is_search());
+        assert_eq!(
+            UsageEvent::from(UsageType::ScanUsage).to_string(),
+            UsageEvent::Other.to_string()
+        );
+        assert_eq!(UsageType::ScanUsage.to_string(), "scan_usage");
+    }
+
     #[test]
     fn test_usage_type_is_pipeline() {
         assert!(UsageType::Pipeline.is_pipeline());
@@ -937,6 +937,62 @@ impl TimeRange {
     }
 }
 
+/// A delete-by-predicate job: removes the rows matching `condition` within
+/// `[start, end)` by rewriting the affected files.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct PredicateDeleteJob {
+    pub id: String,
+    pub org_id: String,
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    /// SQL WHERE clause selecting the rows to delete
+    pub condition: String,
+    /// Start timestamp in microseconds
+    pub start: i64,
+    /// End timestamp in microseconds
+    pub end: i64,
+    pub created_at: i64,
+    #[serde(default)]
+    pub ended_at: i64,
+    #[serde(default)]
+    pub progress: PredicateDeleteProgress,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct PredicateDeleteProgress {
+    /// Number of passes over the candidate files so far
+    pub passes: i64,
+    /// Candidate files left after bloom filter and inverted index pruning
+    pub scanned_files: i64,
+    /// Files rewritten without the matching rows
+    pub rewritten_files: i64,
+    /// Files dropped because every row matched
+    pub removed_files: i64,
+    pub deleted_records: i64,
+}
+
+impl PredicateDeleteJob {
+    /// Key of the job in the `compactor_manual_jobs` table, prefixed like the
+    /// time range delete jobs so org ownership checks apply to both.
+    pub fn manual_job_key(&self) -> String {
+        format!(
+            "{}/{}/{}/predicate/{}",
+            self.org_id, self.stream_type, self.stream_name, self.id
+        )
+    }
+
+    /// The condition actually applied to each row, bounded by the job time range.
+    pub fn delete_condition(&self) -> String {
+        let ts = crate::TIMESTAMP_COL_NAME;
+        format!(
+            "({}) AND {ts} >= {} AND {ts} < {}",
+            self.condition, self.start, self.end
+        )
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, ToSchema, PartialEq)]
 pub struct StreamSettings {
     #[serde(default)]
@@ -2396,6 +2452,34 @@ mod tests {
         assert!(m.is_empty());
     }
 
+    #[test]
+    fn test_predicate_delete_job_keys() {
+        let job = PredicateDeleteJob {
+            id: "job1".to_string(),
+            org_id: "org".to_string(),
+            stream_type: StreamType::Logs,
+            stream_name: "app".to_string(),
+            condition: "user_id = 'x' OR email = 'x@y.z'".to_string(),
+            start: 100,
+            end: 200,
+            created_at: 1,
+            ended_at: 0,
+            progress: PredicateDeleteProgress::default(),
+            error: None,
+        };
+        assert_eq!(job.manual_job_key(), "org/logs/app/predicate/job1");
+        assert_eq!(
+            job.delete_condition(),
+            "(user_id = 'x' OR email = 'x@y.z') AND _timestamp >= 100 AND _timestamp < 200"
+        );
+
+        // progress and error are optional in stored jobs
+        let stored = r#"{"id":"job1","org_id":"org","stream_type":"logs","stream_name":"app","condition":"a = 1","start":1,"end":2,"created_at":1}"#;
+        let parsed: PredicateDeleteJob = json::from_str(stored).unwrap();
+        assert_eq!(parsed.progress, PredicateDeleteProgress::default());
+        assert!(parsed.error.is_none());
+    }
+
     #[test]
     fn test_kv_metadata_to_file_meta_leaves_bloom_ver_zero() {
         // Parquet KV metadata never carries bloom_ver — it must default to 0.
@@ -159,6 +159,10 @@ pub mod keys {
     pub const LIGHT_MODE_THEME_COLOR: &str = "light_mode_theme_color";
     /// Dark mode theme color
     pub const DARK_MODE_THEME_COLOR: &str = "dark_mode_theme_color";
+    /// Query scan budget, org-wide at org/system scope and per user at user scope
+    pub const SCAN_BUDGET: &str = "scan_budget";
+    /// Per-user query scan budgets keyed by role name
+    pub const SCAN_BUDGET_ROLES: &str = "scan_budget_roles";
 }
 
 /// A system setting record
@@ -13,9 +13,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-use crate::{stats::MemorySize, utils::json};
+use crate::{meta::alerts::ALERT_SEVERITY_FIELD, stats::MemorySize, utils::json};
 
 #[derive(Debug, Clone, sqlx::Type, PartialEq, Serialize, Deserialize, Default)]
 #[repr(i32)]
@@ -38,6 +40,9 @@ pub enum TriggerModule {
     QueryRecommendations,
     Backfill,
     AnomalyDetection,
+    RecordingRule,
+    Scrape,
+    WebhookRetry,
 }
 
 impl std::fmt::Display for TriggerModule {
@@ -49,6 +54,9 @@ impl std::fmt::Display for TriggerModule {
             Self::QueryRecommendations => write!(f, "query_recommendations"),
             Self::Backfill => write!(f, "backfill"),
             Self::AnomalyDetection => write!(f, "anomaly_detection"),
+            Self::RecordingRule => write!(f, "recording_rule"),
+            Self::Scrape => write!(f, "scrape"),
+            Self::WebhookRetry => write!(f, "webhook_retry"),
         }
     }
 }
@@ -99,6 +107,81 @@ pub struct ScheduledTriggerData {
     pub last_satisfied_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub backfill_job: Option<BackfillJob>,
+    /// Alert instances that are firing, keyed by the fingerprint of the
+    /// instance
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub firing: HashMap<String, FiringAlertInstance>,
+    /// Since when the condition of an alert with a pending duration holds, in
+    /// microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_since: Option<i64>,
+}
+
+/// Maximum number of firing alert instances tracked per alert, further
+/// instances are not resolved individually
+pub const MAX_FIRING_ALERT_INSTANCES: usize = 1000;
+
+/// Maximum number of labels kept per firing alert instance
+const MAX_ALERT_INSTANCE_LABELS: usize = 32;
+
+/// Maximum length of a label value kept per firing alert instance
+const MAX_ALERT_INSTANCE_LABEL_LEN: usize = 256;
+
+/// State of an alert instance that is firing
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FiringAlertInstance {
+    /// When the instance started firing, in microseconds
+    pub fired_at: i64,
+    /// When the instance last fired, in microseconds
+    pub last_fired_at: i64,
+    /// The labels identifying the instance, used for the resolve notification
+    #[serde(default)]
+    pub labels: json::Map<String, json::Value>,
+}
+
+/// An alert instance that was firing and no longer matches the condition
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolvedAlertInstance {
+    pub fingerprint: String,
+    /// When the instance started firing, in microseconds
+    pub fired_at: i64,
+    /// When the instance was found to be resolved, in microseconds
+    pub resolved_at: i64,
+    /// The labels identifying the instance
+    pub labels: json::Map<String, json::Value>,
+}
+
+impl ResolvedAlertInstance {
+    /// How long the instance was firing, in seconds
+    pub fn duration_secs(&self) -> i64 {
+        (self.resolved_at - self.fired_at).max(0) / 1_000_000
+    }
+}
+
+/// Returns the labels of a result row that identify its alert instance: the
+/// given fingerprint fields, or the short string fields of the row when there
+/// are none. The triggered severity is always kept.
+pub fn alert_instance_labels(
+    row: &json::Map<String, json::Value>,
+    fingerprint_fields: &[String],
+) -> json::Map<String, json::Value> {
+    let mut labels = json::Map::new();
+    for (key, value) in row {
+        let keep = if fingerprint_fields.is_empty() {
+            value
+                .as_str()
+                .is_some_and(|v| v.len() <= MAX_ALERT_INSTANCE_LABEL_LEN)
+        } else {
+            key == ALERT_SEVERITY_FIELD || fingerprint_fields.contains(key)
+        };
+        if keep && labels.len() < MAX_ALERT_INSTANCE_LABELS {
+            labels.insert(key.clone(), value.clone());
+        }
+    }
+    if let Some(severity) = row.get(ALERT_SEVERITY_FIELD) {
+        labels.insert(ALERT_SEVERITY_FIELD.to_string(), severity.clone());
+    }
+    labels
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -138,6 +221,62 @@ impl ScheduledTriggerData {
         self.tolerance = 0;
     }
 
+    /// Tracks how long the condition of an alert holds, like the pending state
+    /// of a Prometheus alerting rule. Returns whether the alert may fire at
+    /// `now`, i.e. the condition held in consecutive evaluations for
+    /// `pending_secs`.
+    pub fn update_pending(&mut self, satisfied: bool, pending_secs: i64, now: i64) -> bool {
+        if !satisfied {
+            self.pending_since = None;
+            return false;
+        }
+        let since = *self.pending_since.get_or_insert(now);
+        now - since >= pending_secs * 1_000_000
+    }
+
+    /// Records the alert instances, with their labels, that fired in an
+    /// evaluation at `now`, returns the instances that were firing and did not
+    /// fire this time. At most [`MAX_FIRING_ALERT_INSTANCES`] are tracked, the
+    /// ones already firing first.
+    pub fn update_firing(
+        &mut self,
+        fired: HashMap<String, json::Map<String, json::Value>>,
+        now: i64,
+    ) -> Vec<ResolvedAlertInstance> {
+        let mut previous = std::mem::take(&mut self.firing);
+        let mut fired = fired.into_iter().collect::<Vec<_>>();
+        fired.sort_by(|(a, _), (b, _)| {
+            (!previous.contains_key(a), a).cmp(&(!previous.contains_key(b), b))
+        });
+        for (fingerprint, labels) in fired {
+            if self.firing.len() >= MAX_FIRING_ALERT_INSTANCES {
+                break;
+            }
+            let fired_at = previous
+                .remove(&fingerprint)
+                .map_or(now, |instance| instance.fired_at);
+            self.firing.insert(
+                fingerprint,
+                FiringAlertInstance {
+                    fired_at,
+                    last_fired_at: now,
+                    labels,
+                },
+            );
+        }
+        let mut resolved = previous
+            .into_iter()
+            .map(|(fingerprint, instance)| ResolvedAlertInstance {
+                fingerprint,
+                fired_at: instance.fired_at,
+                resolved_at: now,
+                labels: instance.labels,
+            })
+            .collect::<Vec<_>>();
+        resolved.sort_by(|a, b| a.fingerprint.cmp(&b.fingerprint));
+        resolved
+    }
+
     pub fn to_json_string(&self) -> String {
         json::to_string(self).unwrap()
     }
@@ -165,6 +304,9 @@ mod tests {
             TriggerModule::AnomalyDetection.to_string(),
             "anomaly_detection"
         );
+        assert_eq!(TriggerModule::RecordingRule.to_string(), "recording_rule");
+        assert_eq!(TriggerModule::Scrape.to_string(), "scrape");
+        assert_eq!(TriggerModule::WebhookRetry.to_string(), "webhook_retry");
     }
 
     #[test]
@@ -174,6 +316,8 @@ mod tests {
             tolerance: 42,
             last_satisfied_at: Some(999),
             backfill_job: None,
+            firing: HashMap::new(),
+            pending_since: None,
         };
         data.reset();
         assert!(data.period_end_time.is_none());
@@ -197,6 +341,8 @@ mod tests {
             tolerance: 10,
             last_satisfied_at: Some(9_999_999),
             backfill_job: None,
+            firing: HashMap::new(),
+            pending_since: None,
         };
         let json = data.to_json_string();
         let restored = ScheduledTriggerData::from_json_string(&json).unwrap();
@@ -363,6 +509,8 @@ mod tests {
                 deletion_job_ids: vec!["j1".to_string()],
                 error: None,
             }),
+            firing: HashMap::new(),
+            pending_since: None,
         };
         let json = data.to_json_string();
         let restored = ScheduledTriggerData::from_json_string(&json).unwrap();
@@ -371,4 +519,104 @@ mod tests {
         assert_eq!(bj.deletion_status, DeletionStatus::Pending);
         assert_eq!(bj.deletion_job_ids.len(), 1);
     }
+
+    #[test]
+    fn test_scheduled_trigger_data_update_pending() {
+        let mut data = ScheduledTriggerData::default();
+        assert!(!data.update_pending(true, 60, 0));
+        assert!(!data.update_pending(true, 60, 30_000_000));
+        assert!(data.update_pending(true, 60, 60_000_000));
+        assert_eq!(data.pending_since, Some(0));
+        // an evaluation not satisfying the condition starts over
+        assert!(!data.update_pending(false, 60, 90_000_000));
+        assert_eq!(data.pending_since, None);
+        assert!(!data.update_pending(true, 60, 120_000_000));
+        assert!(data.update_pending(true, 0, 120_000_000));
+    }
+
+    #[test]
+    fn test_scheduled_trigger_data_update_firing() {
+        let row = |host: &str| {
+            let mut row = json::Map::new();
+            row.insert("host".to_string(), json::Value::from(host));
+            row
+        };
+        let mut data = ScheduledTriggerData::default();
+
+        let resolved = data.update_firing(
+            HashMap::from([("a".to_string(), row("a")), ("b".to_string(), row("b"))]),
+            1_000_000,
+        );
+        assert!(resolved.is_empty());
+        assert_eq!(data.firing.len(), 2);
+
+        // `a` keeps firing, `b` resolves
+        let resolved = data.update_firing(HashMap::from([("a".to_string(), row("a"))]), 61_000_000);
+        assert_eq!(
+            resolved,
+            vec![ResolvedAlertInstance {
+                fingerprint: "b".to_string(),
+                fired_at: 1_000_000,
+                resolved_at: 61_000_000,
+                labels: row("b"),
+            }]
+        );
+        assert_eq!(resolved[0].duration_secs(), 60);
+        assert_eq!(data.firing["a"].fired_at, 1_000_000);
+        assert_eq!(data.firing["a"].last_fired_at, 61_000_000);
+
+        // the state survives a reset and a round trip
+        data.reset();
+        let mut data = ScheduledTriggerData::from_json_string(&data.to_json_string()).unwrap();
+        let resolved = data.update_firing(HashMap::new(), 121_000_000);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].fingerprint, "a");
+        assert_eq!(resolved[0].duration_secs(), 120);
+        assert!(data.firing.is_empty());
+        assert!(!data.to_json_string().contains("firing"));
+    }
+
+    #[test]
+    fn test_scheduled_trigger_data_update_firing_capped() {
+        let mut data = ScheduledTriggerData::default();
+        let fired = |range: std::ops::Range<usize>| {
+            range
+                .map(|i| (format!("{i:05}"), json::Map::new()))
+                .collect::<HashMap<_, _>>()
+        };
+        data.update_firing(fired(0..10), 1_000_000);
+        // the instances already firing are kept over the new ones
+        let resolved = data.update_firing(fired(5..MAX_FIRING_ALERT_INSTANCES + 100), 2_000_000);
+        assert_eq!(resolved.len(), 5);
+        assert_eq!(data.firing.len(), MAX_FIRING_ALERT_INSTANCES);
+        assert_eq!(data.firing["00005"].fired_at, 1_000_000);
+        assert!(
+            !data
+                .firing
+                .contains_key(&format!("{:05}", MAX_FIRING_ALERT_INSTANCES + 99))
+        );
+    }
+
+    #[test]
+    fn test_alert_instance_labels() {
+        let row = json::json!({
+            "host": "a",
+            "count": 10,
+            "message": "x".repeat(MAX_ALERT_INSTANCE_LABEL_LEN + 1),
+            ALERT_SEVERITY_FIELD: "P1",
+        });
+        let row = row.as_object().unwrap();
+
+        let labels = alert_instance_labels(row, &[]);
+        assert_eq!(
+            json::Value::Object(labels),
+            json::json!({"host": "a", ALERT_SEVERITY_FIELD: "P1"})
+        );
+
+        let labels = alert_instance_labels(row, &["count".to_string()]);
+        assert_eq!(
+            json::Value::Object(labels),
+            json::json!({"count": 10, ALERT_SEVERITY_FIELD: "P1"})
+        );
+    }
 }
@@ -447,6 +447,8 @@ fn convert_to_destination(config: PrebuiltDestinationConfig) -> Result<Destinati
                     output_format: Some(output_format),
                     destination_type: Some(endpoint_config.destination_type),
                     metadata: endpoint_metadata,
+                    retry: None,
+                    signing_secret: None,
                 }),
             }
         }
@@ -521,6 +523,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Slack webhook for team notifications".to_string(),
                         ),
                     ]),
+                    retry: None,
+                    signing_secret: None,
                 }),
             },
         },
@@ -549,6 +553,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Microsoft Teams webhook for team notifications".to_string(),
                         ),
                     ]),
+                    retry: None,
+                    signing_secret: None,
                 }),
             },
         },
@@ -579,6 +585,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "PagerDuty incident management".to_string(),
                         ),
                     ]),
+                    retry: None,
+                    signing_secret: None,
                 }),
             },
         },
@@ -606,6 +614,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Discord webhook for community notifications".to_string(),
                         ),
                     ]),
+                    retry: None,
+                    signing_secret: None,
                 }),
             },
         },
@@ -633,6 +643,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Generic webhook destination".to_string(),
                         ),
                     ]),
+                    retry: None,
+                    signing_secret: None,
                 }),
             },
         },
@@ -663,6 +675,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Opsgenie incident management and alerting".to_string(),
                         ),
                     ]),
+                    retry: None,
+                    signing_secret: None,
                 }),
             },
         },
@@ -693,6 +707,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "ServiceNow incident management".to_string(),
                         ),
                     ]),
+                    retry: None,
+                    signing_secret: None,
                 }),
             },
         },
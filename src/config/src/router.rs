@@ -14,7 +14,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 /// usize indicates the number of parts to skip based on their actual paths.
-const QUERIER_ROUTES: [(&str, usize); 30] = [
+const QUERIER_ROUTES: [(&str, usize); 31] = [
     ("config", 0),               // /config
     ("summary", 2),              // /api/{org_id}/summary
     ("organizations", 1),        // /api/organizations
@@ -31,6 +31,7 @@ const QUERIER_ROUTES: [(&str, usize); 30] = [
     ("_search", 2),              // /api/{org_id}/_search
     ("_search_stream", 2),       // /api/{org_id}/_search_stream
     ("_values_stream", 2),       // /api/{org_id}/_values_stream
+    ("_export", 2),              // /api/{org_id}/_export
     ("_around", 3),              // /api/{org_id}/{stream_name}/_around
     ("_values", 3),              // /api/{org_id}/{stream_name}/_values
     ("patterns/extract", 3),     /* /api/{org_id}/streams/{stream_name}/patterns/
@@ -49,11 +50,12 @@ const QUERIER_ROUTES: [(&str, usize); 30] = [
     ("service_streams", 2), // /api/{org_id}/service_streams/...
     ("node/list", 2),       // /api/_meta/node/list
 ];
-const QUERIER_ROUTES_BY_BODY: [&str; 9] = [
+const QUERIER_ROUTES_BY_BODY: [&str; 10] = [
     "/_search",
     "/_search_partition",
     "/_search_stream",
     "/_values_stream",
+    "/_export",
     "/_search_multi_stream",
     "/_search_partition_multi",
     "/_search_multi",
@@ -61,7 +63,7 @@ const QUERIER_ROUTES_BY_BODY: [&str; 9] = [
     "/prometheus/api/v1/query_exemplars",
 ];
 const FIXED_QUERIER_ROUTES: [&str; 3] = ["/summary", "/schema", "/streams"];
-pub const INGESTER_ROUTES: [&str; 12] = [
+pub const INGESTER_ROUTES: [&str; 13] = [
     "/_json",
     "/_bulk",
     "/_multi",
@@ -74,6 +76,7 @@ pub const INGESTER_ROUTES: [&str; 12] = [
     "/v1/metrics",
     "/traces",
     "/v1/traces",
+    "/v2/spans",
 ];
 
 #[inline]
@@ -170,6 +173,7 @@ mod tests {
         assert!(is_querier_route_by_body("/_search_stream?foo=bar"));
         assert!(is_querier_route_by_body("/_values_stream"));
         assert!(is_querier_route_by_body("/_values_stream?foo=bar"));
+        assert!(is_querier_route_by_body("/_export?type=logs"));
         assert!(is_querier_route_by_body("/prometheus/api/v1/query_range"));
         assert!(is_querier_route_by_body(
             "/prometheus/api/v1/query_exemplars"
@@ -0,0 +1,389 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Calendar histogram buckets.
+//!
+//! Week, month, quarter and year buckets have no fixed length: a bucket starts
+//! at local midnight of the first day of its period in the bucket timezone, so
+//! its length follows the month lengths and the DST transitions of the zone.
+//! Bucket counts greater than one are aligned to `2001-01-01`, the origin of the
+//! fixed histogram intervals, e.g. `3 month` buckets start in January, April,
+//! July and October and weeks start on Monday.
+
+use std::fmt;
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Months, NaiveDate, NaiveDateTime, TimeZone,
+};
+use chrono_tz::Tz;
+
+use super::time::parse_timezone_to_offset_opt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarUnit {
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl CalendarUnit {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CalendarUnit::Week => "week",
+            CalendarUnit::Month => "month",
+            CalendarUnit::Quarter => "quarter",
+            CalendarUnit::Year => "year",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarInterval {
+    pub count: u32,
+    pub unit: CalendarUnit,
+}
+
+impl CalendarInterval {
+    /// Parses an interval like `1 month` or `2 weeks`, returns `None` for the
+    /// fixed intervals.
+    pub fn parse(interval: &str) -> Option<Self> {
+        let interval = interval.trim();
+        let pos = interval.find(|c: char| !c.is_ascii_digit())?;
+        let (count, unit) = interval.split_at(pos);
+        let count = count.parse::<u32>().ok().filter(|v| *v > 0)?;
+        let unit = match unit.trim().to_lowercase().as_str() {
+            "week" | "weeks" | "w" => CalendarUnit::Week,
+            "month" | "months" | "mon" | "mons" => CalendarUnit::Month,
+            "quarter" | "quarters" | "q" => CalendarUnit::Quarter,
+            "year" | "years" | "y" | "yr" | "yrs" => CalendarUnit::Year,
+            _ => return None,
+        };
+        Some(Self { count, unit })
+    }
+
+    /// The nominal length, used where a fixed length is needed, e.g. to size
+    /// search partitions.
+    pub fn nominal_seconds(&self) -> i64 {
+        let days = match self.unit {
+            CalendarUnit::Week => 7,
+            CalendarUnit::Month => 30,
+            CalendarUnit::Quarter => 91,
+            CalendarUnit::Year => 365,
+        };
+        self.count as i64 * days * 24 * 3600
+    }
+
+    fn months(&self) -> Option<u32> {
+        match self.unit {
+            CalendarUnit::Week => None,
+            CalendarUnit::Month => Some(self.count),
+            CalendarUnit::Quarter => Some(self.count * 3),
+            CalendarUnit::Year => Some(self.count * 12),
+        }
+    }
+
+    /// Returns the first day of the bucket containing the date.
+    fn bucket_start(&self, date: NaiveDate) -> NaiveDate {
+        // 2001-01-01 is a Monday
+        let origin = NaiveDate::from_ymd_opt(2001, 1, 1).unwrap();
+        match self.months() {
+            None => {
+                let width = 7 * self.count as i64;
+                let days = (date - origin).num_days();
+                origin + Duration::days(days.div_euclid(width) * width)
+            }
+            Some(months) => {
+                let months = months as i64;
+                let index = (date.year() as i64 - 2001) * 12 + date.month0() as i64;
+                let index = index.div_euclid(months) * months;
+                NaiveDate::from_ymd_opt(
+                    2001 + index.div_euclid(12) as i32,
+                    index.rem_euclid(12) as u32 + 1,
+                    1,
+                )
+                .unwrap_or(date)
+            }
+        }
+    }
+
+    /// Returns the first day of the bucket following the one starting at `start`.
+    fn next_bucket_start(&self, start: NaiveDate) -> NaiveDate {
+        match self.months() {
+            None => start + Duration::days(7 * self.count as i64),
+            Some(months) => start
+                .checked_add_months(Months::new(months))
+                .unwrap_or(NaiveDate::MAX),
+        }
+    }
+}
+
+impl fmt::Display for CalendarInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.count, self.unit.as_str())
+    }
+}
+
+/// The timezone of calendar buckets, a fixed offset or an IANA timezone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalendarTimezone {
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl CalendarTimezone {
+    /// Parses a fixed offset like `+08:00` or an IANA name like
+    /// `America/New_York`, `None`, `""` and `"UTC"` are UTC.
+    pub fn parse(timezone: Option<&str>) -> Option<Self> {
+        let timezone = timezone.unwrap_or_default();
+        if let Some(offset) = parse_timezone_to_offset_opt(timezone) {
+            return FixedOffset::east_opt(offset as i32).map(Self::Fixed);
+        }
+        timezone.parse::<Tz>().ok().map(Self::Named)
+    }
+
+    fn to_local(self, micros: i64) -> NaiveDateTime {
+        let utc = DateTime::from_timestamp_micros(micros)
+            .unwrap_or_default()
+            .naive_utc();
+        match self {
+            Self::Fixed(offset) => offset.from_utc_datetime(&utc).naive_local(),
+            Self::Named(tz) => tz.from_utc_datetime(&utc).naive_local(),
+        }
+    }
+
+    /// Returns the instant of local midnight of the date. A few timezones skip
+    /// midnight on DST transitions, the day then starts at the end of the gap.
+    fn midnight(self, date: NaiveDate) -> i64 {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+        match self {
+            Self::Fixed(offset) => offset
+                .from_local_datetime(&midnight)
+                .single()
+                .map_or(i64::MAX, |t| t.timestamp_micros()),
+            Self::Named(tz) => (0..=8)
+                .find_map(|i| {
+                    tz.from_local_datetime(&(midnight + Duration::minutes(15 * i)))
+                        .earliest()
+                })
+                .map_or(i64::MAX, |t| t.timestamp_micros()),
+        }
+    }
+}
+
+/// Calendar buckets of a histogram in a timezone. Instants are in
+/// microseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalendarBuckets {
+    pub interval: CalendarInterval,
+    pub timezone: CalendarTimezone,
+}
+
+impl CalendarBuckets {
+    pub fn new(interval: CalendarInterval, timezone: CalendarTimezone) -> Self {
+        Self { interval, timezone }
+    }
+
+    fn local_bucket_start(&self, micros: i64) -> NaiveDate {
+        self.interval
+            .bucket_start(self.timezone.to_local(micros).date())
+    }
+
+    /// Returns the start of the bucket containing the instant.
+    pub fn floor(&self, micros: i64) -> i64 {
+        self.timezone.midnight(self.local_bucket_start(micros))
+    }
+
+    /// Returns the start of the bucket following the one containing the
+    /// instant.
+    pub fn next(&self, micros: i64) -> i64 {
+        let start = self.local_bucket_start(micros);
+        self.timezone
+            .midnight(self.interval.next_bucket_start(start))
+    }
+
+    /// Returns the instant if it starts a bucket, else the start of the next
+    /// bucket.
+    pub fn ceil(&self, micros: i64) -> i64 {
+        if self.is_aligned(micros) {
+            micros
+        } else {
+            self.next(micros)
+        }
+    }
+
+    pub fn is_aligned(&self, micros: i64) -> bool {
+        self.floor(micros) == micros
+    }
+
+    /// Returns the local wall-clock start of the bucket containing the instant,
+    /// the value `histogram()` returns for it.
+    pub fn floor_local(&self, micros: i64) -> i64 {
+        self.local_bucket_start(micros)
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_micros()
+    }
+
+    /// Returns the `[start, end)` instants of the bucket of a local wall-clock
+    /// value returned by `histogram()`, the inverse of [`Self::floor_local`].
+    pub fn local_bucket_range(&self, local_micros: i64) -> (i64, i64) {
+        let date = chrono::DateTime::from_timestamp_micros(local_micros)
+            .unwrap_or_default()
+            .date_naive();
+        let start = self.interval.bucket_start(date);
+        (
+            self.timezone.midnight(start),
+            self.timezone
+                .midnight(self.interval.next_bucket_start(start)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(s: &str) -> i64 {
+        DateTime::parse_from_rfc3339(s).unwrap().timestamp_micros()
+    }
+
+    fn buckets(interval: &str, timezone: &str) -> CalendarBuckets {
+        CalendarBuckets::new(
+            CalendarInterval::parse(interval).unwrap(),
+            CalendarTimezone::parse(Some(timezone)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_parse_calendar_interval() {
+        assert_eq!(
+            CalendarInterval::parse("1 month"),
+            Some(CalendarInterval {
+                count: 1,
+                unit: CalendarUnit::Month
+            })
+        );
+        assert_eq!(
+            CalendarInterval::parse("2weeks").unwrap().to_string(),
+            "2 week"
+        );
+        assert_eq!(
+            CalendarInterval::parse(" 1 Year ").unwrap().unit,
+            CalendarUnit::Year
+        );
+        assert!(CalendarInterval::parse("1 day").is_none());
+        assert!(CalendarInterval::parse("1 m").is_none());
+        assert!(CalendarInterval::parse("0 month").is_none());
+        assert!(CalendarInterval::parse("month").is_none());
+        assert_eq!(
+            CalendarInterval::parse("1 quarter")
+                .unwrap()
+                .nominal_seconds(),
+            91 * 86400
+        );
+    }
+
+    #[test]
+    fn test_parse_calendar_timezone() {
+        assert!(matches!(
+            CalendarTimezone::parse(None),
+            Some(CalendarTimezone::Fixed(v)) if v.local_minus_utc() == 0
+        ));
+        assert!(matches!(
+            CalendarTimezone::parse(Some("+05:30")),
+            Some(CalendarTimezone::Fixed(v)) if v.local_minus_utc() == 19800
+        ));
+        assert!(matches!(
+            CalendarTimezone::parse(Some("Europe/Berlin")),
+            Some(CalendarTimezone::Named(_))
+        ));
+        assert!(CalendarTimezone::parse(Some("Mars/Olympus")).is_none());
+    }
+
+    #[test]
+    fn test_month_buckets_with_dst() {
+        let b = buckets("1 month", "America/New_York");
+        // March starts in EST, April in EDT
+        let ts = micros("2025-03-20T12:00:00Z");
+        assert_eq!(b.floor(ts), micros("2025-03-01T05:00:00Z"));
+        assert_eq!(b.next(ts), micros("2025-04-01T04:00:00Z"));
+        assert_eq!(b.floor_local(ts), micros("2025-03-01T00:00:00Z"));
+        assert_eq!(
+            b.local_bucket_range(b.floor_local(ts)),
+            (
+                micros("2025-03-01T05:00:00Z"),
+                micros("2025-04-01T04:00:00Z")
+            )
+        );
+        // still February in New York
+        let ts = micros("2025-03-01T03:00:00Z");
+        assert_eq!(b.floor(ts), micros("2025-02-01T05:00:00Z"));
+        assert!(b.is_aligned(micros("2025-04-01T04:00:00Z")));
+        assert!(!b.is_aligned(micros("2025-04-01T00:00:00Z")));
+        assert_eq!(
+            b.ceil(micros("2025-04-01T00:00:00Z")),
+            micros("2025-04-01T04:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_week_quarter_year_buckets() {
+        let b = buckets("1 week", "UTC");
+        // 2025-07-30 is a Wednesday
+        assert_eq!(
+            b.floor(micros("2025-07-30T10:00:00Z")),
+            micros("2025-07-28T00:00:00Z")
+        );
+        assert_eq!(
+            b.next(micros("2025-07-30T10:00:00Z")),
+            micros("2025-08-04T00:00:00Z")
+        );
+
+        let b = buckets("1 quarter", "+08:00");
+        assert_eq!(
+            b.floor(micros("2025-05-15T00:00:00Z")),
+            micros("2025-03-31T16:00:00Z")
+        );
+        assert_eq!(
+            b.next(micros("2025-05-15T00:00:00Z")),
+            micros("2025-06-30T16:00:00Z")
+        );
+
+        let b = buckets("2 year", "UTC");
+        assert_eq!(
+            b.floor(micros("2026-05-15T00:00:00Z")),
+            micros("2025-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            b.next(micros("2026-05-15T00:00:00Z")),
+            micros("2027-01-01T00:00:00Z")
+        );
+        // before the origin
+        assert_eq!(
+            b.floor(micros("1998-05-15T00:00:00Z")),
+            micros("1997-01-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_midnight_in_dst_gap() {
+        // Chile starts DST at local midnight, 2024-09-08 starts at 01:00
+        let timezone = CalendarTimezone::parse(Some("America/Santiago")).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 9, 8).unwrap();
+        assert_eq!(timezone.midnight(date), micros("2024-09-08T04:00:00Z"));
+    }
+}
@@ -65,42 +65,44 @@ pub fn check_auth(req: Request<()>) -> Result<Request<()>, Status> {
             )));
         }
 
-        let credentials = match Credentials::from_header(token) {
-            Ok(c) => c,
-            Err(err) => {
-                log::error!("Err authenticating {err}");
-                return Err(Status::unauthenticated("No valid auth token[3]"));
-            }
-        };
-
-        let user_id = credentials.user_id;
-        let user = if is_root_user(&user_id) {
-            ROOT_USER.get("root").unwrap().to_owned()
-        } else if let Some(user) = get_cached_user_org(org_id.unwrap().to_str().unwrap(), &user_id)
-        {
-            user
-        } else {
-            return Err(Status::unauthenticated("No valid auth token[4]"));
-        };
-
-        if user.token.eq(&credentials.password) {
-            let mut req = req;
-            let user_id_metadata = MetadataValue::try_from(&user_id).unwrap();
-            req.metadata_mut().append("user_id", user_id_metadata);
-            return Ok(req);
-        }
-        let in_pass = get_hash(&credentials.password, &user.salt);
-        if user_id.eq(&user.email)
-            && (credentials.password.eq(&user.password) || in_pass.eq(&user.password))
-        {
-            let mut req = req;
-            let user_id_metadata = MetadataValue::try_from(&user_id).unwrap();
-            req.metadata_mut().append("user_id", user_id_metadata);
-
-            Ok(req)
-        } else {
-            Err(Status::unauthenticated("No valid auth token[5]"))
+        let user_id = check_user_credentials(org_id.unwrap().to_str().unwrap(), &token)?;
+        let mut req = req;
+        let user_id_metadata = MetadataValue::try_from(&user_id).unwrap();
+        req.metadata_mut().append("user_id", user_id_metadata);
+        Ok(req)
+    }
+}
+
+/// Checks the basic auth credentials of a user of the organization and
+/// returns the user id.
+pub fn check_user_credentials(org_id: &str, token: &str) -> Result<String, Status> {
+    let credentials = match Credentials::from_header(token.to_string()) {
+        Ok(c) => c,
+        Err(err) => {
+            log::error!("Err authenticating {err}");
+            return Err(Status::unauthenticated("No valid auth token[3]"));
         }
+    };
+
+    let user_id = credentials.user_id;
+    let user = if is_root_user(&user_id) {
+        ROOT_USER.get("root").unwrap().to_owned()
+    } else if let Some(user) = get_cached_user_org(org_id, &user_id) {
+        user
+    } else {
+        return Err(Status::unauthenticated("No valid auth token[4]"));
+    };
+
+    if user.token.eq(&credentials.password) {
+        return Ok(user_id);
+    }
+    let in_pass = get_hash(&credentials.password, &user.salt);
+    if user_id.eq(&user.email)
+        && (credentials.password.eq(&user.password) || in_pass.eq(&user.password))
+    {
+        Ok(user_id)
+    } else {
+        Err(Status::unauthenticated("No valid auth token[5]"))
     }
 }
 
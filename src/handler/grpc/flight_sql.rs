@@ -0,0 +1,531 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Arrow Flight SQL server for BI tools and ADBC/JDBC drivers.
+//!
+//! Clients authenticate with basic auth and the organization header, either on
+//! every call or once with a handshake which returns a bearer token. The
+//! `FlightInfo` of a query only plans it for its schema, the ticket holds the
+//! query which runs through the search service when the client calls `DoGet`,
+//! with the permissions of the caller.
+
+use std::{
+    pin::Pin,
+    sync::{Arc, LazyLock as Lazy},
+};
+
+use arrow::array::{RecordBatch, StringArray};
+use arrow_flight::{
+    Action, FlightDescriptor, FlightEndpoint, FlightInfo, HandshakeRequest, HandshakeResponse,
+    Ticket,
+    encode::FlightDataEncoderBuilder,
+    error::FlightError,
+    flight_service_server::FlightService,
+    sql::{
+        ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest,
+        ActionCreatePreparedStatementResult, CommandGetCatalogs, CommandGetDbSchemas,
+        CommandGetSqlInfo, CommandGetTableTypes, CommandGetTables, CommandPreparedStatementQuery,
+        CommandStatementQuery, ProstMessageExt, SqlInfo, TicketStatementQuery,
+        metadata::{SqlInfoData, SqlInfoDataBuilder},
+        server::FlightSqlService,
+    },
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use bytes::Bytes;
+use config::{
+    get_config, ider,
+    utils::{json, time::now_micros},
+};
+use futures::{Stream, StreamExt, TryStreamExt};
+use infra::errors::{Error, ErrorCodes};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tonic::{
+    Request, Response, Status, Streaming,
+    metadata::{MetadataMap, MetadataValue},
+};
+
+use crate::{
+    common::utils::auth::is_root_user,
+    handler::grpc::auth::check_user_credentials,
+    service::{
+        db,
+        search::sql_client::{self, ClientQuery},
+    },
+};
+
+/// Response header set by `DoGet` when some rows of the result are missing.
+const PARTIAL_RESULT_HEADER: &str = "x-partial-result";
+
+static SQL_INFO: Lazy<SqlInfoData> = Lazy::new(|| {
+    let mut builder = SqlInfoDataBuilder::new();
+    builder.append(SqlInfo::FlightSqlServerName, "OpenObserve");
+    builder.append(SqlInfo::FlightSqlServerVersion, config::VERSION);
+    builder.append(SqlInfo::FlightSqlServerArrowVersion, "1.3");
+    builder.append(SqlInfo::FlightSqlServerReadOnly, true);
+    builder.build().unwrap()
+});
+
+/// The user a Flight SQL call is made by.
+#[derive(Debug, Serialize, Deserialize)]
+struct FlightSqlUser {
+    org_id: String,
+    user_id: String,
+}
+
+#[derive(Default)]
+pub struct FlightSqlServiceImpl;
+
+#[tonic::async_trait]
+impl FlightSqlService for FlightSqlServiceImpl {
+    type FlightService = FlightSqlServiceImpl;
+
+    async fn do_handshake(
+        &self,
+        request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<
+        Response<Pin<Box<dyn Stream<Item = Result<HandshakeResponse, Status>> + Send>>>,
+        Status,
+    > {
+        let user = authenticate(request.metadata()).await?;
+        let session_id = ider::uuid();
+        let expires_at = now_micros() / 1_000_000 + get_config().grpc.flight_sql_session_ttl;
+        let session = json::to_string(&user).map_err(|e| Status::internal(e.to_string()))?;
+        db::session::set_with_expiry(&session_id, &session, expires_at)
+            .await
+            .map_err(|e| Status::internal(format!("failed to create session: {e}")))?;
+
+        let token = MetadataValue::try_from(format!("Bearer {session_id}"))
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let res = HandshakeResponse {
+            protocol_version: 0,
+            payload: session_id.into(),
+        };
+        let output: Pin<Box<dyn Stream<Item = Result<HandshakeResponse, Status>> + Send>> =
+            Box::pin(futures::stream::once(async move { Ok(res) }));
+        let mut response = Response::new(output);
+        response.metadata_mut().insert("authorization", token);
+        Ok(response)
+    }
+
+    async fn get_flight_info_statement(
+        &self,
+        query: CommandStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let user = authenticate(request.metadata()).await?;
+        plan_query(&user, &query.query, request.into_inner()).await
+    }
+
+    async fn get_flight_info_prepared_statement(
+        &self,
+        cmd: CommandPreparedStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let user = authenticate(request.metadata()).await?;
+        let sql = std::str::from_utf8(&cmd.prepared_statement_handle)
+            .map_err(|_| Status::invalid_argument("invalid prepared statement handle"))?;
+        plan_query(&user, sql, request.into_inner()).await
+    }
+
+    async fn get_flight_info_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        authenticate(request.metadata()).await?;
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        flight_info(
+            &query.into_builder().schema(),
+            ticket,
+            request.into_inner(),
+            -1,
+        )
+    }
+
+    async fn get_flight_info_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        authenticate(request.metadata()).await?;
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        flight_info(
+            &query.into_builder().schema(),
+            ticket,
+            request.into_inner(),
+            -1,
+        )
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        authenticate(request.metadata()).await?;
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        flight_info(
+            &query.into_builder().schema(),
+            ticket,
+            request.into_inner(),
+            -1,
+        )
+    }
+
+    async fn get_flight_info_table_types(
+        &self,
+        query: CommandGetTableTypes,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        authenticate(request.metadata()).await?;
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        flight_info(&table_types_schema(), ticket, request.into_inner(), 1)
+    }
+
+    async fn get_flight_info_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        authenticate(request.metadata()).await?;
+        let ticket = Ticket::new(query.as_any().encode_to_vec());
+        let schema = query.into_builder(&SQL_INFO).schema();
+        flight_info(&schema, ticket, request.into_inner(), -1)
+    }
+
+    async fn do_get_statement(
+        &self,
+        ticket: TicketStatementQuery,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let user = authenticate(request.metadata()).await?;
+        let query = ClientQuery::from_bytes(&ticket.statement_handle)
+            .map_err(|_| Status::invalid_argument("invalid statement ticket"))?;
+        // the ticket may come from another user, the caller must be allowed to run it
+        sql_client::check_permissions(&user.org_id, &user.user_id, &query)
+            .await
+            .map_err(to_status)?;
+        let trace_id = ider::generate_trace_id();
+        let result = sql_client::execute(&trace_id, &user.org_id, &user.user_id, &query)
+            .await
+            .map_err(to_status)?;
+        let mut response = encode(result.schema, result.batch);
+        if let Some(error) = result.partial_error {
+            // header values must be visible ASCII
+            let error = error.replace(|c: char| !c.is_ascii() || c.is_ascii_control(), " ");
+            let value = MetadataValue::try_from(error)
+                .unwrap_or_else(|_| MetadataValue::from_static("true"));
+            response.metadata_mut().insert(PARTIAL_RESULT_HEADER, value);
+        }
+        Ok(response)
+    }
+
+    async fn do_get_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let user = authenticate(request.metadata()).await?;
+        let mut builder = query.into_builder();
+        builder.append(&user.org_id);
+        let schema = builder.schema();
+        let batch = builder
+            .build()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(encode(schema, Some(batch)))
+    }
+
+    async fn do_get_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let user = authenticate(request.metadata()).await?;
+        let mut builder = query.into_builder();
+        for stream_type in sql_client::SCHEMAS {
+            builder.append(&user.org_id, stream_type.as_str());
+        }
+        let schema = builder.schema();
+        let batch = builder
+            .build()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(encode(schema, Some(batch)))
+    }
+
+    async fn do_get_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let user = authenticate(request.metadata()).await?;
+        let include_schema = query.include_schema;
+        let mut builder = query.into_builder();
+        for stream_type in sql_client::SCHEMAS {
+            let streams = db::schema::list(&user.org_id, Some(stream_type), include_schema)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+            for stream in streams {
+                if !sql_client::has_stream_permission(
+                    &user.org_id,
+                    &user.user_id,
+                    &stream.stream_name,
+                    stream_type,
+                )
+                .await
+                {
+                    continue;
+                }
+                builder
+                    .append(
+                        &user.org_id,
+                        stream_type.as_str(),
+                        &stream.stream_name,
+                        "TABLE",
+                        &stream.schema,
+                    )
+                    .map_err(|e| Status::internal(e.to_string()))?;
+            }
+        }
+        let schema = builder.schema();
+        let batch = builder
+            .build()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(encode(schema, Some(batch)))
+    }
+
+    async fn do_get_table_types(
+        &self,
+        _query: CommandGetTableTypes,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        authenticate(request.metadata()).await?;
+        let schema = table_types_schema();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(vec!["TABLE"]))],
+        )
+        .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(encode(schema, Some(batch)))
+    }
+
+    async fn do_get_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        authenticate(request.metadata()).await?;
+        let builder = query.into_builder(&SQL_INFO);
+        let schema = builder.schema();
+        let batch = builder
+            .build()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(encode(schema, Some(batch)))
+    }
+
+    /// Prepared statements are stateless, the handle is the query itself and
+    /// parameters are not supported.
+    async fn do_action_create_prepared_statement(
+        &self,
+        query: ActionCreatePreparedStatementRequest,
+        request: Request<Action>,
+    ) -> Result<ActionCreatePreparedStatementResult, Status> {
+        let user = authenticate(request.metadata()).await?;
+        sql_client::prepare(
+            &user.org_id,
+            &query.query,
+            now_micros(),
+            get_config().grpc.flight_sql_default_time_range,
+        )
+        .map_err(to_status)?;
+        Ok(ActionCreatePreparedStatementResult {
+            prepared_statement_handle: query.query.into(),
+            dataset_schema: Bytes::new(),
+            parameter_schema: Bytes::new(),
+        })
+    }
+
+    async fn do_action_close_prepared_statement(
+        &self,
+        _query: ActionClosePreparedStatementRequest,
+        request: Request<Action>,
+    ) -> Result<(), Status> {
+        authenticate(request.metadata()).await?;
+        Ok(())
+    }
+
+    async fn register_sql_info(&self, _id: i32, _result: &SqlInfo) {}
+}
+
+/// Authenticates a call with the bearer token of a handshake or with basic
+/// auth and the organization header.
+async fn authenticate(metadata: &MetadataMap) -> Result<FlightSqlUser, Status> {
+    let Some(authorization) = metadata
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty())
+    else {
+        return Err(Status::unauthenticated("No valid auth token"));
+    };
+
+    if let Some(session_id) = authorization
+        .get(..7)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("bearer "))
+        .map(|_| authorization[7..].trim())
+    {
+        let Some(session) = crate::service::session::get_session(session_id).await else {
+            return Err(Status::unauthenticated("Session expired"));
+        };
+        let user: FlightSqlUser =
+            json::from_str(&session).map_err(|_| Status::unauthenticated("No valid auth token"))?;
+        // the user may have been removed from the organization since the handshake
+        if !is_root_user(&user.user_id)
+            && crate::service::users::get_user(Some(&user.org_id), &user.user_id)
+                .await
+                .is_none()
+        {
+            return Err(Status::unauthenticated("No valid auth token"));
+        }
+        return Ok(user);
+    }
+
+    let org_header_key = &get_config().grpc.org_header_key;
+    let Some(org_id) = metadata.get(org_header_key).and_then(|v| v.to_str().ok()) else {
+        return Err(Status::invalid_argument(format!(
+            "Please specify organization id with header key '{org_header_key}'"
+        )));
+    };
+    let user_id = check_user_credentials(org_id, authorization)?;
+    Ok(FlightSqlUser {
+        org_id: org_id.to_string(),
+        user_id,
+    })
+}
+
+/// Plans a query for the schema of its `FlightInfo`, the ticket holds the
+/// query which is run by `DoGet`.
+async fn plan_query(
+    user: &FlightSqlUser,
+    sql: &str,
+    descriptor: FlightDescriptor,
+) -> Result<Response<FlightInfo>, Status> {
+    let query = sql_client::prepare(
+        &user.org_id,
+        sql,
+        now_micros(),
+        get_config().grpc.flight_sql_default_time_range,
+    )
+    .map_err(to_status)?;
+    sql_client::check_permissions(&user.org_id, &user.user_id, &query)
+        .await
+        .map_err(to_status)?;
+    let schema = sql_client::result_schema(&user.org_id, &query)
+        .await
+        .map_err(to_status)?;
+
+    let ticket = TicketStatementQuery {
+        statement_handle: query.to_bytes().map_err(to_status)?.into(),
+    };
+    let ticket = Ticket::new(ticket.as_any().encode_to_vec());
+    flight_info(&schema, ticket, descriptor, -1)
+}
+
+fn flight_info(
+    schema: &Schema,
+    ticket: Ticket,
+    descriptor: FlightDescriptor,
+    total_records: i64,
+) -> Result<Response<FlightInfo>, Status> {
+    let info = FlightInfo::new()
+        .try_with_schema(schema)
+        .map_err(|e| Status::internal(format!("failed to encode schema: {e}")))?
+        .with_endpoint(FlightEndpoint::new().with_ticket(ticket))
+        .with_descriptor(descriptor)
+        .with_total_records(total_records);
+    Ok(Response::new(info))
+}
+
+fn encode(
+    schema: SchemaRef,
+    batch: Option<RecordBatch>,
+) -> Response<<FlightSqlServiceImpl as FlightService>::DoGetStream> {
+    let batches = futures::stream::iter(batch.into_iter().map(Ok::<_, FlightError>));
+    let stream = FlightDataEncoderBuilder::new()
+        .with_schema(schema)
+        .build(batches)
+        .map_err(Status::from);
+    Response::new(stream.boxed())
+}
+
+fn table_types_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![Field::new(
+        "table_type",
+        DataType::Utf8,
+        false,
+    )]))
+}
+
+fn to_status(e: Error) -> Status {
+    match e {
+        Error::ErrorCode(
+            code @ (ErrorCodes::SearchSQLNotValid(_)
+            | ErrorCodes::SearchStreamNotFound(_)
+            | ErrorCodes::SearchFieldNotFound(_)
+            | ErrorCodes::SearchFunctionNotDefined(_)
+            | ErrorCodes::InvalidParams(_)),
+        ) => Status::invalid_argument(code.get_message()),
+        Error::ErrorCode(
+            code @ (ErrorCodes::RatelimitExceeded(_) | ErrorCodes::SearchScanBudgetExceeded(_)),
+        ) => Status::resource_exhausted(code.get_message()),
+        Error::ErrorCode(code @ ErrorCodes::SearchTimeout(_)) => {
+            Status::deadline_exceeded(code.get_message())
+        }
+        Error::ErrorCode(code @ ErrorCodes::SearchCancelQuery(_)) => {
+            Status::cancelled(code.get_message())
+        }
+        Error::ErrorCode(code) => Status::internal(code.get_message()),
+        e => Status::internal(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_authenticate_requires_credentials() {
+        let request = Request::new(());
+        let err = authenticate(request.metadata()).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            "Basic cm9vdEBleGFtcGxlLmNvbTp0b2tlbg==".parse().unwrap(),
+        );
+        let err = authenticate(request.metadata()).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_to_status() {
+        let status = to_status(Error::ErrorCode(ErrorCodes::SearchSQLNotValid(
+            "bad sql".to_string(),
+        )));
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        let status = to_status(Error::Message("boom".to_string()));
+        assert_eq!(status.code(), tonic::Code::Internal);
+    }
+}
@@ -19,6 +19,7 @@ use proto::cluster_rpc;
 
 pub mod auth;
 pub mod flight;
+pub mod flight_sql;
 pub mod request;
 
 pub struct MetadataMap<'a>(&'a tonic::metadata::MetadataMap);
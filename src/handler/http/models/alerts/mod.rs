@@ -17,7 +17,10 @@ pub mod requests;
 pub mod responses;
 
 use config::meta::{
-    alerts::{self as meta_alerts, deduplication::DeduplicationConfig, default_align_time},
+    alerts::{
+        self as meta_alerts, deduplication::DeduplicationConfig, default_align_time,
+        incidents::IncidentSeverity,
+    },
     search as meta_search, stream as meta_stream,
     triggers::Trigger,
 };
@@ -140,6 +143,16 @@ pub struct Alert {
     /// instead of sending direct alert notifications.
     #[serde(default)]
     pub creates_incident: bool,
+
+    /// When true, a notification is also sent when a firing alert instance
+    /// stops matching the alert condition.
+    #[serde(default)]
+    pub send_on_resolve: bool,
+
+    /// Optional template name used for resolve notifications. Falls back to
+    /// the alert or destination template when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_template: Option<String>,
 }
 
 /// Configuration for when and how an alert should be triggered.
@@ -205,6 +218,57 @@ pub struct TriggerCondition {
     /// Whether to align query time windows to period boundaries.
     #[serde(default = "default_align_time")]
     pub align_time: bool,
+
+    /// Severity levels evaluated from the same query result. When set, they
+    /// are used instead of `operator` and `threshold`, the most severe
+    /// matching level fires.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub severity_levels: Vec<SeverityLevel>,
+
+    /// Result column compared with the severity level thresholds. When not
+    /// set, the number of result rows is compared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "cpu_usage")]
+    pub threshold_field: Option<String>,
+
+    /// How long in seconds the condition must hold in consecutive evaluations
+    /// before the alert fires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 300)]
+    pub pending_seconds: Option<i64>,
+}
+
+/// A severity of an alert with its own threshold and notification overrides.
+///
+/// ## Example
+/// ```json
+/// {
+///     "severity": "P1",
+///     "operator": ">=",
+///     "threshold": 0.95,
+///     "destinations": ["pagerduty"]
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SeverityLevel {
+    /// Severity triggered when the threshold matches, P1 is the most severe.
+    pub severity: IncidentSeverity,
+
+    /// Comparison operator for the threshold: =, !=, >, >=, <, <=
+    #[serde(default)]
+    pub operator: Operator,
+
+    /// Threshold value, fractional values are allowed.
+    #[schema(example = 0.95)]
+    pub threshold: f64,
+
+    /// Destinations notified at this severity instead of the alert destinations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub destinations: Vec<String>,
+
+    /// Template used at this severity instead of the alert template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
 }
 
 #[derive(Clone, Default, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
@@ -443,6 +507,8 @@ impl From<(meta_alerts::alert::Alert, Option<Trigger>)> for Alert {
             last_edited_by: alert.last_edited_by,
             deduplication: alert.deduplication,
             creates_incident: alert.creates_incident,
+            send_on_resolve: alert.send_on_resolve,
+            resolve_template: alert.resolve_template,
         }
     }
 }
@@ -460,6 +526,25 @@ impl From<meta_alerts::TriggerCondition> for TriggerCondition {
             timezone: value.timezone,
             tolerance_seconds: value.tolerance_in_secs,
             align_time: value.align_time,
+            severity_levels: value
+                .severity_levels
+                .into_iter()
+                .map(|l| l.into())
+                .collect(),
+            threshold_field: value.threshold_field,
+            pending_seconds: value.pending_in_secs,
+        }
+    }
+}
+
+impl From<meta_alerts::SeverityLevel> for SeverityLevel {
+    fn from(value: meta_alerts::SeverityLevel) -> Self {
+        Self {
+            severity: value.severity,
+            operator: value.operator.into(),
+            threshold: value.threshold,
+            destinations: value.destinations,
+            template: value.template,
         }
     }
 }
@@ -624,6 +709,8 @@ impl From<Alert> for meta_alerts::alert::Alert {
         alert.owner = value.owner;
         alert.deduplication = value.deduplication;
         alert.creates_incident = value.creates_incident;
+        alert.send_on_resolve = value.send_on_resolve;
+        alert.resolve_template = value.resolve_template;
 
         alert
     }
@@ -642,6 +729,25 @@ impl From<TriggerCondition> for meta_alerts::TriggerCondition {
             silence: value.silence_minutes,
             timezone: value.timezone,
             tolerance_in_secs: value.tolerance_seconds,
+            severity_levels: value
+                .severity_levels
+                .into_iter()
+                .map(|l| l.into())
+                .collect(),
+            threshold_field: value.threshold_field,
+            pending_in_secs: value.pending_seconds,
+        }
+    }
+}
+
+impl From<SeverityLevel> for meta_alerts::SeverityLevel {
+    fn from(value: SeverityLevel) -> Self {
+        Self {
+            severity: value.severity,
+            operator: value.operator.into(),
+            threshold: value.threshold,
+            destinations: value.destinations,
+            template: value.template,
         }
     }
 }
@@ -1189,6 +1295,9 @@ mod tests {
             timezone: Some("UTC".to_string()),
             tolerance_in_secs: Some(10),
             align_time: false,
+            severity_levels: vec![],
+            threshold_field: None,
+            pending_in_secs: Some(120),
         };
         let tc = TriggerCondition::from(meta);
         assert_eq!(tc.period_minutes, 15);
@@ -1198,7 +1307,12 @@ mod tests {
         assert_eq!(tc.silence_minutes, 60);
         assert_eq!(tc.timezone, Some("UTC".to_string()));
         assert_eq!(tc.tolerance_seconds, Some(10));
+        assert_eq!(tc.pending_seconds, Some(120));
         assert!(!tc.align_time);
+        // the API field keeps the name of the model
+        let json = serde_json::to_value(&tc).unwrap();
+        assert_eq!(json["pending_seconds"], 120);
+        assert!(json.get("pending_in_secs").is_none());
     }
 
     #[test]
@@ -1214,6 +1328,14 @@ mod tests {
             timezone: None,
             tolerance_seconds: None,
             align_time: true,
+            severity_levels: vec![SeverityLevel {
+                severity: IncidentSeverity::P1,
+                operator: Operator::GreaterThanEquals,
+                threshold: 0.95,
+                ..Default::default()
+            }],
+            threshold_field: Some("cpu".to_string()),
+            pending_seconds: None,
         };
         let meta = meta_alerts::TriggerCondition::from(tc);
         assert_eq!(meta.period, 10);
@@ -1224,6 +1346,13 @@ mod tests {
         assert!(meta.timezone.is_none());
         assert!(meta.tolerance_in_secs.is_none());
         assert!(meta.align_time);
+        assert_eq!(meta.severity_levels.len(), 1);
+        assert_eq!(meta.severity_levels[0].threshold, 0.95);
+        assert!(matches!(
+            meta.severity_levels[0].operator,
+            meta_alerts::Operator::GreaterThanEquals
+        ));
+        assert_eq!(meta.threshold_field.as_deref(), Some("cpu"));
     }
 
     #[test]
@@ -1344,7 +1473,9 @@ mod tests {
             "org_id": "myorg",
             "stream_name": "stream1",
             "enabled": true,
-            "creates_incident": true
+            "creates_incident": true,
+            "send_on_resolve": true,
+            "resolve_template": "resolved"
         });
         let alert: Alert = serde_json::from_value(json).unwrap();
         let meta = meta_alerts::alert::Alert::from(alert);
@@ -1353,6 +1484,8 @@ mod tests {
         assert_eq!(meta.stream_name, "stream1");
         assert!(meta.enabled);
         assert!(meta.creates_incident);
+        assert!(meta.send_on_resolve);
+        assert_eq!(meta.resolve_template.as_deref(), Some("resolved"));
     }
 
     #[test]
@@ -1363,6 +1496,8 @@ mod tests {
         assert_eq!(meta.org_id, "");
         assert!(!meta.enabled);
         assert!(!meta.creates_incident);
+        assert!(!meta.send_on_resolve);
+        assert!(meta.resolve_template.is_none());
         assert!(meta.id.is_none());
         assert!(meta.owner.is_none());
     }
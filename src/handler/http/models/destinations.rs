@@ -61,6 +61,8 @@ impl From<meta_dest::Destination> for Destination {
                     output_format: endpoint.output_format,
                     destination_type_name: endpoint.destination_type,
                     metadata: endpoint.metadata,
+                    retry: endpoint.retry,
+                    signing_secret: endpoint.signing_secret,
                     ..Default::default()
                 },
                 meta_dest::DestinationType::Sns(aws_sns) => Self {
@@ -71,6 +73,40 @@ impl From<meta_dest::Destination> for Destination {
                     destination_type: DestinationType::Sns,
                     ..Default::default()
                 },
+                meta_dest::DestinationType::Slack(slack) => Self {
+                    name: value.name,
+                    template,
+                    url: slack.webhook_url.unwrap_or_default(),
+                    bot_token: slack.bot_token,
+                    channel: slack.channel,
+                    destination_type: DestinationType::Slack,
+                    ..Default::default()
+                },
+                meta_dest::DestinationType::MsTeams(teams) => Self {
+                    name: value.name,
+                    template,
+                    url: teams.webhook_url,
+                    destination_type: DestinationType::MsTeams,
+                    ..Default::default()
+                },
+                meta_dest::DestinationType::PagerDuty(pagerduty) => Self {
+                    name: value.name,
+                    template,
+                    routing_key: Some(pagerduty.routing_key),
+                    severity: Some(pagerduty.severity),
+                    dedup_key: pagerduty.dedup_key,
+                    destination_type: DestinationType::PagerDuty,
+                    ..Default::default()
+                },
+                meta_dest::DestinationType::Opsgenie(opsgenie) => Self {
+                    name: value.name,
+                    template,
+                    url: opsgenie.api_url.unwrap_or_default(),
+                    api_key: Some(opsgenie.api_key),
+                    priority: Some(opsgenie.priority),
+                    destination_type: DestinationType::Opsgenie,
+                    ..Default::default()
+                },
             },
             meta_dest::Module::Pipeline { endpoint } => Self {
                 name: value.name,
@@ -114,11 +150,37 @@ impl Destination {
                     output_format: self.output_format,
                     destination_type: self.destination_type_name,
                     metadata: self.metadata,
+                    retry: self.retry,
+                    signing_secret: self.signing_secret.filter(|secret| !secret.is_empty()),
                 }),
                 DestinationType::Sns => meta_dest::DestinationType::Sns(meta_dest::AwsSns {
                     sns_topic_arn: self.sns_topic_arn.ok_or(DestinationError::InvalidSns)?,
                     aws_region: self.aws_region.ok_or(DestinationError::InvalidSns)?,
                 }),
+                DestinationType::Slack => meta_dest::DestinationType::Slack(meta_dest::Slack {
+                    webhook_url: Some(self.url).filter(|url| !url.is_empty()),
+                    bot_token: self.bot_token,
+                    channel: self.channel,
+                }),
+                DestinationType::MsTeams => {
+                    meta_dest::DestinationType::MsTeams(meta_dest::MsTeams {
+                        webhook_url: self.url,
+                    })
+                }
+                DestinationType::PagerDuty => {
+                    meta_dest::DestinationType::PagerDuty(meta_dest::PagerDuty {
+                        routing_key: self.routing_key.unwrap_or_default(),
+                        severity: self.severity.unwrap_or_default(),
+                        dedup_key: self.dedup_key.filter(|key| !key.is_empty()),
+                    })
+                }
+                DestinationType::Opsgenie => {
+                    meta_dest::DestinationType::Opsgenie(meta_dest::Opsgenie {
+                        api_key: self.api_key.unwrap_or_default(),
+                        priority: self.priority.unwrap_or_default(),
+                        api_url: Some(self.url).filter(|url| !url.is_empty()),
+                    })
+                }
                 #[cfg(feature = "enterprise")]
                 DestinationType::Action => {
                     if let Some(action_id) = self.action_id {
@@ -137,6 +199,8 @@ impl Destination {
                             output_format: self.output_format,
                             destination_type: self.destination_type_name,
                             metadata: self.metadata,
+                            retry: None,
+                            signing_secret: None,
                         })
                     } else {
                         return Err(DestinationError::InvalidActionId(anyhow::anyhow!(
@@ -165,6 +229,8 @@ impl Destination {
                 output_format: self.output_format,
                 destination_type: self.destination_type_name,
                 metadata: self.metadata,
+                retry: None,
+                signing_secret: None,
             };
             Ok(meta_dest::Destination {
                 id: None,
@@ -204,7 +270,11 @@ impl Template {
         let template_type = match self.template_type {
             DestinationType::Email => meta_dest::TemplateType::Email { title: self.title },
             DestinationType::Sns => meta_dest::TemplateType::Sns,
-            DestinationType::Http => meta_dest::TemplateType::Http,
+            DestinationType::Http
+            | DestinationType::Slack
+            | DestinationType::MsTeams
+            | DestinationType::PagerDuty
+            | DestinationType::Opsgenie => meta_dest::TemplateType::Http,
             #[cfg(feature = "enterprise")]
             DestinationType::Action => meta_dest::TemplateType::Http,
         };
@@ -252,7 +322,8 @@ pub struct Destination {
     #[serde(default)]
     #[schema(example = "my_alert_webhook")]
     pub name: String,
-    /// Webhook URL for HTTP destinations. Required when `type` is `http`.
+    /// Webhook URL for HTTP destinations. Required when `type` is `http` or `msteams`, the
+    /// incoming webhook URL for `slack` and the optional API URL for `opsgenie`.
     #[serde(default)]
     #[schema(example = "https://example.com/webhook")]
     pub url: String,
@@ -281,7 +352,29 @@ pub struct Destination {
     /// AWS region for SNS destinations. Required when `type` is `sns`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aws_region: Option<String>,
-    /// Destination type: `http` (webhook), `email`, or `sns`. Default is `http`.
+    /// Bot token for Slack destinations posting with the Web API instead of a webhook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_token: Option<String>,
+    /// Channel for Slack destinations. Required with `bot_token`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Events v2 routing key for PagerDuty destinations. Required when `type` is `pagerduty`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing_key: Option<String>,
+    /// Default severity for PagerDuty destinations. Default is `error`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<meta_dest::PagerDutySeverity>,
+    /// Dedup key template for PagerDuty destinations. Defaults to the alert id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<String>,
+    /// API key for Opsgenie destinations. Required when `type` is `opsgenie`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Default priority for Opsgenie destinations. Default is `P3`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<meta_dest::OpsgeniePriority>,
+    /// Destination type: `http` (webhook), `email`, `sns`, `slack`, `msteams`, `pagerduty` or
+    /// `opsgenie`. Default is `http`.
     #[serde(rename = "type")]
     #[serde(default)]
     #[schema(example = "http")]
@@ -299,6 +392,15 @@ pub struct Destination {
     /// Optional key-value metadata for the destination.
     #[serde(default)]
     pub metadata: HashMap<String, String>,
+    /// Retry policy for HTTP alert destinations. Deliveries failing with a server error or a
+    /// timeout are retried with exponential backoff, deliveries that cannot be retried anymore
+    /// are recorded in the `webhook_dead_letters` stream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<meta_dest::WebhookRetryPolicy>,
+    /// Secret for HTTP alert destinations to sign requests with. The `X-OpenObserve-Signature`
+    /// header then carries `t=<unix timestamp>,v1=<hex HMAC-SHA256 of "<timestamp>.<body>">`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_secret: Option<String>,
 }
 
 #[derive(Serialize, Debug, Default, PartialEq, Eq, Deserialize, Clone, ToSchema)]
@@ -308,6 +410,12 @@ pub enum DestinationType {
     Http,
     Email,
     Sns,
+    Slack,
+    #[serde(rename = "msteams")]
+    MsTeams,
+    #[serde(rename = "pagerduty")]
+    PagerDuty,
+    Opsgenie,
     #[cfg(feature = "enterprise")]
     Action,
 }
@@ -317,6 +425,10 @@ impl From<&str> for DestinationType {
         match value.to_lowercase().as_str() {
             "email" => DestinationType::Email,
             "sns" => DestinationType::Sns,
+            "slack" => DestinationType::Slack,
+            "msteams" => DestinationType::MsTeams,
+            "pagerduty" => DestinationType::PagerDuty,
+            "opsgenie" => DestinationType::Opsgenie,
             #[cfg(feature = "enterprise")]
             "action" => DestinationType::Action,
             _ => DestinationType::Http,
@@ -330,6 +442,10 @@ impl fmt::Display for DestinationType {
             DestinationType::Email => write!(f, "email"),
             DestinationType::Http => write!(f, "http"),
             DestinationType::Sns => write!(f, "sns"),
+            DestinationType::Slack => write!(f, "slack"),
+            DestinationType::MsTeams => write!(f, "msteams"),
+            DestinationType::PagerDuty => write!(f, "pagerduty"),
+            DestinationType::Opsgenie => write!(f, "opsgenie"),
             #[cfg(feature = "enterprise")]
             DestinationType::Action => write!(f, "action"),
         }
@@ -521,6 +637,9 @@ mod tests {
         assert!(!obj.contains_key("aws_region"));
         assert!(!obj.contains_key("output_format"));
         assert!(!obj.contains_key("destination_type_name"));
+        assert!(!obj.contains_key("bot_token"));
+        assert!(!obj.contains_key("routing_key"));
+        assert!(!obj.contains_key("api_key"));
     }
 
     #[test]
@@ -595,6 +714,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_destination_into_alert_http_retry_and_signing_roundtrip() {
+        let retry = meta_dest::WebhookRetryPolicy {
+            max_retries: 5,
+            ..Default::default()
+        };
+        let d = Destination {
+            name: "http_dest".to_string(),
+            destination_type: DestinationType::Http,
+            url: "https://example.com/hook".to_string(),
+            retry: Some(retry.clone()),
+            signing_secret: Some("s3cret".to_string()),
+            template: Some("Default".to_string()),
+            ..Destination::default()
+        };
+        let meta = d.into("org1".to_string(), true).unwrap();
+        match &meta.module {
+            meta_dest::Module::Alert {
+                destination_type: meta_dest::DestinationType::Http(ep),
+                ..
+            } => {
+                assert_eq!(ep.retry.as_ref(), Some(&retry));
+                assert_eq!(ep.signing_secret.as_deref(), Some("s3cret"));
+            }
+            _ => panic!("expected Alert Http module"),
+        }
+        let d = Destination::from(meta);
+        assert_eq!(d.retry, Some(retry));
+        assert_eq!(d.signing_secret.as_deref(), Some("s3cret"));
+
+        // an empty secret disables signing
+        let d = Destination {
+            url: "https://example.com/hook".to_string(),
+            signing_secret: Some(String::new()),
+            ..Destination::default()
+        };
+        match d.into("org1".to_string(), true).unwrap().module {
+            meta_dest::Module::Alert {
+                destination_type: meta_dest::DestinationType::Http(ep),
+                ..
+            } => assert!(ep.signing_secret.is_none()),
+            _ => panic!("expected Alert Http module"),
+        }
+    }
+
     #[test]
     fn test_destination_into_alert_sns_missing_arn_returns_error() {
         let d = Destination {
@@ -608,6 +772,72 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_destination_type_native_providers_from_str() {
+        assert_eq!(DestinationType::from("slack"), DestinationType::Slack);
+        assert_eq!(DestinationType::from("MSTeams"), DestinationType::MsTeams);
+        assert_eq!(
+            DestinationType::from("pagerduty"),
+            DestinationType::PagerDuty
+        );
+        assert_eq!(DestinationType::from("opsgenie"), DestinationType::Opsgenie);
+        assert_eq!(DestinationType::PagerDuty.to_string(), "pagerduty");
+        assert_eq!(
+            serde_json::to_value(DestinationType::MsTeams).unwrap(),
+            "msteams"
+        );
+    }
+
+    #[test]
+    fn test_destination_into_alert_pagerduty_roundtrip() {
+        let d = Destination {
+            name: "pd_dest".to_string(),
+            destination_type: DestinationType::PagerDuty,
+            routing_key: Some("R0UT1NG".to_string()),
+            severity: Some(meta_dest::PagerDutySeverity::Critical),
+            template: Some("Default".to_string()),
+            ..Destination::default()
+        };
+        let meta = d.into("org1".to_string(), true).unwrap();
+        match &meta.module {
+            meta_dest::Module::Alert {
+                destination_type: meta_dest::DestinationType::PagerDuty(pd),
+                ..
+            } => {
+                assert_eq!(pd.routing_key, "R0UT1NG");
+                assert_eq!(pd.severity, meta_dest::PagerDutySeverity::Critical);
+                assert!(pd.dedup_key.is_none());
+            }
+            _ => panic!("expected Alert PagerDuty module"),
+        }
+        let d = Destination::from(meta);
+        assert_eq!(d.destination_type, DestinationType::PagerDuty);
+        assert_eq!(d.routing_key.as_deref(), Some("R0UT1NG"));
+    }
+
+    #[test]
+    fn test_destination_into_alert_slack_bot() {
+        let d = Destination {
+            name: "slack_dest".to_string(),
+            destination_type: DestinationType::Slack,
+            bot_token: Some("xoxb-1".to_string()),
+            channel: Some("#alerts".to_string()),
+            template: Some("Default".to_string()),
+            ..Destination::default()
+        };
+        let meta = d.into("org1".to_string(), true).unwrap();
+        match meta.module {
+            meta_dest::Module::Alert {
+                destination_type: meta_dest::DestinationType::Slack(slack),
+                ..
+            } => {
+                assert!(slack.webhook_url.is_none());
+                assert_eq!(slack.channel.as_deref(), Some("#alerts"));
+            }
+            _ => panic!("expected Alert Slack module"),
+        }
+    }
+
     #[test]
     fn test_destination_into_pipeline() {
         let d = Destination {
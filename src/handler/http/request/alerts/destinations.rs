@@ -21,6 +21,7 @@ use axum::{
     http::StatusCode,
     response::Response,
 };
+use config::meta::destinations as meta_dest;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "enterprise")]
@@ -35,7 +36,10 @@ use crate::{
         models::destinations::{Destination, DestinationType},
         request::{BulkDeleteRequest, BulkDeleteResponse},
     },
-    service::{alerts::destinations, db::alerts::destinations::DestinationError},
+    service::{
+        alerts::{destinations, webhooks},
+        db::alerts::destinations::DestinationError,
+    },
 };
 
 #[derive(Debug, Clone, Deserialize, Serialize, utoipa::ToSchema)]
@@ -47,11 +51,22 @@ pub struct TestDestinationRequest {
     pub headers: Option<HashMap<String, String>>,
     pub body: Option<String>,
     pub skip_tls_verify: Option<bool>,
-    /// Destination type: "http" (default) or "email"
+    /// Destination type: "http" (default), "email", "slack", "msteams", "pagerduty" or
+    /// "opsgenie"
     #[serde(rename = "type", default)]
     pub destination_type: DestinationType,
     /// Email recipients for email destination testing
     pub recipients: Option<Vec<String>>,
+    /// Slack bot token, used instead of the webhook url
+    pub bot_token: Option<String>,
+    /// Slack channel, required with the bot token
+    pub channel: Option<String>,
+    /// PagerDuty Events v2 routing key
+    pub routing_key: Option<String>,
+    /// Opsgenie API key
+    pub api_key: Option<String>,
+    /// Secret to sign the HTTP test request with, as it is done for alert notifications
+    pub signing_secret: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, utoipa::ToSchema)]
@@ -85,7 +100,9 @@ impl From<DestinationError> for Response {
     summary = "Test alert destination",
     description = "Tests an alert destination configuration. For HTTP destinations, sends a test HTTP request \
                    to the specified endpoint. For email destinations, validates SMTP configuration and recipient \
-                   permissions, then sends a test email. This allows users to verify that their destination \
+                   permissions, then sends a test email. For Slack, Microsoft Teams, PagerDuty and Opsgenie \
+                   destinations, sends a test notification, paging providers are resolved right after. \
+                   This allows users to verify that their destination \
                    configuration is correct before saving it.",
     security(
         ("Authorization"= [])
@@ -110,6 +127,10 @@ pub async fn test_destination(
     match test_req.destination_type {
         DestinationType::Email => test_email_destination(&org_id, &test_req).await,
         DestinationType::Http => test_http_destination(&test_req).await,
+        DestinationType::Slack
+        | DestinationType::MsTeams
+        | DestinationType::PagerDuty
+        | DestinationType::Opsgenie => test_provider_destination(&org_id, &test_req).await,
         other => MetaHttpResponse::json(TestDestinationResponse {
             success: false,
             status_code: None,
@@ -148,6 +169,42 @@ async fn test_email_destination(org_id: &str, test_req: &TestDestinationRequest)
     }
 }
 
+async fn test_provider_destination(org_id: &str, test_req: &TestDestinationRequest) -> Response {
+    let dest = Destination {
+        url: test_req.url.clone(),
+        bot_token: test_req.bot_token.clone(),
+        channel: test_req.channel.clone(),
+        routing_key: test_req.routing_key.clone(),
+        api_key: test_req.api_key.clone(),
+        destination_type: test_req.destination_type.clone(),
+        ..Default::default()
+    };
+    let result = match dest.into(org_id.to_string(), true) {
+        Ok(meta_dest::Destination {
+            module: meta_dest::Module::Alert {
+                destination_type, ..
+            },
+            ..
+        }) => destinations::test_provider(&destination_type, test_req.body.as_deref()).await,
+        Ok(_) => Err(DestinationError::UnsupportedType),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(msg) => MetaHttpResponse::json(TestDestinationResponse {
+            success: true,
+            status_code: None,
+            response_body: Some(msg),
+            error: None,
+        }),
+        Err(e) => MetaHttpResponse::json(TestDestinationResponse {
+            success: false,
+            status_code: None,
+            response_body: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
 async fn test_http_destination(test_req: &TestDestinationRequest) -> Response {
     let method = test_req
         .method
@@ -219,6 +276,12 @@ async fn test_http_destination(test_req: &TestDestinationRequest) -> Response {
     for (key, value) in headers {
         request_builder = request_builder.header(key, value);
     }
+    if let Some(secret) = test_req.signing_secret.as_ref().filter(|s| !s.is_empty()) {
+        request_builder = request_builder.header(
+            meta_dest::WEBHOOK_SIGNATURE_HEADER,
+            webhooks::signature(secret, chrono::Utc::now().timestamp(), &body),
+        );
+    }
 
     // Add body if not empty
     if !body.is_empty() {
@@ -690,6 +753,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_invalid_provider_is_bad_request() {
+        assert_eq!(
+            status(DestinationError::InvalidProvider(
+                "PagerDuty destination must have a routing key".to_string()
+            )),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
     #[test]
     fn test_empty_email_is_bad_request() {
         assert_eq!(
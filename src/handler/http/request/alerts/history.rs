@@ -100,6 +100,12 @@ pub struct AlertHistoryEntry {
     /// Number of anomalies found in this evaluation run (anomaly detection only).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anomaly_count: Option<i32>,
+    /// Fingerprint of the alert instance, set when an instance resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    /// Id of the silence that suppressed the notification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub silence_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -401,6 +407,8 @@ pub async fn get_alert_history(
                     grouped: None,
                     group_size: None,
                     anomaly_count: Some(anomaly_count as i32),
+                    fingerprint: None,
+                    silence_id: None,
                 }
             })
             .collect();
@@ -644,7 +652,7 @@ pub async fn get_alert_history(
         "SELECT _timestamp, org, key, status, is_realtime, is_silenced, \
          start_time, end_time, retries, \
          delay_in_secs, evaluation_took_in_secs, \
-         source_node, query_took, error \
+         source_node, query_took, error, fingerprint, silence_id \
          FROM \"{TRIGGERS_STREAM}\" \
          WHERE {where_clause} \
          ORDER BY {sort_column} {sort_order} LIMIT {size} OFFSET {from}"
@@ -750,6 +758,14 @@ pub async fn get_alert_history(
                 .and_then(|v| v.as_i64())
                 .map(|v| v as i32),
             anomaly_count: None,
+            fingerprint: hit
+                .get("fingerprint")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            silence_id: hit
+                .get("silence_id")
+                .and_then(|v| v.as_str())
+                .map(String::from),
         });
     }
 
@@ -1037,6 +1053,8 @@ mod tests {
             grouped: None,
             group_size: None,
             anomaly_count: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         assert_eq!(entry.alert_name, "test_alert");
@@ -1088,6 +1106,8 @@ mod tests {
             grouped: None,
             group_size: None,
             anomaly_count: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         let response = AlertHistoryResponse {
@@ -1128,6 +1148,8 @@ mod tests {
             grouped: None,
             group_size: None,
             anomaly_count: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         assert_eq!(entry.status, "error");
@@ -1342,6 +1364,8 @@ mod tests {
             grouped: Some(false),
             group_size: None,
             anomaly_count: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -1379,6 +1403,8 @@ mod tests {
             grouped: None,
             group_size: None,
             anomaly_count: None,
+            fingerprint: None,
+            silence_id: None,
         };
 
         let response = AlertHistoryResponse {
@@ -1422,6 +1448,8 @@ mod tests {
             grouped: None,
             group_size: None,
             anomaly_count: None,
+            fingerprint: None,
+            silence_id: None,
         };
         let json = serde_json::to_value(&entry).unwrap();
         let obj = json.as_object().unwrap();
@@ -1458,6 +1486,8 @@ mod tests {
             grouped: Some(true),
             group_size: Some(3),
             anomaly_count: Some(2),
+            fingerprint: None,
+            silence_id: None,
         };
         let json = serde_json::to_value(&entry).unwrap();
         let obj = json.as_object().unwrap();
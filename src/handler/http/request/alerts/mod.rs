@@ -76,6 +76,7 @@ pub mod deduplication;
 pub mod destinations;
 pub mod history;
 pub mod incidents;
+pub mod silences;
 pub mod templates;
 
 impl From<AlertError> for Response {
@@ -113,6 +114,7 @@ impl From<AlertError> for Response {
             AlertError::PermissionDenied => MetaHttpResponse::forbidden("Unauthorized access"),
             AlertError::UserNotFound => MetaHttpResponse::forbidden("Unauthorized access"),
             AlertError::AlertIdMissing => MetaHttpResponse::bad_request(value),
+            AlertError::InvalidSeverityLevels(_) => MetaHttpResponse::bad_request(value),
         }
     }
 }
@@ -1651,6 +1653,14 @@ mod tests {
         assert_eq!(status(AlertError::UserNotFound), StatusCode::FORBIDDEN);
     }
 
+    #[test]
+    fn test_invalid_severity_levels_is_bad_request() {
+        assert_eq!(
+            status(AlertError::InvalidSeverityLevels("err".to_string())),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
     #[test]
     fn test_permitted_alerts_validator_is_forbidden() {
         assert_eq!(
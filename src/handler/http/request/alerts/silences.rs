@@ -0,0 +1,207 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Alert silence API endpoints
+
+use axum::{Json, extract::Path, http::StatusCode, response::Response};
+use config::meta::alerts::silences::Silence;
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    handler::http::extractors::Headers,
+    service::alerts::silences::{self, SilenceError},
+};
+
+impl From<SilenceError> for Response {
+    fn from(value: SilenceError) -> Self {
+        match value {
+            SilenceError::NotFound(_) => MetaHttpResponse::not_found(value),
+            SilenceError::Invalid(_) => MetaHttpResponse::bad_request(value),
+            SilenceError::Other(e) => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// ListSilences
+#[utoipa::path(
+    get,
+    path = "/{org_id}/alerts/silences",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "ListSilences",
+    summary = "List alert silences",
+    description = "Lists the alert silences of an organization, including inactive and disabled ones.",
+    security(("Authorization" = [])),
+    params(
+        ("org_id" = String, Path, description = "Organization ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = Vec<Silence>),
+        (status = 500, description = "Internal server error"),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Alerts", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List alert silences", "category": "alerts"}))
+    )
+)]
+pub async fn list_silences(Path(org_id): Path<String>) -> Response {
+    match silences::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(list),
+        Err(e) => {
+            log::error!("Error listing silences for org {org_id}: {e}");
+            MetaHttpResponse::internal_error(format!("Failed to list silences: {e}"))
+        }
+    }
+}
+
+/// GetSilence
+#[utoipa::path(
+    get,
+    path = "/{org_id}/alerts/silences/{silence_id}",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "GetSilence",
+    summary = "Get alert silence",
+    description = "Retrieves an alert silence by id.",
+    security(("Authorization" = [])),
+    params(
+        ("org_id" = String, Path, description = "Organization ID"),
+        ("silence_id" = String, Path, description = "Silence ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = Silence),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Alerts", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get alert silence", "category": "alerts"}))
+    )
+)]
+pub async fn get_silence(Path((org_id, silence_id)): Path<(String, String)>) -> Response {
+    match silences::get(&org_id, &silence_id).await {
+        Ok(silence) => MetaHttpResponse::json(silence),
+        Err(e) => e.into(),
+    }
+}
+
+/// CreateSilence
+#[utoipa::path(
+    post,
+    path = "/{org_id}/alerts/silences",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "CreateSilence",
+    summary = "Create alert silence",
+    description = "Creates a silence that suppresses alert notifications matching all of its matchers while its \
+                   schedule is active. Schedules are either a single start/end window or a recurring cron window \
+                   with a duration, e.g. every Sunday 02:00-04:00 UTC.",
+    security(("Authorization" = [])),
+    params(
+        ("org_id" = String, Path, description = "Organization ID"),
+    ),
+    request_body(content = Silence, description = "Silence", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", body = Silence),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error"),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Alerts", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Create alert silence", "category": "alerts"}))
+    )
+)]
+pub async fn create_silence(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(mut silence): Json<Silence>,
+) -> Response {
+    silence.created_by = user_email.user_id;
+    match silences::create(&org_id, silence).await {
+        Ok(silence) => MetaHttpResponse::json(
+            MetaHttpResponse::message(StatusCode::OK, "Silence saved")
+                .with_id(silence.id)
+                .with_name(silence.name),
+        ),
+        Err(e) => e.into(),
+    }
+}
+
+/// UpdateSilence
+#[utoipa::path(
+    put,
+    path = "/{org_id}/alerts/silences/{silence_id}",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "UpdateSilence",
+    summary = "Update alert silence",
+    description = "Updates the matchers, schedule or state of an existing alert silence.",
+    security(("Authorization" = [])),
+    params(
+        ("org_id" = String, Path, description = "Organization ID"),
+        ("silence_id" = String, Path, description = "Silence ID"),
+    ),
+    request_body(content = Silence, description = "Silence", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", body = Silence),
+        (status = 400, description = "Bad request"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Alerts", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Update alert silence", "category": "alerts"}))
+    )
+)]
+pub async fn update_silence(
+    Path((org_id, silence_id)): Path<(String, String)>,
+    Json(silence): Json<Silence>,
+) -> Response {
+    match silences::update(&org_id, &silence_id, silence).await {
+        Ok(_) => MetaHttpResponse::ok("Silence updated"),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteSilence
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/alerts/silences/{silence_id}",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "DeleteSilence",
+    summary = "Delete alert silence",
+    description = "Deletes an alert silence. Notifications it suppressed stay recorded in the alert history.",
+    security(("Authorization" = [])),
+    params(
+        ("org_id" = String, Path, description = "Organization ID"),
+        ("silence_id" = String, Path, description = "Silence ID"),
+    ),
+    responses(
+        (status = 200, description = "Success"),
+        (status = 404, description = "Not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Alerts", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete alert silence", "category": "alerts"}))
+    )
+)]
+pub async fn delete_silence(Path((org_id, silence_id)): Path<(String, String)>) -> Response {
+    match silences::delete(&org_id, &silence_id).await {
+        Ok(()) => MetaHttpResponse::ok("Silence deleted"),
+        Err(e) => e.into(),
+    }
+}
@@ -17,7 +17,7 @@ use std::io::Read;
 
 use axum::{
     body::Bytes,
-    extract::Path,
+    extract::{Path, Query},
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
@@ -25,10 +25,22 @@ use config::axum::middlewares::{get_process_time, insert_process_time_header};
 use flate2::read::GzDecoder;
 use prost::Message;
 use proto::loki_rpc;
+use tracing::Instrument;
 
 use crate::{
-    common::meta::loki::{LokiError, LokiPushRequest},
-    handler::http::request::{CONTENT_TYPE_JSON, CONTENT_TYPE_PROTO},
+    common::{
+        meta::loki::{
+            LokiDataResponse, LokiError, LokiLabelsRequest, LokiPushRequest, LokiQueryRequest,
+            LokiQueryResponse, LokiRangeQueryRequest,
+        },
+        utils::{auth::UserEmail, http::get_or_create_trace_id},
+    },
+    handler::http::{
+        extractors::Headers,
+        request::{
+            CONTENT_TYPE_JSON, CONTENT_TYPE_PROTO, search::error_utils::map_error_to_http_response,
+        },
+    },
     service::{ingestion::get_thread_id, logs},
 };
 
@@ -127,6 +139,431 @@ pub async fn loki_push(Path(org_id): Path<String>, headers: HeaderMap, body: Byt
     resp
 }
 
+/// Loki-compatible LogQL range query
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/query_range",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LokiQueryRange",
+    summary = "Query logs via Loki API",
+    description = "Evaluates a LogQL query over a time range. Log queries return matching entries grouped into \
+                   streams, metric queries (count_over_time, rate, bytes_over_time, bytes_rate with optional \
+                   sum/avg/min/max/count aggregations) return a matrix. The stream is selected by the \
+                   'o2_stream_name' label and defaults to the 'default' stream.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("query" = String, Query, description = "LogQL query"),
+        ("start" = Option<String>, Query, description = "Start timestamp, nanosecond unix epoch or RFC3339, default one hour ago"),
+        ("end" = Option<String>, Query, description = "End timestamp, nanosecond unix epoch or RFC3339, default now"),
+        ("step" = Option<String>, Query, description = "Query resolution step as duration or float number of seconds"),
+        ("limit" = Option<i64>, Query, description = "Max number of entries returned by log queries, default 100"),
+        ("direction" = Option<String>, Query, description = "Sort order of log queries: forward or backward (default)"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": {
+                "resultType": "streams",
+                "result": [{
+                    "stream": {"service": "api"},
+                    "values": [["1609459200000000000", "API request processed successfully"]]
+                }],
+                "stats": {}
+            }
+        })),
+        (status = 400, description = "Bad Request - invalid query or parameters", content_type = "text/plain", body = String),
+        (status = 500, description = "Internal Server Error", content_type = "text/plain", body = String),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_query_range_get(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiRangeQueryRequest>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+) -> Response {
+    loki_query_range(&org_id, req, &user_email.user_id, &headers).await
+}
+
+pub async fn loki_query_range_post(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiRangeQueryRequest>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    axum::Form(form): axum::Form<LokiRangeQueryRequest>,
+) -> Response {
+    let req = if form.query.is_some() { form } else { req };
+    loki_query_range(&org_id, req, &user_email.user_id, &headers).await
+}
+
+async fn loki_query_range(
+    org_id: &str,
+    req: LokiRangeQueryRequest,
+    user_id: &str,
+    headers: &HeaderMap,
+) -> Response {
+    let http_span = query_span(org_id, "query_range");
+    let trace_id = get_or_create_trace_id(headers, &http_span);
+    #[cfg(feature = "enterprise")]
+    {
+        if let Some(resp) = check_query_permissions(org_id, user_id, req.query.as_deref()).await {
+            return resp;
+        }
+    }
+    let result = logs::logql::query_range(&trace_id, org_id, Some(user_id.to_string()), &req)
+        .instrument(http_span)
+        .await;
+    query_response(&trace_id, result.map(LokiQueryResponse::success))
+}
+
+/// Loki-compatible LogQL instant query
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/query",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LokiQuery",
+    summary = "Instant metric query via Loki API",
+    description = "Evaluates a LogQL metric query at a single point in time and returns a vector. Like Loki, log \
+                   queries are only supported by the range query endpoint.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("query" = String, Query, description = "LogQL metric query"),
+        ("time" = Option<String>, Query, description = "Evaluation timestamp, nanosecond unix epoch or RFC3339, default now"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": {
+                "resultType": "vector",
+                "result": [{"metric": {"level": "error"}, "value": [1609459200.0, "42"]}],
+                "stats": {}
+            }
+        })),
+        (status = 400, description = "Bad Request - invalid query or parameters", content_type = "text/plain", body = String),
+        (status = 500, description = "Internal Server Error", content_type = "text/plain", body = String),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_query_get(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiQueryRequest>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+) -> Response {
+    loki_query(&org_id, req, &user_email.user_id, &headers).await
+}
+
+pub async fn loki_query_post(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiQueryRequest>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    axum::Form(form): axum::Form<LokiQueryRequest>,
+) -> Response {
+    let req = if form.query.is_some() { form } else { req };
+    loki_query(&org_id, req, &user_email.user_id, &headers).await
+}
+
+async fn loki_query(
+    org_id: &str,
+    req: LokiQueryRequest,
+    user_id: &str,
+    headers: &HeaderMap,
+) -> Response {
+    let http_span = query_span(org_id, "query");
+    let trace_id = get_or_create_trace_id(headers, &http_span);
+    #[cfg(feature = "enterprise")]
+    {
+        if let Some(resp) = check_query_permissions(org_id, user_id, req.query.as_deref()).await {
+            return resp;
+        }
+    }
+    let result = logs::logql::query_instant(&trace_id, org_id, Some(user_id.to_string()), &req)
+        .instrument(http_span)
+        .await;
+    query_response(&trace_id, result.map(LokiQueryResponse::success))
+}
+
+/// Loki-compatible label names
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/labels",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LokiLabels",
+    summary = "List label names via Loki API",
+    description = "Lists the label names known for the stream selected by the optional query, or for all logs \
+                   streams of the organization.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("query" = Option<String>, Query, description = "Optional stream selector"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": ["level", "o2_stream_name", "service"]
+        })),
+        (status = 400, description = "Bad Request - invalid query", content_type = "text/plain", body = String),
+        (status = 500, description = "Internal Server Error", content_type = "text/plain", body = String),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_labels(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiLabelsRequest>,
+    Headers(_user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+) -> Response {
+    let http_span = query_span(&org_id, "labels");
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    #[cfg(feature = "enterprise")]
+    {
+        if let Some(resp) =
+            check_query_permissions(&org_id, &_user_email.user_id, req.query.as_deref()).await
+        {
+            return resp;
+        }
+    }
+    let result = logs::logql::labels(&org_id, &req)
+        .instrument(http_span)
+        .await;
+    query_response(&trace_id, result.map(LokiDataResponse::success))
+}
+
+/// Loki-compatible label values
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/label/{label_name}/values",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LokiLabelValues",
+    summary = "List label values via Loki API",
+    description = "Lists the distinct values of a label within the time range, scoped by the optional stream \
+                   selector. Values of 'o2_stream_name' are the logs streams of the organization.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("label_name" = String, Path, description = "Label name"),
+        ("query" = Option<String>, Query, description = "Optional stream selector"),
+        ("start" = Option<String>, Query, description = "Start timestamp, nanosecond unix epoch or RFC3339, default one hour ago"),
+        ("end" = Option<String>, Query, description = "End timestamp, nanosecond unix epoch or RFC3339, default now"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": ["error", "info", "warn"]
+        })),
+        (status = 400, description = "Bad Request - invalid query or parameters", content_type = "text/plain", body = String),
+        (status = 500, description = "Internal Server Error", content_type = "text/plain", body = String),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_label_values(
+    Path((org_id, label_name)): Path<(String, String)>,
+    Query(req): Query<LokiLabelsRequest>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+) -> Response {
+    let http_span = query_span(&org_id, "label_values");
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    #[cfg(feature = "enterprise")]
+    {
+        if let Some(resp) =
+            check_query_permissions(&org_id, &user_email.user_id, req.query.as_deref()).await
+        {
+            return resp;
+        }
+    }
+    let result = logs::logql::label_values(
+        &trace_id,
+        &org_id,
+        Some(user_email.user_id),
+        &label_name,
+        &req,
+    )
+    .instrument(http_span)
+    .await;
+    query_response(&trace_id, result.map(LokiDataResponse::success))
+}
+
+/// Loki-compatible series
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/series",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LokiSeries",
+    summary = "List series via Loki API",
+    description = "Lists the distinct label sets matching the 'match[]' stream selector within the time range. \
+                   Label sets consist of the labels used in the selector and 'o2_stream_name'.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("match[]" = String, Query, description = "Stream selector"),
+        ("start" = Option<String>, Query, description = "Start timestamp, nanosecond unix epoch or RFC3339, default one hour ago"),
+        ("end" = Option<String>, Query, description = "End timestamp, nanosecond unix epoch or RFC3339, default now"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": [{"o2_stream_name": "default", "service": "api"}]
+        })),
+        (status = 400, description = "Bad Request - invalid query or parameters", content_type = "text/plain", body = String),
+        (status = 500, description = "Internal Server Error", content_type = "text/plain", body = String),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_series_get(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiLabelsRequest>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+) -> Response {
+    loki_series(&org_id, req, &user_email.user_id, &headers).await
+}
+
+pub async fn loki_series_post(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiLabelsRequest>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    axum::Form(form): axum::Form<LokiLabelsRequest>,
+) -> Response {
+    let req = if form.matcher.is_some() { form } else { req };
+    loki_series(&org_id, req, &user_email.user_id, &headers).await
+}
+
+async fn loki_series(
+    org_id: &str,
+    req: LokiLabelsRequest,
+    user_id: &str,
+    headers: &HeaderMap,
+) -> Response {
+    let http_span = query_span(org_id, "series");
+    let trace_id = get_or_create_trace_id(headers, &http_span);
+    #[cfg(feature = "enterprise")]
+    {
+        if let Some(resp) = check_query_permissions(org_id, user_id, req.matcher.as_deref()).await {
+            return resp;
+        }
+    }
+    let result = logs::logql::series(&trace_id, org_id, Some(user_id.to_string()), &req)
+        .instrument(http_span)
+        .await;
+    query_response(&trace_id, result.map(LokiDataResponse::success))
+}
+
+fn query_span(org_id: &str, endpoint: &str) -> tracing::Span {
+    if config::get_config().common.should_create_span() {
+        tracing::info_span!(
+            "/api/{org_id}/loki/api/v1/{endpoint}",
+            org_id = org_id.to_string(),
+            endpoint = endpoint.to_string()
+        )
+    } else {
+        tracing::Span::none()
+    }
+}
+
+fn query_response<T: serde::Serialize>(trace_id: &str, result: Result<T, LokiError>) -> Response {
+    match result {
+        Ok(data) => axum::Json(data).into_response(),
+        Err(LokiError::Search { source }) => {
+            log::error!("[trace_id {trace_id}] [Loki] query error: {source}");
+            map_error_to_http_response(&source, Some(trace_id.to_string()))
+        }
+        Err(e) => {
+            log::warn!("[trace_id {trace_id}] [Loki] invalid query: {e}");
+            e.into_response()
+        }
+    }
+}
+
+/// Checks that the search is allowed and that the user can read the stream
+/// selected by the query.
+#[cfg(feature = "enterprise")]
+async fn check_query_permissions(
+    org_id: &str,
+    user_id: &str,
+    query: Option<&str>,
+) -> Option<Response> {
+    use config::meta::stream::StreamType;
+    use o2_openfga::meta::mapping::OFGA_MODELS;
+
+    use crate::{
+        common::{
+            meta::http::HttpResponse as MetaHttpResponse,
+            utils::auth::{AuthExtractor, is_root_user},
+        },
+        service::db::org_users::get_cached_user_org,
+    };
+
+    if let Err(e) = crate::service::search::check_search_allowed(org_id, None) {
+        return Some(MetaHttpResponse::too_many_requests(e));
+    }
+    if is_root_user(user_id) {
+        return None;
+    }
+    let Some(query) = query.filter(|q| !q.trim().is_empty()) else {
+        return None;
+    };
+    let stream = match logs::logql::query_stream(query) {
+        Ok(stream) => stream,
+        Err(e) => return Some(e.into_response()),
+    };
+    let Some(user) = get_cached_user_org(org_id, user_id) else {
+        return Some(MetaHttpResponse::forbidden("Unauthorized Access"));
+    };
+    let stream_type_str = StreamType::Logs.as_str();
+    if !crate::handler::http::auth::validator::check_permissions(
+        user_id,
+        AuthExtractor {
+            auth: "".to_string(),
+            method: "GET".to_string(),
+            o2_type: format!(
+                "{}:{}",
+                OFGA_MODELS
+                    .get(stream_type_str)
+                    .map_or(stream_type_str, |model| model.key),
+                stream
+            ),
+            org_id: org_id.to_string(),
+            bypass_check: false,
+            parent_id: "".to_string(),
+            use_all_org: false,
+            use_self_context: false,
+            use_self_parent: true,
+        },
+        user.role,
+        user.is_external,
+    )
+    .await
+    {
+        return Some(MetaHttpResponse::forbidden("Unauthorized Access"));
+    }
+    None
+}
+
 fn parse_json_request(
     content_encoding: Option<&str>,
     body: Bytes,
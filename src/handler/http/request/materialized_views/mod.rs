@@ -0,0 +1,152 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use axum::{Json, extract::Path, response::Response};
+use config::meta::materialized_view::{MaterializedViewList, MaterializedViewRequest};
+
+use crate::{common::meta::http::HttpResponse as MetaHttpResponse, service::materialized_views};
+
+/// CreateMaterializedView
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/materialized_views",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "CreateMaterializedView",
+    summary = "Create materialized view",
+    description = "Creates a view keeping the partial aggregates of a `SELECT histogram(_timestamp, '<interval>'), dims.., aggregates.. FROM stream GROUP BY ..` query over a logs stream. The view is updated every time data of the stream is persisted, from the creation time on, and dashboard queries matching it are answered from the view. Supported aggregates are count, sum, min, max and avg",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(MaterializedViewRequest), description = "View name and defining query", content_type = "application/json", example = json!({
+        "name": "nginx_status_1m",
+        "sql": "SELECT histogram(_timestamp, '1 minute') AS ts, status, count(*) AS requests, avg(took) AS avg_took FROM nginx GROUP BY ts, status"
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "create"}))
+    )
+)]
+pub async fn create(
+    Path(org_id): Path<String>,
+    Json(req): Json<MaterializedViewRequest>,
+) -> Response {
+    match materialized_views::create(&org_id, req).await {
+        Ok(view) => MetaHttpResponse::json(view),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListMaterializedViews
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/materialized_views",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "ListMaterializedViews",
+    summary = "List materialized views",
+    description = "Lists the materialized views of the organization with their base stream, bucket interval, dimensions and aggregates",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(MaterializedViewList)),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "list"}))
+    )
+)]
+pub async fn list(Path(org_id): Path<String>) -> Response {
+    match materialized_views::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(MaterializedViewList { list }),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// GetMaterializedView
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/materialized_views/{name}",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "GetMaterializedView",
+    summary = "Get materialized view",
+    description = "Retrieves the definition of a materialized view",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "View name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"}))
+    )
+)]
+pub async fn get(Path((org_id, name)): Path<(String, String)>) -> Response {
+    match materialized_views::get(&org_id, &name).await {
+        Ok(Some(view)) => MetaHttpResponse::json(view),
+        Ok(None) => MetaHttpResponse::not_found("materialized view not found"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// DeleteMaterializedView
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/materialized_views/{name}",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "DeleteMaterializedView",
+    summary = "Delete materialized view",
+    description = "Stops maintaining a materialized view and no longer answers queries from it. The stream holding the view data is kept and can be deleted like any other stream",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "View name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "delete"}))
+    )
+)]
+pub async fn delete(Path((org_id, name)): Path<(String, String)>) -> Response {
+    match materialized_views::delete(&org_id, &name).await {
+        Ok(true) => MetaHttpResponse::ok("materialized view deleted"),
+        Ok(false) => MetaHttpResponse::not_found("materialized view not found"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
@@ -42,6 +42,7 @@ pub mod kv;
 #[cfg(feature = "enterprise")]
 pub mod license;
 pub mod logs;
+pub mod materialized_views;
 pub mod mcp;
 pub mod metrics;
 pub mod model_pricing;
@@ -74,6 +75,7 @@ pub mod users;
 
 pub const CONTENT_TYPE_JSON: &str = "application/json";
 pub const CONTENT_TYPE_PROTO: &str = "application/x-protobuf";
+pub const CONTENT_TYPE_THRIFT: &str = "application/x-thrift";
 
 // these are the common bulk delete req/res structs
 
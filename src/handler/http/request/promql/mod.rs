@@ -26,6 +26,7 @@ use config::{
 use futures::StreamExt;
 use infra::errors;
 use promql_parser::parser;
+use proto::prometheus_rpc::read_request::ResponseType;
 #[cfg(feature = "enterprise")]
 use {config::meta::stream::StreamType, o2_openfga::meta::mapping::OFGA_MODELS};
 
@@ -37,10 +38,15 @@ use crate::{
     handler::http::{
         extractors::Headers, request::search::error_utils::map_error_to_http_response,
     },
-    service::{metrics, promql},
+    service::{metrics, metrics::prom::RemoteWriteProtocol, promql},
 };
 
+pub mod rules;
+pub mod scrape;
+
 /// prometheus remote-write endpoint for metrics
+
+// refer: https://prometheus.io/docs/concepts/remote_write_spec_2_0/
 #[utoipa::path(
     post,
     path = "/{org_id}/prometheus/api/v1/write",
@@ -48,16 +54,19 @@ use crate::{
     tag = "Metrics",
     operation_id = "PrometheusRemoteWrite",
     summary = "Ingest Prometheus metrics",
-    description = "Receives Prometheus metrics data via remote write protocol. Accepts protobuf-encoded WriteRequest payloads containing time series data and stores them for querying. Compatible with standard Prometheus remote write configuration.",
+    description = "Receives Prometheus metrics data via remote write protocol. Accepts snappy compressed protobuf payloads containing time series data and stores them for querying. Both the 1.0 prometheus.WriteRequest and the 2.0 io.prometheus.write.v2.Request messages are supported, selected by the proto parameter of the Content-Type header, including native histograms and exemplars. Compatible with standard Prometheus remote write configuration.",
         security(
         ("Authorization"= [])
     ),
     params(
         ("org_id" = String, Path, description = "Organization name"),
     ),
-    request_body(content = String, description = "prometheus WriteRequest", content_type = "application/x-protobuf"),
+    request_body(content = String, description = "prometheus WriteRequest or io.prometheus.write.v2.Request", content_type = "application/x-protobuf"),
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200})),
+        (status = 204, description = "Success of a 2.0 request, the written samples, histograms and exemplars are reported in the X-Prometheus-Remote-Write-*-Written headers"),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 415, description = "Unsupported Media Type", content_type = "application/json", body = ()),
         (status = 500, description = "Failure", content_type = "application/json", body = ()),
     ),
     extensions(
@@ -75,14 +84,156 @@ pub async fn remote_write(
         .get("Content-Type")
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
-    if content_type == "application/x-protobuf" {
-        match metrics::prom::remote_write(&org_id, body, user).await {
-            Ok(_) => StatusCode::OK.into_response(),
-            Err(e) => MetaHttpResponse::bad_request(e),
-        }
+    let Some(protocol) = RemoteWriteProtocol::from_content_type(content_type) else {
+        return MetaHttpResponse::unsupported_media_type(format!(
+            "unsupported content type: {content_type}"
+        ));
+    };
+    let content_encoding = headers
+        .get("Content-Encoding")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("snappy");
+    if !content_encoding.eq_ignore_ascii_case("snappy") {
+        return MetaHttpResponse::unsupported_media_type(format!(
+            "unsupported content encoding: {content_encoding}"
+        ));
+    }
+    match metrics::prom::remote_write(&org_id, body, user, protocol).await {
+        Ok(stats) if protocol == RemoteWriteProtocol::V2 => Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .header("X-Prometheus-Remote-Write-Samples-Written", stats.samples)
+            .header(
+                "X-Prometheus-Remote-Write-Histograms-Written",
+                stats.histograms,
+            )
+            .header(
+                "X-Prometheus-Remote-Write-Exemplars-Written",
+                stats.exemplars,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        Ok(_) => StatusCode::OK.into_response(),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// prometheus remote-read endpoint for metrics
+
+// refer: https://prometheus.io/docs/prometheus/latest/querying/remote_read_api/
+#[utoipa::path(
+    post,
+    path = "/{org_id}/prometheus/api/v1/read",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusRemoteRead",
+    summary = "Read Prometheus metrics",
+    description = "Returns raw samples for the series matching the label matchers of each query, via the Prometheus remote read protocol. Accepts snappy compressed protobuf ReadRequest payloads and answers with either a snappy compressed ReadResponse or streamed XOR chunks, depending on the accepted response types of the request.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = String, description = "prometheus ReadRequest", content_type = "application/x-protobuf"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/x-protobuf", body = String),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn remote_read(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let cfg = config::get_config();
+    let http_span = if cfg.common.should_create_span() {
+        tracing::info_span!(
+            "/api/{org_id}/prometheus/api/v1/read",
+            org_id = org_id.to_string()
+        )
     } else {
-        MetaHttpResponse::bad_request("Bad Request")
+        tracing::Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    #[cfg(feature = "enterprise")]
+    {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, None) {
+            return MetaHttpResponse::too_many_requests(e);
+        }
     }
+
+    let req = match metrics::remote_read::decode_request(&body) {
+        Ok(v) => v,
+        Err(e) => return MetaHttpResponse::bad_request(e),
+    };
+    let response_type = match metrics::remote_read::negotiate_response_type(&req) {
+        Ok(v) => v,
+        Err(e) => return MetaHttpResponse::bad_request(e),
+    };
+    let user_id = user_email.user_id;
+
+    if response_type == ResponseType::Samples {
+        let mut results = Vec::with_capacity(req.queries.len());
+        for query in req.queries.iter() {
+            match metrics::remote_read::query(&trace_id, &org_id, &user_id, query).await {
+                Ok(series) => results.push(series),
+                Err(e) => {
+                    log::error!("[trace_id {trace_id}] prometheus remote read error: {e}");
+                    return map_error_to_http_response(&e, Some(trace_id));
+                }
+            }
+        }
+        let body = match metrics::remote_read::encode_samples_response(results) {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[trace_id {trace_id}] prometheus remote read encode error: {e}");
+                return MetaHttpResponse::internal_error(e);
+            }
+        };
+        return Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "application/x-protobuf")
+            .header("content-encoding", "snappy")
+            .body(Body::from(body))
+            .unwrap();
+    }
+
+    // the streamed response can not report an error once it started, it is
+    // terminated instead and the client fails on the incomplete frame
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(4);
+    tokio::spawn(async move {
+        for (index, query) in req.queries.iter().enumerate() {
+            let series =
+                match metrics::remote_read::query(&trace_id, &org_id, &user_id, query).await {
+                    Ok(v) => v,
+                    Err(e) => {
+                        log::error!("[trace_id {trace_id}] prometheus remote read error: {e}");
+                        let _ = tx.send(Err(std::io::Error::other(e.to_string()))).await;
+                        return;
+                    }
+                };
+            for ts in series.iter() {
+                let frames = metrics::remote_read::encode_chunked_frames(index as i64, ts);
+                if tx.send(Ok(Bytes::from(frames))).await.is_err() {
+                    // client went away
+                    return;
+                }
+            }
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", metrics::remote_read::CONTENT_TYPE_STREAMED)
+        .body(Body::from_stream(
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+        ))
+        .unwrap()
 }
 
 /// prometheus instant queries
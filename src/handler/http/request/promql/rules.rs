@@ -0,0 +1,430 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use config::meta::promql::{
+    ApiFuncResponse,
+    rules::{RequestRules, RequestSetRuleGroups, RuleType},
+};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::{
+        alerts::alert::AlertError,
+        metrics::rules::{self, RuleError},
+    },
+};
+
+/// prometheus recording and alerting rules
+
+// refer: https://prometheus.io/docs/prometheus/latest/querying/api/#rules
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/api/v1/rules",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusRules",
+    summary = "List Prometheus rules",
+    description = "Lists the loaded Prometheus recording and alerting rules with their evaluation health, in the format of the Prometheus rules API. Alerting rules report the state of the alert they are mapped to.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Only return the alerting rules (alert) or the recording rules (record)"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": {
+                "groups": [
+                    {
+                        "name": "example",
+                        "file": "default",
+                        "rules": [
+                            {
+                                "name": "job:http_requests:rate5m",
+                                "query": "sum by (job) (rate(http_requests_total[5m]))",
+                                "labels": {},
+                                "health": "ok",
+                                "evaluationTime": 0.012,
+                                "lastEvaluation": "2026-01-01T00:00:00.000Z",
+                                "type": "recording"
+                            }
+                        ],
+                        "interval": 60.0,
+                        "evaluationTime": 0.012,
+                        "lastEvaluation": "2026-01-01T00:00:00.000Z"
+                    }
+                ]
+            }
+        })),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List Prometheus recording and alerting rules", "category": "metrics"}))
+    )
+)]
+pub async fn rules(Path(org_id): Path<String>, Query(req): Query<RequestRules>) -> Response {
+    let rule_type = match req.rule_type.as_deref() {
+        None | Some("") => None,
+        Some("alert") => Some(RuleType::Alerting),
+        Some("record") => Some(RuleType::Recording),
+        Some(v) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiFuncResponse::<()>::err_bad_data(
+                    format!("unsupported type: {v}"),
+                    None,
+                )),
+            )
+                .into_response();
+        }
+    };
+    match rules::list_rules(&org_id, rule_type).await {
+        Ok(data) => (StatusCode::OK, axum::Json(ApiFuncResponse::ok(data, None))).into_response(),
+        Err(e) => {
+            log::error!("list prometheus rules failed: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                axum::Json(ApiFuncResponse::<()>::err_internal(e, None)),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// prometheus active alerts
+
+// refer: https://prometheus.io/docs/prometheus/latest/querying/api/#alerts
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/api/v1/alerts",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusAlerts",
+    summary = "List Prometheus alerts",
+    description = "Lists the firing alerts of the loaded Prometheus alerting rules, in the format of the Prometheus alerts API.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": {
+                "alerts": [
+                    {
+                        "labels": {"alertname": "InstanceDown", "severity": "page"},
+                        "annotations": {"summary": "Instance down"},
+                        "state": "firing",
+                        "activeAt": "2026-01-01T00:00:00.000Z",
+                        "value": ""
+                    }
+                ]
+            }
+        })),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List firing Prometheus alerts", "category": "metrics"}))
+    )
+)]
+pub async fn alerts(Path(org_id): Path<String>) -> Response {
+    match rules::list_alerts(&org_id).await {
+        Ok(data) => (StatusCode::OK, axum::Json(ApiFuncResponse::ok(data, None))).into_response(),
+        Err(e) => {
+            log::error!("list prometheus alerts failed: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                axum::Json(ApiFuncResponse::<()>::err_internal(e, None)),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// List the rule groups of all namespaces
+
+// refer: https://grafana.com/docs/mimir/latest/references/http-api/#list-rule-groups
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/config/v1/rules",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusListRuleGroups",
+    summary = "List Prometheus rule groups",
+    description = "Returns the loaded Prometheus rule groups of all namespaces as YAML, keyed by namespace.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/yaml", body = String),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn list_rule_groups(Path(org_id): Path<String>) -> Response {
+    match rules::get_rule_groups(&org_id, None).await {
+        Ok(namespaces) => yaml_response(&namespaces),
+        Err(e) => rule_error_response(e),
+    }
+}
+
+/// List the rule groups of a namespace
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/config/v1/rules/{namespace}",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusGetRuleNamespace",
+    summary = "Get Prometheus rule namespace",
+    description = "Returns the loaded Prometheus rule groups of a namespace as YAML.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("namespace" = String, Path, description = "Rule namespace"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/yaml", body = String),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn get_namespace(Path((org_id, namespace)): Path<(String, String)>) -> Response {
+    match rules::get_rule_groups(&org_id, Some(&namespace)).await {
+        Ok(namespaces) if namespaces.is_empty() => {
+            MetaHttpResponse::not_found(format!("Rule namespace {namespace} not found"))
+        }
+        Ok(namespaces) => yaml_response(&namespaces),
+        Err(e) => rule_error_response(e),
+    }
+}
+
+/// Load rule groups into a namespace
+
+// refer: https://grafana.com/docs/mimir/latest/references/http-api/#set-rule-group
+#[utoipa::path(
+    post,
+    path = "/{org_id}/prometheus/config/v1/rules/{namespace}",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusSetRuleGroups",
+    summary = "Load Prometheus rule groups",
+    description = "Loads a Prometheus rule file (with a top level groups list) or a single rule group into a namespace, replacing the groups with the same names. Recording rules are evaluated by the scheduler and write their results into metric streams. Alerting rules are created as PromQL alerts notifying the given destinations.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("namespace" = String, Path, description = "Rule namespace"),
+        ("destinations" = Option<String>, Query, description = "Comma separated alert destinations, required by alerting rules unless the group is already loaded"),
+    ),
+    request_body(content = String, description = "Prometheus rule file or rule group", content_type = "application/yaml"),
+    responses(
+        (status = 202, description = "Accepted", content_type = "application/json", body = Object, example = json!({"code": 202, "message": "Rule groups loaded"})),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "create"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn set_rule_groups(
+    Path((org_id, namespace)): Path<(String, String)>,
+    Query(req): Query<RequestSetRuleGroups>,
+    body: Bytes,
+) -> Response {
+    let groups = match rules::parse_rule_groups(&body) {
+        Ok(v) => v,
+        Err(e) => return MetaHttpResponse::bad_request(e),
+    };
+    let destinations = req
+        .destinations
+        .unwrap_or_default()
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>();
+    match rules::set_rule_groups(&org_id, &namespace, groups, &destinations).await {
+        Ok(()) => (
+            StatusCode::ACCEPTED,
+            axum::Json(MetaHttpResponse::message(
+                StatusCode::ACCEPTED,
+                "Rule groups loaded",
+            )),
+        )
+            .into_response(),
+        Err(e) => rule_error_response(e),
+    }
+}
+
+/// Delete the rule groups of a namespace
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/prometheus/config/v1/rules/{namespace}",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusDeleteRuleNamespace",
+    summary = "Delete Prometheus rule namespace",
+    description = "Deletes all the rule groups of a namespace, together with the alerts and the recording rule evaluations they created.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("namespace" = String, Path, description = "Rule namespace"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn delete_namespace(Path((org_id, namespace)): Path<(String, String)>) -> Response {
+    match rules::delete_namespace(&org_id, &namespace).await {
+        Ok(()) => MetaHttpResponse::ok("Rule namespace deleted"),
+        Err(e) => rule_error_response(e),
+    }
+}
+
+/// Get a rule group
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/config/v1/rules/{namespace}/{group}",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusGetRuleGroup",
+    summary = "Get Prometheus rule group",
+    description = "Returns a loaded Prometheus rule group as YAML.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("namespace" = String, Path, description = "Rule namespace"),
+        ("group" = String, Path, description = "Rule group name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/yaml", body = String),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn get_rule_group(
+    Path((org_id, namespace, group)): Path<(String, String, String)>,
+) -> Response {
+    match rules::get_rule_group(&org_id, &namespace, &group).await {
+        Ok(group) => yaml_response(&group),
+        Err(e) => rule_error_response(e),
+    }
+}
+
+/// Delete a rule group
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/prometheus/config/v1/rules/{namespace}/{group}",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusDeleteRuleGroup",
+    summary = "Delete Prometheus rule group",
+    description = "Deletes a rule group, together with the alerts and the recording rule evaluation it created.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("namespace" = String, Path, description = "Rule namespace"),
+        ("group" = String, Path, description = "Rule group name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn delete_rule_group(
+    Path((org_id, namespace, group)): Path<(String, String, String)>,
+) -> Response {
+    match rules::delete_rule_group(&org_id, &namespace, &group).await {
+        Ok(()) => MetaHttpResponse::ok("Rule group deleted"),
+        Err(e) => rule_error_response(e),
+    }
+}
+
+pub(super) fn yaml_response<T: serde::Serialize>(value: &T) -> Response {
+    match serde_yaml::to_string(value) {
+        Ok(body) => Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "application/yaml")
+            .body(Body::from(body))
+            .unwrap(),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+fn rule_error_response(e: RuleError) -> Response {
+    match e {
+        RuleError::RuleGroupNotFound { .. } => MetaHttpResponse::not_found(e),
+        RuleError::InfraError(_)
+        | RuleError::Alert {
+            source: AlertError::InfraError(_),
+            ..
+        } => {
+            log::error!("prometheus rules error: {e}");
+            MetaHttpResponse::internal_error(e)
+        }
+        _ => MetaHttpResponse::bad_request(e),
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{body::Bytes, extract::Path, response::Response};
+
+use super::rules::yaml_response;
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::metrics::scrape::{self, ScrapeError},
+};
+
+/// List the scrape configs
+
+// refer: https://prometheus.io/docs/prometheus/latest/configuration/configuration/#scrape_config
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/config/v1/scrape",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusListScrapeConfigs",
+    summary = "List Prometheus scrape configs",
+    description = "Returns the scrape jobs of the organization as YAML, in the format of the scrape_configs section of a Prometheus configuration.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/yaml", body = String),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn list_scrape_configs(Path(org_id): Path<String>) -> Response {
+    match scrape::list_scrape_configs(&org_id).await {
+        Ok(configs) => yaml_response(&configs),
+        Err(e) => scrape_error_response(e),
+    }
+}
+
+/// Create scrape configs
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/prometheus/config/v1/scrape",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusSetScrapeConfigs",
+    summary = "Create Prometheus scrape configs",
+    description = "Creates the scrape jobs of a Prometheus scrape_configs section, or of a single scrape config, replacing the jobs with the same names. The targets of each job are scraped by the scheduler every scrape interval and their samples written into metric streams, together with the up and scrape_duration_seconds series of every target.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = String, description = "Prometheus scrape_configs or scrape config", content_type = "application/yaml"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200, "message": "Scrape configs saved"})),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "create"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn set_scrape_configs(Path(org_id): Path<String>, body: Bytes) -> Response {
+    let configs = match scrape::parse_scrape_configs(&body) {
+        Ok(v) => v,
+        Err(e) => return MetaHttpResponse::bad_request(e),
+    };
+    match scrape::set_scrape_configs(&org_id, configs).await {
+        Ok(()) => MetaHttpResponse::ok("Scrape configs saved"),
+        Err(e) => scrape_error_response(e),
+    }
+}
+
+/// Get a scrape config
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/config/v1/scrape/{job_name}",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusGetScrapeConfig",
+    summary = "Get Prometheus scrape config",
+    description = "Returns a scrape job as YAML.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("job_name" = String, Path, description = "Scrape job name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/yaml", body = String),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn get_scrape_config(Path((org_id, job_name)): Path<(String, String)>) -> Response {
+    match scrape::get_scrape_config(&org_id, &job_name).await {
+        Ok(config) => yaml_response(&config),
+        Err(e) => scrape_error_response(e),
+    }
+}
+
+/// Delete a scrape config
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/prometheus/config/v1/scrape/{job_name}",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusDeleteScrapeConfig",
+    summary = "Delete Prometheus scrape config",
+    description = "Deletes a scrape job and stops scraping its targets.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("job_name" = String, Path, description = "Scrape job name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn delete_scrape_config(Path((org_id, job_name)): Path<(String, String)>) -> Response {
+    match scrape::delete_scrape_config(&org_id, &job_name).await {
+        Ok(()) => MetaHttpResponse::ok("Scrape config deleted"),
+        Err(e) => scrape_error_response(e),
+    }
+}
+
+fn scrape_error_response(e: ScrapeError) -> Response {
+    match e {
+        ScrapeError::ScrapeConfigNotFound(_) => MetaHttpResponse::not_found(e),
+        ScrapeError::Disabled => MetaHttpResponse::forbidden(e),
+        ScrapeError::InfraError(_) => {
+            log::error!("prometheus scrape error: {e}");
+            MetaHttpResponse::internal_error(e)
+        }
+        _ => MetaHttpResponse::bad_request(e),
+    }
+}
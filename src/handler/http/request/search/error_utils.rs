@@ -27,14 +27,14 @@ use crate::{
 pub fn map_error_to_http_response(err: &errors::Error, trace_id: Option<String>) -> Response {
     match err {
         errors::Error::ErrorCode(code) => match code {
-            errors::ErrorCodes::SearchCancelQuery(_) | errors::ErrorCodes::RatelimitExceeded(_) => {
-                (
-                    StatusCode::TOO_MANY_REQUESTS,
-                    [(ERROR_HEADER, code.to_json())],
-                    Json(MetaHttpResponse::error_code_with_trace_id(code, trace_id)),
-                )
-                    .into_response()
-            }
+            errors::ErrorCodes::SearchCancelQuery(_)
+            | errors::ErrorCodes::RatelimitExceeded(_)
+            | errors::ErrorCodes::SearchScanBudgetExceeded(_) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(ERROR_HEADER, code.to_json())],
+                Json(MetaHttpResponse::error_code_with_trace_id(code, trace_id)),
+            )
+                .into_response(),
             errors::ErrorCodes::SearchTimeout(_) => (
                 StatusCode::REQUEST_TIMEOUT,
                 [(ERROR_HEADER, code.to_json())],
@@ -118,6 +118,15 @@ mod tests {
         assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
     }
 
+    #[test]
+    fn test_map_error_to_http_response_scan_budget_exceeded() {
+        let err = errors::Error::ErrorCode(errors::ErrorCodes::SearchScanBudgetExceeded(
+            "daily budget of 1 GB reached".to_string(),
+        ));
+        let response = map_error_to_http_response(&err, None);
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
     #[test]
     fn test_map_error_to_http_response_search_timeout() {
         let err = errors::Error::ErrorCode(errors::ErrorCodes::SearchTimeout(
@@ -0,0 +1,162 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query},
+    http::{HeaderMap, header},
+    response::Response,
+};
+#[cfg(feature = "enterprise")]
+use config::meta::sql::resolve_stream_names;
+use config::{get_config, meta::search::ExportRequest, utils::json};
+use futures::stream::StreamExt;
+use hashbrown::HashMap;
+use tokio::sync::mpsc;
+use tracing::Span;
+
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::{
+    StreamPermissionResourceType, check_stream_permissions,
+};
+use crate::{
+    common::utils::{
+        auth::UserEmail,
+        http::{get_or_create_trace_id, get_stream_type_from_request},
+    },
+    handler::http::{
+        extractors::Headers, request::search::error_utils::map_error_to_http_response,
+    },
+    service::search::export::{self, ExportProgress},
+};
+
+/// Pages of encoded rows buffered between the search and the client.
+const EXPORT_BUFFER_PAGES: usize = 2;
+
+/// Export search results
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_export",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchExport",
+    summary = "Export the whole result of a query",
+    description = "Streams every row of a query as CSV, NDJSON or Parquet, without the result size limit of the search API. The query is split by search partitions and ordered by _timestamp. An interrupted export resumes from a cursor `<timestamp>:<skip>`: the timestamp of the last row received and how many rows with that timestamp were received.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, default is logs"),
+    ),
+    request_body(content = String, description = "Export query", content_type = "application/json", example = json!({
+        "sql": "select * from logs",
+        "start_time": 1675182660872049i64,
+        "end_time": 1675185660872049i64,
+        "format": "csv",
+        "cursor": "1675182760872049:2"
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/octet-stream"),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn export(
+    Path(org_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let cfg = get_config();
+    let http_span = if cfg.common.should_create_span() {
+        tracing::info_span!("/api/{org_id}/_export", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = user_email.user_id;
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+
+    let mut req: ExportRequest = match json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => return map_error_to_http_response(&e.into(), Some(trace_id)),
+    };
+    if let Err(e) = req.decode() {
+        return map_error_to_http_response(&e.into(), Some(trace_id));
+    }
+    #[cfg(feature = "enterprise")]
+    {
+        let stream_names = match resolve_stream_names(&req.sql) {
+            Ok(v) => v,
+            Err(e) => return map_error_to_http_response(&e.into(), Some(trace_id)),
+        };
+        for stream_name in stream_names.iter() {
+            if let Some(res) = check_stream_permissions(
+                stream_name,
+                &org_id,
+                &user_id,
+                &stream_type,
+                StreamPermissionResourceType::Search,
+            )
+            .await
+            {
+                return res;
+            }
+        }
+    }
+
+    let plan = match export::plan(&trace_id, &org_id, stream_type, Some(user_id), req).await {
+        Ok(v) => v,
+        Err(e) => return map_error_to_http_response(&e, Some(trace_id)),
+    };
+    let format = plan.format();
+
+    let (tx, rx) = mpsc::channel(EXPORT_BUFFER_PAGES);
+    let task_trace_id = trace_id.clone();
+    tokio::spawn(async move {
+        let mut progress = ExportProgress::default();
+        if let Err(e) = plan.run(tx.clone(), &mut progress).await {
+            log::warn!(
+                "[trace_id {task_trace_id}] export stopped after {} rows, cursor: {}, error: {e}",
+                progress.rows,
+                progress
+                    .cursor
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "none".to_string())
+            );
+            // fail the body, so the client sees an incomplete download
+            let _ = tx.send(Err(e)).await;
+        }
+    });
+
+    let stream = tokio_stream::wrappers::ReceiverStream::new(rx)
+        .map(|data| data.map_err(std::io::Error::other));
+    Response::builder()
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"export_{trace_id}.{}\"",
+                format.extension()
+            ),
+        )
+        .body(Body::from_stream(stream))
+        .unwrap()
+}
@@ -76,6 +76,7 @@ use crate::{
 
 pub(crate) mod around;
 pub(crate) mod error_utils;
+pub mod export;
 pub mod multi_streams;
 pub mod query_manager;
 pub mod saved_view;
@@ -184,6 +185,7 @@ async fn can_use_distinct_stream(
         ("is_ui_histogram" = Option<bool>, Query, description = "Whether to return histogram data for UI (default: false)"),
         ("is_multi_stream_search" = Option<bool>, Query, description = "Indicate is search is for multi stream (default: false)"),
         ("validate" = Option<bool>, Query, description = "Validate query fields against stream schema and User-Defined Schema (UDS). When enabled, returns error if queried fields are not in schema or not allowed by UDS (default: false)"),
+        ("dry_run" = Option<bool>, Query, description = "Return the estimated files, bytes, row groups and nodes the query would scan and whether the results cache would hit, instead of running it (default: false)"),
     ),
     request_body(content = inline(Request), description = "Search query", content_type = "application/json", example = json!({
         "query": {
@@ -284,6 +286,7 @@ pub async fn search(
     let is_ui_histogram = get_is_ui_histogram_from_request(&url_query);
     let is_multi_stream_search = get_is_multi_stream_search_from_request(&url_query);
     let validate_query = utils::get_bool_from_request(&url_query, "validate");
+    let dry_run = utils::get_bool_from_request(&url_query, "dry_run");
 
     let dashboard_info = get_dashboard_info_from_request(&url_query);
 
@@ -485,6 +488,28 @@ pub async fn search(
         }
     }
 
+    if dry_run {
+        let res = SearchService::estimate::estimate(
+            &trace_id,
+            &org_id,
+            stream_type,
+            Some(user_id.to_string()),
+            &req,
+        )
+        .instrument(http_span)
+        .await;
+        return match res {
+            Ok(mut res) => {
+                res.took = start.elapsed().as_millis() as usize;
+                Json(res).into_response()
+            }
+            Err(err) => {
+                log::error!("[trace_id {trace_id}] search estimate error: {err}");
+                error_utils::map_error_to_http_response(&err, Some(trace_id))
+            }
+        };
+    }
+
     // run search with cache
     let res = SearchService::cache::search(
         &trace_id,
@@ -22,7 +22,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use config::{
-    meta::stream::{StreamType, TimeRange, UpdateStreamSettings},
+    meta::stream::{PredicateDeleteProgress, StreamType, TimeRange, UpdateStreamSettings},
     utils::schema::format_stream_name,
 };
 use hashbrown::HashMap;
@@ -36,7 +36,10 @@ use crate::{
         meta::{
             self,
             http::HttpResponse as MetaHttpResponse,
-            stream::{ListStream, StreamCreate, StreamDeleteFields, StreamUpdateFields},
+            stream::{
+                ListStream, StreamCreate, StreamDeleteByPredicate, StreamDeleteFields,
+                StreamUpdateFields,
+            },
         },
         utils::{
             auth::UserEmail,
@@ -867,6 +870,161 @@ pub async fn get_delete_stream_data_status(
     (StatusCode::OK, Json(response)).into_response()
 }
 
+/// StreamDeleteDataByPredicate
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/streams/{stream_name}/data_by_predicate",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamDeleteDataByPredicate",
+    summary = "Delete stream data by predicate",
+    description = "Creates a deletion job that permanently removes the rows matching a SQL WHERE clause within the specified time range. Only the files containing matching rows are rewritten. Returns a job ID to track the deletion progress",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = Option<String>, Query, description = "Stream type. one of: logs, metrics, traces. Defaults to logs."),
+    ),
+    request_body(content = inline(StreamDeleteByPredicate), description = "Rows to delete", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "delete"}))
+    )
+)]
+pub async fn delete_stream_data_by_predicate(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Json(req): Json<StreamDeleteByPredicate>,
+) -> Response {
+    let cfg = config::get_config();
+    let mut stream_name = stream_name;
+    if !cfg.common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let time_range = TimeRange::new(req.start, req.end);
+    let job_id = match crate::service::stream::delete_stream_data_by_predicate(
+        &org_id,
+        stream_type,
+        &stream_name,
+        &req.condition,
+        time_range.clone(),
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!(
+                "delete_stream_data_by_predicate {org_id}/{stream_type}/{stream_name}/{time_range} error: {e}",
+            );
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(MetaHttpResponse::error(
+                    StatusCode::BAD_REQUEST,
+                    e.to_string(),
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    let res = serde_json::json!({ "id": job_id });
+    (StatusCode::OK, Json(res)).into_response()
+}
+
+#[derive(serde::Serialize)]
+struct PredicateDeleteStatusRes {
+    #[serde(flatten)]
+    job: CompactorManualJobStatusRes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    progress: Option<PredicateDeleteProgress>,
+}
+
+/// StreamDeleteDataByPredicateJobStatus
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/data_by_predicate/status/{id}",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamDeleteDataByPredicateJobStatus",
+    summary = "Get delete by predicate job status",
+    description = "Retrieves the current status of a delete by predicate job, including the number of files rewritten, records deleted and the last error encountered",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("id" = String, Path, description = "Job ID"),
+        ("type" = Option<String>, Query, description = "Stream type. one of: logs, metrics, traces. Defaults to logs."),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"}))
+    )
+)]
+pub async fn get_delete_stream_data_by_predicate_status(
+    Path((org_id, stream_name, job_id)): Path<(String, String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let cfg = config::get_config();
+    let mut stream_name = stream_name;
+    if !cfg.common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+
+    // predicate delete jobs only run in the local cluster
+    let mut res = get_local_delete_status(&job_id).await;
+    if !job_belongs_to_org(&res, &org_id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(MetaHttpResponse::error(
+                StatusCode::NOT_FOUND,
+                "job not found".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let progress = match crate::service::db::compact::predicate_delete::get(
+        &org_id,
+        stream_type,
+        &stream_name,
+        &job_id,
+    )
+    .await
+    {
+        Ok(Some(job)) => {
+            if let Some(error) = job.error {
+                res.errors.push(serde_json::json!({ "error": error }));
+            }
+            Some(job.progress)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::error!("get predicate delete job {job_id} error: {e}");
+            None
+        }
+    };
+
+    (
+        StatusCode::OK,
+        Json(PredicateDeleteStatusRes { job: res, progress }),
+    )
+        .into_response()
+}
+
 fn job_belongs_to_org(res: &CompactorManualJobStatusRes, org_id: &str) -> bool {
     let org_prefix = format!("{org_id}/");
     res.metadata
@@ -938,6 +1096,7 @@ async fn get_super_cluster_delete_status(
     let trace_id = config::ider::generate_trace_id();
     let mut results = Vec::new();
     let mut any_pending = false;
+    let mut any_failed = false;
     let mut all_completed = true;
     let mut errors = Vec::new();
 
@@ -953,6 +1112,9 @@ async fn get_super_cluster_delete_status(
                 let job_status = CompactorManualJobStatus::from(response.status);
                 if job_status == CompactorManualJobStatus::Pending {
                     any_pending = true;
+                } else if job_status == CompactorManualJobStatus::Failed {
+                    any_failed = true;
+                    all_completed = false;
                 } else if job_status != CompactorManualJobStatus::Completed {
                     all_completed = false;
                 }
@@ -985,7 +1147,9 @@ async fn get_super_cluster_delete_status(
         }
     }
 
-    let status = if any_pending {
+    let status = if any_failed {
+        CompactorManualJobStatus::Failed
+    } else if any_pending {
         CompactorManualJobStatus::Pending
     } else if all_completed && errors.is_empty() {
         CompactorManualJobStatus::Completed
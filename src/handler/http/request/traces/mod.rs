@@ -49,7 +49,8 @@ use crate::{
     handler::http::{
         extractors::Headers,
         request::{
-            CONTENT_TYPE_JSON, CONTENT_TYPE_PROTO, search::error_utils::map_error_to_http_response,
+            CONTENT_TYPE_JSON, CONTENT_TYPE_PROTO, CONTENT_TYPE_THRIFT,
+            search::error_utils::map_error_to_http_response,
         },
     },
     service::{
@@ -150,6 +151,172 @@ pub async fn traces_write(
     }
 }
 
+/// ZipkinSpansIngest
+#[utoipa::path(
+    post,
+    path = "/{org_id}/api/v2/spans",
+    context_path = "/api",
+    tag = "Traces",
+    operation_id = "PostZipkinSpans",
+    summary = "Ingest Zipkin spans",
+    description = "Zipkin v2 compatible collector endpoint. Accepts a list of spans in the Zipkin v2 JSON encoding or, with the application/x-protobuf content type, a Zipkin ListOfSpans protobuf message. The spans are converted to the OpenTelemetry model and stored in the traces stream, so existing Zipkin instrumentation can report to OpenObserve without a collector in between.",
+    security(
+        ("Authorization"= [])
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = String, description = "Zipkin v2 spans", content_type = "application/json"),
+    responses(
+        (status = 202, description = "Accepted"),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    )
+)]
+pub async fn zipkin_write(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let content_type = headers
+        .get("Content-Type")
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("application/json");
+    if content_type.eq(CONTENT_TYPE_PROTO) {
+        collector_write(
+            org_id,
+            user_email,
+            headers,
+            body,
+            CollectorFormat::ZipkinProto,
+        )
+        .await
+    } else if content_type.starts_with(CONTENT_TYPE_JSON) {
+        collector_write(
+            org_id,
+            user_email,
+            headers,
+            body,
+            CollectorFormat::ZipkinJson,
+        )
+        .await
+    } else {
+        MetaHttpResponse::bad_request("Bad Request")
+    }
+}
+
+/// JaegerSpansIngest
+#[utoipa::path(
+    post,
+    path = "/{org_id}/api/traces",
+    context_path = "/api",
+    tag = "Traces",
+    operation_id = "PostJaegerSpans",
+    summary = "Ingest Jaeger spans",
+    description = "Jaeger compatible collector endpoint. Accepts a Jaeger Batch encoded with the Thrift binary protocol, as sent by the HTTP sender of the Jaeger client libraries. The spans are converted to the OpenTelemetry model and stored in the traces stream.",
+    security(
+        ("Authorization"= [])
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = String, description = "Thrift encoded Jaeger Batch", content_type = "application/x-thrift"),
+    responses(
+        (status = 202, description = "Accepted"),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    )
+)]
+pub async fn jaeger_write(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let content_type = headers
+        .get("Content-Type")
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or(CONTENT_TYPE_THRIFT);
+    if !content_type.starts_with(CONTENT_TYPE_THRIFT) {
+        return MetaHttpResponse::bad_request("Bad Request");
+    }
+    collector_write(
+        org_id,
+        user_email,
+        headers,
+        body,
+        CollectorFormat::JaegerThrift,
+    )
+    .await
+}
+
+enum CollectorFormat {
+    ZipkinJson,
+    ZipkinProto,
+    JaegerThrift,
+}
+
+async fn collector_write(
+    org_id: String,
+    user_email: UserEmail,
+    headers: HeaderMap,
+    body: Bytes,
+    format: CollectorFormat,
+) -> Response {
+    // log start processing time
+    let process_time = get_process_time();
+
+    let user = crate::common::meta::ingestion::IngestUser::from_user_email(&user_email.user_id);
+
+    #[cfg(feature = "cloud")]
+    match check_ingestion_allowed(&org_id, StreamType::Traces, None).await {
+        Ok(_) => {}
+        Err(e) => {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+    }
+
+    let cfg = get_config();
+    let org_id = if let Some(Some(v)) = headers
+        .get(&cfg.grpc.org_header_key)
+        .map(|header| header.to_str().ok())
+    {
+        v.to_string()
+    } else {
+        org_id
+    };
+    let in_stream_name = headers
+        .get(&cfg.grpc.stream_header_key)
+        .and_then(|header| header.to_str().ok());
+
+    let result = match format {
+        CollectorFormat::ZipkinJson => {
+            traces::zipkin::zipkin_json(&org_id, body, in_stream_name, user).await
+        }
+        CollectorFormat::ZipkinProto => {
+            traces::zipkin::zipkin_proto(&org_id, body, in_stream_name, user).await
+        }
+        CollectorFormat::JaegerThrift => {
+            traces::jaeger::jaeger_thrift(&org_id, body, in_stream_name, user).await
+        }
+    };
+
+    match result {
+        Ok(mut resp) => {
+            insert_process_time_header(process_time, resp.headers_mut());
+            resp
+        }
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
 /// GetLatestTraces
 ///
 /// #{"ratelimit_module":"Traces", "ratelimit_module_operation":"list"}#
@@ -1305,6 +1472,7 @@ async fn process_latest_traces_stream(
         histogram_interval: 0,
         sampling_ratio: None,
         search_type: Some(config::meta::search::SearchEventType::UI),
+        timezone: None,
     };
 
     let partitions = match SearchService::search_partition(
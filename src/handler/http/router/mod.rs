@@ -634,6 +634,8 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/streams/{stream_name}/cache/results", delete(stream::delete_stream_cache))
         .route("/{org_id}/streams/{stream_name}/data_by_time_range", delete(stream::delete_stream_data_by_time_range))
         .route("/{org_id}/streams/{stream_name}/data_by_time_range/status/{id}", get(stream::get_delete_stream_data_status))
+        .route("/{org_id}/streams/{stream_name}/data_by_predicate", post(stream::delete_stream_data_by_predicate))
+        .route("/{org_id}/streams/{stream_name}/data_by_predicate/status/{id}", get(stream::get_delete_stream_data_by_predicate_status))
 
         // Logs ingestion
         .route("/{org_id}/_bulk", post(logs::ingest::bulk))
@@ -641,11 +643,18 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/{stream_name}/_json", post(logs::ingest::json))
         .route("/{org_id}/_hec", post(logs::ingest::hec))
         .route("/{org_id}/loki/api/v1/push", post(logs::loki::loki_push))
+        .route("/{org_id}/loki/api/v1/query", get(logs::loki::loki_query_get).post(logs::loki::loki_query_post))
+        .route("/{org_id}/loki/api/v1/query_range", get(logs::loki::loki_query_range_get).post(logs::loki::loki_query_range_post))
+        .route("/{org_id}/loki/api/v1/labels", get(logs::loki::loki_labels))
+        .route("/{org_id}/loki/api/v1/label/{label_name}/values", get(logs::loki::loki_label_values))
+        .route("/{org_id}/loki/api/v1/series", get(logs::loki::loki_series_get).post(logs::loki::loki_series_post))
         .route("/{org_id}/v1/logs", post(logs::ingest::otlp_logs_write))
         .route("/{org_id}/v1/metrics", post(metrics::ingest::otlp_metrics_write))
         .route("/{org_id}/v1/traces", post(traces::traces_write))
         .route("/{org_id}/traces", post(traces::traces_write))
         .route("/{org_id}/otel/v1/traces", post(traces::traces_write))
+        .route("/{org_id}/api/v2/spans", post(traces::zipkin_write))
+        .route("/{org_id}/api/traces", post(traces::jaeger_write))
 
         // Traces
         .route("/{org_id}/{stream_name}/traces/latest", get(traces::get_latest_traces))
@@ -667,6 +676,7 @@ pub fn service_routes() -> Router {
 
         // PromQL
         .route("/{org_id}/prometheus/api/v1/write", post(promql::remote_write))
+        .route("/{org_id}/prometheus/api/v1/read", post(promql::remote_read))
         .route("/{org_id}/prometheus/api/v1/query", get(promql::query_get).post(promql::query_post))
         .route("/{org_id}/prometheus/api/v1/query_range", get(promql::query_range_get).post(promql::query_range_post))
         .route("/{org_id}/prometheus/api/v1/query_exemplars", get(promql::query_exemplars_get).post(promql::query_exemplars_post))
@@ -675,6 +685,13 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/prometheus/api/v1/labels", get(promql::labels_get).post(promql::labels_post))
         .route("/{org_id}/prometheus/api/v1/label/{label_name}/values", get(promql::label_values))
         .route("/{org_id}/prometheus/api/v1/format_query", get(promql::format_query_get).post(promql::format_query_post))
+        .route("/{org_id}/prometheus/api/v1/rules", get(promql::rules::rules))
+        .route("/{org_id}/prometheus/api/v1/alerts", get(promql::rules::alerts))
+        .route("/{org_id}/prometheus/config/v1/rules", get(promql::rules::list_rule_groups))
+        .route("/{org_id}/prometheus/config/v1/rules/{namespace}", get(promql::rules::get_namespace).post(promql::rules::set_rule_groups).delete(promql::rules::delete_namespace))
+        .route("/{org_id}/prometheus/config/v1/rules/{namespace}/{group}", get(promql::rules::get_rule_group).delete(promql::rules::delete_rule_group))
+        .route("/{org_id}/prometheus/config/v1/scrape", get(promql::scrape::list_scrape_configs).post(promql::scrape::set_scrape_configs))
+        .route("/{org_id}/prometheus/config/v1/scrape/{job_name}", get(promql::scrape::get_scrape_config).delete(promql::scrape::delete_scrape_config))
 
         // Search
         .route("/{org_id}/_search", post(search::search))
@@ -693,6 +710,7 @@ pub fn service_routes() -> Router {
         // HTTP/2 streaming
         .route("/{org_id}/_search_stream", post(search::search_stream::search_http2_stream))
         .route("/{org_id}/_values_stream", post(search::search_stream::values_http2_stream))
+        .route("/{org_id}/_export", post(search::export::export))
 
         // Saved views
         .route("/{org_id}/savedviews", get(search::saved_view::get_views).post(search::saved_view::create_view))
@@ -776,6 +794,8 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/alerts/destinations/bulk", delete(alerts::destinations::delete_destination_bulk))
 
         // Deduplication
+        .route("/{org_id}/alerts/silences", get(alerts::silences::list_silences).post(alerts::silences::create_silence))
+        .route("/{org_id}/alerts/silences/{silence_id}", get(alerts::silences::get_silence).put(alerts::silences::update_silence).delete(alerts::silences::delete_silence))
         .route("/{org_id}/alerts/deduplication/config", get(alerts::deduplication::get_config).post(alerts::deduplication::set_config).delete(alerts::deduplication::delete_config))
         .route("/{org_id}/alerts/deduplication/semantic-groups", get(alerts::deduplication::get_semantic_groups).put(alerts::deduplication::save_semantic_groups))
         .route("/{org_id}/alerts/deduplication/semantic-groups/preview-diff", post(alerts::deduplication::preview_semantic_groups_diff));
@@ -796,6 +816,8 @@ pub fn service_routes() -> Router {
         // KV store
         .route("/{org_id}/kv/{key}", get(kv::get).post(kv::set).delete(kv::delete))
         .route("/{org_id}/kv", get(kv::list))
+        .route("/{org_id}/materialized_views", get(materialized_views::list).post(materialized_views::create))
+        .route("/{org_id}/materialized_views/{name}", get(materialized_views::get).delete(materialized_views::delete))
 
         // Enrichment tables
         .route("/{org_id}/enrichment_tables/{table_name}", post(enrichment_table::save_enrichment_table))
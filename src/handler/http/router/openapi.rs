@@ -60,13 +60,21 @@ use crate::{common::meta, handler::http::request};
         request::logs::ingest::multi,
         request::logs::ingest::json,
         request::logs::loki::loki_push,
+        request::logs::loki::loki_query_range_get,
+        request::logs::loki::loki_query_get,
+        request::logs::loki::loki_labels,
+        request::logs::loki::loki_label_values,
+        request::logs::loki::loki_series_get,
         request::traces::traces_write,
+        request::traces::zipkin_write,
+        request::traces::jaeger_write,
         request::traces::get_latest_traces,
         request::traces::session::get_latest_sessions,
         request::traces::user::get_latest_users,
         request::traces::dag::get_trace_dag,
         request::metrics::ingest::json,
         request::promql::remote_write,
+        request::promql::remote_read,
         request::promql::query_get,
         request::promql::query_range_get,
         request::promql::metadata,
@@ -74,6 +82,18 @@ use crate::{common::meta, handler::http::request};
         request::promql::labels_get,
         request::promql::label_values,
         request::promql::format_query_get,
+        request::promql::rules::rules,
+        request::promql::rules::alerts,
+        request::promql::rules::list_rule_groups,
+        request::promql::rules::get_namespace,
+        request::promql::rules::set_rule_groups,
+        request::promql::rules::delete_namespace,
+        request::promql::rules::get_rule_group,
+        request::promql::rules::delete_rule_group,
+        request::promql::scrape::list_scrape_configs,
+        request::promql::scrape::set_scrape_configs,
+        request::promql::scrape::get_scrape_config,
+        request::promql::scrape::delete_scrape_config,
         request::enrichment_table::save_enrichment_table,
         request::enrichment_table::save_enrichment_table_from_url,
         request::rum::ingest::log,
@@ -157,6 +177,10 @@ use crate::{common::meta, handler::http::request};
         request::kv::set,
         request::kv::delete,
         request::kv::list,
+        request::materialized_views::create,
+        request::materialized_views::list,
+        request::materialized_views::get,
+        request::materialized_views::delete,
         request::clusters::list_clusters,
         request::short_url::shorten,
         request::short_url::retrieve,
@@ -234,6 +258,7 @@ use crate::{common::meta, handler::http::request};
         request::search::search_job::retry_job,
         request::search::search_stream::search_http2_stream,
         request::search::search_stream::values_http2_stream,
+        request::search::export::export,
         request::patterns::extract_patterns,
         crate::service::traces::service_graph::api::get_current_topology,
         request::service_streams::list_services,
@@ -247,6 +272,11 @@ use crate::{common::meta, handler::http::request};
         request::alerts::deduplication::get_semantic_groups,
         request::alerts::deduplication::preview_semantic_groups_diff,
         request::alerts::deduplication::save_semantic_groups,
+        request::alerts::silences::list_silences,
+        request::alerts::silences::get_silence,
+        request::alerts::silences::create_silence,
+        request::alerts::silences::update_silence,
+        request::alerts::silences::delete_silence,
         request::alerts::dedup_stats::get_dedup_summary,
     ),
     components(
@@ -286,7 +316,11 @@ use crate::{common::meta, handler::http::request};
             config::meta::alerts::QueryType,
             config::meta::alerts::QueryCondition,
             config::meta::alerts::TriggerCondition,
+            config::meta::alerts::SeverityLevel,
             config::meta::destinations::HTTPType,
+            config::meta::destinations::PagerDutySeverity,
+            config::meta::destinations::OpsgeniePriority,
+            config::meta::destinations::WebhookRetryPolicy,
             config::meta::timed_annotations::TimedAnnotation,
             config::meta::timed_annotations::TimedAnnotationReq,
             config::meta::timed_annotations::TimedAnnotationDelete,
@@ -316,6 +350,7 @@ use crate::{common::meta, handler::http::request};
             crate::handler::http::models::alerts::responses::EnableAlertResponseBody,
             crate::handler::http::models::alerts::Alert,
             crate::handler::http::models::alerts::TriggerCondition,
+            crate::handler::http::models::alerts::SeverityLevel,
             crate::handler::http::models::alerts::CompareHistoricData,
             crate::handler::http::models::alerts::FrequencyType,
             crate::handler::http::models::alerts::QueryCondition,
@@ -359,12 +394,20 @@ use crate::{common::meta, handler::http::request};
             config::meta::search::SearchEventContext,
             config::meta::search::SearchPartitionRequest,
             config::meta::search::SearchPartitionResponse,
+            config::meta::search::ExportRequest,
+            config::meta::search::ExportFormat,
             config::meta::search::SearchHistoryRequest,
             config::meta::search::CancelQueryResponse,
             config::meta::search::QueryStatusResponse,
             config::meta::search::QueryStatus,
             config::meta::search::QueryInfo,
             config::meta::search::ScanStats,
+            config::meta::search::SearchEstimateResponse,
+            config::meta::materialized_view::MaterializedView,
+            config::meta::materialized_view::MaterializedViewRequest,
+            config::meta::materialized_view::MaterializedViewList,
+            config::meta::materialized_view::ViewAggregate,
+            config::meta::materialized_view::ViewAggregateFunc,
             config::meta::short_url::ShortenUrlRequest,
             config::meta::short_url::ShortenUrlResponse,
             config::meta::user::UserRole,
@@ -430,6 +473,11 @@ use crate::{common::meta, handler::http::request};
             config::meta::alerts::deduplication::GroupingConfig,
             config::meta::alerts::deduplication::SendStrategy,
             request::alerts::dedup_stats::DedupSummaryResponse,
+            // Alert Silences
+            config::meta::alerts::silences::Silence,
+            config::meta::alerts::silences::SilenceMatcher,
+            config::meta::alerts::silences::SilenceMatchOperator,
+            config::meta::alerts::silences::SilenceSchedule,
             // Backfill
             request::pipelines::backfill::BackfillRequest,
             request::pipelines::backfill::BackfillResponse,
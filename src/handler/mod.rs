@@ -15,3 +15,4 @@
 
 pub mod grpc;
 pub mod http;
+pub mod pgwire;
@@ -0,0 +1,497 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Messages of the PostgreSQL frontend/backend protocol version 3.
+//!
+//! See <https://www.postgresql.org/docs/current/protocol-message-formats.html>.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use hashbrown::HashMap;
+
+const PROTOCOL_VERSION: i32 = 196608;
+const SSL_REQUEST_CODE: i32 = 80877103;
+const GSSENC_REQUEST_CODE: i32 = 80877104;
+const CANCEL_REQUEST_CODE: i32 = 80877102;
+
+/// The first message of a connection, it has no type byte.
+#[derive(Debug, PartialEq)]
+pub enum StartupMessage {
+    SslRequest,
+    GssEncRequest,
+    CancelRequest,
+    Startup { params: HashMap<String, String> },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FrontendMessage {
+    Password(String),
+    Query(String),
+    Parse {
+        name: String,
+        query: String,
+        param_types: Vec<i32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Bytes>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        kind: u8,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        kind: u8,
+        name: String,
+    },
+    Sync,
+    Flush,
+    Terminate,
+}
+
+/// A column of a `RowDescription`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDescription {
+    pub name: String,
+    pub type_oid: i32,
+    pub type_len: i16,
+    pub format: i16,
+}
+
+/// Decodes the startup message from the buffer, `None` until it was fully
+/// received.
+pub fn decode_startup(
+    buf: &mut BytesMut,
+    max_size: usize,
+) -> Result<Option<StartupMessage>, anyhow::Error> {
+    let Some(mut body) = split_message(buf, 0, max_size)? else {
+        return Ok(None);
+    };
+    if body.remaining() < 4 {
+        anyhow::bail!("invalid startup message");
+    }
+    let message = match body.get_i32() {
+        SSL_REQUEST_CODE => StartupMessage::SslRequest,
+        GSSENC_REQUEST_CODE => StartupMessage::GssEncRequest,
+        CANCEL_REQUEST_CODE => StartupMessage::CancelRequest,
+        PROTOCOL_VERSION => {
+            let mut params = HashMap::new();
+            loop {
+                let name = get_cstring(&mut body)?;
+                if name.is_empty() {
+                    break;
+                }
+                let value = get_cstring(&mut body)?;
+                params.insert(name, value);
+            }
+            StartupMessage::Startup { params }
+        }
+        version => anyhow::bail!(
+            "unsupported protocol version {}.{}",
+            version >> 16,
+            version & 0xffff
+        ),
+    };
+    Ok(Some(message))
+}
+
+/// Decodes a message from the buffer, `None` until it was fully received.
+pub fn decode(
+    buf: &mut BytesMut,
+    max_size: usize,
+) -> Result<Option<FrontendMessage>, anyhow::Error> {
+    if buf.is_empty() {
+        return Ok(None);
+    }
+    let tag = buf[0];
+    let Some(mut body) = split_message(buf, 1, max_size)? else {
+        return Ok(None);
+    };
+    let message = match tag {
+        b'p' => FrontendMessage::Password(get_cstring(&mut body)?),
+        b'Q' => FrontendMessage::Query(get_cstring(&mut body)?),
+        b'P' => {
+            let name = get_cstring(&mut body)?;
+            let query = get_cstring(&mut body)?;
+            let n = get_i16(&mut body)?;
+            let param_types = (0..n)
+                .map(|_| get_i32(&mut body))
+                .collect::<Result<_, _>>()?;
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            }
+        }
+        b'B' => {
+            let portal = get_cstring(&mut body)?;
+            let statement = get_cstring(&mut body)?;
+            let n = get_i16(&mut body)?;
+            let param_formats = (0..n)
+                .map(|_| get_i16(&mut body))
+                .collect::<Result<_, _>>()?;
+            let n = get_i16(&mut body)?;
+            let mut params = Vec::with_capacity(n.max(0) as usize);
+            for _ in 0..n {
+                let len = get_i32(&mut body)?;
+                if len < 0 {
+                    params.push(None);
+                    continue;
+                }
+                if body.remaining() < len as usize {
+                    anyhow::bail!("invalid Bind message");
+                }
+                params.push(Some(body.split_to(len as usize)));
+            }
+            let n = get_i16(&mut body)?;
+            let result_formats = (0..n)
+                .map(|_| get_i16(&mut body))
+                .collect::<Result<_, _>>()?;
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            }
+        }
+        b'D' => FrontendMessage::Describe {
+            kind: get_u8(&mut body)?,
+            name: get_cstring(&mut body)?,
+        },
+        b'E' => FrontendMessage::Execute {
+            portal: get_cstring(&mut body)?,
+            max_rows: get_i32(&mut body)?,
+        },
+        b'C' => FrontendMessage::Close {
+            kind: get_u8(&mut body)?,
+            name: get_cstring(&mut body)?,
+        },
+        b'S' => FrontendMessage::Sync,
+        b'H' => FrontendMessage::Flush,
+        b'X' => FrontendMessage::Terminate,
+        tag => anyhow::bail!("unsupported message type '{}'", tag as char),
+    };
+    Ok(Some(message))
+}
+
+/// Splits the body of a message from the buffer, the length follows the
+/// `header` bytes and counts itself.
+fn split_message(
+    buf: &mut BytesMut,
+    header: usize,
+    max_size: usize,
+) -> Result<Option<Bytes>, anyhow::Error> {
+    if buf.len() < header + 4 {
+        return Ok(None);
+    }
+    let len = i32::from_be_bytes(buf[header..header + 4].try_into().unwrap());
+    if len < 4 {
+        anyhow::bail!("invalid message length {len}");
+    }
+    let len = len as usize;
+    if len > max_size {
+        anyhow::bail!("message of {len} bytes exceeds the max size of {max_size} bytes");
+    }
+    if buf.len() < header + len {
+        buf.reserve(header + len - buf.len());
+        return Ok(None);
+    }
+    let mut message = buf.split_to(header + len).freeze();
+    message.advance(header + 4);
+    Ok(Some(message))
+}
+
+fn get_cstring(buf: &mut Bytes) -> Result<String, anyhow::Error> {
+    let Some(end) = buf.iter().position(|b| *b == 0) else {
+        anyhow::bail!("unterminated string in message");
+    };
+    let s = String::from_utf8(buf.split_to(end).to_vec())?;
+    buf.advance(1);
+    Ok(s)
+}
+
+fn get_u8(buf: &mut Bytes) -> Result<u8, anyhow::Error> {
+    if buf.remaining() < 1 {
+        anyhow::bail!("unexpected end of message");
+    }
+    Ok(buf.get_u8())
+}
+
+fn get_i16(buf: &mut Bytes) -> Result<i16, anyhow::Error> {
+    if buf.remaining() < 2 {
+        anyhow::bail!("unexpected end of message");
+    }
+    Ok(buf.get_i16())
+}
+
+fn get_i32(buf: &mut Bytes) -> Result<i32, anyhow::Error> {
+    if buf.remaining() < 4 {
+        anyhow::bail!("unexpected end of message");
+    }
+    Ok(buf.get_i32())
+}
+
+/// Encodes backend messages into a buffer which is written to the client.
+#[derive(Default)]
+pub struct MessageWriter {
+    buf: BytesMut,
+}
+
+impl MessageWriter {
+    pub fn take(&mut self) -> Bytes {
+        self.buf.split().freeze()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Answers an SSL or GSSAPI encryption request, they are not supported.
+    pub fn encryption_refused(&mut self) {
+        self.buf.put_u8(b'N');
+    }
+
+    pub fn authentication_ok(&mut self) {
+        self.message(b'R', |buf| buf.put_i32(0));
+    }
+
+    pub fn authentication_cleartext_password(&mut self) {
+        self.message(b'R', |buf| buf.put_i32(3));
+    }
+
+    pub fn parameter_status(&mut self, name: &str, value: &str) {
+        self.message(b'S', |buf| {
+            put_cstring(buf, name);
+            put_cstring(buf, value);
+        });
+    }
+
+    pub fn backend_key_data(&mut self, process_id: i32, secret_key: i32) {
+        self.message(b'K', |buf| {
+            buf.put_i32(process_id);
+            buf.put_i32(secret_key);
+        });
+    }
+
+    pub fn ready_for_query(&mut self) {
+        self.message(b'Z', |buf| buf.put_u8(b'I'));
+    }
+
+    pub fn parse_complete(&mut self) {
+        self.message(b'1', |_| {});
+    }
+
+    pub fn bind_complete(&mut self) {
+        self.message(b'2', |_| {});
+    }
+
+    pub fn close_complete(&mut self) {
+        self.message(b'3', |_| {});
+    }
+
+    pub fn no_data(&mut self) {
+        self.message(b'n', |_| {});
+    }
+
+    pub fn portal_suspended(&mut self) {
+        self.message(b's', |_| {});
+    }
+
+    pub fn empty_query_response(&mut self) {
+        self.message(b'I', |_| {});
+    }
+
+    pub fn command_complete(&mut self, tag: &str) {
+        self.message(b'C', |buf| put_cstring(buf, tag));
+    }
+
+    pub fn parameter_description(&mut self, type_oids: &[i32]) {
+        self.message(b't', |buf| {
+            buf.put_i16(type_oids.len() as i16);
+            type_oids.iter().for_each(|oid| buf.put_i32(*oid));
+        });
+    }
+
+    pub fn row_description(&mut self, fields: &[FieldDescription]) {
+        self.message(b'T', |buf| {
+            buf.put_i16(fields.len() as i16);
+            for field in fields {
+                put_cstring(buf, &field.name);
+                buf.put_i32(0); // table oid
+                buf.put_i16(0); // column number
+                buf.put_i32(field.type_oid);
+                buf.put_i16(field.type_len);
+                buf.put_i32(-1); // type modifier
+                buf.put_i16(field.format);
+            }
+        });
+    }
+
+    pub fn data_row(&mut self, values: &[Option<Bytes>]) {
+        self.message(b'D', |buf| {
+            buf.put_i16(values.len() as i16);
+            for value in values {
+                match value {
+                    Some(value) => {
+                        buf.put_i32(value.len() as i32);
+                        buf.put_slice(value);
+                    }
+                    None => buf.put_i32(-1),
+                }
+            }
+        });
+    }
+
+    pub fn error_response(&mut self, code: &str, message: &str) {
+        self.notice(b'E', "ERROR", code, message);
+    }
+
+    pub fn notice_response(&mut self, code: &str, message: &str) {
+        self.notice(b'N', "WARNING", code, message);
+    }
+
+    fn notice(&mut self, tag: u8, severity: &str, code: &str, message: &str) {
+        self.message(tag, |buf| {
+            for (field, value) in [
+                (b'S', severity),
+                (b'V', severity),
+                (b'C', code),
+                (b'M', message),
+            ] {
+                buf.put_u8(field);
+                put_cstring(buf, value);
+            }
+            buf.put_u8(0);
+        });
+    }
+
+    fn message(&mut self, tag: u8, body: impl FnOnce(&mut BytesMut)) {
+        self.buf.put_u8(tag);
+        let start = self.buf.len();
+        self.buf.put_i32(0);
+        body(&mut self.buf);
+        let len = (self.buf.len() - start) as i32;
+        self.buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+    }
+}
+
+fn put_cstring(buf: &mut BytesMut, s: &str) {
+    // strings are NUL terminated, a NUL in the value would cut it short
+    buf.put_slice(s.replace('\0', "").as_bytes());
+    buf.put_u8(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(tag: Option<u8>, body: &[u8]) -> BytesMut {
+        let mut buf = BytesMut::new();
+        if let Some(tag) = tag {
+            buf.put_u8(tag);
+        }
+        buf.put_i32(body.len() as i32 + 4);
+        buf.put_slice(body);
+        buf
+    }
+
+    #[test]
+    fn test_decode_startup() {
+        let mut body = BytesMut::new();
+        body.put_i32(PROTOCOL_VERSION);
+        body.put_slice(b"user\0root@example.com\0database\0default\0\0");
+        let mut buf = frame(None, &body);
+        let message = decode_startup(&mut buf, 1024).unwrap().unwrap();
+        let StartupMessage::Startup { params } = message else {
+            panic!("expected a startup message");
+        };
+        assert_eq!(params.get("user").unwrap(), "root@example.com");
+        assert_eq!(params.get("database").unwrap(), "default");
+        assert!(buf.is_empty());
+
+        let mut buf = frame(None, &SSL_REQUEST_CODE.to_be_bytes());
+        assert_eq!(
+            decode_startup(&mut buf, 1024).unwrap(),
+            Some(StartupMessage::SslRequest)
+        );
+    }
+
+    #[test]
+    fn test_decode_partial_message() {
+        let buf = frame(Some(b'Q'), b"SELECT 1\0");
+        let mut partial = BytesMut::from(&buf[..6]);
+        assert_eq!(decode(&mut partial, 1024).unwrap(), None);
+        partial.extend_from_slice(&buf[6..]);
+        assert_eq!(
+            decode(&mut partial, 1024).unwrap(),
+            Some(FrontendMessage::Query("SELECT 1".to_string()))
+        );
+
+        let mut buf = frame(Some(b'Q'), b"SELECT 1\0");
+        assert!(decode(&mut buf, 8).is_err());
+    }
+
+    #[test]
+    fn test_decode_bind() {
+        let mut body = BytesMut::new();
+        body.put_slice(b"\0stmt\0");
+        body.put_i16(1);
+        body.put_i16(0);
+        body.put_i16(2);
+        body.put_i32(2);
+        body.put_slice(b"42");
+        body.put_i32(-1);
+        body.put_i16(1);
+        body.put_i16(1);
+        let mut buf = frame(Some(b'B'), &body);
+        assert_eq!(
+            decode(&mut buf, 1024).unwrap(),
+            Some(FrontendMessage::Bind {
+                portal: "".to_string(),
+                statement: "stmt".to_string(),
+                param_formats: vec![0],
+                params: vec![Some(Bytes::from_static(b"42")), None],
+                result_formats: vec![1],
+            })
+        );
+    }
+
+    #[test]
+    fn test_encode_messages() {
+        let mut writer = MessageWriter::default();
+        writer.command_complete("SELECT 1");
+        assert_eq!(&writer.take()[..], b"C\0\0\0\x0dSELECT 1\0");
+
+        writer.data_row(&[Some(Bytes::from_static(b"a")), None]);
+        assert_eq!(
+            &writer.take()[..],
+            b"D\0\0\0\x0f\0\x02\0\0\0\x01a\xff\xff\xff\xff"
+        );
+
+        writer.notice_response("01000", "partial");
+        assert_eq!(
+            &writer.take()[..],
+            b"N\0\0\0\x27SWARNING\0VWARNING\0C01000\0Mpartial\0\0"
+        );
+        assert!(writer.is_empty());
+    }
+}
@@ -0,0 +1,959 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! PostgreSQL wire protocol endpoint, BI tools and drivers connect to it with
+//! the database set to the organization and query streams as
+//! `<stream_type>.<stream>` tables.
+//!
+//! Connections are read only and authenticate with the user's password or
+//! token sent as a cleartext password, TLS is not supported. Both the simple
+//! and the extended query protocol are served, transaction statements are
+//! accepted and do nothing.
+
+pub mod codec;
+
+use std::{net::SocketAddr, ops::ControlFlow, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, RecordBatch, StringArray},
+    compute::cast,
+    datatypes::{Float64Type, Int32Type, Int64Type},
+    util::display::{ArrayFormatter, FormatOptions},
+};
+use arrow_schema::{DataType, Field, Schema};
+use bytes::{Bytes, BytesMut};
+use config::{
+    get_config, ider,
+    utils::{base64, time::now_micros},
+};
+use hashbrown::HashMap;
+use infra::errors::{Error, ErrorCodes};
+use sqlparser::{
+    ast::{Expr, Statement, Value, ValueWithSpan, Visit, VisitMut, Visitor, VisitorMut},
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+    tokenizer::Span,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use self::codec::{FieldDescription, FrontendMessage, MessageWriter, StartupMessage};
+use crate::{
+    handler::grpc::auth::check_user_credentials,
+    service::search::{
+        pg_catalog::{self, BOOL, FLOAT8, INT4, INT8, PgType, TEXT},
+        sql_client,
+    },
+};
+
+const SERVER_VERSION: &str = "14.0";
+const FORMAT_TEXT: i16 = 0;
+const FORMAT_BINARY: i16 = 1;
+
+/// The run time parameters reported to the client after authentication, `SHOW`
+/// reads them too.
+const PARAMETERS: [(&str, &str); 8] = [
+    ("server_version", SERVER_VERSION),
+    ("server_encoding", "UTF8"),
+    ("client_encoding", "UTF8"),
+    ("DateStyle", "ISO, MDY"),
+    ("TimeZone", "UTC"),
+    ("integer_datetimes", "on"),
+    ("standard_conforming_strings", "on"),
+    ("transaction_isolation", "read committed"),
+];
+
+/// An error sent to the client as an `ErrorResponse` with its SQLSTATE code.
+#[derive(Debug, PartialEq)]
+struct PgError {
+    code: &'static str,
+    message: String,
+}
+
+impl PgError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Error> for PgError {
+    fn from(e: Error) -> Self {
+        let code = match &e {
+            Error::ErrorCode(ErrorCodes::SearchSQLNotValid(_)) => "42601",
+            Error::ErrorCode(ErrorCodes::SearchStreamNotFound(_)) => "42P01",
+            Error::ErrorCode(ErrorCodes::SearchFieldNotFound(_)) => "42703",
+            Error::ErrorCode(ErrorCodes::SearchFunctionNotDefined(_)) => "42883",
+            Error::ErrorCode(ErrorCodes::InvalidParams(_)) => "22023",
+            Error::ErrorCode(
+                ErrorCodes::RatelimitExceeded(_) | ErrorCodes::SearchScanBudgetExceeded(_),
+            ) => "53400",
+            Error::ErrorCode(ErrorCodes::SearchTimeout(_) | ErrorCodes::SearchCancelQuery(_)) => {
+                "57014"
+            }
+            _ => "XX000",
+        };
+        let message = match e {
+            Error::ErrorCode(code) => code.get_message(),
+            e => e.to_string(),
+        };
+        Self::new(code, message)
+    }
+}
+
+/// The result of a statement, the rows of a query or the tag of a command.
+enum Outcome {
+    Command(&'static str),
+    Rows(Arc<Schema>, Vec<RecordBatch>),
+}
+
+struct PreparedStatement {
+    query: String,
+    param_types: Vec<i32>,
+    /// The result of running the statement to describe it, the next portal
+    /// bound to the statement returns it so both see the same columns.
+    described: Option<Outcome>,
+}
+
+struct Portal {
+    fields: Vec<FieldDescription>,
+    rows: Vec<Vec<Option<Bytes>>>,
+    sent: usize,
+    tag: Option<&'static str>,
+}
+
+/// Serves a client connection until it terminates.
+pub async fn handle_connection(stream: TcpStream, peer: SocketAddr) -> Result<(), anyhow::Error> {
+    let mut conn = Connection {
+        stream,
+        buf: BytesMut::with_capacity(8192),
+        writer: MessageWriter::default(),
+        max_message_size: get_config().pgwire.max_message_size,
+        org_id: String::new(),
+        user_id: String::new(),
+        statements: HashMap::new(),
+        portals: HashMap::new(),
+        skip_till_sync: false,
+    };
+    if !conn.startup().await? {
+        return Ok(());
+    }
+    log::debug!(
+        "[PGWIRE] {peer} connected to org {} as {}",
+        conn.org_id,
+        conn.user_id
+    );
+    conn.serve().await
+}
+
+struct Connection {
+    stream: TcpStream,
+    buf: BytesMut,
+    writer: MessageWriter,
+    max_message_size: usize,
+    org_id: String,
+    user_id: String,
+    statements: HashMap<String, PreparedStatement>,
+    portals: HashMap<String, Portal>,
+    /// Set after an error in the extended query protocol, messages are
+    /// discarded until the next `Sync`.
+    skip_till_sync: bool,
+}
+
+impl Connection {
+    /// Negotiates the connection and authenticates the user, `false` when the
+    /// connection ends before that.
+    async fn startup(&mut self) -> Result<bool, anyhow::Error> {
+        let params = loop {
+            let message = loop {
+                if let Some(message) = codec::decode_startup(&mut self.buf, self.max_message_size)?
+                {
+                    break message;
+                }
+                if self.stream.read_buf(&mut self.buf).await? == 0 {
+                    return Ok(false);
+                }
+            };
+            match message {
+                StartupMessage::SslRequest | StartupMessage::GssEncRequest => {
+                    self.writer.encryption_refused();
+                    self.flush().await?;
+                }
+                // queries can't be cancelled, the request is ignored
+                StartupMessage::CancelRequest => return Ok(false),
+                StartupMessage::Startup { params } => break params,
+            }
+        };
+
+        let Some(user_id) = params.get("user").cloned() else {
+            return self.fatal("28000", "no user name specified").await;
+        };
+        let org_id = params
+            .get("database")
+            .cloned()
+            .unwrap_or_else(|| user_id.clone());
+        self.writer.authentication_cleartext_password();
+        self.flush().await?;
+        let password = match self.read_message().await? {
+            Some(FrontendMessage::Password(password)) => password,
+            Some(_) => return self.fatal("08P01", "expected a password message").await,
+            None => return Ok(false),
+        };
+        let token = format!("Basic {}", base64::encode(&format!("{user_id}:{password}")));
+        let user_id = match check_user_credentials(&org_id, &token) {
+            Ok(user_id) => user_id,
+            Err(_) => {
+                return self
+                    .fatal(
+                        "28P01",
+                        &format!("password authentication failed for user \"{user_id}\""),
+                    )
+                    .await;
+            }
+        };
+        self.org_id = org_id;
+        self.user_id = user_id;
+
+        self.writer.authentication_ok();
+        for (name, value) in PARAMETERS {
+            self.writer.parameter_status(name, value);
+        }
+        self.writer
+            .backend_key_data((rand::random::<u32>() >> 1) as i32, rand::random::<i32>());
+        self.writer.ready_for_query();
+        self.flush().await?;
+        Ok(true)
+    }
+
+    async fn serve(&mut self) -> Result<(), anyhow::Error> {
+        while let Some(message) = self.read_message().await? {
+            if self.skip_till_sync && !matches!(message, FrontendMessage::Sync) {
+                continue;
+            }
+            match message {
+                FrontendMessage::Query(sql) => {
+                    self.simple_query(&sql).await;
+                    self.writer.ready_for_query();
+                    self.flush().await?;
+                }
+                FrontendMessage::Sync => {
+                    self.skip_till_sync = false;
+                    self.portals.remove("");
+                    self.writer.ready_for_query();
+                    self.flush().await?;
+                }
+                FrontendMessage::Flush => self.flush().await?,
+                FrontendMessage::Terminate => return Ok(()),
+                FrontendMessage::Password(_) => {
+                    return self
+                        .fatal("08P01", "unexpected password message")
+                        .await
+                        .map(|_| ());
+                }
+                message => {
+                    if let Err(e) = self.extended_query(message).await {
+                        self.writer.error_response(e.code, &e.message);
+                        self.skip_till_sync = true;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn simple_query(&mut self, sql: &str) {
+        let statements = split_statements(sql);
+        if statements.is_empty() {
+            self.writer.empty_query_response();
+            return;
+        }
+        for sql in statements {
+            let outcome = match self.run(sql).await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    self.writer.error_response(e.code, &e.message);
+                    return;
+                }
+            };
+            match encode_outcome(outcome, &[]) {
+                Ok(portal) => {
+                    if let Some(tag) = portal.tag {
+                        self.writer.command_complete(tag);
+                        continue;
+                    }
+                    self.writer.row_description(&portal.fields);
+                    portal.rows.iter().for_each(|row| self.writer.data_row(row));
+                    self.writer
+                        .command_complete(&format!("SELECT {}", portal.rows.len()));
+                }
+                Err(e) => {
+                    self.writer.error_response(e.code, &e.message);
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn extended_query(&mut self, message: FrontendMessage) -> Result<(), PgError> {
+        match message {
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            } => {
+                let count = count_params(&query)?;
+                let mut param_types = param_types;
+                param_types.resize(count.max(param_types.len()), 0);
+                self.statements.insert(
+                    name,
+                    PreparedStatement {
+                        query,
+                        param_types,
+                        described: None,
+                    },
+                );
+                self.writer.parse_complete();
+            }
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            } => {
+                let Some(prepared) = self.statements.get_mut(&statement) else {
+                    return Err(unknown_statement(&statement));
+                };
+                if params.len() != prepared.param_types.len() {
+                    return Err(PgError::new(
+                        "08P01",
+                        format!(
+                            "bind message supplies {} parameters, but prepared statement requires {}",
+                            params.len(),
+                            prepared.param_types.len()
+                        ),
+                    ));
+                }
+                let described = prepared.described.take();
+                let outcome = match described {
+                    Some(outcome) => outcome,
+                    None => {
+                        let values = params
+                            .iter()
+                            .enumerate()
+                            .map(|(i, param)| {
+                                decode_param(
+                                    param.as_ref(),
+                                    prepared.param_types[i],
+                                    format_of(&param_formats, i),
+                                )
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        let sql = bind_params(&prepared.query, &values)?;
+                        self.run(&sql).await?
+                    }
+                };
+                self.portals
+                    .insert(portal, encode_outcome(outcome, &result_formats)?);
+                self.writer.bind_complete();
+            }
+            FrontendMessage::Describe { kind: b'S', name } => {
+                let Some(prepared) = self.statements.get(&name) else {
+                    return Err(unknown_statement(&name));
+                };
+                let param_oids = prepared
+                    .param_types
+                    .iter()
+                    .map(|oid| if *oid == 0 { TEXT.oid } else { *oid })
+                    .collect::<Vec<_>>();
+                self.writer.parameter_description(&param_oids);
+                // the columns are only known after running the query, statements
+                // with parameters can't run before they are bound
+                if !param_oids.is_empty() {
+                    self.writer.no_data();
+                    return Ok(());
+                }
+                let sql = prepared.query.clone();
+                let outcome = self.run(&sql).await?;
+                match &outcome {
+                    Outcome::Rows(schema, _) => self.writer.row_description(&fields(schema, &[])),
+                    Outcome::Command(_) => self.writer.no_data(),
+                }
+                if let Some(prepared) = self.statements.get_mut(&name) {
+                    prepared.described = Some(outcome);
+                }
+            }
+            FrontendMessage::Describe { kind: b'P', name } => {
+                let Some(portal) = self.portals.get(&name) else {
+                    return Err(unknown_portal(&name));
+                };
+                match portal.tag {
+                    Some(_) => self.writer.no_data(),
+                    None => self.writer.row_description(&portal.fields),
+                }
+            }
+            FrontendMessage::Execute {
+                portal: name,
+                max_rows,
+            } => {
+                let Some(portal) = self.portals.get_mut(&name) else {
+                    return Err(unknown_portal(&name));
+                };
+                if let Some(tag) = portal.tag {
+                    self.writer.command_complete(tag);
+                    return Ok(());
+                }
+                let remaining = portal.rows.len() - portal.sent;
+                let n = if max_rows > 0 {
+                    remaining.min(max_rows as usize)
+                } else {
+                    remaining
+                };
+                for row in &portal.rows[portal.sent..portal.sent + n] {
+                    self.writer.data_row(row);
+                }
+                portal.sent += n;
+                if portal.sent < portal.rows.len() {
+                    self.writer.portal_suspended();
+                } else {
+                    self.writer.command_complete(&format!("SELECT {n}"));
+                }
+            }
+            FrontendMessage::Close { kind, name } => {
+                if kind == b'S' {
+                    self.statements.remove(&name);
+                } else {
+                    self.portals.remove(&name);
+                }
+                self.writer.close_complete();
+            }
+            FrontendMessage::Describe { kind, .. } => {
+                return Err(PgError::new(
+                    "08P01",
+                    format!("invalid DESCRIBE message subtype {}", kind as char),
+                ));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Runs a single statement.
+    async fn run(&mut self, sql: &str) -> Result<Outcome, PgError> {
+        let keyword = sql
+            .split(|c: char| c.is_whitespace() || c == '(' || c == ';')
+            .find(|s| !s.is_empty())
+            .unwrap_or_default()
+            .to_uppercase();
+        match keyword.as_str() {
+            "SET" | "RESET" => return Ok(Outcome::Command("SET")),
+            "BEGIN" | "START" => return Ok(Outcome::Command("BEGIN")),
+            "COMMIT" | "END" => return Ok(Outcome::Command("COMMIT")),
+            "ROLLBACK" | "ABORT" => return Ok(Outcome::Command("ROLLBACK")),
+            "DISCARD" => return Ok(Outcome::Command("DISCARD ALL")),
+            "DEALLOCATE" => return Ok(Outcome::Command("DEALLOCATE")),
+            "SHOW" => return show(sql),
+            "SELECT" | "WITH" | "VALUES" | "TABLE" => {}
+            _ => {
+                return Err(PgError::new(
+                    "25006",
+                    format!("cannot execute {keyword} in a read-only connection"),
+                ));
+            }
+        }
+
+        let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+            .map_err(|e| PgError::new("42601", e.to_string()))?;
+        if statements.len() != 1 {
+            return Err(PgError::new("42601", "expected a single statement"));
+        }
+        let statement = statements.remove(0);
+        if pg_catalog::is_catalog_query(&statement) {
+            let (schema, batches) =
+                pg_catalog::execute(&self.org_id, &self.user_id, statement).await?;
+            return Ok(Outcome::Rows(schema, batches));
+        }
+
+        let query = sql_client::prepare(
+            &self.org_id,
+            sql,
+            now_micros(),
+            get_config().pgwire.default_time_range,
+        )?;
+        sql_client::check_permissions(&self.org_id, &self.user_id, &query).await?;
+        let trace_id = ider::generate_trace_id();
+        // the search size is left to the LIMIT of the query, the columns are
+        // those of its plan so a query without rows still describes them
+        let result = sql_client::execute(&trace_id, &self.org_id, &self.user_id, &query).await?;
+        if let Some(error) = result.partial_error {
+            self.writer.notice_response("01000", &error);
+        }
+        Ok(Outcome::Rows(
+            result.schema,
+            result.batch.into_iter().collect(),
+        ))
+    }
+
+    async fn read_message(&mut self) -> Result<Option<FrontendMessage>, anyhow::Error> {
+        loop {
+            if let Some(message) = codec::decode(&mut self.buf, self.max_message_size)? {
+                return Ok(Some(message));
+            }
+            if self.stream.read_buf(&mut self.buf).await? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+
+    async fn flush(&mut self) -> Result<(), anyhow::Error> {
+        if !self.writer.is_empty() {
+            self.stream.write_all(&self.writer.take()).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends an error and ends the connection.
+    async fn fatal(&mut self, code: &str, message: &str) -> Result<bool, anyhow::Error> {
+        self.writer.error_response(code, message);
+        self.flush().await?;
+        Ok(false)
+    }
+}
+
+fn unknown_statement(name: &str) -> PgError {
+    PgError::new(
+        "26000",
+        format!("prepared statement \"{name}\" does not exist"),
+    )
+}
+
+fn unknown_portal(name: &str) -> PgError {
+    PgError::new("34000", format!("portal \"{name}\" does not exist"))
+}
+
+/// Answers `SHOW <parameter>` from the reported run time parameters.
+fn show(sql: &str) -> Result<Outcome, PgError> {
+    let name = sql
+        .trim()
+        .trim_end_matches(';')
+        .split_whitespace()
+        .skip(1)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let name = match name.to_lowercase().as_str() {
+        "transaction isolation level" => "transaction_isolation".to_string(),
+        _ => name,
+    };
+    let Some((_, value)) = PARAMETERS
+        .iter()
+        .find(|(parameter, _)| parameter.eq_ignore_ascii_case(&name))
+    else {
+        return Err(PgError::new(
+            "42704",
+            format!("unrecognized configuration parameter \"{name}\""),
+        ));
+    };
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        name.to_lowercase(),
+        DataType::Utf8,
+        false,
+    )]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(StringArray::from(vec![*value]))],
+    )
+    .map_err(|e| PgError::new("XX000", e.to_string()))?;
+    Ok(Outcome::Rows(schema, vec![batch]))
+}
+
+/// Splits a simple query into its statements, `;` inside quotes, identifiers
+/// and comments doesn't end a statement.
+fn split_statements(sql: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut quote = None;
+    let mut line_comment = false;
+    let mut start = 0;
+    let mut chars = sql.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            _ if line_comment => line_comment = c != '\n',
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '-') if chars.peek().is_some_and(|(_, c)| *c == '-') => line_comment = true,
+            (None, ';') => {
+                statements.push(&sql[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.push(&sql[start..]);
+    statements
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// The number of `$n` parameters of a query.
+fn count_params(query: &str) -> Result<usize, PgError> {
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, query)
+        .map_err(|e| PgError::new("42601", e.to_string()))?;
+    let Some(statement) = statements.pop() else {
+        return Ok(0);
+    };
+    let mut counter = ParamCounter(0);
+    let _ = Visit::visit(&statement, &mut counter);
+    Ok(counter.0)
+}
+
+struct ParamCounter(usize);
+
+impl Visitor for ParamCounter {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        if let Expr::Value(ValueWithSpan {
+            value: Value::Placeholder(p),
+            ..
+        }) = expr
+            && let Some(n) = p.strip_prefix('$').and_then(|n| n.parse::<usize>().ok())
+        {
+            self.0 = self.0.max(n);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Replaces the `$n` parameters of the query with their values.
+fn bind_params(query: &str, values: &[Value]) -> Result<String, PgError> {
+    if values.is_empty() {
+        return Ok(query.to_string());
+    }
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, query)
+        .map_err(|e| PgError::new("42601", e.to_string()))?;
+    let mut binder = ParamBinder { values };
+    let _ = VisitMut::visit(&mut statements, &mut binder);
+    Ok(statements
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join("; "))
+}
+
+struct ParamBinder<'a> {
+    values: &'a [Value],
+}
+
+impl VisitorMut for ParamBinder<'_> {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &mut Expr) -> ControlFlow<Self::Break> {
+        if let Expr::Value(ValueWithSpan {
+            value: Value::Placeholder(p),
+            ..
+        }) = expr
+            && let Some(value) = p
+                .strip_prefix('$')
+                .and_then(|n| n.parse::<usize>().ok())
+                .and_then(|n| self.values.get(n.wrapping_sub(1)))
+        {
+            *expr = Expr::Value(ValueWithSpan {
+                value: value.clone(),
+                span: Span::empty(),
+            });
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// The format of the i-th value, one format applies to all values.
+fn format_of(formats: &[i16], i: usize) -> i16 {
+    match formats {
+        [] => FORMAT_TEXT,
+        [format] => *format,
+        formats => formats.get(i).copied().unwrap_or(FORMAT_TEXT),
+    }
+}
+
+/// Decodes a parameter value into a SQL literal.
+fn decode_param(param: Option<&Bytes>, type_oid: i32, format: i16) -> Result<Value, PgError> {
+    let Some(param) = param else {
+        return Ok(Value::Null);
+    };
+    let invalid = || {
+        PgError::new(
+            "22P03",
+            format!("invalid value for parameter of type {type_oid}"),
+        )
+    };
+    if format == FORMAT_TEXT {
+        let text = std::str::from_utf8(param).map_err(|_| invalid())?;
+        return Ok(match type_oid {
+            16 => Value::Boolean(matches!(
+                text.to_lowercase().as_str(),
+                "t" | "true" | "y" | "yes" | "on" | "1"
+            )),
+            20 | 21 | 23 | 700 | 701 | 1700 => {
+                text.parse::<f64>().map_err(|_| invalid())?;
+                Value::Number(text.to_string(), false)
+            }
+            _ => Value::SingleQuotedString(text.to_string()),
+        });
+    }
+    let bytes = param.as_ref();
+    let number = |n: String| Value::Number(n, false);
+    Ok(match (type_oid, bytes.len()) {
+        (16, 1) => Value::Boolean(bytes[0] != 0),
+        (21, 2) => number(i16::from_be_bytes(bytes.try_into().unwrap()).to_string()),
+        (23, 4) => number(i32::from_be_bytes(bytes.try_into().unwrap()).to_string()),
+        (20, 8) => number(i64::from_be_bytes(bytes.try_into().unwrap()).to_string()),
+        (700, 4) => number(f32::from_be_bytes(bytes.try_into().unwrap()).to_string()),
+        (701, 8) => number(f64::from_be_bytes(bytes.try_into().unwrap()).to_string()),
+        (0 | 25 | 1043, _) => {
+            Value::SingleQuotedString(String::from_utf8(bytes.to_vec()).map_err(|_| invalid())?)
+        }
+        (16 | 20 | 21 | 23 | 700 | 701, _) => return Err(invalid()),
+        _ => {
+            return Err(PgError::new(
+                "0A000",
+                format!("binary format of parameter type {type_oid} is not supported"),
+            ));
+        }
+    })
+}
+
+fn fields(schema: &Schema, formats: &[i16]) -> Vec<FieldDescription> {
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let pg_type = pg_catalog::pg_type(field.data_type());
+            FieldDescription {
+                name: field.name().to_string(),
+                type_oid: pg_type.oid,
+                type_len: pg_type.len,
+                format: format_of(formats, i),
+            }
+        })
+        .collect()
+}
+
+/// Encodes the rows of a result in the requested formats.
+fn encode_outcome(outcome: Outcome, formats: &[i16]) -> Result<Portal, PgError> {
+    let (schema, batches) = match outcome {
+        Outcome::Command(tag) => {
+            return Ok(Portal {
+                fields: vec![],
+                rows: vec![],
+                sent: 0,
+                tag: Some(tag),
+            });
+        }
+        Outcome::Rows(schema, batches) => (schema, batches),
+    };
+    let fields = fields(&schema, formats);
+    let mut rows = Vec::new();
+    for batch in batches {
+        let columns = batch
+            .columns()
+            .iter()
+            .map(to_pg_array)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PgError::new("XX000", e.to_string()))?;
+        for row in 0..batch.num_rows() {
+            rows.push(
+                columns
+                    .iter()
+                    .zip(&fields)
+                    .map(|((pg_type, array), field)| {
+                        encode_value(array, *pg_type, row, field.format == FORMAT_BINARY)
+                    })
+                    .collect(),
+            );
+        }
+    }
+    Ok(Portal {
+        fields,
+        rows,
+        sent: 0,
+        tag: None,
+    })
+}
+
+/// Casts a column to the Arrow type of the PostgreSQL type it is sent as,
+/// text columns are formatted to strings.
+fn to_pg_array(array: &ArrayRef) -> Result<(PgType, ArrayRef), arrow_schema::ArrowError> {
+    let pg_type = pg_catalog::pg_type(array.data_type());
+    let array = match pg_type {
+        BOOL => cast(array, &DataType::Boolean)?,
+        INT4 => cast(array, &DataType::Int32)?,
+        INT8 => cast(array, &DataType::Int64)?,
+        FLOAT8 => cast(array, &DataType::Float64)?,
+        _ => {
+            let formatter = ArrayFormatter::try_new(array.as_ref(), &FormatOptions::default())?;
+            Arc::new(
+                (0..array.len())
+                    .map(|i| array.is_valid(i).then(|| formatter.value(i).to_string()))
+                    .collect::<StringArray>(),
+            )
+        }
+    };
+    Ok((pg_type, array))
+}
+
+fn encode_value(array: &ArrayRef, pg_type: PgType, row: usize, binary: bool) -> Option<Bytes> {
+    if array.is_null(row) {
+        return None;
+    }
+    let value = match (pg_type, binary) {
+        (BOOL, true) => vec![array.as_boolean().value(row) as u8],
+        (BOOL, false) => {
+            let v = if array.as_boolean().value(row) {
+                "t"
+            } else {
+                "f"
+            };
+            v.as_bytes().to_vec()
+        }
+        (INT4, true) => array
+            .as_primitive::<Int32Type>()
+            .value(row)
+            .to_be_bytes()
+            .to_vec(),
+        (INT4, false) => array
+            .as_primitive::<Int32Type>()
+            .value(row)
+            .to_string()
+            .into_bytes(),
+        (INT8, true) => array
+            .as_primitive::<Int64Type>()
+            .value(row)
+            .to_be_bytes()
+            .to_vec(),
+        (INT8, false) => array
+            .as_primitive::<Int64Type>()
+            .value(row)
+            .to_string()
+            .into_bytes(),
+        (FLOAT8, true) => array
+            .as_primitive::<Float64Type>()
+            .value(row)
+            .to_be_bytes()
+            .to_vec(),
+        (FLOAT8, false) => {
+            let v = array.as_primitive::<Float64Type>().value(row);
+            match v {
+                f64::INFINITY => "Infinity".to_string(),
+                f64::NEG_INFINITY => "-Infinity".to_string(),
+                v => v.to_string(),
+            }
+            .into_bytes()
+        }
+        // text is the same in both formats
+        _ => array.as_string::<i32>().value(row).as_bytes().to_vec(),
+    };
+    Some(Bytes::from(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{BooleanArray, Int64Array, UInt8Array};
+
+    use super::*;
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            split_statements("SET a = 1; SELECT ';' AS \"x;y\" -- c;\n; "),
+            vec!["SET a = 1", "SELECT ';' AS \"x;y\" -- c;"]
+        );
+        assert!(split_statements(" ; ").is_empty());
+    }
+
+    #[test]
+    fn test_bind_params() {
+        assert_eq!(
+            count_params("SELECT * FROM logs.app WHERE a = $2 AND b = $1").unwrap(),
+            2
+        );
+        let values = vec![
+            decode_param(Some(&Bytes::from_static(b"x'y")), 0, FORMAT_TEXT).unwrap(),
+            decode_param(Some(&Bytes::from_static(&[0, 0, 0, 7])), 23, FORMAT_BINARY).unwrap(),
+        ];
+        assert_eq!(
+            bind_params("SELECT * FROM logs.app WHERE a = $2 AND b = $1", &values).unwrap(),
+            "SELECT * FROM logs.app WHERE a = 7 AND b = 'x''y'"
+        );
+        assert!(decode_param(Some(&Bytes::from_static(b"1x")), 20, FORMAT_TEXT).is_err());
+        assert_eq!(decode_param(None, 20, FORMAT_BINARY).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_encode_outcome() {
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "a",
+                Arc::new(Int64Array::from(vec![Some(1), None])) as ArrayRef,
+            ),
+            ("b", Arc::new(UInt8Array::from(vec![2, 3])) as ArrayRef),
+            (
+                "c",
+                Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let portal =
+            encode_outcome(Outcome::Rows(batch.schema(), vec![batch]), &[0, 1, 0]).unwrap();
+        assert_eq!(
+            portal.fields.iter().map(|f| f.type_oid).collect::<Vec<_>>(),
+            vec![INT8.oid, INT4.oid, BOOL.oid]
+        );
+        assert_eq!(
+            portal.rows[0],
+            vec![
+                Some(Bytes::from_static(b"1")),
+                Some(Bytes::from_static(&[0, 0, 0, 2])),
+                Some(Bytes::from_static(b"t")),
+            ]
+        );
+        assert_eq!(portal.rows[1][0], None);
+    }
+
+    #[test]
+    fn test_show() {
+        let Outcome::Rows(schema, batches) = show("SHOW TimeZone;").unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(schema.field(0).name(), "timezone");
+        assert_eq!(batches[0].column(0).as_string::<i32>().value(0), "UTC");
+        assert_eq!(show("SHOW foo").err().unwrap().code, "42704");
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let e = PgError::from(Error::ErrorCode(ErrorCodes::SearchSQLNotValid(
+            "bad".to_string(),
+        )));
+        assert_eq!(e.code, "42601");
+        assert_eq!(PgError::from(Error::Message("x".to_string())).code, "XX000");
+    }
+}
@@ -211,6 +211,7 @@ pub enum ErrorCodes {
     InvalidParams(String),
     RatelimitExceeded(String),
     SearchHistogramNotAvailable(String),
+    SearchScanBudgetExceeded(String),
 }
 
 impl From<sea_orm::DbErr> for Error {
@@ -266,6 +267,7 @@ impl ErrorCodes {
             ErrorCodes::InvalidParams(_) => 20011,
             ErrorCodes::RatelimitExceeded(_) => 20012,
             ErrorCodes::SearchHistogramNotAvailable(_) => 20013,
+            ErrorCodes::SearchScanBudgetExceeded(_) => 20014,
         }
     }
 
@@ -295,6 +297,9 @@ impl ErrorCodes {
             ErrorCodes::SearchHistogramNotAvailable(_) => {
                 "Search histogram not available".to_string()
             }
+            ErrorCodes::SearchScanBudgetExceeded(msg) => {
+                format!("Search scan budget exceeded: {msg}")
+            }
         }
     }
 
@@ -314,6 +319,7 @@ impl ErrorCodes {
             ErrorCodes::InvalidParams(msg) => msg.to_owned(),
             ErrorCodes::RatelimitExceeded(msg) => msg.to_owned(),
             ErrorCodes::SearchHistogramNotAvailable(msg) => msg.to_owned(),
+            ErrorCodes::SearchScanBudgetExceeded(msg) => msg.to_owned(),
         }
     }
 
@@ -333,6 +339,7 @@ impl ErrorCodes {
             ErrorCodes::InvalidParams(msg) => msg.to_owned(),
             ErrorCodes::RatelimitExceeded(msg) => msg.to_owned(),
             ErrorCodes::SearchHistogramNotAvailable(msg) => msg.to_owned(),
+            ErrorCodes::SearchScanBudgetExceeded(msg) => msg.to_owned(),
         }
     }
 
@@ -382,6 +389,7 @@ impl ErrorCodes {
             20008 => Ok(ErrorCodes::SearchSQLExecuteError(message)),
             20009 => Ok(ErrorCodes::SearchCancelQuery(message)),
             20010 => Ok(ErrorCodes::SearchTimeout(message)),
+            20014 => Ok(ErrorCodes::SearchScanBudgetExceeded(message)),
             _ => Ok(ErrorCodes::ServerInternalError(json.to_string())),
         }
     }
@@ -450,6 +458,10 @@ mod tests {
             ErrorCodes::SearchHistogramNotAvailable("x".into()).get_code(),
             20013
         );
+        assert_eq!(
+            ErrorCodes::SearchScanBudgetExceeded("x".into()).get_code(),
+            20014
+        );
     }
 
     #[test]
@@ -143,6 +143,13 @@ impl TryFrom<alerts::Model> for MetaAlert {
             silence: value.trigger_silence_seconds / 60,
             timezone: value.trigger_frequency_cron_timezone,
             tolerance_in_secs: value.trigger_tolerance_seconds,
+            severity_levels: value
+                .trigger_severity_levels
+                .map(serde_json::from_value)
+                .transpose()?
+                .unwrap_or_default(),
+            threshold_field: value.trigger_threshold_field,
+            pending_in_secs: value.trigger_pending_seconds,
         };
         alert.set_last_satisfied_at(value.last_satisfied_at);
         alert.set_last_triggered_at(value.last_triggered_at);
@@ -158,6 +165,8 @@ impl TryFrom<alerts::Model> for MetaAlert {
         }
 
         alert.creates_incident = value.creates_incident;
+        alert.send_on_resolve = value.send_on_resolve;
+        alert.resolve_template = value.resolve_template;
 
         Ok(alert)
     }
@@ -666,6 +675,18 @@ fn update_mutable_fields(
         alert.trigger_condition.timezone.filter(|s| !s.is_empty());
     let trigger_silence_seconds = alert.trigger_condition.silence * 60;
     let trigger_tolerance_seconds = alert.trigger_condition.tolerance_in_secs;
+    let trigger_severity_levels = if alert.trigger_condition.severity_levels.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_value(
+            &alert.trigger_condition.severity_levels,
+        )?)
+    };
+    let trigger_threshold_field = alert
+        .trigger_condition
+        .threshold_field
+        .filter(|s| !s.is_empty());
+    let trigger_pending_seconds = alert.trigger_condition.pending_in_secs.filter(|s| *s > 0);
     let owner = alert.owner.filter(|s| !s.is_empty());
     let last_edited_by = alert.last_edited_by.filter(|s| !s.is_empty());
     let align_time = alert.trigger_condition.align_time;
@@ -715,6 +736,9 @@ fn update_mutable_fields(
     alert_am.trigger_frequency_cron_timezone = Set(trigger_frequency_cron_timezone);
     alert_am.trigger_silence_seconds = Set(trigger_silence_seconds);
     alert_am.trigger_tolerance_seconds = Set(trigger_tolerance_seconds);
+    alert_am.trigger_severity_levels = Set(trigger_severity_levels);
+    alert_am.trigger_threshold_field = Set(trigger_threshold_field);
+    alert_am.trigger_pending_seconds = Set(trigger_pending_seconds);
     alert_am.owner = Set(owner);
     alert_am.last_edited_by = Set(last_edited_by);
     alert_am.updated_at = Set(Some(updated_at));
@@ -723,6 +747,8 @@ fn update_mutable_fields(
     alert_am.dedup_time_window_minutes = Set(dedup_time_window_minutes);
     alert_am.dedup_config = Set(dedup_config);
     alert_am.creates_incident = Set(alert.creates_incident);
+    alert_am.send_on_resolve = Set(alert.send_on_resolve);
+    alert_am.resolve_template = Set(alert.resolve_template);
     Ok(())
 }
 
@@ -784,6 +810,11 @@ mod tests {
             dedup_time_window_minutes: None,
             dedup_config: None,
             creates_incident: false,
+            send_on_resolve: false,
+            resolve_template: None,
+            trigger_severity_levels: None,
+            trigger_threshold_field: None,
+            trigger_pending_seconds: None,
         }
     }
 
@@ -857,6 +888,37 @@ mod tests {
         assert!(alert.creates_incident);
     }
 
+    #[test]
+    fn test_try_from_model_send_on_resolve() {
+        let id = Ksuid::new(None, None).to_string();
+        let mut m = make_model(&id);
+        m.send_on_resolve = true;
+        m.resolve_template = Some("resolved".to_string());
+        let alert = MetaAlert::try_from(m).unwrap();
+        assert!(alert.send_on_resolve);
+        assert_eq!(alert.resolve_template.as_deref(), Some("resolved"));
+    }
+
+    #[test]
+    fn test_try_from_model_severity_levels() {
+        let id = Ksuid::new(None, None).to_string();
+        let mut m = make_model(&id);
+        m.trigger_severity_levels = Some(serde_json::json!([
+            {"severity": "P2", "operator": ">=", "threshold": 0.8},
+            {"severity": "P1", "operator": ">=", "threshold": 0.95, "destinations": ["pager"]}
+        ]));
+        m.trigger_threshold_field = Some("cpu".to_string());
+        let alert = MetaAlert::try_from(m).unwrap();
+        let levels = &alert.trigger_condition.severity_levels;
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].threshold, 0.8);
+        assert_eq!(levels[1].destinations, vec!["pager".to_string()]);
+        assert_eq!(
+            alert.trigger_condition.threshold_field.as_deref(),
+            Some("cpu")
+        );
+    }
+
     #[test]
     fn test_try_from_model_destinations_parsed() {
         let id = Ksuid::new(None, None).to_string();
@@ -58,6 +58,7 @@ pub enum Status {
     Pending = 0,
     Running = 1,
     Completed = 2,
+    Failed = 3,
 }
 
 impl From<i64> for Status {
@@ -66,6 +67,7 @@ impl From<i64> for Status {
             0 => Status::Pending,
             1 => Status::Running,
             2 => Status::Completed,
+            3 => Status::Failed,
             _ => unreachable!(),
         }
     }
@@ -259,6 +261,7 @@ mod tests {
         assert!(matches!(Status::from(0i64), Status::Pending));
         assert!(matches!(Status::from(1i64), Status::Running));
         assert!(matches!(Status::from(2i64), Status::Completed));
+        assert!(matches!(Status::from(3i64), Status::Failed));
     }
 
     #[test]
@@ -49,6 +49,11 @@ pub struct Model {
     pub dedup_time_window_minutes: Option<i32>,
     pub dedup_config: Option<Json>,
     pub creates_incident: bool,
+    pub send_on_resolve: bool,
+    pub resolve_template: Option<String>,
+    pub trigger_severity_levels: Option<Json>,
+    pub trigger_threshold_field: Option<String>,
+    pub trigger_pending_seconds: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -121,6 +126,11 @@ mod tests {
             dedup_time_window_minutes: None,
             dedup_config: None,
             creates_incident: false,
+            send_on_resolve: false,
+            resolve_template: None,
+            trigger_severity_levels: None,
+            trigger_threshold_field: None,
+            trigger_pending_seconds: None,
         };
         assert_eq!(m.id, "alert-1");
         assert_eq!(m.name, "High Error Rate");
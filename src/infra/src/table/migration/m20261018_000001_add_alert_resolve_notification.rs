@@ -0,0 +1,111 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migration to add `send_on_resolve` and `resolve_template` columns to the
+//! alerts table.
+//!
+//! When `send_on_resolve` is true, a notification is sent when an alert
+//! instance that was firing no longer matches the alert condition, using
+//! `resolve_template` if it is set.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports a single alteration per statement
+        manager.alter_table(add_send_on_resolve_statement()).await?;
+        manager.alter_table(add_resolve_template_statement()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::ResolveTemplate)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::SendOnResolve)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+fn add_send_on_resolve_statement() -> TableAlterStatement {
+    Table::alter()
+        .table(Alerts::Table)
+        .add_column_if_not_exists(
+            ColumnDef::new(Alerts::SendOnResolve)
+                .boolean()
+                .not_null()
+                .default(false),
+        )
+        .to_owned()
+}
+
+fn add_resolve_template_statement() -> TableAlterStatement {
+    Table::alter()
+        .table(Alerts::Table)
+        .add_column_if_not_exists(ColumnDef::new(Alerts::ResolveTemplate).string().null())
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    SendOnResolve,
+    ResolveTemplate,
+}
+
+#[cfg(test)]
+mod tests {
+    use collapse::*;
+
+    use super::*;
+
+    #[test]
+    fn postgres() {
+        collapsed_eq!(
+            &add_send_on_resolve_statement().to_string(PostgresQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN IF NOT EXISTS "send_on_resolve" bool NOT NULL DEFAULT FALSE"#
+        );
+        collapsed_eq!(
+            &add_resolve_template_statement().to_string(PostgresQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN IF NOT EXISTS "resolve_template" varchar NULL"#
+        );
+    }
+
+    #[test]
+    fn sqlite() {
+        collapsed_eq!(
+            &add_send_on_resolve_statement().to_string(SqliteQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN "send_on_resolve" boolean NOT NULL DEFAULT FALSE"#
+        );
+        collapsed_eq!(
+            &add_resolve_template_statement().to_string(SqliteQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN "resolve_template" varchar NULL"#
+        );
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migration to add `trigger_severity_levels` and `trigger_threshold_field`
+//! columns to the alerts table.
+//!
+//! Severity levels are stored as JSON, each level has its own float threshold,
+//! operator, destinations and template. `trigger_threshold_field` is the result
+//! column compared with the thresholds.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports a single alteration per statement
+        manager.alter_table(add_severity_levels_statement()).await?;
+        manager.alter_table(add_threshold_field_statement()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::TriggerThresholdField)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::TriggerSeverityLevels)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+fn add_severity_levels_statement() -> TableAlterStatement {
+    Table::alter()
+        .table(Alerts::Table)
+        .add_column_if_not_exists(ColumnDef::new(Alerts::TriggerSeverityLevels).json().null())
+        .to_owned()
+}
+
+fn add_threshold_field_statement() -> TableAlterStatement {
+    Table::alter()
+        .table(Alerts::Table)
+        .add_column_if_not_exists(
+            ColumnDef::new(Alerts::TriggerThresholdField)
+                .string_len(256)
+                .null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    TriggerSeverityLevels,
+    TriggerThresholdField,
+}
+
+#[cfg(test)]
+mod tests {
+    use collapse::*;
+
+    use super::*;
+
+    #[test]
+    fn postgres() {
+        collapsed_eq!(
+            &add_severity_levels_statement().to_string(PostgresQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN IF NOT EXISTS "trigger_severity_levels" json NULL"#
+        );
+        collapsed_eq!(
+            &add_threshold_field_statement().to_string(PostgresQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN IF NOT EXISTS "trigger_threshold_field" varchar(256) NULL"#
+        );
+    }
+
+    #[test]
+    fn sqlite() {
+        collapsed_eq!(
+            &add_severity_levels_statement().to_string(SqliteQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN "trigger_severity_levels" json_text NULL"#
+        );
+        collapsed_eq!(
+            &add_threshold_field_statement().to_string(SqliteQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN "trigger_threshold_field" varchar(256) NULL"#
+        );
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migration to add the `trigger_pending_seconds` column to the alerts table.
+//!
+//! It holds how long the condition of an alert must hold in consecutive
+//! evaluations before the alert fires.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.alter_table(add_pending_seconds_statement()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::TriggerPendingSeconds)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+fn add_pending_seconds_statement() -> TableAlterStatement {
+    Table::alter()
+        .table(Alerts::Table)
+        .add_column_if_not_exists(
+            ColumnDef::new(Alerts::TriggerPendingSeconds)
+                .big_integer()
+                .null(),
+        )
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    TriggerPendingSeconds,
+}
+
+#[cfg(test)]
+mod tests {
+    use collapse::*;
+
+    use super::*;
+
+    #[test]
+    fn postgres() {
+        collapsed_eq!(
+            &add_pending_seconds_statement().to_string(PostgresQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN IF NOT EXISTS "trigger_pending_seconds" bigint NULL"#
+        );
+    }
+
+    #[test]
+    fn sqlite() {
+        collapsed_eq!(
+            &add_pending_seconds_statement().to_string(SqliteQueryBuilder),
+            r#"ALTER TABLE "alerts" ADD COLUMN "trigger_pending_seconds" bigint NULL"#
+        );
+    }
+}
@@ -124,6 +124,9 @@ mod m20260520_000004_create_online_eval_jobs_table;
 mod m20260520_000005_drop_eval_templates_table;
 mod m20260604_000001_add_kind_to_pipeline;
 mod m20260622_000001_add_org_id_to_short_urls;
+mod m20261018_000001_add_alert_resolve_notification;
+mod m20261018_000002_add_alert_severity_levels;
+mod m20261018_000003_add_alert_pending_duration;
 
 pub struct Migrator;
 
@@ -237,6 +240,9 @@ impl MigratorTrait for Migrator {
             Box::new(m20260520_000005_drop_eval_templates_table::Migration),
             Box::new(m20260604_000001_add_kind_to_pipeline::Migration),
             Box::new(m20260622_000001_add_org_id_to_short_urls::Migration),
+            Box::new(m20261018_000001_add_alert_resolve_notification::Migration),
+            Box::new(m20261018_000002_add_alert_severity_levels::Migration),
+            Box::new(m20261018_000003_add_alert_pending_duration::Migration),
         ]
     }
 }
@@ -106,6 +106,17 @@ pub async fn run() -> Result<(), anyhow::Error> {
         }
     });
 
+    spawn_pausable_job!(
+        "run_delete_by_predicate",
+        get_config().compact.interval + 5,
+        {
+            log::debug!("[COMPACTOR::JOB] Running data delete by predicate");
+            if let Err(e) = compact::run_delete_by_predicate().await {
+                log::error!("[COMPACTOR::JOB] run data delete by predicate error: {e}");
+            }
+        }
+    );
+
     spawn_pausable_job!(
         "compactor_sync_to_db",
         get_config().compact.sync_to_db_interval,
@@ -33,6 +33,7 @@ pub async fn run() -> Result<(), anyhow::Error> {
     tokio::task::spawn(parquet::run());
     tokio::task::spawn(broadcast::run());
     tokio::task::spawn(clean_empty_dirs());
+    tokio::task::spawn(crate::service::materialized_views::flush::run_replay());
 
     Ok(())
 }
@@ -510,25 +510,26 @@ async fn move_files(
         // yield to other tasks
         tokio::task::yield_now().await;
         // merge file and get the big file key
-        let (account, new_file_name, new_file_meta, new_file_list) = match merge_files(
-            thread_id,
-            latest_schema.clone(),
-            &wal_dir,
-            &files_with_size,
-            num_uds_fields,
-        )
-        .await
-        {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("[INGESTER:JOB] merge files failed: {e}");
-                // need release all the files
-                for file in files_with_size.iter() {
-                    PROCESSING_FILES.write().await.remove(&file.key);
+        let (account, new_file_name, new_file_meta, new_file_list, new_file_data) =
+            match merge_files(
+                thread_id,
+                latest_schema.clone(),
+                &wal_dir,
+                &files_with_size,
+                num_uds_fields,
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("[INGESTER:JOB] merge files failed: {e}");
+                    // need release all the files
+                    for file in files_with_size.iter() {
+                        PROCESSING_FILES.write().await.remove(&file.key);
+                    }
+                    return Ok(());
                 }
-                return Ok(());
-            }
-        };
+            };
         if new_file_name.is_empty() {
             if new_file_list.is_empty() {
                 // no file need to merge
@@ -542,6 +543,7 @@ async fn move_files(
 
         // write file list to storage
         let new_file_min_ts = new_file_meta.min_ts;
+        let new_file_max_ts = new_file_meta.max_ts;
         if let Err(e) =
             db::file_list::set(&account, &new_file_name, Some(new_file_meta), false).await
         {
@@ -564,6 +566,27 @@ async fn move_files(
         )
         .await;
 
+        // fold the new file into the materialized views of the stream
+        if stream_type == StreamType::Logs
+            && !db::materialized_views::list_for_stream(&org_id, &stream_name).is_empty()
+        {
+            let org_id = org_id.clone();
+            let stream_name = stream_name.clone();
+            let account = account.clone();
+            let file = new_file_name.clone();
+            tokio::task::spawn(async move {
+                crate::service::materialized_views::flush::aggregate_file(
+                    &org_id,
+                    &stream_name,
+                    &account,
+                    &file,
+                    (new_file_min_ts, new_file_max_ts),
+                    new_file_data,
+                )
+                .await
+            });
+        }
+
         // check if allowed to delete the file
         for file in new_file_list.iter() {
             let file_key = &file.key;
@@ -636,20 +659,21 @@ async fn move_files(
 }
 
 /// merge some small files into one big file, upload to storage, returns the big
-/// file key and merged files
+/// file key, merged files and the content of the big file
 async fn merge_files(
     thread_id: usize,
     latest_schema: Arc<Schema>,
     wal_dir: &Path,
     files_with_size: &[FileKey],
     num_uds_fields: usize,
-) -> Result<(String, String, FileMeta, Vec<FileKey>), anyhow::Error> {
+) -> Result<(String, String, FileMeta, Vec<FileKey>, Bytes), anyhow::Error> {
     if files_with_size.is_empty() {
         return Ok((
             String::from(""),
             String::from(""),
             FileMeta::default(),
             Vec::new(),
+            Bytes::new(),
         ));
     }
 
@@ -687,6 +711,7 @@ async fn merge_files(
             String::from(""),
             FileMeta::default(),
             Vec::new(),
+            Bytes::new(),
         ));
     }
 
@@ -913,7 +938,7 @@ async fn merge_files(
 
     // skip index generation if not enabled or not supported by stream type
     if !cfg.common.inverted_index_enabled || !stream_type.support_index() {
-        return Ok((account, new_file_key, new_file_meta, retain_file_list));
+        return Ok((account, new_file_key, new_file_meta, retain_file_list, buf));
     }
 
     // skip index generation if no fields to index
@@ -928,7 +953,7 @@ async fn merge_files(
         .any(|f| latest_schema_fields.contains(f));
     if !need_index {
         log::debug!("skip index generation for stream: {org_id}/{stream_type}/{stream_name}");
-        return Ok((account, new_file_key, new_file_meta, retain_file_list));
+        return Ok((account, new_file_key, new_file_meta, retain_file_list, buf));
     }
 
     let index_size = create_tantivy_index(
@@ -938,13 +963,13 @@ async fn merge_files(
         &full_text_search_fields,
         &index_fields,
         latest_schema.clone(), // Use stream schema to include all configured fields
-        buf,
+        buf.clone(),
     )
     .await
     .map_err(|e| anyhow::anyhow!("generate_tantivy_index_on_ingester error: {e}"))?;
     new_file_meta.index_size = index_size as i64;
 
-    Ok((account, new_file_key, new_file_meta, retain_file_list))
+    Ok((account, new_file_key, new_file_meta, retain_file_list, buf))
 }
 
 fn split_perfix(prefix: &str) -> (String, StreamType, String, String) {
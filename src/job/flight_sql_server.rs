@@ -0,0 +1,62 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::SocketAddr;
+
+use arrow_flight::flight_service_server::FlightServiceServer;
+use config::{cluster::LOCAL_NODE, get_config};
+use tonic::{
+    codec::CompressionEncoding,
+    transport::{Identity, ServerTlsConfig},
+};
+
+use crate::handler::grpc::flight_sql::FlightSqlServiceImpl;
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.grpc.flight_sql_enabled || !LOCAL_NODE.is_querier() {
+        return Ok(());
+    }
+
+    let ip = if !cfg.grpc.addr.is_empty() {
+        cfg.grpc.addr.clone()
+    } else {
+        "0.0.0.0".to_string()
+    };
+    let addr: SocketAddr = format!("{ip}:{}", cfg.grpc.flight_sql_port).parse()?;
+    let svc = FlightServiceServer::new(FlightSqlServiceImpl)
+        .send_compressed(CompressionEncoding::Gzip)
+        .accept_compressed(CompressionEncoding::Gzip)
+        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
+
+    let builder = if cfg.grpc.tls_enabled {
+        let cert = std::fs::read_to_string(&cfg.grpc.tls_cert_path)?;
+        let key = std::fs::read_to_string(&cfg.grpc.tls_key_path)?;
+        let identity = Identity::from_pem(cert, key);
+        tonic::transport::Server::builder().tls_config(ServerTlsConfig::new().identity(identity))?
+    } else {
+        tonic::transport::Server::builder()
+    };
+    log::info!(
+        "[FLIGHT SQL] server {} listening on {addr}",
+        if cfg.grpc.tls_enabled { "with TLS" } else { "" },
+    );
+    if let Err(e) = builder.add_service(svc).serve(addr).await {
+        log::error!("[FLIGHT SQL] server error: {e}");
+        return Err(e.into());
+    }
+    Ok(())
+}
@@ -42,12 +42,14 @@ mod file_downloader;
 mod file_list_dump;
 pub(crate) mod files;
 mod flatten_compactor;
+mod flight_sql_server;
 #[cfg(feature = "enterprise")]
 mod incidents;
 pub mod metrics;
 mod mmdb_downloader;
 #[cfg(feature = "enterprise")]
 mod org_storage;
+mod pgwire_server;
 #[cfg(feature = "enterprise")]
 pub(crate) mod pipeline;
 mod pipeline_error_cleanup;
@@ -57,6 +59,8 @@ mod promql_self_consume;
 mod service_graph;
 mod session_cleanup;
 mod stats;
+mod statsd_server;
+mod syslog_server;
 
 pub use file_downloader::{download_from_node, queue_download};
 pub use mmdb_downloader::MMDB_INIT_NOTIFIER;
@@ -452,6 +456,9 @@ pub async fn init() -> Result<(), anyhow::Error> {
     // initialize metadata watcher
     tokio::task::spawn(db::schema::watch());
     tokio::task::spawn(db::functions::watch());
+    tokio::task::spawn(db::materialized_views::watch());
+    tokio::task::spawn(db::materialized_views::watch_pending_folds());
+    tokio::task::spawn(db::materialized_views::watch_gaps());
     tokio::task::spawn(db::compact::retention::watch());
     tokio::task::spawn(db::metrics::watch_prom_cluster_leader());
     tokio::task::spawn(db::system_settings::watch());
@@ -460,6 +467,7 @@ pub async fn init() -> Result<(), anyhow::Error> {
     tokio::task::spawn(db::alerts::destinations::watch());
     tokio::task::spawn(db::alerts::realtime_triggers::watch());
     tokio::task::spawn(db::alerts::alert::watch());
+    tokio::task::spawn(alerts::silences::watch());
     tokio::task::spawn(db::organization::org_settings_watch());
     #[cfg(feature = "enterprise")]
     tokio::task::spawn(o2_enterprise::enterprise::domain_management::db::watch());
@@ -503,6 +511,15 @@ pub async fn init() -> Result<(), anyhow::Error> {
     db::functions::cache()
         .await
         .expect("functions cache failed");
+    db::materialized_views::cache()
+        .await
+        .expect("materialized views cache failed");
+    db::materialized_views::cache_pending_folds()
+        .await
+        .expect("materialized view pending folds cache failed");
+    db::materialized_views::cache_gaps()
+        .await
+        .expect("materialized view gaps cache failed");
     db::compact::retention::cache()
         .await
         .expect("compact delete cache failed");
@@ -604,6 +621,10 @@ pub async fn init() -> Result<(), anyhow::Error> {
 
     tokio::task::spawn(files::run());
     tokio::task::spawn(stats::run());
+    tokio::task::spawn(syslog_server::run());
+    tokio::task::spawn(statsd_server::run());
+    tokio::task::spawn(flight_sql_server::run());
+    tokio::task::spawn(pgwire_server::run());
     tokio::task::spawn(compactor::run());
     tokio::task::spawn(flatten_compactor::run());
     #[cfg(feature = "enterprise")]
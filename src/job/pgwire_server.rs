@@ -0,0 +1,54 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{cluster::LOCAL_NODE, get_config};
+use tokio::{
+    net::TcpListener,
+    time::{self, Duration},
+};
+
+use crate::handler::pgwire;
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.pgwire.enabled || !LOCAL_NODE.is_querier() {
+        return Ok(());
+    }
+
+    let addr = if !cfg.pgwire.addr.is_empty() {
+        cfg.pgwire.addr.clone()
+    } else if cfg.http.ipv6_enabled {
+        "[::]".to_string()
+    } else {
+        "0.0.0.0".to_string()
+    };
+    let listener = TcpListener::bind(format!("{addr}:{}", cfg.pgwire.port)).await?;
+    log::info!("[PGWIRE] server listening on {}", listener.local_addr()?);
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                tokio::task::spawn(async move {
+                    if let Err(e) = pgwire::handle_connection(stream, peer).await {
+                        log::warn!("[PGWIRE] connection from {peer} closed: {e}");
+                    }
+                });
+            }
+            Err(e) => {
+                log::error!("[PGWIRE] accept error: {e}");
+                time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::SocketAddr;
+
+use config::{cluster::LOCAL_NODE, get_config, utils::time::now_micros};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, BufReader},
+    net::{TcpListener, TcpStream, UdpSocket},
+    sync::mpsc,
+    time::{self, Duration},
+};
+
+use crate::service::metrics::{
+    json::ingest_by_ingester,
+    statsd::{self, Aggregator, StatsdMetric},
+};
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.statsd.enabled || !LOCAL_NODE.is_ingester() {
+        return Ok(());
+    }
+
+    let addr = if !cfg.statsd.addr.is_empty() {
+        cfg.statsd.addr.clone()
+    } else if cfg.http.ipv6_enabled {
+        "[::]".to_string()
+    } else {
+        "0.0.0.0".to_string()
+    };
+
+    let (tx, rx) = mpsc::channel(10000);
+    tokio::task::spawn(run_aggregator(rx));
+
+    if cfg.statsd.tcp_port > 0 {
+        let listener = TcpListener::bind(format!("{addr}:{}", cfg.statsd.tcp_port)).await?;
+        log::info!(
+            "[STATSD] TCP listener listening on {}",
+            listener.local_addr()?
+        );
+        tokio::task::spawn(run_tcp(listener, tx.clone()));
+    }
+    if cfg.statsd.udp_port > 0 {
+        let socket = UdpSocket::bind(format!("{addr}:{}", cfg.statsd.udp_port)).await?;
+        log::info!(
+            "[STATSD] UDP listener listening on {}",
+            socket.local_addr()?
+        );
+        tokio::task::spawn(run_udp(socket, tx));
+    }
+    Ok(())
+}
+
+async fn run_tcp(listener: TcpListener, tx: mpsc::Sender<Vec<StatsdMetric>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                let tx = tx.clone();
+                tokio::task::spawn(async move {
+                    if let Err(e) = handle_tcp_connection(stream, peer, tx).await {
+                        log::warn!("[STATSD] TCP connection from {peer} closed: {e}");
+                    }
+                });
+            }
+            Err(e) => {
+                log::error!("[STATSD] TCP accept error: {e}");
+                time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+async fn handle_tcp_connection(
+    stream: TcpStream,
+    peer: SocketAddr,
+    tx: mpsc::Sender<Vec<StatsdMetric>>,
+) -> Result<(), anyhow::Error> {
+    let max_size = get_config().statsd.max_message_size as u64;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        // one byte more than the max size tells an oversized line apart
+        let n = (&mut reader)
+            .take(max_size + 1)
+            .read_line(&mut line)
+            .await?;
+        if n == 0 {
+            return Ok(());
+        }
+        if n as u64 > max_size {
+            anyhow::bail!("line exceeds {max_size} bytes");
+        }
+        let metrics = parse_lines(&line, peer);
+        if !metrics.is_empty() {
+            tx.send(metrics).await?;
+        }
+    }
+}
+
+async fn run_udp(socket: UdpSocket, tx: mpsc::Sender<Vec<StatsdMetric>>) {
+    let mut buf = vec![0u8; get_config().statsd.max_message_size];
+    loop {
+        let (n, peer) = match socket.recv_from(&mut buf).await {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[STATSD] UDP receive error: {e}");
+                continue;
+            }
+        };
+        // clients pack several newline separated lines into a datagram
+        let metrics = parse_lines(&String::from_utf8_lossy(&buf[..n]), peer);
+        if !metrics.is_empty() && tx.send(metrics).await.is_err() {
+            log::error!("[STATSD] aggregation channel closed, stopping UDP listener");
+            return;
+        }
+    }
+}
+
+fn parse_lines(data: &str, peer: SocketAddr) -> Vec<StatsdMetric> {
+    let mut metrics = Vec::new();
+    for line in data.lines() {
+        match statsd::parse_line(line) {
+            Ok(v) => metrics.extend(v),
+            Err(e) => log::debug!("[STATSD] invalid line from {peer}: {e}"),
+        }
+    }
+    metrics
+}
+
+/// Aggregates the received metrics and ingests the aggregates every flush
+/// interval.
+async fn run_aggregator(mut rx: mpsc::Receiver<Vec<StatsdMetric>>) {
+    let cfg = get_config();
+    let quantiles = statsd::parse_quantiles(&cfg.statsd.percentiles);
+    let mut interval = time::interval(Duration::from_secs(cfg.statsd.flush_interval_secs));
+    interval.tick().await; // the first tick completes immediately
+    let mut aggregator = Aggregator::new(cfg.statsd.expire_after_flushes);
+    loop {
+        tokio::select! {
+            ret = rx.recv() => match ret {
+                Some(metrics) => metrics.into_iter().for_each(|m| aggregator.add(m)),
+                None => {
+                    flush(&mut aggregator, &quantiles).await;
+                    return;
+                }
+            },
+            _ = interval.tick() => flush(&mut aggregator, &quantiles).await,
+        }
+    }
+}
+
+async fn flush(aggregator: &mut Aggregator, quantiles: &[f64]) {
+    let records = aggregator.flush(quantiles, now_micros());
+    if records.is_empty() {
+        return;
+    }
+    let count = records.len();
+    match ingest_by_ingester(&get_config().statsd.org_id, records).await {
+        Ok(()) => log::debug!("[STATSD] ingested {count} series"),
+        Err(e) => log::error!("[STATSD] ingestion of {count} series failed: {e}"),
+    }
+}
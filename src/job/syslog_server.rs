@@ -0,0 +1,183 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::SocketAddr;
+
+use bytes::BytesMut;
+use config::{cluster::LOCAL_NODE, get_config, utils::json};
+use tokio::{
+    io::AsyncReadExt,
+    net::{TcpListener, TcpStream, UdpSocket},
+    sync::mpsc,
+    time::{self, Duration},
+};
+
+use crate::service::logs::syslog;
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.syslog.enabled || !LOCAL_NODE.is_ingester() {
+        return Ok(());
+    }
+
+    let addr = if !cfg.syslog.addr.is_empty() {
+        cfg.syslog.addr.clone()
+    } else if cfg.http.ipv6_enabled {
+        "[::]".to_string()
+    } else {
+        "0.0.0.0".to_string()
+    };
+
+    let (tx, rx) = mpsc::channel(cfg.syslog.batch_size * 10);
+    tokio::task::spawn(run_ingester(rx));
+
+    if cfg.syslog.tcp_port > 0 {
+        let listener = TcpListener::bind(format!("{addr}:{}", cfg.syslog.tcp_port)).await?;
+        log::info!(
+            "[SYSLOG] TCP receiver listening on {}",
+            listener.local_addr()?
+        );
+        tokio::task::spawn(run_tcp(listener, tx.clone()));
+    }
+    if cfg.syslog.udp_port > 0 {
+        let socket = UdpSocket::bind(format!("{addr}:{}", cfg.syslog.udp_port)).await?;
+        log::info!(
+            "[SYSLOG] UDP receiver listening on {}",
+            socket.local_addr()?
+        );
+        tokio::task::spawn(run_udp(socket, tx));
+    }
+    Ok(())
+}
+
+async fn run_tcp(listener: TcpListener, tx: mpsc::Sender<json::Value>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                let tx = tx.clone();
+                tokio::task::spawn(async move {
+                    if let Err(e) = handle_tcp_connection(stream, peer, tx).await {
+                        log::warn!("[SYSLOG] TCP connection from {peer} closed: {e}");
+                    }
+                });
+            }
+            Err(e) => {
+                log::error!("[SYSLOG] TCP accept error: {e}");
+                time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+async fn handle_tcp_connection(
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    tx: mpsc::Sender<json::Value>,
+) -> Result<(), anyhow::Error> {
+    let max_size = get_config().syslog.max_message_size;
+    let mut buf = BytesMut::with_capacity(8192);
+    loop {
+        let n = stream.read_buf(&mut buf).await?;
+        while let Some(frame) = syslog::decode_frame(&mut buf, max_size)? {
+            tx.send(syslog::parse_message(&frame, Some(peer.ip())))
+                .await?;
+        }
+        if n == 0 {
+            // a final newline-delimited frame may come without its trailing newline
+            let frame = String::from_utf8_lossy(&buf);
+            let frame = frame.trim();
+            if !frame.is_empty() {
+                tx.send(syslog::parse_message(frame, Some(peer.ip())))
+                    .await?;
+            }
+            return Ok(());
+        }
+    }
+}
+
+async fn run_udp(socket: UdpSocket, tx: mpsc::Sender<json::Value>) {
+    let cfg = get_config();
+    let split_lines = cfg.syslog.udp_split_lines;
+    let mut buf = vec![0u8; cfg.syslog.max_message_size];
+    loop {
+        let (n, peer) = match socket.recv_from(&mut buf).await {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[SYSLOG] UDP receive error: {e}");
+                continue;
+            }
+        };
+        for msg in syslog::decode_datagram(&buf[..n], split_lines) {
+            if tx
+                .send(syslog::parse_message(&msg, Some(peer.ip())))
+                .await
+                .is_err()
+            {
+                log::error!("[SYSLOG] ingestion channel closed, stopping UDP receiver");
+                return;
+            }
+        }
+    }
+}
+
+/// Buffers parsed messages and ingests them when the batch is full or the
+/// flush interval elapses.
+async fn run_ingester(mut rx: mpsc::Receiver<json::Value>) {
+    let cfg = get_config();
+    let batch_size = cfg.syslog.batch_size;
+    let mut interval = time::interval(Duration::from_millis(cfg.syslog.flush_interval_ms));
+    interval.tick().await; // the first tick completes immediately
+    let mut records = Vec::with_capacity(batch_size);
+    loop {
+        tokio::select! {
+            ret = rx.recv() => match ret {
+                Some(record) => {
+                    records.push(record);
+                    if records.len() >= batch_size {
+                        flush(&mut records).await;
+                    }
+                }
+                None => {
+                    flush(&mut records).await;
+                    return;
+                }
+            },
+            _ = interval.tick() => flush(&mut records).await,
+        }
+    }
+}
+
+async fn flush(records: &mut Vec<json::Value>) {
+    if records.is_empty() {
+        return;
+    }
+    let cfg = get_config();
+    let records = std::mem::take(records);
+    let count = records.len();
+    match syslog::ingest(0, &cfg.syslog.org_id, &cfg.syslog.stream_name, records).await {
+        Ok(resp) if resp.code == 200 => {
+            log::debug!("[SYSLOG] ingested {count} messages");
+        }
+        Ok(resp) => {
+            log::error!(
+                "[SYSLOG] ingestion of {count} messages failed: {}",
+                resp.error.unwrap_or_default()
+            );
+        }
+        Err(e) => {
+            log::error!("[SYSLOG] ingestion of {count} messages failed: {e}");
+        }
+    }
+}
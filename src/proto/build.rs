@@ -159,6 +159,36 @@ fn main() -> Result<()> {
         .unwrap();
     file.write_all(code.as_str().as_ref()).unwrap();
 
+    tonic_prost_build::configure()
+        .compile_protos(&["proto/prometheus/write/v2/types.proto"], &["proto"])
+        .unwrap();
+
+    let path = "src/generated/prometheus_write_v2.rs";
+    let generated_source_path = out.join("io.prometheus.write.v2.rs");
+    let code = std::fs::read_to_string(generated_source_path).unwrap();
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(path)
+        .unwrap();
+    file.write_all(code.as_str().as_ref()).unwrap();
+
+    tonic_prost_build::configure()
+        .compile_protos(&["proto/zipkin/zipkin.proto"], &["proto"])
+        .unwrap();
+
+    let path = "src/generated/zipkin.rs";
+    let generated_source_path = out.join("zipkin.proto3.rs");
+    let code = std::fs::read_to_string(generated_source_path).unwrap();
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(path)
+        .unwrap();
+    file.write_all(code.as_str().as_ref()).unwrap();
+
     Ok(())
 }
 
@@ -15,4 +15,6 @@
 
 pub mod cluster;
 pub mod prometheus;
+pub mod prometheus_write_v2;
 pub mod loki;
+pub mod zipkin;
@@ -150,6 +150,10 @@ pub struct Histogram {
     /// conversion from time.Time to Prometheus timestamp.
     #[prost(int64, tag = "15")]
     pub timestamp: i64,
+    /// custom_values are not part of the specification, DO NOT use in remote write clients.
+    /// Used only for converting from OpenTelemetry to Prometheus internally.
+    #[prost(double, repeated, tag = "16")]
+    pub custom_values: ::prost::alloc::vec::Vec<f64>,
     /// Count of observations in the histogram.
     #[prost(oneof = "histogram::Count", tags = "1, 2")]
     pub count: ::core::option::Option<histogram::Count>,
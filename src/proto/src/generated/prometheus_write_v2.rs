@@ -0,0 +1,332 @@
+// This file is @generated by prost-build.
+/// Request represents a request to write the given timeseries to a remote destination.
+/// This message was introduced in the Remote Write 2.0 specification:
+/// <https://prometheus.io/docs/concepts/remote_write_spec_2_0/>
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Request {
+    /// symbols contains a de-duplicated array of string elements used for various
+    /// items in a Request message, like labels and metadata items. For the sender's convenience
+    /// around empty values for optional fields like unit_ref, symbols array MUST start with
+    /// empty string.
+    ///
+    /// To decode each of the symbolized strings, referenced, by "ref(s)" suffix, you
+    /// need to lookup the actual string by index from symbols array. The order of
+    /// strings is up to the sender. The receiver should not assume any particular encoding.
+    #[prost(string, repeated, tag = "4")]
+    pub symbols: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// timeseries represents an array of distinct series with 0 or more samples.
+    #[prost(message, repeated, tag = "5")]
+    pub timeseries: ::prost::alloc::vec::Vec<TimeSeries>,
+}
+/// TimeSeries represents a single series.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TimeSeries {
+    /// labels_refs is a list of label name-value pair references, encoded
+    /// as indices to the Request.symbols array. This list's length is always
+    /// a multiple of two, and the underlying labels should be sorted lexicographically.
+    ///
+    /// Note that there might be multiple TimeSeries objects in the same
+    /// Requests with the same labels e.g. for different exemplars, metadata
+    /// or created timestamp.
+    #[prost(uint32, repeated, tag = "1")]
+    pub labels_refs: ::prost::alloc::vec::Vec<u32>,
+    /// Timeseries messages can either specify samples or (native) histogram samples
+    /// (histogram field), but not both. For a typical sender (real-time metric
+    /// streaming), in healthy cases, there will be only one sample or histogram.
+    ///
+    /// Samples and histograms are sorted by timestamp (older first).
+    #[prost(message, repeated, tag = "2")]
+    pub samples: ::prost::alloc::vec::Vec<Sample>,
+    #[prost(message, repeated, tag = "3")]
+    pub histograms: ::prost::alloc::vec::Vec<Histogram>,
+    /// exemplars represents an optional set of exemplars attached to this series' samples.
+    #[prost(message, repeated, tag = "4")]
+    pub exemplars: ::prost::alloc::vec::Vec<Exemplar>,
+    /// metadata represents the metadata associated with the given series' samples.
+    #[prost(message, optional, tag = "5")]
+    pub metadata: ::core::option::Option<Metadata>,
+    /// created_timestamp represents an optional created timestamp associated with
+    /// this series' samples in ms format, typically for counter or histogram type
+    /// metrics. Created timestamp represents the time when the counter started
+    /// counting (sometimes referred to as start timestamp), which can increase
+    /// the accuracy of query results.
+    ///
+    /// Note that some receivers might require this and in return fail to
+    /// ingest such samples within the Request.
+    ///
+    /// For Go, see github.com/prometheus/prometheus/model/timestamp/timestamp.go
+    /// for conversion from/to time.Time to Prometheus timestamp.
+    ///
+    /// Note that the "optional" keyword is omitted due to
+    /// <https://cloud.google.com/apis/design/design_patterns.md#optional_primitive_fields>
+    /// Zero value means value not set. If you need to use exactly zero value for
+    /// the timestamp, use 1 millisecond before or after.
+    #[prost(int64, tag = "6")]
+    pub created_timestamp: i64,
+}
+/// Exemplar is an additional information attached to some series' samples.
+/// It is typically used to attach an example trace or request ID associated with
+/// the metric changes.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Exemplar {
+    /// labels_refs is an optional list of label name-value pair references, encoded
+    /// as indices to the Request.symbols array. This list's len is always
+    /// a multiple of 2, and the underlying labels should be sorted lexicographically.
+    /// If the exemplar references a trace it should use the `trace_id` label name, as a best practice.
+    #[prost(uint32, repeated, tag = "1")]
+    pub labels_refs: ::prost::alloc::vec::Vec<u32>,
+    /// value represents an exact example value. This can be useful when the exemplar
+    /// is attached to a histogram, which only gives an estimated value through buckets.
+    #[prost(double, tag = "2")]
+    pub value: f64,
+    /// timestamp represents the timestamp of the exemplar in ms.
+    ///
+    /// For Go, see github.com/prometheus/prometheus/model/timestamp/timestamp.go
+    /// for conversion from/to time.Time to Prometheus timestamp.
+    #[prost(int64, tag = "3")]
+    pub timestamp: i64,
+}
+/// Sample represents series sample.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct Sample {
+    /// value of the sample.
+    #[prost(double, tag = "1")]
+    pub value: f64,
+    /// timestamp represents timestamp of the sample in ms.
+    ///
+    /// For Go, see github.com/prometheus/prometheus/model/timestamp/timestamp.go
+    /// for conversion from/to time.Time to Prometheus timestamp.
+    #[prost(int64, tag = "2")]
+    pub timestamp: i64,
+}
+/// Metadata represents the metadata associated with the given series' samples.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Metadata {
+    #[prost(enumeration = "metadata::MetricType", tag = "1")]
+    pub r#type: i32,
+    /// help_ref is a reference to the Request.symbols array representing help
+    /// text for the metric. Help is optional, reference should point to an empty string in
+    /// such a case.
+    #[prost(uint32, tag = "3")]
+    pub help_ref: u32,
+    /// unit_ref is a reference to the Request.symbols array representing a unit
+    /// for the metric. Unit is optional, reference should point to an empty string in
+    /// such a case.
+    #[prost(uint32, tag = "4")]
+    pub unit_ref: u32,
+}
+/// Nested message and enum types in `Metadata`.
+pub mod metadata {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum MetricType {
+        Unspecified = 0,
+        Counter = 1,
+        Gauge = 2,
+        Histogram = 3,
+        Gaugehistogram = 4,
+        Summary = 5,
+        Info = 6,
+        Stateset = 7,
+    }
+    impl MetricType {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Unspecified => "METRIC_TYPE_UNSPECIFIED",
+                Self::Counter => "METRIC_TYPE_COUNTER",
+                Self::Gauge => "METRIC_TYPE_GAUGE",
+                Self::Histogram => "METRIC_TYPE_HISTOGRAM",
+                Self::Gaugehistogram => "METRIC_TYPE_GAUGEHISTOGRAM",
+                Self::Summary => "METRIC_TYPE_SUMMARY",
+                Self::Info => "METRIC_TYPE_INFO",
+                Self::Stateset => "METRIC_TYPE_STATESET",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "METRIC_TYPE_UNSPECIFIED" => Some(Self::Unspecified),
+                "METRIC_TYPE_COUNTER" => Some(Self::Counter),
+                "METRIC_TYPE_GAUGE" => Some(Self::Gauge),
+                "METRIC_TYPE_HISTOGRAM" => Some(Self::Histogram),
+                "METRIC_TYPE_GAUGEHISTOGRAM" => Some(Self::Gaugehistogram),
+                "METRIC_TYPE_SUMMARY" => Some(Self::Summary),
+                "METRIC_TYPE_INFO" => Some(Self::Info),
+                "METRIC_TYPE_STATESET" => Some(Self::Stateset),
+                _ => None,
+            }
+        }
+    }
+}
+/// A native histogram, also known as a sparse histogram.
+/// Original design doc:
+/// <https://docs.google.com/document/d/1cLNv3aufPZb3fNfaJgdaRBZsInZKKIHo9E6HinJVbpM/edit>
+/// The appendix of this design doc also explains the concept of float
+/// histograms. This Histogram message can represent both, the usual
+/// integer histogram as well as a float histogram.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Histogram {
+    /// Sum of observations in the histogram.
+    #[prost(double, tag = "3")]
+    pub sum: f64,
+    /// The schema defines the bucket schema. Currently, valid numbers
+    /// are -53 and numbers in range of -4 \<= n \<= 8. More valid numbers might be
+    /// added in future for new bucketing layouts.
+    ///
+    /// The schema equal to -53 means custom buckets. See
+    /// custom_values field description for more details.
+    ///
+    /// Values between -4 and 8 represent base-2 bucket schema, where 1
+    /// is a bucket boundary in each case, and then each power of two is
+    /// divided into 2^n (n is schema value) logarithmic buckets. Or in other words,
+    /// each bucket boundary is the previous boundary times 2^(2^-n).
+    #[prost(sint32, tag = "4")]
+    pub schema: i32,
+    /// Breadth of the zero bucket.
+    #[prost(double, tag = "5")]
+    pub zero_threshold: f64,
+    /// Negative Buckets.
+    #[prost(message, repeated, tag = "8")]
+    pub negative_spans: ::prost::alloc::vec::Vec<BucketSpan>,
+    /// Use either "negative_deltas" or "negative_counts", the former for
+    /// regular histograms with integer counts, the latter for
+    /// float histograms.
+    ///
+    /// Count delta of each bucket compared to previous one (or to zero for 1st bucket).
+    #[prost(sint64, repeated, tag = "9")]
+    pub negative_deltas: ::prost::alloc::vec::Vec<i64>,
+    /// Absolute count of each bucket.
+    #[prost(double, repeated, tag = "10")]
+    pub negative_counts: ::prost::alloc::vec::Vec<f64>,
+    /// Positive Buckets.
+    ///
+    /// In case of custom buckets (-53 schema value) the positive buckets are interpreted as follows:
+    /// * The span offset+length points to an the index of the custom_values array
+    /// or +Inf if pointing to the len of the array.
+    /// * The counts and deltas have the same meaning as for exponential histograms.
+    #[prost(message, repeated, tag = "11")]
+    pub positive_spans: ::prost::alloc::vec::Vec<BucketSpan>,
+    /// Use either "positive_deltas" or "positive_counts", the former for
+    /// regular histograms with integer counts, the latter for
+    /// float histograms.
+    ///
+    /// Count delta of each bucket compared to previous one (or to zero for 1st bucket).
+    #[prost(sint64, repeated, tag = "12")]
+    pub positive_deltas: ::prost::alloc::vec::Vec<i64>,
+    /// Absolute count of each bucket.
+    #[prost(double, repeated, tag = "13")]
+    pub positive_counts: ::prost::alloc::vec::Vec<f64>,
+    #[prost(enumeration = "histogram::ResetHint", tag = "14")]
+    pub reset_hint: i32,
+    /// timestamp represents timestamp of the sample in ms.
+    ///
+    /// For Go, see github.com/prometheus/prometheus/model/timestamp/timestamp.go
+    /// for conversion from/to time.Time to Prometheus timestamp.
+    #[prost(int64, tag = "15")]
+    pub timestamp: i64,
+    /// custom_values is an additional list of values, which can be used for
+    /// interpreting the positive buckets for custom buckets (-53 schema value).
+    /// The values represent the upper bounds of the buckets, which are sorted
+    /// ascending. Values must be finite and must not contain +Inf.
+    #[prost(double, repeated, tag = "16")]
+    pub custom_values: ::prost::alloc::vec::Vec<f64>,
+    /// Count of observations in the histogram.
+    #[prost(oneof = "histogram::Count", tags = "1, 2")]
+    pub count: ::core::option::Option<histogram::Count>,
+    /// Count in zero bucket.
+    #[prost(oneof = "histogram::ZeroCount", tags = "6, 7")]
+    pub zero_count: ::core::option::Option<histogram::ZeroCount>,
+}
+/// Nested message and enum types in `Histogram`.
+pub mod histogram {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum ResetHint {
+        /// Need to test for a counter reset explicitly.
+        Unspecified = 0,
+        /// This is the 1st histogram after a counter reset.
+        Yes = 1,
+        /// There was no counter reset between this and the previous Histogram.
+        No = 2,
+        /// This is a gauge histogram where counter resets don't happen.
+        Gauge = 3,
+    }
+    impl ResetHint {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Unspecified => "RESET_HINT_UNSPECIFIED",
+                Self::Yes => "RESET_HINT_YES",
+                Self::No => "RESET_HINT_NO",
+                Self::Gauge => "RESET_HINT_GAUGE",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "RESET_HINT_UNSPECIFIED" => Some(Self::Unspecified),
+                "RESET_HINT_YES" => Some(Self::Yes),
+                "RESET_HINT_NO" => Some(Self::No),
+                "RESET_HINT_GAUGE" => Some(Self::Gauge),
+                _ => None,
+            }
+        }
+    }
+    /// Count of observations in the histogram.
+    #[derive(Clone, Copy, PartialEq, ::prost::Oneof)]
+    pub enum Count {
+        #[prost(uint64, tag = "1")]
+        CountInt(u64),
+        #[prost(double, tag = "2")]
+        CountFloat(f64),
+    }
+    /// Count in zero bucket.
+    #[derive(Clone, Copy, PartialEq, ::prost::Oneof)]
+    pub enum ZeroCount {
+        #[prost(uint64, tag = "6")]
+        ZeroCountInt(u64),
+        #[prost(double, tag = "7")]
+        ZeroCountFloat(f64),
+    }
+}
+/// A BucketSpan defines a number of consecutive buckets with their
+/// offset. Logically, it would be more straightforward to include the
+/// bucket counts in the Span. However, the protobuf representation is
+/// more compact in the way the data is structured here (with all the
+/// buckets in a single array separate from the Spans).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct BucketSpan {
+    /// Gap to previous span, or starting point for 1st span (which can be negative).
+    #[prost(sint32, tag = "1")]
+    pub offset: i32,
+    /// Length of consecutive buckets.
+    #[prost(uint32, tag = "2")]
+    pub length: u32,
+}
@@ -26,16 +26,16 @@ use config::{
     SMTP_CLIENT, TIMESTAMP_COL_NAME, get_config,
     meta::{
         alerts::{
-            FrequencyType, Operator, QueryType, TriggerEvalResults,
+            ALERT_SEVERITY_FIELD, FrequencyType, Operator, QueryType, TriggerEvalResults,
             alert::{Alert, AlertListFilter, ListAlertsParams, RowTemplateType},
+            incidents::IncidentSeverity,
         },
-        destinations::{
-            AwsSns, DestinationType, Email, Endpoint, HTTPType, Module, Template, TemplateType,
-        },
+        destinations::{AwsSns, DestinationType, Email, Endpoint, Module, Template, TemplateType},
         folder::{DEFAULT_FOLDER, Folder, FolderType},
         search::{SearchEventContext, SearchEventType},
         sql::resolve_stream_names,
         stream::StreamType,
+        triggers::ResolvedAlertInstance,
     },
     utils::{
         base64,
@@ -70,13 +70,14 @@ use crate::{
     common::{
         infra::config::ORGANIZATIONS,
         meta::authz::Authz,
-        utils::{
-            auth::{is_ofga_unsupported, remove_ownership, set_ownership},
-            ssrf_guard::SsrfGuard,
-        },
+        utils::auth::{is_ofga_unsupported, remove_ownership, set_ownership},
     },
     service::{
-        alerts::{QueryConditionExt, build_sql, destinations},
+        alerts::{
+            QueryConditionExt, build_sql, destinations,
+            providers::{self, ProviderNotification},
+            webhooks,
+        },
         db, folders,
         search::sql::RE_ONLY_SELECT,
         short_url,
@@ -193,6 +194,9 @@ pub enum AlertError {
     /// Not support save destination remote pipeline for alert so far
     #[error("Not support save destination {0} type for alert so far")]
     NotSupportedAlertDestinationType(Module),
+
+    #[error("Invalid alert severity levels: {0}")]
+    InvalidSeverityLevels(String),
 }
 
 pub async fn save(
@@ -330,23 +334,41 @@ async fn prepare_alert(
         }
     }
 
-    // Validate alert-level template if specified
-    if let Some(ref template_name) = alert.template
-        && !template_name.is_empty()
-        && db::alerts::templates::get(org_id, template_name)
-            .await
-            .is_err()
+    validate_severity_levels(alert)?;
+
+    // Validate alert-level, resolve and severity level templates if specified
+    for template_name in [&alert.template, &alert.resolve_template]
+        .into_iter()
+        .chain(
+            alert
+                .trigger_condition
+                .severity_levels
+                .iter()
+                .map(|level| &level.template),
+        )
+        .flatten()
     {
-        return Err(AlertError::AlertTemplateNotFound {
-            template: template_name.clone(),
-        });
+        if !template_name.is_empty()
+            && db::alerts::templates::get(org_id, template_name)
+                .await
+                .is_err()
+        {
+            return Err(AlertError::AlertTemplateNotFound {
+                template: template_name.clone(),
+            });
+        }
     }
 
     // before saving alert check alert destination
     if alert.destinations.is_empty() {
         return Err(AlertError::AlertDestinationMissing);
     }
-    for dest in alert.destinations.iter() {
+    let severity_destinations = alert
+        .trigger_condition
+        .severity_levels
+        .iter()
+        .flat_map(|level| level.destinations.iter());
+    for dest in alert.destinations.iter().chain(severity_destinations) {
         match db::alerts::destinations::get(org_id, dest).await {
             Ok(d) => {
                 if !d.is_alert_destinations() {
@@ -461,6 +483,42 @@ async fn prepare_alert(
     Ok(())
 }
 
+/// Checks that the severity levels of a scheduled alert have distinct
+/// severities and numeric thresholds.
+fn validate_severity_levels(alert: &Alert) -> Result<(), AlertError> {
+    let levels = &alert.trigger_condition.severity_levels;
+    if levels.is_empty() {
+        return Ok(());
+    }
+    if alert.is_real_time {
+        return Err(AlertError::InvalidSeverityLevels(
+            "realtime alerts do not support severity levels".to_string(),
+        ));
+    }
+    let mut severities = HashSet::with_capacity(levels.len());
+    for level in levels.iter() {
+        if !severities.insert(level.severity.to_string()) {
+            return Err(AlertError::InvalidSeverityLevels(format!(
+                "severity {} is defined more than once",
+                level.severity
+            )));
+        }
+        if !level.threshold.is_finite() {
+            return Err(AlertError::InvalidSeverityLevels(format!(
+                "threshold of severity {} is not a number",
+                level.severity
+            )));
+        }
+        if matches!(level.operator, Operator::Contains | Operator::NotContains) {
+            return Err(AlertError::InvalidSeverityLevels(format!(
+                "operator {} of severity {} can not compare numbers",
+                level.operator, level.severity
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub fn update_cron_expression(cron_exp: &str, now: u32) -> String {
     let mut cron_exp = cron_exp.trim().to_owned();
     if cron_exp.starts_with("*") {
@@ -1003,6 +1061,15 @@ pub trait AlertExt: Sync + Send + 'static {
         start_time: Option<i64>,
         evaluation_timestamp: i64,
     ) -> Result<(String, String), AlertError>;
+
+    /// Sends the notification for an alert instance that stopped firing, using
+    /// the resolve template if one is set. Returns the same tuple as
+    /// `send_notification`.
+    async fn send_resolved_notification(
+        &self,
+        resolved: &ResolvedAlertInstance,
+        evaluation_timestamp: i64,
+    ) -> Result<(String, String), AlertError>;
 }
 
 #[async_trait]
@@ -1044,65 +1111,136 @@ impl AlertExt for Alert {
         start_time: Option<i64>,
         evaluation_timestamp: i64,
     ) -> Result<(String, String), AlertError> {
-        let mut err_message = "".to_string();
-        let mut success_message = "".to_string();
-        let mut no_of_error = 0;
-
-        // Get alert-level template if specified (takes precedence over destination templates)
-        let alert_template = if let Some(ref template_name) = self.template {
-            Some(
-                db::alerts::templates::get(&self.org_id, template_name)
-                    .await
-                    .map_err(|_| AlertError::AlertTemplateNotFound {
-                        template: template_name.clone(),
-                    })?,
-            )
-        } else {
-            None
+        send_to_destinations(
+            self,
+            self.template.as_ref(),
+            rows,
+            rows_end_time,
+            start_time,
+            evaluation_timestamp,
+            None,
+        )
+        .await
+    }
+
+    async fn send_resolved_notification(
+        &self,
+        resolved: &ResolvedAlertInstance,
+        evaluation_timestamp: i64,
+    ) -> Result<(String, String), AlertError> {
+        let template = self
+            .resolve_template
+            .as_ref()
+            .filter(|name| !name.is_empty())
+            .or(self.template.as_ref());
+        send_to_destinations(
+            self,
+            template,
+            std::slice::from_ref(&resolved.labels),
+            resolved.resolved_at,
+            Some(resolved.fired_at),
+            evaluation_timestamp,
+            Some(resolved),
+        )
+        .await
+    }
+}
+
+/// Sends the alert notification to every destination of the alert. The named
+/// template takes precedence over destination templates.
+async fn send_to_destinations(
+    alert: &Alert,
+    template_name: Option<&String>,
+    rows: &[Map<String, Value>],
+    rows_end_time: i64,
+    start_time: Option<i64>,
+    evaluation_timestamp: i64,
+    resolved: Option<&ResolvedAlertInstance>,
+) -> Result<(String, String), AlertError> {
+    let mut err_message = "".to_string();
+    let mut success_message = "".to_string();
+    let mut no_of_error = 0;
+
+    // Get alert-level template if specified (takes precedence over destination templates)
+    let alert_template = if let Some(template_name) = template_name {
+        Some(
+            db::alerts::templates::get(&alert.org_id, template_name)
+                .await
+                .map_err(|_| AlertError::AlertTemplateNotFound {
+                    template: template_name.clone(),
+                })?,
+        )
+    } else {
+        None
+    };
+
+    // Paging providers open an incident per alert instance, computed once for
+    // all their destinations
+    let mut instance_rows = None;
+
+    for dest_name in alert.destinations.iter() {
+        let (dest, dest_template) =
+            destinations::get_with_template(&alert.org_id, dest_name).await?;
+        let Module::Alert {
+            destination_type, ..
+        } = dest.module
+        else {
+            return Err(AlertError::GetDestinationWithTemplateError(
+                db::alerts::destinations::DestinationError::UnsupportedType,
+            ));
         };
 
-        for dest_name in self.destinations.iter() {
-            let (dest, dest_template) =
-                destinations::get_with_template(&self.org_id, dest_name).await?;
-            let Module::Alert {
-                destination_type, ..
-            } = dest.module
-            else {
-                return Err(AlertError::GetDestinationWithTemplateError(
-                    db::alerts::destinations::DestinationError::UnsupportedType,
-                ));
-            };
+        // Use alert-level template if specified, otherwise fall back to destination template
+        let template = match (&alert_template, &dest_template) {
+            (Some(alert_tpl), _) => alert_tpl,
+            (None, Some(dest_tpl)) => dest_tpl,
+            (None, None) => {
+                no_of_error += 1;
+                err_message = format!(
+                    "{err_message} No template configured for destination {};",
+                    dest.name
+                );
+                log::error!(
+                    "No template configured for alert {}/{}/{}/{} destination {}",
+                    alert.org_id,
+                    alert.stream_type,
+                    alert.stream_name,
+                    alert.name,
+                    dest.name
+                );
+                continue;
+            }
+        };
 
-            // Use alert-level template if specified, otherwise fall back to destination template
-            let template = match (&alert_template, &dest_template) {
-                (Some(alert_tpl), _) => alert_tpl,
-                (None, Some(dest_tpl)) => dest_tpl,
-                (None, None) => {
-                    no_of_error += 1;
-                    err_message = format!(
-                        "{err_message} No template configured for destination {};",
-                        dest.name
-                    );
-                    log::error!(
-                        "No template configured for alert {}/{}/{}/{} destination {}",
-                        self.org_id,
-                        self.stream_type,
-                        self.stream_name,
-                        self.name,
-                        dest.name
-                    );
-                    continue;
-                }
-            };
+        let notifications = if matches!(
+            destination_type,
+            DestinationType::PagerDuty(_) | DestinationType::Opsgenie(_)
+        ) {
+            if instance_rows.is_none() {
+                instance_rows = Some(group_rows_by_instance(alert, rows, resolved).await);
+            }
+            instance_rows
+                .iter()
+                .flatten()
+                .map(|(fingerprint, rows)| (Some(fingerprint.as_str()), rows.as_slice()))
+                .collect::<Vec<_>>()
+        } else {
+            vec![(None, rows)]
+        };
 
+        let mut dest_failed = false;
+        for (fingerprint, rows) in notifications {
             match send_notification(
-                self,
+                alert,
+                &dest.name,
                 &destination_type,
                 template,
                 rows,
                 rows_end_time,
                 start_time,
                 evaluation_timestamp,
+                resolved,
+                fingerprint,
             )
             .await
             {
@@ -1110,17 +1248,30 @@ impl AlertExt for Alert {
                     success_message =
                         format!("{success_message} destination {} {resp};", dest.name);
                 }
+                Err(e) if e.is::<webhooks::RetryPending>() => {
+                    // The scheduler owns the delivery now, it is not a failure
+                    // of the notification yet
+                    log::warn!(
+                        "Notification for {}/{}/{}/{} to destination {} is pending a retry",
+                        alert.org_id,
+                        alert.stream_type,
+                        alert.stream_name,
+                        alert.name,
+                        dest.name
+                    );
+                    success_message = format!("{success_message} destination {} {e};", dest.name);
+                }
                 Err(e) => {
                     log::error!(
                         "Error sending notification for {}/{}/{}/{} for destination {} err: {}",
-                        self.org_id,
-                        self.stream_type,
-                        self.stream_name,
-                        self.name,
+                        alert.org_id,
+                        alert.stream_type,
+                        alert.stream_name,
+                        alert.name,
                         dest.name,
                         e
                     );
-                    no_of_error += 1;
+                    dest_failed = true;
                     err_message = format!(
                         "{err_message} Error sending notification for destination {} err: {e};",
                         dest.name
@@ -1128,14 +1279,83 @@ impl AlertExt for Alert {
                 }
             }
         }
-        if no_of_error == self.destinations.len() {
-            Err(AlertError::SendNotificationError {
-                error_message: err_message,
+        if dest_failed {
+            no_of_error += 1;
+        }
+    }
+    if no_of_error == alert.destinations.len() {
+        Err(AlertError::SendNotificationError {
+            error_message: err_message,
+        })
+    } else {
+        Ok((success_message, err_message))
+    }
+}
+
+/// Returns the fingerprint of the alert instance of each result row. Without
+/// deduplication the whole alert is a single instance.
+pub(crate) async fn alert_instance_fingerprints(
+    alert: &Alert,
+    rows: &[Map<String, Value>],
+) -> Vec<String> {
+    #[cfg(feature = "enterprise")]
+    if let Some(dedup_config) = alert.deduplication.as_ref().filter(|d| d.enabled) {
+        let org_config =
+            crate::service::alerts::org_config::get_deduplication_config(&alert.org_id)
+                .await
+                .ok()
+                .flatten();
+        let semantic_groups =
+            crate::service::db::system_settings::get_semantic_field_groups(&alert.org_id).await;
+        return rows
+            .iter()
+            .map(|row| {
+                crate::service::alerts::deduplication::calculate_fingerprint(
+                    alert,
+                    row,
+                    dedup_config,
+                    org_config.as_ref(),
+                    &semantic_groups,
+                )
             })
-        } else {
-            Ok((success_message, err_message))
+            .collect();
+    }
+
+    vec![alert.get_unique_key(); rows.len()]
+}
+
+/// Splits the rows of a notification by alert instance, in the order the
+/// instances first appear. A resolve notification is about a single instance.
+async fn group_rows_by_instance(
+    alert: &Alert,
+    rows: &[Map<String, Value>],
+    resolved: Option<&ResolvedAlertInstance>,
+) -> Vec<(String, Vec<Map<String, Value>>)> {
+    if let Some(resolved) = resolved {
+        return vec![(resolved.fingerprint.clone(), rows.to_vec())];
+    }
+    if rows.is_empty() {
+        return vec![(alert.get_unique_key(), vec![])];
+    }
+    let fingerprints = alert_instance_fingerprints(alert, rows).await;
+    let mut groups: Vec<(String, Vec<Map<String, Value>>)> = Vec::new();
+    for (fingerprint, row) in fingerprints.into_iter().zip(rows) {
+        match groups.iter_mut().find(|(fp, _)| *fp == fingerprint) {
+            Some((_, rows)) => rows.push(row.clone()),
+            None => groups.push((fingerprint, vec![row.clone()])),
         }
     }
+    groups
+}
+
+/// Returns the default dedup key of the incident of an alert instance, the
+/// firing and resolved notifications of the instance share it.
+fn instance_dedup_key(alert: &Alert, fingerprint: Option<&str>) -> String {
+    let alert_key = alert.get_unique_key();
+    match fingerprint {
+        Some(fingerprint) if fingerprint != alert_key => format!("{alert_key}/{fingerprint}"),
+        _ => alert_key,
+    }
 }
 
 /// Send a pre-built message string to a single destination type.
@@ -1144,25 +1364,43 @@ impl AlertExt for Alert {
 /// going through the alert template system.
 #[cfg(feature = "enterprise")]
 pub(crate) async fn dispatch_notification(
+    org_id: &str,
+    dest_name: &str,
     dest_type: &DestinationType,
     subject: &str,
     msg: String,
 ) -> Result<String, anyhow::Error> {
     match dest_type {
-        DestinationType::Http(endpoint) => send_http_notification(endpoint, msg).await,
+        DestinationType::Http(endpoint) => {
+            send_http_notification(org_id, dest_name, subject, endpoint, msg).await
+        }
         DestinationType::Email(email) => send_email_notification(subject, email, msg).await,
         DestinationType::Sns(aws_sns) => send_sns_notification(subject, aws_sns, msg).await,
+        provider => {
+            let notification = ProviderNotification {
+                alert_name: subject.to_string(),
+                message: msg,
+                dedup_key: subject.to_string(),
+                severity: None,
+                resolved: false,
+            };
+            providers::send(provider, &notification).await
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn send_notification(
     alert: &Alert,
+    dest_name: &str,
     dest_type: &DestinationType,
     template: &Template,
     rows: &[Map<String, Value>],
     rows_end_time: i64,
     start_time: Option<i64>,
     evaluation_timestamp: i64,
+    resolved: Option<&ResolvedAlertInstance>,
+    fingerprint: Option<&str>,
 ) -> Result<String, anyhow::Error> {
     let org_name = if let Some(org) = ORGANIZATIONS.read().await.get(&alert.org_id) {
         org.name.clone()
@@ -1186,9 +1424,10 @@ async fn send_notification(
         DestinationType::Http(endpoint) => &endpoint.metadata,
         _ => &empty_meta,
     };
+    let body = process_lifecycle_template(&template.body, resolved);
     let msg: String = process_dest_template(
         &org_name,
-        &template.body,
+        &body,
         alert,
         rows,
         &rows_tpl_val,
@@ -1205,7 +1444,7 @@ async fn send_notification(
     let email_subject = if let TemplateType::Email { title } = &template.template_type {
         process_dest_template(
             &org_name,
-            title,
+            &process_lifecycle_template(title, resolved),
             alert,
             rows,
             &rows_tpl_val,
@@ -1223,13 +1462,63 @@ async fn send_notification(
     };
 
     match dest_type {
-        DestinationType::Http(endpoint) => send_http_notification(endpoint, msg).await,
+        DestinationType::Http(endpoint) => {
+            send_http_notification(&alert.org_id, dest_name, &alert.name, endpoint, msg).await
+        }
         DestinationType::Email(email) => send_email_notification(&email_subject, email, msg).await,
         DestinationType::Sns(aws_sns) => send_sns_notification(&alert.name, aws_sns, msg).await,
+        provider => {
+            // The firing and resolved notifications of an instance must share
+            // the dedup key
+            let dedup_key = match provider {
+                DestinationType::PagerDuty(pagerduty) => {
+                    match pagerduty.dedup_key.as_ref().filter(|key| !key.is_empty()) {
+                        Some(key_tpl) => {
+                            process_dest_template(
+                                &org_name,
+                                key_tpl,
+                                alert,
+                                rows,
+                                &rows_tpl_val,
+                                ProcessTemplateOptions {
+                                    rows_end_time,
+                                    start_time,
+                                    evaluation_timestamp,
+                                    is_email,
+                                },
+                                metadata,
+                            )
+                            .await
+                        }
+                        None => instance_dedup_key(alert, fingerprint),
+                    }
+                }
+                _ => instance_dedup_key(alert, fingerprint),
+            };
+            let severity = rows
+                .first()
+                .and_then(|row| row.get(ALERT_SEVERITY_FIELD))
+                .and_then(|v| v.as_str())
+                .and_then(|v| IncidentSeverity::from_str(v).ok());
+            let notification = ProviderNotification {
+                alert_name: alert.name.clone(),
+                message: msg,
+                dedup_key,
+                severity,
+                resolved: resolved.is_some(),
+            };
+            providers::send(provider, &notification).await
+        }
     }
 }
 
-async fn send_http_notification(endpoint: &Endpoint, msg: String) -> Result<String, anyhow::Error> {
+async fn send_http_notification(
+    org_id: &str,
+    dest_name: &str,
+    source: &str,
+    endpoint: &Endpoint,
+    msg: String,
+) -> Result<String, anyhow::Error> {
     #[cfg(feature = "enterprise")]
     let msg = if endpoint.action_id.is_some() {
         let incoming_msg = serde_json::from_str::<serde_json::Value>(&msg)
@@ -1261,68 +1550,7 @@ async fn send_http_notification(endpoint: &Endpoint, msg: String) -> Result<Stri
         msg
     };
 
-    // Block SSRF: validate the destination URL (including DNS resolution) before
-    // making any outbound request. The client is built through `build_safe_client`
-    // so that redirect targets and per-connect DNS resolution are re-validated.
-    if let Err(e) = SsrfGuard::validate_url_with_config_async(&endpoint.url).await {
-        return Err(anyhow::anyhow!(
-            "Destination URL blocked by SSRF guard: {e}"
-        ));
-    }
-
-    let builder = if endpoint.skip_tls_verify {
-        reqwest::Client::builder().danger_accept_invalid_certs(true)
-    } else {
-        reqwest::Client::builder()
-    };
-    let client = crate::common::utils::ssrf_guard::build_safe_client(builder)?;
-    let url = url::Url::parse(&endpoint.url)?;
-    let mut req = match endpoint.method {
-        HTTPType::POST => client.post(url),
-        HTTPType::PUT => client.put(url),
-        HTTPType::GET => client.get(url),
-    };
-
-    // Add additional headers if any from destination description
-    let mut has_context_type = false;
-    if let Some(headers) = &endpoint.headers {
-        for (key, value) in headers.iter() {
-            if !key.is_empty() && !value.is_empty() {
-                if key.to_lowercase().trim() == "content-type" {
-                    has_context_type = true;
-                }
-                req = req.header(key, value);
-            }
-        }
-    };
-    // set default content type
-    if !has_context_type {
-        req = req.header("Content-type", "application/json");
-    }
-
-    let resp = req.body(msg.clone()).send().await?;
-    let resp_status = resp.status();
-    let resp_body = resp.text().await?;
-
-    log::debug!(
-        "Alert sent to destination {} with status: {}, body:\n{}",
-        endpoint.url,
-        resp_status,
-        resp_body,
-    );
-
-    if !resp_status.is_success() {
-        log::error!(
-            "Alert http notification failed with status: {resp_status}, body: {resp_body}, payload: {msg}"
-        );
-        return Err(anyhow::anyhow!(
-            "sent error status: {}, err: {}",
-            resp_status,
-            resp_body
-        ));
-    }
-
-    Ok(format!("sent status: {resp_status}, body: {resp_body}"))
+    webhooks::deliver(org_id, dest_name, source, endpoint, msg).await
 }
 
 pub async fn send_email_notification(
@@ -1391,6 +1619,29 @@ async fn send_sns_notification(
     }
 }
 
+/// Replaces the alert lifecycle variables: `{alert_status}` is `firing` or
+/// `resolved`, the resolve variables are empty unless the alert resolved.
+fn process_lifecycle_template(tpl: &str, resolved: Option<&ResolvedAlertInstance>) -> String {
+    let (status, resolved_at, resolved_at_str, duration, fingerprint) = match resolved {
+        Some(resolved) => (
+            "resolved",
+            resolved.resolved_at.to_string(),
+            Local
+                .timestamp_nanos(resolved.resolved_at * 1000)
+                .format("%Y-%m-%dT%H:%M:%S")
+                .to_string(),
+            resolved.duration_secs().to_string(),
+            resolved.fingerprint.as_str(),
+        ),
+        None => ("firing", String::new(), String::new(), String::new(), ""),
+    };
+    tpl.replace("{alert_status}", status)
+        .replace("{alert_resolved_at_str}", &resolved_at_str)
+        .replace("{alert_resolved_at}", &resolved_at)
+        .replace("{alert_duration_seconds}", &duration)
+        .replace("{alert_fingerprint}", fingerprint)
+}
+
 fn process_row_template(
     org_name: &str,
     tpl: &String,
@@ -3841,6 +4092,48 @@ mod tests {
             e.to_string(),
             "Error creating alert in folder that cannot be found"
         );
+
+        let e = AlertError::InvalidSeverityLevels("bad".to_string());
+        assert_eq!(e.to_string(), "Invalid alert severity levels: bad");
+    }
+
+    #[test]
+    fn test_validate_severity_levels() {
+        use config::meta::alerts::{SeverityLevel, incidents::IncidentSeverity};
+
+        let mut alert = Alert::default();
+        assert!(validate_severity_levels(&alert).is_ok());
+
+        alert.trigger_condition.severity_levels = vec![
+            SeverityLevel {
+                severity: IncidentSeverity::P2,
+                operator: Operator::GreaterThanEquals,
+                threshold: 0.8,
+                ..Default::default()
+            },
+            SeverityLevel {
+                severity: IncidentSeverity::P1,
+                operator: Operator::GreaterThanEquals,
+                threshold: 0.95,
+                ..Default::default()
+            },
+        ];
+        assert!(validate_severity_levels(&alert).is_ok());
+
+        alert.trigger_condition.severity_levels[1].threshold = f64::NAN;
+        assert!(validate_severity_levels(&alert).is_err());
+        alert.trigger_condition.severity_levels[1].threshold = 0.95;
+
+        alert.trigger_condition.severity_levels[1].operator = Operator::Contains;
+        assert!(validate_severity_levels(&alert).is_err());
+        alert.trigger_condition.severity_levels[1].operator = Operator::GreaterThan;
+
+        alert.trigger_condition.severity_levels[1].severity = IncidentSeverity::P2;
+        assert!(validate_severity_levels(&alert).is_err());
+        alert.trigger_condition.severity_levels[1].severity = IncidentSeverity::P1;
+
+        alert.is_real_time = true;
+        assert!(validate_severity_levels(&alert).is_err());
     }
 
     // ── has_spread_rows additional branches ───────────────────────────────
@@ -4004,4 +4297,69 @@ mod tests {
         assert_eq!(parsed["full"], "hello world");
         assert_eq!(parsed["short"], "hello");
     }
+
+    #[test]
+    fn test_process_lifecycle_template() {
+        let tpl = "{alert_name} is {alert_status} after {alert_duration_seconds}s at \
+                   {alert_resolved_at} ({alert_fingerprint})";
+        assert_eq!(
+            process_lifecycle_template(tpl, None),
+            "{alert_name} is firing after s at  ()"
+        );
+
+        let resolved = ResolvedAlertInstance {
+            fingerprint: "abc".to_string(),
+            fired_at: 1_000_000,
+            resolved_at: 91_000_000,
+            labels: Map::new(),
+        };
+        assert_eq!(
+            process_lifecycle_template(tpl, Some(&resolved)),
+            "{alert_name} is resolved after 90s at 91000000 (abc)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_group_rows_by_instance() {
+        let alert = Alert {
+            org_id: "default".to_string(),
+            name: "errors".to_string(),
+            ..Default::default()
+        };
+        let key = alert.get_unique_key();
+        assert_eq!(instance_dedup_key(&alert, None), key);
+        assert_eq!(instance_dedup_key(&alert, Some(&key)), key);
+        assert_eq!(
+            instance_dedup_key(&alert, Some("host=a")),
+            format!("{key}/host=a")
+        );
+
+        let rows = vec![
+            serde_json::json!({"host": "a"})
+                .as_object()
+                .unwrap()
+                .clone(),
+            serde_json::json!({"host": "b"})
+                .as_object()
+                .unwrap()
+                .clone(),
+        ];
+        // without deduplication the alert is a single instance
+        let groups = group_rows_by_instance(&alert, &rows, None).await;
+        assert_eq!(groups, vec![(key.clone(), rows.clone())]);
+
+        let resolved = ResolvedAlertInstance {
+            fingerprint: "host=a".to_string(),
+            fired_at: 0,
+            resolved_at: 0,
+            labels: rows[0].clone(),
+        };
+        let groups = group_rows_by_instance(
+            &alert,
+            std::slice::from_ref(&resolved.labels),
+            Some(&resolved),
+        )
+        .await;
+        assert_eq!(groups, vec![("host=a".to_string(), vec![rows[0].clone()])]);
+    }
 }
@@ -20,6 +20,7 @@
 
 use config::{
     meta::alerts::{
+        ALERT_SEVERITY_FIELD,
         alert::Alert,
         deduplication::{DeduplicationConfig, GlobalDeduplicationConfig},
     },
@@ -30,7 +31,8 @@ use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, Qu
 
 /// Calculate fingerprint for an alert result row
 ///
-/// Delegates to enterprise implementation.
+/// Delegates to enterprise implementation. The fingerprint identifies the
+/// alert instance, it does not change with the triggered severity.
 pub fn calculate_fingerprint(
     alert: &Alert,
     result_row: &Map<String, Value>,
@@ -47,6 +49,19 @@ pub fn calculate_fingerprint(
     )
 }
 
+/// Returns the key of the deduplication state of an alert instance. The
+/// triggered severity of alerts with severity levels is part of the key, so an
+/// escalation is not suppressed by the notification of the lower severity.
+fn dedup_state_key(fingerprint: String, result_row: &Map<String, Value>) -> String {
+    match result_row
+        .get(ALERT_SEVERITY_FIELD)
+        .and_then(|severity| severity.as_str())
+    {
+        Some(severity) => format!("{fingerprint}/{severity}"),
+        None => fingerprint,
+    }
+}
+
 /// Get or create deduplication state
 pub async fn get_dedup_state(
     db: &DatabaseConnection,
@@ -212,8 +227,10 @@ async fn apply_deduplication_impl(
     let mut deduplicated_rows = Vec::new();
 
     for row in result_rows {
-        let fingerprint =
-            calculate_fingerprint(alert, &row, dedup_config, org_config, semantic_groups);
+        let fingerprint = dedup_state_key(
+            calculate_fingerprint(alert, &row, dedup_config, org_config, semantic_groups),
+            &row,
+        );
 
         // Check if this fingerprint exists and is within time window
         let should_send = match get_dedup_state(db, &fingerprint).await? {
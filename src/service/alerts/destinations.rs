@@ -24,10 +24,13 @@ use crate::{
         meta::authz::Authz,
         utils::auth::{is_ofga_unsupported, remove_ownership, set_ownership},
     },
-    service::db::{
-        self,
-        alerts::{destinations::DestinationError, templates::TemplateError},
-        user,
+    service::{
+        alerts::providers,
+        db::{
+            self,
+            alerts::{destinations::DestinationError, templates::TemplateError},
+            user,
+        },
     },
 };
 
@@ -172,12 +175,29 @@ pub async fn save(
                 {
                     return Err(DestinationError::SsrfBlocked(e));
                 }
+                if let Some(retry) = &endpoint.retry {
+                    retry
+                        .validate()
+                        .map_err(DestinationError::InvalidRetryPolicy)?;
+                }
             }
             DestinationType::Sns(aws_sns) => {
                 if aws_sns.sns_topic_arn.is_empty() || aws_sns.aws_region.is_empty() {
                     return Err(DestinationError::InvalidSns);
                 }
             }
+            provider => {
+                providers::validate(provider).map_err(DestinationError::InvalidProvider)?;
+                if let Some(url) = providers::user_url(provider)
+                    && let Err(e) =
+                        crate::common::utils::ssrf_guard::SsrfGuard::validate_url_with_config_async(
+                            url,
+                        )
+                        .await
+                {
+                    return Err(DestinationError::SsrfBlocked(e));
+                }
+            }
         },
         Module::Pipeline { endpoint, .. } => {
             if endpoint.url.is_empty() {
@@ -241,6 +261,8 @@ pub async fn save(
                 }
             }
             DestinationType::Sns(_) => None, // SNS doesn't have prebuilt templates yet
+            // Native providers wrap the rendered template in their own payload
+            _ => None,
         };
 
         // If it's a prebuilt type and doesn't have a custom template, ensure prebuilt template
@@ -328,6 +350,40 @@ pub async fn test_email(
     .map_err(|e| DestinationError::EmailSendFailed(e.to_string()))
 }
 
+/// Validates a native provider destination and sends a test notification.
+/// Paging providers are sent the resolve action right after, so the test
+/// does not leave an open incident behind.
+pub async fn test_provider(
+    dest_type: &DestinationType,
+    body: Option<&str>,
+) -> Result<String, DestinationError> {
+    providers::validate(dest_type).map_err(DestinationError::InvalidProvider)?;
+
+    let mut notification = providers::ProviderNotification {
+        alert_name: "OpenObserve Test Notification".to_string(),
+        message: body
+            .unwrap_or("This is a test notification from OpenObserve to verify your destination is configured correctly.")
+            .to_string(),
+        dedup_key: format!("openobserve-test-{}", config::ider::uuid()),
+        severity: None,
+        resolved: false,
+    };
+    let resp = providers::send(dest_type, &notification)
+        .await
+        .map_err(|e| DestinationError::ProviderSendFailed(e.to_string()))?;
+
+    if matches!(
+        dest_type,
+        DestinationType::PagerDuty(_) | DestinationType::Opsgenie(_)
+    ) {
+        notification.resolved = true;
+        providers::send(dest_type, &notification)
+            .await
+            .map_err(|e| DestinationError::ProviderSendFailed(e.to_string()))?;
+    }
+    Ok(resp)
+}
+
 pub async fn get(org_id: &str, name: &str) -> Result<Destination, DestinationError> {
     db::alerts::destinations::get(org_id, name).await
 }
@@ -21,10 +21,11 @@ use std::collections::HashMap;
 
 use config::{
     meta::alerts::{
+        ALERT_SEVERITY_FIELD,
         alert::Alert,
         incidents::{
             AlertEdge, AlertNode, CorrelationReason, EdgeType, Incident, IncidentAlert,
-            IncidentCorrelationOutcome, IncidentTopology, IncidentWithAlerts,
+            IncidentCorrelationOutcome, IncidentSeverity, IncidentTopology, IncidentWithAlerts,
         },
     },
     utils::json::{Map, Value},
@@ -378,6 +379,8 @@ async fn send_incident_notifications(
                     continue;
                 };
                 match crate::service::alerts::alert::dispatch_notification(
+                    org_id,
+                    dest_name,
                     &destination_type,
                     &subject,
                     msg.clone(),
@@ -572,6 +575,12 @@ pub async fn correlate_alert_to_incident(
             sem_result.group_values
         );
     }
+    // Alerts with severity levels carry the triggered severity in the result row
+    let severity = result_row
+        .get(ALERT_SEVERITY_FIELD)
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.parse::<IncidentSeverity>().ok());
+
     // Find or create incident
     let outcome = find_or_create_incident(
         &alert.org_id,
@@ -581,6 +590,7 @@ pub async fn correlate_alert_to_incident(
         triggered_at,
         &correlation_reason,
         &service_name,
+        severity,
     )
     .await?;
 
@@ -784,15 +794,19 @@ async fn create_new_incident(
     triggered_at: i64,
     correlation_reason: &str,
     service_name: &str,
+    severity: Option<IncidentSeverity>,
 ) -> Result<IncidentCorrelationOutcome, anyhow::Error> {
-    let severity = o2_enterprise::enterprise::alerts::incidents::determine_severity(None);
+    let severity = match severity {
+        Some(severity) => severity.to_string(),
+        None => o2_enterprise::enterprise::alerts::incidents::determine_severity(None).to_string(),
+    };
 
     let title =
         o2_enterprise::enterprise::alerts::incidents::generate_title(&alert.name, group_values);
 
     let incident = infra::table::alert_incidents::create(
         org_id,
-        severity,
+        &severity,
         serde_json::to_value(group_values)?,
         &key_type.to_string(),
         triggered_at,
@@ -866,7 +880,7 @@ async fn create_new_incident(
         && let Err(e) = o2_enterprise::enterprise::super_cluster::queue::incidents_create(
             org_id,
             &key_type.to_string(),
-            severity,
+            &severity,
             serde_json::to_value(group_values)?,
             triggered_at,
             Some(title),
@@ -953,6 +967,7 @@ async fn find_or_create_incident(
     triggered_at: i64,
     correlation_reason: &str,
     service_name: &str,
+    severity: Option<IncidentSeverity>,
 ) -> Result<IncidentCorrelationOutcome, anyhow::Error> {
     use config::meta::alerts::incidents::{DimensionRelationship, KeyType};
 
@@ -1200,6 +1215,7 @@ async fn find_or_create_incident(
         triggered_at,
         correlation_reason,
         service_name,
+        severity,
     )
     .await
 }
@@ -55,8 +55,11 @@ pub mod grouping;
 pub mod incidents;
 #[cfg(feature = "enterprise")]
 pub mod org_config;
+pub mod providers;
 pub mod scheduler;
+pub mod silences;
 pub mod templates;
+pub mod webhooks;
 
 #[async_trait]
 pub trait QueryConditionExt: Sync + Send + 'static {
@@ -228,6 +231,10 @@ impl QueryConditionExt for QueryCondition {
                         })
                         .collect();
 
+                if !trigger_condition.severity_levels.is_empty() {
+                    evaluate_severity_levels(trigger_condition, values, &mut eval_results);
+                    return Ok(eval_results);
+                }
                 let threshold = trigger_condition.threshold as usize;
                 eval_results.data = match trigger_condition.operator {
                     Operator::EqualTo => (values.len() == threshold).then_some(values),
@@ -510,6 +517,10 @@ impl QueryConditionExt for QueryCondition {
             records.len()
         );
         eval_results.query_took = Some(resp.took as i64);
+        if self.search_event_type.is_none() && !trigger_condition.severity_levels.is_empty() {
+            evaluate_severity_levels(trigger_condition, records, &mut eval_results);
+            return Ok(eval_results);
+        }
         eval_results.data = if self.search_event_type.is_none() {
             let threshold = trigger_condition.threshold as usize;
             match trigger_condition.operator {
@@ -529,6 +540,21 @@ impl QueryConditionExt for QueryCondition {
     }
 }
 
+/// Sets the rows matching a severity level of the trigger condition as the
+/// evaluation result, each row is tagged with its own triggered severity. The
+/// most severe level matched is the triggered severity of the alert.
+fn evaluate_severity_levels(
+    trigger_condition: &TriggerCondition,
+    rows: Vec<Map<String, Value>>,
+    eval_results: &mut TriggerEvalResults,
+) {
+    let Some((level, rows)) = trigger_condition.match_severity_level(rows) else {
+        return;
+    };
+    eval_results.severity = Some(level.clone());
+    eval_results.data = Some(rows);
+}
+
 #[async_trait]
 pub trait ConditionListExt: Sync + Send + 'static {
     async fn len(&self) -> u32;
@@ -1263,7 +1289,8 @@ mod tests {
     use arrow_schema::{DataType, Field, Schema};
     use config::{
         meta::alerts::{
-            ConditionGroup, ConditionItem, ConditionItemCondition, LogicalOperator, Operator,
+            ALERT_SEVERITY_FIELD, ConditionGroup, ConditionItem, ConditionItemCondition,
+            LogicalOperator, Operator,
         },
         utils::json::Value,
     };
@@ -2138,4 +2165,43 @@ mod tests {
         let result = build_expr(&cond, "", &DataType::Boolean);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_evaluate_severity_levels_tags_rows() {
+        let trigger_condition = TriggerCondition {
+            severity_levels: vec![config::meta::alerts::SeverityLevel {
+                severity: config::meta::alerts::incidents::IncidentSeverity::P2,
+                operator: Operator::GreaterThan,
+                threshold: 0.5,
+                ..Default::default()
+            }],
+            threshold_field: Some("ratio".to_string()),
+            ..Default::default()
+        };
+        let rows = vec![
+            serde_json::json!({"ratio": 0.25})
+                .as_object()
+                .unwrap()
+                .clone(),
+            serde_json::json!({"ratio": "0.75"})
+                .as_object()
+                .unwrap()
+                .clone(),
+        ];
+
+        let mut eval_results = TriggerEvalResults::default();
+        evaluate_severity_levels(&trigger_condition, rows, &mut eval_results);
+        let data = eval_results.data.unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0][ALERT_SEVERITY_FIELD], "P2");
+        assert_eq!(
+            eval_results.severity.map(|level| level.severity),
+            Some(config::meta::alerts::incidents::IncidentSeverity::P2)
+        );
+
+        let mut eval_results = TriggerEvalResults::default();
+        evaluate_severity_levels(&trigger_condition, vec![], &mut eval_results);
+        assert!(eval_results.data.is_none());
+        assert!(eval_results.severity.is_none());
+    }
 }
@@ -0,0 +1,452 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Native chat and paging destinations
+//!
+//! Builds the provider specific payloads for Slack, Microsoft Teams, PagerDuty
+//! Events v2 and Opsgenie around the rendered alert template, and maps alert
+//! resolution to the provider's resolve or close action.
+
+use config::{
+    meta::{
+        alerts::incidents::IncidentSeverity,
+        destinations::{
+            DestinationType, MsTeams, Opsgenie, OpsgeniePriority, PagerDuty, PagerDutySeverity,
+            Slack,
+        },
+    },
+    utils::json::{self, Value},
+};
+
+use crate::common::utils::ssrf_guard::{SsrfGuard, build_safe_client};
+
+pub const SLACK_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+pub const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
+const SOURCE: &str = "OpenObserve";
+// Provider limits on the summary and description fields
+const PAGERDUTY_SUMMARY_MAX_LEN: usize = 1024;
+const OPSGENIE_MESSAGE_MAX_LEN: usize = 130;
+const OPSGENIE_DESCRIPTION_MAX_LEN: usize = 15000;
+
+/// A rendered alert notification for a native provider destination
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProviderNotification {
+    pub alert_name: String,
+    /// The rendered template
+    pub message: String,
+    /// Groups the firing and resolved notifications of an alert instance
+    pub dedup_key: String,
+    /// Severity reported by the alert, overrides the destination default
+    pub severity: Option<IncidentSeverity>,
+    pub resolved: bool,
+}
+
+impl ProviderNotification {
+    fn title(&self) -> String {
+        if self.resolved {
+            format!("[RESOLVED] {}", self.alert_name)
+        } else {
+            format!("[FIRING] {}", self.alert_name)
+        }
+    }
+}
+
+/// Sends the notification to a native provider destination
+pub async fn send(
+    dest_type: &DestinationType,
+    notification: &ProviderNotification,
+) -> Result<String, anyhow::Error> {
+    let (status, body) = match dest_type {
+        DestinationType::Slack(slack) => send_slack(slack, notification).await?,
+        DestinationType::MsTeams(teams) => {
+            let payload = msteams_payload(notification);
+            post_json(&teams.webhook_url, &[], &payload).await?
+        }
+        DestinationType::PagerDuty(pagerduty) => {
+            let payload = pagerduty_payload(pagerduty, notification);
+            post_json(PAGERDUTY_EVENTS_URL, &[], &payload).await?
+        }
+        DestinationType::Opsgenie(opsgenie) => {
+            let (url, payload) = opsgenie_request(opsgenie, notification);
+            let auth = format!("GenieKey {}", opsgenie.api_key);
+            post_json(&url, &[("Authorization", &auth)], &payload).await?
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "destination is not a native provider destination"
+            ));
+        }
+    };
+    Ok(format!("sent status: {status}, body: {body}"))
+}
+
+async fn send_slack(
+    slack: &Slack,
+    notification: &ProviderNotification,
+) -> Result<(reqwest::StatusCode, String), anyhow::Error> {
+    let payload = slack_payload(slack, notification);
+    if let Some(webhook_url) = slack.webhook_url.as_ref().filter(|u| !u.is_empty()) {
+        return post_json(webhook_url, &[], &payload).await;
+    }
+    let token = slack
+        .bot_token
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Slack destination must have a webhook url or bot token"))?;
+    let auth = format!("Bearer {token}");
+    let (status, body) = post_json(
+        SLACK_POST_MESSAGE_URL,
+        &[("Authorization", &auth)],
+        &payload,
+    )
+    .await?;
+    // The Web API reports errors in the body with a 200 status
+    if let Ok(resp) = json::from_str::<Value>(&body)
+        && resp.get("ok").and_then(|v| v.as_bool()) == Some(false)
+    {
+        return Err(anyhow::anyhow!(
+            "Slack API error: {}",
+            resp.get("error")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+        ));
+    }
+    Ok((status, body))
+}
+
+async fn post_json(
+    url: &str,
+    headers: &[(&str, &str)],
+    payload: &Value,
+) -> Result<(reqwest::StatusCode, String), anyhow::Error> {
+    // Block SSRF, the client also re-validates redirect targets
+    if let Err(e) = SsrfGuard::validate_url_with_config_async(url).await {
+        return Err(anyhow::anyhow!(
+            "Destination URL blocked by SSRF guard: {e}"
+        ));
+    }
+    let client = build_safe_client(reqwest::Client::builder())?;
+    let mut req = client
+        .post(url::Url::parse(url)?)
+        .header("Content-type", "application/json");
+    for (key, value) in headers {
+        req = req.header(*key, *value);
+    }
+    let resp = req.body(json::to_string(payload)?).send().await?;
+    let resp_status = resp.status();
+    let resp_body = resp.text().await?;
+    if !resp_status.is_success() {
+        log::error!(
+            "Alert provider notification failed with status: {resp_status}, body: {resp_body}"
+        );
+        return Err(anyhow::anyhow!(
+            "sent error status: {resp_status}, err: {resp_body}"
+        ));
+    }
+    Ok((resp_status, resp_body))
+}
+
+/// Slack message with a colored attachment, green once resolved
+pub fn slack_payload(slack: &Slack, notification: &ProviderNotification) -> Value {
+    let color = if notification.resolved {
+        "good"
+    } else {
+        "danger"
+    };
+    let mut payload = json::json!({
+        "text": notification.title(),
+        "attachments": [{
+            "color": color,
+            "text": notification.message,
+        }],
+    });
+    if let Some(channel) = slack.channel.as_ref().filter(|c| !c.is_empty()) {
+        payload["channel"] = Value::String(channel.clone());
+    }
+    payload
+}
+
+/// Adaptive card message for a Teams Workflows webhook
+pub fn msteams_payload(notification: &ProviderNotification) -> Value {
+    let color = if notification.resolved {
+        "Good"
+    } else {
+        "Attention"
+    };
+    json::json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {
+                        "type": "TextBlock",
+                        "size": "Medium",
+                        "weight": "Bolder",
+                        "color": color,
+                        "text": notification.title(),
+                        "wrap": true,
+                    },
+                    {
+                        "type": "TextBlock",
+                        "text": notification.message,
+                        "wrap": true,
+                    },
+                ],
+            },
+        }],
+    })
+}
+
+/// Events v2 `trigger` event, or `resolve` once the alert resolved
+pub fn pagerduty_payload(pagerduty: &PagerDuty, notification: &ProviderNotification) -> Value {
+    if notification.resolved {
+        return json::json!({
+            "routing_key": pagerduty.routing_key,
+            "event_action": "resolve",
+            "dedup_key": notification.dedup_key,
+        });
+    }
+    let severity = notification
+        .severity
+        .map(|severity| match severity {
+            IncidentSeverity::P1 => PagerDutySeverity::Critical,
+            IncidentSeverity::P2 => PagerDutySeverity::Error,
+            IncidentSeverity::P3 => PagerDutySeverity::Warning,
+            IncidentSeverity::P4 => PagerDutySeverity::Info,
+        })
+        .unwrap_or(pagerduty.severity);
+    json::json!({
+        "routing_key": pagerduty.routing_key,
+        "event_action": "trigger",
+        "dedup_key": notification.dedup_key,
+        "payload": {
+            "summary": truncate(&notification.title(), PAGERDUTY_SUMMARY_MAX_LEN),
+            "source": SOURCE,
+            "severity": severity.to_string(),
+            "custom_details": {
+                "message": notification.message,
+            },
+        },
+    })
+}
+
+/// Alert API request, creating the alert or closing it by alias once resolved
+pub fn opsgenie_request(
+    opsgenie: &Opsgenie,
+    notification: &ProviderNotification,
+) -> (String, Value) {
+    let base_url = opsgenie
+        .api_url
+        .as_deref()
+        .filter(|u| !u.is_empty())
+        .unwrap_or(OPSGENIE_API_URL)
+        .trim_end_matches('/');
+    if notification.resolved {
+        let url = format!(
+            "{base_url}/v2/alerts/{}/close?identifierType=alias",
+            url::form_urlencoded::byte_serialize(notification.dedup_key.as_bytes())
+                .collect::<String>()
+        );
+        let payload = json::json!({
+            "source": SOURCE,
+            "note": truncate(&notification.message, OPSGENIE_DESCRIPTION_MAX_LEN),
+        });
+        return (url, payload);
+    }
+    let priority = notification
+        .severity
+        .map(|severity| match severity {
+            IncidentSeverity::P1 => OpsgeniePriority::P1,
+            IncidentSeverity::P2 => OpsgeniePriority::P2,
+            IncidentSeverity::P3 => OpsgeniePriority::P3,
+            IncidentSeverity::P4 => OpsgeniePriority::P4,
+        })
+        .unwrap_or(opsgenie.priority);
+    let payload = json::json!({
+        "message": truncate(&notification.title(), OPSGENIE_MESSAGE_MAX_LEN),
+        "alias": notification.dedup_key,
+        "description": truncate(&notification.message, OPSGENIE_DESCRIPTION_MAX_LEN),
+        "priority": priority.to_string(),
+        "source": SOURCE,
+    });
+    (format!("{base_url}/v2/alerts"), payload)
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    s.chars().take(max_chars).collect()
+}
+
+/// Validates the provider config of a destination
+pub fn validate(dest_type: &DestinationType) -> Result<(), String> {
+    match dest_type {
+        DestinationType::Slack(slack) => {
+            let has_webhook = slack.webhook_url.as_ref().is_some_and(|u| !u.is_empty());
+            let has_bot = slack.bot_token.as_ref().is_some_and(|t| !t.is_empty())
+                && slack.channel.as_ref().is_some_and(|c| !c.is_empty());
+            if !has_webhook && !has_bot {
+                return Err(
+                    "Slack destination must have a webhook url or a bot token and channel"
+                        .to_string(),
+                );
+            }
+        }
+        DestinationType::MsTeams(MsTeams { webhook_url }) => {
+            if webhook_url.is_empty() {
+                return Err("Microsoft Teams destination must have a workflow url".to_string());
+            }
+        }
+        DestinationType::PagerDuty(pagerduty) => {
+            if pagerduty.routing_key.is_empty() {
+                return Err("PagerDuty destination must have a routing key".to_string());
+            }
+        }
+        DestinationType::Opsgenie(opsgenie) => {
+            if opsgenie.api_key.is_empty() {
+                return Err("Opsgenie destination must have an API key".to_string());
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// URL the destination sends to when configured by the user, used for SSRF
+/// validation on save
+pub fn user_url(dest_type: &DestinationType) -> Option<&str> {
+    match dest_type {
+        DestinationType::Slack(slack) => slack.webhook_url.as_deref().filter(|u| !u.is_empty()),
+        DestinationType::MsTeams(teams) => Some(teams.webhook_url.as_str()),
+        DestinationType::Opsgenie(opsgenie) => {
+            opsgenie.api_url.as_deref().filter(|u| !u.is_empty())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(resolved: bool) -> ProviderNotification {
+        ProviderNotification {
+            alert_name: "cpu_high".to_string(),
+            message: "cpu is at 95%".to_string(),
+            dedup_key: "default/logs/k8s/cpu_high".to_string(),
+            severity: None,
+            resolved,
+        }
+    }
+
+    #[test]
+    fn test_slack_payload() {
+        let slack = Slack {
+            bot_token: Some("xoxb-1".to_string()),
+            channel: Some("#alerts".to_string()),
+            ..Default::default()
+        };
+        let payload = slack_payload(&slack, &notification(false));
+        assert_eq!(payload["text"], "[FIRING] cpu_high");
+        assert_eq!(payload["channel"], "#alerts");
+        assert_eq!(payload["attachments"][0]["color"], "danger");
+        assert_eq!(payload["attachments"][0]["text"], "cpu is at 95%");
+
+        let payload = slack_payload(&Slack::default(), &notification(true));
+        assert_eq!(payload["text"], "[RESOLVED] cpu_high");
+        assert_eq!(payload["attachments"][0]["color"], "good");
+        assert!(payload.get("channel").is_none());
+    }
+
+    #[test]
+    fn test_msteams_payload() {
+        let payload = msteams_payload(&notification(true));
+        let body = &payload["attachments"][0]["content"]["body"];
+        assert_eq!(body[0]["text"], "[RESOLVED] cpu_high");
+        assert_eq!(body[0]["color"], "Good");
+        assert_eq!(body[1]["text"], "cpu is at 95%");
+    }
+
+    #[test]
+    fn test_pagerduty_payload() {
+        let pagerduty = PagerDuty {
+            routing_key: "R0UT1NG".to_string(),
+            ..Default::default()
+        };
+        let payload = pagerduty_payload(&pagerduty, &notification(false));
+        assert_eq!(payload["event_action"], "trigger");
+        assert_eq!(payload["dedup_key"], "default/logs/k8s/cpu_high");
+        assert_eq!(payload["payload"]["severity"], "error");
+        assert_eq!(payload["payload"]["source"], "OpenObserve");
+
+        let mut n = notification(false);
+        n.severity = Some(IncidentSeverity::P1);
+        let payload = pagerduty_payload(&pagerduty, &n);
+        assert_eq!(payload["payload"]["severity"], "critical");
+
+        let payload = pagerduty_payload(&pagerduty, &notification(true));
+        assert_eq!(payload["event_action"], "resolve");
+        assert_eq!(payload["dedup_key"], "default/logs/k8s/cpu_high");
+        assert!(payload.get("payload").is_none());
+    }
+
+    #[test]
+    fn test_opsgenie_request() {
+        let opsgenie = Opsgenie {
+            api_key: "key".to_string(),
+            ..Default::default()
+        };
+        let (url, payload) = opsgenie_request(&opsgenie, &notification(false));
+        assert_eq!(url, "https://api.opsgenie.com/v2/alerts");
+        assert_eq!(payload["alias"], "default/logs/k8s/cpu_high");
+        assert_eq!(payload["priority"], "P3");
+
+        let opsgenie = Opsgenie {
+            api_url: Some("https://api.eu.opsgenie.com/".to_string()),
+            ..opsgenie
+        };
+        let (url, payload) = opsgenie_request(&opsgenie, &notification(true));
+        assert_eq!(
+            url,
+            "https://api.eu.opsgenie.com/v2/alerts/default%2Flogs%2Fk8s%2Fcpu_high/close?identifierType=alias"
+        );
+        assert_eq!(payload["note"], "cpu is at 95%");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&DestinationType::Slack(Slack::default())).is_err());
+        assert!(
+            validate(&DestinationType::Slack(Slack {
+                bot_token: Some("xoxb-1".to_string()),
+                ..Default::default()
+            }))
+            .is_err()
+        );
+        assert!(
+            validate(&DestinationType::Slack(Slack {
+                webhook_url: Some("https://hooks.slack.com/services/x".to_string()),
+                ..Default::default()
+            }))
+            .is_ok()
+        );
+        assert!(validate(&DestinationType::MsTeams(MsTeams::default())).is_err());
+        assert!(validate(&DestinationType::PagerDuty(PagerDuty::default())).is_err());
+        assert!(validate(&DestinationType::Opsgenie(Opsgenie::default())).is_err());
+        assert!(validate(&DestinationType::default()).is_ok());
+    }
+}
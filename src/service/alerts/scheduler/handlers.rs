@@ -20,15 +20,26 @@ use config::{
     cluster::LOCAL_NODE,
     get_config, ider,
     meta::{
-        alerts::TriggerCondition,
+        alerts::{
+            ALERT_SEVERITY_FIELD, TriggerCondition,
+            alert::Alert,
+            silences::{
+                SILENCE_LABEL_ALERT_NAME, SILENCE_LABEL_FOLDER, SILENCE_LABEL_FOLDER_ID,
+                SILENCE_LABEL_STREAM_NAME, SILENCE_LABEL_STREAM_TYPE, Silence,
+            },
+        },
         dashboards::reports::ReportFrequencyType,
+        folder::Folder,
         pipeline::components::NodeData,
         self_reporting::{
             error::{ErrorData, ErrorSource, PipelineError},
             usage::{TriggerData, TriggerDataStatus, TriggerDataType},
         },
         stream::{StreamParams, StreamType},
-        triggers::ScheduledTriggerData,
+        triggers::{
+            FiringAlertInstance, MAX_FIRING_ALERT_INSTANCES, ResolvedAlertInstance,
+            ScheduledTriggerData, alert_instance_labels,
+        },
     },
     utils::{
         json,
@@ -51,8 +62,12 @@ use crate::service::alerts::scheduler::query_optimization_recommendation::QueryO
 use crate::service::organization::is_org_in_free_trial_period;
 use crate::service::{
     alerts::{
-        alert::{AlertExt, get_alert_start_end_time, get_by_id_db, get_row_column_map},
+        alert::{
+            AlertError, AlertExt, alert_instance_fingerprints, get_alert_start_end_time,
+            get_by_id_db, get_row_column_map,
+        },
         derived_streams::DerivedStreamExt,
+        silences,
     },
     dashboards::reports::SendReport,
     db::{self, alerts::alert::set_without_updating_trigger},
@@ -78,6 +93,15 @@ pub async fn handle_triggers(
         db::scheduler::TriggerModule::AnomalyDetection => {
             handle_anomaly_detection_triggers(trigger).await
         }
+        db::scheduler::TriggerModule::RecordingRule => {
+            crate::service::metrics::rules::handle_recording_rule_triggers(trace_id, trigger).await
+        }
+        db::scheduler::TriggerModule::Scrape => {
+            crate::service::metrics::scrape::handle_scrape_triggers(trace_id, trigger).await
+        }
+        db::scheduler::TriggerModule::WebhookRetry => {
+            crate::service::alerts::webhooks::handle_webhook_retry_triggers(trace_id, trigger).await
+        }
     }
 }
 
@@ -380,10 +404,10 @@ async fn handle_alert_triggers(
     };
 
     // here it can be alert id or alert name
-    let alert = if let Ok(alert_id) = svix_ksuid::Ksuid::from_str(&trigger.module_key) {
+    let (folder, alert) = if let Ok(alert_id) = svix_ksuid::Ksuid::from_str(&trigger.module_key) {
         let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
         match db::alerts::alert::get_by_id(client, &trigger.org, alert_id).await {
-            Ok(Some((_, alert))) => alert,
+            Ok(Some((folder, alert))) => (folder, alert),
             Ok(None) => {
                 log::error!(
                     "[SCHEDULER trace_id {scheduler_trace_id}] Alert not found for module_key: {}, deleting this trigger job",
@@ -609,6 +633,8 @@ async fn handle_alert_triggers(
             tolerance: 0,
             last_satisfied_at: None,
             backfill_job: None,
+            firing: Default::default(),
+            pending_since: None,
         }
     };
 
@@ -813,8 +839,44 @@ async fn handle_alert_triggers(
         return Err(err);
     }
 
-    let trigger_results = result.unwrap();
+    let mut trigger_results = result.unwrap();
     trigger_data_stream.query_took = trigger_results.query_took;
+    // Keep the alert pending until its condition held for the pending duration
+    if let Some(pending) = alert.trigger_condition.pending_in_secs
+        && pending > 0
+    {
+        let satisfied = trigger_results
+            .data
+            .as_ref()
+            .is_some_and(|data| !data.is_empty());
+        if !trigger_data.update_pending(satisfied, pending, final_end_time) && satisfied {
+            log::info!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Alert conditions satisfied but pending, org: {}, module_key: {}, pending since: {}",
+                new_trigger.org,
+                new_trigger.module_key,
+                trigger_data.pending_since.unwrap_or_default()
+            );
+            trigger_results.data = None;
+            trigger_results.severity = None;
+        }
+    }
+    // Notify the destinations of the triggered severity level. Direct
+    // notifications and resolves are routed by the level of each row or
+    // instance instead
+    let unleveled_alert = alert.clone();
+    let alert = match trigger_results.severity.as_ref() {
+        Some(level) => {
+            log::debug!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] alert {} triggered severity {}",
+                new_trigger.module_key,
+                level.severity
+            );
+            let mut alert = alert;
+            level.apply_to(&mut alert);
+            alert
+        }
+        None => alert,
+    };
     log::debug!(
         "[SCHEDULER trace_id {scheduler_trace_id}] result of alert {} evaluation matched condition: {}",
         new_trigger.module_key,
@@ -857,6 +919,103 @@ async fn handle_alert_triggers(
         trigger_data.last_satisfied_at = Some(triggered_at);
     }
 
+    // Track the firing alert instances, the ones that no longer fire are
+    // resolved. Silenced instances keep firing, so that they still resolve
+    // once the silence ends
+    let active_silences = get_active_silences(&alert, triggered_at, &scheduler_trace_id).await;
+    let (fingerprints, mut fired) =
+        firing_alert_instances(&alert, trigger_results.data.as_deref()).await;
+    hold_silenced_instances(
+        &alert,
+        &folder,
+        &active_silences,
+        &trigger_data.firing,
+        &mut fired,
+    );
+    let resolved = trigger_data.update_firing(fired, triggered_at);
+    // Instances over the tracking limit could never be resolved, they are not
+    // notified either
+    if let Some(data) = trigger_results.data.as_mut() {
+        let mut fingerprints = fingerprints.iter();
+        let before = data.len();
+        data.retain(|_| {
+            fingerprints
+                .next()
+                .is_some_and(|fingerprint| trigger_data.firing.contains_key(fingerprint))
+        });
+        let untracked = before - data.len();
+        if untracked > 0 {
+            let msg = format!(
+                "{untracked} result rows of alert instances over the limit of {MAX_FIRING_ALERT_INSTANCES} firing instances were not notified"
+            );
+            log::warn!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] alert {}/{}: {msg}",
+                new_trigger.org,
+                new_trigger.module_key
+            );
+            trigger_data_stream.error = Some(msg);
+        }
+    }
+    // Drop the rows suppressed by an active silence of the organization, the
+    // silenced alert instances are not notified
+    let mut all_silenced = false;
+    if let Some(data) = trigger_results.data.as_mut()
+        && !data.is_empty()
+    {
+        let (remaining, silence_id) =
+            apply_silences(&alert, &folder, &active_silences, std::mem::take(data));
+        trigger_data_stream.silence_id = silence_id;
+        all_silenced = remaining.is_empty();
+        *data = remaining;
+    }
+    if !resolved.is_empty() {
+        // Persist the resolved instances first, so that a failed run does not
+        // resolve them again
+        db::scheduler::update_status(
+            &new_trigger.org,
+            new_trigger.module,
+            &new_trigger.module_key,
+            db::scheduler::TriggerStatus::Processing,
+            trigger.retries,
+            Some(&json::to_string(&trigger_data).unwrap()),
+            true,
+            &query_trace_id,
+        )
+        .await?;
+    }
+    for instance in resolved.iter() {
+        publish_resolved_alert_instance(
+            &unleveled_alert,
+            instance,
+            &trigger_data_stream,
+            triggered_at,
+            &scheduler_trace_id,
+        )
+        .await;
+    }
+
+    if all_silenced {
+        log::info!(
+            "[SCHEDULER trace_id {scheduler_trace_id}] All alert results silenced for org: {}, module_key: {}, silence: {}",
+            new_trigger.org,
+            new_trigger.module_key,
+            trigger_data_stream
+                .silence_id
+                .as_deref()
+                .unwrap_or_default()
+        );
+        trigger_data_stream.status = TriggerDataStatus::Silenced;
+        trigger_data.period_end_time = if should_store_last_end_time {
+            Some(trigger_results.end_time)
+        } else {
+            None
+        };
+        new_trigger.data = json::to_string(&trigger_data).unwrap();
+        db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+        publish_triggers_usage(trigger_data_stream);
+        return Ok(());
+    }
+
     // send notification
     if let Some(data) = trigger_results.data
         && !data.is_empty()
@@ -1145,14 +1304,15 @@ async fn handle_alert_triggers(
             db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
         } else {
             // Direct notification — creates_incident=false, or incident correlation errored.
-            match alert
-                .send_notification(
-                    &data,
-                    trigger_results.end_time,
-                    Some(start_time),
-                    triggered_at,
-                )
-                .await
+            // The rows of every severity level go to the destinations of the level.
+            match send_notification_by_severity(
+                &unleveled_alert,
+                &data,
+                trigger_results.end_time,
+                Some(start_time),
+                triggered_at,
+            )
+            .await
             {
                 Ok((success_msg, err_msg)) => {
                     let success_msg = success_msg.trim().to_owned();
@@ -1268,6 +1428,226 @@ async fn handle_alert_triggers(
     Ok(())
 }
 
+/// Returns the silences of the organization of the alert that are active at
+/// the evaluation time.
+async fn get_active_silences(
+    alert: &Alert,
+    evaluation_timestamp: i64,
+    scheduler_trace_id: &str,
+) -> Vec<Silence> {
+    match silences::get_active(&alert.org_id, evaluation_timestamp).await {
+        Ok(active) => active,
+        Err(e) => {
+            // Don't lose notifications because silences could not be loaded
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Error getting silences for org {}: {e}",
+                alert.org_id
+            );
+            vec![]
+        }
+    }
+}
+
+/// Calls `f` with the labels of the alert that silences are matched against.
+fn with_silence_labels<R>(
+    alert: &Alert,
+    folder: &Folder,
+    f: impl FnOnce(&[(&str, &str)]) -> R,
+) -> R {
+    let stream_type = alert.stream_type.to_string();
+    let labels = [
+        (SILENCE_LABEL_ALERT_NAME, alert.name.as_str()),
+        (SILENCE_LABEL_FOLDER, folder.name.as_str()),
+        (SILENCE_LABEL_FOLDER_ID, folder.folder_id.as_str()),
+        (SILENCE_LABEL_STREAM_NAME, alert.stream_name.as_str()),
+        (SILENCE_LABEL_STREAM_TYPE, stream_type.as_str()),
+    ];
+    f(&labels)
+}
+
+/// Removes the result rows matched by the active silences, returning the
+/// remaining rows and the id of the silence that matched first.
+fn apply_silences(
+    alert: &Alert,
+    folder: &Folder,
+    active_silences: &[Silence],
+    data: Vec<json::Map<String, json::Value>>,
+) -> (Vec<json::Map<String, json::Value>>, Option<String>) {
+    with_silence_labels(alert, folder, |labels| {
+        silences::filter_silenced_rows(active_silences, labels, data)
+    })
+}
+
+/// Keeps firing the instances that stopped firing while an active silence
+/// matches them, so that their resolve is sent once the silence ends rather
+/// than swallowed by it.
+fn hold_silenced_instances(
+    alert: &Alert,
+    folder: &Folder,
+    active_silences: &[Silence],
+    firing: &HashMap<String, FiringAlertInstance>,
+    fired: &mut HashMap<String, json::Map<String, json::Value>>,
+) {
+    if active_silences.is_empty() {
+        return;
+    }
+    with_silence_labels(alert, folder, |labels| {
+        for (fingerprint, instance) in firing {
+            if !fired.contains_key(fingerprint)
+                && silences::find_silence(active_silences, labels, Some(&instance.labels)).is_some()
+            {
+                fired.insert(fingerprint.clone(), instance.labels.clone());
+            }
+        }
+    })
+}
+
+/// Returns the alert instance fingerprint of every row of the evaluation
+/// result, and the labels of the rows keyed by their fingerprint. Without
+/// deduplication the whole alert is a single instance.
+async fn firing_alert_instances(
+    alert: &Alert,
+    data: Option<&[json::Map<String, json::Value>]>,
+) -> (Vec<String>, HashMap<String, json::Map<String, json::Value>>) {
+    let mut instances = HashMap::new();
+    let Some(data) = data else {
+        return (vec![], instances);
+    };
+    let fingerprint_fields = alert
+        .deduplication
+        .as_ref()
+        .filter(|d| d.enabled)
+        .map(|d| d.fingerprint_fields.as_slice())
+        .unwrap_or_default();
+    let fingerprints = alert_instance_fingerprints(alert, data).await;
+    for (fingerprint, row) in fingerprints.iter().zip(data) {
+        instances.insert(
+            fingerprint.clone(),
+            alert_instance_labels(row, fingerprint_fields),
+        );
+    }
+    (fingerprints, instances)
+}
+
+/// Returns the alert with the destinations and template of the severity level
+/// a result row, or the labels of an alert instance, was tagged with.
+fn alert_at_severity(alert: &Alert, labels: &json::Map<String, json::Value>) -> Alert {
+    let mut alert = alert.clone();
+    if let Some(level) = alert.trigger_condition.severity_level_of(labels).cloned() {
+        level.apply_to(&mut alert);
+    }
+    alert
+}
+
+/// Splits the result rows by the severity level they were tagged with, each
+/// group with the alert at its level. Rows without a level form one group
+/// with the alert as is.
+fn group_rows_by_severity(
+    alert: &Alert,
+    data: &[json::Map<String, json::Value>],
+) -> Vec<(Alert, Vec<json::Map<String, json::Value>>)> {
+    let mut groups: Vec<(Option<&str>, Alert, Vec<json::Map<String, json::Value>>)> = Vec::new();
+    for row in data {
+        let severity = row.get(ALERT_SEVERITY_FIELD).and_then(|v| v.as_str());
+        match groups.iter_mut().find(|(s, ..)| *s == severity) {
+            Some((_, _, rows)) => rows.push(row.clone()),
+            None => groups.push((severity, alert_at_severity(alert, row), vec![row.clone()])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(_, alert, rows)| (alert, rows))
+        .collect()
+}
+
+/// Sends the rows of every severity level with the destinations and template
+/// of the level. Fails only when every level failed, so that a retry does not
+/// notify the levels that were sent again.
+async fn send_notification_by_severity(
+    alert: &Alert,
+    data: &[json::Map<String, json::Value>],
+    rows_end_time: i64,
+    start_time: Option<i64>,
+    evaluation_timestamp: i64,
+) -> Result<(String, String), AlertError> {
+    let groups = group_rows_by_severity(alert, data);
+    let mut success_message = String::new();
+    let mut err_message = String::new();
+    let mut failed = 0;
+    for (alert, rows) in groups.iter() {
+        match alert
+            .send_notification(rows, rows_end_time, start_time, evaluation_timestamp)
+            .await
+        {
+            Ok((success, err)) => {
+                success_message.push_str(&success);
+                err_message.push_str(&err);
+            }
+            Err(e) => {
+                failed += 1;
+                err_message = format!("{err_message} {e}");
+            }
+        }
+    }
+    if failed > 0 && failed == groups.len() {
+        Err(AlertError::SendNotificationError {
+            error_message: err_message.trim().to_string(),
+        })
+    } else {
+        Ok((success_message, err_message))
+    }
+}
+
+/// Records a resolved alert instance in the alert history and sends the
+/// resolve notification when the alert asks for it. `alert` is the alert before severity levels are
+/// applied, the notification goes to the destinations of the level the instance fired at.
+async fn publish_resolved_alert_instance(
+    alert: &Alert,
+    instance: &ResolvedAlertInstance,
+    trigger_data_stream: &TriggerData,
+    evaluation_timestamp: i64,
+    scheduler_trace_id: &str,
+) {
+    log::info!(
+        "[SCHEDULER trace_id {scheduler_trace_id}] Alert instance resolved, org: {}, alert: {}, fingerprint: {}, firing for {}s",
+        alert.org_id,
+        alert.name,
+        instance.fingerprint,
+        instance.duration_secs()
+    );
+    let mut resolved_data = TriggerData {
+        status: TriggerDataStatus::Resolved,
+        start_time: instance.fired_at,
+        end_time: instance.resolved_at,
+        fingerprint: Some(instance.fingerprint.clone()),
+        silence_id: None,
+        ..trigger_data_stream.clone()
+    };
+    if alert.send_on_resolve {
+        match alert_at_severity(alert, &instance.labels)
+            .send_resolved_notification(instance, evaluation_timestamp)
+            .await
+        {
+            Ok((success_msg, err_msg)) => {
+                resolved_data.success_response = Some(success_msg);
+                if !err_msg.is_empty() {
+                    resolved_data.error = Some(err_msg);
+                }
+            }
+            Err(e) => {
+                log::error!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Error sending resolve notification for org: {}, alert: {}, fingerprint: {}: {e}",
+                    alert.org_id,
+                    alert.name,
+                    instance.fingerprint
+                );
+                resolved_data.error = Some(format!("error sending resolve notification: {e}"));
+            }
+        }
+    }
+    publish_triggers_usage(resolved_data);
+}
+
 #[cfg(not(feature = "enterprise"))]
 async fn handle_query_recommendations_triggers(
     _trace_id: &str,
@@ -2328,6 +2708,8 @@ async fn handle_derived_stream_triggers(
             tolerance: 0,
             last_satisfied_at: None,
             backfill_job: None,
+            firing: Default::default(),
+            pending_since: None,
         })
         .unwrap();
     }
@@ -3694,6 +4076,7 @@ async fn check_deletion_status(job_id: &str) -> Result<String, anyhow::Error> {
         infra::table::compactor_manual_jobs::Status::Pending => "pending",
         infra::table::compactor_manual_jobs::Status::Running => "running",
         infra::table::compactor_manual_jobs::Status::Completed => "completed",
+        infra::table::compactor_manual_jobs::Status::Failed => "failed",
     };
     Ok(status_str.to_string())
 }
@@ -4305,4 +4688,169 @@ mod tests {
         let result = get_destination_stream_from_pipeline(&pipeline);
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_firing_alert_instances_without_deduplication() {
+        let alert = Alert {
+            org_id: "default".to_string(),
+            name: "errors".to_string(),
+            ..Default::default()
+        };
+        assert!(firing_alert_instances(&alert, None).await.1.is_empty());
+        assert!(firing_alert_instances(&alert, Some(&[])).await.1.is_empty());
+
+        let rows = [
+            json::json!({"host": "a", "count": 1})
+                .as_object()
+                .unwrap()
+                .clone(),
+            json::json!({"host": "b", "count": 2})
+                .as_object()
+                .unwrap()
+                .clone(),
+        ];
+        let (fingerprints, instances) = firing_alert_instances(&alert, Some(&rows)).await;
+        assert_eq!(fingerprints, vec![alert.get_unique_key(); 2]);
+        assert_eq!(instances.len(), 1);
+        assert_eq!(
+            instances.get(&alert.get_unique_key()),
+            json::json!({"host": "b"}).as_object()
+        );
+    }
+
+    #[test]
+    fn test_group_rows_by_severity() {
+        use config::meta::alerts::{SeverityLevel, incidents::IncidentSeverity};
+
+        let mut alert = Alert {
+            destinations: vec!["slack".to_string()],
+            ..Default::default()
+        };
+        alert.trigger_condition.severity_levels = vec![SeverityLevel {
+            severity: IncidentSeverity::P1,
+            destinations: vec!["pagerduty".to_string()],
+            ..Default::default()
+        }];
+        let rows = [
+            json::json!({"host": "a", ALERT_SEVERITY_FIELD: "P1"}),
+            json::json!({"host": "b", ALERT_SEVERITY_FIELD: "P3"}),
+            json::json!({"host": "c", ALERT_SEVERITY_FIELD: "P1"}),
+        ]
+        .map(|row| row.as_object().unwrap().clone());
+        let groups = group_rows_by_severity(&alert, &rows);
+        let groups = groups
+            .iter()
+            .map(|(alert, rows)| {
+                (
+                    alert.destinations.clone(),
+                    rows.iter()
+                        .map(|r| r["host"].as_str().unwrap())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                (vec!["pagerduty".to_string()], vec!["a", "c"]),
+                (vec!["slack".to_string()], vec!["b"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_silenced_instance_resolves_after_silence() {
+        use config::meta::alerts::silences::{
+            SilenceMatchOperator, SilenceMatcher, SilenceSchedule,
+        };
+
+        let alert = Alert {
+            org_id: "default".to_string(),
+            name: "errors".to_string(),
+            ..Default::default()
+        };
+        let folder = Folder::default();
+        let silence = Silence {
+            id: "maintenance".to_string(),
+            name: "maintenance".to_string(),
+            description: String::new(),
+            enabled: true,
+            matchers: vec![SilenceMatcher::new(
+                "host",
+                SilenceMatchOperator::Equal,
+                "a",
+            )],
+            schedule: SilenceSchedule::Once {
+                start_time: 0,
+                end_time: i64::MAX,
+            },
+            created_by: String::new(),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let mut data = ScheduledTriggerData::default();
+        let mut evaluate = |firing: bool, silences: &[Silence], now: i64| {
+            let mut fired = HashMap::new();
+            if firing {
+                fired.insert(
+                    "a".to_string(),
+                    json::json!({ "host": "a" }).as_object().unwrap().clone(),
+                );
+            }
+            hold_silenced_instances(&alert, &folder, silences, &data.firing, &mut fired);
+            data.update_firing(fired, now)
+        };
+
+        assert!(evaluate(true, &[], 1).is_empty());
+        // the silence starts while the instance fires
+        assert!(evaluate(true, std::slice::from_ref(&silence), 2).is_empty());
+        // the condition clears during the silence, the instance is held
+        assert!(evaluate(false, std::slice::from_ref(&silence), 3).is_empty());
+        // the silence ends, the instance resolves
+        let resolved = evaluate(false, &[], 4);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].fingerprint, "a");
+        assert_eq!(resolved[0].fired_at, 1);
+        assert_eq!(resolved[0].resolved_at, 4);
+    }
+
+    #[test]
+    fn test_alert_at_severity() {
+        use config::meta::alerts::{SeverityLevel, incidents::IncidentSeverity};
+
+        let mut alert = Alert {
+            destinations: vec!["slack".to_string()],
+            ..Default::default()
+        };
+        alert.trigger_condition.severity_levels = vec![
+            SeverityLevel {
+                severity: IncidentSeverity::P1,
+                destinations: vec!["pagerduty".to_string()],
+                ..Default::default()
+            },
+            SeverityLevel {
+                severity: IncidentSeverity::P3,
+                ..Default::default()
+            },
+        ];
+        let labels = |severity: &str| {
+            json::json!({ ALERT_SEVERITY_FIELD: severity })
+                .as_object()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(
+            alert_at_severity(&alert, &labels("P1")).destinations,
+            vec!["pagerduty"]
+        );
+        // a level without destinations keeps the ones of the alert
+        assert_eq!(
+            alert_at_severity(&alert, &labels("P3")).destinations,
+            vec!["slack"]
+        );
+        assert_eq!(
+            alert_at_severity(&alert, &json::Map::new()).destinations,
+            vec!["slack"]
+        );
+    }
 }
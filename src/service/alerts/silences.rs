@@ -0,0 +1,288 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Organization-level alert silences
+//!
+//! Silences are stored using the existing key-value DB interface and are
+//! checked by the scheduler right before notifications are sent.
+
+use std::sync::Arc;
+
+use config::{
+    ider,
+    meta::alerts::silences::Silence,
+    utils::json::{Map, Value},
+};
+use infra::db;
+
+use crate::common::infra::config::ALERT_SILENCES;
+
+const MODULE: &str = "alert_silences";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SilenceError {
+    #[error("Silence not found: {0}")]
+    NotFound(String),
+    #[error("Invalid silence: {0}")]
+    Invalid(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// List all silences of an organization
+pub async fn list(org_id: &str) -> Result<Vec<Silence>, anyhow::Error> {
+    let key = format!("{}/", db::build_key(MODULE, org_id, "", 0));
+    let db = db::get_db().await;
+    let values = db
+        .list_values(&key)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list silences: {e}"))?;
+    let mut silences = values
+        .iter()
+        .map(|v| serde_json::from_slice::<Silence>(v))
+        .collect::<Result<Vec<_>, _>>()?;
+    silences.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(silences)
+}
+
+/// Get a silence by id
+pub async fn get(org_id: &str, id: &str) -> Result<Silence, SilenceError> {
+    let key = db::build_key(MODULE, org_id, id, 0);
+    let db = db::get_db().await;
+
+    match db.get(&key).await {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes).map_err(anyhow::Error::from)?),
+        Err(infra::errors::Error::DbError(infra::errors::DbError::KeyNotExists(_))) => {
+            Err(SilenceError::NotFound(id.to_string()))
+        }
+        Err(e) => Err(anyhow::anyhow!("Failed to get silence: {e}").into()),
+    }
+}
+
+/// Create a new silence, the id is always generated
+pub async fn create(org_id: &str, mut silence: Silence) -> Result<Silence, SilenceError> {
+    silence.validate().map_err(SilenceError::Invalid)?;
+    let now = chrono::Utc::now().timestamp_micros();
+    silence.id = ider::generate();
+    silence.created_at = now;
+    silence.updated_at = now;
+    put(org_id, &silence).await?;
+    ALERT_SILENCES.remove(org_id);
+    Ok(silence)
+}
+
+/// Update an existing silence, keeping its creation metadata
+pub async fn update(org_id: &str, id: &str, mut silence: Silence) -> Result<Silence, SilenceError> {
+    silence.validate().map_err(SilenceError::Invalid)?;
+    let existing = get(org_id, id).await?;
+    silence.id = existing.id;
+    silence.created_by = existing.created_by;
+    silence.created_at = existing.created_at;
+    silence.updated_at = chrono::Utc::now().timestamp_micros();
+    put(org_id, &silence).await?;
+    ALERT_SILENCES.remove(org_id);
+    Ok(silence)
+}
+
+/// Delete a silence
+pub async fn delete(org_id: &str, id: &str) -> Result<(), SilenceError> {
+    get(org_id, id).await?;
+    let key = db::build_key(MODULE, org_id, id, 0);
+    let db = db::get_db().await;
+    db.delete_if_exists(&key, false, db::NEED_WATCH)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to delete silence: {e}"))?;
+    ALERT_SILENCES.remove(org_id);
+    Ok(())
+}
+
+async fn put(org_id: &str, silence: &Silence) -> Result<(), anyhow::Error> {
+    let key = db::build_key(MODULE, org_id, &silence.id, 0);
+    let value = serde_json::to_vec(silence)?;
+    let db = db::get_db().await;
+    db.put(&key, value.into(), db::NEED_WATCH, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to save silence: {e}"))
+}
+
+/// Get the silences of an organization that are active at the given time
+pub async fn get_active(org_id: &str, now: i64) -> Result<Vec<Silence>, anyhow::Error> {
+    let silences = match ALERT_SILENCES.get(org_id) {
+        Some(silences) => silences.clone(),
+        None => {
+            let silences = Arc::new(list(org_id).await?);
+            ALERT_SILENCES.insert(org_id.to_string(), silences.clone());
+            silences
+        }
+    };
+    Ok(silences
+        .iter()
+        .filter(|s| s.is_active(now))
+        .cloned()
+        .collect())
+}
+
+/// Drops the cached silences of an organization when one of them changes on
+/// any node.
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = format!("/{MODULE}/");
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(&key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching alert silences");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_alert_silences: event channel closed");
+                break;
+            }
+        };
+        let item_key = match ev {
+            db::Event::Put(ev) | db::Event::Delete(ev) => ev.key,
+            db::Event::Empty => continue,
+        };
+        if let Some(org_id) = item_key
+            .strip_prefix(&key)
+            .and_then(|item| item.split('/').next())
+        {
+            ALERT_SILENCES.remove(org_id);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first silence matching the alert labels and the row, if any
+pub fn find_silence<'a>(
+    silences: &'a [Silence],
+    alert_labels: &[(&str, &str)],
+    row: Option<&Map<String, Value>>,
+) -> Option<&'a Silence> {
+    silences.iter().find(|s| s.matches(alert_labels, row))
+}
+
+/// Splits the rows into the ones that are not silenced and the id of the
+/// first silence that matched any row. Alerts without rows are checked
+/// against the alert labels only.
+pub fn filter_silenced_rows(
+    silences: &[Silence],
+    alert_labels: &[(&str, &str)],
+    rows: Vec<Map<String, Value>>,
+) -> (Vec<Map<String, Value>>, Option<String>) {
+    if silences.is_empty() {
+        return (rows, None);
+    }
+    let mut silence_id = None;
+    if rows.is_empty() {
+        if let Some(s) = find_silence(silences, alert_labels, None) {
+            silence_id = Some(s.id.clone());
+        }
+        return (rows, silence_id);
+    }
+    let rows = rows
+        .into_iter()
+        .filter(
+            |row| match find_silence(silences, alert_labels, Some(row)) {
+                Some(s) => {
+                    silence_id.get_or_insert_with(|| s.id.clone());
+                    false
+                }
+                None => true,
+            },
+        )
+        .collect();
+    (rows, silence_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::alerts::silences::{SilenceMatchOperator, SilenceMatcher, SilenceSchedule};
+
+    use super::*;
+
+    fn silence(id: &str, name: &str, value: &str) -> Silence {
+        Silence {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            enabled: true,
+            matchers: vec![SilenceMatcher::new(
+                name,
+                SilenceMatchOperator::Equal,
+                value,
+            )],
+            schedule: SilenceSchedule::Once {
+                start_time: 0,
+                end_time: i64::MAX,
+            },
+            created_by: String::new(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn row(host: &str) -> Map<String, Value> {
+        let mut row = Map::new();
+        row.insert("host".to_string(), Value::String(host.to_string()));
+        row
+    }
+
+    #[test]
+    fn test_build_key() {
+        let key = db::build_key(MODULE, "org123", "abc", 0);
+        assert_eq!(key, "/alert_silences/org123/abc");
+    }
+
+    #[test]
+    fn test_filter_silenced_rows() {
+        let labels = [("alert_name", "cpu_high")];
+        let rows = vec![row("srv01"), row("srv02"), row("srv03")];
+
+        let (remaining, silence_id) = filter_silenced_rows(&[], &labels, rows.clone());
+        assert_eq!(remaining.len(), 3);
+        assert!(silence_id.is_none());
+
+        let silences = vec![
+            silence("s1", "host", "srv02"),
+            silence("s2", "host", "srv03"),
+        ];
+        let (remaining, silence_id) = filter_silenced_rows(&silences, &labels, rows.clone());
+        assert_eq!(remaining, vec![row("srv01")]);
+        assert_eq!(silence_id.as_deref(), Some("s1"));
+
+        let silences = vec![silence("s3", "alert_name", "cpu_high")];
+        let (remaining, silence_id) = filter_silenced_rows(&silences, &labels, rows);
+        assert!(remaining.is_empty());
+        assert_eq!(silence_id.as_deref(), Some("s3"));
+
+        let (_, silence_id) = filter_silenced_rows(&silences, &labels, vec![]);
+        assert_eq!(silence_id.as_deref(), Some("s3"));
+    }
+
+    #[test]
+    fn test_find_silence() {
+        let labels = [("alert_name", "cpu_high")];
+        let silences = vec![
+            silence("s1", "host", "srv02"),
+            silence("s2", "host", "srv03"),
+        ];
+        assert!(find_silence(&silences, &labels, Some(&row("srv01"))).is_none());
+        assert_eq!(
+            find_silence(&silences, &labels, Some(&row("srv03"))).map(|s| s.id.as_str()),
+            Some("s2")
+        );
+        assert!(find_silence(&silences, &labels, None).is_none());
+    }
+}
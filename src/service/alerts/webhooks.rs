@@ -0,0 +1,462 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Reliable delivery to HTTP endpoint destinations.
+//!
+//! A delivery failing with a server error or a timeout is retried with
+//! exponential backoff and jitter when the endpoint has a retry policy. Every
+//! pending retry is a [`TriggerModule::WebhookRetry`] trigger carrying the
+//! payload, so retries survive restarts. Deliveries that cannot be retried
+//! anymore are recorded in the [`WEBHOOK_DEAD_LETTER_STREAM`] stream of the
+//! organization together with the last error.
+
+use std::time::Duration;
+
+use config::{
+    get_config, ider,
+    meta::{
+        destinations::{
+            DestinationType, Endpoint, HTTPType, Module, WEBHOOK_DEAD_LETTER_STREAM,
+            WEBHOOK_SIGNATURE_HEADER, WebhookDeadLetter, WebhookDelivery, WebhookRetryPolicy,
+        },
+        triggers::{Trigger, TriggerModule, TriggerStatus},
+    },
+    utils::{json, time::now_micros},
+};
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use sha2::Sha256;
+
+use crate::{
+    common::utils::ssrf_guard::{SsrfGuard, build_safe_client},
+    service::{db, self_reporting::publish_webhook_dead_letter},
+};
+
+/// A failed delivery attempt
+#[derive(Debug)]
+pub struct DeliveryError {
+    pub message: String,
+    /// The status the endpoint answered with, if it answered
+    pub status: Option<StatusCode>,
+    /// Whether the same request may succeed later, i.e. the endpoint timed
+    /// out, could not be reached or answered with a server error
+    pub retryable: bool,
+}
+
+impl std::fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl DeliveryError {
+    fn permanent(message: String) -> Self {
+        Self {
+            message,
+            status: None,
+            retryable: false,
+        }
+    }
+
+    /// The status of the attempt for logs, without the response body
+    fn status_text(&self) -> String {
+        match self.status {
+            Some(status) => status.to_string(),
+            None if self.retryable => "no response".to_string(),
+            None => "not sent".to_string(),
+        }
+    }
+}
+
+impl From<reqwest::Error> for DeliveryError {
+    fn from(e: reqwest::Error) -> Self {
+        Self {
+            retryable: e.is_timeout() || e.is_connect() || e.is_request(),
+            status: e.status(),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// A failed delivery handed over to the scheduler, it is neither delivered
+/// nor failed for good yet
+#[derive(Debug, thiserror::Error)]
+#[error("delivery failed: {last_error}, retry {retry}/{max_retries} pending at {next_run_at}")]
+pub struct RetryPending {
+    pub last_error: String,
+    pub retry: u32,
+    pub max_retries: u32,
+    pub next_run_at: i64,
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+/// Value of the [`WEBHOOK_SIGNATURE_HEADER`] header for the given body. The
+/// signature is the hex encoded HMAC-SHA256 of `<timestamp>.<body>`, so
+/// receivers can reject replayed requests by checking the timestamp.
+pub fn signature(secret: &str, timestamp: i64, body: &str) -> String {
+    let signed = hmac_sha256_hex(secret, &format!("{timestamp}.{body}"));
+    format!("t={timestamp},v1={signed}")
+}
+
+fn hmac_sha256_hex(key: &str, data: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(data.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Sends the payload to the endpoint once
+pub async fn send(endpoint: &Endpoint, payload: &str) -> Result<String, DeliveryError> {
+    // Block SSRF: validate the destination URL (including DNS resolution) before
+    // making any outbound request. The client is built through `build_safe_client`
+    // so that redirect targets and per-connect DNS resolution are re-validated.
+    if let Err(e) = SsrfGuard::validate_url_with_config_async(&endpoint.url).await {
+        return Err(DeliveryError::permanent(format!(
+            "Destination URL blocked by SSRF guard: {e}"
+        )));
+    }
+
+    let timeout = Duration::from_secs(get_config().limit.webhook_delivery_timeout.max(1));
+    let builder = reqwest::Client::builder().timeout(timeout);
+    let builder = if endpoint.skip_tls_verify {
+        builder.danger_accept_invalid_certs(true)
+    } else {
+        builder
+    };
+    let client = build_safe_client(builder).map_err(|e| DeliveryError::permanent(e.to_string()))?;
+    let url =
+        url::Url::parse(&endpoint.url).map_err(|e| DeliveryError::permanent(e.to_string()))?;
+    let mut req = match endpoint.method {
+        HTTPType::POST => client.post(url),
+        HTTPType::PUT => client.put(url),
+        HTTPType::GET => client.get(url),
+    };
+
+    // Add additional headers if any from destination description
+    let mut has_context_type = false;
+    if let Some(headers) = &endpoint.headers {
+        for (key, value) in headers.iter() {
+            if !key.is_empty() && !value.is_empty() {
+                if key.to_lowercase().trim() == "content-type" {
+                    has_context_type = true;
+                }
+                req = req.header(key, value);
+            }
+        }
+    };
+    // set default content type
+    if !has_context_type {
+        req = req.header("Content-type", "application/json");
+    }
+    // every attempt is signed with its own timestamp
+    if let Some(secret) = endpoint.signing_secret.as_ref().filter(|s| !s.is_empty()) {
+        let timestamp = chrono::Utc::now().timestamp();
+        req = req.header(
+            WEBHOOK_SIGNATURE_HEADER,
+            signature(secret, timestamp, payload),
+        );
+    }
+
+    let resp = req.body(payload.to_string()).send().await?;
+    let resp_status = resp.status();
+    let resp_body = resp.text().await?;
+
+    log::debug!(
+        "Alert sent to destination {} with status: {}, body:\n{}",
+        endpoint.url,
+        resp_status,
+        resp_body,
+    );
+
+    if !resp_status.is_success() {
+        return Err(DeliveryError {
+            message: format!("sent error status: {resp_status}, err: {resp_body}"),
+            status: Some(resp_status),
+            retryable: is_retryable_status(resp_status),
+        });
+    }
+
+    Ok(format!("sent status: {resp_status}, body: {resp_body}"))
+}
+
+/// Delivers the payload to the endpoint of the destination. A failed delivery
+/// of an endpoint with a retry policy is handed over to the scheduler and
+/// reported as a [`RetryPending`] error, the ones that are not retried are
+/// recorded as dead letters.
+pub async fn deliver(
+    org_id: &str,
+    destination: &str,
+    source: &str,
+    endpoint: &Endpoint,
+    payload: String,
+) -> Result<String, anyhow::Error> {
+    let err = match send(endpoint, &payload).await {
+        Ok(resp) => return Ok(resp),
+        Err(e) => e,
+    };
+    log::error!(
+        "Alert http notification to destination {org_id}/{destination} failed with status: {}",
+        err.status_text()
+    );
+
+    let delivery = WebhookDelivery {
+        destination: destination.to_string(),
+        source: source.to_string(),
+        payload,
+        attempts: 1,
+        first_attempt_at: now_micros(),
+        last_error: err.message,
+    };
+    let policy = match endpoint.retry.as_ref() {
+        Some(policy) if err.retryable && policy.max_retries > 0 => policy,
+        _ => {
+            let last_error = delivery.last_error.clone();
+            dead_letter(org_id, &endpoint.url, delivery).await;
+            return Err(anyhow::anyhow!(last_error));
+        }
+    };
+
+    let next_run_at = match schedule_retry(org_id, policy, &delivery).await {
+        Ok(next_run_at) => next_run_at,
+        Err(e) => {
+            // Without a retry job the delivery would be lost
+            log::error!(
+                "Failed to schedule the retry of the delivery to destination {org_id}/{destination}: {e}"
+            );
+            dead_letter(org_id, &endpoint.url, delivery).await;
+            return Err(e);
+        }
+    };
+    Err(RetryPending {
+        last_error: delivery.last_error,
+        retry: 1,
+        max_retries: policy.max_retries,
+        next_run_at,
+    }
+    .into())
+}
+
+/// The time of the next attempt of the delivery, with up to half of the
+/// backoff added as jitter so failed deliveries do not retry in lockstep
+fn next_attempt_at(policy: &WebhookRetryPolicy, retry: u32, now: i64) -> i64 {
+    let backoff = policy.backoff_secs(retry);
+    let half = backoff / 2;
+    let jitter = if half == 0 {
+        0
+    } else {
+        rand::random_range(0..=half)
+    };
+    let delay_secs = i64::try_from(backoff - half + jitter).unwrap_or(i64::MAX / 1_000_000);
+    now.saturating_add(delay_secs.saturating_mul(1_000_000))
+}
+
+async fn schedule_retry(
+    org_id: &str,
+    policy: &WebhookRetryPolicy,
+    delivery: &WebhookDelivery,
+) -> Result<i64, anyhow::Error> {
+    let next_run_at = next_attempt_at(policy, delivery.attempts, now_micros());
+    let trigger = Trigger {
+        org: org_id.to_string(),
+        module: TriggerModule::WebhookRetry,
+        module_key: format!("{}/{}", delivery.destination, ider::generate()),
+        next_run_at,
+        is_realtime: false,
+        is_silenced: false,
+        data: json::to_string(delivery)?,
+        ..Default::default()
+    };
+    db::scheduler::push(trigger).await?;
+    Ok(next_run_at)
+}
+
+async fn dead_letter(org_id: &str, url: &str, delivery: WebhookDelivery) {
+    log::warn!(
+        "Webhook delivery to destination {org_id}/{} failed permanently after {} attempts, recording it in the {WEBHOOK_DEAD_LETTER_STREAM} stream",
+        delivery.destination,
+        delivery.attempts
+    );
+    publish_webhook_dead_letter(WebhookDeadLetter::new(org_id, url, delivery, now_micros())).await;
+}
+
+/// Retries a failed delivery. The endpoint is read from the destination on
+/// every attempt, so a fixed URL or rotated secret applies to pending
+/// deliveries as well.
+pub async fn handle_webhook_retry_triggers(
+    trace_id: &str,
+    mut trigger: Trigger,
+) -> Result<(), anyhow::Error> {
+    let mut delivery: WebhookDelivery = match json::from_str(&trigger.data) {
+        Ok(delivery) => delivery,
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {trace_id}] Invalid webhook delivery {}/{}: {e}, deleting this trigger job",
+                trigger.org,
+                trigger.module_key
+            );
+            db::scheduler::delete(
+                &trigger.org,
+                TriggerModule::WebhookRetry,
+                &trigger.module_key,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let endpoint = match db::alerts::destinations::get(&trigger.org, &delivery.destination).await {
+        Ok(dest) => match dest.module {
+            Module::Alert {
+                destination_type: DestinationType::Http(endpoint),
+                ..
+            } => Some(endpoint),
+            _ => None,
+        },
+        Err(_) => None,
+    };
+    let Some(endpoint) = endpoint else {
+        delivery.last_error = format!(
+            "HTTP destination {} not found, the delivery is not retried",
+            delivery.destination
+        );
+        dead_letter(&trigger.org, "", delivery).await;
+        db::scheduler::delete(
+            &trigger.org,
+            TriggerModule::WebhookRetry,
+            &trigger.module_key,
+        )
+        .await?;
+        return Ok(());
+    };
+
+    delivery.attempts += 1;
+    let err = match send(&endpoint, &delivery.payload).await {
+        Ok(resp) => {
+            log::info!(
+                "[SCHEDULER trace_id {trace_id}] Webhook delivery to destination {}/{} succeeded on attempt {}: {resp}",
+                trigger.org,
+                delivery.destination,
+                delivery.attempts
+            );
+            db::scheduler::delete(
+                &trigger.org,
+                TriggerModule::WebhookRetry,
+                &trigger.module_key,
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => e,
+    };
+    log::warn!(
+        "[SCHEDULER trace_id {trace_id}] Webhook delivery to destination {}/{} failed on attempt {} with status: {}",
+        trigger.org,
+        delivery.destination,
+        delivery.attempts,
+        err.status_text()
+    );
+    delivery.last_error = err.message;
+
+    let retry = delivery.attempts;
+    match endpoint.retry.as_ref() {
+        Some(policy) if err.retryable && retry <= policy.max_retries => {
+            trigger.next_run_at = next_attempt_at(policy, retry, now_micros());
+            trigger.status = TriggerStatus::Waiting;
+            trigger.retries = 0;
+            trigger.data = json::to_string(&delivery)?;
+            db::scheduler::update_trigger(trigger, true, trace_id).await?;
+        }
+        _ => {
+            dead_letter(&trigger.org, &endpoint.url, delivery).await;
+            db::scheduler::delete(
+                &trigger.org,
+                TriggerModule::WebhookRetry,
+                &trigger.module_key,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hmac_sha256_hex("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_signature() {
+        let header = signature("secret", 1700000000, r#"{"alert":"cpu"}"#);
+        let expected = hmac_sha256_hex("secret", r#"1700000000.{"alert":"cpu"}"#);
+        assert_eq!(header, format!("t=1700000000,v1={expected}"));
+        // the timestamp is part of the signed content
+        assert_ne!(
+            header,
+            signature("secret", 1700000001, r#"{"alert":"cpu"}"#)
+        );
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_delivery_error_status_text() {
+        let err = DeliveryError {
+            message: "sent error status: 502 Bad Gateway, err: <html>...</html>".to_string(),
+            status: Some(StatusCode::BAD_GATEWAY),
+            retryable: true,
+        };
+        assert_eq!(err.status_text(), "502 Bad Gateway");
+        assert_eq!(
+            DeliveryError::permanent("blocked".to_string()).status_text(),
+            "not sent"
+        );
+    }
+
+    #[test]
+    fn test_next_attempt_at() {
+        let policy = WebhookRetryPolicy {
+            max_retries: 5,
+            initial_backoff_secs: 10,
+            max_backoff_secs: 60,
+        };
+        let now = 1_000_000;
+        for _ in 0..20 {
+            let at = next_attempt_at(&policy, 1, now);
+            assert!((now + 5_000_000..=now + 10_000_000).contains(&at));
+            let at = next_attempt_at(&policy, 10, now);
+            assert!((now + 30_000_000..=now + 60_000_000).contains(&at));
+        }
+    }
+}
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use ::datafusion::{arrow::datatypes::Schema, error::DataFusionError};
 use bytes::Bytes;
@@ -594,10 +594,31 @@ pub async fn merge_by_stream(
                 events.sort_by(|a, b| a.key.cmp(&b.key));
 
                 // write file list to storage
-                if let Err(e) = write_file_list(&org_id, stream_type, &events).await {
-                    log::error!("[COMPACTOR] write file list failed: {e}");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                    continue;
+                match swap_file_list(
+                    &org_id,
+                    stream_type,
+                    &stream_name,
+                    &prefix,
+                    delete_file_list,
+                    &events,
+                )
+                .await
+                {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        // a delete by predicate rewrote one of the inputs meanwhile, the
+                        // merged file still has its deleted rows, so drop it
+                        log::warn!(
+                            "[COMPACTOR] merge files for stream: [{org_id}/{stream_type}/{stream_name}] batch_id: {batch_id} inputs changed, discard merged files"
+                        );
+                        delete_orphan_files(&events).await;
+                        continue;
+                    }
+                    Err(e) => {
+                        log::error!("[COMPACTOR] write file list failed: {e}");
+                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                        continue;
+                    }
                 }
 
                 // collect orphan blooms after writing file list successfully
@@ -775,97 +796,19 @@ pub async fn merge_files(
     }
 
     // get latest version of schema
-    let latest_schema = infra::schema::get(org_id, stream_name, stream_type).await?;
-    let stream_settings = infra::schema::unwrap_stream_settings(&latest_schema);
-    let bloom_filter_fields = get_stream_setting_bloom_filter_fields(&stream_settings);
-    let full_text_search_fields = get_stream_setting_fts_fields(&stream_settings);
-    let index_fields = get_stream_setting_index_fields(&stream_settings);
-    let (defined_schema_fields, need_original, index_original_data, index_all_values, storage_type) =
-        match stream_settings {
-            Some(s) => (
-                s.defined_schema_fields,
-                s.store_original_data,
-                s.index_original_data,
-                s.index_all_values,
-                s.storage_type,
-            ),
-            None => (Vec::new(), false, false, false, StorageType::Normal),
-        };
-    let latest_schema = if !defined_schema_fields.is_empty() {
-        let latest_schema = SchemaCache::new(latest_schema);
-        let latest_schema = generate_schema_for_defined_schema_fields(
-            stream_type,
-            &latest_schema,
-            &defined_schema_fields,
-            need_original,
-            index_original_data,
-            index_all_values,
-        );
-        latest_schema.schema().clone()
-    } else {
-        Arc::new(latest_schema)
-    };
+    let settings = MergeSettings::load(org_id, stream_type, stream_name).await?;
 
     // read schema from parquet file and group files by schema
-    let mut schemas = HashMap::new();
-    let files = new_file_list.clone();
-    let mut fi = 0;
-    for file in new_file_list.iter() {
-        fi += 1;
-        log::info!(
-            "[COMPACTOR:WORKER:{thread_id}:{fi}] merge small file: {}",
-            file.key
-        );
-        let buf = file_data::get(&file.account, &file.key, None).await?;
-        let file_format = FileFormat::from_extension(&file.key)
-            .ok_or_else(|| anyhow::anyhow!("invalid file format: {}", file.key))?;
-        let schema = match read_schema_from_bytes(file_format, &buf).await {
-            Ok(schema) => schema,
-            Err(e) => {
-                log::error!(
-                    "[COMPACTOR:WORKER:{thread_id}:{fi}] read schema error for file: {}, err: {e}",
-                    file.key
-                );
-                return Err(e);
-            }
-        };
-        let schema = schema.as_ref().clone().with_metadata(Default::default());
-        let schema_key = schema.hash_key();
-        if !schemas.contains_key(&schema_key) {
-            schemas.insert(schema_key.clone(), schema);
-        }
-    }
-
-    // generate the parquet schema
-    let all_fields = schemas
-        .values()
-        .flat_map(|s| s.fields().iter().map(|f| f.name().to_string()))
-        .collect::<HashSet<_>>();
-    let schema = Arc::new(latest_schema.retain(all_fields));
+    let schema = read_files_schema(thread_id, &new_file_list, &settings.latest_schema).await?;
 
     // generate datafusion tables
     let trace_id = ider::generate();
-    let session = config::meta::search::Session {
-        id: trace_id.to_string(),
-        storage_type: config::meta::search::StorageType::Memory,
-        work_group: None,
-        target_partitions: 2,
-    };
-
-    let tables = match TableBuilder::new()
-        .sorted_by_time(true)
-        .build(session, files.clone(), schema.clone())
-        .await
-    {
-        Ok(tables) => tables,
-        Err(e) => {
-            log::error!("create_parquet_table err: {e}, files: {files:?}, schema: {schema:?}");
-            return Err(DataFusionError::Plan(format!("create_parquet_table err: {e}")).into());
-        }
-    };
+    let files = new_file_list.clone();
+    let tables = build_tables(&trace_id, files.clone(), schema.clone()).await?;
 
     let merge_result = {
         let stream_name = stream_name.to_string();
+        let bloom_filter_fields = settings.bloom_filter_fields.clone();
         DATAFUSION_RUNTIME
             .spawn(async move {
                 merge::merge_parquet_files(
@@ -885,7 +828,7 @@ pub async fn merge_files(
     // clear session data
     crate::service::search::datafusion::storage::file_list::clear(&trace_id);
 
-    let files = new_file_list.into_iter().map(|f| f.key).collect::<Vec<_>>();
+    let files = files.into_iter().map(|f| f.key).collect::<Vec<_>>();
     let buf = match merge_result {
         Ok(v) => v,
         Err(e) => {
@@ -894,71 +837,38 @@ pub async fn merge_files(
         }
     };
 
-    let latest_schema_fields = latest_schema
-        .fields()
-        .iter()
-        .map(|f| f.name())
-        .collect::<HashSet<_>>();
-    let need_index = full_text_search_fields
-        .iter()
-        .chain(index_fields.iter())
-        .any(|f| latest_schema_fields.contains(f));
-    if !need_index {
+    if !settings.need_index() {
         log::debug!("skip index generation for stream: {org_id}/{stream_type}/{stream_name}");
     }
 
     let mut new_files = Vec::new();
     match buf {
-        MergeParquetResult::Single(buf, mut new_file_meta) => {
+        MergeParquetResult::Single(buf, new_file_meta) => {
             if new_file_meta.compressed_size == 0 {
                 return Err(anyhow::anyhow!(
                     "merge_parquet_files error: compressed_size is 0"
                 ));
             }
 
-            let id = ider::generate_file_name();
-            let file_format = get_config().common.file_format.extension();
-            let new_file_key = format!("{prefix}/{id}{file_format}");
+            let new_file = upload_merged_file(
+                org_id,
+                stream_type,
+                prefix,
+                Bytes::from(buf),
+                new_file_meta,
+                &settings,
+                &retain_file_list,
+            )
+            .await?;
             log::info!(
-                "[COMPACTOR:WORKER:{thread_id}] merged {} files into a new file: {new_file_key}, original_size: {}, compressed_size: {}, took: {} ms",
+                "[COMPACTOR:WORKER:{thread_id}] merged {} files into a new file: {}, original_size: {}, compressed_size: {}, took: {} ms",
                 retain_file_list.len(),
-                new_file_meta.original_size,
-                new_file_meta.compressed_size,
+                new_file.key,
+                new_file.meta.original_size,
+                new_file.meta.compressed_size,
                 start.elapsed().as_millis(),
             );
-
-            // upload file to storage
-            let buf = Bytes::from(buf);
-            if cfg.cache_latest_files.enabled
-                && cfg.cache_latest_files.cache_parquet
-                && cfg.cache_latest_files.download_from_node
-            {
-                infra::cache::file_data::disk::set(&new_file_key, buf.clone()).await?;
-                log::debug!("merge_files {new_file_key} file_data::disk::set success");
-            }
-
-            // TODO: check how compliance will interact with org storage
-            let account = storage::get_account(org_id, &new_file_key).unwrap_or_default();
-            if cfg.s3.feature_force_infrequent_access && storage_type.is_compliance() {
-                storage::put_with_compliance(&account, &new_file_key, buf.clone()).await?;
-            } else {
-                storage::put(&account, &new_file_key, buf.clone()).await?;
-            }
-
-            if cfg.common.inverted_index_enabled && stream_type.support_index() && need_index {
-                generate_inverted_index(
-                    org_id,
-                    &new_file_key,
-                    &full_text_search_fields,
-                    &index_fields,
-                    &retain_file_list,
-                    &mut new_file_meta,
-                    latest_schema.clone(),
-                    buf,
-                )
-                .await?;
-            }
-            new_files.push(FileKey::new(0, account, new_file_key, new_file_meta, false));
+            new_files.push(new_file);
         }
         MergeParquetResult::Multiple { bufs, file_metas } => {
             for (buf, file_meta) in bufs.into_iter().zip(file_metas) {
@@ -970,43 +880,17 @@ pub async fn merge_files(
                     ));
                 }
 
-                let id = ider::generate_file_name();
-                let file_format = get_config().common.file_format.extension();
-                let new_file_key = format!("{prefix}/{id}{file_format}");
-
-                // upload file to storage
-                let buf = Bytes::from(buf);
-                if cfg.cache_latest_files.enabled
-                    && cfg.cache_latest_files.cache_parquet
-                    && cfg.cache_latest_files.download_from_node
-                {
-                    infra::cache::file_data::disk::set(&new_file_key, buf.clone()).await?;
-                    log::debug!("merge_files {new_file_key} file_data::disk::set success");
-                }
-
-                // TODO: check how compliance will interact with org storage
-                let account = storage::get_account(org_id, &new_file_key).unwrap_or_default();
-                if cfg.s3.feature_force_infrequent_access && storage_type.is_compliance() {
-                    storage::put_with_compliance(&account, &new_file_key, buf.clone()).await?;
-                } else {
-                    storage::put(&account, &new_file_key, buf.clone()).await?;
-                }
-
-                if cfg.common.inverted_index_enabled && stream_type.support_index() && need_index {
-                    generate_inverted_index(
-                        org_id,
-                        &new_file_key,
-                        &full_text_search_fields,
-                        &index_fields,
-                        &retain_file_list,
-                        &mut new_file_meta,
-                        latest_schema.clone(),
-                        buf,
-                    )
-                    .await?;
-                }
-
-                new_files.push(FileKey::new(0, account, new_file_key, new_file_meta, false));
+                let new_file = upload_merged_file(
+                    org_id,
+                    stream_type,
+                    prefix,
+                    Bytes::from(buf),
+                    new_file_meta,
+                    &settings,
+                    &retain_file_list,
+                )
+                .await?;
+                new_files.push(new_file);
             }
             log::info!(
                 "[COMPACTOR:WORKER:{thread_id}] merged {} files into a new file: {:?}, original_size: {}, compressed_size: {}, took: {} ms",
@@ -1025,6 +909,380 @@ pub async fn merge_files(
     Ok((new_files, retain_file_list))
 }
 
+/// Outcome of [`rewrite_file_by_predicate`] for a file that contained matching rows.
+#[derive(Debug)]
+pub struct PredicateRewrite {
+    /// number of rows removed from the original file
+    pub deleted_records: i64,
+    /// the replacement file, `None` when every row of the original matched
+    pub new_file: Option<FileKey>,
+}
+
+/// Rewrite one file without the rows matching `delete_condition`.
+///
+/// The file goes through the same read/merge/upload path as compaction, and the
+/// replacement (if any rows remain) is swapped for the original in a single
+/// file_list batch. Returns `Ok(None)` when no row matched, in which case
+/// nothing is uploaded and file_list is untouched.
+pub async fn rewrite_file_by_predicate(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    file: &FileKey,
+    delete_condition: &str,
+) -> Result<Option<PredicateRewrite>, anyhow::Error> {
+    let start = std::time::Instant::now();
+    // the file may have been merged away since it was selected, checked again on the swap
+    if !infra_file_list::contains(&file.key).await? {
+        return Err(anyhow::anyhow!(
+            "file {} is no longer in file_list, it was compacted concurrently",
+            file.key
+        ));
+    }
+    let prefix = file.key[..file.key.rfind('/').unwrap_or_default()].to_string();
+    let file = FileKey {
+        selection: None,
+        row_group_size: None,
+        ..file.clone()
+    };
+    let files = vec![file.clone()];
+
+    let settings = MergeSettings::load(org_id, stream_type, stream_name).await?;
+    let schema = read_files_schema(0, &files, &settings.latest_schema).await?;
+
+    let trace_id = ider::generate();
+    let tables = build_tables(&trace_id, files, schema.clone()).await?;
+    let rewrite_result = {
+        let org_id = org_id.to_string();
+        let bloom_filter_fields = settings.bloom_filter_fields.clone();
+        let file_meta = file.meta.clone();
+        let delete_condition = delete_condition.to_string();
+        DATAFUSION_RUNTIME
+            .spawn(async move {
+                merge::rewrite_parquet_files(
+                    &org_id,
+                    schema,
+                    tables,
+                    &bloom_filter_fields,
+                    file_meta,
+                    &delete_condition,
+                )
+                .await
+            })
+            .await?
+    };
+    crate::service::search::datafusion::storage::file_list::clear(&trace_id);
+
+    let (buf, mut new_file_meta) = match rewrite_result {
+        Ok(MergeParquetResult::Single(buf, meta)) => (buf, meta),
+        Ok(MergeParquetResult::Multiple { .. }) => {
+            return Err(anyhow::anyhow!(
+                "rewrite_parquet_files returned multiple files for {}",
+                file.key
+            ));
+        }
+        Err(e) => {
+            log::error!("rewrite_parquet_files err: {e}, file: {}", file.key);
+            return Err(DataFusionError::Plan(format!("rewrite_parquet_files err: {e}")).into());
+        }
+    };
+    let deleted_records = file.meta.records - new_file_meta.records;
+    if deleted_records <= 0 {
+        return Ok(None);
+    }
+
+    let mut events = Vec::with_capacity(2);
+    let new_file = if new_file_meta.records > 0 {
+        // keep the uncompressed size estimate proportional to the remaining rows
+        new_file_meta.original_size =
+            file.meta.original_size * new_file_meta.records / file.meta.records.max(1);
+        let new_file = upload_merged_file(
+            org_id,
+            stream_type,
+            &prefix,
+            Bytes::from(buf),
+            new_file_meta,
+            &settings,
+            std::slice::from_ref(&file),
+        )
+        .await?;
+        events.push(new_file.clone());
+        Some(new_file)
+    } else {
+        None
+    };
+
+    events.push(FileKey {
+        deleted: true,
+        ..file.clone()
+    });
+    events.sort_by(|a, b| a.key.cmp(&b.key));
+    // a merge may have consumed the file meanwhile
+    if !swap_file_list(
+        org_id,
+        stream_type,
+        stream_name,
+        &prefix,
+        std::slice::from_ref(&file),
+        &events,
+    )
+    .await?
+    {
+        delete_orphan_files(&events).await;
+        return Err(anyhow::anyhow!(
+            "file {} is no longer in file_list, it was compacted concurrently",
+            file.key
+        ));
+    }
+
+    log::info!(
+        "[COMPACTOR] delete by predicate rewrote {} into {:?}, deleted {deleted_records} records, took: {} ms",
+        file.key,
+        new_file.as_ref().map(|f| f.key.as_str()),
+        start.elapsed().as_millis(),
+    );
+
+    Ok(Some(PredicateRewrite {
+        deleted_records,
+        new_file,
+    }))
+}
+
+/// Serializes the file_list swaps of a partition on this node, the dist_lock
+/// only covers the other nodes and is a no-op in local mode.
+static PARTITION_SWAP_LOCK: LazyLock<tokio::sync::Mutex<()>> = LazyLock::new(Default::default);
+
+/// Write `events` to file_list if all of `inputs` are still listed.
+///
+/// Compaction and delete by predicate both replace files of a partition, they
+/// take the same lock so that one does not write a file built from inputs the
+/// other has already replaced. Returns `false`, without writing, when an input
+/// is gone.
+async fn swap_file_list(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    prefix: &str,
+    inputs: &[FileKey],
+    events: &[FileKey],
+) -> Result<bool, anyhow::Error> {
+    let lock_key = format!("/compact/merge/{org_id}/{stream_type}/{stream_name}/{prefix}");
+    let _guard = PARTITION_SWAP_LOCK.lock().await;
+    let locker = dist_lock::lock(&lock_key, 0).await?;
+    let ret = async {
+        for file in inputs {
+            if !infra_file_list::contains(&file.key).await? {
+                return Ok(false);
+            }
+        }
+        write_file_list(org_id, stream_type, events).await?;
+        Ok::<_, anyhow::Error>(true)
+    }
+    .await;
+    dist_lock::unlock(&locker).await?;
+    ret
+}
+
+/// Delete the uploaded files of a swap that was not written.
+async fn delete_orphan_files(events: &[FileKey]) {
+    for file in events.iter().filter(|f| !f.deleted) {
+        if let Err(e) = storage::del(vec![(&file.account, &file.key)]).await {
+            log::error!("[COMPACTOR] delete orphan file {} failed: {e}", file.key);
+        }
+    }
+}
+
+/// Stream level settings needed to write merged files.
+struct MergeSettings {
+    latest_schema: Arc<Schema>,
+    bloom_filter_fields: Vec<String>,
+    full_text_search_fields: Vec<String>,
+    index_fields: Vec<String>,
+    storage_type: StorageType,
+}
+
+impl MergeSettings {
+    async fn load(
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let latest_schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+        let stream_settings = infra::schema::unwrap_stream_settings(&latest_schema);
+        let bloom_filter_fields = get_stream_setting_bloom_filter_fields(&stream_settings);
+        let full_text_search_fields = get_stream_setting_fts_fields(&stream_settings);
+        let index_fields = get_stream_setting_index_fields(&stream_settings);
+        let (
+            defined_schema_fields,
+            need_original,
+            index_original_data,
+            index_all_values,
+            storage_type,
+        ) = match stream_settings {
+            Some(s) => (
+                s.defined_schema_fields,
+                s.store_original_data,
+                s.index_original_data,
+                s.index_all_values,
+                s.storage_type,
+            ),
+            None => (Vec::new(), false, false, false, StorageType::Normal),
+        };
+        let latest_schema = if !defined_schema_fields.is_empty() {
+            let latest_schema = SchemaCache::new(latest_schema);
+            let latest_schema = generate_schema_for_defined_schema_fields(
+                stream_type,
+                &latest_schema,
+                &defined_schema_fields,
+                need_original,
+                index_original_data,
+                index_all_values,
+            );
+            latest_schema.schema().clone()
+        } else {
+            Arc::new(latest_schema)
+        };
+        Ok(Self {
+            latest_schema,
+            bloom_filter_fields,
+            full_text_search_fields,
+            index_fields,
+            storage_type,
+        })
+    }
+
+    fn need_index(&self) -> bool {
+        let latest_schema_fields = self
+            .latest_schema
+            .fields()
+            .iter()
+            .map(|f| f.name())
+            .collect::<HashSet<_>>();
+        self.full_text_search_fields
+            .iter()
+            .chain(self.index_fields.iter())
+            .any(|f| latest_schema_fields.contains(f))
+    }
+}
+
+/// Read the schema of every file and narrow the latest stream schema down to
+/// the fields that actually appear in them.
+async fn read_files_schema(
+    thread_id: usize,
+    files: &[FileKey],
+    latest_schema: &Schema,
+) -> Result<Arc<Schema>, anyhow::Error> {
+    let mut schemas = HashMap::new();
+    let mut fi = 0;
+    for file in files.iter() {
+        fi += 1;
+        log::info!(
+            "[COMPACTOR:WORKER:{thread_id}:{fi}] merge small file: {}",
+            file.key
+        );
+        let buf = file_data::get(&file.account, &file.key, None).await?;
+        let file_format = FileFormat::from_extension(&file.key)
+            .ok_or_else(|| anyhow::anyhow!("invalid file format: {}", file.key))?;
+        let schema = match read_schema_from_bytes(file_format, &buf).await {
+            Ok(schema) => schema,
+            Err(e) => {
+                log::error!(
+                    "[COMPACTOR:WORKER:{thread_id}:{fi}] read schema error for file: {}, err: {e}",
+                    file.key
+                );
+                return Err(e);
+            }
+        };
+        let schema = schema.as_ref().clone().with_metadata(Default::default());
+        let schema_key = schema.hash_key();
+        if !schemas.contains_key(&schema_key) {
+            schemas.insert(schema_key.clone(), schema);
+        }
+    }
+
+    // generate the parquet schema
+    let all_fields = schemas
+        .values()
+        .flat_map(|s| s.fields().iter().map(|f| f.name().to_string()))
+        .collect::<HashSet<_>>();
+    Ok(Arc::new(latest_schema.retain(all_fields)))
+}
+
+async fn build_tables(
+    trace_id: &str,
+    files: Vec<FileKey>,
+    schema: Arc<Schema>,
+) -> Result<Vec<Arc<dyn ::datafusion::catalog::TableProvider>>, anyhow::Error> {
+    let session = config::meta::search::Session {
+        id: trace_id.to_string(),
+        storage_type: config::meta::search::StorageType::Memory,
+        work_group: None,
+        target_partitions: 2,
+    };
+
+    match TableBuilder::new()
+        .sorted_by_time(true)
+        .build(session, files.clone(), schema.clone())
+        .await
+    {
+        Ok(tables) => Ok(tables),
+        Err(e) => {
+            log::error!("create_parquet_table err: {e}, files: {files:?}, schema: {schema:?}");
+            Err(DataFusionError::Plan(format!("create_parquet_table err: {e}")).into())
+        }
+    }
+}
+
+/// Upload a merged file to storage and build its inverted index when the
+/// stream has index fields configured.
+async fn upload_merged_file(
+    org_id: &str,
+    stream_type: StreamType,
+    prefix: &str,
+    buf: Bytes,
+    mut new_file_meta: FileMeta,
+    settings: &MergeSettings,
+    retain_file_list: &[FileKey],
+) -> Result<FileKey, anyhow::Error> {
+    let cfg = get_config();
+    let id = ider::generate_file_name();
+    let file_format = cfg.common.file_format.extension();
+    let new_file_key = format!("{prefix}/{id}{file_format}");
+
+    // upload file to storage
+    if cfg.cache_latest_files.enabled
+        && cfg.cache_latest_files.cache_parquet
+        && cfg.cache_latest_files.download_from_node
+    {
+        infra::cache::file_data::disk::set(&new_file_key, buf.clone()).await?;
+        log::debug!("merge_files {new_file_key} file_data::disk::set success");
+    }
+
+    // TODO: check how compliance will interact with org storage
+    let account = storage::get_account(org_id, &new_file_key).unwrap_or_default();
+    if cfg.s3.feature_force_infrequent_access && settings.storage_type.is_compliance() {
+        storage::put_with_compliance(&account, &new_file_key, buf.clone()).await?;
+    } else {
+        storage::put(&account, &new_file_key, buf.clone()).await?;
+    }
+
+    if cfg.common.inverted_index_enabled && stream_type.support_index() && settings.need_index() {
+        generate_inverted_index(
+            org_id,
+            &new_file_key,
+            &settings.full_text_search_fields,
+            &settings.index_fields,
+            retain_file_list,
+            &mut new_file_meta,
+            settings.latest_schema.clone(),
+            buf,
+        )
+        .await?;
+    }
+
+    Ok(FileKey::new(0, account, new_file_key, new_file_meta, false))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn generate_inverted_index(
     org_id: &str,
@@ -39,6 +39,7 @@ pub mod dump;
 pub mod flatten;
 pub mod incremental;
 pub mod merge;
+pub mod predicate_delete;
 pub mod retention;
 pub mod stats;
 pub mod worker;
@@ -98,6 +99,45 @@ pub async fn run_retention() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// compactor delete-by-predicate run steps:
+/// 1. list the jobs and clean up the completed ones past their retention
+/// 2. pick the jobs assigned to this node by consistent hash
+/// 3. run one pass of each job, a job completes once a pass finds nothing to delete
+pub async fn run_delete_by_predicate() -> Result<(), anyhow::Error> {
+    let jobs = db::compact::predicate_delete::list().await?;
+    for mut job in jobs {
+        if job.ended_at > 0 {
+            if let Err(e) = predicate_delete::clean_completed_job(&job).await {
+                log::error!(
+                    "[COMPACTOR] delete by predicate clean job {} error: {e}",
+                    job.id
+                );
+            }
+            continue;
+        }
+
+        let Some(node_name) = get_node_from_consistent_hash(&job.id, &Role::Compactor, None).await
+        else {
+            continue; // no compactor node
+        };
+        if LOCAL_NODE.name.ne(&node_name) {
+            continue; // not this node
+        }
+
+        if let Err(e) = predicate_delete::delete_by_predicate(&mut job).await {
+            log::error!(
+                "[COMPACTOR] delete by predicate job {} [{}/{}/{}] error: {e}",
+                job.id,
+                job.org_id,
+                job.stream_type,
+                job.stream_name
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Generate job for compactor
 pub async fn run_generate_job(job_type: CompactionJobType) -> Result<(), anyhow::Error> {
     let orgs = db::schema::list_organizations_from_cache().await;
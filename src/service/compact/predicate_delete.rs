@@ -0,0 +1,480 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Delete-by-predicate jobs.
+//!
+//! Unlike retention, which drops whole partitions, these jobs remove only the
+//! rows matching a SQL condition. Each pass:
+//!
+//! 1. lists the files of the job time range from file_list,
+//! 2. prunes them with the bloom filters and the inverted index of the stream,
+//! 3. rewrites every remaining candidate through [`merge::rewrite_file_by_predicate`], which swaps
+//!    the new file for the old one in a single file_list batch.
+//!
+//! A job completes after a pass that finds nothing left to delete, so files
+//! produced by a merge racing with a pass are picked up by the next one. The
+//! file list of dumped hours can not be rewritten, a job whose time range
+//! overlaps them fails instead of reporting a partial erasure as done.
+
+use std::sync::Arc;
+
+use config::{
+    get_config,
+    meta::stream::{FileKey, PartitionTimeLevel, PredicateDeleteJob, StreamType},
+    utils::time::now_micros,
+};
+use datafusion::{
+    arrow::datatypes::Schema, common::DFSchema, physical_expr::split_conjunction,
+    prelude::SessionContext, sql::TableReference,
+};
+use futures::{StreamExt, stream};
+use hashbrown::HashSet;
+use infra::{
+    file_list as infra_file_list,
+    schema::{
+        get_partition_time_level, get_stream_setting_bloom_filter_fields,
+        get_stream_setting_index_fields, unwrap_stream_settings,
+    },
+    table::compactor_manual_jobs::Status as CompactorManualJobStatus,
+};
+use sqlparser::{
+    ast::{SetExpr, Statement},
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+
+use super::merge;
+use crate::service::{
+    db,
+    file_list_dump::generate_dump_stream_name,
+    search::{
+        datafusion::{
+            exec::{DataFusionContextBuilder, register_udf},
+            optimizer::physical_optimizer::index::is_expr_valid_for_index,
+        },
+        grpc::{QueryParams, storage::check_bloom_filter},
+        index::{Condition, IndexCondition},
+        tantivy::tantivy_search,
+    },
+};
+
+/// Completed jobs are kept this long so their final progress can be queried.
+const COMPLETED_JOB_RETENTION_DAYS: i64 = 30;
+
+/// Parse a user supplied WHERE clause and return it in normalized form.
+///
+/// The clause is parsed as part of a full statement so anything that would
+/// escape the `WHERE` (a second statement, a `UNION`, ...) is rejected.
+pub fn normalize_condition(condition: &str) -> Result<String, anyhow::Error> {
+    let condition = condition.trim();
+    if condition.is_empty() {
+        return Err(anyhow::anyhow!("condition is required"));
+    }
+    let sql = format!("SELECT * FROM tbl WHERE {condition}");
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, &sql)
+        .map_err(|e| anyhow::anyhow!("invalid condition: {e}"))?;
+    if statements.len() != 1 {
+        return Err(anyhow::anyhow!("condition must be a single expression"));
+    }
+    let Statement::Query(query) = statements.remove(0) else {
+        return Err(anyhow::anyhow!("condition must be a single expression"));
+    };
+    if query.order_by.is_some() || query.limit_clause.is_some() {
+        return Err(anyhow::anyhow!("condition must be a single expression"));
+    }
+    let SetExpr::Select(select) = *query.body else {
+        return Err(anyhow::anyhow!("condition must be a single expression"));
+    };
+    select
+        .selection
+        .map(|expr| expr.to_string())
+        .ok_or_else(|| anyhow::anyhow!("condition is required"))
+}
+
+/// Check that `condition` can be evaluated against the stream schema.
+pub async fn validate_condition(
+    org_id: &str,
+    schema: Arc<Schema>,
+    condition: &str,
+) -> Result<(), anyhow::Error> {
+    let ctx = new_context(org_id).await?;
+    index_condition(&ctx, schema, condition, &HashSet::new())?;
+    Ok(())
+}
+
+/// Run one pass of a delete-by-predicate job and persist its progress.
+pub async fn delete_by_predicate(job: &mut PredicateDeleteJob) -> Result<(), anyhow::Error> {
+    let PredicateDeleteJob {
+        org_id,
+        stream_type,
+        stream_name,
+        ..
+    } = job.clone();
+
+    if job.progress.passes == 0 {
+        set_manual_job_status(job, CompactorManualJobStatus::Running).await;
+    }
+
+    let schema = infra::schema::get(&org_id, &stream_name, stream_type).await?;
+    if schema == Schema::empty() {
+        // the stream was deleted, nothing left to erase
+        return finish(job).await;
+    }
+
+    if let Some((start, end)) =
+        dumped_time_range(&org_id, stream_type, &stream_name, (job.start, job.end)).await?
+    {
+        return fail(
+            job,
+            format!(
+                "the file list of the time range {start} to {end} is dumped, its rows can not be deleted"
+            ),
+        )
+        .await;
+    }
+
+    // the materialized views of the stream must not be read over deleted rows
+    crate::service::materialized_views::invalidate(
+        &org_id,
+        stream_type,
+        &stream_name,
+        (job.start, job.end),
+        &format!("predicate_delete_{}", job.id),
+    )
+    .await?;
+
+    let files = find_candidate_files(job, Arc::new(schema)).await?;
+    let delete_condition = job.delete_condition();
+    log::info!(
+        "[COMPACTOR] delete by predicate job {} [{org_id}/{stream_type}/{stream_name}] pass {}: {} candidate files",
+        job.id,
+        job.progress.passes + 1,
+        files.len(),
+    );
+
+    let mut results = stream::iter(files.iter())
+        .map(|file| {
+            merge::rewrite_file_by_predicate(
+                &org_id,
+                stream_type,
+                &stream_name,
+                file,
+                &delete_condition,
+            )
+        })
+        .buffer_unordered(get_config().limit.file_merge_thread_num.max(1));
+
+    let mut rewritten = 0;
+    let mut last_error = None;
+    while let Some(ret) = results.next().await {
+        match ret {
+            Ok(None) => {}
+            Ok(Some(rewrite)) => {
+                rewritten += 1;
+                job.progress.deleted_records += rewrite.deleted_records;
+                if rewrite.new_file.is_some() {
+                    job.progress.rewritten_files += 1;
+                } else {
+                    job.progress.removed_files += 1;
+                }
+            }
+            Err(e) => {
+                log::error!(
+                    "[COMPACTOR] delete by predicate job {} rewrite file error: {e}",
+                    job.id
+                );
+                last_error = Some(e.to_string());
+            }
+        }
+    }
+
+    job.progress.passes += 1;
+    job.progress.scanned_files += files.len() as i64;
+    job.error = last_error;
+    if job.error.is_none() && rewritten == 0 {
+        return finish(job).await;
+    }
+    db::compact::predicate_delete::set(job).await?;
+    Ok(())
+}
+
+/// Remove completed jobs past their retention.
+pub async fn clean_completed_job(job: &PredicateDeleteJob) -> Result<(), anyhow::Error> {
+    let expire = now_micros() - config::utils::time::day_micros(COMPLETED_JOB_RETENTION_DAYS);
+    if job.ended_at > 0 && job.ended_at < expire {
+        db::compact::predicate_delete::delete(job).await?;
+    }
+    Ok(())
+}
+
+async fn finish(job: &mut PredicateDeleteJob) -> Result<(), anyhow::Error> {
+    job.ended_at = now_micros();
+    db::compact::predicate_delete::set(job).await?;
+    set_manual_job_status(job, CompactorManualJobStatus::Completed).await;
+    log::info!(
+        "[COMPACTOR] delete by predicate job {} [{}/{}/{}] completed: {:?}",
+        job.id,
+        job.org_id,
+        job.stream_type,
+        job.stream_name,
+        job.progress,
+    );
+    Ok(())
+}
+
+async fn fail(job: &mut PredicateDeleteJob, error: String) -> Result<(), anyhow::Error> {
+    log::error!(
+        "[COMPACTOR] delete by predicate job {} [{}/{}/{}] failed: {error}",
+        job.id,
+        job.org_id,
+        job.stream_type,
+        job.stream_name,
+    );
+    job.ended_at = now_micros();
+    job.error = Some(error);
+    db::compact::predicate_delete::set(job).await?;
+    set_manual_job_status(job, CompactorManualJobStatus::Failed).await;
+    Ok(())
+}
+
+/// Returns the part of the time range whose file list was dumped, if any.
+/// Only the live file_list can be rewritten, so the rows of these hours can
+/// not be deleted by predicate.
+pub async fn dumped_time_range(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    (start, end): (i64, i64),
+) -> Result<Option<(i64, i64)>, anyhow::Error> {
+    if !get_config().compact.file_list_dump_enabled {
+        return Ok(None);
+    }
+    let dump_stream_name = generate_dump_stream_name(stream_type, stream_name);
+    let dump_files = infra_file_list::query(
+        org_id,
+        StreamType::Filelist,
+        &dump_stream_name,
+        PartitionTimeLevel::Hourly,
+        (start, end),
+        None,
+    )
+    .await?;
+    Ok(dumped_range(&dump_files, (start, end)))
+}
+
+fn dumped_range(dump_files: &[FileKey], (start, end): (i64, i64)) -> Option<(i64, i64)> {
+    let min_ts = dump_files.iter().map(|f| f.meta.min_ts).min()?;
+    let max_ts = dump_files.iter().map(|f| f.meta.max_ts).max()?;
+    Some((min_ts.max(start), max_ts.min(end)))
+}
+
+// Manual job operations are isolated - any errors are logged and ignored
+async fn set_manual_job_status(job: &PredicateDeleteJob, status: CompactorManualJobStatus) {
+    let mut manual_job = match db::compact::compactor_manual_jobs::get_job(&job.id).await {
+        Ok(manual_job) => manual_job,
+        Err(e) => {
+            log::error!(
+                "[COMPACTOR] delete by predicate get manual job {} failed: {e}",
+                job.id
+            );
+            return;
+        }
+    };
+    manual_job.status = status;
+    if matches!(
+        status,
+        CompactorManualJobStatus::Completed | CompactorManualJobStatus::Failed
+    ) {
+        manual_job.ended_at = job.ended_at;
+    }
+    if let Err(e) = db::compact::compactor_manual_jobs::bulk_update_jobs(vec![manual_job]).await {
+        log::error!(
+            "[COMPACTOR] delete by predicate update manual job {} failed: {e}",
+            job.id
+        );
+    }
+}
+
+/// List the files of the job time range and drop the ones the bloom filters
+/// or the inverted index prove free of matching rows.
+async fn find_candidate_files(
+    job: &PredicateDeleteJob,
+    schema: Arc<Schema>,
+) -> Result<Vec<FileKey>, anyhow::Error> {
+    // the dumped hours are rejected before, only query the live file_list
+    let mut files = infra_file_list::query(
+        &job.org_id,
+        job.stream_type,
+        &job.stream_name,
+        get_partition_time_level(job.stream_type),
+        (job.start, job.end),
+        None,
+    )
+    .await?;
+    if files.is_empty() {
+        return Ok(files);
+    }
+
+    let schema_fields = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().to_string())
+        .collect::<HashSet<_>>();
+    let stream_settings = unwrap_stream_settings(&schema);
+    let index_fields = get_stream_setting_index_fields(&stream_settings)
+        .into_iter()
+        .filter(|v| schema_fields.contains(v))
+        .collect::<HashSet<_>>();
+    let bloom_indexed_fields = get_stream_setting_bloom_filter_fields(&stream_settings)
+        .into_iter()
+        .filter(|v| schema_fields.contains(v))
+        .collect::<Vec<_>>();
+
+    let ctx = new_context(&job.org_id).await?;
+    let Some(index_condition) = index_condition(&ctx, schema, &job.condition, &index_fields)?
+    else {
+        return Ok(files);
+    };
+
+    let query = Arc::new(QueryParams {
+        trace_id: format!("predicate_delete-{}", job.id),
+        org_id: job.org_id.clone(),
+        stream: TableReference::from(job.stream_name.as_str()),
+        stream_type: job.stream_type,
+        stream_name: job.stream_name.clone(),
+        time_range: (job.start, job.end),
+        work_group: None,
+        use_inverted_index: true,
+    });
+    check_bloom_filter(
+        query.clone(),
+        &mut files,
+        Some(index_condition.clone()),
+        bloom_indexed_fields,
+    )
+    .await?;
+    if get_config().common.inverted_index_enabled {
+        tantivy_search(query, &mut files, Some(index_condition), None).await?;
+    }
+
+    // the whole file is rewritten, drop the row selections set by the index
+    for file in files.iter_mut() {
+        file.selection = None;
+        file.row_group_size = None;
+    }
+    Ok(files)
+}
+
+async fn new_context(org_id: &str) -> Result<SessionContext, anyhow::Error> {
+    let ctx = DataFusionContextBuilder::new()
+        .trace_id("predicate_delete")
+        .build(get_config().limit.datafusion_min_partition_num)
+        .await?;
+    register_udf(&ctx, org_id)?;
+    Ok(ctx)
+}
+
+/// Plan `condition` against the stream schema and extract the parts the
+/// bloom filters and the inverted index can decide.
+fn index_condition(
+    ctx: &SessionContext,
+    schema: Arc<Schema>,
+    condition: &str,
+    index_fields: &HashSet<String>,
+) -> Result<Option<IndexCondition>, anyhow::Error> {
+    let df_schema = DFSchema::try_from(schema)?;
+    let expr = ctx.parse_sql_expr(condition, &df_schema)?;
+    let expr = ctx.create_physical_expr(expr, &df_schema)?;
+
+    let mut index_condition = IndexCondition::new();
+    for expr in split_conjunction(&expr) {
+        if is_expr_valid_for_index(expr, index_fields) {
+            index_condition.add_condition(Condition::from_physical_expr(expr));
+        }
+    }
+    if index_condition.is_empty() || index_condition.is_condition_all() {
+        Ok(None)
+    } else {
+        Ok(Some(index_condition))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{DataType, Field};
+
+    use super::*;
+
+    #[test]
+    fn test_dumped_range() {
+        assert_eq!(dumped_range(&[], (0, 100)), None);
+        let file = |min_ts, max_ts| {
+            let mut file = FileKey::default();
+            file.meta.min_ts = min_ts;
+            file.meta.max_ts = max_ts;
+            file
+        };
+        assert_eq!(
+            dumped_range(&[file(40, 60), file(-10, 20)], (0, 100)),
+            Some((0, 60))
+        );
+    }
+
+    #[test]
+    fn test_normalize_condition() {
+        assert_eq!(
+            normalize_condition(" user_id = 'x' ").unwrap(),
+            "user_id = 'x'"
+        );
+        assert_eq!(
+            normalize_condition("user_id IN ('a', 'b') AND code >= 500").unwrap(),
+            "user_id IN ('a', 'b') AND code >= 500"
+        );
+        assert!(normalize_condition("").is_err());
+        assert!(normalize_condition("a = 1; DROP TABLE tbl").is_err());
+        assert!(normalize_condition("a = 1 UNION SELECT * FROM other").is_err());
+        assert!(normalize_condition("a = 1 LIMIT 10").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_index_condition() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("user_id", DataType::Utf8, true),
+            Field::new("code", DataType::Int64, true),
+        ]));
+        let ctx = new_context("test_org").await.unwrap();
+        let index_fields = HashSet::from(["user_id".to_string()]);
+
+        let cond = index_condition(
+            &ctx,
+            schema.clone(),
+            "user_id = 'x' AND code > 1",
+            &index_fields,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            cond.conditions,
+            vec![Condition::Equal("user_id".to_string(), "x".to_string())]
+        );
+
+        // nothing indexable, every file stays a candidate
+        let cond = index_condition(&ctx, schema.clone(), "code > 1", &index_fields).unwrap();
+        assert!(cond.is_none());
+
+        // unknown columns are rejected up front
+        assert!(index_condition(&ctx, schema, "missing = 1", &index_fields).is_err());
+    }
+}
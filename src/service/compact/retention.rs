@@ -370,6 +370,16 @@ pub async fn delete_all(
     let start_time = BASE_TIME.timestamp_micros();
     let end_time = Utc::now().timestamp_micros();
 
+    // the materialized views of the stream must not be read over deleted data
+    crate::service::materialized_views::invalidate(
+        org_id,
+        stream_type,
+        stream_name,
+        (start_time, end_time),
+        "delete_all",
+    )
+    .await?;
+
     let cfg = get_config();
     if is_local_disk_storage() {
         let data_dir = format!(
@@ -447,6 +457,16 @@ pub async fn delete_by_date(
         )
     };
 
+    // the materialized views of the stream must not be read over deleted data
+    crate::service::materialized_views::invalidate(
+        org_id,
+        stream_type,
+        stream_name,
+        time_range,
+        &format!("delete_{}_{}", time_range.0, time_range.1),
+    )
+    .await?;
+
     if is_local_disk_storage() {
         let dirs_to_delete =
             generate_local_dirs(org_id, stream_type, stream_name, date_start, date_end);
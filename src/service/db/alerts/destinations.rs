@@ -42,6 +42,10 @@ pub enum DestinationError {
     SsrfBlocked(String),
     #[error("SNS destination must have Topic ARN and Region")]
     InvalidSns,
+    #[error("Invalid destination: {0}")]
+    InvalidProvider(String),
+    #[error("Invalid retry policy: {0}")]
+    InvalidRetryPolicy(String),
     #[error("Email destination must have at least one email recipient")]
     EmptyEmail,
     #[error("Email destination recipients must be part of this org")]
@@ -71,6 +75,8 @@ pub enum DestinationError {
     TemplateRetrievalFailed(String),
     #[error("Email send failed: {0}")]
     EmailSendFailed(String),
+    #[error("Test notification failed: {0}")]
+    ProviderSendFailed(String),
     #[error("LLM Evaluation destinations are only supported for pipelines, not alerts")]
     NotSupportedAlertDestinationType,
 }
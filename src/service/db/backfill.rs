@@ -186,6 +186,8 @@ pub async fn update_enabled(org: &str, job_id: &str, enabled: bool) -> Result<()
                                 deletion_job_ids: vec![],
                                 error: None,
                             }),
+                            firing: Default::default(),
+                            pending_since: None,
                         };
 
                         let data = match config::utils::json::to_string(&trigger_data) {
@@ -65,6 +65,14 @@ pub async fn add_job(job: CompactorManualJob) -> Result<String, errors::Error> {
     Ok(job_id)
 }
 
+// Add a job that only the local cluster can process, intentionally does not
+// do super cluster sync
+pub async fn add_local_job(job: CompactorManualJob) -> Result<String, errors::Error> {
+    let job_id = job.id.clone();
+    add(job).await?;
+    Ok(job_id)
+}
+
 // Bulk update jobs, intentionally does not do super cluster sync
 // to keep the updates of the job local to the cluster
 pub async fn bulk_update_jobs(jobs: Vec<CompactorManualJob>) -> Result<(), errors::Error> {
@@ -17,6 +17,7 @@ pub mod compactor_manual_jobs;
 pub mod downsampling;
 pub mod files;
 pub mod organization;
+pub mod predicate_delete;
 pub mod retention;
 pub mod stats;
 pub mod stream;
@@ -0,0 +1,81 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use config::{
+    meta::stream::{PredicateDeleteJob, StreamType},
+    utils::json,
+};
+use infra::errors::{DbError, Error, Result};
+
+use crate::service::db;
+
+// DBKey to store delete-by-predicate jobs:
+// /compact/predicate_delete/{org}/{stream_type}/{stream_name}/{id}
+pub const PREDICATE_DELETE_KEY: &str = "/compact/predicate_delete";
+
+#[inline]
+fn mk_key(org_id: &str, stream_type: StreamType, stream_name: &str, id: &str) -> String {
+    format!("{PREDICATE_DELETE_KEY}/{org_id}/{stream_type}/{stream_name}/{id}")
+}
+
+pub async fn get(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    id: &str,
+) -> Result<Option<PredicateDeleteJob>> {
+    let key = mk_key(org_id, stream_type, stream_name, id);
+    match db::get(&key).await {
+        Ok(v) => Ok(Some(json::from_slice(&v)?)),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Lists all jobs, oldest first.
+pub async fn list() -> Result<Vec<PredicateDeleteJob>> {
+    let key = format!("{PREDICATE_DELETE_KEY}/");
+    let mut jobs = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<PredicateDeleteJob>(&v))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    jobs.sort_by_key(|job| job.created_at);
+    Ok(jobs)
+}
+
+pub async fn set(job: &PredicateDeleteJob) -> Result<()> {
+    let key = mk_key(&job.org_id, job.stream_type, &job.stream_name, &job.id);
+    db::put(&key, json::to_vec(job)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete(job: &PredicateDeleteJob) -> Result<()> {
+    let key = mk_key(&job.org_id, job.stream_type, &job.stream_name, &job.id);
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mk_key() {
+        assert_eq!(
+            mk_key("org", StreamType::Logs, "app", "job1"),
+            "/compact/predicate_delete/org/logs/app/job1"
+        );
+        // must not collide with the time range delete markers
+        assert!(!mk_key("org", StreamType::Logs, "app", "job1").starts_with("/compact/delete"));
+    }
+}
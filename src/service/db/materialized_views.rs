@@ -0,0 +1,344 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::sync::Arc;
+
+use config::{
+    ider,
+    meta::materialized_view::{MaterializedView, PendingFold, ViewGap},
+    utils::json,
+};
+use infra::errors::{DbError, Error, Result};
+
+use crate::{
+    common::infra::config::{
+        MATERIALIZED_VIEW_GAPS, MATERIALIZED_VIEW_PENDING_FOLDS, MATERIALIZED_VIEWS,
+    },
+    service::db,
+};
+
+// DBKey to store the materialized views of an org: /materialized_views/{org}/{name}
+pub const MATERIALIZED_VIEWS_KEY: &str = "/materialized_views/";
+// DBKey to store the files not folded into a view yet: /materialized_view_folds/{org}/{name}/{id}
+pub const PENDING_FOLDS_KEY: &str = "/materialized_view_folds/";
+// DBKey to store the time ranges a view is not read over: /materialized_view_gaps/{org}/{name}/{id}
+pub const GAPS_KEY: &str = "/materialized_view_gaps/";
+
+pub async fn get(org_id: &str, name: &str) -> Result<Option<MaterializedView>> {
+    let key = format!("{MATERIALIZED_VIEWS_KEY}{org_id}/{name}");
+    match db::get(&key).await {
+        Ok(v) => Ok(Some(json::from_slice(&v)?)),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<MaterializedView>> {
+    let key = format!("{MATERIALIZED_VIEWS_KEY}{org_id}/");
+    let mut views = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<MaterializedView>(&v))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    views.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(views)
+}
+
+pub async fn set(view: &MaterializedView) -> Result<()> {
+    let key = format!("{MATERIALIZED_VIEWS_KEY}{}/{}", view.org_id, view.name);
+    db::put(&key, json::to_vec(view)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<()> {
+    let key = format!("{MATERIALIZED_VIEWS_KEY}{org_id}/{name}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+/// The cached views of an org that aggregate the given stream
+pub fn list_for_stream(org_id: &str, stream_name: &str) -> Vec<MaterializedView> {
+    MATERIALIZED_VIEWS
+        .iter()
+        .filter(|v| v.org_id == org_id && v.stream_name == stream_name)
+        .map(|v| v.value().clone())
+        .collect()
+}
+
+/// Whether the stream holds the states of a cached view
+pub fn is_view_stream(org_id: &str, stream_name: &str) -> bool {
+    MATERIALIZED_VIEWS.contains_key(&format!("{org_id}/{stream_name}"))
+}
+
+/// Record a file to fold into a view, returns the key to remove once it is.
+pub async fn add_pending_fold(fold: &PendingFold) -> Result<String> {
+    let key = format!(
+        "{PENDING_FOLDS_KEY}{}/{}/{}",
+        fold.org_id,
+        fold.view,
+        ider::generate()
+    );
+    db::put(&key, json::to_vec(fold)?.into(), db::NEED_WATCH, None).await?;
+    Ok(key)
+}
+
+pub async fn remove_pending_fold(key: &str) -> Result<()> {
+    db::delete(key, false, db::NEED_WATCH, None).await
+}
+
+/// Whether the fold is still pending, read from the meta store rather than the
+/// cache which may lag behind.
+pub async fn is_pending_fold(key: &str) -> Result<bool> {
+    match db::get(key).await {
+        Ok(_) => Ok(true),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// The cached pending folds of all views, by key
+pub fn list_pending_folds() -> Vec<(String, PendingFold)> {
+    MATERIALIZED_VIEW_PENDING_FOLDS
+        .iter()
+        .map(|v| (format!("{PENDING_FOLDS_KEY}{}", v.key()), v.value().clone()))
+        .collect()
+}
+
+/// The smallest timestamp of the files not folded into a view yet, the view is
+/// complete below it.
+pub fn pending_fold_min_ts(org_id: &str, name: &str) -> Option<i64> {
+    let prefix = format!("{org_id}/{name}/");
+    MATERIALIZED_VIEW_PENDING_FOLDS
+        .iter()
+        .filter(|v| v.key().starts_with(&prefix))
+        .map(|v| v.min_ts)
+        .min()
+}
+
+/// Record a time range the view must not be read over. A gap recorded again
+/// with the same id is replaced, and gaps it covers are removed.
+pub async fn add_gap(gap: &ViewGap, id: &str) -> Result<()> {
+    let key = format!("{GAPS_KEY}{}/{}/{id}", gap.org_id, gap.view);
+    db::put(&key, json::to_vec(gap)?.into(), db::NEED_WATCH, None).await?;
+    let prefix = format!("{}/{}/", gap.org_id, gap.view);
+    let covered = MATERIALIZED_VIEW_GAPS
+        .iter()
+        .filter(|v| {
+            v.key().starts_with(&prefix)
+                && v.start_time >= gap.start_time
+                && v.end_time <= gap.end_time
+        })
+        .map(|v| format!("{GAPS_KEY}{}", v.key()))
+        .filter(|v| *v != key)
+        .collect::<Vec<_>>();
+    for key in covered {
+        db::delete_if_exists(&key, false, db::NEED_WATCH).await?;
+    }
+    Ok(())
+}
+
+/// The cached gaps of a view, as `(start, end)` with an exclusive end
+pub fn list_gaps(org_id: &str, name: &str) -> Vec<(i64, i64)> {
+    let prefix = format!("{org_id}/{name}/");
+    MATERIALIZED_VIEW_GAPS
+        .iter()
+        .filter(|v| v.key().starts_with(&prefix))
+        .map(|v| (v.start_time, v.end_time))
+        .collect()
+}
+
+pub async fn delete_gaps(org_id: &str, name: &str) -> Result<()> {
+    let prefix = format!("{org_id}/{name}/");
+    let keys = MATERIALIZED_VIEW_GAPS
+        .iter()
+        .filter(|v| v.key().starts_with(&prefix))
+        .map(|v| format!("{GAPS_KEY}{}", v.key()))
+        .collect::<Vec<_>>();
+    for key in keys {
+        db::delete_if_exists(&key, false, db::NEED_WATCH).await?;
+    }
+    Ok(())
+}
+
+pub async fn watch() -> Result<()> {
+    let key = MATERIALIZED_VIEWS_KEY;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching materialized views");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_materialized_views: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: MaterializedView = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                MATERIALIZED_VIEWS.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                MATERIALIZED_VIEWS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<()> {
+    let key = MATERIALIZED_VIEWS_KEY;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        match json::from_slice::<MaterializedView>(&item_value) {
+            Ok(view) => {
+                MATERIALIZED_VIEWS.insert(item_key.to_string(), view);
+            }
+            Err(e) => log::error!("Error deserializing materialized view {item_key}: {e}"),
+        }
+    }
+    log::info!("Materialized views Cached");
+    Ok(())
+}
+
+pub async fn watch_pending_folds() -> Result<()> {
+    let key = PENDING_FOLDS_KEY;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching materialized view pending folds");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_materialized_view_pending_folds: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: PendingFold = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                MATERIALIZED_VIEW_PENDING_FOLDS.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                MATERIALIZED_VIEW_PENDING_FOLDS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache_pending_folds() -> Result<()> {
+    let key = PENDING_FOLDS_KEY;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        match json::from_slice::<PendingFold>(&item_value) {
+            Ok(fold) => {
+                MATERIALIZED_VIEW_PENDING_FOLDS.insert(item_key.to_string(), fold);
+            }
+            Err(e) => log::error!("Error deserializing pending fold {item_key}: {e}"),
+        }
+    }
+    log::info!("Materialized view pending folds Cached");
+    Ok(())
+}
+
+pub async fn watch_gaps() -> Result<()> {
+    let key = GAPS_KEY;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching materialized view gaps");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_materialized_view_gaps: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: ViewGap = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                MATERIALIZED_VIEW_GAPS.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                MATERIALIZED_VIEW_GAPS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache_gaps() -> Result<()> {
+    let key = GAPS_KEY;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        match json::from_slice::<ViewGap>(&item_value) {
+            Ok(gap) => {
+                MATERIALIZED_VIEW_GAPS.insert(item_key.to_string(), gap);
+            }
+            Err(e) => log::error!("Error deserializing materialized view gap {item_key}: {e}"),
+        }
+    }
+    log::info!("Materialized view gaps Cached");
+    Ok(())
+}
@@ -34,6 +34,7 @@ pub mod keys;
 pub mod kv;
 #[cfg(feature = "enterprise")]
 pub mod license;
+pub mod materialized_views;
 pub mod metas;
 pub mod metrics;
 pub mod model_pricing;
@@ -47,6 +48,8 @@ pub mod org_users;
 pub mod organization;
 pub mod pipeline;
 pub mod pipeline_errors;
+pub mod prometheus_rules;
+pub mod prometheus_scrape;
 #[cfg(feature = "vectorscan")]
 pub mod re_pattern;
 pub mod saved_view;
@@ -0,0 +1,94 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{
+    meta::promql::rules::{RuleGroupHealth, StoredRuleGroup},
+    utils::json,
+};
+use infra::errors::{DbError, Error, Result};
+
+use crate::service::db;
+
+// DBKey to store the rule groups of an org: /prometheus_rules/{org}/{namespace}/{group}
+pub const PROMETHEUS_RULES_KEY: &str = "/prometheus_rules";
+// DBKey to store the evaluation health of the recording rules of a rule group
+pub const PROMETHEUS_RULE_HEALTH_KEY: &str = "/prometheus_rule_health";
+
+pub async fn get(org_id: &str, namespace: &str, group: &str) -> Result<Option<StoredRuleGroup>> {
+    let key = format!("{PROMETHEUS_RULES_KEY}/{org_id}/{namespace}/{group}");
+    match db::get(&key).await {
+        Ok(v) => Ok(Some(json::from_slice(&v)?)),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Lists the rule groups of an org, optionally only the ones of a namespace,
+/// sorted by namespace and group name.
+pub async fn list(org_id: &str, namespace: Option<&str>) -> Result<Vec<StoredRuleGroup>> {
+    let key = match namespace {
+        Some(namespace) => format!("{PROMETHEUS_RULES_KEY}/{org_id}/{namespace}/"),
+        None => format!("{PROMETHEUS_RULES_KEY}/{org_id}/"),
+    };
+    let mut groups = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<StoredRuleGroup>(&v))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    groups.sort_by(|a, b| {
+        a.namespace
+            .cmp(&b.namespace)
+            .then_with(|| a.group.name.cmp(&b.group.name))
+    });
+    Ok(groups)
+}
+
+pub async fn set(org_id: &str, group: &StoredRuleGroup) -> Result<()> {
+    let key = format!(
+        "{PROMETHEUS_RULES_KEY}/{org_id}/{}/{}",
+        group.namespace, group.group.name
+    );
+    db::put(&key, json::to_vec(group)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, namespace: &str, group: &str) -> Result<()> {
+    let key = format!("{PROMETHEUS_RULES_KEY}/{org_id}/{namespace}/{group}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await?;
+    let key = format!("{PROMETHEUS_RULE_HEALTH_KEY}/{org_id}/{namespace}/{group}");
+    db::delete_if_exists(&key, false, db::NO_NEED_WATCH).await
+}
+
+pub async fn get_health(
+    org_id: &str,
+    namespace: &str,
+    group: &str,
+) -> Result<Option<RuleGroupHealth>> {
+    let key = format!("{PROMETHEUS_RULE_HEALTH_KEY}/{org_id}/{namespace}/{group}");
+    match db::get(&key).await {
+        Ok(v) => Ok(Some(json::from_slice(&v)?)),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn set_health(
+    org_id: &str,
+    namespace: &str,
+    group: &str,
+    health: &RuleGroupHealth,
+) -> Result<()> {
+    let key = format!("{PROMETHEUS_RULE_HEALTH_KEY}/{org_id}/{namespace}/{group}");
+    db::put(&key, json::to_vec(health)?.into(), db::NO_NEED_WATCH, None).await
+}
@@ -0,0 +1,53 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::promql::scrape::ScrapeConfig, utils::json};
+use infra::errors::{DbError, Error, Result};
+
+use crate::service::db;
+
+// DBKey to store the scrape configs of an org: /prometheus_scrape/{org}/{job_name}
+pub const PROMETHEUS_SCRAPE_KEY: &str = "/prometheus_scrape";
+
+pub async fn get(org_id: &str, job_name: &str) -> Result<Option<ScrapeConfig>> {
+    let key = format!("{PROMETHEUS_SCRAPE_KEY}/{org_id}/{job_name}");
+    match db::get(&key).await {
+        Ok(v) => Ok(Some(json::from_slice(&v)?)),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Lists the scrape configs of an org, sorted by job name.
+pub async fn list(org_id: &str) -> Result<Vec<ScrapeConfig>> {
+    let key = format!("{PROMETHEUS_SCRAPE_KEY}/{org_id}/");
+    let mut configs = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<ScrapeConfig>(&v))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    configs.sort_by(|a, b| a.job_name.cmp(&b.job_name));
+    Ok(configs)
+}
+
+pub async fn set(org_id: &str, config: &ScrapeConfig) -> Result<()> {
+    let key = format!("{PROMETHEUS_SCRAPE_KEY}/{org_id}/{}", config.job_name);
+    db::put(&key, json::to_vec(config)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, job_name: &str) -> Result<()> {
+    let key = format!("{PROMETHEUS_SCRAPE_KEY}/{org_id}/{job_name}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
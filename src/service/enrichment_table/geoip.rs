@@ -303,6 +303,14 @@ impl Geoip {
         Some(map)
     }
 
+    /// Look up a single field of the record of an address, `None` if the
+    /// address is unknown or the record has no value for the field.
+    pub fn lookup_field(&self, ip: IpAddr, field: &str) -> Option<Value> {
+        self.lookup(ip, Some(&[field.to_string()]))?
+            .remove(field)
+            .filter(|value| !value.is_null())
+    }
+
     fn take_translation_from_names<'a>(
         &self,
         names: &maxminddb::geoip2::Names<'a>,
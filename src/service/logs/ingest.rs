@@ -190,6 +190,11 @@ pub async fn ingest(
             UsageType::Loki,
             IngestionData::JSON(logs),
         ),
+        IngestionRequest::JsonValues(IngestionValueType::Syslog, logs) => (
+            "/api/org/ingest/logs/_syslog",
+            UsageType::Syslog,
+            IngestionData::JSON(logs),
+        ),
         IngestionRequest::GCP(req) => (
             "/api/org/ingest/logs/_gcs",
             UsageType::GCPSubscription,
@@ -0,0 +1,1178 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Loki-compatible LogQL query execution over log streams.
+//!
+//! Queries are translated into SQL on the stream selected by the
+//! `o2_stream_name` label. Label matchers, line filters and label filters that
+//! precede any parser stage are pushed down into the search; `json` / `logfmt`
+//! parsers and the label filters following them are evaluated on the returned
+//! rows. Metric queries are counted per time bucket in SQL whenever the
+//! pipeline allows it.
+//!
+//! Series of metric queries are identified by the labels used in the stream
+//! selector and in `by` clauses, as log records have no separate notion of
+//! stream labels.
+
+pub mod parser;
+mod sql;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use config::{
+    ALL_VALUES_COL_NAME, ID_COL_NAME, MESSAGE_COL_NAME, ORIGINAL_DATA_COL_NAME, STREAM_NAME_LABEL,
+    TIMESTAMP_COL_NAME, get_config,
+    meta::{search, stream::StreamType},
+    utils::{
+        json,
+        time::{now_micros, parse_milliseconds, parse_str_to_timestamp_micros},
+    },
+};
+use regex::Regex;
+
+use self::parser::{
+    Grouping, LabelFilter, LabelFilterOp, LabelFilterValue, LineFilterOp, LogQLExpr, LogSelector,
+    MetricExpr, ParserKind, PipelineStage, RangeFunc, VectorOp,
+};
+use crate::common::meta::loki::{
+    LokiError, LokiLabelsRequest, LokiMatrixResult, LokiQueryData, LokiQueryRequest,
+    LokiQueryResult, LokiRangeQueryRequest, LokiStreamResult, LokiVectorResult,
+};
+
+const DEFAULT_LIMIT: i64 = 100;
+/// Default time range of queries without `start`, one hour
+const DEFAULT_LOOKBACK: i64 = 3_600_000_000;
+/// Page size used when rows are filtered after the search
+const PAGE_SIZE: i64 = 1000;
+/// Max number of points per series, same as Loki
+const MAX_POINTS: i64 = 11_000;
+/// Columns that are never exposed as labels
+const INTERNAL_COLUMNS: [&str; 5] = [
+    TIMESTAMP_COL_NAME,
+    ID_COL_NAME,
+    ALL_VALUES_COL_NAME,
+    ORIGINAL_DATA_COL_NAME,
+    MESSAGE_COL_NAME,
+];
+
+type Labels = BTreeMap<String, String>;
+
+/// A metric series with its samples keyed by timestamp in microseconds
+type Series = (Labels, BTreeMap<i64, f64>);
+
+/// Per time bucket entry count and bytes of a series
+type Buckets = BTreeMap<i64, (f64, f64)>;
+
+struct QueryContext<'a> {
+    trace_id: &'a str,
+    org_id: &'a str,
+    user_id: Option<String>,
+}
+
+impl QueryContext<'_> {
+    async fn search(
+        &self,
+        sql: String,
+        start_time: i64,
+        end_time: i64,
+        from: i64,
+        size: i64,
+    ) -> Result<Vec<json::Value>, LokiError> {
+        let req = search::Request {
+            query: search::Query {
+                sql,
+                from,
+                size,
+                start_time,
+                end_time,
+                ..Default::default()
+            },
+            search_type: Some(search::SearchEventType::Other),
+            use_cache: search::default_use_cache(),
+            ..Default::default()
+        };
+        log::debug!(
+            "[trace_id {}] logql search: {}",
+            self.trace_id,
+            req.query.sql
+        );
+        let resp = crate::service::search::search(
+            self.trace_id,
+            self.org_id,
+            StreamType::Logs,
+            self.user_id.clone(),
+            &req,
+        )
+        .await
+        .map_err(|source| LokiError::Search { source })?;
+        Ok(resp.hits)
+    }
+
+    /// Returns the field names of a logs stream, `None` if it does not exist.
+    async fn stream_fields(&self, stream: &str) -> Result<Option<HashSet<String>>, LokiError> {
+        let schema = infra::schema::get(self.org_id, stream, StreamType::Logs)
+            .await
+            .map_err(|source| LokiError::Search { source })?;
+        if schema.fields().is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            schema
+                .fields()
+                .iter()
+                .map(|f| f.name().to_string())
+                .collect(),
+        ))
+    }
+}
+
+/// Returns the stream a query reads from, used for permission checks.
+pub fn query_stream(query: &str) -> Result<String, LokiError> {
+    let expr = parser::parse(query)?;
+    sql::resolve_stream(&selector_of(&expr).matchers)
+}
+
+/// Handles `/loki/api/v1/query_range`.
+pub async fn query_range(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    req: &LokiRangeQueryRequest,
+) -> Result<LokiQueryData, LokiError> {
+    let expr = parser::parse(req.query.as_deref().unwrap_or_default())?;
+    let end = parse_time(req.end.as_deref(), now_micros())?;
+    let start = parse_time(req.start.as_deref(), end - DEFAULT_LOOKBACK)?;
+    if end < start {
+        return Err(invalid_query("end timestamp must not be before start time"));
+    }
+    let ctx = QueryContext {
+        trace_id,
+        org_id,
+        user_id,
+    };
+    let result = match expr {
+        LogQLExpr::Log(selector) => {
+            let limit = req.limit.filter(|v| *v > 0).unwrap_or(DEFAULT_LIMIT);
+            let ascending = is_forward(req.direction.as_deref());
+            LokiQueryResult::Streams(
+                query_logs(&ctx, &selector, start, end, limit, ascending).await?,
+            )
+        }
+        LogQLExpr::Metric(expr) => {
+            let step = parse_step(req.step.as_deref(), start, end)?;
+            let series = query_metric(&ctx, &expr, start, end, step).await?;
+            LokiQueryResult::Matrix(
+                series
+                    .into_iter()
+                    .map(|(metric, samples)| LokiMatrixResult {
+                        metric,
+                        values: samples
+                            .into_iter()
+                            .map(|(ts, v)| (ts as f64 / 1_000_000.0, v.to_string()))
+                            .collect(),
+                    })
+                    .collect(),
+            )
+        }
+    };
+    Ok(result.into())
+}
+
+/// Handles `/loki/api/v1/query`. Like Loki, instant queries only support
+/// metric queries.
+pub async fn query_instant(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    req: &LokiQueryRequest,
+) -> Result<LokiQueryData, LokiError> {
+    let LogQLExpr::Metric(expr) = parser::parse(req.query.as_deref().unwrap_or_default())? else {
+        return Err(invalid_query(
+            "log queries are not supported as an instant query type, use query_range instead",
+        ));
+    };
+    let time = parse_time(req.time.as_deref(), now_micros())?;
+    let ctx = QueryContext {
+        trace_id,
+        org_id,
+        user_id,
+    };
+    let (_, _, range) = expr.range_selector();
+    let series = query_metric(&ctx, &expr, time, time, range).await?;
+    let result = series
+        .into_iter()
+        .filter_map(|(metric, samples)| {
+            let (ts, v) = samples.into_iter().next()?;
+            Some(LokiVectorResult {
+                metric,
+                value: (ts as f64 / 1_000_000.0, v.to_string()),
+            })
+        })
+        .collect();
+    Ok(LokiQueryResult::Vector(result).into())
+}
+
+/// Handles `/loki/api/v1/labels`. Labels are the fields of the stream selected
+/// by the optional query, or of all logs streams.
+pub async fn labels(org_id: &str, req: &LokiLabelsRequest) -> Result<Vec<String>, LokiError> {
+    let mut labels = HashSet::from([STREAM_NAME_LABEL.to_string()]);
+    match req.query.as_deref().filter(|q| !q.trim().is_empty()) {
+        Some(query) => {
+            let expr = parser::parse(query)?;
+            let stream = sql::resolve_stream(&selector_of(&expr).matchers)?;
+            let schema = infra::schema::get(org_id, &stream, StreamType::Logs)
+                .await
+                .map_err(|source| LokiError::Search { source })?;
+            labels.extend(schema.fields().iter().map(|f| f.name().to_string()));
+        }
+        None => {
+            let streams = crate::service::db::schema::list(org_id, Some(StreamType::Logs), true)
+                .await
+                .map_err(|e| LokiError::Search {
+                    source: infra::errors::Error::Message(e.to_string()),
+                })?;
+            for stream in streams {
+                labels.extend(stream.schema.fields().iter().map(|f| f.name().to_string()));
+            }
+        }
+    }
+    let mut labels = labels
+        .into_iter()
+        .filter(|l| !INTERNAL_COLUMNS.contains(&l.as_str()))
+        .collect::<Vec<_>>();
+    labels.sort();
+    Ok(labels)
+}
+
+/// Handles `/loki/api/v1/label/{name}/values`.
+pub async fn label_values(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    name: &str,
+    req: &LokiLabelsRequest,
+) -> Result<Vec<String>, LokiError> {
+    if sql::is_stream_label(name) {
+        let streams = crate::service::db::schema::list(org_id, Some(StreamType::Logs), false)
+            .await
+            .map_err(|e| LokiError::Search {
+                source: infra::errors::Error::Message(e.to_string()),
+            })?;
+        let mut values = streams
+            .into_iter()
+            .map(|s| s.stream_name)
+            .collect::<Vec<_>>();
+        values.sort();
+        return Ok(values);
+    }
+    if INTERNAL_COLUMNS.contains(&name) {
+        return Ok(vec![]);
+    }
+
+    let selector = match req.query.as_deref().filter(|q| !q.trim().is_empty()) {
+        Some(query) => Some(parser::parse(query)?),
+        None => None,
+    };
+    let selector = selector.as_ref().map(selector_of);
+    let stream = match selector {
+        Some(selector) => sql::resolve_stream(&selector.matchers)?,
+        None => config::DEFAULT_STREAM_NAME.to_string(),
+    };
+    let ctx = QueryContext {
+        trace_id,
+        org_id,
+        user_id,
+    };
+    let Some(fields) = ctx.stream_fields(&stream).await? else {
+        return Ok(vec![]);
+    };
+    if !fields.contains(name) {
+        return Ok(vec![]);
+    }
+    let conds = match selector {
+        Some(s) => match sql::where_conditions(s, &fields) {
+            Some(conds) => conds,
+            None => return Ok(vec![]),
+        },
+        None => vec![],
+    };
+    let (start, end) = labels_time_range(req)?;
+    let sql = sql::build_distinct_sql(&stream, &conds, &[name.to_string()]);
+    let hits = ctx.search(sql, start, end, 0, -1).await?;
+    Ok(hits
+        .iter()
+        .filter_map(|row| row.get(name).and_then(label_value))
+        .collect())
+}
+
+/// Handles `/loki/api/v1/series`, returning the distinct label sets of the
+/// labels used in the `match[]` selector.
+pub async fn series(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    req: &LokiLabelsRequest,
+) -> Result<Vec<Labels>, LokiError> {
+    let Some(query) = req.matcher.as_deref().filter(|q| !q.trim().is_empty()) else {
+        return Err(invalid_query("at least one match[] argument is required"));
+    };
+    let expr = parser::parse(query)?;
+    let selector = selector_of(&expr);
+    let stream = sql::resolve_stream(&selector.matchers)?;
+    let ctx = QueryContext {
+        trace_id,
+        org_id,
+        user_id,
+    };
+    let Some(fields) = ctx.stream_fields(&stream).await? else {
+        return Ok(vec![]);
+    };
+    let stream_labels = || Labels::from([(STREAM_NAME_LABEL.to_string(), stream.clone())]);
+    let columns = series_columns(selector, &[], &fields);
+    if columns.is_empty() {
+        return Ok(vec![stream_labels()]);
+    }
+    let Some(conds) = sql::where_conditions(selector, &fields) else {
+        return Ok(vec![]);
+    };
+    let (start, end) = labels_time_range(req)?;
+    let sql = sql::build_distinct_sql(&stream, &conds, &columns);
+    let hits = ctx.search(sql, start, end, 0, -1).await?;
+    Ok(hits
+        .iter()
+        .map(|row| {
+            let mut labels = stream_labels();
+            labels.extend(
+                columns
+                    .iter()
+                    .filter_map(|c| row.get(c).and_then(label_value).map(|v| (c.to_string(), v))),
+            );
+            labels
+        })
+        .collect())
+}
+
+async fn query_logs(
+    ctx: &QueryContext<'_>,
+    selector: &LogSelector,
+    start: i64,
+    end: i64,
+    limit: i64,
+    ascending: bool,
+) -> Result<Vec<LokiStreamResult>, LokiError> {
+    let stream = sql::resolve_stream(&selector.matchers)?;
+    let Some(fields) = ctx.stream_fields(&stream).await? else {
+        return Ok(vec![]);
+    };
+    let has_message = fields.contains(MESSAGE_COL_NAME);
+    let Some(conds) = sql::where_conditions(selector, &fields) else {
+        return Ok(vec![]);
+    };
+    let pipeline = Pipeline::new(&selector.pipeline, has_message)?;
+    let sql = sql::build_logs_sql(&stream, &conds, ascending);
+    let page_size = if pipeline.is_empty() {
+        limit
+    } else {
+        PAGE_SIZE
+    };
+
+    let mut entries = Vec::new();
+    let mut from = 0;
+    loop {
+        let hits = ctx.search(sql.clone(), start, end, from, page_size).await?;
+        let fetched = hits.len() as i64;
+        for row in hits {
+            let Some((ts, mut labels, line)) = row_to_entry(row) else {
+                continue;
+            };
+            if pipeline.process(&line, &mut labels) {
+                entries.push((ts, labels, line));
+                if entries.len() as i64 >= limit {
+                    break;
+                }
+            }
+        }
+        from += fetched;
+        if entries.len() as i64 >= limit || fetched < page_size {
+            break;
+        }
+        check_scan_limit(from)?;
+    }
+    Ok(group_streams(entries))
+}
+
+/// Evaluates a metric query at every step between `start` and `end`.
+async fn query_metric(
+    ctx: &QueryContext<'_>,
+    expr: &MetricExpr,
+    start: i64,
+    end: i64,
+    step: i64,
+) -> Result<Vec<Series>, LokiError> {
+    let (selector, func, range) = expr.range_selector();
+    let stream = sql::resolve_stream(&selector.matchers)?;
+    let Some(fields) = ctx.stream_fields(&stream).await? else {
+        return Ok(vec![]);
+    };
+    let has_message = fields.contains(MESSAGE_COL_NAME);
+    let Some(conds) = sql::where_conditions(selector, &fields) else {
+        return Ok(vec![]);
+    };
+    let pipeline = Pipeline::new(&selector.pipeline, has_message)?;
+
+    // Loki windows are left-open and right-closed: `(t - range, t]`
+    let bucket = gcd(step, range);
+    let offset = start - range + 1;
+    let (search_start, search_end) = (offset, end + 1);
+    let grouping = expr.grouping_labels();
+
+    let mut series: HashMap<Labels, Buckets> = HashMap::new();
+    if pipeline.is_empty() {
+        let columns = series_columns(selector, &grouping, &fields);
+        let sql = sql::build_metric_sql(&stream, &conds, &columns, offset, bucket, has_message);
+        let hits = ctx.search(sql, search_start, search_end, 0, -1).await?;
+        for row in hits {
+            let Some(n) = row.get(sql::BUCKET_COL).and_then(json::Value::as_i64) else {
+                continue;
+            };
+            let count = row
+                .get(sql::COUNT_COL)
+                .and_then(json::Value::as_f64)
+                .unwrap_or_default();
+            let bytes = row
+                .get(sql::BYTES_COL)
+                .and_then(json::Value::as_f64)
+                .unwrap_or_default();
+            let labels = columns
+                .iter()
+                .filter_map(|c| row.get(c).and_then(label_value).map(|v| (c.to_string(), v)))
+                .collect();
+            let entry = series.entry(labels).or_default().entry(n).or_default();
+            entry.0 += count;
+            entry.1 += bytes;
+        }
+    } else {
+        let label_names = series_label_names(selector, &grouping);
+        let sql = sql::build_logs_sql(&stream, &conds, true);
+        let mut from = 0;
+        loop {
+            let hits = ctx
+                .search(sql.clone(), search_start, search_end, from, PAGE_SIZE)
+                .await?;
+            let fetched = hits.len() as i64;
+            for row in hits {
+                let Some((ts, mut labels, line)) = row_to_entry(row) else {
+                    continue;
+                };
+                if !pipeline.process(&line, &mut labels) {
+                    continue;
+                }
+                labels.retain(|k, _| label_names.contains(k));
+                let entry = series
+                    .entry(labels)
+                    .or_default()
+                    .entry((ts - offset) / bucket)
+                    .or_default();
+                entry.0 += 1.0;
+                entry.1 += line.len() as f64;
+            }
+            from += fetched;
+            if fetched < PAGE_SIZE {
+                break;
+            }
+            check_scan_limit(from)?;
+        }
+    }
+
+    let series = series
+        .into_iter()
+        .map(|(labels, buckets)| {
+            let samples = eval_range(&buckets, func, start, end, step, range, bucket);
+            (labels, samples)
+        })
+        .filter(|(_, samples)| !samples.is_empty())
+        .collect();
+    let mut series = evaluate(expr, series);
+    series.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(series)
+}
+
+/// Applies the range function of a series to every step. Bucket `n` covers
+/// `[offset + n * bucket, offset + (n + 1) * bucket)` with `offset` being the
+/// start of the first window, so the window of step `k` spans the buckets
+/// `[k * step / bucket, (k * step + range) / bucket)`.
+fn eval_range(
+    buckets: &Buckets,
+    func: RangeFunc,
+    start: i64,
+    end: i64,
+    step: i64,
+    range: i64,
+    bucket: i64,
+) -> BTreeMap<i64, f64> {
+    let range_secs = range as f64 / 1_000_000.0;
+    let mut samples = BTreeMap::new();
+    let steps = (end - start) / step;
+    for k in 0..=steps {
+        let lo = k * step / bucket;
+        let hi = (k * step + range) / bucket;
+        let (count, bytes) = buckets
+            .range(lo..hi)
+            .fold((0.0, 0.0), |acc, (_, (c, b))| (acc.0 + c, acc.1 + b));
+        if count == 0.0 {
+            continue;
+        }
+        let value = match func {
+            RangeFunc::CountOverTime => count,
+            RangeFunc::Rate => count / range_secs,
+            RangeFunc::BytesOverTime => bytes,
+            RangeFunc::BytesRate => bytes / range_secs,
+        };
+        samples.insert(start + k * step, value);
+    }
+    samples
+}
+
+/// Applies the vector aggregations wrapping the range aggregation.
+fn evaluate(expr: &MetricExpr, series: Vec<Series>) -> Vec<Series> {
+    match expr {
+        MetricExpr::Range { .. } => series,
+        MetricExpr::Vector { op, grouping, expr } => {
+            aggregate(*op, grouping.as_ref(), evaluate(expr, series))
+        }
+    }
+}
+
+fn aggregate(op: VectorOp, grouping: Option<&Grouping>, series: Vec<Series>) -> Vec<Series> {
+    // per group and timestamp: sum, count, min, max
+    let mut groups: HashMap<Labels, BTreeMap<i64, (f64, f64, f64, f64)>> = HashMap::new();
+    for (labels, samples) in series {
+        let key = match grouping {
+            None => Labels::new(),
+            Some(Grouping::By(by)) => labels.into_iter().filter(|(k, _)| by.contains(k)).collect(),
+            Some(Grouping::Without(without)) => labels
+                .into_iter()
+                .filter(|(k, _)| !without.contains(k))
+                .collect(),
+        };
+        let group = groups.entry(key).or_default();
+        for (ts, v) in samples {
+            let acc = group
+                .entry(ts)
+                .or_insert((0.0, 0.0, f64::INFINITY, f64::NEG_INFINITY));
+            acc.0 += v;
+            acc.1 += 1.0;
+            acc.2 = acc.2.min(v);
+            acc.3 = acc.3.max(v);
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(labels, samples)| {
+            let samples = samples
+                .into_iter()
+                .map(|(ts, (sum, count, min, max))| {
+                    let v = match op {
+                        VectorOp::Sum => sum,
+                        VectorOp::Avg => sum / count,
+                        VectorOp::Min => min,
+                        VectorOp::Max => max,
+                        VectorOp::Count => count,
+                    };
+                    (ts, v)
+                })
+                .collect();
+            (labels, samples)
+        })
+        .collect()
+}
+
+/// Pipeline stages evaluated on the rows returned by the search.
+struct Pipeline {
+    stages: Vec<Stage>,
+}
+
+enum Stage {
+    Line(LineFilterOp, String, Option<Regex>),
+    Parser(ParserKind),
+    Label(LabelFilter, Option<Regex>),
+}
+
+impl Pipeline {
+    /// Line filters are part of the pipeline only when they could not be
+    /// pushed down because the stream has no message column.
+    fn new(pipeline: &[PipelineStage], has_message: bool) -> Result<Self, LokiError> {
+        let (pushdown, post) = sql::split_pipeline(pipeline);
+        let line_filters = pushdown
+            .into_iter()
+            .filter(|s| !has_message && matches!(s, PipelineStage::LineFilter(..)));
+        let mut stages = Vec::new();
+        for stage in line_filters.chain(post) {
+            stages.push(match stage {
+                PipelineStage::LineFilter(op, value) => {
+                    let re = match op {
+                        LineFilterOp::Regex | LineFilterOp::NotRegex => Some(compile(value)?),
+                        _ => None,
+                    };
+                    Stage::Line(*op, value.clone(), re)
+                }
+                PipelineStage::Parser(kind) => Stage::Parser(*kind),
+                PipelineStage::LabelFilter(filter) => {
+                    let re = match (&filter.op, &filter.value) {
+                        (
+                            LabelFilterOp::Regex | LabelFilterOp::NotRegex,
+                            LabelFilterValue::String(v),
+                        ) => Some(compile(&format!("^(?:{v})$"))?),
+                        _ => None,
+                    };
+                    Stage::Label(filter.clone(), re)
+                }
+            });
+        }
+        Ok(Self { stages })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Runs the stages on an entry, returns false if the entry is filtered out.
+    fn process(&self, line: &str, labels: &mut Labels) -> bool {
+        self.stages.iter().all(|stage| match stage {
+            Stage::Line(op, value, re) => match op {
+                LineFilterOp::Contains => line.contains(value.as_str()),
+                LineFilterOp::NotContains => !line.contains(value.as_str()),
+                LineFilterOp::Regex => re.as_ref().is_some_and(|re| re.is_match(line)),
+                LineFilterOp::NotRegex => re.as_ref().is_some_and(|re| !re.is_match(line)),
+            },
+            Stage::Parser(ParserKind::Json) => {
+                parse_json_line(line, labels);
+                true
+            }
+            Stage::Parser(ParserKind::Logfmt) => {
+                parse_logfmt_line(line, labels);
+                true
+            }
+            Stage::Label(filter, re) => match_label_filter(filter, re.as_ref(), labels),
+        })
+    }
+}
+
+fn match_label_filter(filter: &LabelFilter, re: Option<&Regex>, labels: &Labels) -> bool {
+    let value = labels.get(&filter.name).map(String::as_str).unwrap_or("");
+    match &filter.value {
+        LabelFilterValue::String(expected) => match filter.op {
+            LabelFilterOp::Equal => value == expected,
+            LabelFilterOp::NotEqual => value != expected,
+            LabelFilterOp::Regex => re.is_some_and(|re| re.is_match(value)),
+            LabelFilterOp::NotRegex => re.is_some_and(|re| !re.is_match(value)),
+            LabelFilterOp::Greater => value > expected.as_str(),
+            LabelFilterOp::GreaterEqual => value >= expected.as_str(),
+            LabelFilterOp::Less => value < expected.as_str(),
+            LabelFilterOp::LessEqual => value <= expected.as_str(),
+        },
+        LabelFilterValue::Number(expected) => {
+            let Ok(value) = value.parse::<f64>() else {
+                return false;
+            };
+            match filter.op {
+                LabelFilterOp::Equal | LabelFilterOp::Regex => value == *expected,
+                LabelFilterOp::NotEqual | LabelFilterOp::NotRegex => value != *expected,
+                LabelFilterOp::Greater => value > *expected,
+                LabelFilterOp::GreaterEqual => value >= *expected,
+                LabelFilterOp::Less => value < *expected,
+                LabelFilterOp::LessEqual => value <= *expected,
+            }
+        }
+    }
+}
+
+/// Extracts the keys of a JSON line as labels, nested keys are joined with
+/// `_`. Keys clashing with existing labels get an `_extracted` suffix.
+fn parse_json_line(line: &str, labels: &mut Labels) {
+    let Ok(json::Value::Object(obj)) = json::from_str::<json::Value>(line) else {
+        labels.insert("__error__".to_string(), "JSONParserErr".to_string());
+        return;
+    };
+    let mut extracted = Vec::new();
+    flatten_json("", &obj, &mut extracted);
+    for (key, value) in extracted {
+        insert_extracted(labels, key, value);
+    }
+}
+
+fn flatten_json(
+    prefix: &str,
+    obj: &json::Map<String, json::Value>,
+    out: &mut Vec<(String, String)>,
+) {
+    for (key, value) in obj {
+        let key = if prefix.is_empty() {
+            sanitize_label(key)
+        } else {
+            format!("{prefix}_{}", sanitize_label(key))
+        };
+        match value {
+            json::Value::Object(obj) => flatten_json(&key, obj, out),
+            json::Value::Array(_) => {}
+            v => {
+                if let Some(v) = label_value(v) {
+                    out.push((key, v));
+                }
+            }
+        }
+    }
+}
+
+/// Extracts `key=value` pairs of a logfmt line as labels.
+fn parse_logfmt_line(line: &str, labels: &mut Labels) {
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '=') {
+            key.push(c);
+        }
+        if key.is_empty() {
+            if chars.next().is_none() {
+                return;
+            }
+            continue;
+        }
+        let mut value = String::new();
+        if chars.next_if_eq(&'=').is_some() {
+            if chars.next_if_eq(&'"').is_some() {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(c) = chars.next() {
+                                value.push(match c {
+                                    'n' => '\n',
+                                    't' => '\t',
+                                    c => c,
+                                });
+                            }
+                        }
+                        c => value.push(c),
+                    }
+                }
+            } else {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    value.push(c);
+                }
+            }
+        }
+        insert_extracted(labels, sanitize_label(&key), value);
+    }
+}
+
+fn insert_extracted(labels: &mut Labels, key: String, value: String) {
+    if labels.contains_key(&key) {
+        labels.insert(format!("{key}_extracted"), value);
+    } else {
+        labels.insert(key, value);
+    }
+}
+
+/// Replaces characters that are not valid in label names with `_`.
+fn sanitize_label(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Splits a search hit into its timestamp, labels and log line. Streams
+/// without a message column use the whole record as line.
+fn row_to_entry(row: json::Value) -> Option<(i64, Labels, String)> {
+    let json::Value::Object(mut row) = row else {
+        return None;
+    };
+    let ts = row.remove(TIMESTAMP_COL_NAME)?.as_i64()?;
+    for col in INTERNAL_COLUMNS {
+        if col != MESSAGE_COL_NAME {
+            row.remove(col);
+        }
+    }
+    let line = match row.remove(MESSAGE_COL_NAME) {
+        Some(json::Value::String(s)) => s,
+        Some(v) if !v.is_null() => v.to_string(),
+        _ => json::Value::Object(row.clone()).to_string(),
+    };
+    let labels = row
+        .iter()
+        .filter_map(|(k, v)| label_value(v).map(|v| (k.to_string(), v)))
+        .collect();
+    Some((ts, labels, line))
+}
+
+/// Groups entries by their label set, keeping the entry order.
+fn group_streams(entries: Vec<(i64, Labels, String)>) -> Vec<LokiStreamResult> {
+    let mut streams: Vec<LokiStreamResult> = Vec::new();
+    let mut index: HashMap<Labels, usize> = HashMap::new();
+    for (ts, labels, line) in entries {
+        let value = ((ts * 1000).to_string(), line);
+        match index.get(&labels) {
+            Some(i) => streams[*i].values.push(value),
+            None => {
+                index.insert(labels.clone(), streams.len());
+                streams.push(LokiStreamResult {
+                    stream: labels,
+                    values: vec![value],
+                });
+            }
+        }
+    }
+    streams
+}
+
+/// Label names identifying the series of a metric query.
+fn series_label_names(selector: &LogSelector, grouping: &[String]) -> Vec<String> {
+    let mut names = selector
+        .matchers
+        .iter()
+        .filter(|m| !sql::is_stream_label(&m.name))
+        .map(|m| m.name.clone())
+        .chain(grouping.iter().cloned())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Series label names that exist as columns of the stream.
+fn series_columns(
+    selector: &LogSelector,
+    grouping: &[String],
+    fields: &HashSet<String>,
+) -> Vec<String> {
+    series_label_names(selector, grouping)
+        .into_iter()
+        .filter(|name| fields.contains(name) && !INTERNAL_COLUMNS.contains(&name.as_str()))
+        .collect()
+}
+
+fn selector_of(expr: &LogQLExpr) -> &LogSelector {
+    match expr {
+        LogQLExpr::Log(selector) => selector,
+        LogQLExpr::Metric(expr) => expr.range_selector().0,
+    }
+}
+
+fn label_value(v: &json::Value) -> Option<String> {
+    match v {
+        json::Value::String(s) if !s.is_empty() => Some(s.to_string()),
+        json::Value::Number(n) => Some(n.to_string()),
+        json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn labels_time_range(req: &LokiLabelsRequest) -> Result<(i64, i64), LokiError> {
+    let end = parse_time(req.end.as_deref(), now_micros())?;
+    let start = parse_time(req.start.as_deref(), end - DEFAULT_LOOKBACK)?;
+    Ok((start, end))
+}
+
+/// Parses a timestamp given as unix epoch in any precision, as float seconds
+/// or as RFC3339.
+fn parse_time(v: Option<&str>, default: i64) -> Result<i64, LokiError> {
+    let Some(v) = v.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(default);
+    };
+    if v.contains('.')
+        && let Ok(secs) = v.parse::<f64>()
+    {
+        return Ok((secs * 1_000_000.0) as i64);
+    }
+    parse_str_to_timestamp_micros(v).map_err(|e| invalid_query(&format!("invalid time '{v}': {e}")))
+}
+
+/// Parses the step given as duration or float number of seconds. Without a
+/// step, the range is split into about 250 points like Loki does.
+fn parse_step(v: Option<&str>, start: i64, end: i64) -> Result<i64, LokiError> {
+    let step = match v.map(str::trim).filter(|v| !v.is_empty()) {
+        None => ((end - start) / 250 / 1_000_000).max(1) * 1_000_000,
+        Some(v) => match v.parse::<f64>() {
+            Ok(secs) => (secs * 1_000_000.0) as i64,
+            Err(_) => {
+                parse_milliseconds(v)
+                    .map_err(|e| invalid_query(&format!("invalid step '{v}': {e}")))?
+                    as i64
+                    * 1000
+            }
+        },
+    };
+    if step <= 0 {
+        return Err(invalid_query(
+            "zero or negative query resolution step widths are not accepted",
+        ));
+    }
+    if (end - start) / step > MAX_POINTS {
+        return Err(invalid_query(&format!(
+            "exceeded maximum resolution of {MAX_POINTS} points per timeseries, try increasing the step"
+        )));
+    }
+    Ok(step)
+}
+
+fn is_forward(direction: Option<&str>) -> bool {
+    direction.is_some_and(|d| d.eq_ignore_ascii_case("forward"))
+}
+
+fn compile(re: &str) -> Result<Regex, LokiError> {
+    Regex::new(re).map_err(|e| invalid_query(&format!("invalid regex '{re}': {e}")))
+}
+
+/// Fails queries evaluated outside of SQL once they scanned the max number of
+/// rows, returning what was found so far would give wrong results.
+fn check_scan_limit(scanned: i64) -> Result<(), LokiError> {
+    let max = get_config().limit.logql_max_scan_rows;
+    if max > 0 && scanned >= max {
+        return Err(LokiError::LimitExceeded {
+            message: format!(
+                "the query scanned {scanned} rows, more than the limit of {max}, narrow the stream selector or time range"
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn invalid_query(message: &str) -> LokiError {
+    LokiError::InvalidQuery {
+        message: message.to_string(),
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_json_line() {
+        let mut l = labels(&[("level", "info")]);
+        parse_json_line(
+            r#"{"level":"error","http":{"status":500,"path":"/api"},"tags":["a"]}"#,
+            &mut l,
+        );
+        assert_eq!(
+            l,
+            labels(&[
+                ("level", "info"),
+                ("level_extracted", "error"),
+                ("http_status", "500"),
+                ("http_path", "/api"),
+            ])
+        );
+
+        let mut l = Labels::new();
+        parse_json_line("not json", &mut l);
+        assert_eq!(l, labels(&[("__error__", "JSONParserErr")]));
+    }
+
+    #[test]
+    fn test_parse_logfmt_line() {
+        let mut l = Labels::new();
+        parse_logfmt_line(
+            r#"level=warn msg="disk \"full\"" duration=1.5s flag trace-id=abc"#,
+            &mut l,
+        );
+        assert_eq!(
+            l,
+            labels(&[
+                ("level", "warn"),
+                ("msg", r#"disk "full""#),
+                ("duration", "1.5s"),
+                ("flag", ""),
+                ("trace_id", "abc"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let LogQLExpr::Log(selector) =
+            parser::parse(r#"{app="api"} |= "GET" | logfmt | status >= 500 | path =~ "/api.*""#)
+                .unwrap()
+        else {
+            unreachable!()
+        };
+        // the line filter is pushed down when the stream has a message column
+        let pipeline = Pipeline::new(&selector.pipeline, true).unwrap();
+        assert_eq!(pipeline.stages.len(), 3);
+        let mut l = Labels::new();
+        assert!(pipeline.process("POST status=503 path=/api/v1", &mut l));
+        assert!(!pipeline.process("GET status=200 path=/api/v1", &mut Labels::new()));
+        assert!(!pipeline.process("GET status=500 path=/web", &mut Labels::new()));
+
+        let pipeline = Pipeline::new(&selector.pipeline, false).unwrap();
+        assert_eq!(pipeline.stages.len(), 4);
+        assert!(!pipeline.process("POST status=503 path=/api/v1", &mut Labels::new()));
+        assert!(pipeline.process("GET status=503 path=/api/v1", &mut Labels::new()));
+    }
+
+    #[test]
+    fn test_eval_range() {
+        let sec = 1_000_000;
+        // step 30s, range 60s: buckets of 30s starting at `start - range + 1`
+        let (start, end, step, range) = (1000 * sec, 1090 * sec, 30 * sec, 60 * sec);
+        let bucket = gcd(step, range);
+        assert_eq!(bucket, 30 * sec);
+        let offset = start - range + 1;
+        let mut buckets = Buckets::new();
+        for ts in [941 * sec, 1000 * sec, 1001 * sec, 1075 * sec] {
+            let entry = buckets.entry((ts - offset) / bucket).or_default();
+            entry.0 += 1.0;
+            entry.1 += 10.0;
+        }
+        let count = eval_range(
+            &buckets,
+            RangeFunc::CountOverTime,
+            start,
+            end,
+            step,
+            range,
+            bucket,
+        );
+        // (940, 1000] -> 2, (970, 1030] -> 2, (1000, 1060] -> 1, (1030, 1090] -> 1
+        assert_eq!(
+            count.into_iter().collect::<Vec<_>>(),
+            vec![
+                (1000 * sec, 2.0),
+                (1030 * sec, 2.0),
+                (1060 * sec, 1.0),
+                (1090 * sec, 1.0)
+            ]
+        );
+        let rate = eval_range(
+            &buckets,
+            RangeFunc::BytesRate,
+            start,
+            end,
+            step,
+            range,
+            bucket,
+        );
+        assert_eq!(rate.get(&(1000 * sec)), Some(&(20.0 / 60.0)));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let series = vec![
+            (
+                labels(&[("app", "api"), ("level", "error")]),
+                BTreeMap::from([(1, 2.0), (2, 4.0)]),
+            ),
+            (
+                labels(&[("app", "api"), ("level", "info")]),
+                BTreeMap::from([(1, 6.0)]),
+            ),
+            (
+                labels(&[("app", "web"), ("level", "info")]),
+                BTreeMap::from([(1, 1.0)]),
+            ),
+        ];
+        let by_app = Grouping::By(vec!["app".to_string()]);
+        let mut sum = aggregate(VectorOp::Sum, Some(&by_app), series.clone());
+        sum.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            sum,
+            vec![
+                (
+                    labels(&[("app", "api")]),
+                    BTreeMap::from([(1, 8.0), (2, 4.0)])
+                ),
+                (labels(&[("app", "web")]), BTreeMap::from([(1, 1.0)])),
+            ]
+        );
+        let without_app = Grouping::Without(vec!["app".to_string()]);
+        let mut max = aggregate(VectorOp::Max, Some(&without_app), series.clone());
+        max.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            max[1],
+            (labels(&[("level", "info")]), BTreeMap::from([(1, 6.0)]))
+        );
+        let count = aggregate(VectorOp::Count, None, series.clone());
+        assert_eq!(
+            count,
+            vec![(Labels::new(), BTreeMap::from([(1, 3.0), (2, 1.0)]))]
+        );
+        let avg = aggregate(VectorOp::Avg, None, series);
+        assert_eq!(avg[0].1.get(&1), Some(&3.0));
+    }
+
+    #[test]
+    fn test_row_to_entry() {
+        let row = json::json!({
+            "_timestamp": 1700000000000000i64,
+            "_o2_id": 1,
+            "message": "hello",
+            "app": "api",
+            "code": 200,
+            "empty": "",
+            "obj": {"a": 1}
+        });
+        let (ts, l, line) = row_to_entry(row).unwrap();
+        assert_eq!(ts, 1700000000000000);
+        assert_eq!(line, "hello");
+        assert_eq!(l, labels(&[("app", "api"), ("code", "200")]));
+
+        let row = json::json!({"_timestamp": 1, "app": "api"});
+        let (_, _, line) = row_to_entry(row).unwrap();
+        assert_eq!(line, r#"{"app":"api"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_query_instant_rejects_log_queries() {
+        let req = LokiQueryRequest {
+            query: Some(r#"{app="api"} |= "error""#.to_string()),
+            ..Default::default()
+        };
+        let err = query_instant("trace", "org", None, &req).await.unwrap_err();
+        assert!(matches!(err, LokiError::InvalidQuery { .. }));
+    }
+
+    #[test]
+    fn test_parse_time_and_step() {
+        assert_eq!(parse_time(None, 5).unwrap(), 5);
+        assert_eq!(
+            parse_time(Some("1700000000000000000"), 0).unwrap(),
+            1700000000000000
+        );
+        assert_eq!(
+            parse_time(Some("1700000000.5"), 0).unwrap(),
+            1700000000500000
+        );
+        assert!(parse_time(Some("yesterday"), 0).is_err());
+
+        let hour = 3_600_000_000;
+        assert_eq!(parse_step(Some("15s"), 0, hour).unwrap(), 15_000_000);
+        assert_eq!(parse_step(Some("0.5"), 0, hour).unwrap(), 500_000);
+        assert_eq!(parse_step(None, 0, hour).unwrap(), 14_000_000);
+        assert!(parse_step(Some("0"), 0, hour).is_err());
+        assert!(parse_step(Some("0.1"), 0, hour).is_err());
+    }
+}
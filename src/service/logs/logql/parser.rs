@@ -0,0 +1,558 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parser for the supported LogQL subset:
+//!
+//! - stream selectors: `{app="api", env=~"prod|staging"}`
+//! - line filters: `|= "text"`, `!= "text"`, `|~ "regex"`, `!~ "regex"`
+//! - parsers: `| json`, `| logfmt`
+//! - label filters: `| status >= 500`, `| level = "error"`
+//! - range aggregations: `count_over_time`, `rate`, `bytes_over_time`, `bytes_rate`
+//! - vector aggregations: `sum`, `avg`, `min`, `max`, `count` with `by` / `without`
+
+use config::utils::time::parse_milliseconds;
+
+use crate::common::meta::loki::LokiError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LogQLExpr {
+    Log(LogSelector),
+    Metric(MetricExpr),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogSelector {
+    pub matchers: Vec<LabelMatcher>,
+    pub pipeline: Vec<PipelineStage>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelMatcher {
+    pub name: String,
+    pub op: MatchOp,
+    pub value: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchOp {
+    Equal,
+    NotEqual,
+    Regex,
+    NotRegex,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PipelineStage {
+    LineFilter(LineFilterOp, String),
+    Parser(ParserKind),
+    LabelFilter(LabelFilter),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineFilterOp {
+    Contains,
+    NotContains,
+    Regex,
+    NotRegex,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParserKind {
+    Json,
+    Logfmt,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelFilter {
+    pub name: String,
+    pub op: LabelFilterOp,
+    pub value: LabelFilterValue,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LabelFilterOp {
+    Equal,
+    NotEqual,
+    Regex,
+    NotRegex,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LabelFilterValue {
+    String(String),
+    Number(f64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetricExpr {
+    Range {
+        func: RangeFunc,
+        selector: LogSelector,
+        /// range in microseconds
+        range: i64,
+    },
+    Vector {
+        op: VectorOp,
+        grouping: Option<Grouping>,
+        expr: Box<MetricExpr>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeFunc {
+    CountOverTime,
+    Rate,
+    BytesOverTime,
+    BytesRate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorOp {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Count,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Grouping {
+    By(Vec<String>),
+    Without(Vec<String>),
+}
+
+impl MetricExpr {
+    /// Returns the log selector and range of the innermost range aggregation.
+    pub fn range_selector(&self) -> (&LogSelector, RangeFunc, i64) {
+        match self {
+            MetricExpr::Range {
+                func,
+                selector,
+                range,
+            } => (selector, *func, *range),
+            MetricExpr::Vector { expr, .. } => expr.range_selector(),
+        }
+    }
+
+    /// Returns all labels referenced by `by` clauses of vector aggregations.
+    pub fn grouping_labels(&self) -> Vec<String> {
+        match self {
+            MetricExpr::Range { .. } => vec![],
+            MetricExpr::Vector { grouping, expr, .. } => {
+                let mut labels = expr.grouping_labels();
+                if let Some(Grouping::By(by)) = grouping {
+                    labels.extend(by.iter().cloned());
+                }
+                labels
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<LogQLExpr, LokiError> {
+    let mut parser = Parser { input, pos: 0 };
+    parser.skip_ws();
+    let expr = if parser.peek() == Some('{') {
+        LogQLExpr::Log(parser.log_selector()?)
+    } else {
+        LogQLExpr::Metric(parser.metric_expr()?)
+    };
+    parser.skip_ws();
+    if parser.pos < input.len() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(expr)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    fn error(&self, message: &str) -> LokiError {
+        LokiError::InvalidQuery {
+            message: format!("{message} at position {}", self.pos),
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), LokiError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{token}'")))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, LokiError> {
+        self.skip_ws();
+        let len = self
+            .rest()
+            .char_indices()
+            .take_while(|(i, c)| {
+                c.is_ascii_alphabetic()
+                    || *c == '_'
+                    || (*i > 0 && (c.is_ascii_digit() || *c == '.'))
+            })
+            .count();
+        if len == 0 {
+            return Err(self.error("expected identifier"));
+        }
+        let ident = self.rest()[..len].to_string();
+        self.pos += len;
+        Ok(ident)
+    }
+
+    fn string(&mut self) -> Result<String, LokiError> {
+        self.skip_ws();
+        let Some(quote @ ('"' | '`')) = self.peek() else {
+            return Err(self.error("expected string"));
+        };
+        self.pos += 1;
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            if c == quote {
+                self.pos += i + 1;
+                return Ok(value);
+            }
+            if c == '\\' && quote == '"' {
+                match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, c)) => value.push(c),
+                    None => break,
+                }
+            } else {
+                value.push(c);
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn number(&mut self) -> Result<f64, LokiError> {
+        self.skip_ws();
+        let len = self
+            .rest()
+            .char_indices()
+            .take_while(|(i, c)| {
+                c.is_ascii_digit() || *c == '.' || *c == 'e' || (*i == 0 && *c == '-')
+            })
+            .count();
+        let value = self.rest()[..len]
+            .parse::<f64>()
+            .map_err(|_| self.error("expected number"))?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    fn log_selector(&mut self) -> Result<LogSelector, LokiError> {
+        self.expect("{")?;
+        let mut matchers = Vec::new();
+        if !self.eat("}") {
+            loop {
+                let name = self.ident()?;
+                let op = if self.eat("=~") {
+                    MatchOp::Regex
+                } else if self.eat("!~") {
+                    MatchOp::NotRegex
+                } else if self.eat("!=") {
+                    MatchOp::NotEqual
+                } else if self.eat("=") {
+                    MatchOp::Equal
+                } else {
+                    return Err(self.error("expected label matcher operator"));
+                };
+                let value = self.string()?;
+                matchers.push(LabelMatcher { name, op, value });
+                if self.eat("}") {
+                    break;
+                }
+                self.expect(",")?;
+            }
+        }
+        if matchers.is_empty() {
+            return Err(self.error("queries require at least one label matcher"));
+        }
+
+        let mut pipeline = Vec::new();
+        loop {
+            let stage = if self.eat("|=") {
+                PipelineStage::LineFilter(LineFilterOp::Contains, self.string()?)
+            } else if self.eat("!=") {
+                PipelineStage::LineFilter(LineFilterOp::NotContains, self.string()?)
+            } else if self.eat("|~") {
+                PipelineStage::LineFilter(LineFilterOp::Regex, self.string()?)
+            } else if self.eat("!~") {
+                PipelineStage::LineFilter(LineFilterOp::NotRegex, self.string()?)
+            } else if self.eat("|") {
+                match self.ident()?.as_str() {
+                    "json" => PipelineStage::Parser(ParserKind::Json),
+                    "logfmt" => PipelineStage::Parser(ParserKind::Logfmt),
+                    name => PipelineStage::LabelFilter(self.label_filter(name.to_string())?),
+                }
+            } else {
+                break;
+            };
+            pipeline.push(stage);
+        }
+        Ok(LogSelector { matchers, pipeline })
+    }
+
+    fn label_filter(&mut self, name: String) -> Result<LabelFilter, LokiError> {
+        let op = if self.eat("=~") {
+            LabelFilterOp::Regex
+        } else if self.eat("!~") {
+            LabelFilterOp::NotRegex
+        } else if self.eat("!=") {
+            LabelFilterOp::NotEqual
+        } else if self.eat(">=") {
+            LabelFilterOp::GreaterEqual
+        } else if self.eat("<=") {
+            LabelFilterOp::LessEqual
+        } else if self.eat("==") || self.eat("=") {
+            LabelFilterOp::Equal
+        } else if self.eat(">") {
+            LabelFilterOp::Greater
+        } else if self.eat("<") {
+            LabelFilterOp::Less
+        } else {
+            return Err(self.error("expected label filter operator"));
+        };
+        self.skip_ws();
+        let value = if matches!(self.peek(), Some('"' | '`')) {
+            LabelFilterValue::String(self.string()?)
+        } else {
+            LabelFilterValue::Number(self.number()?)
+        };
+        if matches!(op, LabelFilterOp::Regex | LabelFilterOp::NotRegex)
+            && !matches!(value, LabelFilterValue::String(_))
+        {
+            return Err(self.error("regex label filter requires a string"));
+        }
+        Ok(LabelFilter { name, op, value })
+    }
+
+    fn metric_expr(&mut self) -> Result<MetricExpr, LokiError> {
+        let name = self.ident()?;
+        let func = match name.as_str() {
+            "count_over_time" => Some(RangeFunc::CountOverTime),
+            "rate" => Some(RangeFunc::Rate),
+            "bytes_over_time" => Some(RangeFunc::BytesOverTime),
+            "bytes_rate" => Some(RangeFunc::BytesRate),
+            _ => None,
+        };
+        if let Some(func) = func {
+            self.expect("(")?;
+            let selector = self.log_selector()?;
+            self.expect("[")?;
+            let Some(end) = self.rest().find(']') else {
+                return Err(self.error("expected ']'"));
+            };
+            let range = parse_milliseconds(self.rest()[..end].trim())
+                .map_err(|e| self.error(&format!("invalid range: {e}")))?;
+            if range == 0 {
+                return Err(self.error("range must be greater than 0"));
+            }
+            self.pos += end + 1;
+            self.expect(")")?;
+            return Ok(MetricExpr::Range {
+                func,
+                selector,
+                range: range as i64 * 1_000,
+            });
+        }
+
+        let op = match name.as_str() {
+            "sum" => VectorOp::Sum,
+            "avg" => VectorOp::Avg,
+            "min" => VectorOp::Min,
+            "max" => VectorOp::Max,
+            "count" => VectorOp::Count,
+            _ => return Err(self.error(&format!("unsupported function '{name}'"))),
+        };
+        let mut grouping = self.grouping()?;
+        self.expect("(")?;
+        let expr = self.metric_expr()?;
+        self.expect(")")?;
+        if grouping.is_none() {
+            grouping = self.grouping()?;
+        }
+        Ok(MetricExpr::Vector {
+            op,
+            grouping,
+            expr: Box::new(expr),
+        })
+    }
+
+    fn grouping(&mut self) -> Result<Option<Grouping>, LokiError> {
+        let by = if self.eat("by") {
+            true
+        } else if self.eat("without") {
+            false
+        } else {
+            return Ok(None);
+        };
+        self.expect("(")?;
+        let mut labels = Vec::new();
+        if !self.eat(")") {
+            loop {
+                labels.push(self.ident()?);
+                if self.eat(")") {
+                    break;
+                }
+                self.expect(",")?;
+            }
+        }
+        Ok(Some(if by {
+            Grouping::By(labels)
+        } else {
+            Grouping::Without(labels)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector(input: &str) -> LogSelector {
+        match parse(input).unwrap() {
+            LogQLExpr::Log(selector) => selector,
+            other => panic!("expected log selector, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_stream_selector() {
+        let sel = selector(r#"{app="api", env=~"prod|staging", pod!="x", ns!~`kube-.*`}"#);
+        assert_eq!(
+            sel.matchers,
+            vec![
+                LabelMatcher {
+                    name: "app".to_string(),
+                    op: MatchOp::Equal,
+                    value: "api".to_string()
+                },
+                LabelMatcher {
+                    name: "env".to_string(),
+                    op: MatchOp::Regex,
+                    value: "prod|staging".to_string()
+                },
+                LabelMatcher {
+                    name: "pod".to_string(),
+                    op: MatchOp::NotEqual,
+                    value: "x".to_string()
+                },
+                LabelMatcher {
+                    name: "ns".to_string(),
+                    op: MatchOp::NotRegex,
+                    value: "kube-.*".to_string()
+                },
+            ]
+        );
+        assert!(sel.pipeline.is_empty());
+    }
+
+    #[test]
+    fn test_parse_pipeline() {
+        let sel = selector(
+            r#"{app="api"} |= "error" != "timeout" |~ "status=5\\d\\d" | json | status >= 500 | level="err""#,
+        );
+        assert_eq!(
+            sel.pipeline,
+            vec![
+                PipelineStage::LineFilter(LineFilterOp::Contains, "error".to_string()),
+                PipelineStage::LineFilter(LineFilterOp::NotContains, "timeout".to_string()),
+                PipelineStage::LineFilter(LineFilterOp::Regex, "status=5\\d\\d".to_string()),
+                PipelineStage::Parser(ParserKind::Json),
+                PipelineStage::LabelFilter(LabelFilter {
+                    name: "status".to_string(),
+                    op: LabelFilterOp::GreaterEqual,
+                    value: LabelFilterValue::Number(500.0),
+                }),
+                PipelineStage::LabelFilter(LabelFilter {
+                    name: "level".to_string(),
+                    op: LabelFilterOp::Equal,
+                    value: LabelFilterValue::String("err".to_string()),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_metric_queries() {
+        let expr = parse(r#"sum by (level) (rate({app="api"} | logfmt [5m]))"#).unwrap();
+        let LogQLExpr::Metric(metric) = expr else {
+            panic!("expected metric query");
+        };
+        let (sel, func, range) = metric.range_selector();
+        assert_eq!(func, RangeFunc::Rate);
+        assert_eq!(range, 300_000_000);
+        assert_eq!(
+            sel.pipeline,
+            vec![PipelineStage::Parser(ParserKind::Logfmt)]
+        );
+        assert_eq!(metric.grouping_labels(), vec!["level".to_string()]);
+
+        // grouping after the expression
+        let expr = parse(r#"count(count_over_time({app="api"}[1m])) without (pod)"#).unwrap();
+        let LogQLExpr::Metric(MetricExpr::Vector { op, grouping, .. }) = expr else {
+            panic!("expected vector aggregation");
+        };
+        assert_eq!(op, VectorOp::Count);
+        assert_eq!(grouping, Some(Grouping::Without(vec!["pod".to_string()])));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("{}").is_err());
+        assert!(parse(r#"{app="api""#).is_err());
+        assert!(parse(r#"{app="api"} |= error"#).is_err());
+        assert!(parse(r#"topk(3, rate({app="api"}[1m]))"#).is_err());
+        assert!(parse(r#"rate({app="api"})"#).is_err());
+        assert!(parse(r#"rate({app="api"}[0s])"#).is_err());
+        assert!(parse(r#"{app="api"} extra"#).is_err());
+        assert!(parse(r#"{app="api"} | status =~ 5"#).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Translation of LogQL selectors into SQL over log streams.
+
+use std::collections::HashSet;
+
+use config::{
+    DEFAULT_STREAM_NAME, MESSAGE_COL_NAME, STREAM_NAME_LABEL, STREAM_NAME_LABEL_OLD,
+    TIMESTAMP_COL_NAME, utils::schema::format_stream_name,
+};
+
+use super::parser::{
+    LabelFilter, LabelFilterOp, LabelFilterValue, LabelMatcher, LineFilterOp, LogSelector, MatchOp,
+    PipelineStage,
+};
+use crate::common::meta::loki::LokiError;
+
+pub(crate) const BUCKET_COL: &str = "zo_bucket";
+pub(crate) const COUNT_COL: &str = "zo_count";
+pub(crate) const BYTES_COL: &str = "zo_bytes";
+
+/// Resolves the stream a selector reads from.
+///
+/// Like the push API, the stream is chosen by the `o2_stream_name` (or
+/// `stream_name`) label and falls back to the default stream.
+pub fn resolve_stream(matchers: &[LabelMatcher]) -> Result<String, LokiError> {
+    let mut stream = None;
+    for m in matchers.iter().filter(|m| is_stream_label(&m.name)) {
+        if m.op != MatchOp::Equal {
+            return Err(LokiError::InvalidQuery {
+                message: format!("label '{}' only supports the '=' matcher", m.name),
+            });
+        }
+        stream = Some(format_stream_name(m.value.clone()));
+    }
+    Ok(stream.unwrap_or_else(|| DEFAULT_STREAM_NAME.to_string()))
+}
+
+pub fn is_stream_label(name: &str) -> bool {
+    name == STREAM_NAME_LABEL || name == STREAM_NAME_LABEL_OLD
+}
+
+/// Splits a pipeline into the stages pushed down as SQL predicates and the
+/// stages evaluated on the returned rows. Line filters never depend on parsed
+/// labels, so they are always pushed down; label filters are only pushed down
+/// until the first parser stage.
+pub fn split_pipeline(pipeline: &[PipelineStage]) -> (Vec<&PipelineStage>, Vec<&PipelineStage>) {
+    let mut pushdown = Vec::new();
+    let mut post = Vec::new();
+    let mut parsed = false;
+    for stage in pipeline {
+        match stage {
+            PipelineStage::LineFilter(..) => pushdown.push(stage),
+            PipelineStage::Parser(_) => {
+                parsed = true;
+                post.push(stage);
+            }
+            PipelineStage::LabelFilter(_) if parsed => post.push(stage),
+            PipelineStage::LabelFilter(_) => pushdown.push(stage),
+        }
+    }
+    (pushdown, post)
+}
+
+/// Builds the WHERE conditions of a selector, without the time range which is
+/// passed to the search request.
+///
+/// Matchers and pushed down label filters on labels missing from the stream
+/// schema are evaluated against an empty value here, as SQL would fail on the
+/// unknown column: conditions that always hold are dropped and `None` is
+/// returned when the selector can not match any row.
+pub fn where_conditions(selector: &LogSelector, fields: &HashSet<String>) -> Option<Vec<String>> {
+    let mut conds = Vec::new();
+    for m in selector
+        .matchers
+        .iter()
+        .filter(|m| !is_stream_label(&m.name))
+    {
+        if fields.contains(&m.name) {
+            conds.push(matcher_to_sql(m));
+        } else if !matcher_matches_missing(m) {
+            return None;
+        }
+    }
+    let has_message = fields.contains(MESSAGE_COL_NAME);
+    let (pushdown, _) = split_pipeline(&selector.pipeline);
+    for stage in pushdown {
+        match stage {
+            PipelineStage::LineFilter(op, value) if has_message => {
+                conds.push(line_filter_to_sql(*op, value));
+            }
+            PipelineStage::LabelFilter(filter) if fields.contains(&filter.name) => {
+                conds.push(label_filter_to_sql(filter))
+            }
+            PipelineStage::LabelFilter(filter) if !label_filter_matches_missing(filter) => {
+                return None;
+            }
+            _ => {}
+        }
+    }
+    Some(conds)
+}
+
+/// Whether a matcher holds for a label that is not set, i.e. an empty value.
+fn matcher_matches_missing(m: &LabelMatcher) -> bool {
+    match m.op {
+        MatchOp::Equal => m.value.is_empty(),
+        MatchOp::NotEqual => !m.value.is_empty(),
+        MatchOp::Regex => regex_matches_empty(&m.value),
+        MatchOp::NotRegex => !regex_matches_empty(&m.value),
+    }
+}
+
+/// Whether a label filter holds for a label that is not set. Like in Loki,
+/// numeric comparisons never hold on a missing label.
+fn label_filter_matches_missing(f: &LabelFilter) -> bool {
+    let LabelFilterValue::String(v) = &f.value else {
+        return false;
+    };
+    match f.op {
+        LabelFilterOp::Equal => v.is_empty(),
+        LabelFilterOp::NotEqual => !v.is_empty(),
+        LabelFilterOp::Regex => regex_matches_empty(v),
+        LabelFilterOp::NotRegex => !regex_matches_empty(v),
+        LabelFilterOp::Greater => "" > v.as_str(),
+        LabelFilterOp::GreaterEqual => "" >= v.as_str(),
+        LabelFilterOp::Less => "" < v.as_str(),
+        LabelFilterOp::LessEqual => "" <= v.as_str(),
+    }
+}
+
+fn regex_matches_empty(re: &str) -> bool {
+    regex::Regex::new(&anchored(re)).is_ok_and(|re| re.is_match(""))
+}
+
+pub fn build_logs_sql(stream: &str, conds: &[String], ascending: bool) -> String {
+    format!(
+        "SELECT * FROM {}{} ORDER BY {} {}",
+        quote_ident(stream),
+        where_clause(conds),
+        quote_ident(TIMESTAMP_COL_NAME),
+        if ascending { "ASC" } else { "DESC" }
+    )
+}
+
+/// Builds an aggregation counting entries and bytes per time bucket and label
+/// set. Bucket `n` covers `[offset + n * bucket, offset + (n + 1) * bucket)`.
+pub fn build_metric_sql(
+    stream: &str,
+    conds: &[String],
+    group_by: &[String],
+    offset: i64,
+    bucket: i64,
+    has_message: bool,
+) -> String {
+    let bucket_expr = format!(
+        "(({} - {offset}) / {bucket})",
+        quote_ident(TIMESTAMP_COL_NAME)
+    );
+    let group_cols = group_by.iter().map(|c| quote_ident(c)).collect::<Vec<_>>();
+    let mut select = vec![format!("{bucket_expr} AS {BUCKET_COL}")];
+    select.extend(group_cols.iter().cloned());
+    select.push(format!("COUNT(*) AS {COUNT_COL}"));
+    if has_message {
+        select.push(format!(
+            "SUM(OCTET_LENGTH({})) AS {BYTES_COL}",
+            quote_ident(MESSAGE_COL_NAME)
+        ));
+    }
+    let mut group = vec![bucket_expr];
+    group.extend(group_cols);
+    format!(
+        "SELECT {} FROM {}{} GROUP BY {}",
+        select.join(", "),
+        quote_ident(stream),
+        where_clause(conds),
+        group.join(", ")
+    )
+}
+
+pub fn build_distinct_sql(stream: &str, conds: &[String], columns: &[String]) -> String {
+    let columns = columns
+        .iter()
+        .map(|c| quote_ident(c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "SELECT {columns} FROM {}{} GROUP BY {columns} ORDER BY {columns}",
+        quote_ident(stream),
+        where_clause(conds),
+    )
+}
+
+fn where_clause(conds: &[String]) -> String {
+    if conds.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conds.join(" AND "))
+    }
+}
+
+fn matcher_to_sql(m: &LabelMatcher) -> String {
+    let col = quote_ident(&m.name);
+    match m.op {
+        // an empty value matches series without the label, like in Prometheus
+        MatchOp::Equal if m.value.is_empty() => format!("({col} IS NULL OR {col} = '')"),
+        MatchOp::Equal => format!("{col} = {}", quote_str(&m.value)),
+        MatchOp::NotEqual if m.value.is_empty() => format!("({col} IS NOT NULL AND {col} != '')"),
+        MatchOp::NotEqual => format!("({col} IS NULL OR {col} != {})", quote_str(&m.value)),
+        MatchOp::Regex => format!("re_match({col}, {})", quote_str(&anchored(&m.value))),
+        MatchOp::NotRegex => format!("re_not_match({col}, {})", quote_str(&anchored(&m.value))),
+    }
+}
+
+fn line_filter_to_sql(op: LineFilterOp, value: &str) -> String {
+    let col = quote_ident(MESSAGE_COL_NAME);
+    let value = quote_str(value);
+    match op {
+        LineFilterOp::Contains => format!("str_match({col}, {value})"),
+        LineFilterOp::NotContains => format!("NOT str_match({col}, {value})"),
+        LineFilterOp::Regex => format!("re_match({col}, {value})"),
+        LineFilterOp::NotRegex => format!("re_not_match({col}, {value})"),
+    }
+}
+
+fn label_filter_to_sql(f: &LabelFilter) -> String {
+    let col = quote_ident(&f.name);
+    match &f.value {
+        LabelFilterValue::String(v) => match f.op {
+            LabelFilterOp::Regex => format!("re_match({col}, {})", quote_str(&anchored(v))),
+            LabelFilterOp::NotRegex => {
+                format!("re_not_match({col}, {})", quote_str(&anchored(v)))
+            }
+            op => format!("{col} {} {}", comparison_op(op), quote_str(v)),
+        },
+        LabelFilterValue::Number(v) => {
+            format!("TRY_CAST({col} AS DOUBLE) {} {v}", comparison_op(f.op))
+        }
+    }
+}
+
+fn comparison_op(op: LabelFilterOp) -> &'static str {
+    match op {
+        LabelFilterOp::Equal | LabelFilterOp::Regex => "=",
+        LabelFilterOp::NotEqual | LabelFilterOp::NotRegex => "!=",
+        LabelFilterOp::Greater => ">",
+        LabelFilterOp::GreaterEqual => ">=",
+        LabelFilterOp::Less => "<",
+        LabelFilterOp::LessEqual => "<=",
+    }
+}
+
+/// Label matchers are fully anchored, line filters are not.
+fn anchored(re: &str) -> String {
+    format!("^(?:{re})$")
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_str(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::logs::logql::parser::{LogQLExpr, parse};
+
+    fn selector(input: &str) -> LogSelector {
+        match parse(input).unwrap() {
+            LogQLExpr::Log(selector) => selector,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_stream() {
+        let sel = selector(r#"{o2_stream_name="app-logs", app="api"}"#);
+        assert_eq!(resolve_stream(&sel.matchers).unwrap(), "app_logs");
+        let sel = selector(r#"{app="api"}"#);
+        assert_eq!(resolve_stream(&sel.matchers).unwrap(), DEFAULT_STREAM_NAME);
+        let sel = selector(r#"{stream_name=~"a|b"}"#);
+        assert!(resolve_stream(&sel.matchers).is_err());
+    }
+
+    fn fields(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_where_conditions() {
+        let sel = selector(
+            r#"{o2_stream_name="app", app="api", env=~"prod.*", pod!="", x!="it's"} |= "error" != "timeout" | code >= 500 | json | level="err""#,
+        );
+        let mut cols = fields(&["app", "env", "pod", "x", "code", "level", MESSAGE_COL_NAME]);
+        assert_eq!(
+            where_conditions(&sel, &cols).unwrap(),
+            vec![
+                r#""app" = 'api'"#,
+                r#"re_match("env", '^(?:prod.*)$')"#,
+                r#"("pod" IS NOT NULL AND "pod" != '')"#,
+                r#"("x" IS NULL OR "x" != 'it''s')"#,
+                r#"str_match("message", 'error')"#,
+                r#"NOT str_match("message", 'timeout')"#,
+                r#"TRY_CAST("code" AS DOUBLE) >= 500"#,
+            ]
+        );
+        // line filters can not be pushed down without a message column
+        cols.remove(MESSAGE_COL_NAME);
+        assert_eq!(where_conditions(&sel, &cols).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_where_conditions_missing_columns() {
+        let cols = fields(&["app"]);
+        // conditions holding for an unset label are dropped
+        let sel = selector(r#"{app="api", pod!="web", env=~"prod|", x="", y!~"a.*"}"#);
+        assert_eq!(
+            where_conditions(&sel, &cols).unwrap(),
+            vec![r#""app" = 'api'"#]
+        );
+        let sel = selector(r#"{app="api"} | level!="err""#);
+        assert_eq!(where_conditions(&sel, &cols).unwrap().len(), 1);
+        // others can not match any row
+        for q in [
+            r#"{app="api", pod="web"}"#,
+            r#"{app="api", env=~"prod.*"}"#,
+            r#"{app="api", pod!=""}"#,
+            r#"{app="api", env!~"prod|"}"#,
+            r#"{app="api"} | code >= 500"#,
+            r#"{app="api"} | level="err""#,
+        ] {
+            assert_eq!(where_conditions(&selector(q), &cols), None, "{q}");
+        }
+    }
+
+    #[test]
+    fn test_build_sql() {
+        let conds = vec![r#""app" = 'api'"#.to_string()];
+        assert_eq!(
+            build_logs_sql("app", &conds, false),
+            r#"SELECT * FROM "app" WHERE "app" = 'api' ORDER BY "_timestamp" DESC"#
+        );
+        assert_eq!(
+            build_metric_sql("app", &conds, &["level".to_string()], 100, 60, true),
+            r#"SELECT (("_timestamp" - 100) / 60) AS zo_bucket, "level", COUNT(*) AS zo_count, SUM(OCTET_LENGTH("message")) AS zo_bytes FROM "app" WHERE "app" = 'api' GROUP BY (("_timestamp" - 100) / 60), "level""#
+        );
+        assert_eq!(
+            build_distinct_sql("app", &[], &["level".to_string()]),
+            r#"SELECT "level" FROM "app" GROUP BY "level" ORDER BY "level""#
+        );
+    }
+}
@@ -58,8 +58,10 @@ use crate::{
 pub mod bulk;
 pub mod hec;
 pub mod ingest;
+pub mod logql;
 pub mod loki;
 pub mod otlp;
+pub mod syslog;
 
 static BULK_OPERATORS: [&str; 3] = ["create", "index", "update"];
 
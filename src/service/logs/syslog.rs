@@ -0,0 +1,548 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Syslog message parsing for the native syslog receiver.
+//!
+//! Supports RFC 5424 and RFC 3164 (BSD) messages, transported either as
+//! octet-counted or newline-delimited frames (RFC 6587).
+
+use std::net::IpAddr;
+
+use bytes::BytesMut;
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, Utc};
+use config::{
+    MESSAGE_COL_NAME, TIMESTAMP_COL_NAME,
+    utils::{json, time::now_micros},
+};
+use infra::errors::Result;
+
+use crate::common::meta::ingestion::{
+    IngestUser, IngestionRequest, IngestionResponse, IngestionValueType, SystemJobType,
+};
+
+const NILVALUE: &str = "-";
+const UTF8_BOM: &str = "\u{feff}";
+// max digits of the MSG-LEN header in octet-counting framing
+const MAX_FRAME_LEN_DIGITS: usize = 10;
+
+const FACILITY_NAMES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const SEVERITY_NAMES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// Ingests a batch of parsed syslog records into the given stream.
+pub async fn ingest(
+    thread_id: usize,
+    org_id: &str,
+    stream_name: &str,
+    records: Vec<json::Value>,
+) -> Result<IngestionResponse> {
+    super::ingest::ingest(
+        thread_id,
+        org_id,
+        stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Syslog, records),
+        IngestUser::SystemJob(SystemJobType::Syslog),
+        None,
+        false,
+    )
+    .await
+}
+
+/// Returns the messages of a UDP syslog datagram. A datagram carries a single
+/// message (RFC 5426), whose MSG may span several lines. Relays packing one
+/// message per line are supported when `split_lines` is set.
+pub fn decode_datagram(data: &[u8], split_lines: bool) -> Vec<String> {
+    let data = String::from_utf8_lossy(data);
+    if split_lines {
+        return data
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    let msg = data.trim_end_matches(['\r', '\n', '\0']);
+    if msg.trim().is_empty() {
+        vec![]
+    } else {
+        vec![msg.to_string()]
+    }
+}
+
+/// Decodes the next complete frame from a TCP syslog stream.
+///
+/// Frames are either octet-counted (`MSG-LEN SP SYSLOG-MSG`) or delimited by
+/// a trailing newline. Returns `Ok(None)` when more data is needed.
+pub fn decode_frame(buf: &mut BytesMut, max_size: usize) -> Result<Option<String>, anyhow::Error> {
+    // skip empty lines and padding between frames
+    let skip = buf
+        .iter()
+        .take_while(|b| matches!(b, b'\n' | b'\r' | b'\0' | b' '))
+        .count();
+    let _ = buf.split_to(skip);
+    if buf.is_empty() {
+        return Ok(None);
+    }
+
+    if buf[0].is_ascii_digit() {
+        let Some(sp) = buf
+            .iter()
+            .take(MAX_FRAME_LEN_DIGITS + 1)
+            .position(|b| *b == b' ')
+        else {
+            if buf.len() > MAX_FRAME_LEN_DIGITS {
+                return Err(anyhow::anyhow!("invalid octet-counting frame header"));
+            }
+            return Ok(None);
+        };
+        let len = std::str::from_utf8(&buf[..sp])
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .ok_or_else(|| anyhow::anyhow!("invalid octet-counting frame length"))?;
+        if len > max_size {
+            return Err(anyhow::anyhow!(
+                "syslog frame size {len} exceeds the limit {max_size}"
+            ));
+        }
+        if buf.len() < sp + 1 + len {
+            return Ok(None);
+        }
+        let _ = buf.split_to(sp + 1);
+        let frame = buf.split_to(len);
+        return Ok(Some(
+            String::from_utf8_lossy(&frame)
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+        ));
+    }
+
+    match buf.iter().position(|b| *b == b'\n') {
+        Some(pos) => {
+            let frame = buf.split_to(pos + 1);
+            Ok(Some(
+                String::from_utf8_lossy(&frame)
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string(),
+            ))
+        }
+        None if buf.len() > max_size => Err(anyhow::anyhow!(
+            "syslog frame size exceeds the limit {max_size}"
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Parses a single syslog message into a log record.
+///
+/// Messages without a valid PRI header are kept as-is in the message field,
+/// stamped with the receive time.
+pub fn parse_message(msg: &str, source_ip: Option<IpAddr>) -> json::Value {
+    let now = now_micros();
+    let mut record = match parse_pri(msg) {
+        Some((pri, rest)) => {
+            let mut record = parse_rfc5424(rest).unwrap_or_else(|| parse_rfc3164(rest, Utc::now()));
+            record.insert("priority".to_string(), json::Value::from(pri));
+            record.insert(
+                "facility".to_string(),
+                json::Value::from(FACILITY_NAMES[(pri >> 3) as usize]),
+            );
+            record.insert(
+                "severity".to_string(),
+                json::Value::from(SEVERITY_NAMES[(pri & 7) as usize]),
+            );
+            record
+        }
+        None => {
+            let mut record = json::Map::new();
+            record.insert(MESSAGE_COL_NAME.to_string(), json::Value::from(msg));
+            record
+        }
+    };
+    if !record.contains_key(TIMESTAMP_COL_NAME) {
+        record.insert(TIMESTAMP_COL_NAME.to_string(), json::Value::from(now));
+    }
+    if let Some(ip) = source_ip {
+        record.insert("source_ip".to_string(), json::Value::from(ip.to_string()));
+    }
+    json::Value::Object(record)
+}
+
+fn parse_pri(msg: &str) -> Option<(u8, &str)> {
+    let rest = msg.strip_prefix('<')?;
+    let end = rest.find('>')?;
+    if end == 0 || end > 3 || !rest[..end].bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let pri = rest[..end].parse::<u8>().ok()?;
+    if pri > 191 {
+        return None;
+    }
+    Some((pri, &rest[end + 1..]))
+}
+
+/// `VERSION SP TIMESTAMP SP HOSTNAME SP APP-NAME SP PROCID SP MSGID SP SD [SP MSG]`
+fn parse_rfc5424(rest: &str) -> Option<json::Map<String, json::Value>> {
+    let mut parts = rest.splitn(7, ' ');
+    let version = parts.next()?;
+    if version.is_empty() || version.len() > 2 || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let timestamp = parts.next()?;
+    let ts = if timestamp == NILVALUE {
+        None
+    } else {
+        Some(
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()?
+                .timestamp_micros(),
+        )
+    };
+    let hostname = parts.next()?;
+    let app_name = parts.next()?;
+    let procid = parts.next()?;
+    let msgid = parts.next()?;
+    let (structured_data, msg) = parse_structured_data(parts.next().unwrap_or(NILVALUE))?;
+
+    let mut record = json::Map::new();
+    record.insert(
+        "syslog_version".to_string(),
+        json::Value::from(version.parse::<u8>().ok()?),
+    );
+    if let Some(ts) = ts {
+        record.insert(TIMESTAMP_COL_NAME.to_string(), json::Value::from(ts));
+    }
+    for (key, val) in [
+        ("hostname", hostname),
+        ("app_name", app_name),
+        ("procid", procid),
+        ("msgid", msgid),
+    ] {
+        if val != NILVALUE {
+            record.insert(key.to_string(), json::Value::from(val));
+        }
+    }
+    if let Some(sd) = structured_data {
+        record.insert("structured_data".to_string(), json::Value::Object(sd));
+    }
+    let msg = msg.strip_prefix(UTF8_BOM).unwrap_or(msg);
+    record.insert(MESSAGE_COL_NAME.to_string(), json::Value::from(msg));
+    Some(record)
+}
+
+type StructuredData = json::Map<String, json::Value>;
+
+/// Parses the STRUCTURED-DATA part and returns it together with the remaining
+/// MSG. Each SD-ELEMENT becomes an object keyed by its SD-ID.
+fn parse_structured_data(input: &str) -> Option<(Option<StructuredData>, &str)> {
+    if let Some(rest) = input.strip_prefix(NILVALUE) {
+        if !rest.is_empty() && !rest.starts_with(' ') {
+            return None;
+        }
+        return Some((None, rest.strip_prefix(' ').unwrap_or(rest)));
+    }
+
+    let mut sd = json::Map::new();
+    let mut rest = input;
+    while let Some(element) = rest.strip_prefix('[') {
+        let id_end = element.find([' ', ']'])?;
+        let sd_id = &element[..id_end];
+        if sd_id.is_empty() {
+            return None;
+        }
+        let mut params = json::Map::new();
+        let mut cur = &element[id_end..];
+        loop {
+            if let Some(after) = cur.strip_prefix(']') {
+                cur = after;
+                break;
+            }
+            cur = cur.strip_prefix(' ')?;
+            let eq = cur.find('=')?;
+            let name = &cur[..eq];
+            let (value, after) = parse_param_value(cur[eq + 1..].strip_prefix('"')?)?;
+            params.insert(name.to_string(), json::Value::from(value));
+            cur = after;
+        }
+        sd.insert(sd_id.to_string(), json::Value::Object(params));
+        rest = cur;
+    }
+    if sd.is_empty() || (!rest.is_empty() && !rest.starts_with(' ')) {
+        return None;
+    }
+    Some((Some(sd), rest.strip_prefix(' ').unwrap_or(rest)))
+}
+
+/// Reads a PARAM-VALUE up to the closing quote, unescaping `\"`, `\\` and `\]`.
+fn parse_param_value(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &input[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, n @ ('"' | '\\' | ']'))) => value.push(n),
+                Some((_, n)) => {
+                    value.push('\\');
+                    value.push(n);
+                }
+                None => return None,
+            },
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+/// `TIMESTAMP SP HOSTNAME SP TAG[PID]: MSG`, every part being optional as
+/// real-world BSD syslog senders are rarely strict about the format.
+fn parse_rfc3164(rest: &str, now: DateTime<Utc>) -> json::Map<String, json::Value> {
+    let mut record = json::Map::new();
+    let mut rest = rest.trim_start();
+
+    if let Some((ts, remaining)) = parse_rfc3164_timestamp(rest, now) {
+        record.insert(TIMESTAMP_COL_NAME.to_string(), json::Value::from(ts));
+        rest = remaining.trim_start();
+    }
+
+    // the first token is the hostname unless it already looks like a TAG
+    if let Some((token, remaining)) = rest.split_once(' ')
+        && !token.ends_with(':')
+        && !token.contains('[')
+        && record.contains_key(TIMESTAMP_COL_NAME)
+    {
+        record.insert("hostname".to_string(), json::Value::from(token));
+        rest = remaining.trim_start();
+    }
+
+    if let Some((tag, remaining)) = rest.split_once(' ')
+        && let Some(tag) = tag.strip_suffix(':')
+        && !tag.is_empty()
+    {
+        match tag.strip_suffix(']').and_then(|t| t.split_once('[')) {
+            Some((app_name, procid)) => {
+                record.insert("app_name".to_string(), json::Value::from(app_name));
+                record.insert("procid".to_string(), json::Value::from(procid));
+            }
+            None => {
+                record.insert("app_name".to_string(), json::Value::from(tag));
+            }
+        }
+        rest = remaining;
+    }
+
+    record.insert(MESSAGE_COL_NAME.to_string(), json::Value::from(rest));
+    record
+}
+
+/// Parses a `Mmm dd hh:mm:ss` timestamp, which carries neither year nor
+/// timezone. It is read as UTC in the current year, or the previous year when
+/// that would put it in the future. RFC 3339 timestamps are accepted as well.
+fn parse_rfc3164_timestamp(input: &str, now: DateTime<Utc>) -> Option<(i64, &str)> {
+    if let Some((token, rest)) = input.split_once(' ')
+        && let Ok(ts) = DateTime::parse_from_rfc3339(token)
+    {
+        return Some((ts.timestamp_micros(), rest));
+    }
+
+    let ts = input.get(..15)?;
+    let normalized = format!(
+        "{} {}",
+        now.year(),
+        ts.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    let parsed = NaiveDateTime::parse_from_str(&normalized, "%Y %b %d %H:%M:%S").ok()?;
+    let mut ts = parsed.and_utc();
+    if ts > now + Duration::days(1) {
+        ts = ts.with_year(now.year() - 1)?;
+    }
+    Some((ts.timestamp_micros(), &input[15..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc5424_full() {
+        let msg = r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="Application" eventID="1011"] An application event log entry"#;
+        let record = parse_message(msg, None);
+        assert_eq!(record["priority"], 165);
+        assert_eq!(record["facility"], "local4");
+        assert_eq!(record["severity"], "notice");
+        assert_eq!(record["syslog_version"], 1);
+        assert_eq!(record["hostname"], "mymachine.example.com");
+        assert_eq!(record["app_name"], "evntslog");
+        assert!(record.get("procid").is_none());
+        assert_eq!(record["msgid"], "ID47");
+        assert_eq!(record["structured_data"]["exampleSDID@32473"]["iut"], "3");
+        assert_eq!(
+            record["structured_data"]["exampleSDID@32473"]["eventSource"],
+            "Application"
+        );
+        assert_eq!(record[MESSAGE_COL_NAME], "An application event log entry");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1065910455003000_i64);
+    }
+
+    #[test]
+    fn test_parse_rfc5424_nil_values_and_bom() {
+        let msg = "<34>1 - - su 1234 - - \u{feff}'su root' failed for lonvick on /dev/pts/8";
+        let record = parse_message(msg, None);
+        assert_eq!(record["facility"], "auth");
+        assert_eq!(record["severity"], "crit");
+        assert!(record.get("hostname").is_none());
+        assert_eq!(record["app_name"], "su");
+        assert_eq!(record["procid"], "1234");
+        assert!(record.get("structured_data").is_none());
+        assert_eq!(
+            record[MESSAGE_COL_NAME],
+            "'su root' failed for lonvick on /dev/pts/8"
+        );
+        assert!(record[TIMESTAMP_COL_NAME].as_i64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_parse_rfc5424_multiple_sd_elements_with_escapes() {
+        let msg = r#"<165>1 2003-10-11T22:14:15.003Z host app - - [a@1 x="q\"uote\]"][b@2 y="1"]"#;
+        let record = parse_message(msg, None);
+        assert_eq!(record["structured_data"]["a@1"]["x"], "q\"uote]");
+        assert_eq!(record["structured_data"]["b@2"]["y"], "1");
+        assert_eq!(record[MESSAGE_COL_NAME], "");
+    }
+
+    #[test]
+    fn test_parse_rfc3164() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let record = parse_rfc3164("Oct 11 22:14:15 mymachine su[230]: 'su root' failed", now);
+        assert_eq!(record["hostname"], "mymachine");
+        assert_eq!(record["app_name"], "su");
+        assert_eq!(record["procid"], "230");
+        assert_eq!(record[MESSAGE_COL_NAME], "'su root' failed");
+        // October is after June, so the message belongs to the previous year
+        let expected = Utc.with_ymd_and_hms(2023, 10, 11, 22, 14, 15).unwrap();
+        assert_eq!(record[TIMESTAMP_COL_NAME], expected.timestamp_micros());
+    }
+
+    #[test]
+    fn test_parse_rfc3164_space_padded_day_without_hostname() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let record = parse_rfc3164("Feb  5 17:32:18 sshd: Accepted publickey", now);
+        assert!(record.get("hostname").is_none());
+        assert_eq!(record["app_name"], "sshd");
+        assert_eq!(record[MESSAGE_COL_NAME], "Accepted publickey");
+        let expected = Utc.with_ymd_and_hms(2024, 2, 5, 17, 32, 18).unwrap();
+        assert_eq!(record[TIMESTAMP_COL_NAME], expected.timestamp_micros());
+    }
+
+    #[test]
+    fn test_parse_message_without_pri() {
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let record = parse_message("just some text", Some(ip));
+        assert_eq!(record[MESSAGE_COL_NAME], "just some text");
+        assert_eq!(record["source_ip"], "10.0.0.1");
+        assert!(record.get("priority").is_none());
+    }
+
+    #[test]
+    fn test_parse_pri() {
+        assert_eq!(parse_pri("<0>x"), Some((0, "x")));
+        assert_eq!(parse_pri("<191>x"), Some((191, "x")));
+        assert_eq!(parse_pri("<192>x"), None);
+        assert_eq!(parse_pri("<>x"), None);
+        assert_eq!(parse_pri("<1a>x"), None);
+        assert_eq!(parse_pri("x"), None);
+    }
+
+    #[test]
+    fn test_decode_frame_newline_delimited() {
+        let mut buf = BytesMut::from("<13>first\r\n<13>second\n<13>part");
+        assert_eq!(
+            decode_frame(&mut buf, 1024).unwrap(),
+            Some("<13>first".to_string())
+        );
+        assert_eq!(
+            decode_frame(&mut buf, 1024).unwrap(),
+            Some("<13>second".to_string())
+        );
+        assert_eq!(decode_frame(&mut buf, 1024).unwrap(), None);
+        assert_eq!(&buf[..], b"<13>part");
+    }
+
+    #[test]
+    fn test_decode_frame_octet_counting() {
+        let mut buf = BytesMut::from("9 <13>a\nb c11 <13>parti");
+        assert_eq!(
+            decode_frame(&mut buf, 1024).unwrap(),
+            Some("<13>a\nb c".to_string())
+        );
+        assert_eq!(decode_frame(&mut buf, 1024).unwrap(), None);
+        buf.extend_from_slice(b"al");
+        assert_eq!(
+            decode_frame(&mut buf, 1024).unwrap(),
+            Some("<13>partial".to_string())
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_decode_datagram() {
+        let data = b"<11>1 2026-01-01T00:00:00Z host app - - - panic\n  at main.rs:1\n";
+        assert_eq!(
+            decode_datagram(data, false),
+            vec!["<11>1 2026-01-01T00:00:00Z host app - - - panic\n  at main.rs:1".to_string()]
+        );
+        assert_eq!(
+            decode_datagram(b"<13>first\n\n<13>second\n", true),
+            vec!["<13>first".to_string(), "<13>second".to_string()]
+        );
+        assert!(decode_datagram(b"\r\n", false).is_empty());
+    }
+
+    #[test]
+    fn test_decode_frame_too_large() {
+        let mut buf = BytesMut::from("2048 <13>");
+        assert!(decode_frame(&mut buf, 1024).is_err());
+        let mut buf = BytesMut::from("<13>no newline yet");
+        assert!(decode_frame(&mut buf, 8).is_err());
+    }
+}
@@ -0,0 +1,428 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Build the partial states of views from the files of their base stream.
+//!
+//! Every WAL file persisted for a stream with views is aggregated once per
+//! view into one row per bucket and dimension values. The rows are ingested
+//! into the view stream, so a bucket can have several rows, one per flush,
+//! and they are merged at query time.
+//!
+//! A file is recorded as a pending fold of every view before it is folded, and
+//! the record is removed once the fold succeeded. Ingesters replay the pending
+//! folds of the files they flushed, and those of ingesters that left the
+//! cluster, from the stored file. A fold can thus run more than once, its rows
+//! carry the file in [`FOLD_COLUMN`] and are read once at query time. A fold
+//! that failed without a record, or whose replay was given up, leaves a gap in
+//! the view, which is then not read over the time range of the file.
+
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+use arrow_schema::Schema;
+use bytes::Bytes;
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::{LOCAL_NODE, is_offline},
+    get_config, ider,
+    meta::materialized_view::{FOLD_COLUMN, MaterializedView, PendingFold, ViewGap},
+    utils::{
+        arrow::record_batches_to_json_rows, json, parquet::read_recordbatch_from_bytes,
+        time::now_micros,
+    },
+};
+use datafusion::{datasource::MemTable, prelude::SessionContext};
+use infra::{cluster::get_node_by_uuid, dist_lock, storage};
+
+use crate::{
+    common::{
+        infra::config::MATERIALIZED_VIEWS,
+        meta::ingestion::{IngestUser, IngestionRequest, SystemJobType},
+    },
+    service::db,
+};
+
+const TABLE_NAME: &str = "tbl";
+
+/// Seconds between two replays of the pending folds
+const REPLAY_INTERVAL_SECONDS: u64 = 60;
+
+/// Pending folds younger than this may still be running
+const REPLAY_DELAY_MICROS: i64 = 600 * 1_000_000;
+
+/// Pending folds whose file can't be read for this long are dropped, the file
+/// was most likely merged away by the compactor
+const REPLAY_GIVE_UP_MICROS: i64 = 86400 * 1_000_000;
+
+/// Aggregate a persisted file of a logs stream into all the views of the
+/// stream. A view whose fold fails keeps the file pending, it is replayed
+/// later.
+pub async fn aggregate_file(
+    org_id: &str,
+    stream_name: &str,
+    account: &str,
+    file: &str,
+    (min_ts, max_ts): (i64, i64),
+    data: Bytes,
+) {
+    let views = db::materialized_views::list_for_stream(org_id, stream_name);
+    if views.is_empty() || data.is_empty() {
+        return;
+    }
+    let mut pending = Vec::with_capacity(views.len());
+    for view in views {
+        let fold = PendingFold {
+            org_id: org_id.to_string(),
+            view: view.name.clone(),
+            account: account.to_string(),
+            file: file.to_string(),
+            min_ts,
+            max_ts,
+            node: LOCAL_NODE.uuid.clone(),
+            created_at: now_micros(),
+        };
+        match db::materialized_views::add_pending_fold(&fold).await {
+            Ok(key) => pending.push((view, fold, Some(key))),
+            Err(e) => {
+                log::error!(
+                    "[MATERIALIZED_VIEW] record pending fold of {file} into view {} error: {e}",
+                    view.name
+                );
+                pending.push((view, fold, None));
+            }
+        }
+    }
+    let (schema, batches) = match read_recordbatch_from_bytes(get_config().common.file_format, data)
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!("[MATERIALIZED_VIEW] read file of {org_id}/logs/{stream_name} error: {e}");
+            // recorded folds are replayed from the stored file
+            for (_, fold, key) in pending {
+                if key.is_none() {
+                    add_fold_gap(&fold, "the fold could not be recorded").await;
+                }
+            }
+            return;
+        }
+    };
+    for (view, fold, key) in pending {
+        if let Err(e) = aggregate_into_view(&view, file, schema.clone(), batches.clone()).await {
+            log::error!(
+                "[MATERIALIZED_VIEW] aggregate {org_id}/{stream_name} into view {} error: {e}",
+                view.name
+            );
+            if key.is_none() {
+                add_fold_gap(&fold, "the fold could not be recorded").await;
+            }
+            continue;
+        }
+        if let Some(key) = key
+            && let Err(e) = db::materialized_views::remove_pending_fold(&key).await
+        {
+            log::error!("[MATERIALIZED_VIEW] remove pending fold {key} error: {e}");
+        }
+    }
+}
+
+/// Replay the pending folds periodically, runs on the ingesters.
+pub async fn run_replay() -> Result<(), anyhow::Error> {
+    loop {
+        if is_offline() {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_secs(REPLAY_INTERVAL_SECONDS)).await;
+        replay_pending_folds().await;
+    }
+    log::info!("[MATERIALIZED_VIEW] replay of pending folds is stopped");
+    Ok(())
+}
+
+async fn replay_pending_folds() {
+    let now = now_micros();
+    for (key, fold) in db::materialized_views::list_pending_folds() {
+        if fold.created_at + REPLAY_DELAY_MICROS > now {
+            continue; // the first fold may still be running
+        }
+        if fold.node != LOCAL_NODE.uuid && get_node_by_uuid(&fold.node).await.is_some() {
+            continue; // replayed by the ingester which flushed the file
+        }
+        if let Err(e) = replay_pending_fold(&key, &fold, now).await {
+            log::error!("[MATERIALIZED_VIEW] replay pending fold {key} error: {e}");
+        }
+    }
+}
+
+async fn replay_pending_fold(key: &str, fold: &PendingFold, now: i64) -> Result<(), anyhow::Error> {
+    // folds of ingesters that left the cluster can be picked by several nodes
+    let locker = dist_lock::lock(key, 0).await?;
+    let ret = async {
+        if !db::materialized_views::is_pending_fold(key).await? {
+            return Ok(());
+        }
+        let view = MATERIALIZED_VIEWS
+            .get(&format!("{}/{}", fold.org_id, fold.view))
+            .map(|v| v.value().clone());
+        let Some(view) = view else {
+            // the view was dropped
+            db::materialized_views::remove_pending_fold(key).await?;
+            return Ok(());
+        };
+        let data = match storage::get_bytes(&fold.account, &fold.file).await {
+            Ok(data) => data,
+            Err(e) if fold.created_at + REPLAY_GIVE_UP_MICROS < now => {
+                log::error!(
+                    "[MATERIALIZED_VIEW] drop pending fold of {} into view {}, the file can't be read: {e}",
+                    fold.file,
+                    fold.view
+                );
+                // the fold stays pending until the view is known to be
+                // incomplete over the file
+                db::materialized_views::add_gap(
+                    &fold_gap(fold, "the file could not be read"),
+                    &ider::generate(),
+                )
+                .await?;
+                db::materialized_views::remove_pending_fold(key).await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let (schema, batches) =
+            read_recordbatch_from_bytes(get_config().common.file_format, data).await?;
+        aggregate_into_view(&view, &fold.file, schema, batches).await?;
+        db::materialized_views::remove_pending_fold(key).await?;
+        log::info!(
+            "[MATERIALIZED_VIEW] replayed pending fold of {} into view {}",
+            fold.file,
+            fold.view
+        );
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    dist_lock::unlock(&locker).await?;
+    ret
+}
+
+/// The time range of a file that could not be folded into a view
+fn fold_gap(fold: &PendingFold, reason: &str) -> ViewGap {
+    ViewGap {
+        org_id: fold.org_id.clone(),
+        view: fold.view.clone(),
+        start_time: fold.min_ts,
+        end_time: fold.max_ts.max(fold.min_ts).saturating_add(1),
+        reason: format!("{}: {reason}", fold.file),
+        created_at: now_micros(),
+    }
+}
+
+async fn add_fold_gap(fold: &PendingFold, reason: &str) {
+    if let Err(e) =
+        db::materialized_views::add_gap(&fold_gap(fold, reason), &ider::generate()).await
+    {
+        log::error!(
+            "[MATERIALIZED_VIEW] record gap of {} in view {} error: {e}",
+            fold.file,
+            fold.view
+        );
+    }
+}
+
+async fn aggregate_into_view(
+    view: &MaterializedView,
+    file: &str,
+    schema: Arc<Schema>,
+    batches: Vec<RecordBatch>,
+) -> Result<(), anyhow::Error> {
+    let mut rows = aggregate_batches(view, schema, batches).await?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+    // a file folded twice gives the same rows, read once at query time
+    for row in rows.iter_mut() {
+        row.insert(FOLD_COLUMN.to_string(), json::Value::from(file));
+    }
+    let records = rows.len();
+    let resp = crate::service::logs::ingest::ingest(
+        0,
+        &view.org_id,
+        &view.name,
+        IngestionRequest::JSON(json::to_vec(&rows)?.into()),
+        IngestUser::SystemJob(SystemJobType::MaterializedView),
+        None,
+        true,
+    )
+    .await?;
+    log::debug!(
+        "[MATERIALIZED_VIEW] ingested {records} state rows into view {}/{}, code: {}",
+        view.org_id,
+        view.name,
+        resp.code
+    );
+    Ok(())
+}
+
+/// Compute the state rows of a view for the given batches of its base stream.
+pub(crate) async fn aggregate_batches(
+    view: &MaterializedView,
+    schema: Arc<Schema>,
+    batches: Vec<RecordBatch>,
+) -> Result<Vec<json::Map<String, json::Value>>, anyhow::Error> {
+    if batches.iter().all(|b| b.num_rows() == 0) {
+        return Ok(Vec::new());
+    }
+    let ctx = SessionContext::new();
+    let table = MemTable::try_new(schema.clone(), vec![batches])?;
+    ctx.register_table(TABLE_NAME, Arc::new(table))?;
+    let batches = ctx
+        .sql(&aggregate_sql(view, &schema))
+        .await?
+        .collect()
+        .await?;
+    record_batches_to_json_rows(&batches.iter().collect::<Vec<_>>())
+}
+
+/// The query computing the states of a view. Fields missing from the file
+/// are read as NULL so every file produces the same columns.
+pub(crate) fn aggregate_sql(view: &MaterializedView, schema: &Schema) -> String {
+    let column = |name: &str| {
+        if schema.field_with_name(name).is_ok() {
+            quote(name)
+        } else {
+            "CAST(NULL AS DOUBLE)".to_string()
+        }
+    };
+    let ts = quote(TIMESTAMP_COL_NAME);
+    let mut columns = vec![format!("{ts} - ({ts} % {}) AS {ts}", view.interval)];
+    for dim in view.dimensions.iter() {
+        columns.push(format!("{} AS {}", column(dim), quote(dim)));
+    }
+    for agg in view.aggregates.iter() {
+        let field = agg.field.as_deref().map(column);
+        for (state, _) in agg.func.states() {
+            let expr = match (*state, field.as_deref()) {
+                ("count", None) => "COUNT(*)".to_string(),
+                ("sum", Some(field)) => format!("SUM(CAST({field} AS DOUBLE))"),
+                (state, Some(field)) => format!("{}({field})", state.to_uppercase()),
+                (state, None) => format!("{}(*)", state.to_uppercase()),
+            };
+            columns.push(format!("{expr} AS {}", quote(&agg.state_column(state))));
+        }
+    }
+    let group_by = (1..=view.dimensions.len() + 1)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "SELECT {} FROM {} WHERE {ts} >= {} GROUP BY {group_by}",
+        columns.join(", "),
+        quote(TABLE_NAME),
+        view.start_time()
+    )
+}
+
+pub(crate) fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Int64Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use config::meta::materialized_view::{ViewAggregate, ViewAggregateFunc};
+
+    use super::*;
+
+    fn view() -> MaterializedView {
+        MaterializedView {
+            name: "nginx_1m".to_string(),
+            org_id: "default".to_string(),
+            stream_name: "nginx".to_string(),
+            sql: "".to_string(),
+            interval: 60_000_000,
+            dimensions: vec!["status".to_string(), "region".to_string()],
+            aggregates: vec![
+                ViewAggregate {
+                    func: ViewAggregateFunc::Count,
+                    field: None,
+                    alias: "cnt".to_string(),
+                },
+                ViewAggregate {
+                    func: ViewAggregateFunc::Avg,
+                    field: Some("took".to_string()),
+                    alias: "avg_took".to_string(),
+                },
+            ],
+            created_at: 0,
+        }
+    }
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new(TIMESTAMP_COL_NAME, DataType::Int64, false),
+            Field::new("status", DataType::Utf8, true),
+            Field::new("took", DataType::Int64, true),
+        ])
+    }
+
+    #[test]
+    fn test_aggregate_sql() {
+        assert_eq!(
+            aggregate_sql(&view(), &schema()),
+            "SELECT \"_timestamp\" - (\"_timestamp\" % 60000000) AS \"_timestamp\", \
+             \"status\" AS \"status\", CAST(NULL AS DOUBLE) AS \"region\", \
+             COUNT(*) AS \"cnt__count\", SUM(CAST(\"took\" AS DOUBLE)) AS \"avg_took__sum\", \
+             COUNT(\"took\") AS \"avg_took__count\" \
+             FROM \"tbl\" WHERE \"_timestamp\" >= 0 GROUP BY 1, 2, 3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_batches() {
+        let schema = Arc::new(schema());
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![
+                    60_000_000,
+                    61_000_000,
+                    62_000_000,
+                    125_000_000,
+                ])),
+                Arc::new(StringArray::from(vec!["200", "200", "500", "200"])),
+                Arc::new(Int64Array::from(vec![Some(10), Some(20), None, Some(5)])),
+            ],
+        )
+        .unwrap();
+        let mut rows = aggregate_batches(&view(), schema, vec![batch])
+            .await
+            .unwrap();
+        rows.sort_by_key(|r| {
+            (
+                r[TIMESTAMP_COL_NAME].as_i64().unwrap(),
+                r["status"].as_str().unwrap().to_string(),
+            )
+        });
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0][TIMESTAMP_COL_NAME], 60_000_000);
+        assert_eq!(rows[0]["cnt__count"], 2);
+        assert_eq!(rows[0]["avg_took__sum"], 30.0);
+        assert_eq!(rows[0]["avg_took__count"], 2);
+        assert_eq!(rows[1]["status"], "500");
+        assert_eq!(rows[1]["avg_took__count"], 0);
+        assert_eq!(rows[2][TIMESTAMP_COL_NAME], 120_000_000);
+        assert_eq!(rows[2]["cnt__count"], 1);
+    }
+}
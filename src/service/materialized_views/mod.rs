@@ -0,0 +1,567 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Materialized aggregation views.
+//!
+//! A view keeps the partial aggregate states of a `GROUP BY histogram(..)`
+//! query over a logs stream. The states are built by [`flush`] every time a
+//! WAL file of the base stream is persisted, and [`rewrite`] answers matching
+//! dashboard queries from them, reading the base stream only for the ranges
+//! that are not materialized yet.
+
+use anyhow::{anyhow, bail};
+use arrow_schema::Schema;
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::{
+        materialized_view::{
+            FOLD_COLUMN, MaterializedView, MaterializedViewRequest, STATE_COLUMN_SEPARATOR,
+            ViewAggregate, ViewAggregateFunc, ViewGap,
+        },
+        stream::StreamType,
+    },
+    utils::{calendar::CalendarInterval, schema::format_stream_name, time::now_micros},
+};
+use sqlparser::{
+    ast::{
+        Expr, FunctionArg, FunctionArgExpr, FunctionArguments, GroupByExpr, Select, SelectItem,
+        SetExpr, Statement, TableFactor, Value, ValueWithSpan,
+    },
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+
+use crate::service::{
+    db, search::sql::visitor::histogram_interval::convert_histogram_interval_to_seconds,
+};
+
+pub mod flush;
+pub mod rewrite;
+
+const DAY_SECONDS: i64 = 86400;
+
+/// The arguments of a `histogram(field, interval, timezone)` call
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Histogram {
+    pub field: String,
+    pub interval: Option<String>,
+    pub timezone: Option<String>,
+}
+
+/// A column of an aggregation query, in projection order
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Column {
+    Histogram,
+    /// Index into [`AggregateQuery::dimensions`]
+    Dimension(usize),
+    /// Index into [`AggregateQuery::aggregates`]
+    Aggregate(usize),
+}
+
+/// An aggregation query of the shape a view can be defined by
+#[derive(Debug, Clone)]
+pub(crate) struct AggregateQuery {
+    pub stream_name: String,
+    pub histogram: Histogram,
+    pub histogram_alias: Option<String>,
+    pub dimensions: Vec<String>,
+    /// The function, field and alias of every aggregate
+    pub aggregates: Vec<(ViewAggregateFunc, Option<String>, Option<String>)>,
+    pub columns: Vec<Column>,
+}
+
+impl AggregateQuery {
+    /// Break a `SELECT histogram(..), dims.., aggregates.. FROM stream GROUP BY ..`
+    /// down into its parts. The `WHERE` clause is left to the caller.
+    pub(crate) fn parse(select: &Select) -> Result<Self, String> {
+        if select.distinct.is_some() || select.having.is_some() {
+            return Err("DISTINCT and HAVING are not supported".to_string());
+        }
+        let stream_name = single_table(select).ok_or("a single stream is required")?;
+
+        let mut histogram = None;
+        let mut histogram_alias = None;
+        let mut dimensions = Vec::new();
+        let mut aggregates = Vec::new();
+        let mut columns = Vec::with_capacity(select.projection.len());
+        for item in select.projection.iter() {
+            let (expr, alias) = match item {
+                SelectItem::UnnamedExpr(expr) => (expr, None),
+                SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+                _ => return Err("columns must be listed explicitly".to_string()),
+            };
+            if let Some(h) = parse_histogram(expr) {
+                if histogram.is_some() {
+                    return Err("a single histogram is supported".to_string());
+                }
+                histogram = Some(h);
+                histogram_alias = alias;
+                columns.push(Column::Histogram);
+            } else if let Some(field) = parse_identifier(expr) {
+                if alias.as_ref().is_some_and(|alias| alias != &field) {
+                    return Err(format!("dimension {field} can not be renamed"));
+                }
+                if dimensions.contains(&field) {
+                    return Err(format!("dimension {field} is selected twice"));
+                }
+                columns.push(Column::Dimension(dimensions.len()));
+                dimensions.push(field);
+            } else if let Some((func, field)) = parse_aggregate(expr) {
+                columns.push(Column::Aggregate(aggregates.len()));
+                aggregates.push((func, field, alias));
+            } else {
+                return Err(format!("unsupported column: {expr}"));
+            }
+        }
+        let histogram = histogram.ok_or("a histogram of _timestamp is required")?;
+
+        // every group key must be a selected histogram or dimension, and all
+        // of them must be grouped
+        let GroupByExpr::Expressions(group_by, modifiers) = &select.group_by else {
+            return Err("GROUP BY ALL is not supported".to_string());
+        };
+        if !modifiers.is_empty() {
+            return Err("GROUP BY modifiers are not supported".to_string());
+        }
+        let mut grouped = vec![false; columns.len()];
+        for expr in group_by.iter() {
+            let pos = if let Some(n) = parse_position(expr) {
+                (1..=columns.len()).contains(&n).then(|| n - 1)
+            } else if parse_histogram(expr).is_some_and(|h| h == histogram) {
+                columns.iter().position(|c| *c == Column::Histogram)
+            } else if let Some(name) = parse_identifier(expr) {
+                if histogram_alias.as_ref() == Some(&name) {
+                    columns.iter().position(|c| *c == Column::Histogram)
+                } else {
+                    dimensions
+                        .iter()
+                        .position(|d| *d == name)
+                        .and_then(|i| columns.iter().position(|c| *c == Column::Dimension(i)))
+                }
+            } else {
+                None
+            };
+            match pos {
+                Some(pos) if !matches!(columns[pos], Column::Aggregate(_)) => grouped[pos] = true,
+                _ => return Err(format!("unsupported group key: {expr}")),
+            }
+        }
+        if columns
+            .iter()
+            .zip(grouped)
+            .any(|(c, grouped)| !matches!(c, Column::Aggregate(_)) && !grouped)
+        {
+            return Err("the histogram and every dimension must be grouped".to_string());
+        }
+
+        Ok(Self {
+            stream_name,
+            histogram,
+            histogram_alias,
+            dimensions,
+            aggregates,
+            columns,
+        })
+    }
+}
+
+/// Parse the defining query of a view.
+pub fn parse_definition(
+    org_id: &str,
+    name: &str,
+    sql: &str,
+) -> Result<MaterializedView, anyhow::Error> {
+    let mut statements =
+        Parser::parse_sql(&PostgreSqlDialect {}, sql).map_err(|e| anyhow!("invalid sql: {e}"))?;
+    if statements.len() != 1 {
+        bail!("a view must be defined by a single query");
+    }
+    let Statement::Query(query) = statements.remove(0) else {
+        bail!("a view must be defined by a query");
+    };
+    if query.with.is_some()
+        || query.order_by.is_some()
+        || query.limit_clause.is_some()
+        || query.fetch.is_some()
+    {
+        bail!("a view query does not support WITH, ORDER BY or LIMIT");
+    }
+    let SetExpr::Select(select) = *query.body else {
+        bail!("a view must be defined by a single SELECT");
+    };
+    if select.selection.is_some() {
+        bail!("a view query does not support WHERE, filter when querying the view instead");
+    }
+    let parsed = AggregateQuery::parse(&select).map_err(|e| anyhow!("invalid view query: {e}"))?;
+
+    if parsed.histogram.field != TIMESTAMP_COL_NAME {
+        bail!("the histogram of a view must be over {TIMESTAMP_COL_NAME}");
+    }
+    if parsed.histogram.timezone.is_some() {
+        bail!("the histogram of a view can not have a timezone");
+    }
+    let Some(interval) = parsed.histogram.interval.as_deref() else {
+        bail!("the histogram of a view must have an explicit interval");
+    };
+    if CalendarInterval::parse(interval).is_some() {
+        bail!("calendar intervals are not supported by views");
+    }
+    let interval = convert_histogram_interval_to_seconds(interval)?;
+    if interval <= 0 || DAY_SECONDS % interval != 0 {
+        bail!("the interval of a view must divide a day evenly");
+    }
+    if parsed.aggregates.is_empty() {
+        bail!("a view must compute at least one aggregate");
+    }
+    if parsed.dimensions.iter().any(|d| d == TIMESTAMP_COL_NAME) {
+        bail!("{TIMESTAMP_COL_NAME} can not be a dimension");
+    }
+
+    let mut aggregates: Vec<ViewAggregate> = Vec::with_capacity(parsed.aggregates.len());
+    for (func, field, alias) in parsed.aggregates {
+        let alias = alias.unwrap_or_else(|| match field.as_deref() {
+            Some(field) => format!("{func}_{field}"),
+            None => func.to_string(),
+        });
+        if !alias
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            || alias.contains(STATE_COLUMN_SEPARATOR)
+        {
+            bail!(
+                "invalid aggregate alias {alias}, use lowercase letters, digits and single underscores"
+            );
+        }
+        if parsed.dimensions.contains(&alias) || aggregates.iter().any(|a| a.alias == alias) {
+            bail!("aggregate alias {alias} is used twice");
+        }
+        if aggregates
+            .iter()
+            .any(|a| a.func == func && a.field == field)
+        {
+            bail!("aggregate {alias} is computed twice");
+        }
+        aggregates.push(ViewAggregate { func, field, alias });
+    }
+
+    Ok(MaterializedView {
+        name: name.to_string(),
+        org_id: org_id.to_string(),
+        stream_name: parsed.stream_name,
+        sql: sql.trim().to_string(),
+        interval: interval * 1_000_000,
+        dimensions: parsed.dimensions,
+        aggregates,
+        created_at: now_micros(),
+    })
+}
+
+pub async fn create(
+    org_id: &str,
+    req: MaterializedViewRequest,
+) -> Result<MaterializedView, anyhow::Error> {
+    let name = req.name.trim();
+    if name.is_empty() || format_stream_name(name.to_string()) != name {
+        bail!("view name must be a valid stream name");
+    }
+    let view = parse_definition(org_id, name, &req.sql)?;
+    if db::materialized_views::get(org_id, name).await?.is_some() {
+        bail!("materialized view {name} already exists");
+    }
+    if infra::schema::get(org_id, name, StreamType::Logs).await? != Schema::empty() {
+        bail!("stream {name} already exists");
+    }
+    if db::materialized_views::is_view_stream(org_id, &view.stream_name) {
+        bail!("a view can not aggregate another view");
+    }
+    let schema = infra::schema::get(org_id, &view.stream_name, StreamType::Logs).await?;
+    if schema == Schema::empty() {
+        bail!("stream {} not found", view.stream_name);
+    }
+    let fields = view
+        .dimensions
+        .iter()
+        .chain(view.aggregates.iter().filter_map(|a| a.field.as_ref()));
+    for field in fields {
+        if field == FOLD_COLUMN {
+            bail!("field {field} is reserved for the view");
+        }
+        if schema.field_with_name(field).is_err() {
+            bail!("field {field} not found in stream {}", view.stream_name);
+        }
+    }
+    db::materialized_views::set(&view).await?;
+    Ok(view)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<MaterializedView>, anyhow::Error> {
+    Ok(db::materialized_views::list(org_id).await?)
+}
+
+pub async fn get(org_id: &str, name: &str) -> Result<Option<MaterializedView>, anyhow::Error> {
+    Ok(db::materialized_views::get(org_id, name).await?)
+}
+
+/// Drop a view, returning `false` if it does not exist. The stream holding
+/// its states is kept and can be deleted like any other stream.
+pub async fn delete(org_id: &str, name: &str) -> Result<bool, anyhow::Error> {
+    if db::materialized_views::get(org_id, name).await?.is_none() {
+        return Ok(false);
+    }
+    db::materialized_views::delete(org_id, name).await?;
+    db::materialized_views::delete_gaps(org_id, name).await?;
+    Ok(true)
+}
+
+/// Stop reading the views of a stream over a time range whose data is deleted
+/// from the stream, their states would still count it. `id` identifies the
+/// delete, recording it again replaces the range.
+pub async fn invalidate(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    time_range: (i64, i64),
+    id: &str,
+) -> Result<(), anyhow::Error> {
+    if stream_type != StreamType::Logs {
+        return Ok(());
+    }
+    for view in db::materialized_views::list_for_stream(org_id, stream_name) {
+        if time_range.1 < view.start_time() {
+            continue;
+        }
+        let gap = ViewGap {
+            org_id: org_id.to_string(),
+            view: view.name.clone(),
+            start_time: time_range.0,
+            end_time: time_range.1.saturating_add(1),
+            reason: format!("data of {stream_name} deleted"),
+            created_at: now_micros(),
+        };
+        db::materialized_views::add_gap(&gap, id).await?;
+    }
+    Ok(())
+}
+
+fn single_table(select: &Select) -> Option<String> {
+    if select.from.len() != 1 || !select.from[0].joins.is_empty() {
+        return None;
+    }
+    let TableFactor::Table { name, args, .. } = &select.from[0].relation else {
+        return None;
+    };
+    if name.0.len() != 1 || args.is_some() {
+        return None;
+    }
+    name.0[0].as_ident().map(|ident| ident.value.clone())
+}
+
+pub(crate) fn parse_identifier(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Identifier(ident) => Some(ident.value.clone()),
+        Expr::Nested(expr) => parse_identifier(expr),
+        _ => None,
+    }
+}
+
+fn parse_string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Value(ValueWithSpan {
+            value: Value::SingleQuotedString(s),
+            ..
+        }) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn parse_position(expr: &Expr) -> Option<usize> {
+    match expr {
+        Expr::Value(ValueWithSpan {
+            value: Value::Number(n, _),
+            ..
+        }) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+/// The plain arguments of a function call, `None` if it uses a window,
+/// filter or any other modifier.
+fn plain_args<'a>(expr: &'a Expr, name: &str) -> Option<&'a [FunctionArg]> {
+    let Expr::Function(func) = expr else {
+        return None;
+    };
+    if func.name.0.len() != 1
+        || !func.name.0[0]
+            .as_ident()
+            .is_some_and(|ident| ident.value.eq_ignore_ascii_case(name))
+        || func.filter.is_some()
+        || func.over.is_some()
+        || func.null_treatment.is_some()
+        || !func.within_group.is_empty()
+    {
+        return None;
+    }
+    let FunctionArguments::List(list) = &func.args else {
+        return None;
+    };
+    if list.duplicate_treatment.is_some() || !list.clauses.is_empty() {
+        return None;
+    }
+    Some(&list.args)
+}
+
+pub(crate) fn parse_histogram(expr: &Expr) -> Option<Histogram> {
+    let args = plain_args(expr, "histogram")?;
+    let mut args = args.iter().map(|arg| match arg {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Some(expr),
+        _ => None,
+    });
+    let field = parse_identifier(args.next()??)?;
+    let interval = match args.next() {
+        Some(arg) => Some(parse_string(arg?)?),
+        None => None,
+    };
+    let timezone = match args.next() {
+        Some(arg) => Some(parse_string(arg?)?),
+        None => None,
+    };
+    if args.next().is_some() {
+        return None;
+    }
+    Some(Histogram {
+        field,
+        interval,
+        timezone,
+    })
+}
+
+/// The function and field of a mergeable aggregate. `count(_timestamp)` is
+/// the same as `count(*)` since every row has a timestamp.
+pub(crate) fn parse_aggregate(expr: &Expr) -> Option<(ViewAggregateFunc, Option<String>)> {
+    let Expr::Function(func) = expr else {
+        return None;
+    };
+    let name = func.name.0.last()?.as_ident()?.value.as_str();
+    let agg = ViewAggregateFunc::from_name(name)?;
+    let args = plain_args(expr, name)?;
+    if args.len() != 1 {
+        return None;
+    }
+    let field = match &args[0] {
+        FunctionArg::Unnamed(FunctionArgExpr::Wildcard) if agg == ViewAggregateFunc::Count => None,
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+            let field = parse_identifier(expr)?;
+            if agg == ViewAggregateFunc::Count && field == TIMESTAMP_COL_NAME {
+                None
+            } else {
+                Some(field)
+            }
+        }
+        _ => return None,
+    };
+    Some((agg, field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_definition() {
+        let sql = "SELECT histogram(_timestamp, '1 minute') AS ts, status, count(*) AS cnt, \
+                   avg(took), max(took) AS max_took FROM nginx GROUP BY ts, status";
+        let view = parse_definition("default", "nginx_1m", sql).unwrap();
+        assert_eq!(view.stream_name, "nginx");
+        assert_eq!(view.interval, 60_000_000);
+        assert_eq!(view.dimensions, vec!["status"]);
+        let aggregates = view
+            .aggregates
+            .iter()
+            .map(|a| (a.func, a.field.as_deref(), a.alias.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            aggregates,
+            vec![
+                (ViewAggregateFunc::Count, None, "cnt"),
+                (ViewAggregateFunc::Avg, Some("took"), "avg_took"),
+                (ViewAggregateFunc::Max, Some("took"), "max_took"),
+            ]
+        );
+
+        // positional group keys
+        let sql = "SELECT histogram(_timestamp, '5m'), host, sum(bytes) FROM nginx GROUP BY 1, 2";
+        let view = parse_definition("default", "v", sql).unwrap();
+        assert_eq!(view.interval, 300_000_000);
+        assert_eq!(view.aggregates[0].alias, "sum_bytes");
+    }
+
+    #[test]
+    fn test_parse_definition_rejects() {
+        let cases = [
+            // no histogram
+            "SELECT status, count(*) AS c FROM t GROUP BY status",
+            // missing interval
+            "SELECT histogram(_timestamp) AS ts, count(*) AS c FROM t GROUP BY ts",
+            // does not divide a day
+            "SELECT histogram(_timestamp, '7 minute') AS ts, count(*) AS c FROM t GROUP BY ts",
+            // calendar interval
+            "SELECT histogram(_timestamp, '1 month') AS ts, count(*) AS c FROM t GROUP BY ts",
+            // timezone
+            "SELECT histogram(_timestamp, '1m', 'UTC') AS ts, count(*) AS c FROM t GROUP BY ts",
+            // filter
+            "SELECT histogram(_timestamp, '1m') AS ts, count(*) AS c FROM t WHERE a = 1 GROUP BY ts",
+            // dimension not grouped
+            "SELECT histogram(_timestamp, '1m') AS ts, host, count(*) AS c FROM t GROUP BY ts",
+            // unsupported aggregate
+            "SELECT histogram(_timestamp, '1m') AS ts, approx_distinct(a) AS c FROM t GROUP BY ts",
+            "SELECT histogram(_timestamp, '1m') AS ts, count(DISTINCT a) AS c FROM t GROUP BY ts",
+            // expressions
+            "SELECT histogram(_timestamp, '1m') AS ts, sum(a + 1) AS c FROM t GROUP BY ts",
+            // join
+            "SELECT histogram(_timestamp, '1m') AS ts, count(*) AS c FROM t JOIN u ON t.a = u.a GROUP BY ts",
+            // duplicated alias
+            "SELECT histogram(_timestamp, '1m') AS ts, sum(a) AS c, max(a) AS c FROM t GROUP BY ts",
+            // alias with the state separator
+            "SELECT histogram(_timestamp, '1m') AS ts, sum(a) AS a__b FROM t GROUP BY ts",
+            "SELECT histogram(_timestamp, '1m') AS ts, count(*) AS c FROM t GROUP BY ts LIMIT 10",
+        ];
+        for sql in cases {
+            assert!(parse_definition("default", "v", sql).is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_parse_aggregate() {
+        let parse = |sql: &str| {
+            let expr = Parser::new(&PostgreSqlDialect {})
+                .try_with_sql(sql)
+                .unwrap()
+                .parse_expr()
+                .unwrap();
+            parse_aggregate(&expr)
+        };
+        assert_eq!(parse("count(*)"), Some((ViewAggregateFunc::Count, None)));
+        assert_eq!(
+            parse("count(_timestamp)"),
+            Some((ViewAggregateFunc::Count, None))
+        );
+        assert_eq!(
+            parse("COUNT(status)"),
+            Some((ViewAggregateFunc::Count, Some("status".to_string())))
+        );
+        assert_eq!(parse("sum(*)"), None);
+        assert_eq!(parse("sum(a) FILTER (WHERE a > 1)"), None);
+        assert_eq!(parse("median(a)"), None);
+    }
+}
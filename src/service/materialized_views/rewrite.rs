@@ -0,0 +1,685 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Answer dashboard queries from materialized views.
+//!
+//! A query matches a view when it groups the base stream by a histogram of
+//! `_timestamp` and a subset of the view dimensions, computes only aggregates
+//! the view keeps, and filters on view dimensions only. Its time range is split
+//! on buckets of the query interval:
+//!
+//! ```text
+//! start        view start            watermark         end
+//!   |-- base --|========= view =========|----- base -----|
+//! ```
+//!
+//! The middle part is read from the view, where the states are merged, and the
+//! head and tail from the base stream. The tail covers the data that may still
+//! sit in the WAL of the ingesters and the files whose fold into the view is
+//! still pending, see [`super::flush`]. The head is extended past the gaps of
+//! the view, the time ranges whose files could not be folded or whose data was
+//! deleted from the base stream.
+
+use std::ops::ControlFlow;
+
+use config::{
+    TIMESTAMP_COL_NAME, get_config, ider,
+    meta::{
+        materialized_view::{FOLD_COLUMN, MaterializedView, ViewAggregateFunc},
+        search::{self, RequestEncoding, SearchEventType},
+        stream::StreamType,
+    },
+    utils::time::now_micros,
+};
+use infra::errors::Error;
+use sqlparser::{
+    ast::{Expr, OrderByKind, SetExpr, Statement, VisitMut, visit_expressions},
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+
+use super::{AggregateQuery, Column, flush::quote, parse_histogram, parse_identifier};
+use crate::service::{
+    db,
+    search::sql::visitor::histogram_interval::{
+        HistogramIntervalVisitor, validate_and_adjust_histogram_interval,
+    },
+};
+
+const DAY_SECONDS: i64 = 86400;
+
+/// Rows flushed within this many seconds may not be folded into the views
+/// yet, on top of the time they can stay in the WAL.
+const FLUSH_DELAY_SECONDS: u64 = 60;
+
+/// The sub-queries answering a query, in time order
+#[derive(Debug)]
+pub struct Plan {
+    pub view_name: String,
+    pub requests: Vec<search::Request>,
+    /// The interval of the histogram, in seconds
+    pub interval: i64,
+    /// Whether rows are ordered by the histogram descending
+    pub descending: bool,
+    pub size: i64,
+}
+
+/// A query that a view can answer
+struct Matched<'a> {
+    view: &'a MaterializedView,
+    query: AggregateQuery,
+    selection: Option<String>,
+    order_by: Option<String>,
+    descending: bool,
+    interval: i64,
+}
+
+/// Plan answering the request from a view, `None` if no view matches.
+pub fn plan(org_id: &str, stream_type: StreamType, req: &search::Request) -> Option<Plan> {
+    if stream_type != StreamType::Logs || req.search_type != Some(SearchEventType::Dashboards) {
+        return None;
+    }
+    let stream_name = single_stream_name(&req.query.sql)?;
+    let views = db::materialized_views::list_for_stream(org_id, &stream_name);
+    if views.is_empty() {
+        return None;
+    }
+    let now = now_micros();
+    plan_with_views(
+        &views,
+        req,
+        |view| {
+            let pending = db::materialized_views::pending_fold_min_ts(&view.org_id, &view.name);
+            watermark(now).min(pending.unwrap_or(i64::MAX))
+        },
+        |view| db::materialized_views::list_gaps(&view.org_id, &view.name),
+    )
+}
+
+/// The time up to which the flushed data of every ingester was persisted, and
+/// so folded into the views unless the fold is pending.
+fn watermark(now: i64) -> i64 {
+    let cfg = get_config();
+    let delay = cfg.limit.max_file_retention_time
+        + cfg.limit.mem_persist_interval
+        + cfg.limit.file_push_interval
+        + FLUSH_DELAY_SECONDS;
+    now - delay as i64 * 1_000_000
+}
+
+/// `watermark` is the time up to which a view is complete, and `gaps` the time
+/// ranges it must not be read over.
+fn plan_with_views(
+    views: &[MaterializedView],
+    req: &search::Request,
+    watermark: impl Fn(&MaterializedView) -> i64,
+    gaps: impl Fn(&MaterializedView) -> Vec<(i64, i64)>,
+) -> Option<Plan> {
+    let query = &req.query;
+    if query.from != 0
+        || query.uses_zo_fn
+        || query.query_fn.as_ref().is_some_and(|f| !f.is_empty())
+        || query.sampling_config.is_some()
+        || query.sampling_ratio.is_some()
+        || query.timezone.as_ref().is_some_and(|tz| !tz.is_empty())
+        || req.encoding == RequestEncoding::Base64
+    {
+        return None;
+    }
+
+    let matched = views.iter().find_map(|view| match_view(view, req))?;
+    let interval = matched.interval * 1_000_000;
+    let view_start = align_up(query.start_time.max(matched.view.start_time()), interval);
+    let view_end = align_down(watermark(matched.view).min(query.end_time), interval);
+    // the base stream is read up to the end of the last gap within the range
+    let view_start = gaps(matched.view)
+        .into_iter()
+        .filter(|(start, end)| *start < view_end && *end > view_start)
+        .fold(view_start, |view_start, (_, end)| {
+            view_start.max(align_up(end, interval))
+        });
+    if view_start >= view_end {
+        return None;
+    }
+
+    let mut requests = Vec::with_capacity(3);
+    let sub_request = |sql: String, start_time: i64, end_time: i64| {
+        let mut req = req.clone();
+        req.query.sql = sql;
+        req.query.start_time = start_time;
+        req.query.end_time = end_time;
+        req.query.histogram_interval = matched.interval;
+        req.query.track_total_hits = false;
+        req
+    };
+    let base_sql = render_sql(&matched, false);
+    if query.start_time < view_start {
+        requests.push(sub_request(base_sql.clone(), query.start_time, view_start));
+    }
+    requests.push(sub_request(
+        render_sql(&matched, true),
+        view_start,
+        view_end,
+    ));
+    if view_end < query.end_time {
+        requests.push(sub_request(base_sql, view_end, query.end_time));
+    }
+
+    Some(Plan {
+        view_name: matched.view.name.clone(),
+        requests,
+        interval: matched.interval,
+        descending: matched.descending,
+        size: query.size,
+    })
+}
+
+fn single_stream_name(sql: &str) -> Option<String> {
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, sql).ok()?;
+    if statements.len() != 1 {
+        return None;
+    }
+    let Statement::Query(query) = statements.remove(0) else {
+        return None;
+    };
+    let SetExpr::Select(select) = *query.body else {
+        return None;
+    };
+    super::single_table(&select)
+}
+
+fn match_view<'a>(view: &'a MaterializedView, req: &search::Request) -> Option<Matched<'a>> {
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, &req.query.sql).ok()?;
+    if statements.len() != 1 {
+        return None;
+    }
+    let Statement::Query(query) = &statements[0] else {
+        return None;
+    };
+    if query.with.is_some() || query.limit_clause.is_some() || query.fetch.is_some() {
+        return None;
+    }
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return None;
+    };
+    let parsed = AggregateQuery::parse(select).ok()?;
+    if parsed.stream_name != view.stream_name
+        || parsed.histogram.field != TIMESTAMP_COL_NAME
+        || parsed.histogram.timezone.is_some()
+    {
+        return None;
+    }
+    // the rows of the view and of the base stream must have the same columns
+    let histogram_alias = parsed.histogram_alias.as_ref()?;
+    if parsed
+        .aggregates
+        .iter()
+        .any(|(_, _, alias)| alias.is_none())
+        || !parsed
+            .dimensions
+            .iter()
+            .all(|d| view.dimensions.contains(d))
+        || !parsed
+            .aggregates
+            .iter()
+            .all(|(func, field, _)| view.find_aggregate(*func, field.as_deref()).is_some())
+    {
+        return None;
+    }
+    if let Some(selection) = select.selection.as_ref()
+        && !is_dimension_filter(selection, &view.dimensions)
+    {
+        return None;
+    }
+
+    // rows of the view and of the base stream are concatenated, which keeps
+    // them ordered only if the histogram is the first sort key
+    let mut descending = false;
+    if let Some(order_by) = query.order_by.as_ref() {
+        let OrderByKind::Expressions(exprs) = &order_by.kind else {
+            return None;
+        };
+        let first = exprs.first()?;
+        let by_histogram = parse_identifier(&first.expr).as_ref() == Some(histogram_alias)
+            || parse_histogram(&first.expr).as_ref() == Some(&parsed.histogram);
+        if !by_histogram || order_by.interpolate.is_some() {
+            return None;
+        }
+        descending = first.options.asc == Some(false);
+    }
+
+    let interval = query_interval(req)?;
+    if interval <= 0 || DAY_SECONDS % interval != 0 || (interval * 1_000_000) % view.interval != 0 {
+        return None;
+    }
+
+    Some(Matched {
+        view,
+        selection: select.selection.as_ref().map(|s| s.to_string()),
+        order_by: query.order_by.as_ref().map(|o| o.to_string()),
+        descending,
+        interval,
+        query: parsed,
+    })
+}
+
+/// The histogram interval the search would use for the whole time range, in
+/// seconds. Calendar intervals are not supported.
+fn query_interval(req: &search::Request) -> Option<i64> {
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, &req.query.sql).ok()?;
+    let time_range = (req.query.start_time, req.query.end_time);
+    let mut visitor = HistogramIntervalVisitor::new(time_range);
+    let _ = statements.visit(&mut visitor);
+    if visitor.error.is_some() || visitor.timezone.is_some() {
+        return None;
+    }
+    if req.query.histogram_interval > 0 {
+        Some(validate_and_adjust_histogram_interval(
+            req.query.histogram_interval,
+            time_range,
+        ))
+    } else if visitor.calendar_interval.is_some() {
+        None
+    } else {
+        visitor.interval
+    }
+}
+
+/// Whether the filter only compares view dimensions with literals.
+fn is_dimension_filter(expr: &Expr, dimensions: &[String]) -> bool {
+    visit_expressions(expr, |expr| match expr {
+        Expr::Identifier(ident) if dimensions.contains(&ident.value) => ControlFlow::Continue(()),
+        Expr::Value(_)
+        | Expr::BinaryOp { .. }
+        | Expr::UnaryOp { .. }
+        | Expr::Nested(_)
+        | Expr::IsNull(_)
+        | Expr::IsNotNull(_)
+        | Expr::InList { .. }
+        | Expr::Between { .. }
+        | Expr::Like { .. }
+        | Expr::ILike { .. } => ControlFlow::Continue(()),
+        _ => ControlFlow::Break(()),
+    })
+    .is_continue()
+}
+
+/// Render the query over the view, merging the states, or over the base
+/// stream, with the histogram interval pinned so that all parts agree.
+fn render_sql(matched: &Matched, from_view: bool) -> String {
+    let view = matched.view;
+    let query = &matched.query;
+    let mut columns = Vec::with_capacity(query.columns.len());
+    let mut group_by = Vec::new();
+    for (i, column) in query.columns.iter().enumerate() {
+        match column {
+            Column::Histogram => {
+                group_by.push((i + 1).to_string());
+                columns.push(format!(
+                    "histogram({}, '{} second') AS {}",
+                    quote(TIMESTAMP_COL_NAME),
+                    matched.interval,
+                    quote(query.histogram_alias.as_deref().unwrap_or_default())
+                ));
+            }
+            Column::Dimension(d) => {
+                group_by.push((i + 1).to_string());
+                columns.push(quote(&query.dimensions[*d]));
+            }
+            Column::Aggregate(a) => {
+                let (func, field, alias) = &query.aggregates[*a];
+                let expr = if from_view {
+                    // matched views keep every aggregate of the query
+                    let agg = view.find_aggregate(*func, field.as_deref()).unwrap();
+                    let state = |state: &str| quote(&agg.state_column(state));
+                    match func {
+                        ViewAggregateFunc::Count => format!("sum({})", state("count")),
+                        ViewAggregateFunc::Sum => format!("sum({})", state("sum")),
+                        ViewAggregateFunc::Min => format!("min({})", state("min")),
+                        ViewAggregateFunc::Max => format!("max({})", state("max")),
+                        ViewAggregateFunc::Avg => {
+                            format!("sum({}) / sum({})", state("sum"), state("count"))
+                        }
+                    }
+                } else {
+                    match (func, field.as_deref()) {
+                        (_, None) => "count(*)".to_string(),
+                        // the states of sums are doubles, keep both parts alike
+                        (ViewAggregateFunc::Sum, Some(field)) => {
+                            format!("sum(CAST({} AS DOUBLE))", quote(field))
+                        }
+                        (func, Some(field)) => format!("{func}({})", quote(field)),
+                    }
+                };
+                columns.push(format!(
+                    "{expr} AS {}",
+                    quote(alias.as_deref().unwrap_or_default())
+                ));
+            }
+        }
+    }
+
+    let from = if from_view {
+        // the rows of a file folded more than once are read once
+        let mut view_columns = vec![quote(TIMESTAMP_COL_NAME), quote(FOLD_COLUMN)];
+        view_columns.extend(view.dimensions.iter().map(|d| quote(d)));
+        for agg in view.aggregates.iter() {
+            view_columns.extend(
+                agg.func
+                    .states()
+                    .iter()
+                    .map(|(state, _)| quote(&agg.state_column(state))),
+            );
+        }
+        format!(
+            "(SELECT DISTINCT {} FROM {})",
+            view_columns.join(", "),
+            quote(&view.name)
+        )
+    } else {
+        quote(&view.stream_name)
+    };
+    let mut sql = format!("SELECT {} FROM {from}", columns.join(", "));
+    if let Some(selection) = matched.selection.as_ref() {
+        sql.push_str(&format!(" WHERE {selection}"));
+    }
+    sql.push_str(&format!(" GROUP BY {}", group_by.join(", ")));
+    if let Some(order_by) = matched.order_by.as_ref() {
+        sql.push_str(&format!(" {order_by}"));
+    }
+    sql
+}
+
+/// Run the sub-queries of a plan and merge their results.
+pub async fn search(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    plan: Plan,
+) -> Result<search::Response, Error> {
+    let trace_id = if trace_id.is_empty() {
+        ider::generate_trace_id()
+    } else {
+        trace_id.to_string()
+    };
+    log::info!(
+        "[trace_id {trace_id}] search answered from materialized view {org_id}/{} in {} parts",
+        plan.view_name,
+        plan.requests.len()
+    );
+    let mut responses = Vec::with_capacity(plan.requests.len());
+    for (i, req) in plan.requests.iter().enumerate() {
+        let resp = crate::service::search::search_without_views(
+            &format!("{trace_id}-{i}"),
+            org_id,
+            StreamType::Logs,
+            user_id.clone(),
+            req,
+        )
+        .await?;
+        responses.push(resp);
+    }
+    Ok(merge_responses(&trace_id, &plan, responses))
+}
+
+fn merge_responses(
+    trace_id: &str,
+    plan: &Plan,
+    mut responses: Vec<search::Response>,
+) -> search::Response {
+    if plan.descending {
+        responses.reverse();
+    }
+    let mut merged = search::Response {
+        trace_id: trace_id.to_string(),
+        histogram_interval: Some(plan.interval),
+        ..Default::default()
+    };
+    for resp in responses {
+        if merged.columns.is_empty() {
+            merged.columns = resp.columns;
+        }
+        if merged.order_by.is_none() {
+            merged.order_by = resp.order_by;
+        }
+        merged.took += resp.took;
+        merged.scan_files += resp.scan_files;
+        merged.scan_size += resp.scan_size;
+        merged.idx_scan_size += resp.idx_scan_size;
+        merged.scan_records += resp.scan_records;
+        merged.is_partial |= resp.is_partial;
+        merged.function_error.extend(resp.function_error);
+        merged.hits.extend(resp.hits);
+    }
+    if plan.size > 0 {
+        merged.hits.truncate(plan.size as usize);
+    }
+    merged.total = merged.hits.len();
+    merged.size = merged.hits.len() as i64;
+    merged
+}
+
+fn align_up(ts: i64, interval: i64) -> i64 {
+    let rem = ts.rem_euclid(interval);
+    if rem == 0 { ts } else { ts - rem + interval }
+}
+
+fn align_down(ts: i64, interval: i64) -> i64 {
+    ts - ts.rem_euclid(interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{meta::materialized_view::ViewAggregate, utils::json};
+
+    use super::*;
+
+    const MINUTE: i64 = 60_000_000;
+    const HOUR: i64 = 60 * MINUTE;
+
+    fn view() -> MaterializedView {
+        MaterializedView {
+            name: "nginx_1m".to_string(),
+            org_id: "default".to_string(),
+            stream_name: "nginx".to_string(),
+            sql: "".to_string(),
+            interval: MINUTE,
+            dimensions: vec!["status".to_string(), "region".to_string()],
+            aggregates: vec![
+                ViewAggregate {
+                    func: ViewAggregateFunc::Count,
+                    field: None,
+                    alias: "cnt".to_string(),
+                },
+                ViewAggregate {
+                    func: ViewAggregateFunc::Avg,
+                    field: Some("took".to_string()),
+                    alias: "avg_took".to_string(),
+                },
+            ],
+            created_at: 10 * HOUR + 30_000_000,
+        }
+    }
+
+    fn request(sql: &str, start_time: i64, end_time: i64) -> search::Request {
+        search::Request {
+            query: search::Query {
+                sql: sql.to_string(),
+                start_time,
+                end_time,
+                size: -1,
+                ..Default::default()
+            },
+            search_type: Some(SearchEventType::Dashboards),
+            ..Default::default()
+        }
+    }
+
+    const SQL: &str = "SELECT histogram(_timestamp, '5 minute') AS \"x_axis_1\", status, \
+                       count(_timestamp) AS \"y_axis_1\", avg(took) AS \"y_axis_2\" \
+                       FROM \"nginx\" WHERE status <> '200' GROUP BY x_axis_1, status \
+                       ORDER BY x_axis_1 DESC";
+
+    #[test]
+    fn test_plan() {
+        let views = vec![view()];
+        let req = request(SQL, 10 * HOUR + 2 * MINUTE, 12 * HOUR);
+        let plan = plan_with_views(&views, &req, |_| 11 * HOUR + 7 * MINUTE, |_| vec![]).unwrap();
+        assert_eq!(plan.interval, 300);
+        assert!(plan.descending);
+        let ranges = plan
+            .requests
+            .iter()
+            .map(|r| (r.query.start_time, r.query.end_time))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                (10 * HOUR + 2 * MINUTE, 10 * HOUR + 5 * MINUTE),
+                (10 * HOUR + 5 * MINUTE, 11 * HOUR + 5 * MINUTE),
+                (11 * HOUR + 5 * MINUTE, 12 * HOUR),
+            ]
+        );
+        assert!(
+            plan.requests
+                .iter()
+                .all(|r| r.query.histogram_interval == 300)
+        );
+        assert_eq!(
+            plan.requests[1].query.sql,
+            "SELECT histogram(\"_timestamp\", '300 second') AS \"x_axis_1\", \"status\", \
+             sum(\"cnt__count\") AS \"y_axis_1\", \
+             sum(\"avg_took__sum\") / sum(\"avg_took__count\") AS \"y_axis_2\" \
+             FROM (SELECT DISTINCT \"_timestamp\", \"_fold\", \"status\", \"region\", \
+             \"cnt__count\", \"avg_took__sum\", \"avg_took__count\" FROM \"nginx_1m\") \
+             WHERE status <> '200' GROUP BY 1, 2 ORDER BY x_axis_1 DESC"
+        );
+        assert_eq!(
+            plan.requests[0].query.sql,
+            "SELECT histogram(\"_timestamp\", '300 second') AS \"x_axis_1\", \"status\", \
+             count(*) AS \"y_axis_1\", avg(\"took\") AS \"y_axis_2\" \
+             FROM \"nginx\" WHERE status <> '200' GROUP BY 1, 2 ORDER BY x_axis_1 DESC"
+        );
+
+        // a pending fold of the view moves the watermark back
+        let plan = plan_with_views(&views, &req, |_| 10 * HOUR + 33 * MINUTE, |_| vec![]).unwrap();
+        assert_eq!(plan.requests[1].query.end_time, 10 * HOUR + 30 * MINUTE);
+        assert_eq!(plan.requests[2].query.start_time, 10 * HOUR + 30 * MINUTE);
+
+        // the base stream is read over the gaps of the view
+        let gaps = |_: &MaterializedView| {
+            vec![
+                (10 * HOUR + 12 * MINUTE, 10 * HOUR + 21 * MINUTE),
+                (10 * HOUR + 40 * MINUTE, 10 * HOUR + 41 * MINUTE),
+                (13 * HOUR, 14 * HOUR),
+            ]
+        };
+        let plan = plan_with_views(&views, &req, |_| 11 * HOUR + 7 * MINUTE, gaps).unwrap();
+        assert_eq!(plan.requests[0].query.end_time, 10 * HOUR + 45 * MINUTE);
+        assert_eq!(plan.requests[1].query.start_time, 10 * HOUR + 45 * MINUTE);
+        assert_eq!(plan.requests[1].query.end_time, 11 * HOUR + 5 * MINUTE);
+        // a gap over the whole range
+        let gaps = |_: &MaterializedView| vec![(0, 11 * HOUR + 7 * MINUTE)];
+        assert!(plan_with_views(&views, &req, |_| 11 * HOUR + 7 * MINUTE, gaps).is_none());
+    }
+
+    #[test]
+    fn test_plan_no_match() {
+        let views = vec![view()];
+        let (start, end, watermark) = (10 * HOUR, 12 * HOUR, 11 * HOUR);
+        let cases = [
+            // dimension the view does not keep
+            SQL.replace("status", "host"),
+            // aggregate the view does not keep
+            SQL.replace("avg(took)", "max(took)"),
+            // filter on a non dimension field
+            SQL.replace("status <> '200'", "took > 10"),
+            // filter with a function
+            SQL.replace("status <> '200'", "match_all('error')"),
+            // interval finer than the view
+            SQL.replace("5 minute", "30 second"),
+            // calendar interval
+            SQL.replace("5 minute", "1 month"),
+            // timezone
+            SQL.replace("'5 minute'", "'5 minute', 'Asia/Kolkata'"),
+            // ordered by an aggregate first
+            SQL.replace("ORDER BY x_axis_1", "ORDER BY y_axis_1"),
+            // no alias
+            SQL.replace("AS \"y_axis_2\"", ""),
+            format!("{SQL} LIMIT 10"),
+        ];
+        for sql in cases {
+            let req = request(&sql, start, end);
+            assert!(
+                plan_with_views(&views, &req, |_| watermark, |_| vec![]).is_none(),
+                "{sql}"
+            );
+        }
+
+        // not a dashboard query
+        let mut req = request(SQL, start, end);
+        req.search_type = Some(SearchEventType::UI);
+        assert!(plan("default", StreamType::Logs, &req).is_none());
+
+        // range not materialized yet
+        let req = request(SQL, 11 * HOUR + 2 * MINUTE, end);
+        assert!(plan_with_views(&views, &req, |_| watermark, |_| vec![]).is_none());
+        // range before the view was created
+        let req = request(SQL, 9 * HOUR, 10 * HOUR);
+        assert!(plan_with_views(&views, &req, |_| watermark, |_| vec![]).is_none());
+    }
+
+    #[test]
+    fn test_merge_responses() {
+        let plan = Plan {
+            view_name: "nginx_1m".to_string(),
+            requests: vec![],
+            interval: 300,
+            descending: true,
+            size: 3,
+        };
+        let response = |hits: Vec<i64>| search::Response {
+            hits: hits.into_iter().map(|v| json::json!({ "x": v })).collect(),
+            scan_size: 1,
+            took: 2,
+            ..Default::default()
+        };
+        let merged = merge_responses(
+            "trace",
+            &plan,
+            vec![
+                response(vec![1]),
+                response(vec![3, 2]),
+                response(vec![5, 4]),
+            ],
+        );
+        let hits = merged
+            .hits
+            .iter()
+            .map(|h| h["x"].as_i64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(hits, vec![5, 4, 3]);
+        assert_eq!(merged.total, 3);
+        assert_eq!(merged.scan_size, 3);
+        assert_eq!(merged.took, 6);
+        assert_eq!(merged.histogram_interval, Some(300));
+    }
+
+    #[test]
+    fn test_align() {
+        assert_eq!(align_up(61, 60), 120);
+        assert_eq!(align_up(120, 60), 120);
+        assert_eq!(align_down(119, 60), 60);
+    }
+}
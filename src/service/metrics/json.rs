@@ -40,6 +40,7 @@ use config::{
 };
 use datafusion::arrow::datatypes::Schema;
 use infra::schema::{SchemaCache, get_partition_time_level};
+use proto::cluster_rpc;
 
 use super::get_exclude_labels;
 use crate::{
@@ -53,7 +54,7 @@ use crate::{
         db, format_stream_name,
         ingestion::{
             TriggerAlertData, check_ingestion_allowed, evaluate_trigger, get_thread_id,
-            get_write_partition_key, write_file,
+            get_write_partition_key, ingestion_service, write_file,
         },
         pipeline::batch_execution::ExecutablePipeline,
         schema::check_for_schema,
@@ -63,6 +64,25 @@ use crate::{
 
 const VALID_METRICS_TYPES: &[&str] = &["counter", "gauge", "histogram", "summary"];
 
+/// Ingests metric records produced inside the cluster, e.g. by recording rules
+/// or scrape jobs, by forwarding them to an ingester. The stream of each record
+/// is taken from its `__name__`.
+pub async fn ingest_by_ingester(org_id: &str, records: Vec<json::Value>) -> Result<()> {
+    let req = cluster_rpc::IngestionRequest {
+        org_id: org_id.to_string(),
+        stream_name: "".to_string(),
+        stream_type: StreamType::Metrics.to_string(),
+        data: Some(cluster_rpc::IngestionData::from(records)),
+        ingestion_type: Some(cluster_rpc::IngestionType::Json.into()),
+        metadata: None,
+    };
+    match ingestion_service::ingest(req).await {
+        Ok(resp) if resp.status_code == 200 => Ok(()),
+        Ok(resp) => Err(anyhow!(resp.message)),
+        Err(e) => Err(e.into()),
+    }
+}
+
 pub async fn ingest(
     org_id: &str,
     stream_name: Option<&str>,
@@ -14,7 +14,9 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use config::{
-    meta::promql::{EXEMPLARS_LABEL, HASH_LABEL, METADATA_LABEL, Metadata, VALUE_LABEL},
+    meta::promql::{
+        EXEMPLARS_LABEL, HASH_LABEL, METADATA_LABEL, Metadata, NATIVE_HISTOGRAM_LABEL, VALUE_LABEL,
+    },
     utils::hash::{Sum64, gxhash},
 };
 use datafusion::arrow::datatypes::Schema;
@@ -23,11 +25,16 @@ pub mod json;
 pub mod otlp;
 mod otlp_json_compat;
 pub mod prom;
+pub mod remote_read;
+pub mod rules;
+pub mod scrape;
+pub mod statsd;
 
-const EXCLUDE_LABELS: [&str; 8] = [
+const EXCLUDE_LABELS: [&str; 9] = [
     VALUE_LABEL,
     HASH_LABEL,
     EXEMPLARS_LABEL,
+    NATIVE_HISTOGRAM_LABEL,
     "is_monotonic",
     "trace_id",
     "span_id",
@@ -45,9 +45,9 @@ use infra::{
     errors::{Error, Result},
     schema::{SchemaCache, get_partition_time_level},
 };
-use promql_parser::{label::MatchOp, parser};
+use promql_parser::parser;
 use prost::Message;
-use proto::prometheus_rpc;
+use proto::{prometheus_rpc, prometheus_write_v2_rpc};
 
 use crate::{
     common::{
@@ -67,11 +67,58 @@ use crate::{
     },
 };
 
+const REMOTE_WRITE_V1_PROTO: &str = "prometheus.WriteRequest";
+const REMOTE_WRITE_V2_PROTO: &str = "io.prometheus.write.v2.Request";
+
+/// Remote write protocol of a request, negotiated with the `Content-Type`
+/// header, refer: https://prometheus.io/docs/concepts/remote_write_spec_2_0/#protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteWriteProtocol {
+    V1,
+    V2,
+}
+
+impl RemoteWriteProtocol {
+    /// Returns `None` for unsupported media types or protobuf messages. A
+    /// missing `proto` parameter means the 1.0 `prometheus.WriteRequest`.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mut parts = content_type.split(';');
+        let media_type = parts.next().unwrap_or_default().trim();
+        if !media_type.eq_ignore_ascii_case("application/x-protobuf") {
+            return None;
+        }
+        let mut protocol = Self::V1;
+        for param in parts {
+            let Some((key, value)) = param.split_once('=') else {
+                continue;
+            };
+            if key.trim().eq_ignore_ascii_case("proto") {
+                protocol = match value.trim().trim_matches('"') {
+                    REMOTE_WRITE_V1_PROTO => Self::V1,
+                    REMOTE_WRITE_V2_PROTO => Self::V2,
+                    _ => return None,
+                };
+            }
+        }
+        Some(protocol)
+    }
+}
+
+/// Number of samples, histograms and exemplars written by a remote write
+/// request, reported in the 2.0 response headers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WriteStats {
+    pub samples: u64,
+    pub histograms: u64,
+    pub exemplars: u64,
+}
+
 pub async fn remote_write(
     org_id: &str,
     body: Bytes,
     user: IngestUser,
-) -> std::result::Result<(), anyhow::Error> {
+    protocol: RemoteWriteProtocol,
+) -> std::result::Result<WriteStats, anyhow::Error> {
     // check system resource
     check_ingestion_allowed(org_id, StreamType::Metrics, None).await?;
 
@@ -107,8 +154,18 @@ pub async fn remote_write(
     let decoded = snap::raw::Decoder::new()
         .decompress_vec(&body)
         .map_err(|e| anyhow::anyhow!("Invalid snappy compressed data: {e}"))?;
-    let request = prometheus_rpc::WriteRequest::decode(bytes::Bytes::from(decoded))
-        .map_err(|e| anyhow::anyhow!("Invalid protobuf: {e}"))?;
+    let request = match protocol {
+        RemoteWriteProtocol::V1 => {
+            prometheus_rpc::WriteRequest::decode(bytes::Bytes::from(decoded))
+                .map_err(|e| anyhow::anyhow!("Invalid protobuf: {e}"))?
+        }
+        RemoteWriteProtocol::V2 => {
+            let request = prometheus_write_v2_rpc::Request::decode(bytes::Bytes::from(decoded))
+                .map_err(|e| anyhow::anyhow!("Invalid protobuf: {e}"))?;
+            v2_to_write_request(request)?
+        }
+    };
+    let mut write_stats = WriteStats::default();
 
     // records buffer
     let mut json_data_by_stream: HashMap<String, Vec<_>> = HashMap::new();
@@ -166,7 +223,7 @@ pub async fn remote_write(
                 "",
             ])
             .inc();
-        return Ok(());
+        return Ok(write_stats);
     }
 
     // parse timeseries
@@ -297,11 +354,26 @@ pub async fn remote_write(
         // Note: All configurations (pipeline, UDS, schema, partition, alerts) are now pre-loaded
         // before the loop to avoid repeated async queries
 
+        // native histograms are stored next to the float samples of the
+        // series, with the observation count as their value
+        let histograms = event.histograms.iter().map(|h| {
+            let histogram = NativeHistogram::from(h);
+            (h.timestamp, histogram.count, Some(histogram))
+        });
+        let points = event
+            .samples
+            .iter()
+            .map(|s| (s.timestamp, s.value, None))
+            .chain(histograms)
+            .collect::<Vec<_>>();
+        // exemplars are attached to the latest point of the series
+        let last_point = points.len().saturating_sub(1);
+        let mut exemplars = Some(exemplars_to_json(&event.exemplars)).filter(|v| !v.is_empty());
+
         // parse samples
         let sample_start = std::time::Instant::now();
-        for sample in event.samples {
+        for (idx, (sample_ts, mut sample_val, histogram)) in points.into_iter().enumerate() {
             sample_count += 1;
-            let mut sample_val = sample.value;
             // revisit in future
             if sample_val.is_infinite() {
                 if sample_val == f64::INFINITY || sample_val > f64::MAX {
@@ -370,15 +442,32 @@ pub async fn remote_write(
                         "",
                     ])
                     .inc();
-                return Ok(());
+                return Ok(WriteStats::default());
             }
 
             let mut value: json::Value = json::to_value(&metric).unwrap();
-            let timestamp = parse_i64_to_timestamp_micros(sample.timestamp);
-            value.as_object_mut().unwrap().insert(
+            let timestamp = parse_i64_to_timestamp_micros(sample_ts);
+            let value_map = value.as_object_mut().unwrap();
+            value_map.insert(
                 TIMESTAMP_COL_NAME.to_string(),
                 json::Value::Number(timestamp.into()),
             );
+            match histogram {
+                Some(histogram) => {
+                    value_map.insert(
+                        NATIVE_HISTOGRAM_LABEL.to_string(),
+                        json::Value::String(json::to_string(&histogram).unwrap()),
+                    );
+                    write_stats.histograms += 1;
+                }
+                None => write_stats.samples += 1,
+            }
+            if idx == last_point
+                && let Some(exemplars) = exemplars.take()
+            {
+                write_stats.exemplars += exemplars.len() as u64;
+                value_map.insert(EXEMPLARS_LABEL.to_string(), json::Value::Array(exemplars));
+            }
 
             // ready to be buffered for downstream processing
             if stream_executable_pipelines
@@ -512,7 +601,10 @@ pub async fn remote_write(
         let partition_time_level = get_partition_time_level(StreamType::Metrics);
 
         for (mut val_map, timestamp) in json_data {
-            let hash = super::signature_without_labels(&val_map, &[VALUE_LABEL]);
+            let hash = super::signature_without_labels(
+                &val_map,
+                &[VALUE_LABEL, EXEMPLARS_LABEL, NATIVE_HISTOGRAM_LABEL],
+            );
             val_map.insert(HASH_LABEL.to_string(), json::Value::Number(hash.into()));
             val_map.insert(
                 TIMESTAMP_COL_NAME.to_string(),
@@ -738,7 +830,158 @@ pub async fn remote_write(
         log::info!("[remote_write] org: {org_id}, total time: {total_ms} ms");
     }
 
-    Ok(())
+    Ok(write_stats)
+}
+
+/// Converts a Remote Write 2.0 request into the 1.0 model by resolving the
+/// symbol references. The per series metadata is collected into the request
+/// metadata, keyed by metric name.
+fn v2_to_write_request(
+    request: prometheus_write_v2_rpc::Request,
+) -> std::result::Result<prometheus_rpc::WriteRequest, anyhow::Error> {
+    let symbols = &request.symbols;
+    let mut metadata: HashMap<String, prometheus_rpc::MetricMetadata> = HashMap::new();
+    let mut timeseries = Vec::with_capacity(request.timeseries.len());
+    for series in request.timeseries.iter() {
+        let labels = resolve_labels(symbols, &series.labels_refs)?;
+        if let Some(md) = series.metadata
+            && (md.r#type != 0 || md.help_ref != 0 || md.unit_ref != 0)
+            && let Some(name) = labels.iter().find(|l| l.name == NAME_LABEL)
+            && !metadata.contains_key(&name.value)
+        {
+            // the metric types of both versions share the same numbering
+            metadata.insert(
+                name.value.clone(),
+                prometheus_rpc::MetricMetadata {
+                    r#type: md.r#type,
+                    metric_family_name: name.value.clone(),
+                    help: resolve_symbol(symbols, md.help_ref)?,
+                    unit: resolve_symbol(symbols, md.unit_ref)?,
+                },
+            );
+        }
+        let exemplars = series
+            .exemplars
+            .iter()
+            .map(|e| {
+                Ok(prometheus_rpc::Exemplar {
+                    labels: resolve_labels(symbols, &e.labels_refs)?,
+                    value: e.value,
+                    timestamp: e.timestamp,
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, anyhow::Error>>()?;
+        timeseries.push(prometheus_rpc::TimeSeries {
+            labels,
+            samples: series
+                .samples
+                .iter()
+                .map(|s| prometheus_rpc::Sample {
+                    value: s.value,
+                    timestamp: s.timestamp,
+                })
+                .collect(),
+            exemplars,
+            histograms: series.histograms.iter().map(v2_to_histogram).collect(),
+        });
+    }
+    Ok(prometheus_rpc::WriteRequest {
+        timeseries,
+        metadata: metadata.into_values().collect(),
+    })
+}
+
+fn v2_to_histogram(h: &prometheus_write_v2_rpc::Histogram) -> prometheus_rpc::Histogram {
+    use prometheus_rpc::histogram::{Count, ZeroCount};
+    use prometheus_write_v2_rpc::histogram as v2;
+
+    let spans = |spans: &[prometheus_write_v2_rpc::BucketSpan]| {
+        spans
+            .iter()
+            .map(|s| prometheus_rpc::BucketSpan {
+                offset: s.offset,
+                length: s.length,
+            })
+            .collect()
+    };
+    prometheus_rpc::Histogram {
+        count: h.count.map(|c| match c {
+            v2::Count::CountInt(v) => Count::CountInt(v),
+            v2::Count::CountFloat(v) => Count::CountFloat(v),
+        }),
+        sum: h.sum,
+        schema: h.schema,
+        zero_threshold: h.zero_threshold,
+        zero_count: h.zero_count.map(|c| match c {
+            v2::ZeroCount::ZeroCountInt(v) => ZeroCount::ZeroCountInt(v),
+            v2::ZeroCount::ZeroCountFloat(v) => ZeroCount::ZeroCountFloat(v),
+        }),
+        negative_spans: spans(&h.negative_spans),
+        negative_deltas: h.negative_deltas.clone(),
+        negative_counts: h.negative_counts.clone(),
+        positive_spans: spans(&h.positive_spans),
+        positive_deltas: h.positive_deltas.clone(),
+        positive_counts: h.positive_counts.clone(),
+        // the reset hints of both versions share the same numbering
+        reset_hint: h.reset_hint,
+        timestamp: h.timestamp,
+        custom_values: h.custom_values.clone(),
+    }
+}
+
+fn resolve_symbol(symbols: &[String], idx: u32) -> std::result::Result<String, anyhow::Error> {
+    match symbols.get(idx as usize) {
+        Some(v) => Ok(v.clone()),
+        // the first symbol is always the empty string, tolerate senders that
+        // do not send any symbols
+        None if idx == 0 => Ok(String::new()),
+        None => Err(anyhow::anyhow!(
+            "Invalid symbol reference {idx}, symbols table has {} entries",
+            symbols.len()
+        )),
+    }
+}
+
+fn resolve_labels(
+    symbols: &[String],
+    refs: &[u32],
+) -> std::result::Result<Vec<prometheus_rpc::Label>, anyhow::Error> {
+    if refs.len() % 2 != 0 {
+        return Err(anyhow::anyhow!(
+            "Invalid labels references, expected name and value pairs"
+        ));
+    }
+    refs.chunks_exact(2)
+        .map(|pair| {
+            Ok(prometheus_rpc::Label {
+                name: resolve_symbol(symbols, pair[0])?,
+                value: resolve_symbol(symbols, pair[1])?,
+            })
+        })
+        .collect()
+}
+
+/// Converts exemplars to the layout of the exemplars column, like OTLP
+/// ingestion does.
+fn exemplars_to_json(exemplars: &[prometheus_rpc::Exemplar]) -> Vec<json::Value> {
+    exemplars
+        .iter()
+        .map(|e| {
+            let mut rec = json::Map::new();
+            for label in e.labels.iter() {
+                rec.insert(
+                    format_label_name(&label.name),
+                    json::Value::String(label.value.clone()),
+                );
+            }
+            rec.insert(VALUE_LABEL.to_string(), e.value.into());
+            rec.insert(
+                TIMESTAMP_COL_NAME.to_string(),
+                parse_i64_to_timestamp_micros(e.timestamp).into(),
+            );
+            json::Value::Object(rec)
+        })
+        .collect()
 }
 
 pub(crate) async fn get_metadata(org_id: &str, req: RequestMetadata) -> Result<ResponseMetadata> {
@@ -848,7 +1091,12 @@ pub(crate) async fn get_series(
         .fields()
         .iter()
         .map(|f| f.name().as_str())
-        .filter(|&s| s != TIMESTAMP_COL_NAME && s != VALUE_LABEL && s != HASH_LABEL)
+        .filter(|&s| {
+            s != TIMESTAMP_COL_NAME
+                && s != VALUE_LABEL
+                && s != HASH_LABEL
+                && s != NATIVE_HISTOGRAM_LABEL
+        })
         .collect::<Vec<_>>()
         .join("\", \"");
     if label_names.is_empty() {
@@ -856,30 +1104,8 @@ pub(crate) async fn get_series(
     }
 
     let mut sql = format!("SELECT DISTINCT({HASH_LABEL}), \"{label_names}\" FROM {metric_name}");
-    let mut sql_where = Vec::new();
     if let Some(selector) = selector {
-        for mat in selector.matchers.matchers.iter() {
-            if mat.name == TIMESTAMP_COL_NAME
-                || mat.name == VALUE_LABEL
-                || schema.field_with_name(&mat.name).is_err()
-            {
-                continue;
-            }
-            match &mat.op {
-                MatchOp::Equal => {
-                    sql_where.push(format!("{} = '{}'", mat.name, mat.value));
-                }
-                MatchOp::NotEqual => {
-                    sql_where.push(format!("{} != '{}'", mat.name, mat.value));
-                }
-                MatchOp::Re(_re) => {
-                    sql_where.push(format!("re_match({}, '{}')", mat.name, mat.value));
-                }
-                MatchOp::NotRe(_re) => {
-                    sql_where.push(format!("re_not_match({}, '{}')", mat.name, mat.value));
-                }
-            }
-        }
+        let sql_where = crate::service::promql::utils::matchers_to_sql(&schema, &selector.matchers);
         if !sql_where.is_empty() {
             sql.push_str(" WHERE ");
             sql.push_str(&sql_where.join(" AND "));
@@ -951,7 +1177,12 @@ pub(crate) async fn get_labels(
                 .fields()
                 .iter()
                 .map(|f| f.name())
-                .filter(|&s| s != TIMESTAMP_COL_NAME && s != VALUE_LABEL && s != HASH_LABEL)
+                .filter(|&s| {
+                    s != TIMESTAMP_COL_NAME
+                        && s != VALUE_LABEL
+                        && s != HASH_LABEL
+                        && s != NATIVE_HISTOGRAM_LABEL
+                })
                 .cloned();
             label_names.extend(field_names);
         }
@@ -1033,33 +1264,15 @@ pub(crate) async fn get_label_values(
 
     // Build SQL query with optional WHERE clause based on selector matchers
     let mut sql = format!("SELECT DISTINCT({label_name}) FROM {metric_name}");
-    let mut sql_where = Vec::new();
-
     if let Some(selector) = selector {
-        for mat in selector.matchers.matchers.iter() {
-            // Skip special fields and fields that don't exist in the schema
-            if mat.name == TIMESTAMP_COL_NAME
-                || mat.name == VALUE_LABEL
-                || mat.name == NAME_LABEL
-                || schema.field_with_name(&mat.name).is_err()
-            {
-                continue;
-            }
-            match &mat.op {
-                MatchOp::Equal => {
-                    sql_where.push(format!("{} = '{}'", mat.name, mat.value));
-                }
-                MatchOp::NotEqual => {
-                    sql_where.push(format!("{} != '{}'", mat.name, mat.value));
-                }
-                MatchOp::Re(_re) => {
-                    sql_where.push(format!("re_match({}, '{}')", mat.name, mat.value));
-                }
-                MatchOp::NotRe(_re) => {
-                    sql_where.push(format!("re_not_match({}, '{}')", mat.name, mat.value));
-                }
-            }
+        // the metric name already selected the stream, skip it like the other
+        // special fields
+        let mut matchers = selector.matchers.clone();
+        matchers.matchers.retain(|mat| mat.name != NAME_LABEL);
+        for group in matchers.or_matchers.iter_mut() {
+            group.retain(|mat| mat.name != NAME_LABEL);
         }
+        let sql_where = crate::service::promql::utils::matchers_to_sql(&schema, &matchers);
         if !sql_where.is_empty() {
             sql.push_str(" WHERE ");
             sql.push_str(&sql_where.join(" AND "));
@@ -1280,4 +1493,130 @@ mod tests {
         };
         assert_eq!(try_into_metric_name(&sel), Some("direct_name".to_string()));
     }
+
+    #[test]
+    fn test_remote_write_protocol_from_content_type() {
+        assert_eq!(
+            RemoteWriteProtocol::from_content_type("application/x-protobuf"),
+            Some(RemoteWriteProtocol::V1)
+        );
+        assert_eq!(
+            RemoteWriteProtocol::from_content_type(
+                "application/x-protobuf;proto=prometheus.WriteRequest"
+            ),
+            Some(RemoteWriteProtocol::V1)
+        );
+        assert_eq!(
+            RemoteWriteProtocol::from_content_type(
+                "application/x-protobuf; proto=io.prometheus.write.v2.Request"
+            ),
+            Some(RemoteWriteProtocol::V2)
+        );
+        assert_eq!(
+            RemoteWriteProtocol::from_content_type("application/x-protobuf;proto=foo.Request"),
+            None
+        );
+        assert_eq!(
+            RemoteWriteProtocol::from_content_type("application/json"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_v2_to_write_request() {
+        use prometheus_write_v2_rpc as v2;
+
+        let symbols = [
+            "",
+            "__name__",
+            "http_latency",
+            "job",
+            "api",
+            "trace_id",
+            "abc",
+            "help",
+        ];
+        let request = v2::Request {
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            timeseries: vec![v2::TimeSeries {
+                labels_refs: vec![1, 2, 3, 4],
+                samples: vec![],
+                histograms: vec![v2::Histogram {
+                    count: Some(v2::histogram::Count::CountInt(3)),
+                    schema: 0,
+                    positive_spans: vec![v2::BucketSpan {
+                        offset: 1,
+                        length: 2,
+                    }],
+                    positive_deltas: vec![1, 1],
+                    reset_hint: v2::histogram::ResetHint::No as i32,
+                    timestamp: 1_700_000_000_000,
+                    ..Default::default()
+                }],
+                exemplars: vec![v2::Exemplar {
+                    labels_refs: vec![5, 6],
+                    value: 0.25,
+                    timestamp: 1_700_000_000_000,
+                }],
+                metadata: Some(v2::Metadata {
+                    r#type: v2::metadata::MetricType::Histogram as i32,
+                    help_ref: 7,
+                    unit_ref: 0,
+                }),
+                created_timestamp: 0,
+            }],
+        };
+        let req = v2_to_write_request(request).unwrap();
+        let series = &req.timeseries[0];
+        assert_eq!(
+            series
+                .labels
+                .iter()
+                .map(|l| (l.name.as_str(), l.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("__name__", "http_latency"), ("job", "api")]
+        );
+        assert_eq!(series.exemplars[0].labels[0].name, "trace_id");
+        let histogram = NativeHistogram::from(&series.histograms[0]);
+        assert_eq!(histogram.count, 3.0);
+        assert_eq!(histogram.positive_buckets, vec![1.0, 2.0]);
+        assert_eq!(histogram.reset_hint, "NO");
+        assert_eq!(req.metadata.len(), 1);
+        assert_eq!(req.metadata[0].metric_family_name, "http_latency");
+        assert_eq!(
+            req.metadata[0].r#type(),
+            prometheus_rpc::metric_metadata::MetricType::Histogram
+        );
+        assert_eq!(req.metadata[0].help, "help");
+
+        // references out of the symbols table are rejected
+        let request = v2::Request {
+            symbols: vec!["".to_string()],
+            timeseries: vec![v2::TimeSeries {
+                labels_refs: vec![1, 2],
+                ..Default::default()
+            }],
+        };
+        assert!(v2_to_write_request(request).is_err());
+    }
+
+    #[test]
+    fn test_exemplars_to_json() {
+        let exemplars = exemplars_to_json(&[prometheus_rpc::Exemplar {
+            labels: vec![prometheus_rpc::Label {
+                name: "trace_id".to_string(),
+                value: "abc".to_string(),
+            }],
+            value: 0.5,
+            timestamp: 1_700_000_000_000,
+        }]);
+        assert_eq!(
+            exemplars,
+            vec![json::json!({
+                "trace_id": "abc",
+                "value": 0.5,
+                "_timestamp": 1_700_000_000_000_000i64,
+            })]
+        );
+    }
 }
@@ -0,0 +1,774 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus remote read, refer:
+//! https://prometheus.io/docs/prometheus/latest/querying/remote_read_api/
+
+use std::collections::HashMap;
+
+use config::{
+    TIMESTAMP_COL_NAME, get_config,
+    meta::{
+        promql::{HASH_LABEL, NAME_LABEL, VALUE_LABEL},
+        search,
+        stream::StreamType,
+    },
+    utils::{json, schema::format_stream_name},
+};
+use infra::errors::{Error, Result};
+use promql_parser::label::{MatchOp, Matcher, Matchers};
+use prost::Message;
+use proto::prometheus_rpc::{
+    self, ChunkedReadResponse, ChunkedSeries, Label, ReadRequest, ReadResponse, Sample, TimeSeries,
+    chunk, label_matcher, read_request::ResponseType,
+};
+
+use crate::service::{
+    db,
+    promql::utils::{matchers_to_sql, matches_empty},
+    search as search_service,
+};
+
+pub const CONTENT_TYPE_STREAMED: &str =
+    "application/x-streamed-protobuf; proto=prometheus.ChunkedReadResponse";
+
+/// Max number of samples per XOR chunk, same as Prometheus
+const SAMPLES_PER_CHUNK: usize = 120;
+
+/// Max size of the chunks sent in a single frame, same as Prometheus
+const MAX_FRAME_BYTES: usize = 1024 * 1024;
+
+/// Decodes a snappy compressed `ReadRequest`.
+pub fn decode_request(body: &[u8]) -> std::result::Result<ReadRequest, anyhow::Error> {
+    let decompressed = snap::raw::Decoder::new().decompress_vec(body)?;
+    Ok(ReadRequest::decode(decompressed.as_slice())?)
+}
+
+/// Picks the first accepted response type, `SAMPLES` when the client does not
+/// negotiate. Both response types are implemented, so only unknown values are
+/// rejected.
+pub fn negotiate_response_type(req: &ReadRequest) -> std::result::Result<ResponseType, String> {
+    match req.accepted_response_types.first() {
+        None => Ok(ResponseType::Samples),
+        Some(v) => ResponseType::try_from(*v).map_err(|_| {
+            format!(
+                "server does not support any of the requested response types: {:?}",
+                req.accepted_response_types
+            )
+        }),
+    }
+}
+
+/// Returns the series matching a query, with labels and series sorted.
+pub async fn query(
+    trace_id: &str,
+    org_id: &str,
+    user_id: &str,
+    query: &prometheus_rpc::Query,
+) -> Result<Vec<TimeSeries>> {
+    let matchers = convert_matchers(&query.matchers)?;
+    let start_time = query.start_timestamp_ms * 1000;
+    // the end of a remote read query is inclusive
+    let end_time = query.end_timestamp_ms * 1000 + 1;
+
+    // a sample is a row, the limit is checked by asking for one more row
+    let max_samples = get_config().limit.prometheus_remote_read_max_samples;
+    let mut remaining = max_samples;
+    let mut series = Vec::new();
+    for stream in matching_streams(org_id, &matchers).await? {
+        let schema = infra::schema::get(org_id, &stream, StreamType::Metrics).await?;
+        if schema.fields().is_empty() {
+            continue;
+        }
+        // a matcher on a label that the stream does not have is evaluated
+        // against the empty value, like in Prometheus
+        if matchers
+            .iter()
+            .any(|m| schema.field_with_name(&m.name).is_err() && !matches_empty(m))
+        {
+            continue;
+        }
+        let conditions = matchers_to_sql(&schema, &Matchers::new(matchers.clone()));
+        let mut sql = format!("SELECT * FROM \"{stream}\"");
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        let req = search::Request {
+            query: search::Query {
+                sql,
+                from: 0,
+                size: remaining + 1,
+                start_time,
+                end_time,
+                ..Default::default()
+            },
+            search_type: Some(search::SearchEventType::Other),
+            use_cache: search::default_use_cache(),
+            ..Default::default()
+        };
+        let resp = search_service::search(
+            trace_id,
+            org_id,
+            StreamType::Metrics,
+            Some(user_id.to_string()),
+            &req,
+        )
+        .await?;
+        if resp.hits.len() as i64 > remaining {
+            return Err(Error::Message(format!(
+                "remote read query exceeded the limit of {max_samples} samples, narrow the matchers or time range"
+            )));
+        }
+        remaining -= resp.hits.len() as i64;
+        series.extend(rows_to_series(&stream, resp.hits));
+    }
+    series.sort_by(|a, b| label_pairs(&a.labels).cmp(&label_pairs(&b.labels)));
+    Ok(series)
+}
+
+/// Encodes the `SAMPLES` response, a snappy compressed `ReadResponse`.
+pub fn encode_samples_response(
+    results: Vec<Vec<TimeSeries>>,
+) -> std::result::Result<Vec<u8>, snap::Error> {
+    let resp = ReadResponse {
+        results: results
+            .into_iter()
+            .map(|timeseries| prometheus_rpc::QueryResult { timeseries })
+            .collect(),
+    };
+    snap::raw::Encoder::new().compress_vec(&resp.encode_to_vec())
+}
+
+/// Encodes a series as `STREAMED_XOR_CHUNKS` frames. Large series are split
+/// across several frames.
+pub fn encode_chunked_frames(query_index: i64, series: &TimeSeries) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut chunks = Vec::new();
+    let mut size = 0;
+    for chunk in xor_chunks(&series.samples) {
+        size += chunk.data.len();
+        chunks.push(chunk);
+        if size >= MAX_FRAME_BYTES {
+            write_frame(
+                &mut out,
+                query_index,
+                &series.labels,
+                std::mem::take(&mut chunks),
+            );
+            size = 0;
+        }
+    }
+    if !chunks.is_empty() {
+        write_frame(&mut out, query_index, &series.labels, chunks);
+    }
+    out
+}
+
+/// Writes a frame: the uvarint size of the message, its big endian CRC32
+/// Castagnoli checksum and the message itself.
+fn write_frame(
+    out: &mut Vec<u8>,
+    query_index: i64,
+    labels: &[Label],
+    chunks: Vec<prometheus_rpc::Chunk>,
+) {
+    let msg = ChunkedReadResponse {
+        chunked_series: vec![ChunkedSeries {
+            labels: labels.to_vec(),
+            chunks,
+        }],
+        query_index,
+    }
+    .encode_to_vec();
+    put_uvarint(out, msg.len() as u64);
+    out.extend_from_slice(&crc32c(&msg).to_be_bytes());
+    out.extend_from_slice(&msg);
+}
+
+fn convert_matchers(matchers: &[prometheus_rpc::LabelMatcher]) -> Result<Vec<Matcher>> {
+    matchers
+        .iter()
+        .map(|m| {
+            let op = match label_matcher::Type::try_from(m.r#type) {
+                Ok(label_matcher::Type::Eq) => MatchOp::Equal,
+                Ok(label_matcher::Type::Neq) => MatchOp::NotEqual,
+                Ok(label_matcher::Type::Re) => MatchOp::Re(anchored_regex(&m.value)?),
+                Ok(label_matcher::Type::Nre) => MatchOp::NotRe(anchored_regex(&m.value)?),
+                Err(_) => {
+                    return Err(Error::Message(format!(
+                        "unknown label matcher type: {}",
+                        m.r#type
+                    )));
+                }
+            };
+            Ok(Matcher {
+                op,
+                name: m.name.clone(),
+                value: m.value.clone(),
+            })
+        })
+        .collect()
+}
+
+fn anchored_regex(re: &str) -> Result<regex::Regex> {
+    regex::Regex::new(&format!("^(?:{re})$"))
+        .map_err(|e| Error::Message(format!("invalid regex '{re}': {e}")))
+}
+
+fn is_match(m: &Matcher, value: &str) -> bool {
+    match &m.op {
+        MatchOp::Equal => m.value == value,
+        MatchOp::NotEqual => m.value != value,
+        MatchOp::Re(re) => re.is_match(value),
+        MatchOp::NotRe(re) => !re.is_match(value),
+    }
+}
+
+/// Resolves the metric streams selected by the `__name__` matchers.
+async fn matching_streams(org_id: &str, matchers: &[Matcher]) -> Result<Vec<String>> {
+    let name_matchers = matchers
+        .iter()
+        .filter(|m| m.name == NAME_LABEL)
+        .collect::<Vec<_>>();
+    if let Some(m) = name_matchers
+        .iter()
+        .find(|m| matches!(m.op, MatchOp::Equal))
+    {
+        return Ok(vec![format_stream_name(m.value.clone())]);
+    }
+    let streams = db::schema::list(org_id, Some(StreamType::Metrics), false)
+        .await
+        .map_err(|e| Error::Message(e.to_string()))?;
+    Ok(streams
+        .into_iter()
+        .map(|s| s.stream_name)
+        .filter(|name| name_matchers.iter().all(|m| is_match(m, name)))
+        .collect())
+}
+
+/// Groups search hits into series by their hash, samples sorted by time.
+fn rows_to_series(stream: &str, rows: Vec<json::Value>) -> Vec<TimeSeries> {
+    let mut series: HashMap<String, TimeSeries> = HashMap::new();
+    for row in rows {
+        let json::Value::Object(row) = row else {
+            continue;
+        };
+        let Some(timestamp) = row.get(TIMESTAMP_COL_NAME).and_then(json::Value::as_i64) else {
+            continue;
+        };
+        let Some(value) = row.get(VALUE_LABEL).and_then(json::Value::as_f64) else {
+            continue;
+        };
+        let key = row
+            .get(HASH_LABEL)
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        series
+            .entry(key)
+            .or_insert_with(|| TimeSeries {
+                labels: row_labels(stream, &row),
+                ..Default::default()
+            })
+            .samples
+            .push(Sample {
+                value,
+                timestamp: timestamp / 1000,
+            });
+    }
+    series
+        .into_values()
+        .map(|mut ts| {
+            ts.samples.sort_by_key(|s| s.timestamp);
+            ts
+        })
+        .collect()
+}
+
+fn row_labels(stream: &str, row: &json::Map<String, json::Value>) -> Vec<Label> {
+    let mut labels = row
+        .iter()
+        .filter(|(k, _)| !super::EXCLUDE_LABELS.contains(&k.as_str()))
+        .filter_map(|(k, v)| match v {
+            json::Value::String(v) if !v.is_empty() => Some(Label {
+                name: k.to_string(),
+                value: v.to_string(),
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !labels.iter().any(|l| l.name == NAME_LABEL) {
+        labels.push(Label {
+            name: NAME_LABEL.to_string(),
+            value: stream.to_string(),
+        });
+    }
+    labels.sort_by(|a, b| a.name.cmp(&b.name));
+    labels
+}
+
+fn label_pairs(labels: &[Label]) -> Vec<(&str, &str)> {
+    labels
+        .iter()
+        .map(|l| (l.name.as_str(), l.value.as_str()))
+        .collect()
+}
+
+/// Encodes samples into Prometheus XOR (gorilla) chunks.
+fn xor_chunks(samples: &[Sample]) -> Vec<prometheus_rpc::Chunk> {
+    samples
+        .chunks(SAMPLES_PER_CHUNK)
+        .map(|samples| {
+            let mut enc = XorEncoder::default();
+            for s in samples {
+                enc.append(s.timestamp, s.value);
+            }
+            prometheus_rpc::Chunk {
+                min_time_ms: samples[0].timestamp,
+                max_time_ms: samples[samples.len() - 1].timestamp,
+                r#type: chunk::Encoding::Xor as i32,
+                data: enc.finish(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct BitWriter {
+    buf: Vec<u8>,
+    /// free bits in the last byte
+    free: u8,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.free == 0 {
+            self.buf.push(0);
+            self.free = 8;
+        }
+        self.free -= 1;
+        if bit {
+            *self.buf.last_mut().unwrap() |= 1 << self.free;
+        }
+    }
+
+    /// Writes the `n` least significant bits of `v`, most significant first.
+    fn write_bits(&mut self, v: u64, n: u8) {
+        for i in (0..n).rev() {
+            self.write_bit((v >> i) & 1 == 1);
+        }
+    }
+
+    fn write_uvarint(&mut self, mut v: u64) {
+        while v >= 0x80 {
+            self.write_bits((v as u8 | 0x80) as u64, 8);
+            v >>= 7;
+        }
+        self.write_bits(v, 8);
+    }
+
+    fn write_varint(&mut self, v: i64) {
+        self.write_uvarint(((v << 1) ^ (v >> 63)) as u64);
+    }
+}
+
+/// Port of the XOR chunk encoder of the Prometheus TSDB (`chunkenc.XORChunk`).
+struct XorEncoder {
+    bits: BitWriter,
+    num: u16,
+    t: i64,
+    t_delta: i64,
+    v: f64,
+    leading: u8,
+    trailing: u8,
+}
+
+impl Default for XorEncoder {
+    fn default() -> Self {
+        Self {
+            bits: BitWriter::default(),
+            num: 0,
+            t: 0,
+            t_delta: 0,
+            v: 0.0,
+            leading: 0xff,
+            trailing: 0,
+        }
+    }
+}
+
+impl XorEncoder {
+    fn append(&mut self, t: i64, v: f64) {
+        match self.num {
+            0 => {
+                self.bits.write_varint(t);
+                self.bits.write_bits(v.to_bits(), 64);
+            }
+            1 => {
+                self.t_delta = t - self.t;
+                self.bits.write_uvarint(self.t_delta as u64);
+                self.write_value(v);
+            }
+            _ => {
+                let t_delta = t - self.t;
+                let dod = t_delta - self.t_delta;
+                match dod {
+                    0 => self.bits.write_bit(false),
+                    dod if bit_range(dod, 14) => {
+                        self.bits.write_bits(0b10, 2);
+                        self.bits.write_bits(dod as u64, 14);
+                    }
+                    dod if bit_range(dod, 17) => {
+                        self.bits.write_bits(0b110, 3);
+                        self.bits.write_bits(dod as u64, 17);
+                    }
+                    dod if bit_range(dod, 20) => {
+                        self.bits.write_bits(0b1110, 4);
+                        self.bits.write_bits(dod as u64, 20);
+                    }
+                    dod => {
+                        self.bits.write_bits(0b1111, 4);
+                        self.bits.write_bits(dod as u64, 64);
+                    }
+                }
+                self.t_delta = t_delta;
+                self.write_value(v);
+            }
+        }
+        self.t = t;
+        self.v = v;
+        self.num += 1;
+    }
+
+    fn write_value(&mut self, v: f64) {
+        let delta = v.to_bits() ^ self.v.to_bits();
+        if delta == 0 {
+            self.bits.write_bit(false);
+            return;
+        }
+        self.bits.write_bit(true);
+
+        let leading = (delta.leading_zeros() as u8).min(31);
+        let trailing = delta.trailing_zeros() as u8;
+        if self.leading != 0xff && leading >= self.leading && trailing >= self.trailing {
+            self.bits.write_bit(false);
+            self.bits
+                .write_bits(delta >> self.trailing, 64 - self.leading - self.trailing);
+            return;
+        }
+        self.leading = leading;
+        self.trailing = trailing;
+        self.bits.write_bit(true);
+        self.bits.write_bits(leading as u64, 5);
+        // 64 significant bits overflow to 0, which decoders read back as 64
+        let sig_bits = 64 - leading - trailing;
+        self.bits.write_bits(sig_bits as u64, 6);
+        self.bits.write_bits(delta >> trailing, sig_bits);
+    }
+
+    /// Returns the chunk data: the big endian number of samples followed by
+    /// the bit stream.
+    fn finish(self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(2 + self.bits.buf.len());
+        data.extend_from_slice(&self.num.to_be_bytes());
+        data.extend_from_slice(&self.bits.buf);
+        data
+    }
+}
+
+fn bit_range(x: i64, nbits: u8) -> bool {
+    -((1 << (nbits - 1)) - 1) <= x && x <= 1 << (nbits - 1)
+}
+
+fn put_uvarint(out: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        out.push(v as u8 | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+const CRC32C_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82f6_3b78
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32c(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, b| {
+        CRC32C_TABLE[((crc ^ *b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test-only port of the Prometheus XOR chunk iterator.
+    struct BitReader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn read_bit(&mut self) -> bool {
+            let bit = (self.data[self.pos / 8] >> (7 - self.pos % 8)) & 1 == 1;
+            self.pos += 1;
+            bit
+        }
+
+        fn read_bits(&mut self, n: u8) -> u64 {
+            (0..n).fold(0, |acc, _| (acc << 1) | self.read_bit() as u64)
+        }
+
+        fn read_uvarint(&mut self) -> u64 {
+            let mut v = 0;
+            let mut shift = 0;
+            loop {
+                let b = self.read_bits(8);
+                v |= (b & 0x7f) << shift;
+                if b < 0x80 {
+                    return v;
+                }
+                shift += 7;
+            }
+        }
+    }
+
+    fn decode_xor(data: &[u8]) -> Vec<(i64, f64)> {
+        let num = u16::from_be_bytes([data[0], data[1]]);
+        let mut r = BitReader {
+            data: &data[2..],
+            pos: 0,
+        };
+        let mut out = Vec::new();
+        let (mut t, mut t_delta, mut v) = (0i64, 0i64, 0u64);
+        let (mut leading, mut trailing) = (0u8, 0u8);
+        for i in 0..num {
+            match i {
+                0 => {
+                    let u = r.read_uvarint();
+                    t = ((u >> 1) as i64) ^ -((u & 1) as i64);
+                    v = r.read_bits(64);
+                    out.push((t, f64::from_bits(v)));
+                    continue;
+                }
+                1 => {
+                    t_delta = r.read_uvarint() as i64;
+                }
+                _ => {
+                    let mut prefix = 0;
+                    while prefix < 4 && r.read_bit() {
+                        prefix += 1;
+                    }
+                    let nbits = [0, 14, 17, 20, 64][prefix];
+                    if nbits > 0 {
+                        let mut dod = r.read_bits(nbits) as i64;
+                        if nbits < 64 && dod > 1 << (nbits - 1) {
+                            dod -= 1 << nbits;
+                        }
+                        t_delta += dod;
+                    }
+                }
+            }
+            t += t_delta;
+            if r.read_bit() {
+                if r.read_bit() {
+                    leading = r.read_bits(5) as u8;
+                    let mut sig = r.read_bits(6) as u8;
+                    if sig == 0 {
+                        sig = 64;
+                    }
+                    trailing = 64 - leading - sig;
+                }
+                let sig = 64 - leading - trailing;
+                v ^= r.read_bits(sig) << trailing;
+            }
+            out.push((t, f64::from_bits(v)));
+        }
+        out
+    }
+
+    #[test]
+    fn test_crc32c() {
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+        assert_eq!(crc32c(b""), 0);
+    }
+
+    #[test]
+    fn test_xor_single_sample() {
+        let mut enc = XorEncoder::default();
+        enc.append(1000, 1.0);
+        assert_eq!(
+            enc.finish(),
+            vec![0, 1, 0xd0, 0x0f, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_xor_roundtrip() {
+        let mut samples = vec![(1_700_000_000_000, 1.0), (1_700_000_015_000, 1.0)];
+        // regular and irregular intervals, growing and shrinking values
+        let deltas = [
+            15_000,
+            15_001,
+            14_000,
+            30_000,
+            1_000_000,
+            100_000_000,
+            15_000,
+        ];
+        let values = [2.5, -3.75, 1e10, 1e-10, 0.0, f64::MAX, 42.0];
+        for (d, v) in deltas.iter().zip(values) {
+            let (t, _) = samples[samples.len() - 1];
+            samples.push((t + d, v));
+        }
+        let mut enc = XorEncoder::default();
+        for (t, v) in &samples {
+            enc.append(*t, *v);
+        }
+        assert_eq!(decode_xor(&enc.finish()), samples);
+    }
+
+    #[test]
+    fn test_xor_chunks_split() {
+        let samples = (0..250)
+            .map(|i| Sample {
+                value: i as f64,
+                timestamp: i * 1000,
+            })
+            .collect::<Vec<_>>();
+        let chunks = xor_chunks(&samples);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[1].min_time_ms, 120_000);
+        assert_eq!(chunks[1].max_time_ms, 239_000);
+        assert_eq!(chunks[2].r#type, chunk::Encoding::Xor as i32);
+        let decoded = decode_xor(&chunks[2].data);
+        assert_eq!(decoded.len(), 10);
+        assert_eq!(decoded[9], (249_000, 249.0));
+    }
+
+    #[test]
+    fn test_encode_chunked_frames() {
+        let series = TimeSeries {
+            labels: vec![Label {
+                name: NAME_LABEL.to_string(),
+                value: "up".to_string(),
+            }],
+            samples: vec![Sample {
+                value: 1.0,
+                timestamp: 1000,
+            }],
+            ..Default::default()
+        };
+        let frame = encode_chunked_frames(3, &series);
+        let size = frame[0] as usize;
+        assert_eq!(frame.len(), 1 + 4 + size);
+        let msg = &frame[5..];
+        assert_eq!(
+            u32::from_be_bytes(frame[1..5].try_into().unwrap()),
+            crc32c(msg)
+        );
+        let resp = ChunkedReadResponse::decode(msg).unwrap();
+        assert_eq!(resp.query_index, 3);
+        assert_eq!(resp.chunked_series[0].labels, series.labels);
+        assert_eq!(resp.chunked_series[0].chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_negotiate_response_type() {
+        let mut req = ReadRequest::default();
+        assert_eq!(
+            negotiate_response_type(&req).unwrap(),
+            ResponseType::Samples
+        );
+        req.accepted_response_types = vec![ResponseType::StreamedXorChunks as i32, 0];
+        assert_eq!(
+            negotiate_response_type(&req).unwrap(),
+            ResponseType::StreamedXorChunks
+        );
+        req.accepted_response_types = vec![7];
+        assert!(negotiate_response_type(&req).is_err());
+    }
+
+    #[test]
+    fn test_rows_to_series() {
+        let rows = vec![
+            json::json!({"__name__": "up", "job": "a", "__hash__": "1", "_timestamp": 2000000, "value": 0.0}),
+            json::json!({"__name__": "up", "job": "a", "__hash__": "1", "_timestamp": 1000000, "value": 1.0}),
+            json::json!({"job": "b", "__hash__": "2", "_timestamp": 1000000, "value": 1.0}),
+        ];
+        let mut series = rows_to_series("up", rows);
+        series.sort_by(|a, b| label_pairs(&a.labels).cmp(&label_pairs(&b.labels)));
+        assert_eq!(series.len(), 2);
+        assert_eq!(
+            label_pairs(&series[0].labels),
+            vec![("__name__", "up"), ("job", "a")]
+        );
+        assert_eq!(
+            series[0]
+                .samples
+                .iter()
+                .map(|s| s.timestamp)
+                .collect::<Vec<_>>(),
+            vec![1000, 2000]
+        );
+        assert_eq!(
+            label_pairs(&series[1].labels),
+            vec![("__name__", "up"), ("job", "b")]
+        );
+    }
+
+    #[test]
+    fn test_convert_matchers() {
+        let matchers = convert_matchers(&[
+            prometheus_rpc::LabelMatcher {
+                r#type: label_matcher::Type::Re as i32,
+                name: "job".to_string(),
+                value: "api|web".to_string(),
+            },
+            prometheus_rpc::LabelMatcher {
+                r#type: label_matcher::Type::Neq as i32,
+                name: "env".to_string(),
+                value: "".to_string(),
+            },
+        ])
+        .unwrap();
+        assert!(is_match(&matchers[0], "web"));
+        assert!(!is_match(&matchers[0], "webapp"));
+        assert!(!is_match(&matchers[1], ""));
+        assert!(
+            convert_matchers(&[prometheus_rpc::LabelMatcher {
+                r#type: 9,
+                ..Default::default()
+            }])
+            .is_err()
+        );
+    }
+}
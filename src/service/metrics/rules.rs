@@ -0,0 +1,923 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus recording and alerting rules.
+//!
+//! Rule groups are loaded per namespace and kept in the meta store. Alerting
+//! rules are mapped onto PromQL [`Alert`]s, so they share the destinations,
+//! deduplication and silence handling of the other alerts. Recording rules are
+//! evaluated by the scheduler, one [`TriggerModule::RecordingRule`] trigger per
+//! group, and their results are written back into metric streams.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use chrono::{DateTime, SecondsFormat};
+use config::{
+    meta::{
+        alerts::{Condition, Operator, QueryCondition, QueryType, TriggerCondition, alert::Alert},
+        folder::DEFAULT_FOLDER,
+        promql::{
+            NAME_LABEL, VALUE_LABEL,
+            rules::{
+                ActiveAlert, AlertDiscovery, AlertState, Rule, RuleDiscovery, RuleFile, RuleGroup,
+                RuleGroupHealth, RuleGroupStatus, RuleHealth, RuleStatus, RuleType,
+                StoredRuleGroup,
+            },
+            value::Value,
+        },
+        search::SearchEventType,
+        stream::StreamType,
+        triggers::{ScheduledTriggerData, Trigger, TriggerModule, TriggerStatus},
+    },
+    utils::{json, schema::format_stream_name, time::now_micros},
+};
+use hashbrown::HashMap;
+use infra::db::{ORM_CLIENT, connect_to_orm};
+use promql_parser::parser;
+use svix_ksuid::Ksuid;
+
+use crate::service::{
+    alerts::alert::{self, AlertError},
+    db,
+    promql::{self, name_visitor::MetricNameVisitor},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum RuleError {
+    #[error("Error parsing rule groups: {0}")]
+    ParseRuleGroups(#[from] serde_yaml::Error),
+
+    #[error("Invalid rule group: {0}")]
+    InvalidRuleGroup(String),
+
+    #[error("Rule group {namespace}/{group} not found")]
+    RuleGroupNotFound { namespace: String, group: String },
+
+    #[error("Alerting rules require at least one alert destination")]
+    AlertDestinationMissing,
+
+    #[error("Alerting rule {alert}: {source}")]
+    Alert { alert: String, source: AlertError },
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+}
+
+/// Parses a rule file (`groups: [...]`), or a single rule group as accepted by
+/// the Cortex/Mimir ruler API, and validates its groups.
+pub fn parse_rule_groups(body: &[u8]) -> Result<Vec<RuleGroup>, RuleError> {
+    let value: serde_yaml::Value = serde_yaml::from_slice(body)?;
+    let groups = if value.get("groups").is_some() {
+        serde_yaml::from_value::<RuleFile>(value)?.groups
+    } else {
+        vec![serde_yaml::from_value::<RuleGroup>(value)?]
+    };
+    if groups.is_empty() {
+        return Err(RuleError::InvalidRuleGroup(
+            "no rule groups found".to_string(),
+        ));
+    }
+    for (i, group) in groups.iter().enumerate() {
+        if groups[..i].iter().any(|g| g.name == group.name) {
+            return Err(RuleError::InvalidRuleGroup(format!(
+                "group {} is defined more than once",
+                group.name
+            )));
+        }
+        group.validate().map_err(RuleError::InvalidRuleGroup)?;
+        for rule in group.rules.iter() {
+            let expr = parser::parse(&rule.expr).map_err(|e| {
+                RuleError::InvalidRuleGroup(format!(
+                    "group {}: rule {}: {e}",
+                    group.name,
+                    rule.name()
+                ))
+            })?;
+            if rule.rule_type() == RuleType::Alerting && alert_stream_name(&expr).is_none() {
+                return Err(RuleError::InvalidRuleGroup(format!(
+                    "group {}: alerting rule {} must select at least one metric by name",
+                    group.name,
+                    rule.name()
+                )));
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// Loads rule groups into a namespace, replacing the groups with the same
+/// names. When no destinations are given the ones of the replaced group are
+/// kept.
+pub async fn set_rule_groups(
+    org_id: &str,
+    namespace: &str,
+    groups: Vec<RuleGroup>,
+    destinations: &[String],
+) -> Result<(), RuleError> {
+    if namespace.trim().is_empty() || namespace.contains('/') {
+        return Err(RuleError::InvalidRuleGroup(format!(
+            "invalid namespace: {namespace}"
+        )));
+    }
+    for group in groups {
+        set_rule_group(org_id, namespace, group, destinations).await?;
+    }
+    Ok(())
+}
+
+async fn set_rule_group(
+    org_id: &str,
+    namespace: &str,
+    group: RuleGroup,
+    destinations: &[String],
+) -> Result<(), RuleError> {
+    let existing = db::prometheus_rules::get(org_id, namespace, &group.name).await?;
+    let (destinations, mut old_alert_ids) = match existing {
+        Some(existing) if destinations.is_empty() => (existing.destinations, existing.alert_ids),
+        Some(existing) => (destinations.to_vec(), existing.alert_ids),
+        None => (destinations.to_vec(), BTreeMap::new()),
+    };
+    let alerts = alert_names(&group);
+    if !alerts.is_empty() && destinations.is_empty() {
+        return Err(RuleError::AlertDestinationMissing);
+    }
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let mut alert_ids = BTreeMap::new();
+    let mut created = Vec::new();
+    for (i, name) in alerts {
+        let mut alert = build_alert(namespace, &group, &group.rules[i], &name, &destinations);
+        let existing_id = old_alert_ids
+            .remove(&name)
+            .and_then(|id| Ksuid::from_str(&id).ok());
+        let is_new = existing_id.is_none();
+        let ret = match existing_id {
+            Some(id) => {
+                alert.id = Some(id);
+                match alert::update(client, org_id, None, alert.clone()).await {
+                    Err(AlertError::AlertNotFound) => {
+                        alert.id = None;
+                        alert::create(client, org_id, DEFAULT_FOLDER, alert, false).await
+                    }
+                    ret => ret,
+                }
+            }
+            None => alert::create(client, org_id, DEFAULT_FOLDER, alert, false).await,
+        };
+        match ret {
+            Ok(alert) => {
+                if is_new {
+                    created.push(alert.get_unique_key());
+                }
+                alert_ids.insert(name, alert.get_unique_key());
+            }
+            Err(source) => {
+                // don't leave behind the alerts of a group that failed to load
+                for id in created {
+                    if let Err(e) = delete_alert(org_id, &id).await {
+                        log::error!("[PROMETHEUS RULES] failed to delete alert {id}: {e}");
+                    }
+                }
+                return Err(RuleError::Alert {
+                    alert: name,
+                    source,
+                });
+            }
+        }
+    }
+    // alerting rules which were removed from the group
+    for id in old_alert_ids.into_values() {
+        delete_alert(org_id, &id).await?;
+    }
+
+    let stored = StoredRuleGroup {
+        namespace: namespace.to_string(),
+        group,
+        destinations,
+        alert_ids,
+    };
+    db::prometheus_rules::set(org_id, &stored).await?;
+    sync_recording_trigger(org_id, &stored).await
+}
+
+/// Returns the rule groups of an org keyed by namespace.
+pub async fn get_rule_groups(
+    org_id: &str,
+    namespace: Option<&str>,
+) -> Result<BTreeMap<String, Vec<RuleGroup>>, RuleError> {
+    let mut namespaces: BTreeMap<String, Vec<RuleGroup>> = BTreeMap::new();
+    for stored in db::prometheus_rules::list(org_id, namespace).await? {
+        namespaces
+            .entry(stored.namespace)
+            .or_default()
+            .push(stored.group);
+    }
+    Ok(namespaces)
+}
+
+pub async fn get_rule_group(
+    org_id: &str,
+    namespace: &str,
+    group: &str,
+) -> Result<RuleGroup, RuleError> {
+    match db::prometheus_rules::get(org_id, namespace, group).await? {
+        Some(stored) => Ok(stored.group),
+        None => Err(RuleError::RuleGroupNotFound {
+            namespace: namespace.to_string(),
+            group: group.to_string(),
+        }),
+    }
+}
+
+/// Deletes a rule group together with its alerts and recording rule trigger.
+pub async fn delete_rule_group(
+    org_id: &str,
+    namespace: &str,
+    group: &str,
+) -> Result<(), RuleError> {
+    let Some(stored) = db::prometheus_rules::get(org_id, namespace, group).await? else {
+        return Err(RuleError::RuleGroupNotFound {
+            namespace: namespace.to_string(),
+            group: group.to_string(),
+        });
+    };
+    for (name, id) in stored.alert_ids.iter() {
+        delete_alert(org_id, id)
+            .await
+            .map_err(|source| match source {
+                RuleError::Alert { source, .. } => RuleError::Alert {
+                    alert: name.to_string(),
+                    source,
+                },
+                e => e,
+            })?;
+    }
+    let key = trigger_key(namespace, group);
+    if db::scheduler::exists(org_id, TriggerModule::RecordingRule, &key).await {
+        db::scheduler::delete(org_id, TriggerModule::RecordingRule, &key).await?;
+    }
+    db::prometheus_rules::delete(org_id, namespace, group).await?;
+    Ok(())
+}
+
+/// Deletes all the rule groups of a namespace.
+pub async fn delete_namespace(org_id: &str, namespace: &str) -> Result<(), RuleError> {
+    let groups = db::prometheus_rules::list(org_id, Some(namespace)).await?;
+    if groups.is_empty() {
+        return Err(RuleError::RuleGroupNotFound {
+            namespace: namespace.to_string(),
+            group: "*".to_string(),
+        });
+    }
+    for stored in groups {
+        delete_rule_group(org_id, namespace, &stored.group.name).await?;
+    }
+    Ok(())
+}
+
+/// Lists the rules of an org in the format of the Prometheus `/api/v1/rules`
+/// endpoint.
+pub async fn list_rules(
+    org_id: &str,
+    rule_type: Option<RuleType>,
+) -> Result<RuleDiscovery, RuleError> {
+    let mut groups = Vec::new();
+    for stored in db::prometheus_rules::list(org_id, None).await? {
+        let health =
+            db::prometheus_rules::get_health(org_id, &stored.namespace, &stored.group.name).await?;
+        let alerts: HashMap<usize, String> = alert_names(&stored.group).into_iter().collect();
+        let mut rules = Vec::with_capacity(stored.group.rules.len());
+        for (i, rule) in stored.group.rules.iter().enumerate() {
+            if rule_type.is_some_and(|t| t != rule.rule_type()) {
+                continue;
+            }
+            let status = match alerts.get(&i) {
+                Some(name) => {
+                    let alert_id = stored.alert_ids.get(name).map(String::as_str);
+                    alerting_rule_status(org_id, rule, alert_id).await
+                }
+                None => recording_rule_status(rule, health.as_ref(), i),
+            };
+            rules.push(status);
+        }
+        if rules.is_empty() && rule_type.is_some() {
+            continue;
+        }
+        groups.push(RuleGroupStatus {
+            name: stored.group.name.clone(),
+            file: stored.namespace.clone(),
+            rules,
+            interval: stored.group.interval_seconds() as f64,
+            evaluation_time: health.as_ref().map_or(0.0, |h| h.evaluation_time),
+            last_evaluation: format_time(health.as_ref().map(|h| h.last_evaluation)),
+        });
+    }
+    Ok(RuleDiscovery { groups })
+}
+
+/// Lists the firing and pending alerts of the alerting rules of an org in the
+/// format of the Prometheus `/api/v1/alerts` endpoint.
+pub async fn list_alerts(org_id: &str) -> Result<AlertDiscovery, RuleError> {
+    let mut alerts = Vec::new();
+    for stored in db::prometheus_rules::list(org_id, None).await? {
+        for (i, name) in alert_names(&stored.group) {
+            let alert_id = stored.alert_ids.get(&name).map(String::as_str);
+            let status = alerting_rule_status(org_id, &stored.group.rules[i], alert_id).await;
+            alerts.extend(status.alerts.unwrap_or_default());
+        }
+    }
+    Ok(AlertDiscovery { alerts })
+}
+
+/// Evaluates the recording rules of a rule group and reschedules the trigger
+/// for the next evaluation interval.
+///
+/// The rules are evaluated in order, but the samples are ingested
+/// asynchronously, so a rule reading the output of a previous rule of the same
+/// group sees it one evaluation later.
+pub async fn handle_recording_rule_triggers(
+    trace_id: &str,
+    mut trigger: Trigger,
+) -> Result<(), anyhow::Error> {
+    let stored = match trigger.module_key.split_once('/') {
+        Some((namespace, group)) => {
+            db::prometheus_rules::get(&trigger.org, namespace, group).await?
+        }
+        None => None,
+    };
+    let Some(stored) = stored.filter(|s| s.group.has_recording_rules()) else {
+        log::warn!(
+            "[SCHEDULER trace_id {trace_id}] Rule group not found for module_key: {}, deleting this trigger job",
+            trigger.module_key
+        );
+        db::scheduler::delete(
+            &trigger.org,
+            TriggerModule::RecordingRule,
+            &trigger.module_key,
+        )
+        .await?;
+        return Ok(());
+    };
+
+    // evaluate at the scheduled time, so the recorded samples stay aligned on
+    // the interval even when the scheduler runs late
+    let eval_time = trigger.next_run_at;
+    let started = std::time::Instant::now();
+    let mut health = RuleGroupHealth {
+        last_evaluation: eval_time,
+        ..Default::default()
+    };
+    for (i, rule) in stored.group.rules.iter().enumerate() {
+        let Some(record) = rule.record.as_deref() else {
+            continue;
+        };
+        if let Err(e) =
+            evaluate_recording_rule(trace_id, &trigger.org, record, rule, eval_time).await
+        {
+            log::error!(
+                "[SCHEDULER trace_id {trace_id}] Recording rule {}/{}/{record} evaluation failed: {e}",
+                stored.namespace,
+                stored.group.name
+            );
+            health.errors.insert(i, e.to_string());
+        }
+    }
+    health.evaluation_time = started.elapsed().as_secs_f64();
+    if let Err(e) = db::prometheus_rules::set_health(
+        &trigger.org,
+        &stored.namespace,
+        &stored.group.name,
+        &health,
+    )
+    .await
+    {
+        log::error!("[SCHEDULER trace_id {trace_id}] Error saving rule group health: {e}");
+    }
+
+    let interval = stored.group.interval_seconds() as i64 * 1_000_000;
+    trigger.next_run_at = next_run_at(trigger.next_run_at, interval, now_micros());
+    trigger.status = TriggerStatus::Waiting;
+    trigger.retries = 0;
+    db::scheduler::update_trigger(trigger, true, trace_id).await?;
+    Ok(())
+}
+
+async fn evaluate_recording_rule(
+    trace_id: &str,
+    org_id: &str,
+    record: &str,
+    rule: &Rule,
+    eval_time: i64,
+) -> Result<(), anyhow::Error> {
+    let req = promql::MetricsQueryRequest {
+        query: rule.expr.clone(),
+        start: eval_time,
+        end: eval_time,
+        step: 300_000_000, // 5m
+        query_exemplars: false,
+        use_cache: None,
+        search_type: Some(SearchEventType::DerivedStream),
+        regions: vec![],
+        clusters: vec![],
+    };
+    // check super cluster
+    #[cfg(not(feature = "enterprise"))]
+    let is_super_cluster = false;
+    #[cfg(feature = "enterprise")]
+    let is_super_cluster = o2_enterprise::enterprise::common::config::get_config()
+        .super_cluster
+        .enabled;
+    let value = promql::search::search(trace_id, org_id, &req, "", 0, is_super_cluster).await?;
+    let records = recorded_samples(record, &rule.labels, value, eval_time);
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    super::json::ingest_by_ingester(org_id, records).await
+}
+
+/// Converts the result of a recording rule into metric records named after the
+/// rule, with the labels of the rule overriding the labels of the result.
+fn recorded_samples(
+    record: &str,
+    rule_labels: &BTreeMap<String, String>,
+    value: Value,
+    eval_time: i64,
+) -> Vec<json::Value> {
+    let series: Vec<(Vec<(String, String)>, f64)> = match value {
+        Value::Vector(values) => values
+            .into_iter()
+            .map(|v| {
+                let labels = v
+                    .labels
+                    .iter()
+                    .map(|l| (l.name.clone(), l.value.clone()))
+                    .collect();
+                (labels, v.sample.value)
+            })
+            .collect(),
+        Value::Instant(v) => {
+            let labels = v
+                .labels
+                .iter()
+                .map(|l| (l.name.clone(), l.value.clone()))
+                .collect();
+            vec![(labels, v.sample.value)]
+        }
+        Value::Sample(s) => vec![(vec![], s.value)],
+        Value::Float(v) => vec![(vec![], v)],
+        _ => vec![],
+    };
+
+    series
+        .into_iter()
+        .filter(|(_, value)| !value.is_nan())
+        .map(|(labels, value)| {
+            let mut record_map = json::Map::with_capacity(labels.len() + rule_labels.len() + 3);
+            for (name, label_value) in labels {
+                if name != NAME_LABEL {
+                    record_map.insert(name, label_value.into());
+                }
+            }
+            for (name, label_value) in rule_labels {
+                record_map.insert(name.clone(), label_value.clone().into());
+            }
+            record_map.insert(NAME_LABEL.to_string(), record.into());
+            record_map.insert(VALUE_LABEL.to_string(), value.into());
+            record_map.insert("_timestamp".to_string(), eval_time.into());
+            json::Value::Object(record_map)
+        })
+        .collect()
+}
+
+/// Builds the alert an alerting rule is mapped to.
+///
+/// The alert fires while the expression returns any series, i.e. the PromQL
+/// condition `(expr) != NaN` is always true and the trigger condition counts
+/// the series. The alert looks at the last evaluation interval, rounded up to
+/// whole minutes, and stays pending until the expression returned series in
+/// the consecutive evaluations of the `for` duration.
+fn build_alert(
+    namespace: &str,
+    group: &RuleGroup,
+    rule: &Rule,
+    name: &str,
+    destinations: &[String],
+) -> Alert {
+    let stream_name = parser::parse(&rule.expr)
+        .ok()
+        .and_then(|expr| alert_stream_name(&expr))
+        .unwrap_or_default();
+
+    let mut context_attributes = HashMap::with_capacity(rule.labels.len() + 4);
+    context_attributes.insert("alertname".to_string(), rule.name().to_string());
+    context_attributes.insert("rule_namespace".to_string(), namespace.to_string());
+    context_attributes.insert("rule_group".to_string(), group.name.clone());
+    for (k, v) in rule.labels.iter().chain(rule.annotations.iter()) {
+        context_attributes.insert(k.clone(), v.clone());
+    }
+    let description = rule
+        .annotations
+        .get("description")
+        .or(rule.annotations.get("summary"))
+        .cloned()
+        .unwrap_or_default();
+
+    Alert {
+        name: name.to_string(),
+        stream_type: StreamType::Metrics,
+        stream_name,
+        query_condition: QueryCondition {
+            query_type: QueryType::PromQL,
+            promql: Some(rule.expr.clone()),
+            promql_condition: Some(Condition {
+                column: VALUE_LABEL.to_string(),
+                operator: Operator::NotEqualTo,
+                value: "NaN".into(),
+                ignore_case: false,
+            }),
+            ..Default::default()
+        },
+        trigger_condition: TriggerCondition {
+            period: std::cmp::max(1, group.interval_seconds().div_ceil(60) as i64),
+            operator: Operator::GreaterThan,
+            threshold: 0,
+            frequency: group.interval_seconds() as i64,
+            pending_in_secs: Some(rule.for_seconds() as i64).filter(|s| *s > 0),
+            ..Default::default()
+        },
+        destinations: destinations.to_vec(),
+        context_attributes: Some(context_attributes),
+        description,
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+/// The stream an alerting rule is attached to: the first metric, by name,
+/// selected by its expression.
+fn alert_stream_name(expr: &parser::Expr) -> Option<String> {
+    let mut visitor = MetricNameVisitor::default();
+    promql_parser::util::walk_expr(&mut visitor, expr).ok()?;
+    visitor
+        .name
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .min()
+        .map(format_stream_name)
+}
+
+/// Names of the alerts of the alerting rules of a group, keyed by the position
+/// of the rule. Alerting rules sharing a name, usually with different
+/// severities, get a numeric suffix.
+fn alert_names(group: &RuleGroup) -> Vec<(usize, String)> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    group
+        .rules
+        .iter()
+        .enumerate()
+        .filter_map(|(i, rule)| {
+            let alert = rule.alert.as_deref()?;
+            let count = seen.entry(alert).or_default();
+            *count += 1;
+            let name = if *count == 1 {
+                alert.to_string()
+            } else {
+                format!("{alert}_{count}")
+            };
+            Some((i, name))
+        })
+        .collect()
+}
+
+async fn alerting_rule_status(org_id: &str, rule: &Rule, alert_id: Option<&str>) -> RuleStatus {
+    let mut status = RuleStatus {
+        name: rule.name().to_string(),
+        query: rule.expr.clone(),
+        duration: Some(rule.for_seconds()),
+        labels: rule.labels.clone(),
+        annotations: Some(rule.annotations.clone()),
+        alerts: Some(vec![]),
+        state: Some(AlertState::Inactive),
+        health: RuleHealth::Unknown,
+        last_error: None,
+        evaluation_time: 0.0,
+        last_evaluation: format_time(None),
+        rule_type: RuleType::Alerting,
+    };
+    let Some(alert_id) = alert_id.and_then(|id| Ksuid::from_str(id).ok()) else {
+        return status;
+    };
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let Ok(Some((_, alert))) = db::alerts::alert::get_by_id(client, org_id, alert_id).await else {
+        return status;
+    };
+    let trigger = db::scheduler::get(org_id, TriggerModule::Alert, &alert_id.to_string())
+        .await
+        .ok();
+    let last_triggered_at = alert.get_last_triggered_at(trigger.as_ref());
+    let last_satisfied_at = alert.get_last_satisfied_at(trigger.as_ref());
+    if last_triggered_at.is_some() {
+        status.health = RuleHealth::Ok;
+        status.last_evaluation = format_time(last_triggered_at);
+    }
+    let firing = alert.enabled
+        && last_satisfied_at
+            .zip(last_triggered_at)
+            .is_some_and(|(satisfied, triggered)| satisfied >= triggered);
+    let pending_since = trigger
+        .as_ref()
+        .and_then(|t| json::from_str::<ScheduledTriggerData>(&t.data).ok())
+        .and_then(|data| data.pending_since);
+    let (state, active_at) = if firing {
+        (AlertState::Firing, last_satisfied_at)
+    } else if alert.enabled && pending_since.is_some() {
+        (AlertState::Pending, pending_since)
+    } else {
+        return status;
+    };
+    let mut labels = rule.labels.clone();
+    labels.insert("alertname".to_string(), rule.name().to_string());
+    status.state = Some(state);
+    status.alerts = Some(vec![ActiveAlert {
+        labels,
+        annotations: rule.annotations.clone(),
+        state,
+        active_at: format_time(active_at),
+        value: "".to_string(),
+    }]);
+    status
+}
+
+fn recording_rule_status(
+    rule: &Rule,
+    health: Option<&RuleGroupHealth>,
+    index: usize,
+) -> RuleStatus {
+    let last_error = health.and_then(|h| h.errors.get(&index).cloned());
+    RuleStatus {
+        name: rule.name().to_string(),
+        query: rule.expr.clone(),
+        duration: None,
+        labels: rule.labels.clone(),
+        annotations: None,
+        alerts: None,
+        state: None,
+        health: match (health, &last_error) {
+            (None, _) => RuleHealth::Unknown,
+            (Some(_), Some(_)) => RuleHealth::Err,
+            (Some(_), None) => RuleHealth::Ok,
+        },
+        last_error,
+        evaluation_time: health.map_or(0.0, |h| h.evaluation_time),
+        last_evaluation: format_time(health.map(|h| h.last_evaluation)),
+        rule_type: RuleType::Recording,
+    }
+}
+
+async fn sync_recording_trigger(org_id: &str, stored: &StoredRuleGroup) -> Result<(), RuleError> {
+    let key = trigger_key(&stored.namespace, &stored.group.name);
+    let exists = db::scheduler::exists(org_id, TriggerModule::RecordingRule, &key).await;
+    if !stored.group.has_recording_rules() {
+        if exists {
+            db::scheduler::delete(org_id, TriggerModule::RecordingRule, &key).await?;
+        }
+        return Ok(());
+    }
+    // an existing trigger picks up a changed interval when it is rescheduled
+    if !exists {
+        let trigger = Trigger {
+            org: org_id.to_string(),
+            module: TriggerModule::RecordingRule,
+            module_key: key,
+            next_run_at: now_micros(),
+            is_realtime: false,
+            is_silenced: false,
+            ..Default::default()
+        };
+        db::scheduler::push(trigger).await?;
+    }
+    Ok(())
+}
+
+async fn delete_alert(org_id: &str, alert_id: &str) -> Result<(), RuleError> {
+    let Ok(id) = Ksuid::from_str(alert_id) else {
+        return Ok(());
+    };
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    alert::delete_by_id(client, org_id, id)
+        .await
+        .map_err(|source| RuleError::Alert {
+            alert: alert_id.to_string(),
+            source,
+        })
+}
+
+fn trigger_key(namespace: &str, group: &str) -> String {
+    format!("{namespace}/{group}")
+}
+
+/// Next evaluation time, aligned on the previous schedule and skipping the
+/// evaluations missed while the scheduler was behind.
+pub(super) fn next_run_at(prev: i64, interval: i64, now: i64) -> i64 {
+    let next = prev + interval;
+    if next > now {
+        next
+    } else {
+        next + ((now - next) / interval + 1) * interval
+    }
+}
+
+fn format_time(micros: Option<i64>) -> String {
+    micros
+        .and_then(DateTime::from_timestamp_micros)
+        .map_or("0001-01-01T00:00:00Z".to_string(), |t| {
+            t.to_rfc3339_opts(SecondsFormat::Millis, true)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use config::meta::promql::value::{InstantValue, Label, Sample};
+
+    use super::*;
+
+    const RULE_FILE: &str = r#"
+groups:
+  - name: example
+    interval: 30s
+    rules:
+      - record: job:http_requests:rate5m
+        expr: sum by (job) (rate(http_requests_total[5m]))
+        labels:
+          team: web
+      - alert: HighErrorRate
+        expr: job:http_requests:rate5m{job="api"} > 0.5
+        for: 90s
+        labels:
+          severity: page
+        annotations:
+          summary: High request rate
+      - alert: HighErrorRate
+        expr: job:http_requests:rate5m{job="api"} > 0.2
+        labels:
+          severity: warning
+"#;
+
+    #[test]
+    fn test_parse_rule_groups() {
+        let groups = parse_rule_groups(RULE_FILE.as_bytes()).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "example");
+        assert_eq!(groups[0].interval_seconds(), 30);
+        assert_eq!(groups[0].rules.len(), 3);
+        assert_eq!(groups[0].rules[1].for_seconds(), 90);
+
+        // a single group, as sent to the ruler API
+        let group = "name: single\nrules:\n  - record: up:sum\n    expr: sum(up)\n";
+        let groups = parse_rule_groups(group.as_bytes()).unwrap();
+        assert_eq!(groups[0].name, "single");
+        assert_eq!(groups[0].rules[0].record.as_deref(), Some("up:sum"));
+    }
+
+    #[test]
+    fn test_parse_rule_groups_invalid() {
+        // invalid expression
+        let group = "name: g\nrules:\n  - record: up:sum\n    expr: sum(up\n";
+        assert!(parse_rule_groups(group.as_bytes()).is_err());
+        // alerting rule without metric name
+        let group = "name: g\nrules:\n  - alert: Always\n    expr: vector(1)\n";
+        assert!(parse_rule_groups(group.as_bytes()).is_err());
+        // duplicated group
+        let file = "groups:\n  - name: g\n    rules:\n      - record: a\n        expr: up\n  - name: g\n    rules:\n      - record: b\n        expr: up\n";
+        assert!(parse_rule_groups(file.as_bytes()).is_err());
+        // not yaml
+        assert!(parse_rule_groups(b"groups: [").is_err());
+    }
+
+    #[test]
+    fn test_alert_names() {
+        let groups = parse_rule_groups(RULE_FILE.as_bytes()).unwrap();
+        assert_eq!(
+            alert_names(&groups[0]),
+            vec![
+                (1, "HighErrorRate".to_string()),
+                (2, "HighErrorRate_2".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_alert() {
+        let groups = parse_rule_groups(RULE_FILE.as_bytes()).unwrap();
+        let group = &groups[0];
+        let alert = build_alert(
+            "ns",
+            group,
+            &group.rules[1],
+            "HighErrorRate",
+            &["slack".to_string()],
+        );
+        assert_eq!(alert.name, "HighErrorRate");
+        assert_eq!(alert.stream_type, StreamType::Metrics);
+        assert_eq!(
+            alert.stream_name,
+            format_stream_name("job:http_requests:rate5m".to_string())
+        );
+        assert_eq!(alert.query_condition.query_type, QueryType::PromQL);
+        assert_eq!(
+            alert.query_condition.promql.as_deref(),
+            Some(r#"job:http_requests:rate5m{job="api"} > 0.5"#)
+        );
+        let condition = alert.query_condition.promql_condition.unwrap();
+        assert_eq!(condition.operator, Operator::NotEqualTo);
+        assert_eq!(condition.value, json::json!("NaN"));
+        assert_eq!(alert.trigger_condition.period, 1);
+        assert_eq!(alert.trigger_condition.pending_in_secs, Some(90));
+        assert_eq!(alert.trigger_condition.frequency, 30);
+        assert_eq!(alert.trigger_condition.operator, Operator::GreaterThan);
+        assert_eq!(alert.trigger_condition.threshold, 0);
+        assert_eq!(alert.destinations, vec!["slack".to_string()]);
+        assert_eq!(alert.description, "High request rate");
+        assert!(alert.enabled);
+        let attrs = alert.context_attributes.unwrap();
+        assert_eq!(attrs.get("severity").map(String::as_str), Some("page"));
+        assert_eq!(
+            attrs.get("alertname").map(String::as_str),
+            Some("HighErrorRate")
+        );
+        assert_eq!(attrs.get("rule_group").map(String::as_str), Some("example"));
+
+        // without `for` the alert fires on the first evaluation
+        let alert = build_alert("ns", group, &group.rules[2], "HighErrorRate_2", &[]);
+        assert_eq!(alert.trigger_condition.period, 1);
+        assert_eq!(alert.trigger_condition.pending_in_secs, None);
+    }
+
+    #[test]
+    fn test_recorded_samples() {
+        let value = Value::Vector(vec![
+            InstantValue {
+                labels: vec![
+                    Arc::new(Label::new(NAME_LABEL, "http_requests_total")),
+                    Arc::new(Label::new("job", "api")),
+                    Arc::new(Label::new("team", "db")),
+                ],
+                sample: Sample::new(1, 2.5),
+            },
+            InstantValue {
+                labels: vec![Arc::new(Label::new("job", "web"))],
+                sample: Sample::new(1, f64::NAN),
+            },
+        ]);
+        let rule_labels = BTreeMap::from([("team".to_string(), "web".to_string())]);
+        let records = recorded_samples("job:http_requests:rate5m", &rule_labels, value, 100);
+        assert_eq!(
+            records,
+            vec![json::json!({
+                "__name__": "job:http_requests:rate5m",
+                "job": "api",
+                "team": "web",
+                "value": 2.5,
+                "_timestamp": 100
+            })]
+        );
+
+        let records = recorded_samples("answer", &BTreeMap::new(), Value::Float(42.0), 100);
+        assert_eq!(
+            records,
+            vec![json::json!({"__name__": "answer", "value": 42.0, "_timestamp": 100})]
+        );
+        assert!(recorded_samples("none", &BTreeMap::new(), Value::None, 100).is_empty());
+    }
+
+    #[test]
+    fn test_next_run_at() {
+        assert_eq!(next_run_at(100, 30, 110), 130);
+        assert_eq!(next_run_at(100, 30, 130), 160);
+        assert_eq!(next_run_at(100, 30, 1000), 1030);
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(None), "0001-01-01T00:00:00Z");
+        assert_eq!(
+            format_time(Some(1_700_000_000_123_000)),
+            "2023-11-14T22:13:20.123Z"
+        );
+    }
+}
@@ -0,0 +1,682 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus scrape mode.
+//!
+//! Scrape configs are kept per org in the meta store and each job is run by the
+//! scheduler, one [`TriggerModule::Scrape`] trigger per job. A run fetches the
+//! targets of the job concurrently, parses their text or OpenMetrics
+//! exposition and ingests the samples into metric streams, together with the
+//! `up`, `scrape_duration_seconds` and `scrape_samples_*` series of every
+//! target.
+
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail};
+use config::{
+    get_config,
+    meta::{
+        promql::{
+            NAME_LABEL, TYPE_LABEL, VALUE_LABEL,
+            scrape::{
+                FileSdConfig, Relabeler, ScrapeConfig, ScrapeConfigFile, ScrapeTarget, TargetGroup,
+                relabel,
+            },
+        },
+        triggers::{Trigger, TriggerModule, TriggerStatus},
+    },
+    utils::{json, time::now_micros},
+};
+use futures::{StreamExt, stream};
+use regex::Regex;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+
+use crate::{
+    common::utils::ssrf_guard::{SsrfGuard, build_safe_client},
+    service::db,
+};
+
+mod parser;
+
+const SCRAPE_ACCEPT_HEADER: &str =
+    "application/openmetrics-text;version=1.0.0;q=0.5,text/plain;version=0.0.4;q=0.4,*/*;q=0.1";
+const FILE_PATH_LABEL: &str = "__meta_filepath";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScrapeError {
+    #[error("Error parsing scrape configs: {0}")]
+    ParseScrapeConfigs(#[from] serde_yaml::Error),
+
+    #[error("Invalid scrape config: {0}")]
+    InvalidScrapeConfig(String),
+
+    #[error("Scrape config {0} not found")]
+    ScrapeConfigNotFound(String),
+
+    #[error("Scrape mode is disabled, set ZO_PROMETHEUS_SCRAPE_ENABLED=true to enable it")]
+    Disabled,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+}
+
+/// Parses the `scrape_configs` section of a Prometheus configuration, or a
+/// single scrape config, and validates its jobs.
+pub fn parse_scrape_configs(body: &[u8]) -> Result<Vec<ScrapeConfig>, ScrapeError> {
+    let value: serde_yaml::Value = serde_yaml::from_slice(body)?;
+    let configs = if value.get("scrape_configs").is_some() {
+        serde_yaml::from_value::<ScrapeConfigFile>(value)?.scrape_configs
+    } else {
+        vec![serde_yaml::from_value::<ScrapeConfig>(value)?]
+    };
+    if configs.is_empty() {
+        return Err(ScrapeError::InvalidScrapeConfig(
+            "no scrape configs found".to_string(),
+        ));
+    }
+    let cfg = get_config();
+    for (i, config) in configs.iter().enumerate() {
+        if configs[..i].iter().any(|c| c.job_name == config.job_name) {
+            return Err(ScrapeError::InvalidScrapeConfig(format!(
+                "job {} is defined more than once",
+                config.job_name
+            )));
+        }
+        config
+            .validate()
+            .map_err(ScrapeError::InvalidScrapeConfig)?;
+        for file in config.file_sd_configs.iter().flat_map(|c| c.files.iter()) {
+            file_sd_path(&cfg.prom.scrape_file_sd_dir, file).map_err(|e| {
+                ScrapeError::InvalidScrapeConfig(format!("job {}: {e}", config.job_name))
+            })?;
+        }
+    }
+    Ok(configs)
+}
+
+/// Creates the scrape jobs, replacing the jobs with the same names.
+pub async fn set_scrape_configs(
+    org_id: &str,
+    configs: Vec<ScrapeConfig>,
+) -> Result<(), ScrapeError> {
+    if !get_config().prom.scrape_enabled {
+        return Err(ScrapeError::Disabled);
+    }
+    for config in configs {
+        db::prometheus_scrape::set(org_id, &config).await?;
+        // an existing trigger picks up a changed interval when it is rescheduled
+        if !db::scheduler::exists(org_id, TriggerModule::Scrape, &config.job_name).await {
+            let trigger = Trigger {
+                org: org_id.to_string(),
+                module: TriggerModule::Scrape,
+                module_key: config.job_name,
+                next_run_at: now_micros(),
+                is_realtime: false,
+                is_silenced: false,
+                ..Default::default()
+            };
+            db::scheduler::push(trigger).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn list_scrape_configs(org_id: &str) -> Result<ScrapeConfigFile, ScrapeError> {
+    Ok(ScrapeConfigFile {
+        scrape_configs: db::prometheus_scrape::list(org_id).await?,
+    })
+}
+
+pub async fn get_scrape_config(org_id: &str, job_name: &str) -> Result<ScrapeConfig, ScrapeError> {
+    db::prometheus_scrape::get(org_id, job_name)
+        .await?
+        .ok_or_else(|| ScrapeError::ScrapeConfigNotFound(job_name.to_string()))
+}
+
+/// Deletes a scrape job together with its scheduler trigger.
+pub async fn delete_scrape_config(org_id: &str, job_name: &str) -> Result<(), ScrapeError> {
+    if db::prometheus_scrape::get(org_id, job_name)
+        .await?
+        .is_none()
+    {
+        return Err(ScrapeError::ScrapeConfigNotFound(job_name.to_string()));
+    }
+    if db::scheduler::exists(org_id, TriggerModule::Scrape, job_name).await {
+        db::scheduler::delete(org_id, TriggerModule::Scrape, job_name).await?;
+    }
+    db::prometheus_scrape::delete(org_id, job_name).await?;
+    Ok(())
+}
+
+/// Scrapes the targets of a job and reschedules the trigger for the next scrape
+/// interval. The trigger is kept, without scraping, while scrape mode is
+/// disabled.
+pub async fn handle_scrape_triggers(
+    trace_id: &str,
+    mut trigger: Trigger,
+) -> Result<(), anyhow::Error> {
+    let Some(config) = db::prometheus_scrape::get(&trigger.org, &trigger.module_key).await? else {
+        log::warn!(
+            "[SCHEDULER trace_id {trace_id}] Scrape config not found for module_key: {}, deleting this trigger job",
+            trigger.module_key
+        );
+        db::scheduler::delete(&trigger.org, TriggerModule::Scrape, &trigger.module_key).await?;
+        return Ok(());
+    };
+
+    if get_config().prom.scrape_enabled
+        && let Err(e) = scrape_job(trace_id, &trigger.org, &config).await
+    {
+        log::error!(
+            "[SCHEDULER trace_id {trace_id}] Scrape job {}/{} failed: {e}",
+            trigger.org,
+            config.job_name
+        );
+    }
+
+    let interval = config.interval_seconds() as i64 * 1_000_000;
+    trigger.next_run_at = super::rules::next_run_at(trigger.next_run_at, interval, now_micros());
+    trigger.status = TriggerStatus::Waiting;
+    trigger.retries = 0;
+    db::scheduler::update_trigger(trigger, true, trace_id).await?;
+    Ok(())
+}
+
+async fn scrape_job(
+    trace_id: &str,
+    org_id: &str,
+    config: &ScrapeConfig,
+) -> Result<(), anyhow::Error> {
+    let mut groups = config.static_configs.clone();
+    if !config.file_sd_configs.is_empty() {
+        groups.extend(file_sd_target_groups(trace_id, &config.file_sd_configs).await);
+    }
+    let targets = config.targets(&groups).map_err(|e| anyhow!(e))?;
+    let metric_relabelers = config
+        .metric_relabel_configs
+        .iter()
+        .map(Relabeler::new)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow!(e))?;
+
+    let cfg = get_config();
+    let timeout = config.timeout_seconds();
+    let builder = reqwest::Client::builder().timeout(Duration::from_secs(timeout));
+    let client = if cfg.prom.scrape_allow_private_targets {
+        builder.build()?
+    } else {
+        build_safe_client(builder)?
+    };
+
+    stream::iter(targets.iter())
+        .for_each_concurrent(cfg.prom.scrape_concurrency.max(1), |target| {
+            scrape_target(
+                trace_id,
+                org_id,
+                &client,
+                config,
+                target,
+                &metric_relabelers,
+            )
+        })
+        .await;
+    Ok(())
+}
+
+async fn scrape_target(
+    trace_id: &str,
+    org_id: &str,
+    client: &reqwest::Client,
+    config: &ScrapeConfig,
+    target: &ScrapeTarget,
+    metric_relabelers: &[Relabeler],
+) {
+    let scrape_time = now_micros();
+    let started = Instant::now();
+    let body_size_limit = config.body_size_limit_bytes(get_config().prom.scrape_body_size_limit);
+    let result = fetch(
+        client,
+        &target.url,
+        config.timeout_seconds(),
+        body_size_limit,
+    )
+    .await;
+    let duration = started.elapsed().as_secs_f64();
+
+    let (mut records, up, scraped) = match result {
+        Ok(samples) => {
+            let scraped = samples.len();
+            let records = target_records(
+                samples,
+                &target.labels,
+                config.honor_labels,
+                config.honor_timestamps,
+                metric_relabelers,
+                scrape_time,
+            );
+            (records, 1.0, scraped)
+        }
+        Err(e) => {
+            log::warn!(
+                "[SCHEDULER trace_id {trace_id}] Scrape of {} for job {}/{} failed: {e}",
+                target.url,
+                org_id,
+                config.job_name
+            );
+            (vec![], 0.0, 0)
+        }
+    };
+    let post_relabeling = records.len();
+    for (name, value) in [
+        ("up", up),
+        ("scrape_duration_seconds", duration),
+        ("scrape_samples_scraped", scraped as f64),
+        (
+            "scrape_samples_post_metric_relabeling",
+            post_relabeling as f64,
+        ),
+    ] {
+        records.extend(series_record(
+            name,
+            &target.labels,
+            "gauge",
+            value,
+            scrape_time,
+        ));
+    }
+
+    if let Err(e) = super::json::ingest_by_ingester(org_id, records).await {
+        log::error!(
+            "[SCHEDULER trace_id {trace_id}] Ingesting the scrape of {} for job {}/{} failed: {e}",
+            target.url,
+            org_id,
+            config.job_name
+        );
+    }
+}
+
+async fn fetch(
+    client: &reqwest::Client,
+    url: &str,
+    timeout: u64,
+    body_size_limit: Option<u64>,
+) -> Result<Vec<parser::Sample>, anyhow::Error> {
+    if !get_config().prom.scrape_allow_private_targets {
+        SsrfGuard::validate_url_with_config_async(url)
+            .await
+            .map_err(|e| anyhow!(e))?;
+    }
+    let resp = client
+        .get(url)
+        .header(ACCEPT, SCRAPE_ACCEPT_HEADER)
+        .header("X-Prometheus-Scrape-Timeout-Seconds", timeout.to_string())
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        bail!("server returned HTTP status {}", resp.status());
+    }
+    let openmetrics = resp
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/openmetrics-text"));
+    let body = read_body(resp, body_size_limit).await?;
+    parser::parse(&body, openmetrics).map_err(|e| anyhow!(e))
+}
+
+/// Reads a response body, failing once it grows over the limit instead of
+/// buffering it whole.
+async fn read_body(
+    mut resp: reqwest::Response,
+    limit: Option<u64>,
+) -> Result<String, anyhow::Error> {
+    let limit = limit.unwrap_or(u64::MAX);
+    if resp.content_length().is_some_and(|len| len > limit) {
+        bail!("body size limit of {limit} bytes exceeded");
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await? {
+        if (body.len() + chunk.len()) as u64 > limit {
+            bail!("body size limit of {limit} bytes exceeded");
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Converts the samples of a target into metric records: attaches the target
+/// labels and applies the `metric_relabel_configs`.
+///
+/// With `honor_labels` the labels of the samples win over the target labels,
+/// otherwise the conflicting labels of the samples are kept as
+/// `exported_<name>`.
+fn target_records(
+    samples: Vec<parser::Sample>,
+    target_labels: &BTreeMap<String, String>,
+    honor_labels: bool,
+    honor_timestamps: bool,
+    metric_relabelers: &[Relabeler],
+    scrape_time: i64,
+) -> Vec<json::Value> {
+    samples
+        .into_iter()
+        .filter_map(|sample| {
+            let mut labels: BTreeMap<String, String> = sample.labels.into_iter().collect();
+            labels.insert(NAME_LABEL.to_string(), sample.name);
+            for (name, value) in target_labels {
+                if honor_labels {
+                    labels.entry(name.clone()).or_insert_with(|| value.clone());
+                    continue;
+                }
+                if let Some(exported_value) = labels.remove(name) {
+                    let mut exported = format!("exported_{name}");
+                    while labels.contains_key(&exported) {
+                        exported = format!("exported_{exported}");
+                    }
+                    labels.insert(exported, exported_value);
+                }
+                labels.insert(name.clone(), value.clone());
+            }
+            if !relabel(&mut labels, metric_relabelers) {
+                return None;
+            }
+            labels.retain(|_, v| !v.is_empty());
+            let name = labels.remove(NAME_LABEL)?;
+            let timestamp = match sample.timestamp {
+                Some(ts) if honor_timestamps => ts * 1000,
+                _ => scrape_time,
+            };
+            series_record(
+                &name,
+                &labels,
+                sample.metric_type.as_ingest_type(),
+                sample.value,
+                timestamp,
+            )
+        })
+        .collect()
+}
+
+/// Builds the metric record of a sample, NaN samples are skipped as JSON has no
+/// representation for them and infinities are clamped like in remote write.
+pub(super) fn series_record(
+    name: &str,
+    labels: &BTreeMap<String, String>,
+    metric_type: &str,
+    value: f64,
+    timestamp: i64,
+) -> Option<json::Value> {
+    if value.is_nan() {
+        return None;
+    }
+    let mut record = json::Map::with_capacity(labels.len() + 4);
+    for (label_name, label_value) in labels {
+        record.insert(label_name.clone(), label_value.clone().into());
+    }
+    record.insert(NAME_LABEL.to_string(), name.into());
+    record.insert(TYPE_LABEL.to_string(), metric_type.into());
+    record.insert(
+        VALUE_LABEL.to_string(),
+        value.clamp(f64::MIN, f64::MAX).into(),
+    );
+    record.insert("_timestamp".to_string(), timestamp.into());
+    Some(json::Value::Object(record))
+}
+
+/// Resolves a file of `file_sd_configs` inside the file SD directory. Only the
+/// file name may contain `*` wildcards.
+fn file_sd_path(dir: &str, file: &str) -> Result<PathBuf, String> {
+    if dir.is_empty() {
+        return Err(
+            "file based service discovery is disabled, set ZO_PROMETHEUS_SCRAPE_FILE_SD_DIR to enable it"
+                .to_string(),
+        );
+    }
+    let path = Path::new(file);
+    if path.is_absolute()
+        || path.file_name().is_none()
+        || path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return Err(format!(
+            "file_sd file must be a relative path inside the file SD directory: {file}"
+        ));
+    }
+    if path
+        .parent()
+        .is_some_and(|p| p.to_string_lossy().contains('*'))
+    {
+        return Err(format!(
+            "file_sd file can only have wildcards in its file name: {file}"
+        ));
+    }
+    Ok(Path::new(dir).join(path))
+}
+
+async fn file_sd_target_groups(trace_id: &str, configs: &[FileSdConfig]) -> Vec<TargetGroup> {
+    let cfg = get_config();
+    let mut groups = Vec::new();
+    for file in configs.iter().flat_map(|c| c.files.iter()) {
+        let paths = match file_sd_files(&cfg.prom.scrape_file_sd_dir, file).await {
+            Ok(v) => v,
+            Err(e) => {
+                log::warn!(
+                    "[SCHEDULER trace_id {trace_id}] Error listing file_sd files {file}: {e}"
+                );
+                continue;
+            }
+        };
+        for path in paths {
+            let file_groups = match tokio::fs::read(&path).await {
+                Ok(data) => parse_target_groups(&path, &data),
+                Err(e) => Err(e.into()),
+            };
+            match file_groups {
+                Ok(file_groups) => {
+                    groups.extend(file_groups.into_iter().map(|mut group| {
+                        group
+                            .labels
+                            .insert(FILE_PATH_LABEL.to_string(), path.display().to_string());
+                        group
+                    }));
+                }
+                Err(e) => log::warn!(
+                    "[SCHEDULER trace_id {trace_id}] Error reading file_sd file {}: {e}",
+                    path.display()
+                ),
+            }
+        }
+    }
+    groups
+}
+
+async fn file_sd_files(dir: &str, file: &str) -> Result<Vec<PathBuf>, anyhow::Error> {
+    let path = file_sd_path(dir, file).map_err(|e| anyhow!(e))?;
+    let Some(pattern) = path
+        .file_name()
+        .and_then(|v| v.to_str())
+        .filter(|v| v.contains('*'))
+    else {
+        return Ok(vec![path]);
+    };
+    let re = Regex::new(&format!(
+        "^{}$",
+        regex::escape(pattern).replace(r"\*", ".*")
+    ))?;
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(path.parent().unwrap_or(Path::new(dir))).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_name().to_str().is_some_and(|v| re.is_match(v)) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn parse_target_groups(path: &Path, data: &[u8]) -> Result<Vec<TargetGroup>, anyhow::Error> {
+    match path.extension().and_then(|v| v.to_str()) {
+        Some("json") => Ok(json::from_slice(data)?),
+        Some("yml" | "yaml") => Ok(serde_yaml::from_slice(data)?),
+        _ => bail!("unsupported file extension, expected .json, .yml or .yaml"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_scrape_configs() {
+        let body = r#"
+scrape_configs:
+  - job_name: node
+    scrape_interval: 15s
+    static_configs:
+      - targets: ["node-1:9100", "node-2:9100"]
+        labels:
+          env: prod
+  - job_name: app
+    metrics_path: /internal/metrics
+    static_configs:
+      - targets: ["app:8080"]
+"#;
+        let configs = parse_scrape_configs(body.as_bytes()).unwrap();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].interval_seconds(), 15);
+        assert_eq!(configs[1].metrics_path, "/internal/metrics");
+
+        // a single scrape config
+        let body = "job_name: app\nstatic_configs:\n  - targets: [\"app:8080\"]\n";
+        assert_eq!(parse_scrape_configs(body.as_bytes()).unwrap().len(), 1);
+
+        let body = r#"
+scrape_configs:
+  - job_name: node
+    static_configs: [{targets: ["a:9100"]}]
+  - job_name: node
+    static_configs: [{targets: ["b:9100"]}]
+"#;
+        assert!(matches!(
+            parse_scrape_configs(body.as_bytes()),
+            Err(ScrapeError::InvalidScrapeConfig(_))
+        ));
+        assert!(matches!(
+            parse_scrape_configs(b"scrape_configs: []"),
+            Err(ScrapeError::InvalidScrapeConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_target_records() {
+        let samples = parser::parse(
+            "# TYPE requests_total counter\nrequests_total{job=\"app\",path=\"/\"} 3 1000\ngo_goroutines 7\ndebug_info 1\n",
+            false,
+        )
+        .unwrap();
+        let target_labels = labels(&[("instance", "a:9100"), ("job", "node")]);
+        let relabelers = vec![
+            Relabeler::new(
+                &json::from_value(json::json!({
+                    "source_labels": ["__name__"],
+                    "regex": "debug_.*",
+                    "action": "drop",
+                }))
+                .unwrap(),
+            )
+            .unwrap(),
+        ];
+
+        let records = target_records(
+            samples.clone(),
+            &target_labels,
+            false,
+            true,
+            &relabelers,
+            5_000_000,
+        );
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0],
+            json::json!({
+                "__name__": "requests_total",
+                "__type__": "counter",
+                "exported_job": "app",
+                "instance": "a:9100",
+                "job": "node",
+                "path": "/",
+                "value": 3.0,
+                "_timestamp": 1_000_000,
+            })
+        );
+        assert_eq!(records[1]["__type__"], "gauge");
+        assert_eq!(records[1]["_timestamp"], 5_000_000);
+
+        let records = target_records(samples, &target_labels, true, false, &[], 5_000_000);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["job"], "app");
+        assert!(records[0].get("exported_job").is_none());
+        assert_eq!(records[0]["_timestamp"], 5_000_000);
+    }
+
+    #[test]
+    fn test_series_record() {
+        let target_labels = labels(&[("instance", "a:9100")]);
+        assert!(series_record("up", &target_labels, "gauge", f64::NAN, 0).is_none());
+        let record = series_record("up", &target_labels, "gauge", f64::INFINITY, 0).unwrap();
+        assert_eq!(record["value"].as_f64(), Some(f64::MAX));
+        assert_eq!(record["instance"], "a:9100");
+    }
+
+    #[test]
+    fn test_file_sd_path() {
+        assert!(file_sd_path("", "targets.json").is_err());
+        assert_eq!(
+            file_sd_path("/etc/sd", "node/*.json").unwrap(),
+            PathBuf::from("/etc/sd/node/*.json")
+        );
+        assert!(file_sd_path("/etc/sd", "/etc/passwd").is_err());
+        assert!(file_sd_path("/etc/sd", "../passwd").is_err());
+        assert!(file_sd_path("/etc/sd", "node/../../passwd").is_err());
+        assert!(file_sd_path("/etc/sd", "*/targets.json").is_err());
+    }
+
+    #[test]
+    fn test_parse_target_groups() {
+        let groups = parse_target_groups(
+            Path::new("targets.json"),
+            br#"[{"targets": ["a:9100"], "labels": {"env": "prod"}}]"#,
+        )
+        .unwrap();
+        assert_eq!(groups[0].targets, vec!["a:9100".to_string()]);
+        assert_eq!(groups[0].labels, labels(&[("env", "prod")]));
+        let groups =
+            parse_target_groups(Path::new("targets.yml"), b"- targets: [\"a:9100\"]\n").unwrap();
+        assert_eq!(groups.len(), 1);
+        assert!(parse_target_groups(Path::new("targets.txt"), b"").is_err());
+    }
+}
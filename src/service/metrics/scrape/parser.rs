@@ -0,0 +1,359 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parser of the Prometheus text and OpenMetrics exposition formats.
+//!
+//! refer: https://prometheus.io/docs/instrumenting/exposition_formats/
+//! refer: https://github.com/prometheus/OpenMetrics/blob/main/specification/OpenMetrics.md
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+    GaugeHistogram,
+    Summary,
+    Info,
+    StateSet,
+    Unknown,
+}
+
+impl MetricType {
+    fn parse(s: &str) -> Self {
+        match s {
+            "counter" => Self::Counter,
+            "gauge" => Self::Gauge,
+            "histogram" => Self::Histogram,
+            "gaugehistogram" => Self::GaugeHistogram,
+            "summary" => Self::Summary,
+            "info" => Self::Info,
+            "stateset" => Self::StateSet,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// The `__type__` of the ingested series, metrics ingestion only knows
+    /// counters, gauges, histograms and summaries.
+    pub fn as_ingest_type(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Histogram | Self::GaugeHistogram => "histogram",
+            Self::Summary => "summary",
+            Self::Gauge | Self::Info | Self::StateSet | Self::Unknown => "gauge",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    pub name: String,
+    pub labels: Vec<(String, String)>,
+    pub value: f64,
+    /// timestamp in milliseconds
+    pub timestamp: Option<i64>,
+    pub metric_type: MetricType,
+}
+
+/// Suffixes of the samples of a metric family.
+const SAMPLE_SUFFIXES: &[&str] = &[
+    "_total", "_created", "_bucket", "_count", "_sum", "_gcount", "_gsum", "_info",
+];
+
+/// Parses an exposition, `openmetrics` selects the OpenMetrics format, which
+/// differs from the text format in its float second timestamps and `# EOF`
+/// terminator. Exemplars are ignored, as are the `_created` samples of
+/// counters, histograms and summaries.
+pub fn parse(body: &str, openmetrics: bool) -> Result<Vec<Sample>, String> {
+    let mut types: HashMap<&str, MetricType> = HashMap::new();
+    let mut samples = Vec::new();
+    for (i, line) in body.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut parts = comment.split_whitespace();
+            match parts.next() {
+                Some("EOF") if openmetrics => break,
+                Some("TYPE") => {
+                    if let (Some(name), Some(metric_type)) = (parts.next(), parts.next()) {
+                        types.insert(name, MetricType::parse(metric_type));
+                    }
+                }
+                // HELP, UNIT and plain comments
+                _ => {}
+            }
+            continue;
+        }
+
+        let mut sample =
+            parse_sample(line, openmetrics).map_err(|e| format!("line {}: {e}", i + 1))?;
+        if let Some(family) = sample.name.strip_suffix("_created")
+            && matches!(
+                types.get(family),
+                Some(MetricType::Counter | MetricType::Histogram | MetricType::Summary)
+            )
+        {
+            continue;
+        }
+        sample.metric_type = family_type(&types, &sample.name);
+        samples.push(sample);
+    }
+    Ok(samples)
+}
+
+fn family_type(types: &HashMap<&str, MetricType>, name: &str) -> MetricType {
+    if let Some(t) = types.get(name) {
+        return *t;
+    }
+    SAMPLE_SUFFIXES
+        .iter()
+        .filter_map(|suffix| name.strip_suffix(suffix))
+        .find_map(|family| types.get(family).copied())
+        .unwrap_or(MetricType::Unknown)
+}
+
+fn parse_sample(line: &str, openmetrics: bool) -> Result<Sample, String> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .unwrap_or(line.len());
+    let name = &line[..name_end];
+    if !is_valid_metric_name(name) {
+        return Err(format!("invalid metric name: {name}"));
+    }
+
+    let mut rest = &line[name_end..];
+    let mut labels = Vec::new();
+    if let Some(label_set) = rest.strip_prefix('{') {
+        (labels, rest) = parse_labels(label_set)?;
+    }
+    // strip the exemplar
+    if let Some(pos) = rest.find('#') {
+        rest = &rest[..pos];
+    }
+
+    let mut fields = rest.split_whitespace();
+    let value = fields.next().ok_or("missing value")?;
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| format!("invalid value: {value}"))?;
+    let timestamp = fields
+        .next()
+        .map(|ts| parse_timestamp(ts, openmetrics))
+        .transpose()?;
+    if let Some(field) = fields.next() {
+        return Err(format!("unexpected data after timestamp: {field}"));
+    }
+
+    Ok(Sample {
+        name: name.to_string(),
+        labels,
+        value,
+        timestamp,
+        metric_type: MetricType::Unknown,
+    })
+}
+
+/// Parses a label set after its opening brace, returns the labels and the rest
+/// of the line after the closing brace.
+fn parse_labels(s: &str) -> Result<(Vec<(String, String)>, &str), String> {
+    let mut labels = Vec::new();
+    let mut rest = s.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix('}') {
+            return Ok((labels, after));
+        }
+        let name_end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .ok_or("unterminated label set")?;
+        let name = &rest[..name_end];
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("invalid label name at: {rest}"));
+        }
+        rest = rest[name_end..]
+            .trim_start()
+            .strip_prefix('=')
+            .ok_or_else(|| format!("expected '=' after label {name}"))?;
+        rest = rest
+            .trim_start()
+            .strip_prefix('"')
+            .ok_or_else(|| format!("expected quoted value of label {name}"))?;
+
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((i, '"')) => break i,
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, c @ ('\\' | '"'))) => value.push(c),
+                    Some((_, c)) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                    None => return Err(format!("unterminated value of label {name}")),
+                },
+                Some((_, c)) => value.push(c),
+                None => return Err(format!("unterminated value of label {name}")),
+            }
+        };
+        labels.push((name.to_string(), value));
+
+        rest = rest[end + 1..].trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with('}') {
+            return Err(format!("expected ',' or '}}' after label {name}"));
+        }
+    }
+}
+
+fn parse_timestamp(ts: &str, openmetrics: bool) -> Result<i64, String> {
+    let timestamp = if openmetrics {
+        // seconds, with an optional fraction
+        ts.parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|v| (v * 1000.0).round() as i64)
+    } else {
+        ts.parse::<i64>().ok()
+    };
+    timestamp.ok_or_else(|| format!("invalid timestamp: {ts}"))
+}
+
+fn is_valid_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_text_format() {
+        let body = r#"
+# HELP http_requests_total The total number of HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method="post",code="200"} 1027 1395066363000
+http_requests_total{method="post",code="400"}    3 1395066363000
+
+# A histogram
+# TYPE http_request_duration_seconds histogram
+http_request_duration_seconds_bucket{le="0.05"} 24054
+http_request_duration_seconds_bucket{le="+Inf"} 144320
+http_request_duration_seconds_sum 53423
+http_request_duration_seconds_count 144320
+
+msdos_file_access_time_seconds{path="C:\\DIR\\FILE.TXT",error="Cannot find file:\n\"FILE.TXT\""} 1.458255915e9
+metric_without_timestamp_and_labels 12.47
+something_weird{problem="division by zero"} +Inf -3982045
+"#;
+        let samples = parse(body, false).unwrap();
+        assert_eq!(samples.len(), 9);
+        assert_eq!(
+            samples[0],
+            Sample {
+                name: "http_requests_total".to_string(),
+                labels: labels(&[("method", "post"), ("code", "200")]),
+                value: 1027.0,
+                timestamp: Some(1395066363000),
+                metric_type: MetricType::Counter,
+            }
+        );
+        assert_eq!(samples[1].value, 3.0);
+        assert_eq!(samples[2].metric_type, MetricType::Histogram);
+        assert_eq!(samples[3].labels, labels(&[("le", "+Inf")]));
+        assert_eq!(samples[5].metric_type, MetricType::Histogram);
+        assert_eq!(
+            samples[6].labels,
+            labels(&[
+                ("path", r"C:\DIR\FILE.TXT"),
+                ("error", "Cannot find file:\n\"FILE.TXT\"")
+            ])
+        );
+        assert_eq!(samples[6].metric_type, MetricType::Unknown);
+        assert_eq!(samples[7].labels, vec![]);
+        assert_eq!(samples[7].timestamp, None);
+        assert_eq!(samples[8].value, f64::INFINITY);
+        assert_eq!(samples[8].timestamp, Some(-3982045));
+    }
+
+    #[test]
+    fn test_parse_openmetrics_format() {
+        let body = r#"# TYPE acme_http_router_request_seconds summary
+# UNIT acme_http_router_request_seconds seconds
+acme_http_router_request_seconds_sum{path="/api/v1",method="GET"} 9036.32
+acme_http_router_request_seconds_count{path="/api/v1",method="GET"} 807283.0
+acme_http_router_request_seconds_created{path="/api/v1",method="GET"} 1605281325.0
+# TYPE go_goroutines gauge
+go_goroutines 69 1605281325.5
+# TYPE foo counter
+foo_total{a="b",} 17.0 # {trace_id="KOO5S4vxi0o"} 0.67
+# TYPE build info
+build_info{version="1.2"} 1
+# EOF
+ignored 1
+"#;
+        let samples = parse(body, true).unwrap();
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[0].metric_type, MetricType::Summary);
+        assert_eq!(samples[1].value, 807283.0);
+        assert_eq!(samples[2].name, "go_goroutines");
+        assert_eq!(samples[2].timestamp, Some(1605281325500));
+        assert_eq!(samples[3].name, "foo_total");
+        assert_eq!(samples[3].labels, labels(&[("a", "b")]));
+        assert_eq!(samples[3].value, 17.0);
+        assert_eq!(samples[3].timestamp, None);
+        assert_eq!(samples[3].metric_type, MetricType::Counter);
+        assert_eq!(samples[4].metric_type.as_ingest_type(), "gauge");
+    }
+
+    #[test]
+    fn test_parse_special_values() {
+        let samples = parse("a NaN\nb -Inf\nc +Inf\n", false).unwrap();
+        assert!(samples[0].value.is_nan());
+        assert_eq!(samples[1].value, f64::NEG_INFINITY);
+        assert_eq!(samples[2].value, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("1abc 1", false).is_err());
+        assert!(parse("abc", false).is_err());
+        assert!(parse("abc one", false).is_err());
+        assert!(parse(r#"abc{a="b} 1"#, false).is_err());
+        assert!(parse(r#"abc{a=b} 1"#, false).is_err());
+        assert!(parse(r#"abc{a="b" c="d"} 1"#, false).is_err());
+        assert!(parse("abc 1 1.5", false).is_err());
+        assert!(parse("abc 1 2 3", false).is_err());
+        assert_eq!(
+            parse("ok 1\nabc", false).unwrap_err(),
+            "line 2: missing value"
+        );
+    }
+}
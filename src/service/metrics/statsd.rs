@@ -0,0 +1,441 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! StatsD and DogStatsD metrics.
+//!
+//! Lines of the StatsD protocol, including the DogStatsD tags, are parsed into
+//! [`StatsdMetric`]s and aggregated by an [`Aggregator`] until the next flush,
+//! which turns them into metric records:
+//! - counters are summed, scaled by their sample rate, and written as cumulative Prometheus
+//!   counters,
+//! - gauges keep their last value, values prefixed by `+` or `-` adjust it,
+//! - timers, histograms and distributions are written as summaries with the configured quantiles of
+//!   the interval and cumulative `_sum` and `_count`,
+//! - sets are written as gauges of the number of unique values of the interval.
+//!
+//! refer: https://github.com/statsd/statsd/blob/master/docs/metric_types.md
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    utils::{flatten::format_label_name, json, schema::format_stream_name},
+};
+
+use super::scrape::series_record;
+
+const QUANTILE_LABEL: &str = "quantile";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatsdValue {
+    Counter(f64),
+    Gauge { value: f64, delta: bool },
+    Timer(f64),
+    Set(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsdMetric {
+    pub name: String,
+    pub value: StatsdValue,
+    pub sample_rate: f64,
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Parses a line of the StatsD protocol:
+/// `<name>:<value>[:<value>...]|<type>[|@<sample rate>][|#<tag>:<value>,...]`.
+/// DogStatsD events and service checks are ignored.
+pub fn parse_line(line: &str) -> Result<Vec<StatsdMetric>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with("_e{") || line.starts_with("_sc|") {
+        return Ok(vec![]);
+    }
+    let (name, rest) = line
+        .split_once(':')
+        .ok_or_else(|| format!("missing value: {line}"))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("missing metric name: {line}"));
+    }
+    let name = format_stream_name(name.to_string());
+
+    let mut sections = rest.split('|');
+    let values = sections.next().unwrap_or_default();
+    let metric_type = sections
+        .next()
+        .ok_or_else(|| format!("missing metric type: {line}"))?;
+    let mut sample_rate = 1.0;
+    let mut tags = BTreeMap::new();
+    for section in sections {
+        if let Some(rate) = section.strip_prefix('@') {
+            sample_rate = rate
+                .parse::<f64>()
+                .ok()
+                .filter(|v| *v > 0.0 && *v <= 1.0)
+                .ok_or_else(|| format!("invalid sample rate: {rate}"))?;
+        } else if let Some(section) = section.strip_prefix('#') {
+            for tag in section.split(',') {
+                // tags without a value can not become labels
+                if let Some((key, value)) = tag.split_once(':')
+                    && !value.is_empty()
+                {
+                    let key = format_label_name(key.trim());
+                    if !key.is_empty() && !key.starts_with("__") && key != TIMESTAMP_COL_NAME {
+                        tags.insert(key, value.to_string());
+                    }
+                }
+            }
+        }
+        // other DogStatsD sections, e.g. container ids, are ignored
+    }
+
+    values
+        .split(':')
+        .map(|value| {
+            let value = match metric_type {
+                "c" => StatsdValue::Counter(parse_number(value)?),
+                "g" => StatsdValue::Gauge {
+                    value: parse_number(value)?,
+                    delta: value.starts_with(['+', '-']),
+                },
+                "ms" | "h" | "d" => StatsdValue::Timer(parse_number(value)?),
+                "s" => StatsdValue::Set(value.to_string()),
+                _ => return Err(format!("unknown metric type: {metric_type}")),
+            };
+            Ok(StatsdMetric {
+                name: name.clone(),
+                value,
+                sample_rate,
+                tags: tags.clone(),
+            })
+        })
+        .collect()
+}
+
+fn parse_number(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("invalid value: {value}"))
+}
+
+/// Parses the comma separated quantiles of `ZO_STATSD_PERCENTILES`.
+pub fn parse_quantiles(value: &str) -> Vec<f64> {
+    value
+        .split(',')
+        .filter_map(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| (0.0..=1.0).contains(v))
+        .collect()
+}
+
+type SeriesKey = (String, BTreeMap<String, String>);
+
+#[derive(Debug, Default)]
+struct CounterState {
+    total: f64,
+    updated: bool,
+    /// Number of flushes since the series was last updated
+    idle: u64,
+}
+
+#[derive(Debug, Default)]
+struct GaugeState {
+    value: f64,
+    updated: bool,
+    idle: u64,
+}
+
+#[derive(Debug, Default)]
+struct TimerState {
+    values: Vec<f64>,
+    sum: f64,
+    count: f64,
+    idle: u64,
+}
+
+/// Aggregates StatsD metrics between two flushes. The state of counters,
+/// gauges and timer sums is kept across flushes, only the series updated in an
+/// interval are written. The state of a series not updated for `expire_after`
+/// flushes is dropped, `0` keeps it forever.
+#[derive(Debug, Default)]
+pub struct Aggregator {
+    counters: HashMap<SeriesKey, CounterState>,
+    gauges: HashMap<SeriesKey, GaugeState>,
+    timers: HashMap<SeriesKey, TimerState>,
+    sets: HashMap<SeriesKey, HashSet<String>>,
+    expire_after: u64,
+}
+
+impl Aggregator {
+    pub fn new(expire_after: u64) -> Self {
+        Self {
+            expire_after,
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&mut self, metric: StatsdMetric) {
+        let key = (metric.name, metric.tags);
+        match metric.value {
+            StatsdValue::Counter(v) => {
+                let state = self.counters.entry(key).or_default();
+                state.total += v / metric.sample_rate;
+                state.updated = true;
+            }
+            StatsdValue::Gauge { value, delta } => {
+                let state = self.gauges.entry(key).or_default();
+                if delta {
+                    state.value += value;
+                } else {
+                    state.value = value;
+                }
+                state.updated = true;
+            }
+            StatsdValue::Timer(v) => {
+                let state = self.timers.entry(key).or_default();
+                state.values.push(v);
+                state.sum += v / metric.sample_rate;
+                state.count += 1.0 / metric.sample_rate;
+            }
+            StatsdValue::Set(v) => {
+                self.sets.entry(key).or_default().insert(v);
+            }
+        }
+    }
+
+    /// Returns the metric records of the series updated since the last flush
+    /// and drops the expired series.
+    pub fn flush(&mut self, quantiles: &[f64], timestamp: i64) -> Vec<json::Value> {
+        let expire_after = self.expire_after;
+        let keep = |idle: &mut u64| {
+            *idle += 1;
+            expire_after == 0 || *idle < expire_after
+        };
+        let mut records = Vec::new();
+        self.counters.retain(|(name, labels), state| {
+            if !std::mem::take(&mut state.updated) {
+                return keep(&mut state.idle);
+            }
+            state.idle = 0;
+            records.extend(series_record(
+                name,
+                labels,
+                "counter",
+                state.total,
+                timestamp,
+            ));
+            true
+        });
+        self.gauges.retain(|(name, labels), state| {
+            if !std::mem::take(&mut state.updated) {
+                return keep(&mut state.idle);
+            }
+            state.idle = 0;
+            records.extend(series_record(name, labels, "gauge", state.value, timestamp));
+            true
+        });
+        self.timers.retain(|(name, labels), state| {
+            if state.values.is_empty() {
+                return keep(&mut state.idle);
+            }
+            state.idle = 0;
+            let mut values = std::mem::take(&mut state.values);
+            values.sort_by(f64::total_cmp);
+            for quantile in quantiles {
+                let mut labels = labels.clone();
+                labels.insert(QUANTILE_LABEL.to_string(), quantile.to_string());
+                let value = quantile_value(&values, *quantile);
+                records.extend(series_record(name, &labels, "summary", value, timestamp));
+            }
+            records.extend(series_record(
+                &format!("{name}_sum"),
+                labels,
+                "summary",
+                state.sum,
+                timestamp,
+            ));
+            records.extend(series_record(
+                &format!("{name}_count"),
+                labels,
+                "summary",
+                state.count,
+                timestamp,
+            ));
+            true
+        });
+        for ((name, labels), values) in self.sets.drain() {
+            records.extend(series_record(
+                &name,
+                &labels,
+                "gauge",
+                values.len() as f64,
+                timestamp,
+            ));
+        }
+        records
+    }
+}
+
+/// Returns the nearest-rank quantile of sorted values.
+fn quantile_value(values: &[f64], quantile: f64) -> f64 {
+    let rank = (quantile * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::promql::{NAME_LABEL, TYPE_LABEL, VALUE_LABEL};
+
+    use super::*;
+
+    fn tags(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("api_requests:2|c|@0.5|#env:prod,region:us-east,bare").unwrap(),
+            vec![StatsdMetric {
+                name: "api_requests".to_string(),
+                value: StatsdValue::Counter(2.0),
+                sample_rate: 0.5,
+                tags: tags(&[("env", "prod"), ("region", "us-east")]),
+            }]
+        );
+        let metrics = parse_line("queue_size:-3|g").unwrap();
+        assert_eq!(
+            metrics[0].value,
+            StatsdValue::Gauge {
+                value: -3.0,
+                delta: true
+            }
+        );
+        let metrics = parse_line("latency:10:20:30|ms|#__name__:x,host:a").unwrap();
+        assert_eq!(metrics.len(), 3);
+        assert_eq!(metrics[2].value, StatsdValue::Timer(30.0));
+        assert_eq!(metrics[2].tags, tags(&[("host", "a")]));
+        assert_eq!(
+            parse_line("users:alice|s").unwrap()[0].value,
+            StatsdValue::Set("alice".to_string())
+        );
+        assert!(parse_line("_e{5,4}:title|text").unwrap().is_empty());
+        assert!(parse_line("_sc|redis|0").unwrap().is_empty());
+
+        assert!(parse_line("no_value").is_err());
+        assert!(parse_line("no_type:1").is_err());
+        assert!(parse_line("bad_type:1|x").is_err());
+        assert!(parse_line("bad_value:abc|c").is_err());
+        assert!(parse_line("bad_rate:1|c|@2").is_err());
+        assert!(parse_line(":1|c").is_err());
+    }
+
+    #[test]
+    fn test_parse_quantiles() {
+        assert_eq!(parse_quantiles("0.5, 0.99,2,x"), vec![0.5, 0.99]);
+    }
+
+    fn values(records: &[json::Value], name: &str) -> Vec<f64> {
+        let mut values: Vec<f64> = records
+            .iter()
+            .filter(|r| r[NAME_LABEL] == name)
+            .map(|r| r[VALUE_LABEL].as_f64().unwrap())
+            .collect();
+        values.sort_by(f64::total_cmp);
+        values
+    }
+
+    #[test]
+    fn test_aggregator() {
+        let mut aggregator = Aggregator::default();
+        for line in [
+            "hits:1|c",
+            "hits:1|c|@0.5",
+            "temp:10|g",
+            "temp:+5|g",
+            "latency:1:2:3:4|ms",
+            "users:a|s",
+            "users:b|s",
+            "users:a|s",
+        ] {
+            for metric in parse_line(line).unwrap() {
+                aggregator.add(metric);
+            }
+        }
+        let records = aggregator.flush(&[0.5, 0.99], 1000);
+        assert_eq!(values(&records, "hits"), vec![3.0]);
+        assert_eq!(values(&records, "temp"), vec![15.0]);
+        assert_eq!(values(&records, "latency"), vec![2.0, 4.0]);
+        assert_eq!(values(&records, "latency_sum"), vec![10.0]);
+        assert_eq!(values(&records, "latency_count"), vec![4.0]);
+        assert_eq!(values(&records, "users"), vec![2.0]);
+        let hits = records.iter().find(|r| r[NAME_LABEL] == "hits").unwrap();
+        assert_eq!(hits[TYPE_LABEL], "counter");
+        assert_eq!(hits[TIMESTAMP_COL_NAME], 1000);
+
+        // only updated series are written, counters and timer sums accumulate
+        for line in ["hits:2|c", "latency:6|ms"] {
+            for metric in parse_line(line).unwrap() {
+                aggregator.add(metric);
+            }
+        }
+        let records = aggregator.flush(&[0.5], 2000);
+        assert_eq!(values(&records, "hits"), vec![5.0]);
+        assert_eq!(values(&records, "latency"), vec![6.0]);
+        assert_eq!(values(&records, "latency_sum"), vec![16.0]);
+        assert!(values(&records, "temp").is_empty());
+        assert!(values(&records, "users").is_empty());
+    }
+
+    #[test]
+    fn test_aggregator_expire() {
+        let mut aggregator = Aggregator::new(2);
+        let add = |aggregator: &mut Aggregator, line: &str| {
+            for metric in parse_line(line).unwrap() {
+                aggregator.add(metric);
+            }
+        };
+        add(&mut aggregator, "hits:1|c");
+        add(&mut aggregator, "temp:10|g");
+        add(&mut aggregator, "latency:1|ms");
+        assert_eq!(aggregator.flush(&[0.5], 1000).len(), 5);
+        // an update resets the idle flushes of a series
+        aggregator.flush(&[0.5], 2000);
+        add(&mut aggregator, "hits:1|c");
+        aggregator.flush(&[0.5], 3000);
+        assert_eq!(aggregator.counters.len(), 1);
+        assert!(aggregator.gauges.is_empty());
+        assert!(aggregator.timers.is_empty());
+        // an expired counter starts over
+        aggregator.flush(&[0.5], 4000);
+        aggregator.flush(&[0.5], 5000);
+        add(&mut aggregator, "hits:1|c");
+        let records = aggregator.flush(&[0.5], 6000);
+        assert_eq!(values(&records, "hits"), vec![1.0]);
+    }
+
+    #[test]
+    fn test_quantile_value() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(quantile_value(&values, 0.0), 1.0);
+        assert_eq!(quantile_value(&values, 0.5), 2.0);
+        assert_eq!(quantile_value(&values, 0.75), 3.0);
+        assert_eq!(quantile_value(&values, 1.0), 4.0);
+    }
+}
@@ -39,6 +39,7 @@ pub mod kv;
 #[cfg(feature = "enterprise")]
 pub mod llm_evaluations;
 pub mod logs;
+pub mod materialized_views;
 pub mod metadata;
 pub mod metrics;
 pub mod node;
@@ -0,0 +1,146 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::meta::promql::value::{EvalContext, LabelsExt, RangeValue, Value};
+use datafusion::error::{DataFusionError, Result};
+use rayon::prelude::*;
+
+/// Keeps a deterministic sample of about `ratio` of the series. A series is
+/// selected by the hash of its labels, so `limit_ratio(r, v)` and
+/// `limit_ratio(-(1.0 - r), v)` select complementary sets of series. The
+/// selection does not depend on the grouping, hence no label modifier.
+pub fn limit_ratio(ratio: f64, data: Value, eval_ctx: &EvalContext) -> Result<Value> {
+    let start = std::time::Instant::now();
+    let matrix = match data {
+        Value::Matrix(m) => m,
+        Value::None => return Ok(Value::None),
+        _ => {
+            return Err(DataFusionError::Plan(
+                "[limit_ratio] function only accept matrix values".to_string(),
+            ));
+        }
+    };
+
+    if ratio.is_nan() {
+        return Err(DataFusionError::Plan(
+            "[limit_ratio] ratio value is NaN".to_string(),
+        ));
+    }
+    let ratio = ratio.clamp(-1.0, 1.0);
+
+    let result: Vec<RangeValue> = matrix
+        .into_par_iter()
+        .filter(|series| ratio_selected(ratio, sample_offset(series)))
+        .collect();
+
+    log::info!(
+        "[trace_id: {}] [PromQL Timing] limit_ratio(ratio={ratio}) completed in {:?}, produced {} series",
+        eval_ctx.trace_id,
+        start.elapsed(),
+        result.len()
+    );
+
+    if result.is_empty() {
+        Ok(Value::None)
+    } else {
+        Ok(Value::Matrix(result))
+    }
+}
+
+/// Maps the series onto `[0, 1)`
+fn sample_offset(series: &RangeValue) -> f64 {
+    series.labels.signature() as f64 / u64::MAX as f64
+}
+
+fn ratio_selected(ratio: f64, offset: f64) -> bool {
+    (ratio >= 0.0 && offset < ratio) || (ratio < 0.0 && offset >= 1.0 + ratio)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use config::meta::promql::value::{Label, Sample};
+    use hashbrown::HashSet;
+
+    use super::*;
+
+    fn series(count: usize) -> Vec<RangeValue> {
+        (0..count)
+            .map(|i| RangeValue {
+                labels: vec![Arc::new(Label::new("instance".to_string(), i.to_string()))],
+                samples: vec![Sample::new(1000, i as f64)],
+                exemplars: None,
+                time_window: None,
+            })
+            .collect()
+    }
+
+    fn instances(value: Value) -> HashSet<String> {
+        match value {
+            Value::Matrix(m) => m.iter().map(|rv| rv.labels.get_value("instance")).collect(),
+            Value::None => HashSet::new(),
+            _ => panic!("Expected Matrix result"),
+        }
+    }
+
+    #[test]
+    fn test_ratio_selected() {
+        assert!(ratio_selected(0.5, 0.2));
+        assert!(!ratio_selected(0.5, 0.7));
+        assert!(!ratio_selected(-0.5, 0.2));
+        assert!(ratio_selected(-0.5, 0.7));
+        assert!(!ratio_selected(0.0, 0.0));
+        assert!(ratio_selected(1.0, 0.999));
+        assert!(ratio_selected(-1.0, 0.0));
+    }
+
+    #[test]
+    fn test_limit_ratio() {
+        let eval_ctx = EvalContext::new(1000, 1000, 0, "test".to_string());
+        let all = instances(Value::Matrix(series(100)));
+
+        // Prometheus limit.test: limit_ratio(1, ...) and limit_ratio(-1, ...)
+        // keep every series, limit_ratio(0, ...) none
+        for ratio in [1.0, -1.0, 2.0] {
+            let result = limit_ratio(ratio, Value::Matrix(series(100)), &eval_ctx).unwrap();
+            assert_eq!(instances(result), all);
+        }
+        let result = limit_ratio(0.0, Value::Matrix(series(100)), &eval_ctx).unwrap();
+        assert!(instances(result).is_empty());
+
+        // limit_ratio(r, ...) or limit_ratio(-(1.0 - r), ...) selects every
+        // series exactly once
+        for r in [0.1, 0.5, 0.8] {
+            let a = instances(limit_ratio(r, Value::Matrix(series(100)), &eval_ctx).unwrap());
+            let b =
+                instances(limit_ratio(-(1.0 - r), Value::Matrix(series(100)), &eval_ctx).unwrap());
+            assert!(a.is_disjoint(&b));
+            assert_eq!(a.union(&b).cloned().collect::<HashSet<_>>(), all);
+        }
+
+        // the selection is stable
+        let a = instances(limit_ratio(0.5, Value::Matrix(series(100)), &eval_ctx).unwrap());
+        let b = instances(limit_ratio(0.5, Value::Matrix(series(100)), &eval_ctx).unwrap());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_limit_ratio_invalid_input() {
+        let eval_ctx = EvalContext::new(1000, 1000, 0, "test".to_string());
+        assert!(limit_ratio(f64::NAN, Value::Matrix(series(1)), &eval_ctx).is_err());
+        assert!(limit_ratio(0.5, Value::Float(1.0), &eval_ctx).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::meta::promql::value::{EvalContext, RangeValue, Value};
+use datafusion::error::{DataFusionError, Result};
+use hashbrown::HashMap;
+use promql_parser::parser::LabelModifier;
+use rayon::prelude::*;
+
+/// Aggregates Matrix input for range queries
+/// For each timestamp, keeps at most K series of each group, unlike topk
+/// without considering the sample values
+pub fn limitk(
+    k: usize,
+    modifier: &Option<LabelModifier>,
+    data: Value,
+    eval_ctx: &EvalContext,
+) -> Result<Value> {
+    let start = std::time::Instant::now();
+    let matrix = match data {
+        Value::Matrix(m) => m,
+        Value::None => return Ok(Value::None),
+        _ => {
+            return Err(DataFusionError::Plan(
+                "[limitk] function only accept matrix values".to_string(),
+            ));
+        }
+    };
+
+    if matrix.is_empty() || k == 0 {
+        return Ok(Value::None);
+    }
+
+    // Group series by label modifier
+    let grouped_series = super::group_series_by_labels(&matrix, modifier);
+
+    let result: Vec<RangeValue> = grouped_series
+        .into_par_iter()
+        .flat_map(|(_, series_indices)| select_first_k_series(&matrix, series_indices, k))
+        .collect();
+
+    log::info!(
+        "[trace_id: {}] [PromQL Timing] limitk(k={k}) completed in {:?}, produced {} series",
+        eval_ctx.trace_id,
+        start.elapsed(),
+        result.len()
+    );
+
+    if result.is_empty() {
+        Ok(Value::None)
+    } else {
+        Ok(Value::Matrix(result))
+    }
+}
+
+// Series are taken in label order, so that the same series are selected at
+// every timestamp where they have samples
+fn select_first_k_series(
+    matrix: &[RangeValue],
+    mut series_indices: Vec<usize>,
+    k: usize,
+) -> Vec<RangeValue> {
+    series_indices.sort_by(|&a, &b| {
+        matrix[a]
+            .labels
+            .partial_cmp(&matrix[b].labels)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut selected: HashMap<i64, usize> = HashMap::new();
+    let mut result = Vec::with_capacity(series_indices.len().min(k));
+    for idx in series_indices {
+        let series = &matrix[idx];
+        let samples: Vec<_> = series
+            .samples
+            .iter()
+            .filter(|sample| {
+                let count = selected.entry(sample.timestamp).or_default();
+                if *count < k {
+                    *count += 1;
+                    true
+                } else {
+                    false
+                }
+            })
+            .copied()
+            .collect();
+        if !samples.is_empty() {
+            result.push(RangeValue {
+                labels: series.labels.clone(),
+                samples,
+                exemplars: series.exemplars.clone(),
+                time_window: series.time_window.clone(),
+            });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use config::meta::promql::value::{Label, Sample};
+
+    use super::*;
+
+    // Mirrors the `http_requests` series of Prometheus' limit.test
+    fn http_requests() -> Vec<RangeValue> {
+        let mut matrix = vec![];
+        for group in ["production", "canary"] {
+            for instance in ["0", "1", "2", "3"] {
+                matrix.push(RangeValue {
+                    labels: vec![
+                        Arc::new(Label::new("group", group)),
+                        Arc::new(Label::new("instance", instance)),
+                        Arc::new(Label::new("job", "api-server")),
+                    ],
+                    samples: vec![Sample::new(1000, 10.0), Sample::new(2000, 20.0)],
+                    exemplars: None,
+                    time_window: None,
+                });
+            }
+        }
+        matrix
+    }
+
+    fn count_at(value: &Value, timestamp: i64) -> usize {
+        match value {
+            Value::Matrix(m) => m
+                .iter()
+                .filter(|rv| rv.samples.iter().any(|s| s.timestamp == timestamp))
+                .count(),
+            Value::None => 0,
+            _ => panic!("Expected Matrix result"),
+        }
+    }
+
+    #[test]
+    fn test_limitk() {
+        let eval_ctx = EvalContext::new(1000, 2000, 1000, "test".to_string());
+        let by_group = Some(LabelModifier::Include(promql_parser::label::Labels {
+            labels: vec!["group".to_string()],
+        }));
+
+        // count(limitk by (group) (k, http_requests))
+        for (k, expected) in [(0, 0), (1, 2), (2, 4), (10, 8)] {
+            let result = limitk(k, &by_group, Value::Matrix(http_requests()), &eval_ctx).unwrap();
+            assert_eq!(count_at(&result, 1000), expected);
+            assert_eq!(count_at(&result, 2000), expected);
+        }
+
+        // without grouping the limit applies to the whole vector
+        let result = limitk(3, &None, Value::Matrix(http_requests()), &eval_ctx).unwrap();
+        assert_eq!(count_at(&result, 1000), 3);
+        // the same series are selected at every timestamp
+        if let Value::Matrix(m) = result {
+            assert_eq!(m.len(), 3);
+            assert!(m.iter().all(|rv| rv.samples.len() == 2));
+        }
+    }
+
+    #[test]
+    fn test_limitk_invalid_input() {
+        let eval_ctx = EvalContext::new(1000, 1000, 0, "test".to_string());
+        assert!(matches!(
+            limitk(1, &None, Value::None, &eval_ctx).unwrap(),
+            Value::None
+        ));
+        assert!(limitk(1, &None, Value::Float(1.0), &eval_ctx).is_err());
+    }
+}
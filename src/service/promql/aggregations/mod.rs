@@ -28,6 +28,8 @@ mod bottomk;
 mod count;
 mod count_values;
 mod group;
+mod limit_ratio;
+mod limitk;
 mod max;
 mod min;
 mod quantile;
@@ -41,6 +43,8 @@ pub(crate) use bottomk::bottomk;
 pub(crate) use count::count;
 pub(crate) use count_values::count_values;
 pub(crate) use group::group;
+pub(crate) use limit_ratio::limit_ratio;
+pub(crate) use limitk::limitk;
 pub(crate) use max::max;
 pub(crate) use min::min;
 pub(crate) use quantile::quantile;
@@ -19,7 +19,9 @@ use arrow::array::Array;
 use async_recursion::async_recursion;
 use config::{
     TIMESTAMP_COL_NAME,
-    meta::promql::{EXEMPLARS_LABEL, HASH_LABEL, NAME_LABEL, VALUE_LABEL, value::*},
+    meta::promql::{
+        EXEMPLARS_LABEL, HASH_LABEL, NAME_LABEL, NATIVE_HISTOGRAM_LABEL, VALUE_LABEL, value::*,
+    },
     utils::{
         hash::{Sum64, gxhash},
         json,
@@ -55,7 +57,8 @@ use super::{
     utils::{apply_label_selector, apply_matchers},
 };
 use crate::service::promql::{
-    aggregations, binaries, functions, micros, rewrite::remove_filter_all,
+    aggregations, binaries, functions, micros,
+    rewrite::{remove_filter_all, split_or_matchers_by_name},
 };
 #[cfg(feature = "enterprise")]
 use crate::service::search::SEARCH_SERVER;
@@ -200,8 +203,10 @@ impl Engine {
     ) {
         if let Some(label_modifier) = modifier {
             match op.id() {
-                // topk and bottomk query all columns when with modifiers
-                token::T_TOPK | token::T_BOTTOMK => self.label_selector.clear(),
+                // topk, bottomk and limitk query all columns when with modifiers
+                token::T_TOPK | token::T_BOTTOMK | token::T_LIMITK | token::T_LIMIT_RATIO => {
+                    self.label_selector.clear()
+                }
                 _ => {
                     if let (label_selector, LabelModifier::Include(labels)) =
                         (&mut self.label_selector, label_modifier)
@@ -329,12 +334,11 @@ impl Engine {
             PromExpr::VectorSelector(vs) => {
                 let mut vs = vs.clone();
                 remove_filter_all(&mut vs);
-                if !vs.matchers.or_matchers.is_empty() {
-                    return Err(DataFusionError::Plan(
-                        "VectorSelector: or_matchers is not supported".into(),
-                    ));
+                let mut data = Vec::new();
+                for vs in split_selector("VectorSelector", vs)? {
+                    data.extend(self.eval_vector_selector(&vs).await?);
                 }
-                let data = self.eval_vector_selector(&vs).await?;
+                let data = dedup_series(data);
                 if data.is_empty() {
                     Value::None
                 } else {
@@ -344,12 +348,11 @@ impl Engine {
             PromExpr::MatrixSelector(MatrixSelector { vs, range }) => {
                 let mut vs = vs.clone();
                 remove_filter_all(&mut vs);
-                if !vs.matchers.or_matchers.is_empty() {
-                    return Err(DataFusionError::Plan(
-                        "MatrixSelector: or_matchers is not supported".into(),
-                    ));
+                let mut data = Vec::new();
+                for vs in split_selector("MatrixSelector", vs)? {
+                    data.extend(self.eval_matrix_selector(&vs, *range).await?);
                 }
-                let data = self.eval_matrix_selector(&vs, *range).await?;
+                let data = dedup_series(data);
                 if data.is_empty() {
                     Value::None
                 } else {
@@ -893,6 +896,32 @@ impl Engine {
                 };
                 aggregations::bottomk(k, modifier, input, &eval_ctx)?
             }
+            token::T_LIMITK => {
+                let param_expr = param.clone().unwrap();
+                let k_value = self.exec_expr(&param_expr).await?;
+                let k = match k_value {
+                    Value::Float(f) => f as usize,
+                    _ => {
+                        return Err(DataFusionError::Plan(
+                            "[limitk] param must be a number".to_string(),
+                        ));
+                    }
+                };
+                aggregations::limitk(k, modifier, input, &eval_ctx)?
+            }
+            token::T_LIMIT_RATIO => {
+                let param_expr = param.clone().unwrap();
+                let ratio_value = self.exec_expr(&param_expr).await?;
+                let ratio = match ratio_value {
+                    Value::Float(f) => f,
+                    _ => {
+                        return Err(DataFusionError::Plan(
+                            "[limit_ratio] param must be a number".to_string(),
+                        ));
+                    }
+                };
+                aggregations::limit_ratio(ratio, input, &eval_ctx)?
+            }
             token::T_COUNT_VALUES => {
                 let param_expr = param.clone().unwrap();
                 let label_name = self.exec_expr(&param_expr).await?;
@@ -1002,6 +1031,7 @@ impl Engine {
             "hour",
             "minute",
             "month",
+            "pi",
             "time",
             "year",
         ]);
@@ -1043,6 +1073,12 @@ impl Engine {
             Func::Abs => functions::abs(input)?,
             Func::Absent => functions::absent(input, &self.eval_ctx)?,
             Func::AbsentOverTime => functions::absent_over_time(input, &self.eval_ctx)?,
+            Func::Acos => functions::acos(input)?,
+            Func::Acosh => functions::acosh(input)?,
+            Func::Asin => functions::asin(input)?,
+            Func::Asinh => functions::asinh(input)?,
+            Func::Atan => functions::atan(input)?,
+            Func::Atanh => functions::atanh(input)?,
             Func::AvgOverTime => functions::avg_over_time(input, &self.eval_ctx)?,
             Func::Ceil => functions::ceil(input)?,
             Func::Changes => functions::changes(input, &self.eval_ctx)?,
@@ -1096,11 +1132,14 @@ impl Engine {
                 };
                 functions::clamp(input, min_f, f64::MAX)?
             }
+            Func::Cos => functions::cos(input)?,
+            Func::Cosh => functions::cosh(input)?,
             Func::CountOverTime => functions::count_over_time(input, &self.eval_ctx)?,
             Func::DayOfMonth => functions::day_of_month(input)?,
             Func::DayOfWeek => functions::day_of_week(input)?,
             Func::DayOfYear => functions::day_of_year(input)?,
             Func::DaysInMonth => functions::days_in_month(input)?,
+            Func::Deg => functions::deg(input)?,
             Func::Delta => functions::delta(input, &self.eval_ctx)?,
             Func::Deriv => functions::deriv(input, &self.eval_ctx)?,
             Func::Exp => functions::exp(input)?,
@@ -1214,10 +1253,12 @@ impl Engine {
             Func::Ln => functions::ln(input)?,
             Func::Log10 => functions::log10(input)?,
             Func::Log2 => functions::log2(input)?,
+            Func::MadOverTime => functions::mad_over_time(input, &self.eval_ctx)?,
             Func::MaxOverTime => functions::max_over_time(input, &self.eval_ctx)?,
             Func::MinOverTime => functions::min_over_time(input, &self.eval_ctx)?,
             Func::Minute => functions::minute(input)?,
             Func::Month => functions::month(input)?,
+            Func::Pi => Value::Float(std::f64::consts::PI),
             Func::PredictLinear => {
                 let err = "Invalid args, expected \"predict_linear(v range-vector, t scalar)\"";
 
@@ -1231,6 +1272,7 @@ impl Engine {
                 )?;
                 functions::predict_linear(input, prediction_steps, &self.eval_ctx)?
             }
+            Func::PresentOverTime => functions::present_over_time(input, &self.eval_ctx)?,
             Func::QuantileOverTime => {
                 let err = "Invalid args, expected \"quantile_over_time(scalar, range-vector)\"";
 
@@ -1246,11 +1288,14 @@ impl Engine {
                 let input = self.call_expr_second_arg(args).await?;
                 functions::quantile_over_time(phi_quantile, input, &self.eval_ctx)?
             }
+            Func::Rad => functions::rad(input)?,
             Func::Rate => functions::rate(input, &self.eval_ctx)?,
             Func::Resets => functions::resets(input, &self.eval_ctx)?,
             Func::Round => functions::round(input)?,
             Func::Scalar => functions::scalar(input, &self.eval_ctx)?,
             Func::Sgn => functions::sgn(input)?,
+            Func::Sin => functions::sin(input)?,
+            Func::Sinh => functions::sinh(input)?,
             Func::Sort => {
                 return Err(DataFusionError::NotImplemented(format!(
                     "Unsupported Function: {func_name:?}"
@@ -1261,10 +1306,32 @@ impl Engine {
                     "Unsupported Function: {func_name:?}"
                 )));
             }
+            Func::SortByLabel | Func::SortByLabelDesc => {
+                // the input was already evaluated if there are no labels given
+                let input = match args.len() {
+                    1 => input,
+                    _ => self.call_expr_first_arg(args).await?,
+                };
+                let mut labels = vec![];
+                for arg in args.args[1..].iter() {
+                    match self.exec_expr(arg).await? {
+                        Value::String(label) => labels.push(label),
+                        _ => {
+                            return Err(DataFusionError::Plan(format!(
+                                "{}: label must be a string",
+                                func.name
+                            )));
+                        }
+                    }
+                }
+                functions::sort_by_label(input, &labels, func_name == Func::SortByLabelDesc)?
+            }
             Func::Sqrt => functions::sqrt(input)?,
             Func::StddevOverTime => functions::stddev_over_time(input, &self.eval_ctx)?,
             Func::StdvarOverTime => functions::stdvar_over_time(input, &self.eval_ctx)?,
             Func::SumOverTime => functions::sum_over_time(input, &self.eval_ctx)?,
+            Func::Tan => functions::tan(input)?,
+            Func::Tanh => functions::tanh(input)?,
             // TODO: check this implementation
             Func::Time => Value::Float((self.eval_ctx.start / 1_000_000) as f64),
             Func::Timestamp => functions::timestamp(input)?,
@@ -1354,7 +1421,11 @@ async fn selector_load_data_from_datafusion(
         .iter()
         .filter_map(|field| {
             let name = field.name();
-            if name == TIMESTAMP_COL_NAME || name == VALUE_LABEL || name == EXEMPLARS_LABEL {
+            if name == TIMESTAMP_COL_NAME
+                || name == VALUE_LABEL
+                || name == EXEMPLARS_LABEL
+                || name == NATIVE_HISTOGRAM_LABEL
+            {
                 None
             } else {
                 Some(name)
@@ -1756,6 +1827,22 @@ async fn load_exemplars_from_datafusion(
     Ok((metrics, all_unique_timestamps))
 }
 
+/// Splits a selector with `or` matcher groups into one selector per metric
+/// stream, see [`split_or_matchers_by_name`].
+fn split_selector(kind: &str, vs: VectorSelector) -> Result<Vec<VectorSelector>> {
+    split_or_matchers_by_name(vs)
+        .ok_or_else(|| DataFusionError::Plan(format!("{kind}: metric name is required")))
+}
+
+/// Keeps the first series of each label set, as a series can be selected by
+/// more than one `or` matcher group.
+fn dedup_series(data: Vec<RangeValue>) -> Vec<RangeValue> {
+    let mut seen = HashSet::with_capacity(data.len());
+    data.into_iter()
+        .filter(|rv| seen.insert(rv.labels.signature()))
+        .collect()
+}
+
 fn get_offset_modifier(offset: Option<Offset>) -> i64 {
     if let Some(offset) = offset {
         match offset {
@@ -3423,6 +3510,38 @@ mod tests {
         assert_eq!(values.len(), 0); // Mock provider returns empty data
     }
 
+    #[test]
+    fn test_dedup_series() {
+        let series = |job: &str| RangeValue {
+            labels: vec![Arc::new(Label::new("job", job))],
+            samples: vec![],
+            exemplars: None,
+            time_window: None,
+        };
+        let data = dedup_series(vec![series("a"), series("b"), series("a")]);
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].labels.get_value("job"), "a");
+        assert_eq!(data[1].labels.get_value("job"), "b");
+    }
+
+    #[test]
+    fn test_split_selector_requires_metric_name() {
+        let matcher = |name: &str, value: &str| promql_parser::label::Matcher {
+            op: MatchOp::Equal,
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        let mut vs = VectorSelector {
+            name: None,
+            matchers: Matchers::empty(),
+            offset: None,
+            at: None,
+        };
+        vs.matchers.or_matchers = vec![vec![matcher(NAME_LABEL, "up")], vec![matcher("job", "a")]];
+        let err = split_selector("VectorSelector", vs).unwrap_err();
+        assert!(err.to_string().contains("metric name is required"));
+    }
+
     #[test]
     fn test_get_offset_modifier_none() {
         assert_eq!(get_offset_modifier(None), 0);
@@ -26,7 +26,7 @@ use tokio::sync::{RwLock, Semaphore};
 
 use super::Engine;
 use crate::service::promql::{
-    DEFAULT_LOOKBACK, TableProvider, micros, micros_since_epoch,
+    DEFAULT_LOOKBACK, TableProvider, functions, micros, micros_since_epoch,
     selector_visitor::MetricSelectorVisitor,
 };
 
@@ -145,7 +145,12 @@ impl PromqlContext {
         };
 
         let mut sorted_value = final_value;
-        sorted_value.sort();
+        match functions::sort_by_label_args(&expr) {
+            Some((labels, descending)) => {
+                functions::sort_value_by_label(&mut sorted_value, &labels, descending)
+            }
+            None => sorted_value.sort(),
+        }
         Ok((
             sorted_value,
             final_result_type,
@@ -0,0 +1,108 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use config::meta::promql::value::{EvalContext, Sample, Value};
+use datafusion::error::Result;
+
+use crate::service::promql::{common::quantile, functions::RangeFunc};
+
+/// Enhancement over the Prometheus specification: median absolute deviation of
+/// all sample values in the specified interval.
+pub(crate) fn mad_over_time(data: Value, eval_ctx: &EvalContext) -> Result<Value> {
+    super::eval_range(data, MadOverTimeFunc::new(), eval_ctx)
+}
+
+pub struct MadOverTimeFunc;
+
+impl MadOverTimeFunc {
+    pub fn new() -> Self {
+        MadOverTimeFunc {}
+    }
+}
+
+impl RangeFunc for MadOverTimeFunc {
+    fn name(&self) -> &'static str {
+        "mad_over_time"
+    }
+
+    fn exec(&self, samples: &[Sample], _eval_ts: i64, _range: &Duration) -> Option<f64> {
+        let values: Vec<f64> = samples.iter().map(|s| s.value).collect();
+        let median = quantile(&values, 0.5)?;
+        let deviations: Vec<f64> = values.iter().map(|v| (v - median).abs()).collect();
+        quantile(&deviations, 0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::promql::value::{Labels, RangeValue, TimeWindow};
+
+    use super::*;
+
+    fn make_samples(values: &[f64]) -> Vec<Sample> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| Sample::new(i as i64 * 10_000_000, v))
+            .collect()
+    }
+
+    #[test]
+    fn test_mad_over_time_exec_empty_samples_returns_none() {
+        let func = MadOverTimeFunc::new();
+        assert!(func.exec(&[], 0, &Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_mad_over_time_exec() {
+        let func = MadOverTimeFunc::new();
+        // Prometheus functions.test: `metric 4 6 2 1 999 1 2` => 1
+        let samples = make_samples(&[4.0, 6.0, 2.0, 1.0, 999.0, 1.0, 2.0]);
+        assert_eq!(func.exec(&samples, 0, &Duration::ZERO), Some(1.0));
+
+        // a constant series has no deviation
+        let samples = make_samples(&[5.0, 5.0, 5.0]);
+        assert_eq!(func.exec(&samples, 0, &Duration::ZERO), Some(0.0));
+
+        // the median of an even number of values is interpolated
+        let samples = make_samples(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(func.exec(&samples, 0, &Duration::ZERO), Some(1.0));
+    }
+
+    #[test]
+    fn test_mad_over_time_function() {
+        let range_value = RangeValue {
+            labels: Labels::default(),
+            samples: make_samples(&[4.0, 6.0, 2.0, 1.0, 999.0, 1.0, 2.0]),
+            exemplars: None,
+            time_window: Some(TimeWindow {
+                range: Duration::from_secs(70),
+                offset: Duration::ZERO,
+            }),
+        };
+        let eval_ctx = EvalContext::new(60_000_000, 60_000_000, 0, "test".to_string());
+        let result = mad_over_time(Value::Matrix(vec![range_value]), &eval_ctx).unwrap();
+        match result {
+            Value::Matrix(m) => {
+                assert_eq!(m.len(), 1);
+                assert_eq!(m[0].samples.len(), 1);
+                assert_eq!(m[0].samples[0].value, 1.0);
+            }
+            _ => panic!("Expected Matrix result"),
+        }
+    }
+}
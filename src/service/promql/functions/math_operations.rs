@@ -21,15 +21,29 @@ use strum::EnumIter;
 #[derive(Debug, EnumIter)]
 pub enum MathOperationsType {
     Abs,
+    Acos,
+    Acosh,
+    Asin,
+    Asinh,
+    Atan,
+    Atanh,
     Ceil,
+    Cos,
+    Cosh,
+    Deg,
     Exp,
     Floor,
     Ln,
     Log10,
     Log2,
+    Rad,
     Round,
     Sgn,
+    Sin,
+    Sinh,
     Sqrt,
+    Tan,
+    Tanh,
 }
 
 impl MathOperationsType {
@@ -37,15 +51,29 @@ impl MathOperationsType {
     pub fn apply(&self, input: f64) -> f64 {
         match self {
             Self::Abs => input.abs(),
+            Self::Acos => input.acos(),
+            Self::Acosh => input.acosh(),
+            Self::Asin => input.asin(),
+            Self::Asinh => input.asinh(),
+            Self::Atan => input.atan(),
+            Self::Atanh => input.atanh(),
             Self::Ceil => input.ceil(),
+            Self::Cos => input.cos(),
+            Self::Cosh => input.cosh(),
+            Self::Deg => input.to_degrees(),
             Self::Exp => input.exp(),
             Self::Floor => input.floor(),
             Self::Ln => input.ln(),
             Self::Log2 => input.log2(),
             Self::Log10 => input.log10(),
+            Self::Rad => input.to_radians(),
             Self::Sgn => input.signum(),
+            Self::Sin => input.sin(),
+            Self::Sinh => input.sinh(),
             Self::Sqrt => input.sqrt(),
             Self::Round => input.round(),
+            Self::Tan => input.tan(),
+            Self::Tanh => input.tanh(),
         }
     }
 }
@@ -90,6 +118,62 @@ pub(crate) fn sgn(data: Value) -> Result<Value> {
     exec(data, &MathOperationsType::Sgn)
 }
 
+pub(crate) fn acos(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Acos)
+}
+
+pub(crate) fn acosh(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Acosh)
+}
+
+pub(crate) fn asin(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Asin)
+}
+
+pub(crate) fn asinh(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Asinh)
+}
+
+pub(crate) fn atan(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Atan)
+}
+
+pub(crate) fn atanh(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Atanh)
+}
+
+pub(crate) fn cos(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Cos)
+}
+
+pub(crate) fn cosh(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Cosh)
+}
+
+pub(crate) fn deg(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Deg)
+}
+
+pub(crate) fn rad(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Rad)
+}
+
+pub(crate) fn sin(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Sin)
+}
+
+pub(crate) fn sinh(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Sinh)
+}
+
+pub(crate) fn tan(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Tan)
+}
+
+pub(crate) fn tanh(data: Value) -> Result<Value> {
+    exec(data, &MathOperationsType::Tanh)
+}
+
 fn exec(data: Value, op: &MathOperationsType) -> Result<Value> {
     match data {
         Value::Matrix(matrix) => {
@@ -196,6 +280,54 @@ mod tests {
         assert_eq!(MathOperationsType::Sgn.apply(-5.0), -1.0);
     }
 
+    #[test]
+    fn test_trigonometric_operations_apply() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+        assert_eq!(MathOperationsType::Sin.apply(0.0), 0.0);
+        assert_eq!(MathOperationsType::Sin.apply(FRAC_PI_2), 1.0);
+        assert_eq!(MathOperationsType::Cos.apply(0.0), 1.0);
+        assert_eq!(MathOperationsType::Cos.apply(PI), -1.0);
+        assert!((MathOperationsType::Tan.apply(FRAC_PI_4) - 1.0).abs() < 1e-12);
+        assert_eq!(MathOperationsType::Asin.apply(1.0), FRAC_PI_2);
+        assert_eq!(MathOperationsType::Acos.apply(1.0), 0.0);
+        assert_eq!(MathOperationsType::Atan.apply(1.0), FRAC_PI_4);
+        assert_eq!(MathOperationsType::Sinh.apply(0.0), 0.0);
+        assert_eq!(MathOperationsType::Cosh.apply(0.0), 1.0);
+        assert_eq!(MathOperationsType::Tanh.apply(0.0), 0.0);
+        assert_eq!(MathOperationsType::Asinh.apply(0.0), 0.0);
+        assert_eq!(MathOperationsType::Acosh.apply(1.0), 0.0);
+        assert_eq!(MathOperationsType::Atanh.apply(0.0), 0.0);
+
+        // out of domain inputs yield NaN or infinities, like Go's math package
+        assert!(MathOperationsType::Asin.apply(2.0).is_nan());
+        assert!(MathOperationsType::Acos.apply(-2.0).is_nan());
+        assert!(MathOperationsType::Acosh.apply(0.5).is_nan());
+        assert_eq!(MathOperationsType::Atanh.apply(1.0), f64::INFINITY);
+        assert!(MathOperationsType::Sin.apply(f64::INFINITY).is_nan());
+
+        assert!((MathOperationsType::Deg.apply(PI) - 180.0).abs() < 1e-12);
+        assert!((MathOperationsType::Deg.apply(-FRAC_PI_2) + 90.0).abs() < 1e-12);
+        assert!((MathOperationsType::Rad.apply(180.0) - PI).abs() < 1e-12);
+        assert!((MathOperationsType::Rad.apply(-90.0) + FRAC_PI_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_deg_rad_roundtrip() {
+        let eval_ts = 1000;
+        let value = create_matrix(eval_ts, vec![0.0, 45.0, 360.0]);
+        let result = deg(rad(value).unwrap()).unwrap();
+
+        if let Value::Matrix(result_matrix) = result {
+            assert_eq!(result_matrix.len(), 3);
+            assert_eq!(result_matrix[0].samples[0].value, 0.0);
+            assert!((result_matrix[1].samples[0].value - 45.0).abs() < 1e-12);
+            assert!((result_matrix[2].samples[0].value - 360.0).abs() < 1e-12);
+        } else {
+            panic!("Expected Matrix result");
+        }
+    }
+
     #[test]
     fn test_abs() {
         let eval_ts = 1000;
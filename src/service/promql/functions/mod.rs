@@ -38,14 +38,17 @@ mod irate;
 mod label_join;
 mod label_replace;
 mod last_over_time;
+mod mad_over_time;
 mod math_operations;
 mod max_over_time;
 mod min_over_time;
 mod predict_linear;
+mod present_over_time;
 mod quantile_over_time;
 mod rate;
 mod resets;
 mod scalar;
+mod sort_by_label;
 mod stddev_over_time;
 mod stdvar_over_time;
 mod sum_over_time;
@@ -68,14 +71,17 @@ pub(crate) use irate::irate;
 pub(crate) use label_join::label_join;
 pub(crate) use label_replace::label_replace;
 pub(crate) use last_over_time::last_over_time;
+pub(crate) use mad_over_time::mad_over_time;
 pub(crate) use math_operations::*;
 pub(crate) use max_over_time::max_over_time;
 pub(crate) use min_over_time::min_over_time;
 pub(crate) use predict_linear::predict_linear;
+pub(crate) use present_over_time::present_over_time;
 pub(crate) use quantile_over_time::quantile_over_time;
 pub(crate) use rate::rate;
 pub(crate) use resets::resets;
 pub(crate) use scalar::scalar;
+pub(crate) use sort_by_label::{sort_by_label, sort_by_label_args, sort_value_by_label};
 pub(crate) use stddev_over_time::stddev_over_time;
 pub(crate) use stdvar_over_time::stdvar_over_time;
 pub(crate) use sum_over_time::sum_over_time;
@@ -89,17 +95,26 @@ pub(crate) enum Func {
     Abs,
     Absent,
     AbsentOverTime,
+    Acos,
+    Acosh,
+    Asin,
+    Asinh,
+    Atan,
+    Atanh,
     AvgOverTime,
     Ceil,
     Changes,
     Clamp,
     ClampMax,
     ClampMin,
+    Cos,
+    Cosh,
     CountOverTime,
     DayOfMonth,
     DayOfWeek,
     DayOfYear,
     DaysInMonth,
+    Deg,
     Delta,
     Deriv,
     Exp,
@@ -119,23 +134,33 @@ pub(crate) enum Func {
     Ln,
     Log10,
     Log2,
+    MadOverTime,
     MaxOverTime,
     MinOverTime,
     Minute,
     Month,
+    Pi,
     PredictLinear,
+    PresentOverTime,
     QuantileOverTime,
+    Rad,
     Rate,
     Resets,
     Round,
     Scalar,
     Sgn,
+    Sin,
+    Sinh,
     Sort,
+    SortByLabel,
+    SortByLabelDesc,
     SortDesc,
     Sqrt,
     StddevOverTime,
     StdvarOverTime,
     SumOverTime,
+    Tan,
+    Tanh,
     Time,
     Timestamp,
     Vector,
@@ -315,6 +340,17 @@ mod tests {
         );
         assert_eq!("label_join".parse::<Func>().unwrap(), Func::LabelJoin);
         assert_eq!("label_replace".parse::<Func>().unwrap(), Func::LabelReplace);
+        assert_eq!("atanh".parse::<Func>().unwrap(), Func::Atanh);
+        assert_eq!("pi".parse::<Func>().unwrap(), Func::Pi);
+        assert_eq!("mad_over_time".parse::<Func>().unwrap(), Func::MadOverTime);
+        assert_eq!(
+            "present_over_time".parse::<Func>().unwrap(),
+            Func::PresentOverTime
+        );
+        assert_eq!(
+            "sort_by_label_desc".parse::<Func>().unwrap(),
+            Func::SortByLabelDesc
+        );
     }
 
     #[test]
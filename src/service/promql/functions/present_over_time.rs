@@ -0,0 +1,105 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use config::meta::promql::value::{EvalContext, Sample, Value};
+use datafusion::error::Result;
+
+use crate::service::promql::functions::RangeFunc;
+
+/// Returns the value 1 for any series that has samples in the specified
+/// interval.
+pub(crate) fn present_over_time(data: Value, eval_ctx: &EvalContext) -> Result<Value> {
+    super::eval_range(data, PresentOverTimeFunc::new(), eval_ctx)
+}
+
+pub struct PresentOverTimeFunc;
+
+impl PresentOverTimeFunc {
+    pub fn new() -> Self {
+        PresentOverTimeFunc {}
+    }
+}
+
+impl RangeFunc for PresentOverTimeFunc {
+    fn name(&self) -> &'static str {
+        "present_over_time"
+    }
+
+    fn exec(&self, samples: &[Sample], _eval_ts: i64, _range: &Duration) -> Option<f64> {
+        if samples.is_empty() {
+            return None;
+        }
+        Some(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use config::meta::promql::{
+        NAME_LABEL,
+        value::{Label, LabelsExt, RangeValue, TimeWindow},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_present_over_time_value_none_input() {
+        let eval_ctx = EvalContext::new(3000, 3000, 0, "test".to_string());
+        let result = present_over_time(Value::None, &eval_ctx).unwrap();
+        assert!(matches!(result, Value::None));
+    }
+
+    #[test]
+    fn test_present_over_time_function() {
+        // Prometheus functions.test: series with samples in the window yield 1
+        // without the metric name, series without samples are dropped
+        let series = |instance: &str, samples: Vec<Sample>| RangeValue {
+            labels: vec![
+                Arc::new(Label::new(NAME_LABEL, "http_requests")),
+                Arc::new(Label::new("instance", instance)),
+            ],
+            samples,
+            exemplars: None,
+            time_window: Some(TimeWindow {
+                range: Duration::from_secs(60),
+                offset: Duration::ZERO,
+            }),
+        };
+        let matrix = Value::Matrix(vec![
+            series(
+                "0",
+                vec![Sample::new(100_000_000, 5.0), Sample::new(110_000_000, 0.0)],
+            ),
+            series("1", vec![Sample::new(10_000_000, 5.0)]),
+        ]);
+        let eval_ctx = EvalContext::new(120_000_000, 120_000_000, 0, "test".to_string());
+        let result = present_over_time(matrix, &eval_ctx).unwrap();
+        match result {
+            Value::Matrix(m) => {
+                assert_eq!(m.len(), 1);
+                assert_eq!(m[0].labels.get_value("instance"), "0");
+                assert_eq!(m[0].labels.get_value(NAME_LABEL), "");
+                assert_eq!(m[0].samples.len(), 1);
+                assert_eq!(m[0].samples[0].timestamp, 120_000_000);
+                assert_eq!(m[0].samples[0].value, 1.0);
+            }
+            _ => panic!("Expected Matrix result"),
+        }
+    }
+}
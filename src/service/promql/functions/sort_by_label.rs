@@ -0,0 +1,211 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::cmp::Ordering;
+
+use config::meta::promql::value::{Labels, LabelsExt, Value};
+use datafusion::error::{DataFusionError, Result};
+use promql_parser::parser::{Call, Expr as PromExpr, ParenExpr, StringLiteral};
+
+/// Sorts the series by the values of the given labels in natural sort order.
+/// Series with equal values are ordered by their full label set.
+///
+/// Reference: https://prometheus.io/docs/prometheus/latest/querying/functions/#sort_by_label
+pub(crate) fn sort_by_label(data: Value, labels: &[String], descending: bool) -> Result<Value> {
+    let mut data = match data {
+        Value::Matrix(_) | Value::Vector(_) => data,
+        Value::None => return Ok(Value::None),
+        _ => {
+            return Err(DataFusionError::Plan(format!(
+                "Invalid input for sort_by_label, expected instant vector but got: {:?}",
+                data.get_type()
+            )));
+        }
+    };
+    sort_value_by_label(&mut data, labels, descending);
+    Ok(data)
+}
+
+pub(crate) fn sort_value_by_label(data: &mut Value, labels: &[String], descending: bool) {
+    let cmp = |a: &Labels, b: &Labels| {
+        let ord = compare_labels(a, b, labels);
+        if descending { ord.reverse() } else { ord }
+    };
+    match data {
+        Value::Matrix(v) => v.sort_by(|a, b| cmp(&a.labels, &b.labels)),
+        Value::Vector(v) => v.sort_by(|a, b| cmp(&a.labels, &b.labels)),
+        _ => {}
+    }
+}
+
+/// Returns the labels and direction when the outermost function of the query
+/// is `sort_by_label` or `sort_by_label_desc`, whose order must be kept in the
+/// final result.
+pub(crate) fn sort_by_label_args(expr: &PromExpr) -> Option<(Vec<String>, bool)> {
+    match expr {
+        PromExpr::Paren(ParenExpr { expr }) => sort_by_label_args(expr),
+        PromExpr::Call(Call { func, args }) => {
+            let descending = match func.name {
+                "sort_by_label" => false,
+                "sort_by_label_desc" => true,
+                _ => return None,
+            };
+            let labels = args
+                .args
+                .iter()
+                .skip(1)
+                .filter_map(|arg| match arg.as_ref() {
+                    PromExpr::StringLiteral(StringLiteral { val }) => Some(val.clone()),
+                    _ => None,
+                })
+                .collect();
+            Some((labels, descending))
+        }
+        _ => None,
+    }
+}
+
+fn compare_labels(a: &Labels, b: &Labels, labels: &[String]) -> Ordering {
+    for label in labels {
+        let (va, vb) = (a.get_value(label), b.get_value(label));
+        if va != vb {
+            return natural_cmp(&va, &vb);
+        }
+    }
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Compares strings treating runs of digits as numbers, so that `a2` sorts
+/// before `a10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        match (a.is_empty(), b.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            _ => {}
+        }
+        let (chunk_a, rest_a) = split_chunk(a);
+        let (chunk_b, rest_b) = split_chunk(b);
+        let is_num = |s: &str| s.as_bytes()[0].is_ascii_digit();
+        let ord = if is_num(chunk_a) && is_num(chunk_b) {
+            let (ta, tb) = (
+                chunk_a.trim_start_matches('0'),
+                chunk_b.trim_start_matches('0'),
+            );
+            ta.len()
+                .cmp(&tb.len())
+                .then_with(|| ta.cmp(tb))
+                .then_with(|| chunk_a.len().cmp(&chunk_b.len()))
+        } else {
+            chunk_a.cmp(chunk_b)
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+        (a, b) = (rest_a, rest_b);
+    }
+}
+
+/// Splits off the leading run of digits or non-digits.
+fn split_chunk(s: &str) -> (&str, &str) {
+    let digit = s.as_bytes()[0].is_ascii_digit();
+    let end = s
+        .find(|c: char| c.is_ascii_digit() != digit)
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use config::meta::promql::value::{InstantValue, Label, Sample};
+
+    use super::*;
+
+    fn vector(series: &[(&str, &str)]) -> Value {
+        Value::Vector(
+            series
+                .iter()
+                .map(|(instance, job)| InstantValue {
+                    labels: vec![
+                        Arc::new(Label::new("instance", *instance)),
+                        Arc::new(Label::new("job", *job)),
+                    ],
+                    sample: Sample::new(0, 1.0),
+                })
+                .collect(),
+        )
+    }
+
+    fn instances(value: &Value) -> Vec<String> {
+        match value {
+            Value::Vector(v) => v.iter().map(|s| s.labels.get_value("instance")).collect(),
+            _ => panic!("Expected Vector result"),
+        }
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("a2", "a10"), Ordering::Less);
+        assert_eq!(natural_cmp("a10", "a2"), Ordering::Greater);
+        assert_eq!(natural_cmp("10.0.0.2", "10.0.0.10"), Ordering::Less);
+        assert_eq!(natural_cmp("abc", "abd"), Ordering::Less);
+        assert_eq!(natural_cmp("a", "a1"), Ordering::Less);
+        assert_eq!(natural_cmp("a01", "a1"), Ordering::Greater);
+        assert_eq!(natural_cmp("x", "x"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_sort_by_label() {
+        // Prometheus functions.test: instances sort in natural order and ties
+        // on the given label fall back to the full label set
+        let data = vector(&[("10", "api"), ("2", "web"), ("2", "api"), ("1", "web")]);
+        let result = sort_by_label(data, &["instance".to_string()], false).unwrap();
+        assert_eq!(instances(&result), vec!["1", "2", "2", "10"]);
+        if let Value::Vector(v) = &result {
+            assert_eq!(v[1].labels.get_value("job"), "api");
+            assert_eq!(v[2].labels.get_value("job"), "web");
+        }
+
+        let data = vector(&[("10", "api"), ("2", "web"), ("2", "api"), ("1", "web")]);
+        let result =
+            sort_by_label(data, &["job".to_string(), "instance".to_string()], true).unwrap();
+        assert_eq!(instances(&result), vec!["2", "1", "10", "2"]);
+    }
+
+    #[test]
+    fn test_sort_by_label_invalid_input() {
+        assert!(matches!(
+            sort_by_label(Value::None, &[], false).unwrap(),
+            Value::None
+        ));
+        assert!(sort_by_label(Value::Float(1.0), &[], false).is_err());
+    }
+
+    #[test]
+    fn test_sort_by_label_args() {
+        let expr =
+            promql_parser::parser::parse(r#"(sort_by_label_desc(up, "job", "instance"))"#).unwrap();
+        assert_eq!(
+            sort_by_label_args(&expr),
+            Some((vec!["job".to_string(), "instance".to_string()], true))
+        );
+        let expr = promql_parser::parser::parse(r#"sum(sort_by_label(up, "job"))"#).unwrap();
+        assert_eq!(sort_by_label_args(&expr), None);
+    }
+}
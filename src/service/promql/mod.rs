@@ -36,7 +36,7 @@ pub mod name_visitor;
 mod rewrite;
 pub mod search;
 pub mod selector_visitor;
-mod utils;
+pub(crate) mod utils;
 
 pub use engine::Engine;
 pub use exec::PromqlContext;
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::get_config;
+use config::{get_config, meta::promql::NAME_LABEL};
 use promql_parser::{
     label::{MatchOp, Matcher},
     parser::VectorSelector,
@@ -54,6 +54,48 @@ pub fn remove_filter_all(vs: &mut VectorSelector) {
     RemoveFilterAllRewriter::new().rewrite(vs);
 }
 
+/// Splits a selector with `or` matcher groups into one selector per metric
+/// name, as every metric is stored in its own stream. The groups of each
+/// selector are evaluated as an OR of predicates when scanning the stream.
+///
+/// Returns `None` when the metric name can not be resolved, i.e. an `or`
+/// group of a selector without a metric name has no `__name__="..."` matcher.
+pub fn split_or_matchers_by_name(vs: VectorSelector) -> Option<Vec<VectorSelector>> {
+    if vs.matchers.or_matchers.is_empty() || vs.name.is_some() {
+        return Some(vec![vs]);
+    }
+    if let Some(name) = metric_name(&vs.matchers.matchers) {
+        let mut vs = vs;
+        vs.name = Some(name);
+        return Some(vec![vs]);
+    }
+
+    let mut selectors: Vec<VectorSelector> = Vec::new();
+    for group in vs.matchers.or_matchers.iter() {
+        let name = metric_name(group)?;
+        match selectors
+            .iter_mut()
+            .find(|s| s.name.as_deref() == Some(name.as_str()))
+        {
+            Some(selector) => selector.matchers.or_matchers.push(group.clone()),
+            None => {
+                let mut selector = vs.clone();
+                selector.name = Some(name);
+                selector.matchers.or_matchers = vec![group.clone()];
+                selectors.push(selector);
+            }
+        }
+    }
+    Some(selectors)
+}
+
+fn metric_name(matchers: &[Matcher]) -> Option<String> {
+    matchers
+        .iter()
+        .find(|m| m.name == NAME_LABEL && m.op == MatchOp::Equal)
+        .map(|m| m.value.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use promql_parser::label::Matchers;
@@ -229,4 +271,40 @@ mod tests {
         assert_eq!(vs.matchers.matchers.len(), 0);
         assert_eq!(vs.matchers.or_matchers.len(), 0);
     }
+
+    #[test]
+    fn test_split_or_matchers_by_name() {
+        let job_a = create_test_matcher("job", MatchOp::Equal, "a");
+        let job_b = create_test_matcher("job", MatchOp::Equal, "b");
+
+        // a named selector reads a single stream
+        let mut vs = create_vector_selector_with_matchers(vec![]);
+        vs.matchers.or_matchers = vec![vec![job_a.clone()], vec![job_b.clone()]];
+        let selectors = split_or_matchers_by_name(vs).unwrap();
+        assert_eq!(selectors.len(), 1);
+        assert_eq!(selectors[0].matchers.or_matchers.len(), 2);
+
+        // groups are split by the metric name they select
+        let name_up = create_test_matcher(NAME_LABEL, MatchOp::Equal, "up");
+        let name_down = create_test_matcher(NAME_LABEL, MatchOp::Equal, "down");
+        let mut vs = create_vector_selector_with_matchers(vec![]);
+        vs.name = None;
+        vs.matchers.or_matchers = vec![
+            vec![name_up.clone(), job_a.clone()],
+            vec![name_down.clone()],
+            vec![name_up.clone(), job_b.clone()],
+        ];
+        let selectors = split_or_matchers_by_name(vs).unwrap();
+        assert_eq!(selectors.len(), 2);
+        assert_eq!(selectors[0].name.as_deref(), Some("up"));
+        assert_eq!(selectors[0].matchers.or_matchers.len(), 2);
+        assert_eq!(selectors[1].name.as_deref(), Some("down"));
+        assert_eq!(selectors[1].matchers.or_matchers.len(), 1);
+
+        // every group needs a metric name
+        let mut vs = create_vector_selector_with_matchers(vec![]);
+        vs.name = None;
+        vs.matchers.or_matchers = vec![vec![name_up], vec![job_a]];
+        assert!(split_or_matchers_by_name(vs).is_none());
+    }
 }
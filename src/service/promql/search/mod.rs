@@ -51,7 +51,7 @@ use {
 use crate::service::{
     promql::{
         DEFAULT_LOOKBACK, DEFAULT_MAX_POINTS_PER_SERIES, MetricsQueryRequest, adjust_start_end,
-        micros,
+        functions, micros,
     },
     search::server_internal_error,
     self_reporting::report_request_usage_stats,
@@ -494,7 +494,15 @@ async fn search_in_cluster(
     let values = if result_type == "matrix" {
         merge_matrix_query(&series_data, &req.org_id).await?
     } else if result_type == "vector" {
-        merge_vector_query(&series_data, &req.org_id).await?
+        let mut value = merge_vector_query(&series_data, &req.org_id).await?;
+        // keep the order of sort_by_label(), the merged vector is sorted by value
+        if let Some((labels, descending)) = promql_parser::parser::parse(query)
+            .ok()
+            .and_then(|expr| functions::sort_by_label_args(&expr))
+        {
+            functions::sort_value_by_label(&mut value, &labels, descending);
+        }
+        value
     } else if result_type == "scalar" {
         merge_scalar_query(&series_data)
     } else if result_type == "exemplars" {
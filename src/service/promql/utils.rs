@@ -20,10 +20,11 @@ use config::{
 use datafusion::{
     arrow::datatypes::Schema,
     error::Result,
+    logical_expr::Expr,
     prelude::{DataFrame, col, lit},
 };
 use hashbrown::HashSet;
-use promql_parser::label::{MatchOp, Matchers};
+use promql_parser::label::{MatchOp, Matcher, Matchers};
 
 #[cfg(test)]
 mod tests {
@@ -108,6 +109,104 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_matchers_to_sql() {
+        use promql_parser::label::{MatchOp, Matcher};
+        let schema = ArrowSchema::new(vec![
+            Field::new("job", DataType::Utf8, true),
+            Field::new("path", DataType::Utf8, true),
+        ]);
+        let re = regex::Regex::new("/api.*").unwrap();
+        let matchers = Matchers::new(vec![
+            Matcher {
+                op: MatchOp::Equal,
+                name: "job".to_string(),
+                value: "it's".to_string(),
+            },
+            Matcher {
+                op: MatchOp::NotRe(re),
+                name: "path".to_string(),
+                value: "/api.*".to_string(),
+            },
+            Matcher {
+                op: MatchOp::NotEqual,
+                name: "unknown_col".to_string(),
+                value: "x".to_string(),
+            },
+        ]);
+        assert_eq!(
+            matchers_to_sql(&schema, &matchers),
+            vec![
+                r#""job" = 'it''s'"#.to_string(),
+                r#"re_not_match("path", '^(?:/api.*)$')"#.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matchers_to_sql_or_matchers() {
+        use promql_parser::label::{MatchOp, Matcher};
+        let schema = ArrowSchema::new(vec![Field::new("job", DataType::Utf8, true)]);
+        let matcher = |op: MatchOp, name: &str, value: &str| Matcher {
+            op,
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        let mut matchers = Matchers::new(vec![]);
+        matchers.or_matchers = vec![
+            vec![matcher(MatchOp::Equal, "job", "a")],
+            vec![
+                matcher(MatchOp::Equal, "job", "b"),
+                matcher(MatchOp::Equal, "unknown_col", ""),
+            ],
+            vec![matcher(MatchOp::Equal, "unknown_col", "x")],
+        ];
+        assert_eq!(
+            matchers_to_sql(&schema, &matchers),
+            vec![r#"(("job" = 'a') OR ("job" = 'b') OR (false))"#.to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_matchers_or_matchers_dedup() {
+        use datafusion::arrow::array::StringArray;
+        use promql_parser::label::{MatchOp, Matcher};
+
+        let schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("job", DataType::Utf8, true),
+            Field::new("instance", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "a", "b", "c"])),
+                Arc::new(StringArray::from(vec!["1", "2", "1", "1"])),
+            ],
+        )
+        .unwrap();
+        let df = SessionContext::new().read_batch(batch).unwrap();
+        let matcher = |name: &str, value: &str| Matcher {
+            op: MatchOp::Equal,
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        // the first row matches both groups and must only be returned once
+        let mut matchers = Matchers::new(vec![]);
+        matchers.or_matchers = vec![
+            vec![matcher("job", "a"), matcher("instance", "1")],
+            vec![matcher("instance", "1"), matcher("unknown_col", "")],
+        ];
+        let df = apply_matchers(df, &schema, &matchers).unwrap();
+        let rows: usize = df
+            .collect()
+            .await
+            .unwrap()
+            .iter()
+            .map(|b| b.num_rows())
+            .sum();
+        assert_eq!(rows, 3);
+    }
+
     #[test]
     fn test_apply_matchers_timestamp_col_is_skipped() {
         let (df, schema) = make_df();
@@ -126,32 +225,148 @@ use crate::service::search::datafusion::udf::regexp_udf::{REGEX_MATCH_UDF, REGEX
 
 pub fn apply_matchers(df: DataFrame, schema: &Schema, matchers: &Matchers) -> Result<DataFrame> {
     let mut df = df;
-    for mat in matchers.matchers.iter() {
-        if mat.name == TIMESTAMP_COL_NAME
-            || mat.name == VALUE_LABEL
-            || schema.field_with_name(&mat.name).is_err()
-        {
-            continue;
+    let (all, any) = select_matchers(schema, matchers);
+    for mat in all {
+        df = df.filter(matcher_expr(mat))?;
+    }
+    let or_expr = any
+        .into_iter()
+        .map(|group| match group {
+            Some(group) => group
+                .into_iter()
+                .map(matcher_expr)
+                .reduce(Expr::and)
+                .unwrap_or(lit(true)),
+            None => lit(false),
+        })
+        .reduce(Expr::or);
+    if let Some(expr) = or_expr {
+        df = df.filter(expr)?;
+    }
+    Ok(df)
+}
+
+fn matcher_expr(mat: &Matcher) -> Expr {
+    match &mat.op {
+        MatchOp::Equal => col(mat.name.clone()).eq(lit(mat.value.clone())),
+        MatchOp::NotEqual => col(mat.name.clone()).not_eq(lit(mat.value.clone())),
+        MatchOp::Re(regex) => {
+            let regexp_match_udf = REGEX_MATCH_UDF.clone();
+            let regex = format!("^{}$", regex.as_str());
+            regexp_match_udf.call(vec![col(mat.name.clone()), lit(regex)])
         }
-        match &mat.op {
-            MatchOp::Equal => df = df.filter(col(mat.name.clone()).eq(lit(mat.value.clone())))?,
-            MatchOp::NotEqual => {
-                df = df.filter(col(mat.name.clone()).not_eq(lit(mat.value.clone())))?
-            }
-            MatchOp::Re(regex) => {
-                let regexp_match_udf = REGEX_MATCH_UDF.clone();
-                let regex = format!("^{}$", regex.as_str());
-                df = df.filter(regexp_match_udf.call(vec![col(mat.name.clone()), lit(regex)]))?
-            }
-            MatchOp::NotRe(regex) => {
-                let regexp_not_match_udf = REGEX_NOT_MATCH_UDF.clone();
-                let regex = format!("^{}$", regex.as_str());
-                df =
-                    df.filter(regexp_not_match_udf.call(vec![col(mat.name.clone()), lit(regex)]))?
+        MatchOp::NotRe(regex) => {
+            let regexp_not_match_udf = REGEX_NOT_MATCH_UDF.clone();
+            let regex = format!("^{}$", regex.as_str());
+            regexp_not_match_udf.call(vec![col(mat.name.clone()), lit(regex)])
+        }
+    }
+}
+
+/// Picks the matchers to evaluate against a stream, shared by the DataFrame
+/// and SQL conversions. Returns the top-level matchers, which must all hold,
+/// and the `or` matcher groups, of which one must hold.
+///
+/// Top-level matchers on labels missing from the stream are skipped. In an
+/// `or` group, skipping them would widen the group to all series, so they are
+/// evaluated against the empty value instead, like Prometheus does for absent
+/// labels: a group that can not match is `None`.
+#[allow(clippy::type_complexity)]
+fn select_matchers<'a>(
+    schema: &Schema,
+    matchers: &'a Matchers,
+) -> (Vec<&'a Matcher>, Vec<Option<Vec<&'a Matcher>>>) {
+    let all = matchers
+        .matchers
+        .iter()
+        .filter(|mat| {
+            mat.name != TIMESTAMP_COL_NAME
+                && mat.name != VALUE_LABEL
+                && schema.field_with_name(&mat.name).is_ok()
+        })
+        .collect();
+    let any = matchers
+        .or_matchers
+        .iter()
+        .map(|group| {
+            let mut selected = Vec::with_capacity(group.len());
+            for mat in group.iter() {
+                if mat.name == TIMESTAMP_COL_NAME || mat.name == VALUE_LABEL {
+                    continue;
+                }
+                if schema.field_with_name(&mat.name).is_ok() {
+                    selected.push(mat);
+                } else if !matches_empty(mat) {
+                    return None;
+                }
             }
+            Some(selected)
+        })
+        .collect();
+    (all, any)
+}
+
+/// Converts label matchers into SQL conditions, with the same semantics as
+/// [`apply_matchers`]. The `or` matcher groups are appended as a single
+/// condition.
+pub fn matchers_to_sql(schema: &Schema, matchers: &Matchers) -> Vec<String> {
+    let (all, any) = select_matchers(schema, matchers);
+    let mut conditions: Vec<String> = all.into_iter().map(matcher_sql).collect();
+    if !any.is_empty() {
+        let groups = any
+            .into_iter()
+            .map(|group| match group {
+                Some(group) if !group.is_empty() => format!(
+                    "({})",
+                    group
+                        .into_iter()
+                        .map(matcher_sql)
+                        .collect::<Vec<_>>()
+                        .join(" AND ")
+                ),
+                Some(_) => "(true)".to_string(),
+                None => "(false)".to_string(),
+            })
+            .collect::<Vec<_>>();
+        conditions.push(format!("({})", groups.join(" OR ")));
+    }
+    conditions
+}
+
+fn matcher_sql(mat: &Matcher) -> String {
+    let name = format!("\"{}\"", mat.name.replace('"', "\"\""));
+    let value = |v: &str| format!("'{}'", v.replace('\'', "''"));
+    match &mat.op {
+        MatchOp::Equal => format!("{name} = {}", value(&mat.value)),
+        MatchOp::NotEqual => format!("{name} != {}", value(&mat.value)),
+        MatchOp::Re(_) => {
+            format!(
+                "re_match({name}, {})",
+                value(&format!("^(?:{})$", mat.value))
+            )
+        }
+        MatchOp::NotRe(_) => {
+            format!(
+                "re_not_match({name}, {})",
+                value(&format!("^(?:{})$", mat.value))
+            )
         }
     }
-    Ok(df)
+}
+
+/// Whether the matcher selects series where its label is absent.
+pub(crate) fn matches_empty(mat: &Matcher) -> bool {
+    let re_matches_empty = || {
+        regex::Regex::new(&format!("^(?:{})$", mat.value))
+            .map(|re| re.is_match(""))
+            .unwrap_or(false)
+    };
+    match &mat.op {
+        MatchOp::Equal => mat.value.is_empty(),
+        MatchOp::NotEqual => !mat.value.is_empty(),
+        MatchOp::Re(_) => re_matches_empty(),
+        MatchOp::NotRe(_) => !re_matches_empty(),
+    }
 }
 
 pub fn apply_label_selector(
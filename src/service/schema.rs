@@ -27,8 +27,8 @@ use config::{
     ider::SnowflakeIdGenerator,
     meta::{
         promql::{
-            BUCKET_LABEL, EXEMPLARS_LABEL, HASH_LABEL, METADATA_LABEL, NAME_LABEL, QUANTILE_LABEL,
-            VALUE_LABEL,
+            BUCKET_LABEL, EXEMPLARS_LABEL, HASH_LABEL, METADATA_LABEL, NAME_LABEL,
+            NATIVE_HISTOGRAM_LABEL, QUANTILE_LABEL, VALUE_LABEL,
         },
         stream::StreamType,
     },
@@ -536,6 +536,7 @@ pub fn check_schema_for_defined_schema_fields(
             fields.insert(BUCKET_LABEL.to_string());
             fields.insert(QUANTILE_LABEL.to_string());
             fields.insert(EXEMPLARS_LABEL.to_string());
+            fields.insert(NATIVE_HISTOGRAM_LABEL.to_string());
             fields.insert(VALUE_LABEL.to_string());
             fields.insert("trace_id".to_string());
             fields.insert("span_id".to_string());
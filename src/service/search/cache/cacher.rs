@@ -17,7 +17,7 @@ use bytes::Bytes;
 use config::{
     TIMESTAMP_COL_NAME,
     meta::{search::Response, sql::OrderBy, stream::StreamType},
-    utils::{file::scan_files, json},
+    utils::{calendar::CalendarBuckets, file::scan_files, json},
 };
 use infra::cache::{
     file_data::disk::{self, QUERY_RESULT_CACHE},
@@ -47,6 +47,7 @@ pub async fn invalidate_cached_response_by_stream_min_ts(
     file_path: &str,
     responses: &[CachedQueryResponse],
     histogram_interval: i64, // microseconds; 0 for non-histogram queries
+    histogram_calendar: Option<CalendarBuckets>,
 ) -> Result<Vec<CachedQueryResponse>, String> {
     let components: Vec<&str> = file_path.split('/').collect();
     if components.len() < 3 {
@@ -83,7 +84,9 @@ pub async fn invalidate_cached_response_by_stream_min_ts(
             .filter_map(|mut meta| {
                 meta.cached_response.hits.retain(|hit| {
                     let hit_ts = get_ts_value(&meta.ts_column, hit);
-                    hit_ts + histogram_interval > stream_min_ts
+                    let (_, bucket_end) =
+                        histogram_bucket_range(hit_ts, histogram_interval, histogram_calendar);
+                    bucket_end > stream_min_ts
                 });
                 if meta.cached_response.hits.is_empty() {
                     None
@@ -111,6 +114,20 @@ pub async fn invalidate_cached_response_by_stream_min_ts(
     Ok(filtered_responses)
 }
 
+/// Returns the `[start, end)` time range of the histogram bucket of a hit.
+/// Calendar buckets vary in length and their hits carry local wall-clock
+/// timestamps, so they are resolved through the bucket timezone.
+pub(super) fn histogram_bucket_range(
+    hit_ts: i64,
+    histogram_interval: i64,
+    histogram_calendar: Option<CalendarBuckets>,
+) -> (i64, i64) {
+    match histogram_calendar {
+        Some(buckets) => buckets.local_bucket_range(hit_ts),
+        None => (hit_ts, hit_ts + histogram_interval),
+    }
+}
+
 #[tracing::instrument(
     name = "service:search:cache:cacher:check_cache",
     skip_all,
@@ -180,6 +197,11 @@ pub async fn check_cache(
         req.query.sql = origin_sql.to_owned();
         histogram_interval = interval * 1000 * 1000; // in microseconds
     }
+    let histogram_calendar = if histogram_interval > 0 {
+        sql.histogram_calendar
+    } else {
+        None
+    };
 
     // Note: is_descending refinement for histogram queries is now done in prepare_cache_response()
     // before calling this function, so we use the pre-refined value directly
@@ -203,6 +225,7 @@ pub async fn check_cache(
     };
     if histogram_interval > 0 {
         multi_resp.histogram_interval = histogram_interval / 1000 / 1000;
+        multi_resp.histogram_calendar = histogram_calendar;
     }
     log::info!(
         "[trace_id {trace_id}] check_cache: result_ts_col: {}, histogram_interval: {}, time range: {} - {}",
@@ -224,6 +247,7 @@ pub async fn check_cache(
                 histogram_interval,
                 is_descending,
                 is_histogram_non_ts_order,
+                histogram_calendar,
             },
         )
         .await;
@@ -238,6 +262,7 @@ pub async fn check_cache(
             file_path,
             &cached_responses,
             histogram_interval,
+            histogram_calendar,
         )
         .await
         {
@@ -309,6 +334,7 @@ pub async fn check_cache(
                 histogram_interval,
                 is_descending,
                 is_histogram_non_ts_order,
+                histogram_calendar,
             },
             None,
         )
@@ -320,6 +346,7 @@ pub async fn check_cache(
                     file_path,
                     &[cached_resp.clone()],
                     histogram_interval,
+                    histogram_calendar,
                 )
                 .await
                 {
@@ -430,12 +457,19 @@ pub async fn get_cached_results(
                 m.end_time
             );
             // check if the data is matching for histogram
-            if cache_req.is_aggregate
-                && cache_req.histogram_interval > 0
-                && (m.start_time % cache_req.histogram_interval != 0
-                    || m.end_time % cache_req.histogram_interval != 0)
-            {
-                return false;
+            if cache_req.is_aggregate && cache_req.histogram_interval > 0 {
+                let is_aligned = match cache_req.histogram_calendar {
+                    Some(buckets) => {
+                        buckets.is_aligned(m.start_time) && buckets.is_aligned(m.end_time)
+                    }
+                    None => {
+                        m.start_time % cache_req.histogram_interval == 0
+                            && m.end_time % cache_req.histogram_interval == 0
+                    }
+                };
+                if !is_aligned {
+                    return false;
+                }
             }
             m.start_time <= cache_req.q_end_time && m.end_time >= cache_req.q_start_time
         })
@@ -476,17 +510,27 @@ pub async fn get_cached_results(
     // filter data based on request time range
     let (hits_allowed_start_time, hits_allowed_end_time) =
         (cache_req.q_start_time, cache_req.q_end_time);
+    // convert histogram interval to microseconds
+    let histogram_interval = cached_response.histogram_interval.unwrap_or_default() * 1_000_000;
+    let histogram_calendar = cache_req.histogram_calendar;
     let first_ts = get_ts_value(&cache_req.ts_column, cached_response.hits.first().unwrap());
     let last_ts = get_ts_value(&cache_req.ts_column, cached_response.hits.last().unwrap());
+    let (first_ts, last_ts) = match histogram_calendar {
+        Some(buckets) => (
+            buckets.local_bucket_range(first_ts).0,
+            buckets.local_bucket_range(last_ts).0,
+        ),
+        None => (first_ts, last_ts),
+    };
     let data_start_time = std::cmp::min(first_ts, last_ts);
     let data_end_time = std::cmp::max(first_ts, last_ts);
-    // convert histogram interval to microseconds
-    let histogram_interval = cached_response.histogram_interval.unwrap_or_default() * 1_000_000;
     // check if need to filter the data
     if data_start_time < cache_req.q_start_time || data_end_time > cache_req.q_end_time {
         cached_response.hits.retain(|hit| {
             let hit_ts = get_ts_value(&cache_req.ts_column, hit);
-            hit_ts + histogram_interval < hits_allowed_end_time && hit_ts >= hits_allowed_start_time
+            let (bucket_start, bucket_end) =
+                histogram_bucket_range(hit_ts, histogram_interval, histogram_calendar);
+            bucket_end < hits_allowed_end_time && bucket_start >= hits_allowed_start_time
         });
         // if the data is empty after filtering, return None
         if cached_response.hits.is_empty() {
@@ -495,8 +539,12 @@ pub async fn get_cached_results(
         // reset the start and end time
         let first_ts = get_ts_value(&cache_req.ts_column, cached_response.hits.first().unwrap());
         let last_ts = get_ts_value(&cache_req.ts_column, cached_response.hits.last().unwrap());
-        matching_meta.start_time = std::cmp::min(first_ts, last_ts);
-        matching_meta.end_time = std::cmp::max(first_ts, last_ts) + histogram_interval;
+        let (first_start, first_end) =
+            histogram_bucket_range(first_ts, histogram_interval, histogram_calendar);
+        let (last_start, last_end) =
+            histogram_bucket_range(last_ts, histogram_interval, histogram_calendar);
+        matching_meta.start_time = std::cmp::min(first_start, last_start);
+        matching_meta.end_time = std::cmp::max(first_end, last_end);
     }
     cached_response.total = cached_response.hits.len();
 
@@ -940,11 +988,13 @@ pub fn handle_histogram(
     };
 
     let field = args.first().unwrap_or(&"_timestamp");
+    // keep the timezone, it changes the bucket boundaries
+    let histogram = match args.get(2) {
+        Some(timezone) => format!("histogram({field},'{interval}','{timezone}')"),
+        None => format!("histogram({field},'{interval}')"),
+    };
 
-    *origin_sql = origin_sql.replace(
-        caps.get(0).unwrap().as_str(),
-        &format!("histogram({field},'{interval}')"),
-    );
+    *origin_sql = origin_sql.replace(caps.get(0).unwrap().as_str(), &histogram);
 }
 
 fn calculate_deltas_multi(
@@ -1172,6 +1222,31 @@ mod tests {
         handle_histogram(&mut sql, time_range, 10);
         assert!(sql.contains("histogram(_timestamp,"));
         assert!(sql.contains("second"));
+
+        // Test case 2: calendar interval keeps its timezone
+        let mut sql =
+            "SELECT histogram(_timestamp, '1 month', 'Europe/Berlin') FROM logs".to_string();
+        handle_histogram(&mut sql, time_range, 0);
+        assert_eq!(
+            sql,
+            "SELECT histogram(_timestamp,'1 month','Europe/Berlin') FROM logs"
+        );
+    }
+
+    #[test]
+    fn test_histogram_bucket_range() {
+        use config::utils::calendar::{CalendarInterval, CalendarTimezone};
+
+        assert_eq!(histogram_bucket_range(3600, 60, None), (3600, 3660));
+        let buckets = CalendarBuckets::new(
+            CalendarInterval::parse("1 month").unwrap(),
+            CalendarTimezone::parse(Some("+02:00")).unwrap(),
+        );
+        // local 2022-01-01T00:00:00 -> [2021-12-31T22:00:00Z, 2022-01-31T22:00:00Z)
+        assert_eq!(
+            histogram_bucket_range(1640995200000000, 0, Some(buckets)),
+            (1640988000000000, 1643666400000000)
+        );
     }
 
     #[test]
@@ -1207,6 +1282,7 @@ mod tests {
             group_by: vec![],
             order_by: vec![("_timestamp".to_string(), OrderBy::Desc)],
             histogram_interval: None,
+            histogram_calendar: None,
             timezone: None,
             sorted_by_time: true,
             sampling_config: None,
@@ -1307,7 +1383,8 @@ mod tests {
         ];
 
         let file_path = "test_org/logs/test_stream";
-        let result = invalidate_cached_response_by_stream_min_ts(file_path, &responses, 0).await;
+        let result =
+            invalidate_cached_response_by_stream_min_ts(file_path, &responses, 0, None).await;
         assert!(result.is_ok());
         let filtered_responses = result.unwrap();
         assert_eq!(filtered_responses.len(), 2);
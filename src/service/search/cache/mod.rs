@@ -32,6 +32,7 @@ use config::{
     },
     utils::{
         base64,
+        calendar::CalendarBuckets,
         hash::Sum64,
         json,
         sql::{is_complex_query, is_eligible_for_histogram},
@@ -469,6 +470,7 @@ pub async fn search(
             c_resp.is_descending,
             req.clear_cache,
             is_histogram_non_ts_order,
+            c_resp.histogram_calendar,
         )
         .await;
     }
@@ -589,6 +591,9 @@ pub async fn prepare_cache_response(
     if !req.clusters.is_empty() {
         hash_body.extend(req.clusters.clone());
     }
+    if let Some(timezone) = req.query.timezone.as_ref().filter(|tz| !tz.is_empty()) {
+        hash_body.push(timezone.to_string());
+    }
     let mut h = config::utils::hash::gxhash::new();
     let hashed_query = h.sum64(&hash_body.join(","));
 
@@ -654,6 +659,7 @@ pub async fn prepare_cache_response(
             order_by: sql.order_by,
             limit: sql.limit,
             file_path,
+            histogram_calendar: sql.histogram_calendar,
             ..Default::default()
         }
     };
@@ -941,6 +947,7 @@ pub async fn write_results(
     is_descending: bool,
     clear_cache: bool,
     is_histogram_non_ts_order: bool,
+    histogram_calendar: Option<CalendarBuckets>,
 ) {
     if res.hits.is_empty() {
         return;
@@ -950,7 +957,15 @@ pub async fn write_results(
     let mut accept_start_time = req_query_start_time;
     let mut accept_end_time = req_query_end_time;
     let mut need_adjust_end_time = false;
-    if is_aggregate
+    let histogram_calendar = histogram_calendar.filter(|_| is_aggregate);
+    if let Some(buckets) = histogram_calendar {
+        // calendar buckets vary in length, align to their boundaries instead
+        accept_start_time = buckets.ceil(accept_start_time);
+        if !buckets.is_aligned(accept_end_time) {
+            need_adjust_end_time = true;
+            accept_end_time = buckets.floor(buckets.floor(accept_end_time) - 1);
+        }
+    } else if is_aggregate
         && let Some(interval) = res.histogram_interval
         && interval > 0
     {
@@ -972,6 +987,13 @@ pub async fn write_results(
     let is_time_ordered = !is_histogram_non_ts_order;
     let (data_start_time, data_end_time) =
         extract_timestamp_range(&res.hits, ts_column, is_time_ordered);
+    let (data_start_time, data_end_time) = match histogram_calendar {
+        Some(buckets) => (
+            buckets.local_bucket_range(data_start_time).0,
+            buckets.local_bucket_range(data_end_time).0,
+        ),
+        None => (data_start_time, data_end_time),
+    };
     let delay_ts = second_micros(get_config().limit.cache_delay_secs);
     let mut accept_end_time =
         std::cmp::min(Utc::now().timestamp_micros() - delay_ts, accept_end_time);
@@ -980,7 +1002,14 @@ pub async fn write_results(
             if let Some(hit_ts) = hit.get(ts_column)
                 && let Some(hit_ts_datetime) = convert_ts_value_to_datetime(hit_ts)
             {
-                let item_ts = hit_ts_datetime.timestamp_micros();
+                let item_ts = match histogram_calendar {
+                    Some(buckets) => {
+                        buckets
+                            .local_bucket_range(hit_ts_datetime.timestamp_micros())
+                            .0
+                    }
+                    None => hit_ts_datetime.timestamp_micros(),
+                };
                 // only keep the records within the accept time range
                 item_ts >= accept_start_time && item_ts <= accept_end_time
             } else {
@@ -1004,7 +1033,9 @@ pub async fn write_results(
     }
 
     // 5. adjust the cache time range
-    if need_adjust_end_time
+    if need_adjust_end_time && let Some(buckets) = histogram_calendar {
+        accept_end_time = buckets.next(accept_end_time);
+    } else if need_adjust_end_time
         && is_aggregate
         && let Some(interval) = res.histogram_interval
         && interval > 0
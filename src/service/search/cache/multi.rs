@@ -96,6 +96,7 @@ mod tests {
             histogram_interval: 0,
             is_descending: false,
             is_histogram_non_ts_order: false,
+            histogram_calendar: None,
         }
     }
 
@@ -69,6 +69,7 @@ use crate::{
                 table_provider::{catalog::StreamTypeProvider, empty_table::NewEmptyTable},
             },
             inspector::{SearchInspectorFieldsBuilder, search_inspector_fields},
+            scan_budget::ScanBudgets,
             sql::Sql,
             utils::{ScanStatsVisitor, check_query_default_limit_exceeded},
         },
@@ -82,6 +83,7 @@ use crate::{
     fields(org_id = req.org_id)
 )]
 pub async fn search(trace_id: &str, sql: Arc<Sql>, mut req: Request) -> Result<SearchResult> {
+    let started_at = now_micros();
     let mut took_watch = TookWatcher::new();
     let cfg = get_config();
     log::info!("[trace_id {trace_id}] flight->search: start {sql}");
@@ -134,6 +136,21 @@ pub async fn search(trace_id: &str, sql: Arc<Sql>, mut req: Request) -> Result<S
         )
     );
 
+    // 2. check the scan budgets against the size of the file list
+    let scan_budgets = ScanBudgets::resolve(&sql.org_id, req.user_id.as_deref()).await;
+    if let Err(e) = scan_budgets
+        .check(
+            trace_id,
+            file_id_list_vec.iter().map(|v| v.original_size).sum(),
+        )
+        .await
+    {
+        log::warn!("[trace_id {trace_id}] flight->search: {e}");
+        return Err(e);
+    }
+    let budget_stream_name = sql.stream_names.iter().map(|s| s.stream_name()).join(",");
+    let budget_stream_type = sql.stream_type;
+
     #[cfg(feature = "enterprise")]
     let scan_stats = ScanStats {
         files: file_id_list_num as i64,
@@ -367,6 +384,18 @@ pub async fn search(trace_id: &str, sql: Arc<Sql>, mut req: Request) -> Result<S
         took_watch.get_summary()
     );
 
+    // report what was actually scanned, the nodes that scanned it have already
+    // added it to the scan budget usage
+    scan_budgets
+        .record(
+            trace_id,
+            budget_stream_type,
+            &budget_stream_name,
+            &scan_stats,
+            started_at,
+        )
+        .await;
+
     scan_stats.format_to_mb();
     scan_stats.file_list_took += file_id_list_took as i64;
     Ok((
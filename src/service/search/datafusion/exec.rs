@@ -301,6 +301,7 @@ pub fn register_udf(ctx: &SessionContext, org_id: &str) -> Result<()> {
     ctx.register_udf(super::udf::spath_udf::SPATH_UDF.clone());
     ctx.register_udf(super::udf::to_arr_string_udf::TO_ARR_STRING.clone());
     ctx.register_udf(super::udf::histogram_udf::HISTOGRAM_UDF.clone());
+    ctx.register_udf(super::udf::histogram_calendar_udf::HISTOGRAM_CALENDAR_UDF.clone());
     ctx.register_udf(super::udf::match_all_hash_udf::MATCH_ALL_HASH_UDF.clone());
     ctx.register_udf(super::udf::match_all_udf::MATCH_ALL_UDF.clone());
     ctx.register_udf(super::udf::match_all_udf::FUZZY_MATCH_ALL_UDF.clone());
@@ -308,6 +309,17 @@ pub fn register_udf(ctx: &SessionContext, org_id: &str) -> Result<()> {
         super::udaf::summary_percentile::SummaryPercentile::new(),
     ));
     ctx.register_udf(super::udf::cast_to_timestamp_udf::CAST_TO_TIMESTAMP_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::CIDR_MATCH_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_TO_INT_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::INT_TO_IP_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IS_PRIVATE_IP_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_VERSION_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IPV6_TO_IPV4_UDF.clone());
+    ctx.register_udf(super::udf::geoip_udf::GEOIP_UDF.clone());
+    ctx.register_udf(super::udf::grok_udf::grok_udf());
+    ctx.register_udf(super::udf::parse_udf::PARSE_LOGFMT_UDF.clone());
+    ctx.register_udf(super::udf::parse_udf::PARSE_KV_UDF.clone());
+    ctx.register_udf(super::udf::parse_udf::EXTRACT_CSV_UDF.clone());
     let udf_list = get_all_transform(org_id)?;
     for udf in udf_list {
         ctx.register_udf(udf.clone());
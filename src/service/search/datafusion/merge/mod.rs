@@ -26,12 +26,13 @@ use datafusion::{
     catalog::TableProvider,
     error::{DataFusionError, Result},
     physical_plan::execute_stream,
+    prelude::SessionContext,
 };
 use futures::TryStreamExt;
 use parquet::{arrow::AsyncArrowWriter, file::metadata::KeyValue};
 
 use super::table_provider::uniontable::NewUnionTable;
-use crate::service::search::datafusion::exec::DataFusionContextBuilder;
+use crate::service::search::datafusion::exec::{DataFusionContextBuilder, register_udf};
 
 #[cfg(feature = "enterprise")]
 pub mod downsampling;
@@ -112,23 +113,119 @@ pub async fn merge_parquet_files(
     let union_table = Arc::new(NewUnionTable::new(schema.clone(), tables));
     ctx.register_table("tbl", union_table)?;
 
-    let plan = ctx.state().create_logical_plan(&sql).await?;
+    let (schema, mut rx, read_task) = execute_to_channel(&ctx, &sql, "merge_parquet_files").await?;
+
+    // write batches to the appropriate format
+    let buf = match cfg.common.file_format {
+        FileFormat::Parquet => {
+            write_parquet(
+                &schema,
+                bloom_filter_fields,
+                &metadata,
+                is_ingester,
+                &mut rx,
+                read_task,
+            )
+            .await?
+            .0
+        }
+        FileFormat::Vortex => write_vortex(schema, rx, read_task).await?,
+    };
+
+    log::debug!(
+        "merge_parquet_files took {} ms",
+        start.elapsed().as_millis()
+    );
+
+    metadata.compressed_size = buf.len() as i64;
+    Ok(MergeParquetResult::Single(buf, metadata))
+}
+
+/// Rewrite the given files without the rows matching `delete_condition`.
+///
+/// Rows for which the condition evaluates to false **or NULL** are kept, so a
+/// predicate on a column that is missing from some records never drops them.
+/// The returned [`FileMeta`] carries the number of rows actually written.
+pub async fn rewrite_parquet_files(
+    org_id: &str,
+    schema: Arc<Schema>,
+    tables: Vec<Arc<dyn TableProvider>>,
+    bloom_filter_fields: &[String],
+    mut metadata: FileMeta,
+    delete_condition: &str,
+) -> Result<MergeParquetResult> {
+    let start = std::time::Instant::now();
+    let cfg = get_config();
+    if cfg.common.file_format != FileFormat::Parquet {
+        return Err(DataFusionError::Execution(
+            "delete by predicate only supports the parquet file format".to_string(),
+        ));
+    }
+
+    let sql = format!(
+        "SELECT * FROM tbl WHERE ({delete_condition}) IS NOT TRUE ORDER BY {TIMESTAMP_COL_NAME} DESC"
+    );
+    log::debug!("rewrite_parquet_files sql: {sql}");
+
+    let ctx = DataFusionContextBuilder::new()
+        .trace_id("rewrite_parquet_files")
+        .sorted_by_time(true)
+        .build(cfg.limit.datafusion_min_partition_num)
+        .await?;
+    register_udf(&ctx, org_id)?;
+    let union_table = Arc::new(NewUnionTable::new(schema.clone(), tables));
+    ctx.register_table("tbl", union_table)?;
+
+    let (schema, mut rx, read_task) =
+        execute_to_channel(&ctx, &sql, "rewrite_parquet_files").await?;
+    let (buf, records) = write_parquet(
+        &schema,
+        bloom_filter_fields,
+        &metadata,
+        false,
+        &mut rx,
+        read_task,
+    )
+    .await?;
+
+    log::debug!(
+        "rewrite_parquet_files took {} ms",
+        start.elapsed().as_millis()
+    );
+
+    metadata.records = records;
+    metadata.compressed_size = buf.len() as i64;
+    Ok(MergeParquetResult::Single(buf, metadata))
+}
+
+/// Plan and execute `sql`, streaming the resulting batches into a bounded
+/// channel that the file writers drain.
+async fn execute_to_channel(
+    ctx: &SessionContext,
+    sql: &str,
+    name: &'static str,
+) -> Result<(
+    Arc<Schema>,
+    tokio::sync::mpsc::Receiver<RecordBatch>,
+    tokio::task::JoinHandle<Result<()>>,
+)> {
+    let plan = ctx.state().create_logical_plan(sql).await?;
     let physical_plan = ctx.state().create_physical_plan(&plan).await?;
     let schema = physical_plan.schema();
 
     // print the physical plan
-    if cfg.common.print_key_sql {
+    if get_config().common.print_key_sql {
         let plan = datafusion::physical_plan::displayable(physical_plan.as_ref())
             .indent(false)
             .to_string();
         println!("+---------------------------+--------------------------+");
-        println!("merge_parquet_files");
+        println!("{name}");
         println!("+---------------------------+--------------------------+");
         println!("{plan}");
     }
 
     let mut batch_stream = execute_stream(physical_plan, ctx.task_ctx())?;
-    let (tx, mut rx) = tokio::sync::mpsc::channel::<RecordBatch>(2);
+    let (tx, rx) = tokio::sync::mpsc::channel::<RecordBatch>(2);
     let read_task = tokio::task::spawn(async move {
         loop {
             match batch_stream.try_next().await {
@@ -137,42 +234,19 @@ pub async fn merge_parquet_files(
                 }
                 Ok(Some(batch)) => {
                     if let Err(e) = tx.send(batch).await {
-                        log::error!("merge_parquet_files write to channel error: {e}");
+                        log::error!("{name} write to channel error: {e}");
                         return Err(DataFusionError::External(Box::new(e)));
                     }
                 }
                 Err(e) => {
-                    log::error!("merge_parquet_files execute stream error: {e}");
+                    log::error!("{name} execute stream error: {e}");
                     return Err(e);
                 }
             }
         }
         Ok(())
     });
-
-    // write batches to the appropriate format
-    let buf = match cfg.common.file_format {
-        FileFormat::Parquet => {
-            write_parquet(
-                &schema,
-                bloom_filter_fields,
-                &metadata,
-                is_ingester,
-                &mut rx,
-                read_task,
-            )
-            .await?
-        }
-        FileFormat::Vortex => write_vortex(schema, rx, read_task).await?,
-    };
-
-    log::debug!(
-        "merge_parquet_files took {} ms",
-        start.elapsed().as_millis()
-    );
-
-    metadata.compressed_size = buf.len() as i64;
-    Ok(MergeParquetResult::Single(buf, metadata))
+    Ok((schema, rx, read_task))
 }
 
 async fn write_parquet(
@@ -182,7 +256,7 @@ async fn write_parquet(
     is_ingester: bool,
     rx: &mut tokio::sync::mpsc::Receiver<RecordBatch>,
     read_task: tokio::task::JoinHandle<Result<()>>,
-) -> Result<Vec<u8>> {
+) -> Result<(Vec<u8>, i64)> {
     let cfg = get_config();
     let mut buf = Vec::new();
     let compression = if is_ingester && cfg.common.feature_ingester_none_compression {
@@ -214,7 +288,7 @@ async fn write_parquet(
         .map_err(|e| DataFusionError::External(Box::new(e)))??;
     append_metadata(&mut writer, &new_file_meta)?;
     writer.close().await?;
-    Ok(buf)
+    Ok((buf, new_file_meta.records))
 }
 
 async fn write_vortex(
@@ -296,4 +370,51 @@ mod tests {
         // The exact behavior depends on implementation details
         assert!(result.is_ok() || result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_rewrite_parquet_files_keeps_null_rows() {
+        use arrow::array::{Int64Array, StringArray};
+        use datafusion::datasource::MemTable;
+
+        let schema = create_test_schema();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    Some("b"),
+                    None,
+                    Some("a"),
+                ])),
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3), Some(4)])),
+            ],
+        )
+        .unwrap();
+        let table: Arc<dyn TableProvider> =
+            Arc::new(MemTable::try_new(schema.clone(), vec![vec![batch]]).unwrap());
+        let metadata = FileMeta {
+            min_ts: 1,
+            max_ts: 4,
+            records: 4,
+            ..Default::default()
+        };
+
+        let result = rewrite_parquet_files(
+            "test_org",
+            schema,
+            vec![table],
+            &[],
+            metadata,
+            "field1 = 'a'",
+        )
+        .await
+        .unwrap();
+        let MergeParquetResult::Single(buf, meta) = result else {
+            panic!("expected a single file");
+        };
+        // the NULL row does not match the predicate and must survive
+        assert_eq!(meta.records, 2);
+        assert_eq!(meta.compressed_size, buf.len() as i64);
+    }
 }
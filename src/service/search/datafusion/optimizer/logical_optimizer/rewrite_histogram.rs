@@ -16,7 +16,10 @@
 use std::sync::Arc;
 
 use arrow_schema::{DataType, IntervalUnit};
-use config::utils::time::parse_timezone_to_offset_at;
+use config::utils::{
+    calendar::{CalendarInterval, CalendarTimezone},
+    time::parse_timezone_to_offset_at,
+};
 use datafusion::{
     common::{
         Result,
@@ -36,7 +39,9 @@ use datafusion::{
 };
 
 use crate::service::search::{
-    datafusion::udf::histogram_udf::HISTOGRAM_UDF_NAME,
+    datafusion::udf::{
+        histogram_calendar_udf::HISTOGRAM_CALENDAR_UDF, histogram_udf::HISTOGRAM_UDF_NAME,
+    },
     sql::visitor::histogram_interval::generate_histogram_interval,
 };
 
@@ -166,6 +171,46 @@ impl HistogramToDatebin {
     }
 }
 
+impl HistogramToDatebin {
+    /// Rewrite `histogram(ts, '1 month'[, tz])` to `histogram_calendar()`, the
+    /// timezone is passed through so buckets follow the local calendar and DST.
+    fn rewrite_calendar(
+        &self,
+        args: &[Expr],
+        interval: CalendarInterval,
+    ) -> Result<Transformed<Expr>, DataFusionError> {
+        let timezone = match args.get(2) {
+            Some(Expr::Literal(ScalarValue::Utf8(tz), _)) => tz.clone(),
+            Some(other) => {
+                return Err(DataFusionError::Internal(format!(
+                    "Unexpected timezone argument type in histogram function: {other:?}"
+                )));
+            }
+            None => self.timezone.clone(),
+        }
+        .unwrap_or_default();
+        if CalendarTimezone::parse(Some(&timezone)).is_none() {
+            return Err(DataFusionError::Plan(format!(
+                "Invalid timezone in histogram(): '{timezone}'. Expected a fixed offset like '+08:00' / '-05:00', 'UTC', or an IANA name like 'America/Los_Angeles'."
+            )));
+        }
+        let source = Expr::ScalarFunction(ScalarFunction {
+            func: Arc::new(ScalarUDF::from(ToTimestampMicrosFunc::new_with_config(
+                &self.options,
+            ))),
+            args: vec![args[0].clone()],
+        });
+        Ok(Transformed::yes(Expr::ScalarFunction(ScalarFunction {
+            func: Arc::new(HISTOGRAM_CALENDAR_UDF.clone()),
+            args: vec![
+                source,
+                Expr::Literal(ScalarValue::from(interval.to_string()), None),
+                Expr::Literal(ScalarValue::from(timezone), None),
+            ],
+        })))
+    }
+}
+
 impl TreeNodeRewriter for HistogramToDatebin {
     type Node = Expr;
 
@@ -180,6 +225,12 @@ impl TreeNodeRewriter for HistogramToDatebin {
                             args.len()
                         )));
                     }
+                    // calendar intervals have no fixed length, date_bin can't bucket them
+                    if let Some(Expr::Literal(ScalarValue::Utf8(Some(interval)), _)) = args.get(1)
+                        && let Some(interval) = CalendarInterval::parse(interval)
+                    {
+                        return self.rewrite_calendar(args, interval);
+                    }
                     let new_func = Arc::new(ScalarUDF::from(DateBinFunc::new()));
                     // construct interval (date_bin arg #1)
                     let arg1 = if args.len() == 1 {
@@ -620,6 +671,47 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_rewrite_histogram_calendar_interval() {
+        // 2024-03-01T03:00:00Z is still February in New York (22:00 on Feb 29)
+        const TS_2024_03_01T03: i64 = 1_709_262_000_000_000;
+        const D_2024_02_01: i64 = 1_706_745_600_000_000; // 2024-02-01T00:00:00 wall clock
+        const D_2024_03_01: i64 = 1_709_251_200_000_000; // 2024-03-01T00:00:00 wall clock
+        const D_2024_02_26: i64 = 1_708_905_600_000_000; // Monday of 2024-03-01 (Friday)
+        let ctx = histogram_test_ctx(TS_2024_03_01T03, Some("America/New_York"));
+
+        assert_eq!(
+            run_histogram_micros(&ctx, "select histogram(_timestamp, '1 month') from t").await,
+            D_2024_02_01,
+        );
+        assert_eq!(
+            run_histogram_micros(
+                &ctx,
+                "select histogram(_timestamp, '1 month', 'UTC') from t"
+            )
+            .await,
+            D_2024_03_01,
+        );
+        assert_eq!(
+            run_histogram_micros(&ctx, "select histogram(_timestamp, '1 quarter') from t").await,
+            D_2024_01_01,
+        );
+        assert_eq!(
+            run_histogram_micros(&ctx, "select histogram(_timestamp, '1 week', 'UTC') from t")
+                .await,
+            D_2024_02_26,
+        );
+
+        let err = ctx
+            .sql("select histogram(_timestamp, '1 year', 'Not/AZone') from t")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid timezone in histogram()"));
+    }
+
     #[test]
     fn test_rewrite_histogram_new_stores_fields() {
         use datafusion::optimizer::OptimizerRule;
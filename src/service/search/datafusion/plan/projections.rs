@@ -15,6 +15,7 @@
 
 use std::{collections::HashMap, sync::Arc};
 
+use arrow_schema::{Field, Schema, SchemaRef};
 use config::{
     datafusion::request::Request,
     meta::search::{HavingNode, LogicalOperator},
@@ -542,6 +543,24 @@ impl<'n> TreeNodeVisitor<'n> for ResultSchemaExtractor {
     }
 }
 
+/// Columns of the result of a query, taken from its logical plan. Every field is
+/// nullable, the hits of a search leave out the values which are null.
+pub async fn get_result_arrow_schema(sql: &Sql) -> Result<SchemaRef, anyhow::Error> {
+    let sql_arc = Arc::new(sql.clone());
+    let ctx = SearchContextBuilder::new()
+        .build(&Request::default(), &sql_arc)
+        .await?;
+    register_table(&ctx, &sql_arc).await?;
+    let plan = ctx.state().create_logical_plan(&sql_arc.sql).await?;
+    let fields = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| Field::new(f.name(), f.data_type().clone(), true))
+        .collect::<Vec<_>>();
+    Ok(Arc::new(Schema::new(fields)))
+}
+
 pub async fn get_result_schema(
     mut sql: Sql,
     is_streaming: bool,
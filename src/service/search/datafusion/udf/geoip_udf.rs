@@ -0,0 +1,201 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::{
+    net::IpAddr,
+    sync::{Arc, LazyLock as Lazy},
+};
+
+use datafusion::{
+    arrow::{
+        array::{ArrayRef, StringArray},
+        datatypes::DataType,
+    },
+    error::{DataFusionError, Result},
+    logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, TypeSignature,
+        Volatility,
+    },
+};
+use vrl::value::Value;
+
+use super::string_values;
+use crate::common::infra::config::{GEOIP_ASN_TABLE, GEOIP_CITY_TABLE};
+
+/// The name of the geoip UDF given to DataFusion.
+pub(crate) const GEOIP_UDF_NAME: &str = "geoip";
+
+/// Implementation of geoip(ip, field), a field of the MaxMind record of an
+/// address, such as `geoip(client_ip, 'country')`
+pub(crate) static GEOIP_UDF: Lazy<ScalarUDF> = Lazy::new(|| ScalarUDF::from(GeoipUdf::new()));
+
+/// Fields read from the ASN database, the others are read from the city one
+const ASN_FIELDS: [&str; 4] = [
+    "autonomous_system_number",
+    "autonomous_system_organization",
+    "isp",
+    "organization",
+];
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct GeoipUdf {
+    signature: Signature,
+}
+
+impl GeoipUdf {
+    fn new() -> Self {
+        let strings = [DataType::Utf8, DataType::Utf8View, DataType::LargeUtf8];
+        Self {
+            signature: Signature::one_of(
+                strings
+                    .iter()
+                    .map(|ip| TypeSignature::Exact(vec![ip.clone(), DataType::Utf8]))
+                    .collect(),
+                // the databases are refreshed in the background
+                Volatility::Stable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for GeoipUdf {
+    fn name(&self) -> &str {
+        GEOIP_UDF_NAME
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        if args.args.len() != 2 {
+            return Err(DataFusionError::Execution(
+                "UDF params should be: geoip(ip, field)".to_string(),
+            ));
+        }
+        let ips = args.args[0].to_array(args.number_rows)?;
+        let fields = args.args[1].to_array(args.number_rows)?;
+        let fields = string_values(&fields)?;
+        let ips = string_values(&ips)?;
+
+        let city = GEOIP_CITY_TABLE.read();
+        let asn = GEOIP_ASN_TABLE.read();
+        #[cfg(feature = "enterprise")]
+        let enterprise = crate::common::infra::config::GEOIP_ENT_TABLE.read();
+        let array = ips
+            .into_iter()
+            .zip(fields)
+            .map(|(ip, field)| {
+                let ip = ip?.parse::<IpAddr>().ok()?;
+                let field = field_name(field?);
+                // the enterprise database has the fields of both
+                #[cfg(feature = "enterprise")]
+                if let Some(table) = enterprise.as_ref() {
+                    return table.lookup_field(ip, field).map(value_to_string);
+                }
+                let table = if ASN_FIELDS.contains(&field) {
+                    asn.as_ref()
+                } else {
+                    city.as_ref()
+                };
+                table?.lookup_field(ip, field).map(value_to_string)
+            })
+            .collect::<StringArray>();
+        Ok(ColumnarValue::Array(Arc::new(array) as ArrayRef))
+    }
+}
+
+/// Map the short names accepted by the UDF to the fields of the record
+fn field_name(field: &str) -> &str {
+    match field {
+        "country" => "country_name",
+        "city" => "city_name",
+        "region" => "region_name",
+        "continent" => "continent_code",
+        "asn" => "autonomous_system_number",
+        "as_org" => "autonomous_system_organization",
+        field => field,
+    }
+}
+
+fn value_to_string(value: Value) -> String {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{
+            array::Array,
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_field_name() {
+        assert_eq!(field_name("country"), "country_name");
+        assert_eq!(field_name("asn"), "autonomous_system_number");
+        assert_eq!(field_name("postal_code"), "postal_code");
+    }
+
+    #[test]
+    fn test_value_to_string() {
+        assert_eq!(value_to_string(Value::from("IN")), "IN");
+        assert_eq!(value_to_string(Value::Integer(15169)), "15169");
+    }
+
+    #[tokio::test]
+    async fn test_geoip_udf_without_database() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "ip",
+            DataType::Utf8View,
+            true,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(datafusion::arrow::array::StringViewArray::from(
+                vec![Some("8.8.8.8"), Some("invalid"), None],
+            ))],
+        )
+        .unwrap();
+        let ctx = SessionContext::new();
+        ctx.register_udf(GEOIP_UDF.clone());
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+
+        let batches = ctx
+            .sql("select geoip(ip, 'country') as country from t")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        // nothing is known about any address until a database is loaded
+        let column = batches[0].column(0);
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.null_count(), 3);
+    }
+}
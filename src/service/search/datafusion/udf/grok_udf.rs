@@ -0,0 +1,215 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use datafusion::{
+    arrow::datatypes::DataType,
+    error::{DataFusionError, Result},
+    logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, TypeSignature,
+        Volatility,
+    },
+};
+use grok::{Grok, Pattern};
+use parking_lot::Mutex;
+
+use super::{
+    parse_udf::{STRING_MAP_TYPE, append_pairs, new_map_builder},
+    string_values,
+};
+
+/// The name of the grok UDF given to DataFusion.
+pub(crate) const GROK_UDF_NAME: &str = "grok";
+
+/// Implementation of grok(field, pattern), a map of the named captures of a
+/// grok pattern such as `%{IP:client} %{WORD:method} %{URIPATHPARAM:path}`,
+/// the standard pattern library is available.
+///
+/// Compiled patterns are kept by the UDF, so a new one is created for every
+/// query to compile each pattern once per query.
+pub(crate) fn grok_udf() -> ScalarUDF {
+    ScalarUDF::from(GrokUdf::new())
+}
+
+struct GrokUdf {
+    signature: Signature,
+    patterns: Mutex<HashMap<String, Arc<Pattern>>>,
+}
+
+impl GrokUdf {
+    fn new() -> Self {
+        let strings = [DataType::Utf8, DataType::Utf8View, DataType::LargeUtf8];
+        Self {
+            signature: Signature::one_of(
+                strings
+                    .iter()
+                    .map(|field| TypeSignature::Exact(vec![field.clone(), DataType::Utf8]))
+                    .collect(),
+                Volatility::Immutable,
+            ),
+            patterns: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn compile(&self, pattern: &str) -> Result<Arc<Pattern>> {
+        if let Some(compiled) = self.patterns.lock().get(pattern) {
+            return Ok(compiled.clone());
+        }
+        let mut grok = Grok::with_default_patterns();
+        let compiled = grok.compile(pattern, true).map_err(|e| {
+            DataFusionError::Execution(format!("{GROK_UDF_NAME}: invalid pattern '{pattern}': {e}"))
+        })?;
+        let compiled = Arc::new(compiled);
+        self.patterns
+            .lock()
+            .insert(pattern.to_string(), compiled.clone());
+        Ok(compiled)
+    }
+}
+
+impl fmt::Debug for GrokUdf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GrokUdf")
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl PartialEq for GrokUdf {
+    fn eq(&self, other: &Self) -> bool {
+        self.signature == other.signature
+    }
+}
+
+impl Eq for GrokUdf {}
+
+impl Hash for GrokUdf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.signature.hash(state);
+    }
+}
+
+impl ScalarUDFImpl for GrokUdf {
+    fn name(&self) -> &str {
+        GROK_UDF_NAME
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(STRING_MAP_TYPE.clone())
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        if args.args.len() != 2 {
+            return Err(DataFusionError::Execution(
+                "UDF params should be: grok(field, pattern)".to_string(),
+            ));
+        }
+        let arrays = ColumnarValue::values_to_arrays(&args.args)?;
+        let fields = string_values(&arrays[0])?;
+        let patterns = string_values(&arrays[1])?;
+
+        let mut builder = new_map_builder();
+        for (field, pattern) in fields.into_iter().zip(patterns) {
+            let pairs = match (field, pattern) {
+                (Some(field), Some(pattern)) => {
+                    self.compile(pattern)?.match_against(field).map(|matches| {
+                        matches
+                            .iter()
+                            .map(|(name, value)| (name.to_string(), value.to_string()))
+                            .collect::<Vec<_>>()
+                    })
+                }
+                _ => None,
+            };
+            append_pairs(&mut builder, pairs)?;
+        }
+        Ok(ColumnarValue::Array(Arc::new(builder.finish())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{
+            array::StringViewArray,
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_grok_pattern_is_compiled_once() {
+        let udf = GrokUdf::new();
+        let first = udf.compile("%{IP:client}").unwrap();
+        let second = udf.compile("%{IP:client}").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(udf.compile("%{NO_SUCH_PATTERN:x}").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_grok_udf() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "log",
+            DataType::Utf8View,
+            true,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringViewArray::from(vec![
+                Some("10.0.0.1 GET /index.html 200"),
+                Some("10.0.0.2 POST /login 401"),
+                Some("not an access log"),
+                None,
+            ]))],
+        )
+        .unwrap();
+        let ctx = SessionContext::new();
+        ctx.register_udf(grok_udf());
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+
+        let pattern = "%{IP:client} %{WORD:method} %{URIPATHPARAM:path} %{NUMBER:status}";
+        let sql = format!(
+            "select grok(log, '{pattern}')['client'] as client, grok(log, '{pattern}')['path'] as path \
+             from t where grok(log, '{pattern}')['status'] = '401'"
+        );
+        let batches = ctx.sql(&sql).await.unwrap().collect().await.unwrap();
+        assert_batches_eq!(
+            [
+                "+----------+--------+",
+                "| client   | path   |",
+                "+----------+--------+",
+                "| 10.0.0.2 | /login |",
+                "+----------+--------+",
+            ],
+            &batches
+        );
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, LazyLock as Lazy};
+
+use config::utils::calendar::{CalendarBuckets, CalendarInterval, CalendarTimezone};
+use datafusion::{
+    arrow::{
+        array::{ArrayRef, TimestampMicrosecondArray},
+        datatypes::{DataType, TimeUnit},
+    },
+    common::{ScalarValue, cast::as_timestamp_microsecond_array},
+    error::DataFusionError,
+    logical_expr::{ColumnarValue, ScalarUDF, Volatility},
+    prelude::create_udf,
+};
+
+/// The name of the histogram_calendar UDF given to DataFusion.
+pub const HISTOGRAM_CALENDAR_UDF_NAME: &str = "histogram_calendar";
+
+/// Implementation of the calendar buckets of `histogram()`, the logical
+/// optimizer rewrites `histogram(ts, '1 month', 'Europe/Berlin')` to
+/// `histogram_calendar(to_timestamp_micros(ts), '1 month', 'Europe/Berlin')`.
+pub(crate) static HISTOGRAM_CALENDAR_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        HISTOGRAM_CALENDAR_UDF_NAME,
+        vec![
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+            DataType::Utf8,
+            DataType::Utf8,
+        ],
+        DataType::Timestamp(TimeUnit::Microsecond, None),
+        Volatility::Immutable,
+        Arc::new(histogram_calendar_impl),
+    )
+});
+
+/// Returns the start of the calendar bucket of every timestamp, as local
+/// wall-clock time of the bucket timezone like the fixed interval buckets.
+pub fn histogram_calendar_impl(args: &[ColumnarValue]) -> datafusion::error::Result<ColumnarValue> {
+    if args.len() != 3 {
+        return Err(DataFusionError::Execution(
+            "UDF params should be: histogram_calendar(timestamp, interval, timezone)".to_string(),
+        ));
+    }
+    let interval = scalar_string(&args[1])?;
+    let interval = CalendarInterval::parse(&interval).ok_or_else(|| {
+        DataFusionError::Execution(format!("Invalid calendar interval: '{interval}'"))
+    })?;
+    let timezone = scalar_string(&args[2])?;
+    let timezone = CalendarTimezone::parse(Some(&timezone)).ok_or_else(|| {
+        DataFusionError::Execution(format!("Invalid timezone in histogram(): '{timezone}'"))
+    })?;
+    let buckets = CalendarBuckets::new(interval, timezone);
+
+    match &args[0] {
+        ColumnarValue::Scalar(ScalarValue::TimestampMicrosecond(v, _)) => {
+            Ok(ColumnarValue::Scalar(ScalarValue::TimestampMicrosecond(
+                v.map(|v| buckets.floor_local(v)),
+                None,
+            )))
+        }
+        ColumnarValue::Array(array) => {
+            let array = as_timestamp_microsecond_array(array)?;
+            let result: TimestampMicrosecondArray = array.unary(|v| buckets.floor_local(v));
+            Ok(ColumnarValue::Array(Arc::new(result) as ArrayRef))
+        }
+        other => Err(DataFusionError::Execution(format!(
+            "Unexpected timestamp argument in histogram_calendar: {other:?}"
+        ))),
+    }
+}
+
+fn scalar_string(arg: &ColumnarValue) -> datafusion::error::Result<String> {
+    match arg {
+        ColumnarValue::Scalar(
+            ScalarValue::Utf8(v) | ScalarValue::Utf8View(v) | ScalarValue::LargeUtf8(v),
+        ) => Ok(v.clone().unwrap_or_default()),
+        other => Err(DataFusionError::Execution(format!(
+            "histogram_calendar expects a literal string, got: {other:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(s: &str) -> i64 {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .unwrap()
+            .timestamp_micros()
+    }
+
+    fn string(v: &str) -> ColumnarValue {
+        ColumnarValue::Scalar(ScalarValue::Utf8(Some(v.to_string())))
+    }
+
+    #[test]
+    fn test_histogram_calendar_impl() {
+        let timestamps = TimestampMicrosecondArray::from(vec![
+            micros("2025-03-01T04:00:00Z"),
+            micros("2025-03-31T23:00:00Z"),
+            micros("2025-04-01T04:00:00Z"),
+        ]);
+        let args = [
+            ColumnarValue::Array(Arc::new(timestamps)),
+            string("1 month"),
+            string("America/New_York"),
+        ];
+        let ColumnarValue::Array(result) = histogram_calendar_impl(&args).unwrap() else {
+            panic!("expected an array");
+        };
+        let result = as_timestamp_microsecond_array(&result).unwrap();
+        assert_eq!(result.value(0), micros("2025-02-01T00:00:00Z"));
+        assert_eq!(result.value(1), micros("2025-03-01T00:00:00Z"));
+        assert_eq!(result.value(2), micros("2025-04-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_histogram_calendar_impl_invalid_args() {
+        let ts = ColumnarValue::Scalar(ScalarValue::TimestampMicrosecond(Some(0), None));
+        assert!(histogram_calendar_impl(&[ts.clone()]).is_err());
+        assert!(histogram_calendar_impl(&[ts.clone(), string("1 day"), string("")]).is_err());
+        assert!(
+            histogram_calendar_impl(&[ts.clone(), string("1 month"), string("Nowhere")]).is_err()
+        );
+        assert!(matches!(
+            histogram_calendar_impl(&[ts, string("1 year"), string("")]).unwrap(),
+            ColumnarValue::Scalar(ScalarValue::TimestampMicrosecond(Some(v), None))
+                if v == micros("1970-01-01T00:00:00Z")
+        ));
+    }
+}
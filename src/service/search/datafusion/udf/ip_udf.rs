@@ -0,0 +1,430 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! IP address and CIDR functions.
+//!
+//! Addresses are read from string columns, invalid addresses give NULL.
+//! IPv4 and IPv6 addresses are distinct, an IPv4-mapped IPv6 address only
+//! matches IPv6 networks, use `ipv6_to_ipv4` to compare it as IPv4.
+
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    sync::{Arc, LazyLock as Lazy},
+};
+
+use datafusion::{
+    arrow::{
+        array::{ArrayRef, BooleanArray, Int64Array, StringArray},
+        datatypes::DataType,
+    },
+    common::cast::as_int64_array,
+    error::{DataFusionError, Result},
+    logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, TypeSignature,
+        Volatility,
+    },
+};
+
+use super::string_values;
+
+/// The name of the cidr_match UDF given to DataFusion.
+pub(crate) const CIDR_MATCH_UDF_NAME: &str = "cidr_match";
+/// The name of the ip_to_int UDF given to DataFusion.
+pub(crate) const IP_TO_INT_UDF_NAME: &str = "ip_to_int";
+/// The name of the int_to_ip UDF given to DataFusion.
+pub(crate) const INT_TO_IP_UDF_NAME: &str = "int_to_ip";
+/// The name of the is_private_ip UDF given to DataFusion.
+pub(crate) const IS_PRIVATE_IP_UDF_NAME: &str = "is_private_ip";
+/// The name of the ip_version UDF given to DataFusion.
+pub(crate) const IP_VERSION_UDF_NAME: &str = "ip_version";
+/// The name of the ipv6_to_ipv4 UDF given to DataFusion.
+pub(crate) const IPV6_TO_IPV4_UDF_NAME: &str = "ipv6_to_ipv4";
+
+/// Implementation of cidr_match(ip, cidr), whether the address is in the network
+pub(crate) static CIDR_MATCH_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(IpUdf::new(IpFunc::CidrMatch)));
+
+/// Implementation of ip_to_int(ip), the integer value of an IPv4 address
+pub(crate) static IP_TO_INT_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(IpUdf::new(IpFunc::IpToInt)));
+
+/// Implementation of int_to_ip(int), the IPv4 address of an integer
+pub(crate) static INT_TO_IP_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(IpUdf::new(IpFunc::IntToIp)));
+
+/// Implementation of is_private_ip(ip)
+pub(crate) static IS_PRIVATE_IP_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(IpUdf::new(IpFunc::IsPrivateIp)));
+
+/// Implementation of ip_version(ip), 4 or 6
+pub(crate) static IP_VERSION_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(IpUdf::new(IpFunc::IpVersion)));
+
+/// Implementation of ipv6_to_ipv4(ip), the IPv4 address of an IPv4-mapped
+/// IPv6 address
+pub(crate) static IPV6_TO_IPV4_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(IpUdf::new(IpFunc::Ipv6ToIpv4)));
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+enum IpFunc {
+    CidrMatch,
+    IpToInt,
+    IntToIp,
+    IsPrivateIp,
+    IpVersion,
+    Ipv6ToIpv4,
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct IpUdf {
+    func: IpFunc,
+    signature: Signature,
+}
+
+impl IpUdf {
+    fn new(func: IpFunc) -> Self {
+        let strings = [DataType::Utf8, DataType::Utf8View, DataType::LargeUtf8];
+        let signature = match func {
+            IpFunc::CidrMatch => Signature::one_of(
+                strings
+                    .iter()
+                    .flat_map(|ip| {
+                        strings
+                            .iter()
+                            .map(|cidr| TypeSignature::Exact(vec![ip.clone(), cidr.clone()]))
+                    })
+                    .collect(),
+                Volatility::Immutable,
+            ),
+            IpFunc::IntToIp => Signature::exact(vec![DataType::Int64], Volatility::Immutable),
+            _ => Signature::one_of(
+                strings
+                    .iter()
+                    .map(|ip| TypeSignature::Exact(vec![ip.clone()]))
+                    .collect(),
+                Volatility::Immutable,
+            ),
+        };
+        Self { func, signature }
+    }
+}
+
+impl ScalarUDFImpl for IpUdf {
+    fn name(&self) -> &str {
+        match self.func {
+            IpFunc::CidrMatch => CIDR_MATCH_UDF_NAME,
+            IpFunc::IpToInt => IP_TO_INT_UDF_NAME,
+            IpFunc::IntToIp => INT_TO_IP_UDF_NAME,
+            IpFunc::IsPrivateIp => IS_PRIVATE_IP_UDF_NAME,
+            IpFunc::IpVersion => IP_VERSION_UDF_NAME,
+            IpFunc::Ipv6ToIpv4 => IPV6_TO_IPV4_UDF_NAME,
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(match self.func {
+            IpFunc::CidrMatch | IpFunc::IsPrivateIp => DataType::Boolean,
+            IpFunc::IpToInt | IpFunc::IpVersion => DataType::Int64,
+            IpFunc::IntToIp | IpFunc::Ipv6ToIpv4 => DataType::Utf8,
+        })
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let expected = if self.func == IpFunc::CidrMatch { 2 } else { 1 };
+        if args.args.len() != expected {
+            return Err(DataFusionError::Execution(format!(
+                "{} expects {expected} arguments",
+                self.name()
+            )));
+        }
+        let arrays = args
+            .args
+            .iter()
+            .map(|arg| arg.to_array(args.number_rows))
+            .collect::<Result<Vec<_>>>()?;
+        let array: ArrayRef = match self.func {
+            IpFunc::CidrMatch => Arc::new(cidr_match(&arrays[0], &arrays[1])?),
+            IpFunc::IpToInt => Arc::new(
+                parse_ips(&arrays[0])?
+                    .map(|ip| ip.and_then(to_ipv4).map(|ip| u32::from(ip) as i64))
+                    .collect::<Int64Array>(),
+            ),
+            IpFunc::IntToIp => Arc::new(
+                as_int64_array(&arrays[0])?
+                    .iter()
+                    .map(|v| {
+                        v.and_then(|v| u32::try_from(v).ok())
+                            .map(|v| Ipv4Addr::from(v).to_string())
+                    })
+                    .collect::<StringArray>(),
+            ),
+            IpFunc::IsPrivateIp => Arc::new(
+                parse_ips(&arrays[0])?
+                    .map(|ip| ip.map(is_private))
+                    .collect::<BooleanArray>(),
+            ),
+            IpFunc::IpVersion => Arc::new(
+                parse_ips(&arrays[0])?
+                    .map(|ip| ip.map(|ip| if ip.is_ipv4() { 4 } else { 6 }))
+                    .collect::<Int64Array>(),
+            ),
+            IpFunc::Ipv6ToIpv4 => Arc::new(
+                parse_ips(&arrays[0])?
+                    .map(|ip| ip.and_then(to_ipv4).map(|ip| ip.to_string()))
+                    .collect::<StringArray>(),
+            ),
+        };
+        Ok(ColumnarValue::Array(array))
+    }
+}
+
+/// An IPv4 or IPv6 network, such as `10.0.0.0/8`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Cidr {
+    pub addr: IpAddr,
+    pub prefix_len: u8,
+}
+
+impl Cidr {
+    /// Parse a network, a plain address is a network of that single address.
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        let (addr, prefix_len) = match s.trim().split_once('/') {
+            Some((addr, prefix_len)) => (addr.parse::<IpAddr>().ok()?, prefix_len.parse().ok()?),
+            None => {
+                let addr = s.trim().parse::<IpAddr>().ok()?;
+                (addr, if addr.is_ipv4() { 32 } else { 128 })
+            }
+        };
+        let max_len = if addr.is_ipv4() { 32 } else { 128 };
+        (prefix_len <= max_len).then_some(Self { addr, prefix_len })
+    }
+
+    pub(crate) fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+fn cidr_match(ips: &ArrayRef, cidrs: &ArrayRef) -> Result<BooleanArray> {
+    let cidrs = string_values(cidrs)?;
+    // the network is nearly always a literal, parse it once
+    let mut last: Option<(&str, Cidr)> = None;
+    parse_ips(ips)?
+        .zip(cidrs)
+        .map(|(ip, cidr)| {
+            let (Some(ip), Some(cidr)) = (ip, cidr) else {
+                return Ok(None);
+            };
+            let net = match last {
+                Some((s, net)) if s == cidr => net,
+                _ => {
+                    let net = Cidr::parse(cidr).ok_or_else(|| {
+                        DataFusionError::Execution(format!(
+                            "{CIDR_MATCH_UDF_NAME}: invalid network '{cidr}'"
+                        ))
+                    })?;
+                    last = Some((cidr, net));
+                    net
+                }
+            };
+            Ok(Some(net.contains(ip)))
+        })
+        .collect()
+}
+
+fn parse_ips(array: &ArrayRef) -> Result<impl Iterator<Item = Option<IpAddr>> + '_> {
+    Ok(string_values(array)?
+        .into_iter()
+        .map(|v| v.and_then(|v| v.parse::<IpAddr>().ok())))
+}
+
+fn to_ipv4(ip: IpAddr) -> Option<Ipv4Addr> {
+    match ip {
+        IpAddr::V4(ip) => Some(ip),
+        IpAddr::V6(ip) => ip.to_ipv4_mapped(),
+    }
+}
+
+/// RFC 1918 IPv4 and RFC 4193 unique local IPv6 addresses
+fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_private(),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => ip.is_private(),
+            None => ip.segments()[0] & 0xfe00 == 0xfc00,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{
+            array::StringViewArray,
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_cidr() {
+        let net = Cidr::parse("10.0.0.0/8").unwrap();
+        assert!(net.contains("10.1.2.3".parse().unwrap()));
+        assert!(!net.contains("11.1.2.3".parse().unwrap()));
+        assert!(!net.contains("::ffff:10.1.2.3".parse().unwrap()));
+
+        let net = Cidr::parse("2001:db8::/32").unwrap();
+        assert!(net.contains("2001:db8::1".parse().unwrap()));
+        assert!(!net.contains("2001:db9::1".parse().unwrap()));
+
+        assert!(
+            Cidr::parse("0.0.0.0/0")
+                .unwrap()
+                .contains("1.2.3.4".parse().unwrap())
+        );
+        assert_eq!(Cidr::parse("1.2.3.4").unwrap().prefix_len, 32);
+        assert!(Cidr::parse("10.0.0.0/33").is_none());
+        assert!(Cidr::parse("10.0.0/8").is_none());
+    }
+
+    #[test]
+    fn test_is_private() {
+        assert!(is_private("192.168.1.1".parse().unwrap()));
+        assert!(is_private("172.16.0.1".parse().unwrap()));
+        assert!(!is_private("8.8.8.8".parse().unwrap()));
+        assert!(is_private("fd00::1".parse().unwrap()));
+        assert!(is_private("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!is_private("2001:db8::1".parse().unwrap()));
+    }
+
+    fn context() -> SessionContext {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("ip", DataType::Utf8, true),
+            Field::new("ip_view", DataType::Utf8View, true),
+            Field::new("n", DataType::Int64, true),
+        ]));
+        let ips = vec![
+            Some("10.1.2.3"),
+            Some("192.168.0.1"),
+            Some("::ffff:8.8.8.8"),
+            Some("not an ip"),
+            None,
+        ];
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(ips.clone())),
+                Arc::new(StringViewArray::from(ips)),
+                Arc::new(Int64Array::from(vec![
+                    Some(167838211),
+                    Some(-1),
+                    Some(4294967296),
+                    Some(0),
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+        let ctx = SessionContext::new();
+        for udf in [
+            &CIDR_MATCH_UDF,
+            &IP_TO_INT_UDF,
+            &INT_TO_IP_UDF,
+            &IS_PRIVATE_IP_UDF,
+            &IP_VERSION_UDF,
+            &IPV6_TO_IPV4_UDF,
+        ] {
+            ctx.register_udf(ScalarUDF::clone(udf));
+        }
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_ip_udfs() {
+        let ctx = context();
+        let sql = "select cidr_match(ip, '10.0.0.0/8') as a, cidr_match(ip_view, '192.168.0.0/16') as b, \
+                   ip_to_int(ip_view) as c, int_to_ip(n) as d, is_private_ip(ip) as e, \
+                   ip_version(ip_view) as f, ipv6_to_ipv4(ip) as g from t";
+        let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        assert_batches_eq!(
+            [
+                "+-------+-------+------------+----------+-------+---+-------------+",
+                "| a     | b     | c          | d        | e     | f | g           |",
+                "+-------+-------+------------+----------+-------+---+-------------+",
+                "| true  | false | 167838211  | 10.1.2.3 | true  | 4 | 10.1.2.3    |",
+                "| false | true  | 3232235521 |          | true  | 4 | 192.168.0.1 |",
+                "| false | false | 134744072  |          | false | 6 | 8.8.8.8     |",
+                "|       |       |            | 0.0.0.0  |       |   |             |",
+                "|       |       |            |          |       |   |             |",
+                "+-------+-------+------------+----------+-------+---+-------------+",
+            ],
+            &batches
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cidr_match_filter() {
+        let ctx = context();
+        let batches = ctx
+            .sql("select ip from t where cidr_match(ip_view, '10.0.0.0/8') or cidr_match(ip, '192.168.0.0/24')")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_batches_eq!(
+            [
+                "+-------------+",
+                "| ip          |",
+                "+-------------+",
+                "| 10.1.2.3    |",
+                "| 192.168.0.1 |",
+                "+-------------+",
+            ],
+            &batches
+        );
+
+        let ret = ctx
+            .sql("select cidr_match(ip, '10.0.0.0/40') from t")
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(ret.is_err());
+    }
+}
@@ -14,6 +14,11 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use config::{meta::function::ZoFunction, utils::json};
+use datafusion::{
+    arrow::{array::ArrayRef, datatypes::DataType},
+    common::cast::{as_large_string_array, as_string_array, as_string_view_array},
+    error::{DataFusionError, Result},
+};
 
 pub(crate) mod arr_descending_udf;
 pub(crate) mod arrcount_udf;
@@ -27,9 +32,14 @@ pub(crate) mod cast_to_timestamp_udf;
 pub(crate) mod cipher_udf;
 pub(crate) mod date_format_udf;
 pub(crate) mod fuzzy_match_udf;
+pub(crate) mod geoip_udf;
+pub(crate) mod grok_udf;
+pub(crate) mod histogram_calendar_udf;
 pub(crate) mod histogram_udf;
+pub(crate) mod ip_udf;
 pub(crate) mod match_all_hash_udf;
 pub(crate) mod match_all_udf;
+pub(crate) mod parse_udf;
 pub(crate) mod regexp_matches_udf;
 pub(crate) mod regexp_udf;
 pub(crate) mod spath_udf;
@@ -56,7 +66,7 @@ pub(crate) const REGEX_NOT_MATCH_UDF_NAME: &str = "re_not_match";
 /// The name of the regex_matches UDF given to DataFusion.
 pub(crate) const REGEX_MATCHES_UDF_NAME: &str = "re_matches";
 
-pub(crate) const DEFAULT_FUNCTIONS: [ZoFunction; 11] = [
+pub(crate) const DEFAULT_FUNCTIONS: [ZoFunction; 15] = [
     ZoFunction {
         name: "match_all",
         text: "match_all('v')",
@@ -101,8 +111,38 @@ pub(crate) const DEFAULT_FUNCTIONS: [ZoFunction; 11] = [
         name: cast_to_timestamp_udf::CAST_TO_TIMESTAMP_UDF_NAME,
         text: "cast_to_timestamp('pattern')",
     },
+    ZoFunction {
+        name: ip_udf::CIDR_MATCH_UDF_NAME,
+        text: "cidr_match(field, '10.0.0.0/8')",
+    },
+    ZoFunction {
+        name: geoip_udf::GEOIP_UDF_NAME,
+        text: "geoip(field, 'country')",
+    },
+    ZoFunction {
+        name: grok_udf::GROK_UDF_NAME,
+        text: "grok(field, '%{IP:client} %{WORD:method}')",
+    },
+    ZoFunction {
+        name: parse_udf::PARSE_KV_UDF_NAME,
+        text: "parse_kv(field, ',', '=')",
+    },
 ];
 
+/// The values of a Utf8, LargeUtf8 or Utf8View array
+pub(crate) fn string_values(array: &ArrayRef) -> Result<Vec<Option<&str>>> {
+    Ok(match array.data_type() {
+        DataType::Utf8 => as_string_array(array)?.iter().collect(),
+        DataType::LargeUtf8 => as_large_string_array(array)?.iter().collect(),
+        DataType::Utf8View => as_string_view_array(array)?.iter().collect(),
+        other => {
+            return Err(DataFusionError::Execution(format!(
+                "expected a string argument, got {other}"
+            )));
+        }
+    })
+}
+
 pub fn stringify_json_value(field: &json::Value) -> String {
     match field {
         serde_json::Value::Bool(b) => b.to_string(),
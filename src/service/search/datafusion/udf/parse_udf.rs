@@ -0,0 +1,401 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Query-time parsing of unstructured fields.
+//!
+//! `parse_logfmt` and `parse_kv` return a `Map<Utf8, Utf8>` of the pairs of a
+//! value, so a key can be projected or filtered with `parse_kv(f, ',', '=')['k']`.
+//! `extract_csv` returns a single column of a CSV line.
+
+use std::sync::{Arc, LazyLock as Lazy};
+
+use datafusion::{
+    arrow::{
+        array::{Array, ArrayRef, MapBuilder, StringArray, StringBuilder},
+        datatypes::DataType,
+    },
+    common::cast::as_int64_array,
+    error::{DataFusionError, Result},
+    logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, TypeSignature,
+        Volatility,
+    },
+};
+
+use super::string_values;
+
+/// The name of the parse_logfmt UDF given to DataFusion.
+pub(crate) const PARSE_LOGFMT_UDF_NAME: &str = "parse_logfmt";
+/// The name of the parse_kv UDF given to DataFusion.
+pub(crate) const PARSE_KV_UDF_NAME: &str = "parse_kv";
+/// The name of the extract_csv UDF given to DataFusion.
+pub(crate) const EXTRACT_CSV_UDF_NAME: &str = "extract_csv";
+
+/// Implementation of parse_logfmt(field)
+pub(crate) static PARSE_LOGFMT_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(ParseUdf::new(ParseFunc::Logfmt)));
+
+/// Implementation of parse_kv(field, pair_sep, kv_sep)
+pub(crate) static PARSE_KV_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(ParseUdf::new(ParseFunc::Kv)));
+
+/// Implementation of extract_csv(field, index), the 1-based column of a CSV
+/// line
+pub(crate) static EXTRACT_CSV_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(ParseUdf::new(ParseFunc::Csv)));
+
+/// The type of the maps returned by the parsing UDFs
+pub(crate) static STRING_MAP_TYPE: Lazy<DataType> =
+    Lazy::new(|| new_map_builder().finish().data_type().clone());
+
+pub(crate) fn new_map_builder() -> MapBuilder<StringBuilder, StringBuilder> {
+    MapBuilder::new(None, StringBuilder::new(), StringBuilder::new())
+}
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+enum ParseFunc {
+    Logfmt,
+    Kv,
+    Csv,
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct ParseUdf {
+    func: ParseFunc,
+    signature: Signature,
+}
+
+impl ParseUdf {
+    fn new(func: ParseFunc) -> Self {
+        let strings = [DataType::Utf8, DataType::Utf8View, DataType::LargeUtf8];
+        let signatures = strings
+            .iter()
+            .map(|field| match func {
+                ParseFunc::Logfmt => TypeSignature::Exact(vec![field.clone()]),
+                ParseFunc::Kv => {
+                    TypeSignature::Exact(vec![field.clone(), DataType::Utf8, DataType::Utf8])
+                }
+                ParseFunc::Csv => TypeSignature::Exact(vec![field.clone(), DataType::Int64]),
+            })
+            .collect();
+        Self {
+            func,
+            signature: Signature::one_of(signatures, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ParseUdf {
+    fn name(&self) -> &str {
+        match self.func {
+            ParseFunc::Logfmt => PARSE_LOGFMT_UDF_NAME,
+            ParseFunc::Kv => PARSE_KV_UDF_NAME,
+            ParseFunc::Csv => EXTRACT_CSV_UDF_NAME,
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(match self.func {
+            ParseFunc::Logfmt | ParseFunc::Kv => STRING_MAP_TYPE.clone(),
+            ParseFunc::Csv => DataType::Utf8,
+        })
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let expected = match self.func {
+            ParseFunc::Logfmt => 1,
+            ParseFunc::Kv => 3,
+            ParseFunc::Csv => 2,
+        };
+        if args.args.len() != expected {
+            return Err(DataFusionError::Execution(format!(
+                "{} expects {expected} arguments",
+                self.name()
+            )));
+        }
+        let arrays = ColumnarValue::values_to_arrays(&args.args)?;
+        let fields = string_values(&arrays[0])?;
+        let array: ArrayRef = match self.func {
+            ParseFunc::Logfmt => {
+                let mut builder = new_map_builder();
+                for field in fields {
+                    append_pairs(&mut builder, field.map(|v| split_pairs(v, None, '=')))?;
+                }
+                Arc::new(builder.finish())
+            }
+            ParseFunc::Kv => {
+                let pair_seps = string_values(&arrays[1])?;
+                let kv_seps = string_values(&arrays[2])?;
+                let mut builder = new_map_builder();
+                for ((field, pair_sep), kv_sep) in fields.into_iter().zip(pair_seps).zip(kv_seps) {
+                    let pairs = match (field, pair_sep, kv_sep) {
+                        (Some(field), Some(pair_sep), Some(kv_sep)) => {
+                            Some(split_pairs(field, Some(pair_sep), single_char(kv_sep)?))
+                        }
+                        _ => None,
+                    };
+                    append_pairs(&mut builder, pairs)?;
+                }
+                Arc::new(builder.finish())
+            }
+            ParseFunc::Csv => {
+                let indexes = as_int64_array(&arrays[1])?;
+                Arc::new(
+                    fields
+                        .into_iter()
+                        .zip(indexes.iter())
+                        .map(|(field, index)| csv_column(field?, index?))
+                        .collect::<StringArray>(),
+                )
+            }
+        };
+        Ok(ColumnarValue::Array(array))
+    }
+}
+
+fn single_char(sep: &str) -> Result<char> {
+    let mut chars = sep.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(DataFusionError::Execution(format!(
+            "{PARSE_KV_UDF_NAME}: the key value separator should be a single character, got '{sep}'"
+        ))),
+    }
+}
+
+pub(crate) fn append_pairs(
+    builder: &mut MapBuilder<StringBuilder, StringBuilder>,
+    pairs: Option<Vec<(String, String)>>,
+) -> Result<()> {
+    let is_valid = pairs.is_some();
+    for (key, value) in pairs.into_iter().flatten() {
+        builder.keys().append_value(key);
+        builder.values().append_value(value);
+    }
+    Ok(builder.append(is_valid)?)
+}
+
+/// Split `key=value` pairs, values may be double quoted to contain the
+/// separators. Pairs are separated by whitespace when `pair_sep` is `None`,
+/// as in logfmt, where a key without a value is a flag set to `true`.
+fn split_pairs(s: &str, pair_sep: Option<&str>, kv_sep: char) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    for token in split_unquoted(s, pair_sep) {
+        let token = token.trim();
+        if token.is_empty() {
+            continue;
+        }
+        match token.split_once(kv_sep) {
+            Some((key, value)) => {
+                let key = key.trim();
+                if !key.is_empty() {
+                    pairs.push((key.to_string(), unquote(value.trim())));
+                }
+            }
+            None if pair_sep.is_none() => pairs.push((token.to_string(), "true".to_string())),
+            None => {}
+        }
+    }
+    pairs
+}
+
+/// Split on a separator, or on whitespace, outside of double quotes
+fn split_unquoted<'a>(s: &'a str, sep: Option<&str>) -> Vec<&'a str> {
+    let mut tokens = Vec::new();
+    let (mut start, mut quoted, mut escaped) = (0, false, false);
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            _ => {
+                let sep_len = match sep {
+                    Some(sep) if !sep.is_empty() && s[i..].starts_with(sep) => sep.len(),
+                    None if c.is_whitespace() => c.len_utf8(),
+                    _ => continue,
+                };
+                tokens.push(&s[start..i]);
+                start = i + sep_len;
+                // skip the rest of a multi character separator
+                while chars.clone().next().is_some_and(|(j, _)| j < start) {
+                    chars.next();
+                }
+            }
+        }
+    }
+    tokens.push(&s[start..]);
+    tokens
+}
+
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(value) => {
+            let mut out = String::with_capacity(value.len());
+            let mut chars = value.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => match chars.next() {
+                        Some('n') => out.push('\n'),
+                        Some('t') => out.push('\t'),
+                        Some(c) => out.push(c),
+                        None => out.push('\\'),
+                    },
+                    c => out.push(c),
+                }
+            }
+            out
+        }
+        None => value.to_string(),
+    }
+}
+
+fn csv_column(line: &str, index: i64) -> Option<String> {
+    let index = usize::try_from(index).ok()?.checked_sub(1)?;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(line.as_bytes());
+    let record = reader.records().next()?.ok()?;
+    record.get(index).map(|v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{
+            array::StringViewArray,
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    fn pairs(v: &[(&str, &str)]) -> Vec<(String, String)> {
+        v.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_pairs() {
+        assert_eq!(
+            split_pairs(
+                r#"level=info msg="hello \"world\"" debug  dur=1.5s"#,
+                None,
+                '='
+            ),
+            pairs(&[
+                ("level", "info"),
+                ("msg", r#"hello "world""#),
+                ("debug", "true"),
+                ("dur", "1.5s"),
+            ])
+        );
+        assert_eq!(
+            split_pairs(r#"a: 1; b: "x; y";; c; :d"#, Some(";"), ':'),
+            pairs(&[("a", "1"), ("b", "x; y")])
+        );
+        assert_eq!(
+            split_pairs("a=1&&b=2", Some("&&"), '='),
+            pairs(&[("a", "1"), ("b", "2")])
+        );
+    }
+
+    #[test]
+    fn test_csv_column() {
+        let line = r#"1,"a, b",,"say ""hi""""#;
+        assert_eq!(csv_column(line, 1).as_deref(), Some("1"));
+        assert_eq!(csv_column(line, 2).as_deref(), Some("a, b"));
+        assert_eq!(csv_column(line, 3).as_deref(), Some(""));
+        assert_eq!(csv_column(line, 4).as_deref(), Some(r#"say "hi""#));
+        assert_eq!(csv_column(line, 5), None);
+        assert_eq!(csv_column(line, 0), None);
+    }
+
+    #[tokio::test]
+    async fn test_parse_udfs() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("log", DataType::Utf8View, true),
+            Field::new("kv", DataType::Utf8, true),
+            Field::new("line", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringViewArray::from(vec![
+                    Some("level=error msg=\"disk full\""),
+                    Some("level=info"),
+                    None,
+                ])),
+                Arc::new(StringArray::from(vec![
+                    Some("user:alice, role:admin"),
+                    Some("user:bob"),
+                    Some("garbage"),
+                ])),
+                Arc::new(StringArray::from(vec![
+                    Some("a,b,c"),
+                    Some("x,\"y,z\""),
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+        let ctx = SessionContext::new();
+        for udf in [&PARSE_LOGFMT_UDF, &PARSE_KV_UDF, &EXTRACT_CSV_UDF] {
+            ctx.register_udf(ScalarUDF::clone(udf));
+        }
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+
+        let sql = "select parse_logfmt(log)['msg'] as msg, parse_kv(kv, ',', ':')['role'] as role, \
+                   extract_csv(line, 2) as second from t \
+                   where parse_logfmt(log)['level'] = 'error' or parse_kv(kv, ',', ':')['user'] = 'bob'";
+        let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        assert_batches_eq!(
+            [
+                "+-----------+-------+--------+",
+                "| msg       | role  | second |",
+                "+-----------+-------+--------+",
+                "| disk full | admin | b      |",
+                "|           |       | y,z    |",
+                "+-----------+-------+--------+",
+            ],
+            &batches
+        );
+
+        let ret = ctx
+            .sql("select parse_kv(kv, ',', '::') from t")
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(ret.is_err());
+    }
+}
@@ -0,0 +1,354 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dry run of a search: plans the query and resolves the files it would read,
+//! through the same partition key, bloom filter and inverted index pruning
+//! as the queriers, without scanning any data.
+
+use std::sync::Arc;
+
+use config::{
+    PARQUET_MAX_ROW_GROUP_SIZE, get_config,
+    meta::{
+        cluster::RoleGroup,
+        search::{self, ScanStats, SearchEstimateResponse, SearchEventType},
+        sql::TableReferenceExt,
+        stream::{FileKey, FileSelection, StreamType},
+    },
+};
+use datafusion::physical_optimizer::PhysicalOptimizerRule;
+use hashbrown::HashSet;
+use infra::{
+    errors::Error,
+    file_list::FileId,
+    schema::{
+        get_stream_setting_bloom_filter_fields, get_stream_setting_index_fields,
+        unwrap_stream_settings,
+    },
+};
+use parking_lot::Mutex;
+use proto::cluster_rpc::SearchQuery;
+
+use crate::{
+    common::meta::search::MultiCachedQueryResponse,
+    service::{
+        file_list,
+        search::{
+            bloom_pruner, cache,
+            cluster::flight::{
+                SearchContextBuilder, get_file_id_lists, get_online_querier_nodes,
+                partition_file_list, register_table,
+            },
+            datafusion::optimizer::{create_physical_plan, physical_optimizer::index::IndexRule},
+            grpc::QueryParams,
+            index::IndexCondition,
+            match_file,
+            sql::Sql,
+            tantivy::tantivy_search,
+        },
+    },
+};
+
+/// Estimates the cost of a search request.
+pub async fn estimate(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+) -> Result<SearchEstimateResponse, Error> {
+    let start = std::time::Instant::now();
+    let cfg = get_config();
+
+    // the results cache is only used for the first page
+    let use_cache = in_req.use_cache && in_req.query.from == 0;
+    let mut cache_req = in_req.clone();
+    let (c_resp, _) =
+        cache::prepare_cache_response(trace_id, org_id, stream_type, &mut cache_req, use_cache)
+            .await?;
+    let result_cache_ratio =
+        result_cache_ratio(&c_resp, in_req.query.start_time, in_req.query.end_time);
+
+    let query: SearchQuery = in_req.query.clone().into();
+    let request = config::datafusion::request::Request::new(
+        trace_id.to_string(),
+        org_id.to_string(),
+        stream_type,
+        in_req.timeout,
+        user_id,
+        Some((query.start_time, query.end_time)),
+        in_req.search_type.map(|v| v.to_string()),
+        in_req.query.histogram_interval,
+        in_req.clear_cache,
+    );
+    let sql = Arc::new(Sql::new_from_req(&request, &query).await?);
+
+    let file_list_start = std::time::Instant::now();
+    let file_id_lists = get_file_id_lists(
+        trace_id,
+        org_id,
+        stream_type,
+        &sql.stream_names,
+        sql.time_range,
+    )
+    .await?;
+    let mut scan_stats = ScanStats {
+        file_list_took: file_list_start.elapsed().as_millis() as i64,
+        ..Default::default()
+    };
+    let file_list_files = file_id_lists.values().map(|v| v.len() as i64).sum();
+
+    let role_group = in_req
+        .search_type
+        .map(RoleGroup::from)
+        .or(Some(RoleGroup::Interactive));
+    let nodes =
+        get_online_querier_nodes(trace_id, org_id, &sql.get_first_stream_key(), role_group).await?;
+    let mut used_nodes = nodes
+        .iter()
+        .map(|node| node.is_ingester() && !cfg.common.feature_query_skip_wal)
+        .collect::<Vec<_>>();
+
+    let mut row_groups = 0;
+    for (stream, file_ids) in file_id_lists {
+        let stream_name = stream.stream_name();
+        let stream_type = stream.get_stream_type(stream_type);
+        let Some(schema) = sql.schemas.get(&stream) else {
+            continue;
+        };
+        let stream_settings = unwrap_stream_settings(schema.schema());
+        let partition_keys = stream_settings
+            .as_ref()
+            .map(|s| s.partition_keys.clone())
+            .unwrap_or_default();
+        let equal_items = sql.equal_items.get(&stream).cloned().unwrap_or_default();
+
+        // partition key pruning
+        let ids = file_ids.iter().map(|f| f.id).collect::<Vec<_>>();
+        let file_list = file_list::query_by_ids(
+            trace_id,
+            org_id,
+            stream_type,
+            &stream_name,
+            Some(sql.time_range),
+            &ids,
+        )
+        .await?;
+        let mut files = Vec::with_capacity(file_list.len());
+        for file in file_list {
+            if match_file(
+                org_id,
+                stream_type,
+                &stream_name,
+                Some(sql.time_range),
+                &file,
+                &partition_keys,
+                &equal_items,
+            )
+            .await
+            {
+                files.push(file);
+            }
+        }
+
+        // the index condition is planned per stream, it can't be attributed to
+        // a stream of a query reading several streams
+        let index_fields = get_stream_setting_index_fields(&stream_settings)
+            .into_iter()
+            .filter(|f| schema.contains_field(f))
+            .collect::<Vec<_>>();
+        let index_condition = if sql.stream_names.len() == 1 && cfg.common.inverted_index_enabled {
+            plan_index_condition(&request, &sql, index_fields).await?
+        } else {
+            None
+        };
+
+        if let Some(index_condition) = index_condition {
+            let bloom_indexed_fields = get_stream_setting_bloom_filter_fields(&stream_settings)
+                .into_iter()
+                .filter(|f| schema.contains_field(f))
+                .collect::<Vec<_>>();
+            if cfg.common.bloom_filter_enabled && !bloom_indexed_fields.is_empty() {
+                files = bloom_pruner::prune(
+                    trace_id,
+                    org_id,
+                    stream_type,
+                    &stream_name,
+                    files,
+                    &index_condition,
+                    bloom_indexed_fields,
+                )
+                .await;
+            }
+            let query_params = Arc::new(QueryParams {
+                trace_id: trace_id.to_string(),
+                org_id: org_id.to_string(),
+                stream: stream.clone(),
+                stream_type,
+                stream_name: stream_name.to_string(),
+                time_range: sql.time_range,
+                work_group: None,
+                use_inverted_index: true,
+            });
+            let idx_scan_size = files.iter().map(|f| f.meta.index_size).sum::<i64>();
+            let (idx_took, ..) =
+                tantivy_search(query_params, &mut files, Some(index_condition), None).await?;
+            scan_stats.idx_scan_size += idx_scan_size;
+            scan_stats.idx_took = scan_stats.idx_took.max(idx_took as i64);
+        }
+
+        for file in files.iter() {
+            scan_stats.files += 1;
+            scan_stats.records += file.meta.records;
+            scan_stats.original_size += file.meta.original_size;
+            scan_stats.compressed_size += file.meta.compressed_size;
+            row_groups += file_row_groups(file);
+        }
+
+        let file_ids = files
+            .iter()
+            .map(|f| FileId {
+                id: f.id,
+                records: f.meta.records,
+                original_size: f.meta.original_size,
+                deleted: f.deleted,
+            })
+            .collect::<Vec<_>>();
+        let partitions = partition_file_list(file_ids, &nodes, role_group).await?;
+        for (i, partition) in partitions.iter().enumerate() {
+            if !partition.is_empty() {
+                used_nodes[i] = true;
+            }
+        }
+    }
+
+    let nodes = nodes
+        .iter()
+        .zip(used_nodes)
+        .filter_map(|(node, used)| used.then(|| node.name.clone()))
+        .collect();
+    Ok(SearchEstimateResponse {
+        trace_id: trace_id.to_string(),
+        took: start.elapsed().as_millis() as usize,
+        scan_stats,
+        file_list_files,
+        row_groups,
+        nodes,
+        result_cache_hit: result_cache_ratio == 100,
+        result_cache_ratio,
+    })
+}
+
+/// Plans the query and extracts the filters the inverted index can evaluate,
+/// like the queriers do before searching the index.
+async fn plan_index_condition(
+    req: &config::datafusion::request::Request,
+    sql: &Arc<Sql>,
+    index_fields: Vec<String>,
+) -> Result<Option<IndexCondition>, Error> {
+    let ctx = SearchContextBuilder::new()
+        .target_partitions(get_config().limit.cpu_num)
+        .build(req, sql)
+        .await?;
+    register_table(&ctx, sql).await?;
+    let plan = create_physical_plan(&ctx, &sql.sql).await?;
+    let index_condition = Arc::new(Mutex::new(None));
+    let rule = IndexRule::new(
+        index_fields.into_iter().collect::<HashSet<_>>(),
+        index_condition.clone(),
+    );
+    rule.optimize(plan, ctx.state().config_options())?;
+    let index_condition = index_condition.lock().take();
+    Ok(index_condition.filter(|c| !c.is_condition_all()))
+}
+
+/// Percentage of the time range the results cache holds.
+fn result_cache_ratio(c_resp: &MultiCachedQueryResponse, start_time: i64, end_time: i64) -> usize {
+    if !c_resp.has_cached_data || end_time <= start_time {
+        return 0;
+    }
+    let total = (end_time - start_time) as u128;
+    let missing = c_resp
+        .deltas
+        .iter()
+        .map(|d| (d.delta_end_time - d.delta_start_time).max(0) as u128)
+        .sum::<u128>()
+        .min(total);
+    ((total - missing) * 100 / total) as usize
+}
+
+/// Row groups of a file the search reads, only the ones holding rows selected
+/// by the inverted index or sampling when the file has a selection.
+fn file_row_groups(file: &FileKey) -> i64 {
+    let row_group_size = file
+        .row_group_size
+        .map(|v| v as usize)
+        .filter(|v| *v > 0)
+        .unwrap_or(PARQUET_MAX_ROW_GROUP_SIZE);
+    match &file.selection {
+        Some(FileSelection::Rows(rows)) => rows
+            .set_indices()
+            .map(|i| i / row_group_size)
+            .collect::<HashSet<_>>()
+            .len() as i64,
+        Some(FileSelection::RowGroups(row_groups)) => row_groups.len() as i64,
+        None => (file.meta.records as usize).div_ceil(row_group_size) as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::buffer::BooleanBuffer;
+    use config::meta::stream::FileMeta;
+
+    use super::*;
+    use crate::common::meta::search::QueryDelta;
+
+    #[test]
+    fn test_file_row_groups() {
+        let mut file = FileKey {
+            meta: FileMeta {
+                records: PARQUET_MAX_ROW_GROUP_SIZE as i64 * 2 + 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(file_row_groups(&file), 3);
+
+        let rows = (0..40)
+            .map(|i| i == 1 || i == 3 || i == 35)
+            .collect::<BooleanBuffer>();
+        file.with_selection(FileSelection::Rows(Arc::new(rows)), Some(10));
+        assert_eq!(file_row_groups(&file), 2);
+
+        file.with_selection(FileSelection::RowGroups(Arc::new(vec![0, 4])), None);
+        assert_eq!(file_row_groups(&file), 2);
+    }
+
+    #[test]
+    fn test_result_cache_ratio() {
+        let mut c_resp = MultiCachedQueryResponse::default();
+        assert_eq!(result_cache_ratio(&c_resp, 0, 100), 0);
+
+        c_resp.has_cached_data = true;
+        assert_eq!(result_cache_ratio(&c_resp, 0, 100), 100);
+
+        c_resp.deltas.push(QueryDelta {
+            delta_start_time: 75,
+            delta_end_time: 100,
+        });
+        assert_eq!(result_cache_ratio(&c_resp, 0, 100), 75);
+    }
+}
@@ -0,0 +1,467 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use arrow_schema::Schema;
+use bytes::Bytes;
+use config::{
+    TIMESTAMP_COL_NAME, get_config, get_parquet_compression,
+    meta::{
+        search::{
+            self, ExportCursor, ExportFormat, ExportRequest, SearchEventType,
+            SearchPartitionRequest,
+        },
+        sql::OrderBy,
+        stream::StreamType,
+    },
+    utils::{json, record_batch_ext::convert_json_to_record_batch},
+};
+use infra::errors::{Error, ErrorCodes};
+use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+use tokio::sync::mpsc;
+
+use crate::service::search::{datafusion::plan::projections::get_result_arrow_schema, sql::Sql};
+
+/// An export whose query was validated and split into partitions, so that
+/// invalid requests still fail before any data is sent.
+pub struct ExportPlan {
+    trace_id: String,
+    org_id: String,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    sql: String,
+    format: ExportFormat,
+    /// Columns of the query result, resolved from its logical plan
+    schema: Arc<Schema>,
+    order_by: OrderBy,
+    partitions: Vec<[i64; 2]>,
+    cursor: Option<ExportCursor>,
+}
+
+/// Rows sent by an export and the cursor to resume after them.
+#[derive(Debug, Default)]
+pub struct ExportProgress {
+    pub rows: usize,
+    pub cursor: Option<ExportCursor>,
+}
+
+/// Validates an export request and partitions its time range.
+///
+/// Exports are ordered by `_timestamp`, this is what makes them resumable:
+/// aggregations, other orderings and `LIMIT` are rejected.
+pub async fn plan(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    mut req: ExportRequest,
+) -> Result<ExportPlan, Error> {
+    req.decode()
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?;
+    let cursor = match req.cursor.as_deref().filter(|v| !v.is_empty()) {
+        Some(v) => Some(
+            v.parse::<ExportCursor>()
+                .map_err(|e| Error::ErrorCode(ErrorCodes::InvalidParams(e)))?,
+        ),
+        None => None,
+    };
+
+    let query = search::Query {
+        sql: req.sql.clone(),
+        start_time: req.start_time,
+        end_time: req.end_time,
+        ..Default::default()
+    };
+    let sql = Sql::new(
+        &query.into(),
+        org_id,
+        stream_type,
+        Some(SearchEventType::Download),
+    )
+    .await?;
+    if sql.is_complex {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "export doesn't support aggregations, use the search API instead".to_string(),
+        )));
+    }
+    if sql.limit > 0 {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "export returns every row of the query, remove the LIMIT clause".to_string(),
+        )));
+    }
+    let order_by = match sql.order_by.first() {
+        Some((field, order)) if field == TIMESTAMP_COL_NAME => *order,
+        _ => {
+            return Err(Error::ErrorCode(ErrorCodes::InvalidParams(format!(
+                "export must be ordered by {TIMESTAMP_COL_NAME}"
+            ))));
+        }
+    };
+
+    // every page is encoded with the columns of the plan, whatever rows it holds
+    let schema = get_result_arrow_schema(&sql).await.map_err(|e| {
+        Error::ErrorCode(ErrorCodes::SearchSQLNotValid(format!(
+            "export failed to plan the query: {e}"
+        )))
+    })?;
+
+    // the cursor narrows the time range to what is left to export
+    let (mut start_time, mut end_time) = (req.start_time, req.end_time);
+    if let Some(cursor) = cursor {
+        match order_by {
+            OrderBy::Asc => start_time = start_time.max(cursor.timestamp),
+            OrderBy::Desc => end_time = end_time.min(cursor.timestamp + 1),
+        }
+    }
+
+    let mut partitions = if start_time < end_time {
+        let partition_req = SearchPartitionRequest {
+            sql: req.sql.clone(),
+            start_time,
+            end_time,
+            encoding: search::RequestEncoding::Empty,
+            regions: vec![],
+            clusters: vec![],
+            query_fn: None,
+            streaming_output: false,
+            histogram_interval: 0,
+            sampling_ratio: None,
+            search_type: Some(SearchEventType::Download),
+            timezone: None,
+        };
+        super::search_partition(
+            trace_id,
+            org_id,
+            user_id.as_deref(),
+            stream_type,
+            &partition_req,
+            false,
+            false,
+        )
+        .await?
+        .partitions
+    } else {
+        vec![]
+    };
+    partitions.sort_by_key(|p| p[0]);
+    if order_by == OrderBy::Desc {
+        partitions.reverse();
+    }
+
+    Ok(ExportPlan {
+        trace_id: trace_id.to_string(),
+        org_id: org_id.to_string(),
+        stream_type,
+        user_id,
+        sql: req.sql,
+        format: req.format,
+        schema,
+        order_by,
+        partitions,
+        cursor,
+    })
+}
+
+impl ExportPlan {
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    /// Runs the search partition by partition, a page at a time, and sends the
+    /// encoded rows. The bounded channel provides the backpressure: the next
+    /// page is only searched once the previous one was taken by the receiver.
+    ///
+    /// Pages are read with the cursor of the last sent row: the time range of
+    /// the partition is narrowed to its timestamp and only the rows at that
+    /// timestamp are skipped, so a page does not re-scan the previous ones. An
+    /// offset is only used when the rows have no timestamp.
+    pub async fn run(
+        self,
+        tx: mpsc::Sender<Result<Bytes, Error>>,
+        progress: &mut ExportProgress,
+    ) -> Result<(), Error> {
+        let page_size = get_config().limit.query_export_page_size;
+        let mut encoder = ExportEncoder::new(self.format, self.schema.clone())?;
+        progress.cursor = self.cursor;
+
+        for [start_time, end_time] in self.partitions.iter().copied() {
+            let mut from = 0;
+            loop {
+                let (mut start, mut end, mut offset) = (start_time, end_time, from);
+                if let Some(cursor) = progress
+                    .cursor
+                    .filter(|c| c.timestamp >= start_time && c.timestamp < end_time)
+                {
+                    match self.order_by {
+                        OrderBy::Asc => start = cursor.timestamp,
+                        OrderBy::Desc => end = cursor.timestamp + 1,
+                    }
+                    offset = cursor.skip;
+                }
+                let req = search::Request {
+                    query: search::Query {
+                        sql: self.sql.clone(),
+                        from: offset,
+                        size: page_size,
+                        start_time: start,
+                        end_time: end,
+                        ..Default::default()
+                    },
+                    search_type: Some(SearchEventType::Download),
+                    use_cache: false,
+                    ..Default::default()
+                };
+                let res = super::search(
+                    &self.trace_id,
+                    &self.org_id,
+                    self.stream_type,
+                    self.user_id.clone(),
+                    &req,
+                )
+                .await?;
+                if res.is_partial {
+                    return Err(Error::ErrorCode(ErrorCodes::ServerInternalError(format!(
+                        "export got partial results for [{start}, {end}): {}",
+                        res.function_error.join(", ")
+                    ))));
+                }
+
+                let num_rows = res.hits.len();
+                if num_rows > 0 {
+                    let data = encoder.encode(&res.hits)?;
+                    if tx.send(Ok(data)).await.is_err() {
+                        return Err(Error::ErrorCode(ErrorCodes::SearchCancelQuery(
+                            "export receiver is closed".to_string(),
+                        )));
+                    }
+                    progress.rows += num_rows;
+                    for hit in res.hits.iter() {
+                        match hit.get(TIMESTAMP_COL_NAME).and_then(json::Value::as_i64) {
+                            Some(ts) => progress.cursor.get_or_insert_default().advance(ts),
+                            None => progress.cursor = None,
+                        }
+                    }
+                }
+                if (num_rows as i64) < page_size {
+                    break;
+                }
+                from += num_rows as i64;
+            }
+        }
+
+        let data = encoder.finish()?;
+        if !data.is_empty() && tx.send(Ok(data)).await.is_err() {
+            return Err(Error::ErrorCode(ErrorCodes::SearchCancelQuery(
+                "export receiver is closed".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Encodes pages of hits into one continuous output, with the columns of the
+/// query result.
+enum ExportEncoder {
+    Ndjson,
+    /// The header is written with the first page, or on finish when there are
+    /// no rows.
+    Csv {
+        columns: Vec<String>,
+        header_written: bool,
+    },
+    /// Row groups are flushed per page.
+    Parquet {
+        schema: Arc<Schema>,
+        writer: ArrowWriter<Vec<u8>>,
+    },
+}
+
+impl ExportEncoder {
+    fn new(format: ExportFormat, schema: Arc<Schema>) -> Result<Self, Error> {
+        Ok(match format {
+            ExportFormat::Ndjson => Self::Ndjson,
+            ExportFormat::Csv => Self::Csv {
+                columns: schema.fields().iter().map(|f| f.name().clone()).collect(),
+                header_written: false,
+            },
+            ExportFormat::Parquet => Self::Parquet {
+                writer: new_parquet_writer(schema.clone())?,
+                schema,
+            },
+        })
+    }
+
+    fn encode(&mut self, hits: &[json::Value]) -> Result<Bytes, Error> {
+        match self {
+            Self::Ndjson => {
+                let mut buf = Vec::new();
+                for hit in hits {
+                    buf.extend(json::to_vec(hit)?);
+                    buf.push(b'\n');
+                }
+                Ok(buf.into())
+            }
+            Self::Csv {
+                columns,
+                header_written,
+            } => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                if !*header_written {
+                    writer.write_record(columns.iter()).map_err(csv_error)?;
+                    *header_written = true;
+                }
+                for hit in hits {
+                    writer
+                        .write_record(columns.iter().map(|c| csv_value(hit.get(c))))
+                        .map_err(csv_error)?;
+                }
+                let buf = writer
+                    .into_inner()
+                    .map_err(|e| Error::Message(e.to_string()))?;
+                Ok(buf.into())
+            }
+            Self::Parquet { schema, writer } => {
+                let data = hits.iter().cloned().map(Arc::new).collect::<Vec<_>>();
+                let batch = convert_json_to_record_batch(schema, &data)?;
+                writer.write(&batch)?;
+                writer.flush()?;
+                Ok(std::mem::take(writer.inner_mut()).into())
+            }
+        }
+    }
+
+    fn finish(mut self) -> Result<Bytes, Error> {
+        match self {
+            Self::Csv {
+                header_written: false,
+                ..
+            } => self.encode(&[]),
+            Self::Ndjson | Self::Csv { .. } => Ok(Bytes::new()),
+            // an export without rows is still a valid parquet file
+            Self::Parquet { writer, .. } => Ok(writer.into_inner()?.into()),
+        }
+    }
+}
+
+fn new_parquet_writer(schema: Arc<Schema>) -> Result<ArrowWriter<Vec<u8>>, Error> {
+    let props = WriterProperties::builder()
+        .set_compression(get_parquet_compression(
+            &get_config().common.parquet_compression,
+        ))
+        .build();
+    Ok(ArrowWriter::try_new(Vec::new(), schema, Some(props))?)
+}
+
+fn csv_value(value: Option<&json::Value>) -> String {
+    match value {
+        None | Some(json::Value::Null) => String::new(),
+        Some(json::Value::String(v)) => v.clone(),
+        Some(v) => v.to_string(),
+    }
+}
+
+fn csv_error(e: csv::Error) -> Error {
+    Error::Message(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_schema::{DataType, Field};
+
+    use super::*;
+
+    fn schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new(TIMESTAMP_COL_NAME, DataType::Int64, true),
+            Field::new("code", DataType::Int64, true),
+            Field::new("level", DataType::Utf8, true),
+            Field::new("msg", DataType::Utf8, true),
+        ]))
+    }
+
+    fn hits() -> Vec<json::Value> {
+        vec![
+            json::json!({"_timestamp": 1, "msg": "hello, world", "level": "info"}),
+            json::json!({"_timestamp": 2, "msg": "bye", "code": 7}),
+        ]
+    }
+
+    #[test]
+    fn test_encode_ndjson() {
+        let mut encoder = ExportEncoder::new(ExportFormat::Ndjson, schema()).unwrap();
+        let data = encoder.encode(&hits()).unwrap();
+        let lines = std::str::from_utf8(&data)
+            .unwrap()
+            .lines()
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(json::from_str::<json::Value>(lines[1]).unwrap(), hits()[1]);
+        assert!(encoder.finish().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let mut encoder = ExportEncoder::new(ExportFormat::Csv, schema()).unwrap();
+        let data = encoder.encode(&hits()).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&data).unwrap(),
+            "_timestamp,code,level,msg\n1,,info,\"hello, world\"\n2,7,,bye\n"
+        );
+        // columns missing from the plan are left out
+        let data = encoder
+            .encode(&[json::json!({"_timestamp": 3, "msg": "again", "new": true})])
+            .unwrap();
+        assert_eq!(std::str::from_utf8(&data).unwrap(), "3,,,again\n");
+        assert!(encoder.finish().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_encode_csv_without_rows() {
+        let encoder = ExportEncoder::new(ExportFormat::Csv, schema()).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&encoder.finish().unwrap()).unwrap(),
+            "_timestamp,code,level,msg\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_parquet() {
+        let mut encoder = ExportEncoder::new(ExportFormat::Parquet, schema()).unwrap();
+        let mut buf = encoder.encode(&hits()[..1]).unwrap().to_vec();
+        buf.extend_from_slice(&encoder.encode(&hits()).unwrap());
+        buf.extend_from_slice(&encoder.finish().unwrap());
+
+        let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(
+            Bytes::from(buf),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        // the columns of the first page do not restrict the later ones
+        assert_eq!(reader.schema(), schema());
+        let rows = reader.map(|batch| batch.unwrap().num_rows()).sum::<usize>();
+        assert_eq!(rows, 3);
+    }
+
+    #[test]
+    fn test_encode_parquet_without_rows() {
+        let encoder = ExportEncoder::new(ExportFormat::Parquet, schema()).unwrap();
+        let buf = encoder.finish().unwrap();
+        let builder =
+            parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(buf).unwrap();
+        assert_eq!(builder.schema().fields().len(), 4);
+    }
+}
@@ -71,6 +71,7 @@ use crate::service::{
         index::IndexCondition,
         inspector::{SearchInspectorFieldsBuilder, search_inspector_fields},
         match_file,
+        scan_budget::ScanBudgets,
     },
 };
 
@@ -492,6 +493,19 @@ pub async fn search(
         idx_optimize_rule,
     )?;
 
+    // reserve what this node scans against the scan budgets, the leader only
+    // checked the size of the file list before pruning
+    let scan_budgets =
+        ScanBudgets::resolve(&org_id, req.super_cluster_info.user_id.as_deref()).await;
+    if let Err(e) = scan_budgets
+        .reserve(&trace_id, scan_stats.original_size)
+        .await
+    {
+        super::super::datafusion::storage::file_list::clear(&trace_id);
+        log::warn!("[trace_id {trace_id}] flight->search: {e}");
+        return Err(e);
+    }
+
     log::info!(
         "{}",
         search_inspector_fields(
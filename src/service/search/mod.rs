@@ -71,12 +71,17 @@ pub(crate) mod cache;
 pub(crate) mod cardinality;
 pub(crate) mod cluster;
 pub(crate) mod datafusion;
+pub(crate) mod estimate;
+pub(crate) mod export;
 pub(crate) mod grpc;
 pub(crate) mod grpc_search;
 pub(crate) mod index;
 pub(crate) mod inspector;
 pub(crate) mod partition;
+pub(crate) mod pg_catalog;
+pub(crate) mod scan_budget;
 pub(crate) mod sql;
+pub(crate) mod sql_client;
 pub(crate) mod streaming;
 #[cfg(feature = "enterprise")]
 pub(crate) mod super_cluster;
@@ -93,13 +98,34 @@ pub static SEARCH_SERVER: Lazy<Searcher> = Lazy::new(Searcher::new);
 
 // Please note: `query_fn` which is the vrl needs to be base64::decoded
 // when using this search
-#[tracing::instrument(name = "service:search:enter", skip_all)]
 pub async fn search(
     trace_id: &str,
     org_id: &str,
     stream_type: StreamType,
     user_id: Option<String>,
     in_req: &search::Request,
+) -> Result<search::Response, Error> {
+    // dashboard queries matching a materialized view are answered from it
+    if let Some(plan) =
+        crate::service::materialized_views::rewrite::plan(org_id, stream_type, in_req)
+    {
+        return crate::service::materialized_views::rewrite::search(
+            trace_id, org_id, user_id, plan,
+        )
+        .await;
+    }
+    search_without_views(trace_id, org_id, stream_type, user_id, in_req).await
+}
+
+/// Search the streams of the query as they are, without reading materialized
+/// views.
+#[tracing::instrument(name = "service:search:enter", skip_all)]
+pub(crate) async fn search_without_views(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
 ) -> Result<search::Response, Error> {
     let start = std::time::Instant::now();
     let started_at = now_micros();
@@ -1025,6 +1051,7 @@ pub async fn search_partition_multi(
                 histogram_interval: req.histogram_interval,
                 sampling_ratio: None,
                 search_type: None,
+                timezone: None,
             },
             false,
             false, // disable aggs cache
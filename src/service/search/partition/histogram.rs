@@ -15,7 +15,7 @@
 
 use std::cmp::max;
 
-use config::meta::sql::OrderBy;
+use config::{meta::sql::OrderBy, utils::calendar::CalendarBuckets};
 
 /// Generate partitions aligned with the histogram interval
 pub(super) fn generate_partitions_aligned_with_histogram_interval(
@@ -34,6 +34,41 @@ pub(super) fn generate_partitions_aligned_with_histogram_interval(
     partitions
 }
 
+/// Generate partitions aligned with calendar histogram buckets (week, month,
+/// quarter, year). Buckets are grouped until a partition is at least `step`
+/// long, so no bucket is split across partitions.
+pub(super) fn generate_partitions_aligned_with_calendar_interval(
+    start_time: i64,
+    end_time: i64,
+    step: i64,
+    order_by: OrderBy,
+    buckets: &CalendarBuckets,
+) -> Vec<[i64; 2]> {
+    let mut partitions = Vec::new();
+    let mut start = start_time;
+    while start < end_time {
+        let mut end = buckets.next(start);
+        while end < end_time && end - start < step {
+            end = buckets.next(end);
+        }
+        let end = end.min(end_time);
+        partitions.push([start, end]);
+        start = end;
+    }
+
+    if partitions.is_empty() {
+        partitions.push([start_time, end_time]);
+    }
+
+    // We need to reverse partitions if query is ASC order
+    partitions.sort_by(|a, b| b[0].cmp(&a[0]));
+    if order_by == OrderBy::Asc {
+        partitions.reverse();
+    }
+
+    partitions
+}
+
 /// Generate histogram-aligned partitions
 fn generate_histogram_aligned_partitions(
     start_time: i64,
@@ -646,4 +681,62 @@ mod tests {
         assert_eq!(partitions.first().unwrap()[0], start_time);
         assert_eq!(partitions.last().unwrap()[1], end_time);
     }
+
+    #[test]
+    fn test_partition_generator_with_calendar_alignment() {
+        use config::utils::calendar::{CalendarInterval, CalendarTimezone};
+
+        let micros = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .unwrap()
+                .timestamp_micros()
+        };
+        let buckets = CalendarBuckets::new(
+            CalendarInterval::parse("1 month").unwrap(),
+            CalendarTimezone::parse(Some("America/New_York")).unwrap(),
+        );
+        let start_time = micros("2025-01-15T00:00:00Z");
+        let end_time = micros("2025-04-10T00:00:00Z");
+        let step = 40 * 86400 * 1_000_000; // 40 days in microseconds
+
+        let partitions = generate_partitions_aligned_with_calendar_interval(
+            start_time,
+            end_time,
+            step,
+            OrderBy::Asc,
+            &buckets,
+        );
+        print_partitions("CALENDAR PARTITIONS (ASC):", &partitions);
+        assert_eq!(
+            partitions,
+            vec![
+                [start_time, micros("2025-03-01T05:00:00Z")],
+                [micros("2025-03-01T05:00:00Z"), end_time],
+            ]
+        );
+
+        // one bucket per partition when the step is shorter than a month
+        let partitions = generate_partitions_aligned_with_calendar_interval(
+            start_time,
+            end_time,
+            86400 * 1_000_000,
+            OrderBy::Desc,
+            &buckets,
+        );
+        assert_eq!(
+            partitions,
+            vec![
+                [micros("2025-04-01T04:00:00Z"), end_time],
+                [
+                    micros("2025-03-01T05:00:00Z"),
+                    micros("2025-04-01T04:00:00Z")
+                ],
+                [
+                    micros("2025-02-01T05:00:00Z"),
+                    micros("2025-03-01T05:00:00Z")
+                ],
+                [start_time, micros("2025-02-01T05:00:00Z")],
+            ]
+        );
+    }
 }
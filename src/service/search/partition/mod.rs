@@ -24,8 +24,12 @@ pub(crate) mod stream_files;
 use config::get_config;
 
 use crate::service::search::partition::{
-    histogram::generate_partitions_aligned_with_histogram_interval,
-    regular_partition::generate_partitions_with_mini_partition, settings::PartitionSettings,
+    histogram::{
+        generate_partitions_aligned_with_calendar_interval,
+        generate_partitions_aligned_with_histogram_interval,
+    },
+    regular_partition::generate_partitions_with_mini_partition,
+    settings::PartitionSettings,
     sql_context::PartitionSqlContext,
 };
 
@@ -42,7 +46,11 @@ pub fn generate_partitions(
     let step = partition_settings.step;
     let min_step = partition_settings.min_step;
 
-    if ctx.sql.histogram_interval.is_some() {
+    if let Some(buckets) = ctx.sql.histogram_calendar.as_ref() {
+        generate_partitions_aligned_with_calendar_interval(
+            start_time, end_time, step, order_by, buckets,
+        )
+    } else if ctx.sql.histogram_interval.is_some() {
         generate_partitions_aligned_with_histogram_interval(
             start_time, end_time, step, order_by, min_step,
         )
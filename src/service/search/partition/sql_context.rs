@@ -55,6 +55,7 @@ impl PartitionSqlContext {
             end_time: req.end_time,
             sql: req.sql.to_string(),
             histogram_interval: req.histogram_interval,
+            timezone: req.timezone.clone(),
             ..Default::default()
         };
         let sql = Sql::new(&query, org_id, stream_type, None).await?;
@@ -154,6 +155,7 @@ mod tests {
                 group_by: Default::default(),
                 order_by,
                 histogram_interval,
+                histogram_calendar: None,
                 timezone: None,
                 sorted_by_time: false,
                 sampling_config: None,
@@ -0,0 +1,536 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `pg_catalog` and `information_schema` shims for PostgreSQL clients.
+//!
+//! Clients browse the schema with queries against the system catalogs, they
+//! are answered from in-memory tables built from the stream schemas of the
+//! organization. The catalogs only have the columns clients commonly read,
+//! enough to list schemas, tables and columns.
+
+use std::{
+    ops::ControlFlow,
+    sync::{Arc, LazyLock as Lazy},
+};
+
+use arrow::array::{ArrayRef, BooleanArray, Int32Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use config::{VERSION, meta::stream::StreamType};
+use datafusion::{
+    catalog::MemorySchemaProvider,
+    common::{TableReference, cast::as_int64_array},
+    datasource::MemTable,
+    error::DataFusionError,
+    logical_expr::{ColumnarValue, ScalarUDF, Volatility},
+    prelude::{SessionConfig, SessionContext, create_udf},
+};
+use infra::errors::{Error, ErrorCodes};
+use sqlparser::{
+    ast::{
+        Expr, ObjectName, Statement, Value, ValueWithSpan, VisitMut, VisitorMut, visit_relations,
+    },
+    tokenizer::Span,
+};
+
+use crate::service::{db, search::sql_client};
+
+const PG_CATALOG: &str = "pg_catalog";
+const INFORMATION_SCHEMA: &str = "information_schema";
+const PG_CATALOG_OID: i32 = 11;
+const INFORMATION_SCHEMA_OID: i32 = 12;
+/// Oid of the first stream type schema, the next ones follow it.
+const FIRST_SCHEMA_OID: i32 = 2200;
+/// Oid of the first stream table, the next ones follow it.
+const FIRST_TABLE_OID: i32 = 16384;
+const DATABASE_OID: i32 = 16383;
+const OWNER_OID: i32 = 10;
+const OWNER: &str = "openobserve";
+
+/// A PostgreSQL type, results are sent with the type their column maps to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PgType {
+    pub oid: i32,
+    pub name: &'static str,
+    pub len: i16,
+}
+
+pub const BOOL: PgType = PgType {
+    oid: 16,
+    name: "boolean",
+    len: 1,
+};
+pub const INT8: PgType = PgType {
+    oid: 20,
+    name: "bigint",
+    len: 8,
+};
+pub const INT4: PgType = PgType {
+    oid: 23,
+    name: "integer",
+    len: 4,
+};
+pub const TEXT: PgType = PgType {
+    oid: 25,
+    name: "text",
+    len: -1,
+};
+pub const FLOAT8: PgType = PgType {
+    oid: 701,
+    name: "double precision",
+    len: 8,
+};
+const PG_TYPES: [PgType; 5] = [BOOL, INT8, INT4, TEXT, FLOAT8];
+
+/// Maps an Arrow type to the PostgreSQL type of its values, types without a
+/// match are sent as text.
+pub fn pg_type(data_type: &DataType) -> PgType {
+    match data_type {
+        DataType::Boolean => BOOL,
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            INT4
+        }
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => INT8,
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => FLOAT8,
+        _ => TEXT,
+    }
+}
+
+/// Whether the query reads the system catalogs, or no table at all, and is
+/// answered by [`execute`] instead of the search service.
+pub fn is_catalog_query(statement: &Statement) -> bool {
+    let mut has_tables = false;
+    let mut has_catalog_tables = false;
+    let _ = visit_relations(statement, |name: &ObjectName| {
+        has_tables = true;
+        let parts = name
+            .0
+            .iter()
+            .filter_map(|part| part.as_ident().map(|ident| ident.value.to_lowercase()))
+            .collect::<Vec<_>>();
+        let is_catalog = match parts.len() {
+            1 => parts[0].starts_with("pg_"),
+            n if n > 1 => [PG_CATALOG, INFORMATION_SCHEMA].contains(&parts[n - 2].as_str()),
+            _ => false,
+        };
+        has_catalog_tables |= is_catalog;
+        ControlFlow::<()>::Continue(())
+    });
+    has_catalog_tables || !has_tables
+}
+
+/// Runs a catalog query of a client connected to the organization as the
+/// user, the user only sees the streams they may search.
+pub async fn execute(
+    org_id: &str,
+    user_id: &str,
+    mut statement: Statement,
+) -> Result<(Arc<Schema>, Vec<RecordBatch>), Error> {
+    let _ = statement.visit(&mut PgFunctionRewriter { org_id, user_id });
+    let ctx = new_context(org_id, user_id)
+        .await
+        .map_err(|e| Error::Message(e.to_string()))?;
+    let df = ctx
+        .sql(&statement.to_string())
+        .await
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?;
+    let schema = Arc::new(df.schema().as_arrow().clone());
+    let batches = df
+        .collect()
+        .await
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLExecuteError(e.to_string())))?;
+    Ok((schema, batches))
+}
+
+struct CatalogTable {
+    schema_oid: i32,
+    schema_name: &'static str,
+    oid: i32,
+    name: String,
+    schema: Arc<Schema>,
+}
+
+async fn new_context(org_id: &str, user_id: &str) -> Result<SessionContext, DataFusionError> {
+    let config = SessionConfig::new()
+        .with_create_default_catalog_and_schema(true)
+        .with_default_catalog_and_schema(org_id, PG_CATALOG)
+        .with_information_schema(false);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.register_udf(FORMAT_TYPE_UDF.clone());
+    let Some(catalog) = ctx.catalog(org_id) else {
+        return Err(DataFusionError::Internal(format!(
+            "catalog {org_id} was not created"
+        )));
+    };
+    catalog.register_schema(INFORMATION_SCHEMA, Arc::new(MemorySchemaProvider::new()))?;
+
+    let mut tables = Vec::new();
+    for (i, stream_type) in sql_client::SCHEMAS.into_iter().enumerate() {
+        let streams = db::schema::list(org_id, Some(stream_type), true)
+            .await
+            .map_err(|e| DataFusionError::External(e.into()))?;
+        for stream in streams {
+            if !sql_client::has_stream_permission(org_id, user_id, &stream.stream_name, stream_type)
+                .await
+            {
+                continue;
+            }
+            tables.push(CatalogTable {
+                schema_oid: FIRST_SCHEMA_OID + i as i32,
+                schema_name: stream_type.as_str(),
+                oid: FIRST_TABLE_OID + tables.len() as i32,
+                name: stream.stream_name,
+                schema: Arc::new(stream.schema),
+            });
+        }
+    }
+
+    let register = |schema: &str, name: &str, batch: RecordBatch| {
+        let table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+        ctx.register_table(TableReference::full(org_id, schema, name), Arc::new(table))
+            .map(|_| ())
+    };
+    register(PG_CATALOG, "pg_database", pg_database(org_id)?)?;
+    register(PG_CATALOG, "pg_namespace", pg_namespace()?)?;
+    register(PG_CATALOG, "pg_class", pg_class(&tables)?)?;
+    register(PG_CATALOG, "pg_attribute", pg_attribute(&tables)?)?;
+    register(PG_CATALOG, "pg_type", pg_type_table()?)?;
+    register(INFORMATION_SCHEMA, "schemata", schemata(org_id)?)?;
+    register(
+        INFORMATION_SCHEMA,
+        "tables",
+        information_schema_tables(org_id, &tables)?,
+    )?;
+    register(
+        INFORMATION_SCHEMA,
+        "columns",
+        information_schema_columns(org_id, &tables)?,
+    )?;
+    Ok(ctx)
+}
+
+/// The schemas of the catalog: the system schemas and the stream types.
+fn namespaces() -> Vec<(i32, &'static str)> {
+    [
+        (PG_CATALOG_OID, PG_CATALOG),
+        (INFORMATION_SCHEMA_OID, INFORMATION_SCHEMA),
+    ]
+    .into_iter()
+    .chain(
+        sql_client::SCHEMAS
+            .iter()
+            .enumerate()
+            .map(|(i, stream_type)| (FIRST_SCHEMA_OID + i as i32, stream_type.as_str())),
+    )
+    .collect()
+}
+
+fn batch(columns: Vec<(&str, ArrayRef)>) -> Result<RecordBatch, DataFusionError> {
+    Ok(RecordBatch::try_from_iter(columns)?)
+}
+
+fn strings<S: AsRef<str>>(values: impl IntoIterator<Item = S>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+fn ints(values: impl IntoIterator<Item = i32>) -> ArrayRef {
+    Arc::new(Int32Array::from_iter_values(values))
+}
+
+fn bools(values: impl IntoIterator<Item = bool>) -> ArrayRef {
+    Arc::new(BooleanArray::from_iter(values.into_iter().map(Some)))
+}
+
+fn pg_database(org_id: &str) -> Result<RecordBatch, DataFusionError> {
+    batch(vec![
+        ("oid", ints([DATABASE_OID])),
+        ("datname", strings([org_id])),
+        ("datdba", ints([OWNER_OID])),
+    ])
+}
+
+fn pg_namespace() -> Result<RecordBatch, DataFusionError> {
+    let namespaces = namespaces();
+    batch(vec![
+        ("oid", ints(namespaces.iter().map(|(oid, _)| *oid))),
+        ("nspname", strings(namespaces.iter().map(|(_, name)| *name))),
+        ("nspowner", ints(namespaces.iter().map(|_| OWNER_OID))),
+    ])
+}
+
+fn pg_class(tables: &[CatalogTable]) -> Result<RecordBatch, DataFusionError> {
+    batch(vec![
+        ("oid", ints(tables.iter().map(|t| t.oid))),
+        ("relname", strings(tables.iter().map(|t| &t.name))),
+        ("relnamespace", ints(tables.iter().map(|t| t.schema_oid))),
+        ("relkind", strings(tables.iter().map(|_| "r"))),
+        ("relowner", ints(tables.iter().map(|_| OWNER_OID))),
+        ("relpersistence", strings(tables.iter().map(|_| "p"))),
+        ("relispartition", bools(tables.iter().map(|_| false))),
+        ("relhasindex", bools(tables.iter().map(|_| false))),
+    ])
+}
+
+/// The columns of every table, as (table, position, field) with 1 based
+/// positions.
+fn columns(tables: &[CatalogTable]) -> impl Iterator<Item = (&CatalogTable, i32, &Field)> {
+    tables.iter().flat_map(|table| {
+        table
+            .schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(move |(i, field)| (table, i as i32 + 1, field.as_ref()))
+    })
+}
+
+fn pg_attribute(tables: &[CatalogTable]) -> Result<RecordBatch, DataFusionError> {
+    batch(vec![
+        ("attrelid", ints(columns(tables).map(|(t, ..)| t.oid))),
+        (
+            "attname",
+            strings(columns(tables).map(|(_, _, f)| f.name())),
+        ),
+        (
+            "atttypid",
+            ints(columns(tables).map(|(_, _, f)| pg_type(f.data_type()).oid)),
+        ),
+        ("attnum", ints(columns(tables).map(|(_, i, _)| i))),
+        (
+            "attlen",
+            ints(columns(tables).map(|(_, _, f)| pg_type(f.data_type()).len as i32)),
+        ),
+        ("atttypmod", ints(columns(tables).map(|_| -1))),
+        (
+            "attnotnull",
+            bools(columns(tables).map(|(_, _, f)| !f.is_nullable())),
+        ),
+        ("attisdropped", bools(columns(tables).map(|_| false))),
+    ])
+}
+
+fn pg_type_table() -> Result<RecordBatch, DataFusionError> {
+    batch(vec![
+        ("oid", ints(PG_TYPES.iter().map(|t| t.oid))),
+        ("typname", strings(PG_TYPES.iter().map(|t| t.name))),
+        (
+            "typnamespace",
+            ints(PG_TYPES.iter().map(|_| PG_CATALOG_OID)),
+        ),
+        ("typlen", ints(PG_TYPES.iter().map(|t| t.len as i32))),
+        ("typtype", strings(PG_TYPES.iter().map(|_| "b"))),
+    ])
+}
+
+fn schemata(org_id: &str) -> Result<RecordBatch, DataFusionError> {
+    let namespaces = namespaces();
+    batch(vec![
+        ("catalog_name", strings(namespaces.iter().map(|_| org_id))),
+        (
+            "schema_name",
+            strings(namespaces.iter().map(|(_, name)| *name)),
+        ),
+        ("schema_owner", strings(namespaces.iter().map(|_| OWNER))),
+    ])
+}
+
+fn information_schema_tables(
+    org_id: &str,
+    tables: &[CatalogTable],
+) -> Result<RecordBatch, DataFusionError> {
+    batch(vec![
+        ("table_catalog", strings(tables.iter().map(|_| org_id))),
+        (
+            "table_schema",
+            strings(tables.iter().map(|t| t.schema_name)),
+        ),
+        ("table_name", strings(tables.iter().map(|t| &t.name))),
+        ("table_type", strings(tables.iter().map(|_| "BASE TABLE"))),
+    ])
+}
+
+fn information_schema_columns(
+    org_id: &str,
+    tables: &[CatalogTable],
+) -> Result<RecordBatch, DataFusionError> {
+    batch(vec![
+        ("table_catalog", strings(columns(tables).map(|_| org_id))),
+        (
+            "table_schema",
+            strings(columns(tables).map(|(t, ..)| t.schema_name)),
+        ),
+        (
+            "table_name",
+            strings(columns(tables).map(|(t, ..)| &t.name)),
+        ),
+        (
+            "column_name",
+            strings(columns(tables).map(|(_, _, f)| f.name())),
+        ),
+        ("ordinal_position", ints(columns(tables).map(|(_, i, _)| i))),
+        (
+            "data_type",
+            strings(columns(tables).map(|(_, _, f)| pg_type(f.data_type()).name)),
+        ),
+        (
+            "is_nullable",
+            strings(columns(tables).map(|(_, _, f)| if f.is_nullable() { "YES" } else { "NO" })),
+        ),
+    ])
+}
+
+/// `format_type(type_oid, typemod)` returns the name of a type.
+static FORMAT_TYPE_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        "format_type",
+        vec![DataType::Int64, DataType::Int64],
+        DataType::Utf8,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let args = ColumnarValue::values_to_arrays(args)?;
+            let oids = as_int64_array(&args[0])?;
+            let names = oids
+                .iter()
+                .map(|oid| {
+                    oid.and_then(|oid| PG_TYPES.iter().find(|t| t.oid as i64 == oid))
+                        .map(|t| t.name)
+                })
+                .collect::<StringArray>();
+            Ok(ColumnarValue::Array(Arc::new(names)))
+        }),
+    )
+});
+
+/// Replaces the PostgreSQL functions clients call on the catalogs with their
+/// values for the session.
+struct PgFunctionRewriter<'a> {
+    org_id: &'a str,
+    user_id: &'a str,
+}
+
+impl VisitorMut for PgFunctionRewriter<'_> {
+    type Break = ();
+
+    fn post_visit_expr(&mut self, expr: &mut Expr) -> ControlFlow<Self::Break> {
+        let Expr::Function(func) = expr else {
+            return ControlFlow::Continue(());
+        };
+        let Some(name) = func
+            .name
+            .0
+            .last()
+            .and_then(|part| part.as_ident())
+            .map(|ident| ident.value.to_lowercase())
+        else {
+            return ControlFlow::Continue(());
+        };
+        let value = match name.as_str() {
+            "version" => {
+                Value::SingleQuotedString(format!("PostgreSQL 14.0 (OpenObserve {VERSION})"))
+            }
+            "current_database" | "current_catalog" => {
+                Value::SingleQuotedString(self.org_id.to_string())
+            }
+            "current_schema" => Value::SingleQuotedString(StreamType::Logs.as_str().to_string()),
+            "current_user" | "session_user" | "user" => {
+                Value::SingleQuotedString(self.user_id.to_string())
+            }
+            "pg_get_userbyid" => Value::SingleQuotedString(OWNER.to_string()),
+            "pg_table_is_visible"
+            | "has_table_privilege"
+            | "has_schema_privilege"
+            | "has_database_privilege" => Value::Boolean(true),
+            "obj_description" | "col_description" | "shobj_description" | "pg_get_expr" => {
+                Value::Null
+            }
+            _ => return ControlFlow::Continue(()),
+        };
+        *expr = Expr::Value(ValueWithSpan {
+            value,
+            span: Span::empty(),
+        });
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::{dialect::PostgreSqlDialect, parser::Parser};
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        Parser::parse_sql(&PostgreSqlDialect {}, sql)
+            .unwrap()
+            .remove(0)
+    }
+
+    #[test]
+    fn test_is_catalog_query() {
+        assert!(is_catalog_query(&parse("SELECT version()")));
+        assert!(is_catalog_query(&parse(
+            "SELECT table_name FROM information_schema.tables"
+        )));
+        assert!(is_catalog_query(&parse(
+            "SELECT c.relname FROM pg_catalog.pg_class c JOIN pg_namespace n ON c.relnamespace = n.oid"
+        )));
+        assert!(!is_catalog_query(&parse("SELECT * FROM logs.app")));
+        assert!(!is_catalog_query(&parse("SELECT * FROM default.logs.app")));
+    }
+
+    #[test]
+    fn test_rewrite_pg_functions() {
+        let mut statement = parse(
+            "SELECT current_database(), pg_catalog.pg_table_is_visible(c.oid), obj_description(c.oid) FROM pg_class c",
+        );
+        let _ = statement.visit(&mut PgFunctionRewriter {
+            org_id: "default",
+            user_id: "root@example.com",
+        });
+        assert_eq!(
+            statement.to_string(),
+            "SELECT 'default', true, NULL FROM pg_class c"
+        );
+    }
+
+    #[test]
+    fn test_pg_type() {
+        assert_eq!(pg_type(&DataType::Int64), INT8);
+        assert_eq!(pg_type(&DataType::UInt16), INT4);
+        assert_eq!(pg_type(&DataType::Float32), FLOAT8);
+        assert_eq!(pg_type(&DataType::Utf8View), TEXT);
+    }
+
+    #[tokio::test]
+    async fn test_catalog_tables() {
+        let tables = vec![CatalogTable {
+            schema_oid: FIRST_SCHEMA_OID,
+            schema_name: "logs",
+            oid: FIRST_TABLE_OID,
+            name: "app".to_string(),
+            schema: Arc::new(Schema::new(vec![
+                Field::new("_timestamp", DataType::Int64, false),
+                Field::new("msg", DataType::Utf8, true),
+            ])),
+        }];
+        let batch = information_schema_columns("default", &tables).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let batch = pg_attribute(&tables).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(
+            pg_namespace().unwrap().num_rows(),
+            2 + sql_client::SCHEMAS.len()
+        );
+    }
+}
@@ -0,0 +1,603 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Query scan budgets.
+//!
+//! A budget caps the bytes scanned by a single query and by all queries within
+//! the current hour and the last 24 hours. Budgets are read from the
+//! `scan_budget` setting, which applies to the org as a whole at org (or
+//! system) scope and to a single user at user scope, and from the org-scoped
+//! `scan_budget_roles` map, which applies to every user of a role that has no
+//! budget of their own.
+//!
+//! The leader checks a search against the file list size before dispatching
+//! it. Every node then reserves the bytes it is about to scan, once its file
+//! list has been pruned, against hourly usage counters kept in the meta store
+//! and fails the search when they don't fit. The bytes scanned by the
+//! partitions of a search are summed in the meta store too, by the trace id
+//! they share, so the per query limit holds for the search as a whole
+//! whichever querier runs a partition. Partitions of a long search that start
+//! after the budget is spent fail, so such a search is cut short rather than
+//! overrunning it.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+
+use bytes::Bytes;
+use config::{
+    SIZE_IN_MB,
+    meta::{
+        search::{ScanBudget, ScanStats},
+        self_reporting::usage::{RequestStats, UsageType},
+        stream::StreamType,
+        system_settings::{
+            SettingScope,
+            keys::{SCAN_BUDGET, SCAN_BUDGET_ROLES},
+        },
+    },
+    utils::{size::bytes_to_human_readable, time::now_micros},
+};
+use infra::errors::{DbError, Error, ErrorCodes, Result};
+use serde::de::DeserializeOwned;
+
+use crate::service::{db::system_settings, self_reporting::report_request_usage_stats, users};
+
+const USAGE_KEY_PREFIX: &str = "/scan_usage/";
+/// Usage principal of the org-wide counters, user ids are emails so it can't clash.
+const ORG_PRINCIPAL: &str = "_org";
+/// Usage principal of the per search counters.
+const QUERY_PRINCIPAL: &str = "_query";
+const HOUR_MICROS: i64 = 3_600_000_000;
+const DAY_HOURS: i64 = 24;
+
+/// Bytes scanned by a principal in the current hour and in the last 24 hours.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Usage {
+    hour: u64,
+    day: u64,
+}
+
+/// The budgets that apply to a search: the org-wide one and the one of the
+/// user running it.
+#[derive(Clone, Debug, Default)]
+pub struct ScanBudgets {
+    org_id: String,
+    user_id: Option<String>,
+    org: ScanBudget,
+    user: ScanBudget,
+}
+
+impl ScanBudgets {
+    /// Resolves the budgets of the org and the user. Settings that can't be
+    /// read are logged and treated as unlimited, so that a meta store hiccup
+    /// doesn't fail searches.
+    pub async fn resolve(org_id: &str, user_id: Option<&str>) -> Self {
+        let org = get_setting::<ScanBudget>(None, org_id, None, SCAN_BUDGET)
+            .await
+            .unwrap_or_default();
+        let user = match user_id {
+            Some(user_id) => resolve_user_budget(org_id, user_id).await,
+            None => ScanBudget::default(),
+        };
+        Self {
+            org_id: org_id.to_string(),
+            user_id: user_id.map(|v| v.to_string()),
+            org,
+            user,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.org.is_unlimited() && self.user.is_unlimited()
+    }
+
+    /// Fails with `SearchScanBudgetExceeded` if scanning `estimated_bytes`
+    /// would exceed the per query limit, counting what the previous partitions
+    /// of the search scanned, or what is left of the hourly or daily budget of
+    /// the org or the user.
+    pub async fn check(&self, trace_id: &str, estimated_bytes: i64) -> Result<()> {
+        if self.is_unlimited() {
+            return Ok(());
+        }
+        let estimated = estimated_bytes.max(0) as u64;
+        let query_scanned = load_query_scanned(&self.org_id, trace_id).await?;
+        self.check_usage(estimated, query_scanned, now_micros())
+            .await
+    }
+
+    /// Reserves the bytes a node is about to scan for its part of a search,
+    /// once the file list has been pruned. Fails with
+    /// `SearchScanBudgetExceeded` when they don't fit in the per query limit
+    /// or in what is left of the hourly or daily budget, otherwise adds them
+    /// to the hourly usage of the principals with an hourly or daily budget.
+    /// `scanned_bytes` must not be converted to MB yet.
+    pub async fn reserve(&self, trace_id: &str, scanned_bytes: i64) -> Result<()> {
+        let scanned = scanned_bytes.max(0) as u64;
+        if scanned == 0 || self.is_unlimited() {
+            return Ok(());
+        }
+        let now = now_micros();
+        // the search is counted even when it fails here, so that its later
+        // partitions fail too
+        let query_scanned = add_query_scanned(&self.org_id, trace_id, scanned, now).await?;
+        self.check_usage(scanned, query_scanned, now).await?;
+        for (budget, principal) in self.principals() {
+            if !has_windows(budget) {
+                continue;
+            }
+            if let Err(e) = add_usage(&self.org_id, principal, hour_bucket(now), scanned).await {
+                log::error!(
+                    "[trace_id {trace_id}] scan_budget: failed to record usage of {principal} in org {}: {e}",
+                    self.org_id
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_usage(&self, bytes: u64, query_scanned: u64, now: i64) -> Result<()> {
+        for (budget, principal) in self.principals() {
+            let usage = if has_windows(budget) {
+                load_usage(&self.org_id, principal, now).await?
+            } else {
+                Usage::default()
+            };
+            if let Some(reason) = exceeded(budget, bytes, query_scanned, usage) {
+                let owner = if principal == ORG_PRINCIPAL {
+                    format!("org {}", self.org_id)
+                } else {
+                    format!("user {principal}")
+                };
+                return Err(Error::ErrorCode(ErrorCodes::SearchScanBudgetExceeded(
+                    format!("{owner}: {reason}"),
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reports the bytes scanned by a finished search through self-reporting
+    /// for charge back, they were added to the budget usage by the nodes that
+    /// scanned them. `stats` must not be converted to MB yet.
+    pub async fn record(
+        &self,
+        trace_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+        stats: &ScanStats,
+        started_at: i64,
+    ) {
+        let scanned = stats.original_size.max(0) as u64;
+        let now = now_micros();
+        let req_stats = RequestStats {
+            size: scanned as f64 / SIZE_IN_MB,
+            records: stats.records,
+            response_time: (now - started_at) as f64 / 1_000_000.0,
+            scan_files: Some(stats.files),
+            compressed_size: Some(stats.compressed_size as f64 / SIZE_IN_MB),
+            user_email: self.user_id.clone(),
+            trace_id: Some(trace_id.to_string()),
+            ..Default::default()
+        };
+        report_request_usage_stats(
+            req_stats,
+            &self.org_id,
+            stream_name,
+            stream_type,
+            UsageType::ScanUsage,
+            0,
+            started_at,
+        )
+        .await;
+    }
+
+    fn principals(&self) -> impl Iterator<Item = (&ScanBudget, &str)> {
+        std::iter::once((&self.org, ORG_PRINCIPAL))
+            .chain(self.user_id.as_deref().map(|user_id| (&self.user, user_id)))
+    }
+}
+
+/// A user's own budget wins over the budget of their role.
+async fn resolve_user_budget(org_id: &str, user_id: &str) -> ScanBudget {
+    if let Some(budget) =
+        get_setting::<ScanBudget>(Some(SettingScope::User), org_id, Some(user_id), SCAN_BUDGET)
+            .await
+    {
+        return budget;
+    }
+    let Some(roles) = get_setting::<HashMap<String, ScanBudget>>(
+        Some(SettingScope::Org),
+        org_id,
+        None,
+        SCAN_BUDGET_ROLES,
+    )
+    .await
+    else {
+        return ScanBudget::default();
+    };
+    match users::get_user(Some(org_id), user_id).await {
+        Some(user) => roles
+            .get(&user.role.to_string())
+            .copied()
+            .unwrap_or_default(),
+        None => ScanBudget::default(),
+    }
+}
+
+/// Reads a setting at `scope`, or resolved from org to system level when no
+/// scope is given.
+async fn get_setting<T: DeserializeOwned>(
+    scope: Option<SettingScope>,
+    org_id: &str,
+    user_id: Option<&str>,
+    key: &str,
+) -> Option<T> {
+    let setting = match scope {
+        Some(scope) => system_settings::get(&scope, Some(org_id), user_id, key).await,
+        None => system_settings::get_resolved(Some(org_id), user_id, key).await,
+    };
+    let setting = match setting {
+        Ok(setting) => setting?,
+        Err(e) => {
+            log::error!("scan_budget: failed to get setting {key} of org {org_id}: {e}");
+            return None;
+        }
+    };
+    match serde_json::from_value(setting.setting_value) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            log::warn!("scan_budget: invalid setting {key} of org {org_id}: {e}");
+            None
+        }
+    }
+}
+
+fn has_windows(budget: &ScanBudget) -> bool {
+    budget.max_bytes_per_hour > 0 || budget.max_bytes_per_day > 0
+}
+
+/// Explains which limit of `budget` a scan of `estimated` bytes would exceed,
+/// `query_scanned` is what the previous partitions of the search scanned.
+fn exceeded(
+    budget: &ScanBudget,
+    estimated: u64,
+    query_scanned: u64,
+    usage: Usage,
+) -> Option<String> {
+    let human = |v: u64| bytes_to_human_readable(v as f64);
+    let query_total = query_scanned.saturating_add(estimated);
+    if budget.max_bytes_per_query > 0 && query_total > budget.max_bytes_per_query {
+        return Some(format!(
+            "query would scan {} which exceeds the per query limit of {}",
+            human(query_total),
+            human(budget.max_bytes_per_query)
+        ));
+    }
+    for (limit, used, window) in [
+        (budget.max_bytes_per_hour, usage.hour, "hourly"),
+        (budget.max_bytes_per_day, usage.day, "daily"),
+    ] {
+        if limit > 0 && used.saturating_add(estimated) > limit {
+            return Some(format!(
+                "query would scan {} but only {} of the {window} limit of {} is left",
+                human(estimated),
+                human(limit.saturating_sub(used)),
+                human(limit)
+            ));
+        }
+    }
+    None
+}
+
+/// The partitions of a search run with the trace id of the search suffixed by
+/// `-<partition>`.
+fn query_id(trace_id: &str) -> &str {
+    trace_id.split('-').next().unwrap_or(trace_id)
+}
+
+fn query_key(org_id: &str, trace_id: &str) -> String {
+    format!(
+        "{}/{}",
+        usage_prefix(org_id, QUERY_PRINCIPAL),
+        query_id(trace_id)
+    )
+}
+
+/// A search counter holds the bytes scanned by the search and the time of its
+/// last partition.
+fn parse_query_scanned(value: &Bytes) -> (u64, i64) {
+    let value = std::str::from_utf8(value).unwrap_or_default();
+    let mut parts = value.trim().splitn(2, ' ');
+    let scanned = parts
+        .next()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default();
+    let updated_at = parts
+        .next()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default();
+    (scanned, updated_at)
+}
+
+async fn load_query_scanned(org_id: &str, trace_id: &str) -> Result<u64> {
+    let db = infra::db::get_db().await;
+    match db.get(&query_key(org_id, trace_id)).await {
+        Ok(value) => Ok(parse_query_scanned(&value).0),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Ok(0),
+        Err(e) => Err(e),
+    }
+}
+
+/// Adds the bytes scanned by a partition to its search and returns what the
+/// search had scanned before. The first partition of a search drops the
+/// counters of searches without a partition for an hour.
+async fn add_query_scanned(org_id: &str, trace_id: &str, bytes: u64, now: i64) -> Result<u64> {
+    let key = query_key(org_id, trace_id);
+    let previous = Arc::new(AtomicU64::new(0));
+    let is_new = Arc::new(AtomicBool::new(false));
+    let db = infra::db::get_db().await;
+    {
+        let previous = previous.clone();
+        let is_new = is_new.clone();
+        db.get_for_update(
+            &key.clone(),
+            infra::db::NO_NEED_WATCH,
+            None,
+            Box::new(move |value| {
+                let value_exists = value.is_some();
+                let scanned = value.map(|v| parse_query_scanned(&v).0).unwrap_or_default();
+                previous.store(scanned, Ordering::Relaxed);
+                let new_value = Bytes::from(format!("{} {now}", scanned.saturating_add(bytes)));
+                if value_exists {
+                    Ok(Some((Some(new_value), None)))
+                } else {
+                    is_new.store(true, Ordering::Relaxed);
+                    Ok(Some((None, Some((key, new_value, None)))))
+                }
+            }),
+        )
+        .await?;
+    }
+    if is_new.load(Ordering::Relaxed) {
+        delete_stale_queries(org_id, now).await;
+    }
+    Ok(previous.load(Ordering::Relaxed))
+}
+
+async fn delete_stale_queries(org_id: &str, now: i64) {
+    let prefix = format!("{}/", usage_prefix(org_id, QUERY_PRINCIPAL));
+    let db = infra::db::get_db().await;
+    let queries = match db.list(&prefix).await {
+        Ok(queries) => queries,
+        Err(e) => {
+            log::warn!("scan_budget: failed to list searches of org {org_id}: {e}");
+            return;
+        }
+    };
+    for (key, value) in queries {
+        if !is_stale_query(&value, now) {
+            continue;
+        }
+        if let Err(e) = db
+            .delete_if_exists(&key, false, infra::db::NO_NEED_WATCH)
+            .await
+        {
+            log::warn!("scan_budget: failed to delete stale search {key}: {e}");
+        }
+    }
+}
+
+fn is_stale_query(value: &Bytes, now: i64) -> bool {
+    parse_query_scanned(value).1 + HOUR_MICROS <= now
+}
+
+fn hour_bucket(ts: i64) -> i64 {
+    ts - ts.rem_euclid(HOUR_MICROS)
+}
+
+fn usage_prefix(org_id: &str, principal: &str) -> String {
+    format!("{USAGE_KEY_PREFIX}{org_id}/{principal}")
+}
+
+/// Sums hourly buckets into the usage of the current hour and of the last 24
+/// hours, also returning the buckets that have fallen out of the day.
+fn sum_usage(buckets: &[(i64, u64)], now: i64) -> (Usage, Vec<i64>) {
+    let current = hour_bucket(now);
+    let day_start = current - (DAY_HOURS - 1) * HOUR_MICROS;
+    let mut usage = Usage::default();
+    let mut stale = Vec::new();
+    for &(bucket, bytes) in buckets {
+        if bucket < day_start {
+            stale.push(bucket);
+            continue;
+        }
+        usage.day = usage.day.saturating_add(bytes);
+        if bucket == current {
+            usage.hour = usage.hour.saturating_add(bytes);
+        }
+    }
+    (usage, stale)
+}
+
+async fn load_usage(org_id: &str, principal: &str, now: i64) -> Result<Usage> {
+    let prefix = usage_prefix(org_id, principal);
+    let db = infra::db::get_db().await;
+    let buckets = db
+        .list(&format!("{prefix}/"))
+        .await?
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let bucket = key.rsplit('/').next()?.parse::<i64>().ok()?;
+            Some((bucket, parse_bytes(&value)))
+        })
+        .collect::<Vec<_>>();
+    let (usage, stale) = sum_usage(&buckets, now);
+    for bucket in stale {
+        let key = format!("{prefix}/{bucket}");
+        if let Err(e) = db
+            .delete_if_exists(&key, false, infra::db::NO_NEED_WATCH)
+            .await
+        {
+            log::warn!("scan_budget: failed to delete stale usage {key}: {e}");
+        }
+    }
+    Ok(usage)
+}
+
+async fn add_usage(org_id: &str, principal: &str, bucket: i64, bytes: u64) -> Result<()> {
+    let key = format!("{}/{bucket}", usage_prefix(org_id, principal));
+    let db = infra::db::get_db().await;
+    db.get_for_update(
+        &key.clone(),
+        infra::db::NO_NEED_WATCH,
+        None,
+        Box::new(move |value| {
+            let value_exists = value.is_some();
+            let used = value.map(|v| parse_bytes(&v)).unwrap_or_default();
+            let new_value = Bytes::from(used.saturating_add(bytes).to_string());
+            if value_exists {
+                Ok(Some((Some(new_value), None)))
+            } else {
+                Ok(Some((None, Some((key, new_value, None)))))
+            }
+        }),
+    )
+    .await
+}
+
+fn parse_bytes(value: &Bytes) -> u64 {
+    std::str::from_utf8(value)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn budget(query: u64, hour: u64, day: u64) -> ScanBudget {
+        ScanBudget {
+            max_bytes_per_query: query,
+            max_bytes_per_hour: hour,
+            max_bytes_per_day: day,
+        }
+    }
+
+    #[test]
+    fn test_exceeded_unlimited() {
+        assert!(exceeded(&ScanBudget::default(), u64::MAX, 0, Usage::default()).is_none());
+    }
+
+    #[test]
+    fn test_exceeded_per_query() {
+        let budget = budget(GB, 0, 0);
+        assert!(exceeded(&budget, GB, 0, Usage::default()).is_none());
+        let reason = exceeded(&budget, 2 * GB, 0, Usage::default()).unwrap();
+        assert!(reason.contains("per query limit of 1.00 GB"), "{reason}");
+        // earlier partitions of the search count against the limit
+        let reason = exceeded(&budget, GB / 2, GB, Usage::default()).unwrap();
+        assert!(reason.contains("would scan 1.50 GB"), "{reason}");
+    }
+
+    #[test]
+    fn test_exceeded_windows() {
+        let budget = budget(0, 10 * GB, 20 * GB);
+        let usage = Usage {
+            hour: 8 * GB,
+            day: 12 * GB,
+        };
+        assert!(exceeded(&budget, 2 * GB, 0, usage).is_none());
+        let reason = exceeded(&budget, 3 * GB, 0, usage).unwrap();
+        assert!(reason.contains("2.00 GB of the hourly limit"), "{reason}");
+
+        let usage = Usage {
+            hour: 0,
+            day: 19 * GB,
+        };
+        let reason = exceeded(&budget, 2 * GB, 0, usage).unwrap();
+        assert!(reason.contains("daily limit"), "{reason}");
+    }
+
+    #[test]
+    fn test_sum_usage() {
+        let now = 100 * HOUR_MICROS + 42;
+        let current = hour_bucket(now);
+        assert_eq!(current, 100 * HOUR_MICROS);
+        let buckets = [
+            (current, 5),
+            (current - HOUR_MICROS, 7),
+            (current - 23 * HOUR_MICROS, 11),
+            (current - 24 * HOUR_MICROS, 13),
+        ];
+        let (usage, stale) = sum_usage(&buckets, now);
+        assert_eq!(usage, Usage { hour: 5, day: 23 });
+        assert_eq!(stale, vec![current - 24 * HOUR_MICROS]);
+    }
+
+    #[test]
+    fn test_principals() {
+        let budgets = ScanBudgets {
+            org_id: "default".to_string(),
+            user_id: Some("alice@example.com".to_string()),
+            org: budget(GB, 0, 0),
+            user: ScanBudget::default(),
+        };
+        let principals = budgets.principals().map(|(_, p)| p).collect::<Vec<_>>();
+        assert_eq!(principals, vec![ORG_PRINCIPAL, "alice@example.com"]);
+        assert!(!budgets.is_unlimited());
+
+        let budgets = ScanBudgets {
+            user_id: None,
+            ..Default::default()
+        };
+        assert_eq!(budgets.principals().count(), 1);
+        assert!(budgets.is_unlimited());
+    }
+
+    #[test]
+    fn test_query_scanned() {
+        assert_eq!(query_id("abc-1"), "abc");
+        assert_eq!(query_id("abc"), "abc");
+
+        assert_eq!(
+            query_key("default", "abc-1"),
+            "/scan_usage/default/_query/abc"
+        );
+
+        let now = 100 * HOUR_MICROS;
+        let value = Bytes::from(format!("12 {}", now - HOUR_MICROS / 2));
+        assert_eq!(parse_query_scanned(&value), (12, now - HOUR_MICROS / 2));
+        assert!(!is_stale_query(&value, now));
+        // idle searches are dropped
+        assert!(is_stale_query(
+            &Bytes::from(format!("1 {}", now - HOUR_MICROS)),
+            now
+        ));
+        assert_eq!(parse_query_scanned(&Bytes::from("garbage")), (0, 0));
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(parse_bytes(&Bytes::from("1024")), 1024);
+        assert_eq!(parse_bytes(&Bytes::from("garbage")), 0);
+    }
+}
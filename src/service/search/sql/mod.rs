@@ -26,7 +26,11 @@ use config::{
         sql::{OrderBy, TableReferenceExt, resolve_stream_names_with_type},
         stream::StreamType,
     },
-    utils::{query_select_utils::replace_o2_custom_patterns, sql::is_complex_query_stmt},
+    utils::{
+        calendar::{CalendarBuckets, CalendarTimezone},
+        query_select_utils::replace_o2_custom_patterns,
+        sql::is_complex_query_stmt,
+    },
 };
 use datafusion::{arrow::datatypes::Schema, common::TableReference};
 use hashbrown::{HashMap, HashSet};
@@ -41,7 +45,8 @@ use sqlparser::{ast::VisitMut, dialect::PostgreSqlDialect, parser::Parser};
 use crate::service::search::sql::{
     rewriter::{
         add_o2_id::AddO2IdVisitor, add_timestamp::AddTimestampVisitor,
-        match_all_raw::MatchAllRawVisitor, remove_dashboard_placeholder::RemoveDashboardAllVisitor,
+        cidr_match::CidrMatchVisitor, match_all_raw::MatchAllRawVisitor,
+        remove_dashboard_placeholder::RemoveDashboardAllVisitor,
         track_total_hits::TrackTotalHitsVisitor,
     },
     schema::{generate_schema_fields, generate_select_star_schema, has_original_column},
@@ -82,6 +87,8 @@ pub struct Sql {
     pub group_by: Vec<String>,
     pub order_by: Vec<(String, OrderBy)>,
     pub histogram_interval: Option<i64>,
+    /// Calendar buckets of a week, month, quarter or year histogram
+    pub histogram_calendar: Option<CalendarBuckets>,
     pub timezone: Option<String>,
     pub sorted_by_time: bool, // if only order by _timestamp
     pub sampling_config: Option<proto::cluster_rpc::SamplingConfig>,
@@ -155,6 +162,10 @@ impl Sql {
         // 4. rewrite match_all_raw and match_all_raw_ignore_case to match_all
         let mut match_all_raw_visitor = MatchAllRawVisitor::new();
         let _ = statement.visit(&mut match_all_raw_visitor);
+
+        // add prefix filters to cidr_match so that it can prune files
+        let mut cidr_match_visitor = CidrMatchVisitor::new();
+        let _ = statement.visit(&mut cidr_match_visitor);
         //********************Change the sql end*********************************//
 
         // 5. get column name, alias, group by, order by
@@ -253,7 +264,18 @@ impl Sql {
                 }
             })
             .flatten();
-        let timezone = query.timezone.clone();
+        // an explicit timezone of the histogram wins over the request timezone
+        let timezone = histogram_interval_visitor
+            .timezone
+            .or_else(|| query.timezone.clone());
+        // a request interval overrides the interval of the histogram
+        let histogram_calendar = histogram_interval_visitor
+            .calendar_interval
+            .filter(|_| histogram_interval.is_some() && query.histogram_interval <= 0)
+            .and_then(|interval| {
+                CalendarTimezone::parse(timezone.as_deref())
+                    .map(|timezone| CalendarBuckets::new(interval, timezone))
+            });
 
         //********************Change the sql start*********************************//
         // 11. add _timestamp and _o2_id if need
@@ -289,6 +311,7 @@ impl Sql {
             group_by,
             order_by,
             histogram_interval,
+            histogram_calendar,
             timezone,
             sorted_by_time: need_sort_by_time,
             sampling_config: parse_sampling_config(
@@ -0,0 +1,223 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::{net::IpAddr, ops::ControlFlow};
+
+use sqlparser::{
+    ast::{
+        BinaryOperator, Expr, FunctionArg, FunctionArgExpr, FunctionArguments, Query, SetExpr,
+        Value, ValueWithSpan, VisitorMut,
+    },
+    tokenizer::Span,
+};
+
+use crate::service::search::datafusion::udf::ip_udf::{CIDR_MATCH_UDF_NAME, Cidr};
+
+/// Add filters that can prune files to `cidr_match` on IPv4 networks of whole
+/// octets. A single address is prefixed with an equality, which can use the
+/// bloom filters and the index, and a network such as `10.1.0.0/16` with
+/// `field LIKE '10.1.%'`, which can use the min/max statistics.
+///
+/// Only filters combined with AND and OR are rewritten, a filter under NOT is
+/// left alone since negating the prefix filter would change which rows match.
+/// The result is left alone when the query is rewritten again.
+pub struct CidrMatchVisitor {}
+
+impl CidrMatchVisitor {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl VisitorMut for CidrMatchVisitor {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<Self::Break> {
+        if let SetExpr::Select(select) = query.body.as_mut()
+            && let Some(selection) = select.selection.as_mut()
+        {
+            rewrite_filter(selection);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn rewrite_filter(expr: &mut Expr) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And | BinaryOperator::Or,
+            right,
+        } => {
+            // already rewritten
+            if matches!(
+                left.as_ref(),
+                Expr::Like { .. }
+                    | Expr::BinaryOp {
+                        op: BinaryOperator::Eq,
+                        ..
+                    }
+            ) && cidr_match_args(right).is_some()
+            {
+                return;
+            }
+            rewrite_filter(left);
+            rewrite_filter(right);
+        }
+        Expr::Nested(expr) => rewrite_filter(expr),
+        Expr::Function(_) => {
+            if let Some(new_expr) = prefix_filter(expr) {
+                *expr = new_expr;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The field and network of a `cidr_match(field, 'network')` call
+fn cidr_match_args(expr: &Expr) -> Option<(&Expr, Cidr)> {
+    let Expr::Function(func) = expr else {
+        return None;
+    };
+    if !func
+        .name
+        .to_string()
+        .eq_ignore_ascii_case(CIDR_MATCH_UDF_NAME)
+    {
+        return None;
+    }
+    let FunctionArguments::List(list) = &func.args else {
+        return None;
+    };
+    let [
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(
+            field @ (Expr::Identifier(_) | Expr::CompoundIdentifier(_)),
+        )),
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(ValueWithSpan {
+            value: Value::SingleQuotedString(network),
+            ..
+        }))),
+    ] = list.args.as_slice()
+    else {
+        return None;
+    };
+    Some((field, Cidr::parse(network)?))
+}
+
+fn prefix_filter(expr: &Expr) -> Option<Expr> {
+    let (field, cidr) = cidr_match_args(expr)?;
+    let IpAddr::V4(addr) = cidr.addr else {
+        return None;
+    };
+    let octets = (cidr.prefix_len / 8) as usize;
+    if octets == 0 {
+        return None;
+    }
+    let literal = |s: String| {
+        Box::new(Expr::Value(ValueWithSpan {
+            value: Value::SingleQuotedString(s),
+            span: Span::empty(),
+        }))
+    };
+    // the prefix filter only narrows the rows, cidr_match is kept since it
+    // returns NULL rather than false for values that are not addresses
+    let filter = if octets == 4 {
+        Expr::BinaryOp {
+            left: Box::new(field.clone()),
+            op: BinaryOperator::Eq,
+            right: literal(addr.to_string()),
+        }
+    } else {
+        let prefix = addr.octets()[..octets]
+            .iter()
+            .map(|o| o.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        Expr::Like {
+            negated: false,
+            any: false,
+            expr: Box::new(field.clone()),
+            pattern: literal(format!("{prefix}.%")),
+            escape_char: None,
+        }
+    };
+    Some(Expr::Nested(Box::new(Expr::BinaryOp {
+        left: Box::new(filter),
+        op: BinaryOperator::And,
+        right: Box::new(expr.clone()),
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::{ast::VisitMut, dialect::GenericDialect};
+
+    use super::*;
+
+    fn rewrite(sql: &str) -> String {
+        let mut statement = sqlparser::parser::Parser::parse_sql(&GenericDialect {}, sql)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let _ = statement.visit(&mut CidrMatchVisitor::new());
+        statement.to_string()
+    }
+
+    #[test]
+    fn test_cidr_match_rewrite() {
+        assert_eq!(
+            rewrite("SELECT * FROM t WHERE cidr_match(ip, '10.1.0.0/16') AND a = 1"),
+            "SELECT * FROM t WHERE (ip LIKE '10.1.%' AND cidr_match(ip, '10.1.0.0/16')) AND a = 1"
+        );
+        assert_eq!(
+            rewrite("SELECT * FROM t WHERE cidr_match(ip, '10.1.2.3/32')"),
+            "SELECT * FROM t WHERE (ip = '10.1.2.3' AND cidr_match(ip, '10.1.2.3/32'))"
+        );
+        // a prefix of 12 bits is filtered on its first octet
+        assert_eq!(
+            rewrite("SELECT * FROM t WHERE cidr_match(ip, '172.16.0.0/12') OR b = 2"),
+            "SELECT * FROM t WHERE (ip LIKE '172.%' AND cidr_match(ip, '172.16.0.0/12')) OR b = 2"
+        );
+    }
+
+    #[test]
+    fn test_cidr_match_rewrite_is_idempotent() {
+        let sql = rewrite("SELECT * FROM t WHERE cidr_match(ip, '10.0.0.0/8')");
+        assert_eq!(rewrite(&sql), sql);
+        let sql = rewrite("SELECT * FROM t WHERE cidr_match(ip, '10.1.2.3/32')");
+        assert_eq!(rewrite(&sql), sql);
+    }
+
+    #[test]
+    fn test_cidr_match_not_rewritten() {
+        let cases = [
+            // IPv6
+            "SELECT * FROM t WHERE cidr_match(ip, '2001:db8::/32')",
+            // less than an octet
+            "SELECT * FROM t WHERE cidr_match(ip, '0.0.0.0/0')",
+            // the network is a column
+            "SELECT * FROM t WHERE cidr_match(ip, net)",
+            // not a filter
+            "SELECT cidr_match(ip, '10.0.0.0/8') FROM t",
+            // inside a comparison
+            "SELECT * FROM t WHERE cidr_match(ip, '10.0.0.0/8') = false",
+            // negated
+            "SELECT * FROM t WHERE NOT cidr_match(ip, '10.1.2.3/32')",
+            "SELECT * FROM t WHERE NOT (cidr_match(ip, '10.1.0.0/16') AND a = 1)",
+        ];
+        for sql in cases {
+            assert_eq!(rewrite(sql), sql);
+        }
+    }
+}
@@ -15,6 +15,7 @@
 
 use std::ops::ControlFlow;
 
+use config::utils::calendar::CalendarInterval;
 use infra::errors::{Error, ErrorCodes};
 use sqlparser::ast::{Expr, FunctionArguments, VisitorMut};
 
@@ -22,6 +23,10 @@ use sqlparser::ast::{Expr, FunctionArguments, VisitorMut};
 pub struct HistogramIntervalVisitor {
     pub is_histogram: bool,
     pub interval: Option<i64>,
+    /// The calendar interval, for week, month, quarter and year buckets
+    pub calendar_interval: Option<CalendarInterval>,
+    /// The explicit timezone argument of the histogram
+    pub timezone: Option<String>,
     time_range: (i64, i64),
     pub error: Option<String>,
 }
@@ -31,6 +36,8 @@ impl HistogramIntervalVisitor {
         Self {
             is_histogram: false,
             interval: None,
+            calendar_interval: None,
+            timezone: None,
             time_range,
             error: None,
         }
@@ -63,6 +70,13 @@ impl VisitorMut for HistogramIntervalVisitor {
                 } else {
                     generate_histogram_interval(self.time_range).to_string()
                 };
+                // third is timezone
+                self.timezone = args.next().map(|v| {
+                    v.to_string()
+                        .trim_matches(|v| v == '\'' || v == '"')
+                        .to_string()
+                });
+                self.calendar_interval = CalendarInterval::parse(&interval);
                 let interval_seconds =
                     convert_histogram_interval_to_seconds(&interval).unwrap_or_default();
                 // Validate and adjust the histogram interval
@@ -136,11 +150,15 @@ pub fn convert_histogram_interval_to_seconds(interval: &str) -> Result<i64, Erro
         "minute" | "minutes" | "m" | "mins" | "min" => num * 60,
         "hour" | "hours" | "h" | "hrs" | "hr" => num * 3600,
         "day" | "days" | "d" => num * 86400,
-        _ => {
-            return Err(Error::ErrorCode(ErrorCodes::SearchSQLNotValid(
-                "Unsupported histogram interval unit".to_string(),
-            )));
-        }
+        // calendar units have no fixed length, use their nominal length
+        unit => match CalendarInterval::parse(&format!("1 {unit}")) {
+            Some(calendar_interval) => num * calendar_interval.nominal_seconds(),
+            None => {
+                return Err(Error::ErrorCode(ErrorCodes::SearchSQLNotValid(
+                    "Unsupported histogram interval unit".to_string(),
+                )));
+            }
+        },
     };
 
     Ok(seconds)
@@ -259,13 +277,28 @@ mod tests {
             convert_histogram_interval_to_seconds("1 days").unwrap(),
             86400
         );
-        assert!(convert_histogram_interval_to_seconds("1 week").is_err()); // week is not supported
-        assert!(convert_histogram_interval_to_seconds("1 weeks").is_err()); // weeks is not supported
-        assert!(convert_histogram_interval_to_seconds("1 month").is_err()); // month is not supported
-        assert!(convert_histogram_interval_to_seconds("1 months").is_err()); // months is not supported
-        assert!(convert_histogram_interval_to_seconds("1 year").is_err()); // year is not supported
-        assert!(convert_histogram_interval_to_seconds("1 years").is_err()); // years is not
-        // supported
+        // calendar units use their nominal length
+        assert_eq!(
+            convert_histogram_interval_to_seconds("1 week").unwrap(),
+            7 * 86400
+        );
+        assert_eq!(
+            convert_histogram_interval_to_seconds("2 weeks").unwrap(),
+            14 * 86400
+        );
+        assert_eq!(
+            convert_histogram_interval_to_seconds("1 month").unwrap(),
+            30 * 86400
+        );
+        assert_eq!(
+            convert_histogram_interval_to_seconds("1 quarter").unwrap(),
+            91 * 86400
+        );
+        assert_eq!(
+            convert_histogram_interval_to_seconds("1 years").unwrap(),
+            365 * 86400
+        );
+        assert!(convert_histogram_interval_to_seconds("1 fortnight").is_err());
     }
 
     #[test]
@@ -467,6 +500,24 @@ mod tests {
 
         // Should extract the interval from the histogram function
         assert_eq!(histogram_interval_visitor.interval, Some(10));
+        assert_eq!(histogram_interval_visitor.calendar_interval, None);
+    }
+
+    #[test]
+    fn test_histogram_interval_visitor_calendar_interval() {
+        let sql = "SELECT histogram(_timestamp, '1 month', 'Europe/Berlin') FROM logs";
+        let mut statement = sqlparser::parser::Parser::parse_sql(&GenericDialect {}, sql)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let mut visitor = HistogramIntervalVisitor::new((0, 0));
+        let _ = statement.visit(&mut visitor);
+        assert_eq!(visitor.interval, Some(30 * 86400));
+        assert_eq!(
+            visitor.calendar_interval.map(|v| v.to_string()),
+            Some("1 month".to_string())
+        );
+        assert_eq!(visitor.timezone.as_deref(), Some("Europe/Berlin"));
     }
 
     #[test]
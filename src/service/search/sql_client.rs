@@ -0,0 +1,494 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Queries of SQL clients, received by the Arrow Flight SQL and PostgreSQL wire
+//! protocol servers.
+//!
+//! Both map the organization to the catalog (the database), the stream type to
+//! the schema and the stream to the table, so `default.logs.app` and
+//! `logs.app` both read the logs stream `app`. SQL clients don't send a time
+//! range, it is taken from the `_timestamp` filters of the query instead.
+
+use std::{ops::ControlFlow, sync::Arc};
+
+use arrow::array::RecordBatch;
+use arrow_schema::SchemaRef;
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::{
+        search::{self, SearchEventType},
+        stream::StreamType,
+    },
+    utils::{json, record_batch_ext::convert_json_to_record_batch},
+};
+use infra::errors::{Error, ErrorCodes};
+use serde::{Deserialize, Serialize};
+use sqlparser::{
+    ast::{
+        BinaryOperator, Expr, ObjectName, SetExpr, Statement, Value, ValueWithSpan,
+        visit_relations_mut,
+    },
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+#[cfg(feature = "enterprise")]
+use {
+    crate::handler::http::request::search::utils::{
+        StreamPermissionResourceType, check_stream_permissions,
+    },
+    config::meta::sql::{TableReferenceExt, resolve_stream_names_with_type},
+};
+
+use super::{datafusion::plan::projections::get_result_arrow_schema, sql::Sql};
+
+/// Stream types listed as the schemas of an organization's catalog.
+pub const SCHEMAS: [StreamType; 5] = [
+    StreamType::Logs,
+    StreamType::Metrics,
+    StreamType::Traces,
+    StreamType::EnrichmentTables,
+    StreamType::Metadata,
+];
+
+/// A query of a SQL client rewritten for the search service.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClientQuery {
+    pub sql: String,
+    pub stream_type: StreamType,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// Rows of a query of a SQL client.
+#[derive(Debug)]
+pub struct ClientResult {
+    /// Columns of the query, from its plan, also when no row matched
+    pub schema: SchemaRef,
+    /// `None` when the query matched nothing
+    pub batch: Option<RecordBatch>,
+    /// Why some rows are missing, `None` when the result is complete
+    pub partial_error: Option<String>,
+}
+
+/// Rewrites the table names of a query to stream names and resolves the time
+/// range it searches. Queries without a lower bound search the last
+/// `default_time_range` hours, `now` is the end of the range of queries without
+/// an upper bound.
+pub fn prepare(
+    org_id: &str,
+    sql: &str,
+    now: i64,
+    default_time_range: i64,
+) -> Result<ClientQuery, Error> {
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?;
+    if statements.len() != 1 {
+        return Err(invalid_sql("only one statement is supported"));
+    }
+    let mut statement = statements.remove(0);
+    let Statement::Query(query) = &statement else {
+        return Err(invalid_sql("only SELECT queries are supported"));
+    };
+    let (start_time, end_time) = match query.body.as_ref() {
+        SetExpr::Select(select) => {
+            let mut range = (None, None);
+            if let Some(selection) = select.selection.as_ref() {
+                time_range_from_filter(selection, &mut range);
+            }
+            range
+        }
+        _ => (None, None),
+    };
+
+    let mut stream_type = None;
+    let ret = visit_relations_mut(&mut statement, |name| match strip_catalog(org_id, name) {
+        Ok(Some(schema)) => {
+            stream_type.get_or_insert(schema);
+            ControlFlow::Continue(())
+        }
+        Ok(None) => ControlFlow::Continue(()),
+        Err(e) => ControlFlow::Break(e),
+    });
+    if let ControlFlow::Break(e) = ret {
+        return Err(e);
+    }
+
+    let default_range = default_time_range * 3600 * 1_000_000;
+    let end_time = end_time.unwrap_or(now);
+    let start_time = start_time.unwrap_or(end_time - default_range);
+    if start_time >= end_time {
+        return Err(invalid_sql(
+            "the _timestamp filters of the query select no time range",
+        ));
+    }
+    Ok(ClientQuery {
+        sql: statement.to_string(),
+        stream_type: stream_type.unwrap_or_default(),
+        start_time,
+        end_time,
+    })
+}
+
+/// Resolves the columns of a query from its plan, without running it.
+pub async fn result_schema(org_id: &str, query: &ClientQuery) -> Result<SchemaRef, Error> {
+    let sql = Sql::new(
+        &search_query(query).into(),
+        org_id,
+        query.stream_type,
+        Some(SearchEventType::Other),
+    )
+    .await?;
+    get_result_arrow_schema(&sql)
+        .await
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))
+}
+
+/// Runs a query through the search service and returns its rows as a single
+/// record batch with the columns of the plan.
+pub async fn execute(
+    trace_id: &str,
+    org_id: &str,
+    user_id: &str,
+    query: &ClientQuery,
+) -> Result<ClientResult, Error> {
+    let schema = result_schema(org_id, query).await?;
+    let req = search::Request {
+        query: search_query(query),
+        search_type: Some(SearchEventType::Other),
+        ..Default::default()
+    };
+    let res = super::search(
+        trace_id,
+        org_id,
+        query.stream_type,
+        Some(user_id.to_string()),
+        &req,
+    )
+    .await?;
+    let partial_error = res.is_partial.then(|| {
+        let error = res.function_error.join(", ");
+        log::warn!("[trace_id {trace_id}] sql client query returned partial results: {error}");
+        if error.is_empty() {
+            "the query returned partial results".to_string()
+        } else {
+            error
+        }
+    });
+
+    let batch = if res.hits.is_empty() {
+        None
+    } else {
+        let hits = res.hits.into_iter().map(Arc::new).collect::<Vec<_>>();
+        Some(convert_json_to_record_batch(&schema, &hits)?)
+    };
+    Ok(ClientResult {
+        schema,
+        batch,
+        partial_error,
+    })
+}
+
+/// The search query of a client query. The size is left to the `LIMIT` of the
+/// query, the rows of queries without one are capped by the search service.
+fn search_query(query: &ClientQuery) -> search::Query {
+    search::Query {
+        sql: query.sql.clone(),
+        size: -1,
+        start_time: query.start_time,
+        end_time: query.end_time,
+        ..Default::default()
+    }
+}
+
+/// Checks the user may search every stream the query reads.
+#[cfg(feature = "enterprise")]
+pub async fn check_permissions(
+    org_id: &str,
+    user_id: &str,
+    query: &ClientQuery,
+) -> Result<(), Error> {
+    let tables = resolve_stream_names_with_type(&query.sql)
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?;
+    for table in tables {
+        let stream_name = table.stream_name();
+        let stream_type = table.get_stream_type(query.stream_type);
+        if !has_stream_permission(org_id, user_id, &stream_name, stream_type).await {
+            return Err(Error::ErrorCode(ErrorCodes::SearchStreamNotFound(format!(
+                "Unauthorized access to stream {stream_name}"
+            ))));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "enterprise"))]
+pub async fn check_permissions(
+    _org_id: &str,
+    _user_id: &str,
+    _query: &ClientQuery,
+) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Whether the user may search the stream, used to hide streams from the
+/// catalogs listed to SQL clients.
+#[cfg(feature = "enterprise")]
+pub async fn has_stream_permission(
+    org_id: &str,
+    user_id: &str,
+    stream_name: &str,
+    stream_type: StreamType,
+) -> bool {
+    check_stream_permissions(
+        stream_name,
+        org_id,
+        user_id,
+        &stream_type,
+        StreamPermissionResourceType::Search,
+    )
+    .await
+    .is_none()
+}
+
+#[cfg(not(feature = "enterprise"))]
+pub async fn has_stream_permission(
+    _org_id: &str,
+    _user_id: &str,
+    _stream_name: &str,
+    _stream_type: StreamType,
+) -> bool {
+    true
+}
+
+/// Removes the catalog from a table name and returns the stream type it is
+/// qualified with.
+fn strip_catalog(org_id: &str, name: &mut ObjectName) -> Result<Option<StreamType>, Error> {
+    let parts = name
+        .0
+        .iter()
+        .map(|part| {
+            part.as_ident()
+                .map(|ident| ident.value.clone())
+                .ok_or_else(|| invalid_sql(&format!("unsupported table name: {name}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match parts.len() {
+        1 => Ok(None),
+        2 => parse_stream_type(&parts[0]).map(Some),
+        3 => {
+            if parts[0] != org_id {
+                return Err(invalid_sql(&format!(
+                    "catalog {} is not the organization of the session",
+                    parts[0]
+                )));
+            }
+            let stream_type = parse_stream_type(&parts[1])?;
+            name.0.remove(0);
+            Ok(Some(stream_type))
+        }
+        _ => Err(invalid_sql(&format!("invalid table name: {name}"))),
+    }
+}
+
+fn parse_stream_type(schema: &str) -> Result<StreamType, Error> {
+    let stream_type = StreamType::from(schema);
+    let schema = schema.to_lowercase();
+    if stream_type.as_str() != schema && schema != "enrich" {
+        return Err(invalid_sql(&format!("unknown schema: {schema}")));
+    }
+    Ok(stream_type)
+}
+
+/// Narrows `range` with the `_timestamp` filters which are ANDed at the top
+/// level of the WHERE clause, the end of the range is exclusive.
+fn time_range_from_filter(expr: &Expr, range: &mut (Option<i64>, Option<i64>)) {
+    match expr {
+        Expr::Nested(expr) => time_range_from_filter(expr, range),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            time_range_from_filter(left, range);
+            time_range_from_filter(right, range);
+        }
+        Expr::BinaryOp { left, op, right } => {
+            let (op, value) = if is_timestamp_column(left)
+                && let Some(value) = int_value(right)
+            {
+                (op.clone(), value)
+            } else if is_timestamp_column(right)
+                && let Some(value) = int_value(left)
+            {
+                let op = match op {
+                    BinaryOperator::Gt => BinaryOperator::Lt,
+                    BinaryOperator::GtEq => BinaryOperator::LtEq,
+                    BinaryOperator::Lt => BinaryOperator::Gt,
+                    BinaryOperator::LtEq => BinaryOperator::GtEq,
+                    op => op.clone(),
+                };
+                (op, value)
+            } else {
+                return;
+            };
+            match op {
+                BinaryOperator::Gt => narrow_start(range, value + 1),
+                BinaryOperator::GtEq => narrow_start(range, value),
+                BinaryOperator::Lt => narrow_end(range, value),
+                BinaryOperator::LtEq => narrow_end(range, value + 1),
+                BinaryOperator::Eq => {
+                    narrow_start(range, value);
+                    narrow_end(range, value + 1);
+                }
+                _ => {}
+            }
+        }
+        Expr::Between {
+            expr,
+            negated: false,
+            low,
+            high,
+        } if is_timestamp_column(expr) => {
+            if let Some(low) = int_value(low) {
+                narrow_start(range, low);
+            }
+            if let Some(high) = int_value(high) {
+                narrow_end(range, high + 1);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn narrow_start(range: &mut (Option<i64>, Option<i64>), start: i64) {
+    range.0 = Some(range.0.map_or(start, |v| v.max(start)));
+}
+
+fn narrow_end(range: &mut (Option<i64>, Option<i64>), end: i64) {
+    range.1 = Some(range.1.map_or(end, |v| v.min(end)));
+}
+
+fn is_timestamp_column(expr: &Expr) -> bool {
+    match expr {
+        Expr::Identifier(ident) => ident.value == TIMESTAMP_COL_NAME,
+        Expr::CompoundIdentifier(idents) => idents
+            .last()
+            .is_some_and(|ident| ident.value == TIMESTAMP_COL_NAME),
+        _ => false,
+    }
+}
+
+fn int_value(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Value(ValueWithSpan {
+            value: Value::Number(n, _),
+            ..
+        }) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+fn invalid_sql(msg: &str) -> Error {
+    Error::ErrorCode(ErrorCodes::SearchSQLNotValid(msg.to_string()))
+}
+
+impl ClientQuery {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(json::to_vec(self)?)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        Ok(json::from_slice(data)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000_000_000;
+
+    #[test]
+    fn test_prepare_table_names() {
+        let query = prepare("default", "SELECT * FROM default.traces.spans", NOW, 24).unwrap();
+        assert_eq!(query.sql, "SELECT * FROM traces.spans");
+        assert_eq!(query.stream_type, StreamType::Traces);
+
+        let query = prepare("default", "SELECT * FROM metrics.cpu", NOW, 24).unwrap();
+        assert_eq!(query.stream_type, StreamType::Metrics);
+
+        let query = prepare("default", "SELECT * FROM app", NOW, 24).unwrap();
+        assert_eq!(query.stream_type, StreamType::Logs);
+
+        assert!(prepare("default", "SELECT * FROM other.logs.app", NOW, 24).is_err());
+        assert!(prepare("default", "SELECT * FROM unknown.app", NOW, 24).is_err());
+        assert!(prepare("default", "DELETE FROM app", NOW, 24).is_err());
+        assert!(prepare("default", "SELECT 1; SELECT 2", NOW, 24).is_err());
+    }
+
+    #[test]
+    fn test_prepare_time_range() {
+        let hour = 3600 * 1_000_000;
+        let query = prepare("default", "SELECT * FROM app", NOW, 24).unwrap();
+        assert_eq!((query.start_time, query.end_time), (NOW - 24 * hour, NOW));
+
+        let query = prepare(
+            "default",
+            "SELECT * FROM app WHERE _timestamp >= 100 AND (code = 1 AND 200 > _timestamp)",
+            NOW,
+            24,
+        )
+        .unwrap();
+        assert_eq!((query.start_time, query.end_time), (100, 200));
+
+        let query = prepare(
+            "default",
+            "SELECT * FROM app WHERE app._timestamp BETWEEN 100 AND 200",
+            NOW,
+            24,
+        )
+        .unwrap();
+        assert_eq!((query.start_time, query.end_time), (100, 201));
+
+        // filters under OR don't bound the time range
+        let query = prepare(
+            "default",
+            "SELECT * FROM app WHERE _timestamp < 200 OR code = 1",
+            NOW,
+            24,
+        )
+        .unwrap();
+        assert_eq!(query.end_time, NOW);
+
+        assert!(
+            prepare(
+                "default",
+                "SELECT * FROM app WHERE _timestamp > 200 AND _timestamp < 100",
+                NOW,
+                24
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_query_bytes() {
+        let query = prepare("default", "SELECT * FROM app", NOW, 24).unwrap();
+        assert_eq!(
+            ClientQuery::from_bytes(&query.to_bytes().unwrap()).unwrap(),
+            query
+        );
+    }
+}
@@ -464,6 +464,7 @@ pub async fn get_partitions(
         histogram_interval: req.query.histogram_interval,
         sampling_ratio: req.query.sampling_ratio,
         search_type: req.search_type,
+        timezone: req.query.timezone.clone(),
     };
 
     let res = SearchService::search_partition(
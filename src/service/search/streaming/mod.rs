@@ -204,7 +204,10 @@ pub async fn process_search_stream_request(
                             ControlFlow::Break(()) => break, // histogram found, stop early
                         }
                     }
-                    if let Some(interval) = visitor.interval {
+                    // calendar intervals have no fixed length, they are kept in the sql
+                    if let Some(interval) = visitor.interval
+                        && visitor.calendar_interval.is_none()
+                    {
                         req.query.histogram_interval = validate_and_adjust_histogram_interval(
                             interval,
                             (req.query.start_time, req.query.end_time),
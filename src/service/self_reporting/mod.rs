@@ -25,14 +25,16 @@ use config::{
     cluster::LOCAL_NODE,
     get_config,
     meta::{
+        destinations::{WEBHOOK_DEAD_LETTER_STREAM, WebhookDeadLetter},
         self_reporting::{
             EnqueueError, ReportingData,
             error::ErrorData,
             usage::{RequestStats, TriggerData, UsageData, UsageEvent, UsageType},
         },
-        stream::StreamType,
+        stream::{StreamParams, StreamType},
     },
     metrics,
+    utils::json,
 };
 #[cfg(feature = "enterprise")]
 pub use o2_enterprise::enterprise::common::auditor;
@@ -321,6 +323,31 @@ pub fn publish_triggers_usage(trigger: TriggerData) {
     }
 }
 
+/// Records a webhook delivery that failed permanently in the dead-letter
+/// stream of its organization. Unlike usage data this is always ingested, as
+/// the payload would be lost otherwise.
+pub async fn publish_webhook_dead_letter(dead_letter: WebhookDeadLetter) {
+    let stream = StreamParams::new(
+        &dead_letter.org,
+        WEBHOOK_DEAD_LETTER_STREAM,
+        StreamType::Logs,
+    );
+    let value = match json::to_value(&dead_letter) {
+        Ok(value) => value,
+        Err(e) => {
+            log::error!("[SELF-REPORTING] Failed to serialize webhook dead letter: {e}");
+            return;
+        }
+    };
+    if let Err(e) = ingestion::ingest_reporting_data(vec![value], stream).await {
+        log::error!(
+            "[SELF-REPORTING] Error ingesting webhook dead letter for {}/{}: {e}",
+            dead_letter.org,
+            dead_letter.destination
+        );
+    }
+}
+
 pub async fn publish_error(error_data: ErrorData) {
     let cfg = get_config();
     #[cfg(not(feature = "enterprise"))]
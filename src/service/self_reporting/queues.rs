@@ -466,6 +466,8 @@ mod tests {
             dedup_count: None,
             grouped: None,
             group_size: None,
+            fingerprint: None,
+            silence_id: None,
         }
     }
 
@@ -165,15 +165,16 @@ mod tests {
         assert!(field_names.contains(&"status".to_string()));
         assert!(field_names.contains(&"next_run_at".to_string()));
 
-        // Verify count matches struct fields (26 total: 21 original + 5 dedup/grouping fields)
-        assert_eq!(field_names.len(), 26);
+        // Verify count matches struct fields (28 total: 21 original + 5 dedup/grouping fields +
+        // the fingerprint of resolved alert instances + the id of the suppressing silence)
+        assert_eq!(field_names.len(), 28);
 
         // Verify no duplicate fields
         let unique_count = field_names
             .iter()
             .collect::<std::collections::HashSet<_>>()
             .len();
-        assert_eq!(unique_count, 26);
+        assert_eq!(unique_count, 28);
     }
 
     #[tokio::test]
@@ -26,8 +26,8 @@ use config::{
     meta::{
         promql,
         stream::{
-            DistinctField, PartitionTimeLevel, StreamField, StreamParams, StreamSettings,
-            StreamStats, StreamType, TimeRange, UpdateStreamSettings,
+            DistinctField, PartitionTimeLevel, PredicateDeleteJob, StreamField, StreamParams,
+            StreamSettings, StreamStats, StreamType, TimeRange, UpdateStreamSettings,
         },
     },
     utils::{flatten::format_label_name, json, time::now_micros, util::get_distinct_stream_name},
@@ -1209,6 +1209,78 @@ pub async fn delete_stream_data_by_time_range(
     crate::service::db::compact::compactor_manual_jobs::add_job(job).await
 }
 
+pub async fn delete_stream_data_by_predicate(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    condition: &str,
+    time_range: TimeRange,
+) -> Result<String, infra::errors::Error> {
+    if time_range.start >= time_range.end {
+        return Err(infra::errors::Error::Message(
+            "Start time must be less than end time".to_string(),
+        ));
+    }
+
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    if schema == Schema::empty() {
+        return Err(infra::errors::Error::Message(format!(
+            "Stream {stream_name} not found"
+        )));
+    }
+    let condition = crate::service::compact::predicate_delete::normalize_condition(condition)
+        .map_err(|e| infra::errors::Error::Message(e.to_string()))?;
+    crate::service::compact::predicate_delete::validate_condition(
+        org_id,
+        std::sync::Arc::new(schema),
+        &condition,
+    )
+    .await
+    .map_err(|e| infra::errors::Error::Message(format!("invalid condition: {e}")))?;
+    // the rows of dumped hours can not be rewritten, don't accept a job that
+    // could only erase part of the time range
+    if let Some((start, end)) = crate::service::compact::predicate_delete::dumped_time_range(
+        org_id,
+        stream_type,
+        stream_name,
+        (time_range.start, time_range.end),
+    )
+    .await
+    .map_err(|e| infra::errors::Error::Message(e.to_string()))?
+    {
+        return Err(infra::errors::Error::Message(format!(
+            "The file list of the time range {start} to {end} is dumped, rows can only be deleted from data that is not dumped"
+        )));
+    }
+
+    let job = PredicateDeleteJob {
+        id: config::ider::uuid(),
+        org_id: org_id.to_string(),
+        stream_type,
+        stream_name: stream_name.to_string(),
+        condition,
+        start: time_range.start,
+        end: time_range.end,
+        created_at: Utc::now().timestamp_micros(),
+        ended_at: 0,
+        progress: Default::default(),
+        error: None,
+    };
+    // Create a job in the compact manual jobs table so the status shows up
+    // like the time range delete jobs, then hand the job to the compactor
+    let manual_job = infra::table::compactor_manual_jobs::CompactorManualJob {
+        id: job.id.clone(),
+        key: job.manual_job_key(),
+        status: infra::table::compactor_manual_jobs::Status::Pending,
+        created_at: job.created_at,
+        ended_at: 0,
+    };
+    let job_id =
+        crate::service::db::compact::compactor_manual_jobs::add_local_job(manual_job).await?;
+    crate::service::db::compact::predicate_delete::set(&job).await?;
+    Ok(job_id)
+}
+
 async fn transform_stats(
     stats: &mut StreamStats,
     org_id: &str,
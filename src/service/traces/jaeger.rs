@@ -0,0 +1,731 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Jaeger Thrift-over-HTTP span ingestion.
+//!
+//! The collector endpoint receives a `jaeger.thrift` `Batch` in the Thrift
+//! binary protocol, as sent by the `HTTPSender` of the Jaeger clients. The
+//! batch is converted into an OTLP [`ExportTraceServiceRequest`] and ingested
+//! by [`super::handle_otlp_request`], so the spans are stored like native OTLP
+//! spans.
+//!
+//! refer: https://github.com/jaegertracing/jaeger-idl/blob/main/thrift/jaeger.thrift
+
+use std::io::Error;
+
+use axum::{body::Bytes, response::Response as HttpResponse};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use config::meta::otlp::OtlpRequestType;
+use opentelemetry_proto::tonic::{
+    collector::trace::v1::ExportTraceServiceRequest,
+    common::v1::{AnyValue, KeyValue, any_value::Value},
+    resource::v1::Resource,
+    trace::v1::{
+        ResourceSpans, ScopeSpans, Span, Status,
+        span::{Event, Link, SpanKind},
+        status::StatusCode,
+    },
+};
+
+use super::SERVICE_NAME;
+use crate::common::meta::{http::HttpResponse as MetaHttpResponse, ingestion::IngestUser};
+
+const SPAN_KIND_TAG: &str = "span.kind";
+const ERROR_TAG: &str = "error";
+const OTEL_STATUS_CODE_TAG: &str = "otel.status_code";
+const OTEL_STATUS_DESCRIPTION_TAG: &str = "otel.status_description";
+const EVENT_FIELD: &str = "event";
+// refer: https://github.com/jaegertracing/jaeger-idl/blob/main/thrift/jaeger.thrift#L61
+const REF_TYPE_CHILD_OF: i32 = 0;
+
+#[derive(Debug, Default, PartialEq)]
+struct Batch {
+    process: Process,
+    spans: Vec<JaegerSpan>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Process {
+    service_name: String,
+    tags: Vec<Tag>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct JaegerSpan {
+    trace_id_low: i64,
+    trace_id_high: i64,
+    span_id: i64,
+    parent_span_id: i64,
+    operation_name: String,
+    references: Vec<SpanRef>,
+    start_time: i64,
+    duration: i64,
+    tags: Vec<Tag>,
+    logs: Vec<Log>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct SpanRef {
+    ref_type: i32,
+    trace_id_low: i64,
+    trace_id_high: i64,
+    span_id: i64,
+}
+
+#[derive(Debug, PartialEq)]
+struct Tag {
+    key: String,
+    value: TagValue,
+}
+
+#[derive(Debug, PartialEq)]
+enum TagValue {
+    String(String),
+    Double(f64),
+    Bool(bool),
+    Long(i64),
+    Binary(Vec<u8>),
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Log {
+    timestamp: i64,
+    fields: Vec<Tag>,
+}
+
+pub async fn jaeger_thrift(
+    org_id: &str,
+    body: Bytes,
+    in_stream_name: Option<&str>,
+    user: IngestUser,
+) -> Result<HttpResponse, Error> {
+    let batch = match ThriftReader::new(&body).read_batch() {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!("[TRACES:JAEGER] Invalid thrift: org_id: {org_id}, error: {e}");
+            return Ok(MetaHttpResponse::bad_request(format!(
+                "Invalid thrift: {e}"
+            )));
+        }
+    };
+    super::handle_otlp_request(
+        org_id,
+        to_otlp_request(batch),
+        OtlpRequestType::HttpJson,
+        in_stream_name,
+        user,
+    )
+    .await
+    .map(super::collector_response)
+}
+
+fn to_otlp_request(batch: Batch) -> ExportTraceServiceRequest {
+    let mut attributes = vec![KeyValue {
+        key: SERVICE_NAME.to_string(),
+        value: Some(AnyValue {
+            value: Some(Value::StringValue(batch.process.service_name)),
+        }),
+    }];
+    attributes.extend(batch.process.tags.into_iter().map(to_attribute));
+
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(Resource {
+                attributes,
+                ..Default::default()
+            }),
+            scope_spans: vec![ScopeSpans {
+                spans: batch.spans.into_iter().map(to_otlp_span).collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+}
+
+fn to_otlp_span(span: JaegerSpan) -> Span {
+    let trace_id = trace_id_bytes(span.trace_id_high, span.trace_id_low);
+
+    // the parent is either given by the parent span id or by the first
+    // CHILD_OF reference within the trace, the other references become links
+    let mut parent_span_id = span.parent_span_id;
+    let mut links = Vec::new();
+    for reference in span.references {
+        let same_trace = reference.trace_id_high == span.trace_id_high
+            && reference.trace_id_low == span.trace_id_low;
+        if parent_span_id == 0 && same_trace && reference.ref_type == REF_TYPE_CHILD_OF {
+            parent_span_id = reference.span_id;
+        } else if !(same_trace && reference.span_id == parent_span_id) {
+            links.push(Link {
+                trace_id: trace_id_bytes(reference.trace_id_high, reference.trace_id_low),
+                span_id: reference.span_id.to_be_bytes().to_vec(),
+                ..Default::default()
+            });
+        }
+    }
+
+    let mut kind = SpanKind::Internal;
+    let mut status_code = StatusCode::Unset;
+    let mut status_message = String::new();
+    let mut attributes = Vec::with_capacity(span.tags.len());
+    for tag in span.tags {
+        match (tag.key.as_str(), &tag.value) {
+            (SPAN_KIND_TAG, TagValue::String(v)) => {
+                kind = match v.as_str() {
+                    "client" => SpanKind::Client,
+                    "server" => SpanKind::Server,
+                    "producer" => SpanKind::Producer,
+                    "consumer" => SpanKind::Consumer,
+                    _ => SpanKind::Internal,
+                };
+            }
+            (OTEL_STATUS_CODE_TAG, TagValue::String(v)) => {
+                status_code = match v.as_str() {
+                    "OK" => StatusCode::Ok,
+                    "ERROR" => StatusCode::Error,
+                    _ => status_code,
+                };
+            }
+            (OTEL_STATUS_DESCRIPTION_TAG, TagValue::String(v)) => status_message = v.clone(),
+            (ERROR_TAG, TagValue::Bool(true)) => {
+                if status_code == StatusCode::Unset {
+                    status_code = StatusCode::Error;
+                }
+            }
+            (ERROR_TAG, TagValue::String(v)) if v == "true" => {
+                if status_code == StatusCode::Unset {
+                    status_code = StatusCode::Error;
+                }
+            }
+            _ => attributes.push(to_attribute(tag)),
+        }
+    }
+    let status = (status_code != StatusCode::Unset).then(|| Status {
+        code: status_code.into(),
+        message: status_message,
+    });
+
+    let events = span
+        .logs
+        .into_iter()
+        .map(|log| {
+            let mut name = String::new();
+            let mut attributes = Vec::with_capacity(log.fields.len());
+            for field in log.fields {
+                match field {
+                    Tag {
+                        key,
+                        value: TagValue::String(v),
+                    } if key == EVENT_FIELD && name.is_empty() => name = v,
+                    field => attributes.push(to_attribute(field)),
+                }
+            }
+            Event {
+                time_unix_nano: micros_to_nanos(log.timestamp),
+                name,
+                attributes,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    Span {
+        trace_id,
+        span_id: span.span_id.to_be_bytes().to_vec(),
+        parent_span_id: if parent_span_id == 0 {
+            vec![]
+        } else {
+            parent_span_id.to_be_bytes().to_vec()
+        },
+        name: span.operation_name,
+        kind: kind.into(),
+        start_time_unix_nano: micros_to_nanos(span.start_time),
+        end_time_unix_nano: micros_to_nanos(span.start_time.saturating_add(span.duration)),
+        attributes,
+        events,
+        links,
+        status,
+        ..Default::default()
+    }
+}
+
+fn trace_id_bytes(high: i64, low: i64) -> Vec<u8> {
+    let mut id = Vec::with_capacity(16);
+    id.extend_from_slice(&high.to_be_bytes());
+    id.extend_from_slice(&low.to_be_bytes());
+    id
+}
+
+fn micros_to_nanos(micros: i64) -> u64 {
+    (micros.max(0) as u64).saturating_mul(1000)
+}
+
+fn to_attribute(tag: Tag) -> KeyValue {
+    let value = match tag.value {
+        TagValue::String(v) => Value::StringValue(v),
+        TagValue::Double(v) => Value::DoubleValue(v),
+        TagValue::Bool(v) => Value::BoolValue(v),
+        TagValue::Long(v) => Value::IntValue(v),
+        TagValue::Binary(v) => Value::StringValue(BASE64_STANDARD.encode(v)),
+    };
+    KeyValue {
+        key: tag.key,
+        value: Some(AnyValue { value: Some(value) }),
+    }
+}
+
+// Thrift binary protocol field types
+// refer: https://github.com/apache/thrift/blob/master/doc/specs/thrift-binary-protocol.md
+const T_STOP: u8 = 0;
+const T_BOOL: u8 = 2;
+const T_BYTE: u8 = 3;
+const T_DOUBLE: u8 = 4;
+const T_I16: u8 = 6;
+const T_I32: u8 = 8;
+const T_I64: u8 = 10;
+const T_STRING: u8 = 11;
+const T_STRUCT: u8 = 12;
+const T_MAP: u8 = 13;
+const T_SET: u8 = 14;
+const T_LIST: u8 = 15;
+const MAX_SKIP_DEPTH: usize = 64;
+
+/// A reader of the Thrift binary protocol, decoding only the structs of
+/// `jaeger.thrift` and skipping unknown fields.
+struct ThriftReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> ThriftReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.buf.len() < len {
+            return Err("unexpected end of data".to_string());
+        }
+        let (bytes, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(bytes)
+    }
+
+    fn read_byte(&mut self) -> Result<u8, String> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_i16(&mut self) -> Result<i16, String> {
+        Ok(i16::from_be_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_i64(&mut self) -> Result<i64, String> {
+        Ok(i64::from_be_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+
+    fn read_double(&mut self) -> Result<f64, String> {
+        Ok(f64::from_bits(self.read_i64()? as u64))
+    }
+
+    fn read_bool(&mut self) -> Result<bool, String> {
+        Ok(self.read_byte()? != 0)
+    }
+
+    fn read_size(&mut self) -> Result<usize, String> {
+        let size = self.read_i32()?;
+        usize::try_from(size).map_err(|_| format!("negative size: {size}"))
+    }
+
+    fn read_binary(&mut self) -> Result<&'a [u8], String> {
+        let len = self.read_size()?;
+        self.read_bytes(len)
+    }
+
+    fn read_string(&mut self) -> Result<String, String> {
+        Ok(String::from_utf8_lossy(self.read_binary()?).into_owned())
+    }
+
+    /// Reads a field header, returns `None` at the end of a struct.
+    fn read_field_header(&mut self) -> Result<Option<(u8, i16)>, String> {
+        let field_type = self.read_byte()?;
+        if field_type == T_STOP {
+            return Ok(None);
+        }
+        Ok(Some((field_type, self.read_i16()?)))
+    }
+
+    /// Reads a list header and checks the element type.
+    fn read_list_header(&mut self, elem_type: u8) -> Result<usize, String> {
+        let actual = self.read_byte()?;
+        if actual != elem_type {
+            return Err(format!(
+                "unexpected list element type {actual}, expected {elem_type}"
+            ));
+        }
+        let size = self.read_size()?;
+        // every element takes at least one byte
+        if size > self.buf.len() {
+            return Err(format!("list size {size} exceeds the data"));
+        }
+        Ok(size)
+    }
+
+    fn read_list<T>(
+        &mut self,
+        mut read: impl FnMut(&mut Self) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let size = self.read_list_header(T_STRUCT)?;
+        (0..size).map(|_| read(self)).collect()
+    }
+
+    fn skip(&mut self, field_type: u8, depth: usize) -> Result<(), String> {
+        if depth > MAX_SKIP_DEPTH {
+            return Err("maximum nesting depth exceeded".to_string());
+        }
+        match field_type {
+            T_BOOL | T_BYTE => self.read_bytes(1).map(|_| ()),
+            T_I16 => self.read_bytes(2).map(|_| ()),
+            T_I32 => self.read_bytes(4).map(|_| ()),
+            T_DOUBLE | T_I64 => self.read_bytes(8).map(|_| ()),
+            T_STRING => self.read_binary().map(|_| ()),
+            T_STRUCT => {
+                while let Some((field_type, _)) = self.read_field_header()? {
+                    self.skip(field_type, depth + 1)?;
+                }
+                Ok(())
+            }
+            T_MAP => {
+                let key_type = self.read_byte()?;
+                let value_type = self.read_byte()?;
+                for _ in 0..self.read_size()? {
+                    self.skip(key_type, depth + 1)?;
+                    self.skip(value_type, depth + 1)?;
+                }
+                Ok(())
+            }
+            T_SET | T_LIST => {
+                let elem_type = self.read_byte()?;
+                for _ in 0..self.read_size()? {
+                    self.skip(elem_type, depth + 1)?;
+                }
+                Ok(())
+            }
+            _ => Err(format!("unknown field type {field_type}")),
+        }
+    }
+
+    fn read_batch(&mut self) -> Result<Batch, String> {
+        let mut batch = Batch::default();
+        while let Some((field_type, id)) = self.read_field_header()? {
+            match (id, field_type) {
+                (1, T_STRUCT) => batch.process = self.read_process()?,
+                (2, T_LIST) => batch.spans = self.read_list(Self::read_span)?,
+                _ => self.skip(field_type, 0)?,
+            }
+        }
+        Ok(batch)
+    }
+
+    fn read_process(&mut self) -> Result<Process, String> {
+        let mut process = Process::default();
+        while let Some((field_type, id)) = self.read_field_header()? {
+            match (id, field_type) {
+                (1, T_STRING) => process.service_name = self.read_string()?,
+                (2, T_LIST) => process.tags = self.read_list(Self::read_tag)?,
+                _ => self.skip(field_type, 0)?,
+            }
+        }
+        Ok(process)
+    }
+
+    fn read_span(&mut self) -> Result<JaegerSpan, String> {
+        let mut span = JaegerSpan::default();
+        while let Some((field_type, id)) = self.read_field_header()? {
+            match (id, field_type) {
+                (1, T_I64) => span.trace_id_low = self.read_i64()?,
+                (2, T_I64) => span.trace_id_high = self.read_i64()?,
+                (3, T_I64) => span.span_id = self.read_i64()?,
+                (4, T_I64) => span.parent_span_id = self.read_i64()?,
+                (5, T_STRING) => span.operation_name = self.read_string()?,
+                (6, T_LIST) => span.references = self.read_list(Self::read_span_ref)?,
+                (8, T_I64) => span.start_time = self.read_i64()?,
+                (9, T_I64) => span.duration = self.read_i64()?,
+                (10, T_LIST) => span.tags = self.read_list(Self::read_tag)?,
+                (11, T_LIST) => span.logs = self.read_list(Self::read_log)?,
+                _ => self.skip(field_type, 0)?,
+            }
+        }
+        Ok(span)
+    }
+
+    fn read_span_ref(&mut self) -> Result<SpanRef, String> {
+        let mut span_ref = SpanRef::default();
+        while let Some((field_type, id)) = self.read_field_header()? {
+            match (id, field_type) {
+                (1, T_I32) => span_ref.ref_type = self.read_i32()?,
+                (2, T_I64) => span_ref.trace_id_low = self.read_i64()?,
+                (3, T_I64) => span_ref.trace_id_high = self.read_i64()?,
+                (4, T_I64) => span_ref.span_id = self.read_i64()?,
+                _ => self.skip(field_type, 0)?,
+            }
+        }
+        Ok(span_ref)
+    }
+
+    fn read_tag(&mut self) -> Result<Tag, String> {
+        let mut key = String::new();
+        let mut value_type = 0;
+        let (mut v_str, mut v_double, mut v_bool, mut v_long, mut v_binary) =
+            (None, None, None, None, None);
+        while let Some((field_type, id)) = self.read_field_header()? {
+            match (id, field_type) {
+                (1, T_STRING) => key = self.read_string()?,
+                (2, T_I32) => value_type = self.read_i32()?,
+                (3, T_STRING) => v_str = Some(self.read_string()?),
+                (4, T_DOUBLE) => v_double = Some(self.read_double()?),
+                (5, T_BOOL) => v_bool = Some(self.read_bool()?),
+                (6, T_I64) => v_long = Some(self.read_i64()?),
+                (7, T_STRING) => v_binary = Some(self.read_binary()?.to_vec()),
+                _ => self.skip(field_type, 0)?,
+            }
+        }
+        // refer: https://github.com/jaegertracing/jaeger-idl/blob/main/thrift/jaeger.thrift#L19
+        let value = match value_type {
+            0 => TagValue::String(v_str.unwrap_or_default()),
+            1 => TagValue::Double(v_double.unwrap_or_default()),
+            2 => TagValue::Bool(v_bool.unwrap_or_default()),
+            3 => TagValue::Long(v_long.unwrap_or_default()),
+            4 => TagValue::Binary(v_binary.unwrap_or_default()),
+            v => return Err(format!("unknown tag type {v} of tag {key}")),
+        };
+        Ok(Tag { key, value })
+    }
+
+    fn read_log(&mut self) -> Result<Log, String> {
+        let mut log = Log::default();
+        while let Some((field_type, id)) = self.read_field_header()? {
+            match (id, field_type) {
+                (1, T_I64) => log.timestamp = self.read_i64()?,
+                (2, T_LIST) => log.fields = self.read_list(Self::read_tag)?,
+                _ => self.skip(field_type, 0)?,
+            }
+        }
+        Ok(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A writer of the Thrift binary protocol, to build test batches.
+    #[derive(Default)]
+    struct ThriftWriter {
+        buf: Vec<u8>,
+    }
+
+    impl ThriftWriter {
+        fn field(&mut self, field_type: u8, id: i16) -> &mut Self {
+            self.buf.push(field_type);
+            self.buf.extend_from_slice(&id.to_be_bytes());
+            self
+        }
+
+        fn i32(&mut self, id: i16, v: i32) -> &mut Self {
+            self.field(T_I32, id);
+            self.buf.extend_from_slice(&v.to_be_bytes());
+            self
+        }
+
+        fn i64(&mut self, id: i16, v: i64) -> &mut Self {
+            self.field(T_I64, id);
+            self.buf.extend_from_slice(&v.to_be_bytes());
+            self
+        }
+
+        fn bool(&mut self, id: i16, v: bool) -> &mut Self {
+            self.field(T_BOOL, id);
+            self.buf.push(v as u8);
+            self
+        }
+
+        fn string(&mut self, id: i16, v: &str) -> &mut Self {
+            self.field(T_STRING, id);
+            self.buf.extend_from_slice(&(v.len() as i32).to_be_bytes());
+            self.buf.extend_from_slice(v.as_bytes());
+            self
+        }
+
+        fn list(&mut self, id: i16, size: i32) -> &mut Self {
+            self.field(T_LIST, id);
+            self.buf.push(T_STRUCT);
+            self.buf.extend_from_slice(&size.to_be_bytes());
+            self
+        }
+
+        fn stop(&mut self) -> &mut Self {
+            self.buf.push(T_STOP);
+            self
+        }
+
+        fn string_tag(&mut self, key: &str, v: &str) -> &mut Self {
+            self.string(1, key).i32(2, 0).string(3, v).stop()
+        }
+    }
+
+    fn test_batch() -> Vec<u8> {
+        let mut w = ThriftWriter::default();
+        // process
+        w.field(T_STRUCT, 1)
+            .string(1, "frontend")
+            .list(2, 1)
+            .string_tag("hostname", "host-1")
+            .stop();
+        // spans
+        w.list(2, 1)
+            .i64(1, 0x5af7183fb1d4cf5f)
+            .i64(2, 0)
+            .i64(3, 0x352bff9a74ca9ad2)
+            .i64(4, 0)
+            .string(5, "GET /api")
+            .list(6, 1)
+            .i32(1, REF_TYPE_CHILD_OF)
+            .i64(2, 0x5af7183fb1d4cf5f)
+            .i64(3, 0)
+            .i64(4, 0x6b221d5bc9e6496c)
+            .stop()
+            .i32(7, 1)
+            .i64(8, 1556604172355737)
+            .i64(9, 1431)
+            .list(10, 3)
+            .string_tag("span.kind", "server")
+            .string_tag("http.method", "GET");
+        w.string(1, "error").i32(2, 2).bool(5, true).stop();
+        w.list(11, 1)
+            .i64(1, 1556604172355800)
+            .list(2, 2)
+            .string_tag("event", "cache miss")
+            .string_tag("key", "user:1")
+            .stop();
+        // unknown field of the span
+        w.string(99, "ignored").stop();
+        // end of batch
+        w.i64(3, 1).stop();
+        w.buf
+    }
+
+    #[test]
+    fn test_read_batch() {
+        let batch = ThriftReader::new(&test_batch()).read_batch().unwrap();
+        assert_eq!(batch.process.service_name, "frontend");
+        assert_eq!(
+            batch.process.tags,
+            vec![Tag {
+                key: "hostname".to_string(),
+                value: TagValue::String("host-1".to_string()),
+            }]
+        );
+        assert_eq!(batch.spans.len(), 1);
+        let span = &batch.spans[0];
+        assert_eq!(span.operation_name, "GET /api");
+        assert_eq!(span.references.len(), 1);
+        assert_eq!(span.tags.len(), 3);
+        assert_eq!(span.tags[2].value, TagValue::Bool(true));
+        assert_eq!(span.logs[0].fields.len(), 2);
+    }
+
+    #[test]
+    fn test_read_batch_invalid() {
+        let batch = test_batch();
+        assert!(
+            ThriftReader::new(&batch[..batch.len() / 2])
+                .read_batch()
+                .is_err()
+        );
+        // list of i64 where a list of spans is expected
+        let mut w = ThriftWriter::default();
+        w.field(T_LIST, 2).buf.push(T_I64);
+        w.buf.extend_from_slice(&1i32.to_be_bytes());
+        assert!(ThriftReader::new(&w.buf).read_batch().is_err());
+        // negative size
+        let mut w = ThriftWriter::default();
+        w.list(2, -1);
+        assert!(ThriftReader::new(&w.buf).read_batch().is_err());
+    }
+
+    #[test]
+    fn test_to_otlp_request() {
+        let batch = ThriftReader::new(&test_batch()).read_batch().unwrap();
+        let request = to_otlp_request(batch);
+        assert_eq!(request.resource_spans.len(), 1);
+        let resource = request.resource_spans[0].resource.as_ref().unwrap();
+        assert_eq!(resource.attributes[0].key, SERVICE_NAME);
+        assert_eq!(resource.attributes[1].key, "hostname");
+
+        let span = &request.resource_spans[0].scope_spans[0].spans[0];
+        assert_eq!(span.trace_id, trace_id_bytes(0, 0x5af7183fb1d4cf5f));
+        assert_eq!(span.span_id, 0x352bff9a74ca9ad2i64.to_be_bytes().to_vec());
+        // the parent comes from the CHILD_OF reference
+        assert_eq!(
+            span.parent_span_id,
+            0x6b221d5bc9e6496ci64.to_be_bytes().to_vec()
+        );
+        assert!(span.links.is_empty());
+        assert_eq!(span.kind, i32::from(SpanKind::Server));
+        assert_eq!(span.start_time_unix_nano, 1556604172355737000);
+        assert_eq!(span.end_time_unix_nano, 1556604172357168000);
+        assert_eq!(
+            span.status.as_ref().unwrap().code,
+            i32::from(StatusCode::Error)
+        );
+        assert_eq!(span.attributes.len(), 1);
+        assert_eq!(span.attributes[0].key, "http.method");
+        assert_eq!(span.events[0].name, "cache miss");
+        assert_eq!(span.events[0].attributes[0].key, "key");
+    }
+
+    #[test]
+    fn test_to_otlp_span_links() {
+        let span = to_otlp_span(JaegerSpan {
+            trace_id_low: 1,
+            span_id: 2,
+            parent_span_id: 3,
+            references: vec![
+                SpanRef {
+                    ref_type: REF_TYPE_CHILD_OF,
+                    trace_id_low: 1,
+                    span_id: 3,
+                    ..Default::default()
+                },
+                SpanRef {
+                    ref_type: 1, // FOLLOWS_FROM
+                    trace_id_low: 7,
+                    span_id: 8,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+        assert_eq!(span.parent_span_id, 3i64.to_be_bytes().to_vec());
+        assert_eq!(span.links.len(), 1);
+        assert_eq!(span.links[0].trace_id, trace_id_bytes(0, 7));
+        assert_eq!(span.links[0].span_id, 8i64.to_be_bytes().to_vec());
+        assert_eq!(span.kind, i32::from(SpanKind::Internal));
+        assert!(span.status.is_none());
+    }
+}
@@ -51,8 +51,10 @@ use prost::Message;
 use serde_json::Map;
 
 pub mod inferred;
+pub mod jaeger;
 pub mod otel;
 pub mod service_graph;
+pub mod zipkin;
 
 #[cfg(feature = "cloud")]
 use crate::service::stream::get_stream;
@@ -1078,6 +1080,16 @@ fn format_response(
     }
 }
 
+/// The Zipkin and Jaeger collectors answer accepted requests with 202 and an
+/// empty body.
+fn collector_response(resp: HttpResponse) -> HttpResponse {
+    if resp.status() == http::StatusCode::OK {
+        http::StatusCode::ACCEPTED.into_response()
+    } else {
+        resp
+    }
+}
+
 async fn write_traces_by_stream(
     org_id: &str,
     time_stats: (i64, &Instant),
@@ -0,0 +1,445 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Zipkin v2 span ingestion.
+//!
+//! Spans posted in the Zipkin v2 JSON or protobuf encoding are converted into
+//! an OTLP [`ExportTraceServiceRequest`] and ingested by
+//! [`super::handle_otlp_request`], so they are stored like native OTLP spans.
+//!
+//! refer: https://zipkin.io/zipkin-api/#/default/post_spans
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Error,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use axum::{body::Bytes, response::Response as HttpResponse};
+use config::meta::otlp::OtlpRequestType;
+use opentelemetry_proto::tonic::{
+    collector::trace::v1::ExportTraceServiceRequest,
+    common::v1::{AnyValue, KeyValue, any_value::Value},
+    resource::v1::Resource,
+    trace::v1::{
+        ResourceSpans, ScopeSpans, Span, Status,
+        span::{Event, SpanKind},
+        status::StatusCode,
+    },
+};
+use prost::Message;
+use proto::zipkin_rpc;
+use serde::Deserialize;
+
+use super::SERVICE_NAME;
+use crate::common::meta::{http::HttpResponse as MetaHttpResponse, ingestion::IngestUser};
+
+const UNKNOWN_SERVICE: &str = "unknown_service";
+const ERROR_TAG: &str = "error";
+const PEER_SERVICE: &str = "peer.service";
+const PEER_IP: &str = "net.peer.ip";
+const PEER_PORT: &str = "net.peer.port";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZipkinSpan {
+    trace_id: String,
+    id: String,
+    #[serde(default)]
+    parent_id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    timestamp: Option<u64>,
+    #[serde(default)]
+    duration: Option<u64>,
+    #[serde(default)]
+    local_endpoint: Option<ZipkinEndpoint>,
+    #[serde(default)]
+    remote_endpoint: Option<ZipkinEndpoint>,
+    #[serde(default)]
+    annotations: Vec<ZipkinAnnotation>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZipkinEndpoint {
+    #[serde(default)]
+    service_name: Option<String>,
+    #[serde(default)]
+    ipv4: Option<String>,
+    #[serde(default)]
+    ipv6: Option<String>,
+    #[serde(default)]
+    port: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZipkinAnnotation {
+    timestamp: u64,
+    value: String,
+}
+
+pub async fn zipkin_proto(
+    org_id: &str,
+    body: Bytes,
+    in_stream_name: Option<&str>,
+    user: IngestUser,
+) -> Result<HttpResponse, Error> {
+    let spans = match zipkin_rpc::ListOfSpans::decode(body) {
+        Ok(v) => v.spans,
+        Err(e) => {
+            log::error!("[TRACES:ZIPKIN] Invalid proto: org_id: {org_id}, error: {e}");
+            return Ok(MetaHttpResponse::bad_request(format!("Invalid proto: {e}")));
+        }
+    };
+    ingest_spans(org_id, spans, in_stream_name, user).await
+}
+
+pub async fn zipkin_json(
+    org_id: &str,
+    body: Bytes,
+    in_stream_name: Option<&str>,
+    user: IngestUser,
+) -> Result<HttpResponse, Error> {
+    let spans = match decode_json(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!("[TRACES:ZIPKIN] Invalid json: org_id: {org_id}, error: {e}");
+            return Ok(MetaHttpResponse::bad_request(e));
+        }
+    };
+    ingest_spans(org_id, spans, in_stream_name, user).await
+}
+
+async fn ingest_spans(
+    org_id: &str,
+    spans: Vec<zipkin_rpc::Span>,
+    in_stream_name: Option<&str>,
+    user: IngestUser,
+) -> Result<HttpResponse, Error> {
+    let request = to_otlp_request(spans);
+    super::handle_otlp_request(
+        org_id,
+        request,
+        OtlpRequestType::HttpJson,
+        in_stream_name,
+        user,
+    )
+    .await
+    .map(super::collector_response)
+}
+
+/// Decodes Zipkin v2 JSON spans into their protobuf representation.
+fn decode_json(body: &[u8]) -> Result<Vec<zipkin_rpc::Span>, String> {
+    let spans: Vec<ZipkinSpan> =
+        serde_json::from_slice(body).map_err(|e| format!("Invalid json: {e}"))?;
+    spans
+        .into_iter()
+        .map(|span| {
+            Ok(zipkin_rpc::Span {
+                trace_id: decode_id(&span.trace_id, "traceId")?,
+                parent_id: match span.parent_id.as_deref() {
+                    Some(id) if !id.is_empty() => decode_id(id, "parentId")?,
+                    _ => vec![],
+                },
+                id: decode_id(&span.id, "id")?,
+                kind: span
+                    .kind
+                    .as_deref()
+                    .and_then(zipkin_rpc::span::Kind::from_str_name)
+                    .unwrap_or(zipkin_rpc::span::Kind::SpanKindUnspecified)
+                    .into(),
+                name: span.name.unwrap_or_default(),
+                timestamp: span.timestamp.unwrap_or_default(),
+                duration: span.duration.unwrap_or_default(),
+                local_endpoint: span.local_endpoint.map(decode_endpoint).transpose()?,
+                remote_endpoint: span.remote_endpoint.map(decode_endpoint).transpose()?,
+                annotations: span
+                    .annotations
+                    .into_iter()
+                    .map(|a| zipkin_rpc::Annotation {
+                        timestamp: a.timestamp,
+                        value: a.value,
+                    })
+                    .collect(),
+                tags: span.tags,
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+fn decode_id(id: &str, field: &str) -> Result<Vec<u8>, String> {
+    hex::decode(id).map_err(|e| format!("invalid {field} {id}: {e}"))
+}
+
+fn decode_endpoint(endpoint: ZipkinEndpoint) -> Result<zipkin_rpc::Endpoint, String> {
+    let ipv4 = match endpoint.ipv4.as_deref() {
+        Some(ip) => ip
+            .parse::<Ipv4Addr>()
+            .map_err(|e| format!("invalid ipv4 {ip}: {e}"))?
+            .octets()
+            .to_vec(),
+        None => vec![],
+    };
+    let ipv6 = match endpoint.ipv6.as_deref() {
+        Some(ip) => ip
+            .parse::<Ipv6Addr>()
+            .map_err(|e| format!("invalid ipv6 {ip}: {e}"))?
+            .octets()
+            .to_vec(),
+        None => vec![],
+    };
+    Ok(zipkin_rpc::Endpoint {
+        service_name: endpoint.service_name.unwrap_or_default(),
+        ipv4,
+        ipv6,
+        port: endpoint.port.unwrap_or_default(),
+    })
+}
+
+/// Converts Zipkin spans into OTLP resource spans, one per local service.
+fn to_otlp_request(spans: Vec<zipkin_rpc::Span>) -> ExportTraceServiceRequest {
+    let mut spans_by_service: BTreeMap<String, Vec<Span>> = BTreeMap::new();
+    for span in spans {
+        let service_name = span
+            .local_endpoint
+            .as_ref()
+            .map(|e| e.service_name.as_str())
+            .filter(|v| !v.is_empty())
+            .unwrap_or(UNKNOWN_SERVICE)
+            .to_string();
+        spans_by_service
+            .entry(service_name)
+            .or_default()
+            .push(to_otlp_span(span));
+    }
+
+    let resource_spans = spans_by_service
+        .into_iter()
+        .map(|(service_name, spans)| ResourceSpans {
+            resource: Some(Resource {
+                attributes: vec![string_attribute(SERVICE_NAME, service_name)],
+                ..Default::default()
+            }),
+            scope_spans: vec![ScopeSpans {
+                spans,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .collect();
+    ExportTraceServiceRequest { resource_spans }
+}
+
+fn to_otlp_span(span: zipkin_rpc::Span) -> Span {
+    let kind = match span.kind() {
+        zipkin_rpc::span::Kind::Client => SpanKind::Client,
+        zipkin_rpc::span::Kind::Server => SpanKind::Server,
+        zipkin_rpc::span::Kind::Producer => SpanKind::Producer,
+        zipkin_rpc::span::Kind::Consumer => SpanKind::Consumer,
+        zipkin_rpc::span::Kind::SpanKindUnspecified => SpanKind::Internal,
+    };
+
+    // the error tag marks a failed span, its value is the error message
+    let mut status = None;
+    let mut attributes = Vec::with_capacity(span.tags.len() + 3);
+    let mut tags = span.tags.into_iter().collect::<Vec<_>>();
+    tags.sort();
+    for (key, value) in tags {
+        if key == ERROR_TAG {
+            status = Some(Status {
+                code: StatusCode::Error.into(),
+                message: value,
+            });
+        } else {
+            attributes.push(string_attribute(&key, value));
+        }
+    }
+    if let Some(remote) = span.remote_endpoint {
+        if !remote.service_name.is_empty() {
+            attributes.push(string_attribute(PEER_SERVICE, remote.service_name));
+        }
+        if let Ok(ip) = <[u8; 4]>::try_from(remote.ipv4.as_slice()) {
+            attributes.push(string_attribute(PEER_IP, Ipv4Addr::from(ip).to_string()));
+        } else if let Ok(ip) = <[u8; 16]>::try_from(remote.ipv6.as_slice()) {
+            attributes.push(string_attribute(PEER_IP, Ipv6Addr::from(ip).to_string()));
+        }
+        if remote.port > 0 {
+            attributes.push(KeyValue {
+                key: PEER_PORT.to_string(),
+                value: Some(AnyValue {
+                    value: Some(Value::IntValue(remote.port.into())),
+                }),
+            });
+        }
+    }
+
+    let events = span
+        .annotations
+        .into_iter()
+        .map(|a| Event {
+            time_unix_nano: a.timestamp * 1000,
+            name: a.value,
+            ..Default::default()
+        })
+        .collect();
+
+    Span {
+        trace_id: widen_trace_id(span.trace_id),
+        span_id: span.id,
+        parent_span_id: span.parent_id,
+        name: span.name,
+        kind: kind.into(),
+        start_time_unix_nano: span.timestamp * 1000,
+        end_time_unix_nano: (span.timestamp + span.duration) * 1000,
+        attributes,
+        events,
+        status,
+        ..Default::default()
+    }
+}
+
+/// Zipkin allows 64 bit trace ids, OTLP trace ids are always 128 bits.
+fn widen_trace_id(trace_id: Vec<u8>) -> Vec<u8> {
+    if trace_id.len() == 8 {
+        let mut id = vec![0; 8];
+        id.extend(trace_id);
+        id
+    } else {
+        trace_id
+    }
+}
+
+fn string_attribute(key: &str, value: String) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(AnyValue {
+            value: Some(Value::StringValue(value)),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZIPKIN_JSON: &str = r#"[
+        {
+            "traceId": "5af7183fb1d4cf5f",
+            "parentId": "6b221d5bc9e6496c",
+            "id": "352bff9a74ca9ad2",
+            "kind": "CLIENT",
+            "name": "get /api",
+            "timestamp": 1556604172355737,
+            "duration": 1431,
+            "localEndpoint": {"serviceName": "frontend", "ipv4": "192.168.99.1", "port": 3306},
+            "remoteEndpoint": {"serviceName": "backend", "ipv4": "172.19.0.2", "port": 9000},
+            "annotations": [{"timestamp": 1556604172355800, "value": "ws"}],
+            "tags": {"http.method": "GET", "http.path": "/api", "error": "timeout"}
+        },
+        {
+            "traceId": "5af7183fb1d4cf5f5af7183fb1d4cf5f",
+            "id": "6b221d5bc9e6496c",
+            "timestamp": 1556604172355000,
+            "duration": 2000
+        }
+    ]"#;
+
+    #[test]
+    fn test_decode_json() {
+        let spans = decode_json(ZIPKIN_JSON.as_bytes()).unwrap();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].trace_id, hex::decode("5af7183fb1d4cf5f").unwrap());
+        assert_eq!(spans[0].kind(), zipkin_rpc::span::Kind::Client);
+        assert_eq!(
+            spans[0].local_endpoint.as_ref().unwrap().ipv4,
+            vec![192, 168, 99, 1]
+        );
+        assert!(spans[1].parent_id.is_empty());
+        assert!(spans[1].local_endpoint.is_none());
+
+        assert!(decode_json(br#"[{"traceId": "xyz", "id": "352bff9a74ca9ad2"}]"#).is_err());
+        assert!(decode_json(br#"{"traceId": "5af7183fb1d4cf5f"}"#).is_err());
+    }
+
+    #[test]
+    fn test_to_otlp_request() {
+        let spans = decode_json(ZIPKIN_JSON.as_bytes()).unwrap();
+        let request = to_otlp_request(spans);
+        assert_eq!(request.resource_spans.len(), 2);
+
+        let frontend = &request.resource_spans[0];
+        assert_eq!(
+            frontend.resource.as_ref().unwrap().attributes,
+            vec![string_attribute(SERVICE_NAME, "frontend".to_string())]
+        );
+        let span = &frontend.scope_spans[0].spans[0];
+        assert_eq!(span.trace_id.len(), 16);
+        assert_eq!(
+            &span.trace_id[8..],
+            hex::decode("5af7183fb1d4cf5f").unwrap()
+        );
+        assert_eq!(
+            span.parent_span_id,
+            hex::decode("6b221d5bc9e6496c").unwrap()
+        );
+        assert_eq!(span.kind, i32::from(SpanKind::Client));
+        assert_eq!(span.start_time_unix_nano, 1556604172355737000);
+        assert_eq!(span.end_time_unix_nano, 1556604172357168000);
+        assert_eq!(
+            span.status,
+            Some(Status {
+                code: StatusCode::Error.into(),
+                message: "timeout".to_string(),
+            })
+        );
+        let keys = span
+            .attributes
+            .iter()
+            .map(|kv| kv.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec!["http.method", "http.path", PEER_SERVICE, PEER_IP, PEER_PORT]
+        );
+        assert_eq!(span.events.len(), 1);
+        assert_eq!(span.events[0].name, "ws");
+        assert_eq!(span.events[0].time_unix_nano, 1556604172355800000);
+
+        let unknown = &request.resource_spans[1];
+        assert_eq!(
+            unknown.resource.as_ref().unwrap().attributes,
+            vec![string_attribute(SERVICE_NAME, UNKNOWN_SERVICE.to_string())]
+        );
+        let span = &unknown.scope_spans[0].spans[0];
+        assert_eq!(span.kind, i32::from(SpanKind::Internal));
+        assert!(span.status.is_none());
+    }
+
+    #[test]
+    fn test_decode_proto() {
+        let list = zipkin_rpc::ListOfSpans {
+            spans: decode_json(ZIPKIN_JSON.as_bytes()).unwrap(),
+        };
+        let decoded = zipkin_rpc::ListOfSpans::decode(list.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, list);
+    }
+}
@@ -234,6 +234,64 @@ async fn update(msg: Message) -> Result<()> {
                 );
             }
         }
+        TriggerModule::RecordingRule => {
+            // The module_key of a recording rule trigger is `{namespace}/{group}`
+            let rule_group = match trigger.module_key.split_once('/') {
+                Some((namespace, group)) => {
+                    db::prometheus_rules::get(&trigger.org, namespace, group)
+                        .await
+                        .unwrap_or(None)
+                }
+                None => None,
+            };
+            if rule_group.is_some() {
+                scheduler::push(trigger.clone()).await.map_err(|e| {
+                    let error_msg = format!(
+                        "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                        trigger.org, trigger.module, trigger.module_key, e
+                    );
+                    log::error!("{error_msg}");
+                    anyhow::anyhow!(error_msg)
+                })?;
+            } else {
+                log::warn!(
+                    "[SUPER_CLUSTER:sync] Rule group not found for module_key: {}. No need to sync this trigger",
+                    trigger.module_key
+                );
+            }
+        }
+        TriggerModule::Scrape => {
+            if db::prometheus_scrape::get(&trigger.org, &trigger.module_key)
+                .await
+                .unwrap_or(None)
+                .is_some()
+            {
+                scheduler::push(trigger.clone()).await.map_err(|e| {
+                    let error_msg = format!(
+                        "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                        trigger.org, trigger.module, trigger.module_key, e
+                    );
+                    log::error!("{error_msg}");
+                    anyhow::anyhow!(error_msg)
+                })?;
+            } else {
+                log::warn!(
+                    "[SUPER_CLUSTER:sync] Scrape config not found for module_key: {}. No need to sync this trigger",
+                    trigger.module_key
+                );
+            }
+        }
+        TriggerModule::WebhookRetry => {
+            // The pending delivery is carried in the trigger data, no module record to check
+            scheduler::push(trigger.clone()).await.map_err(|e| {
+                let error_msg = format!(
+                    "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                    trigger.org, trigger.module, trigger.module_key, e
+                );
+                log::error!("{error_msg}");
+                anyhow::anyhow!(error_msg)
+            })?;
+        }
     }
     Ok(())
 }